@@ -3,10 +3,14 @@
 //! IPC commands that can be called from the frontend.
 
 use crate::gpu::{self, GpuInfo, GpuMetrics, GpuMetricsHistory, GPU_METRICS};
-use crate::system::{self, SystemInfo, SystemMetrics, ProcessInfo};
+use crate::system::{self, SystemInfo, SystemMetrics, ProcessInfo, ProcessUsage};
 use serde::{Deserialize, Serialize};
 use std::process::{Command, Child};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time a model gets to exit after a terminate request before it is killed
+pub const DEFAULT_STOP_GRACE_SECS: u64 = 5;
 
 /// Running model state
 static RUNNING_MODEL: once_cell::sync::Lazy<Mutex<Option<RunningModel>>> =
@@ -17,10 +21,50 @@ pub struct RunningModel {
     pub name: String,
     pub pid: u32,
     pub started_at: u64,
+    /// When the process was seen to have exited
+    pub stopped_at: Option<u64>,
+    pub exit_code: Option<i32>,
     #[serde(skip)]
     pub process: Option<Child>,
 }
 
+impl RunningModel {
+    /// Current status, reaping the process if it has exited
+    fn status(&mut self) -> ModelStatus {
+        if self.stopped_at.is_none() {
+            if let Some(process) = self.process.as_mut() {
+                match process.try_wait() {
+                    Ok(None) => {}
+                    Ok(Some(exit)) => {
+                        self.exit_code = exit.code();
+                        self.stopped_at = Some(now_secs());
+                    }
+                    Err(_) => self.stopped_at = Some(now_secs()),
+                }
+            }
+        }
+
+        let running = self.stopped_at.is_none();
+
+        ModelStatus {
+            name: self.name.clone(),
+            pid: self.pid,
+            running,
+            status: if running { "running" } else { "stopped" }.to_string(),
+            started_at: self.started_at,
+            duration_seconds: self.stopped_at.unwrap_or_else(now_secs).saturating_sub(self.started_at),
+            exit_code: self.exit_code,
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 /// All metrics combined for dashboard
 #[derive(Debug, Serialize)]
 pub struct AllMetrics {
@@ -40,9 +84,35 @@ pub struct GpuWithMetrics {
 #[derive(Debug, Clone, Serialize)]
 pub struct ModelStatus {
     pub name: String,
+    pub pid: u32,
+    /// Whether the process is still alive
     pub running: bool,
+    /// "running" or "stopped"
+    pub status: String,
     pub started_at: u64,
+    /// Uptime, frozen at exit once the process has stopped
     pub duration_seconds: u64,
+    pub exit_code: Option<i32>,
+}
+
+/// How a model process was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StopOutcome {
+    /// Exited within the grace period after SIGTERM
+    Graceful,
+    /// Still alive after the grace period and force-killed
+    Killed,
+    /// Had already exited before the stop request
+    AlreadyExited,
+}
+
+/// Resource usage of the running model process
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelResourceUsage {
+    pub status: ModelStatus,
+    /// `None` once the process has stopped
+    pub usage: Option<ProcessUsage>,
 }
 
 /// Get information about all detected GPUs
@@ -57,6 +127,30 @@ pub fn get_gpu_metrics(gpu_id: u32) -> GpuMetrics {
     gpu::get_current_metrics(gpu_id)
 }
 
+/// Get recorded GPU metrics from the last `window_secs`, oldest first per GPU
+#[tauri::command]
+pub fn get_gpu_metrics_history(window_secs: u64) -> Vec<GpuMetricsHistory> {
+    gpu::get_metrics_history(window_secs)
+}
+
+/// Change the GPU polling interval; returns the interval applied after clamping
+#[tauri::command]
+pub fn set_metrics_interval(ms: u64) -> u64 {
+    gpu::set_metrics_interval(ms)
+}
+
+/// Stop GPU metric sampling
+#[tauri::command]
+pub fn pause_metrics() {
+    gpu::pause_metrics();
+}
+
+/// Resume GPU metric sampling
+#[tauri::command]
+pub fn resume_metrics() {
+    gpu::resume_metrics();
+}
+
 /// Get system information
 #[tauri::command]
 pub fn get_system_info() -> SystemInfo {
@@ -76,7 +170,7 @@ pub fn get_all_metrics() -> AllMetrics {
             let history = histories
                 .iter()
                 .find(|h| h.gpu_id == info.id)
-                .map(|h| h.samples.clone())
+                .map(|h| h.samples.iter().cloned().collect())
                 .unwrap_or_default();
             
             GpuWithMetrics {
@@ -90,22 +184,7 @@ pub fn get_all_metrics() -> AllMetrics {
     let system = system::get_system_metrics();
     let model_processes = system::find_model_processes();
     
-    let running_model = {
-        let model = RUNNING_MODEL.lock().unwrap();
-        model.as_ref().map(|m| {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            
-            ModelStatus {
-                name: m.name.clone(),
-                running: true,
-                started_at: m.started_at,
-                duration_seconds: now - m.started_at,
-            }
-        })
-    };
+    let running_model = get_model_status();
 
     AllMetrics {
         gpus,
@@ -127,7 +206,7 @@ pub struct ModelConfig {
 pub async fn start_model(config: ModelConfig) -> Result<ModelStatus, String> {
     let mut running = RUNNING_MODEL.lock().map_err(|e| e.to_string())?;
     
-    if running.is_some() {
+    if running.as_mut().is_some_and(|m| m.status().running) {
         return Err("A model is already running".to_string());
     }
 
@@ -140,26 +219,18 @@ pub async fn start_model(config: ModelConfig) -> Result<ModelStatus, String> {
         .spawn()
         .map_err(|e| format!("Failed to start model: {}", e))?;
 
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
     let pid = process.id();
     
-    let model = RunningModel {
+    let mut model = RunningModel {
         name: config.name.clone(),
         pid,
-        started_at: now,
+        started_at: now_secs(),
+        stopped_at: None,
+        exit_code: None,
         process: Some(process),
     };
 
-    let status = ModelStatus {
-        name: model.name.clone(),
-        running: true,
-        started_at: model.started_at,
-        duration_seconds: 0,
-    };
+    let status = model.status();
 
     *running = Some(model);
     
@@ -167,37 +238,163 @@ pub async fn start_model(config: ModelConfig) -> Result<ModelStatus, String> {
 }
 
 /// Stop the currently running model
+///
+/// Asks the process to terminate, waits up to `grace_period_secs` (default 5s)
+/// for it to exit, then kills it. The model is untracked immediately.
 #[tauri::command]
-pub async fn stop_model() -> Result<(), String> {
-    let mut running = RUNNING_MODEL.lock().map_err(|e| e.to_string())?;
+pub async fn stop_model(grace_period_secs: Option<u64>) -> Result<StopOutcome, String> {
+    let model = RUNNING_MODEL.lock().map_err(|e| e.to_string())?.take();
     
-    if let Some(mut model) = running.take() {
-        if let Some(ref mut process) = model.process {
-            let _ = process.kill();
+    let Some(mut process) = model.and_then(|m| m.process) else {
+        return Err("No model is currently running".to_string());
+    };
+    
+    let grace = Duration::from_secs(grace_period_secs.unwrap_or(DEFAULT_STOP_GRACE_SECS));
+    tokio::task::spawn_blocking(move || terminate_process(&mut process, grace))
+        .await
+        .map_err(|e| format!("Failed to stop model: {}", e))
+}
+
+/// Stop the model on app exit so it isn't orphaned
+pub fn shutdown_model() {
+    let model = RUNNING_MODEL.lock().ok().and_then(|mut running| running.take());
+    
+    if let Some(mut process) = model.and_then(|m| m.process) {
+        let outcome = terminate_process(&mut process, Duration::from_secs(DEFAULT_STOP_GRACE_SECS));
+        tracing::info!("Stopped model process on exit: {:?}", outcome);
+    }
+}
+
+/// Ask `process` to exit, force-killing it if it is still alive after `grace`
+fn terminate_process(process: &mut Child, grace: Duration) -> StopOutcome {
+    if let Ok(Some(_)) = process.try_wait() {
+        return StopOutcome::AlreadyExited;
+    }
+    
+    request_terminate(process);
+    
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        match process.try_wait() {
+            Ok(Some(_)) => return StopOutcome::Graceful,
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => break,
         }
-        Ok(())
-    } else {
-        Err("No model is currently running".to_string())
     }
+    
+    let _ = process.kill();
+    let _ = process.wait();
+    StopOutcome::Killed
+}
+
+#[cfg(unix)]
+fn request_terminate(process: &Child) {
+    unsafe {
+        libc::kill(process.id() as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(windows)]
+fn request_terminate(process: &Child) {
+    // Without /F, taskkill asks the process to close rather than terminating it
+    let _ = Command::new("taskkill")
+        .args(["/PID", &process.id().to_string()])
+        .output();
 }
 
+#[cfg(not(any(unix, windows)))]
+fn request_terminate(_process: &Child) {}
+
 /// Get current model status
 #[tauri::command]
 pub fn get_model_status() -> Option<ModelStatus> {
-    let running = RUNNING_MODEL.lock().ok()?;
-    
-    running.as_ref().map(|m| {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        ModelStatus {
-            name: m.name.clone(),
-            running: true,
-            started_at: m.started_at,
-            duration_seconds: now - m.started_at,
-        }
-    })
+    let mut running = RUNNING_MODEL.lock().ok()?;
+    
+    running.as_mut().map(|m| m.status())
 }
 
+/// Get CPU, memory and GPU memory use of the model process.
+/// A process that has exited reports a stopped status with no usage.
+#[tauri::command]
+pub fn get_model_resource_usage() -> Option<ModelResourceUsage> {
+    // Release the lock before sampling, which sleeps briefly
+    let status = get_model_status()?;
+    
+    let usage = if status.running {
+        system::get_process_usage(status.pid)
+    } else {
+        None
+    };
+    
+    // The process may exit between the status check and the sample
+    let status = match usage {
+        Some(_) => status,
+        None => get_model_status()?,
+    };
+    
+    Some(ModelResourceUsage { status, usage })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_model_status_running_then_stopped() {
+        let process = Command::new("sleep").arg("1").spawn().unwrap();
+        let mut model = RunningModel {
+            name: "test".to_string(),
+            pid: process.id(),
+            started_at: now_secs(),
+            stopped_at: None,
+            exit_code: None,
+            process: Some(process),
+        };
+
+        let status = model.status();
+        assert!(status.running);
+        assert_eq!(status.status, "running");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while model.status().running {
+            assert!(Instant::now() < deadline, "process should have exited");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let status = model.status();
+        assert_eq!(status.status, "stopped");
+        assert_eq!(status.exit_code, Some(0));
+        assert!(system::get_process_usage(status.pid).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_graceful() {
+        let mut process = Command::new("sleep").arg("30").spawn().unwrap();
+
+        let outcome = terminate_process(&mut process, Duration::from_secs(5));
+
+        assert_eq!(outcome, StopOutcome::Graceful);
+        assert_eq!(terminate_process(&mut process, Duration::from_secs(5)), StopOutcome::AlreadyExited);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_kills_after_grace_when_sigterm_ignored() {
+        let mut process = Command::new("sh")
+            .args(["-c", "trap '' TERM; exec sleep 30"])
+            .spawn()
+            .unwrap();
+        // Let the shell install the trap before signalling
+        std::thread::sleep(Duration::from_millis(300));
+
+        let grace = Duration::from_millis(500);
+        let start = Instant::now();
+        let outcome = terminate_process(&mut process, grace);
+
+        assert_eq!(outcome, StopOutcome::Killed);
+        assert!(start.elapsed() >= grace);
+        assert!(process.try_wait().unwrap().is_some());
+    }
+}
@@ -7,6 +7,8 @@
 //! - wgpu for cross-platform fallback
 
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
@@ -43,28 +45,62 @@ pub struct GpuMetrics {
     pub timestamp: u64,
 }
 
-/// GPU metrics history for charts
+/// Default number of samples kept per GPU (5 minutes at 1s polling)
+pub const DEFAULT_HISTORY_SAMPLES: usize = 300;
+
+/// Samples kept per GPU; applied to histories created after it changes
+static HISTORY_SAMPLES: AtomicUsize = AtomicUsize::new(DEFAULT_HISTORY_SAMPLES);
+
+/// GPU metrics history for charts (fixed-size ring buffer, oldest first)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuMetricsHistory {
     pub gpu_id: u32,
-    pub samples: Vec<GpuMetrics>,
+    pub samples: VecDeque<GpuMetrics>,
     pub max_samples: usize,
 }
 
 impl GpuMetricsHistory {
     pub fn new(gpu_id: u32, max_samples: usize) -> Self {
+        let max_samples = max_samples.max(1);
         Self {
             gpu_id,
-            samples: Vec::with_capacity(max_samples),
+            samples: VecDeque::with_capacity(max_samples),
             max_samples,
         }
     }
 
     pub fn push(&mut self, metrics: GpuMetrics) {
-        if self.samples.len() >= self.max_samples {
-            self.samples.remove(0);
+        while self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(metrics);
+    }
+
+    /// Resize the buffer, dropping the oldest samples if it shrinks
+    pub fn set_max_samples(&mut self, max_samples: usize) {
+        self.max_samples = max_samples.max(1);
+        while self.samples.len() > self.max_samples {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Samples with `timestamp >= since_ms`, oldest first
+    pub fn samples_since(&self, since_ms: u64) -> Vec<GpuMetrics> {
+        self.samples
+            .iter()
+            .filter(|s| s.timestamp >= since_ms)
+            .cloned()
+            .collect()
+    }
+
+    /// Copy of this history restricted to the last `window_secs` before `now_ms`
+    pub fn window(&self, window_secs: u64, now_ms: u64) -> GpuMetricsHistory {
+        let since_ms = now_ms.saturating_sub(window_secs.saturating_mul(1000));
+        GpuMetricsHistory {
+            gpu_id: self.gpu_id,
+            samples: self.samples_since(since_ms).into(),
+            max_samples: self.max_samples,
         }
-        self.samples.push(metrics);
     }
 }
 
@@ -72,6 +108,66 @@ impl GpuMetricsHistory {
 pub static GPU_METRICS: once_cell::sync::Lazy<Arc<Mutex<Vec<GpuMetricsHistory>>>> =
     once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
 
+/// Default time between metric samples
+pub const DEFAULT_METRICS_INTERVAL_MS: u64 = 1000;
+
+/// Shortest allowed polling interval
+pub const MIN_METRICS_INTERVAL_MS: u64 = 100;
+
+/// Polling interval, read by the poller on every iteration
+pub static METRICS_INTERVAL_MS: once_cell::sync::Lazy<Arc<AtomicU64>> =
+    once_cell::sync::Lazy::new(|| Arc::new(AtomicU64::new(DEFAULT_METRICS_INTERVAL_MS)));
+
+/// When set, the poller keeps running but skips sampling
+pub static METRICS_PAUSED: once_cell::sync::Lazy<Arc<AtomicBool>> =
+    once_cell::sync::Lazy::new(|| Arc::new(AtomicBool::new(false)));
+
+/// Change the polling interval (clamped to `MIN_METRICS_INTERVAL_MS`).
+/// Returns the interval actually applied.
+pub fn set_metrics_interval(ms: u64) -> u64 {
+    let ms = ms.max(MIN_METRICS_INTERVAL_MS);
+    METRICS_INTERVAL_MS.store(ms, Ordering::Relaxed);
+    ms
+}
+
+/// Stop sampling (e.g. while the window is hidden)
+pub fn pause_metrics() {
+    METRICS_PAUSED.store(true, Ordering::Relaxed);
+}
+
+/// Resume sampling
+pub fn resume_metrics() {
+    METRICS_PAUSED.store(false, Ordering::Relaxed);
+}
+
+/// Whether sampling is paused
+pub fn is_metrics_paused() -> bool {
+    METRICS_PAUSED.load(Ordering::Relaxed)
+}
+
+/// Change how many samples are kept per GPU
+pub fn set_history_samples(max_samples: usize) {
+    HISTORY_SAMPLES.store(max_samples.max(1), Ordering::Relaxed);
+    if let Ok(mut histories) = GPU_METRICS.lock() {
+        for history in histories.iter_mut() {
+            history.set_max_samples(max_samples);
+        }
+    }
+}
+
+/// Recorded samples from the last `window_secs`, per GPU
+pub fn get_metrics_history(window_secs: u64) -> Vec<GpuMetricsHistory> {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    GPU_METRICS
+        .lock()
+        .map(|histories| histories.iter().map(|h| h.window(window_secs, now_ms)).collect())
+        .unwrap_or_default()
+}
+
 /// Detect all available GPUs
 pub fn detect_gpus() -> Vec<GpuInfo> {
     let mut gpus = Vec::new();
@@ -224,6 +320,43 @@ fn get_nvidia_metrics(gpu_id: u32) -> Result<GpuMetrics, Box<dyn std::error::Err
     })
 }
 
+/// GPU memory used by `pid` across all devices, if the driver can attribute it
+pub fn process_gpu_memory_mb(pid: u32) -> Option<u64> {
+    #[cfg(feature = "nvidia")]
+    {
+        if let Ok(used) = get_nvidia_process_memory(pid) {
+            return used;
+        }
+    }
+
+    let _ = pid;
+    None
+}
+
+/// Sum the compute and graphics memory NVML attributes to `pid`
+#[cfg(feature = "nvidia")]
+fn get_nvidia_process_memory(pid: u32) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    use nvml_wrapper::enums::device::UsedGpuMemory;
+    use nvml_wrapper::Nvml;
+
+    let nvml = Nvml::init()?;
+    let mut total: Option<u64> = None;
+
+    for i in 0..nvml.device_count()? {
+        let device = nvml.device_by_index(i)?;
+        let mut processes = device.running_compute_processes().unwrap_or_default();
+        processes.extend(device.running_graphics_processes().unwrap_or_default());
+
+        for process in processes.iter().filter(|p| p.pid == pid) {
+            if let UsedGpuMemory::Used(bytes) = process.used_gpu_memory {
+                *total.get_or_insert(0) += bytes / (1024 * 1024);
+            }
+        }
+    }
+
+    Ok(total)
+}
+
 /// Get simulated GPU metrics for testing
 fn get_simulated_metrics(_gpu_id: u32) -> GpuMetrics {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -266,6 +399,33 @@ fn rand_float() -> f32 {
     (nanos % 1000) as f32 / 1000.0
 }
 
+/// One polling iteration: sample every GPU into its history and hand each sample
+/// to `emit`, unless paused. Returns how long to sleep before the next iteration.
+fn poll_once(
+    gpus: &[GpuInfo],
+    interval_ms: &AtomicU64,
+    paused: &AtomicBool,
+    mut emit: impl FnMut(u32, &GpuMetrics),
+) -> Duration {
+    if !paused.load(Ordering::Relaxed) {
+        for gpu in gpus {
+            let metrics = get_current_metrics(gpu.id);
+
+            // Update history
+            {
+                let mut histories = GPU_METRICS.lock().unwrap();
+                if let Some(history) = histories.iter_mut().find(|h| h.gpu_id == gpu.id) {
+                    history.push(metrics.clone());
+                }
+            }
+
+            emit(gpu.id, &metrics);
+        }
+    }
+
+    Duration::from_millis(interval_ms.load(Ordering::Relaxed).max(MIN_METRICS_INTERVAL_MS))
+}
+
 /// Start background metrics polling
 pub fn start_metrics_polling(app: AppHandle) {
     let gpus = detect_gpus();
@@ -274,31 +434,84 @@ pub fn start_metrics_polling(app: AppHandle) {
     {
         let mut histories = GPU_METRICS.lock().unwrap();
         for gpu in &gpus {
-            histories.push(GpuMetricsHistory::new(gpu.id, 120)); // 2 minutes at 1s interval
+            histories.push(GpuMetricsHistory::new(gpu.id, HISTORY_SAMPLES.load(Ordering::Relaxed)));
         }
     }
 
-    // Poll metrics every second
     loop {
-        for gpu in &gpus {
-            let metrics = get_current_metrics(gpu.id);
-            
-            // Update history
-            {
-                let mut histories = GPU_METRICS.lock().unwrap();
-                if let Some(history) = histories.iter_mut().find(|h| h.gpu_id == gpu.id) {
-                    history.push(metrics.clone());
-                }
-            }
-
+        let delay = poll_once(&gpus, &METRICS_INTERVAL_MS, &METRICS_PAUSED, |gpu_id, metrics| {
             // Emit event to frontend
             let _ = app.emit("gpu-metrics", serde_json::json!({
-                "gpu_id": gpu.id,
+                "gpu_id": gpu_id,
                 "metrics": metrics
             }));
-        }
+        });
 
-        std::thread::sleep(Duration::from_secs(1));
+        std::thread::sleep(delay);
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, utilization: f32) -> GpuMetrics {
+        GpuMetrics {
+            gpu_utilization: utilization,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_history_ring_buffer_and_window() {
+        let mut history = GpuMetricsHistory::new(0, 5);
+        for i in 0..8u64 {
+            history.push(sample(1_000 * i, i as f32));
+        }
+
+        // Only the newest 5 samples survive, oldest first
+        let all = history.window(3600, 7_000);
+        let timestamps: Vec<u64> = all.samples.iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, vec![3_000, 4_000, 5_000, 6_000, 7_000]);
+
+        // A 2 second window ending at 7s covers 5s..=7s
+        let recent = history.window(2, 7_000);
+        let utilization: Vec<f32> = recent.samples.iter().map(|s| s.gpu_utilization).collect();
+        assert_eq!(utilization, vec![5.0, 6.0, 7.0]);
+
+        history.set_max_samples(2);
+        assert_eq!(history.samples.len(), 2);
+        assert_eq!(history.samples[0].timestamp, 6_000);
+    }
+
+    #[test]
+    fn test_poll_interval_read_each_iteration() {
+        let gpus = vec![GpuInfo {
+            id: 42,
+            name: "Test GPU".to_string(),
+            vendor: "Test".to_string(),
+            driver_version: "1".to_string(),
+            memory_total_mb: 0,
+            cuda_cores: None,
+            compute_capability: None,
+        }];
+        let interval = AtomicU64::new(500);
+        let paused = AtomicBool::new(false);
+        let mut emitted = 0;
+
+        assert_eq!(poll_once(&gpus, &interval, &paused, |_, _| emitted += 1), Duration::from_millis(500));
+        assert_eq!(emitted, 1);
+
+        // A live change is picked up by the next iteration, floored at the minimum
+        interval.store(250, Ordering::Relaxed);
+        assert_eq!(poll_once(&gpus, &interval, &paused, |_, _| {}), Duration::from_millis(250));
+        interval.store(5, Ordering::Relaxed);
+        assert_eq!(poll_once(&gpus, &interval, &paused, |_, _| {}), Duration::from_millis(MIN_METRICS_INTERVAL_MS));
+
+        // Paused iterations don't sample
+        paused.store(true, Ordering::Relaxed);
+        poll_once(&gpus, &interval, &paused, |_, _| emitted += 1);
+        assert_eq!(emitted, 1);
+    }
+}
@@ -6,6 +6,7 @@ pub mod vfs;
 pub mod gpu;
 pub mod system;
 pub mod commands;
+pub mod logging;
 
 use tauri::{Manager, tray::TrayIconEvent};
 use vfs::commands::VfsStateWrapper;
@@ -19,6 +20,7 @@ fn show_window(window: tauri::Window) {
     if let Some(webview_window) = window.get_webview_window("main") {
         let _ = webview_window.show();
         let _ = webview_window.set_focus();
+        gpu::resume_metrics();
     }
 }
 
@@ -26,6 +28,8 @@ fn show_window(window: tauri::Window) {
 fn hide_window(window: tauri::Window) {
     if let Some(webview_window) = window.get_webview_window("main") {
         let _ = webview_window.hide();
+        // Nothing is watching the metrics while hidden
+        gpu::pause_metrics();
     }
 }
 
@@ -34,9 +38,11 @@ fn toggle_window(window: tauri::Window) {
     if let Some(webview_window) = window.get_webview_window("main") {
         if webview_window.is_visible().unwrap_or(false) {
             let _ = webview_window.hide();
+            gpu::pause_metrics();
         } else {
             let _ = webview_window.show();
             let _ = webview_window.set_focus();
+            gpu::resume_metrics();
         }
     }
 }
@@ -71,7 +77,8 @@ fn close_devtools(_window: tauri::Window) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tracing_subscriber::fmt::init();
+    // Held until the app exits so buffered log lines reach the file
+    let _log_guard = logging::init();
     
     let vfs_state = VfsStateWrapper::new();
     
@@ -87,6 +94,12 @@ pub fn run() {
                 gpu::start_metrics_polling(handle);
             });
             
+            // Watch for drives being plugged in or removed
+            vfs::commands::start_volume_monitor(app.handle().clone());
+            
+            // Find out early whether media features can work
+            vfs::commands::start_media_probe();
+            
             // Setup tray icon click handler
             let app_handle = app.handle().clone();
             if let Some(tray) = app.tray_by_id("main") {
@@ -95,9 +108,11 @@ pub fn run() {
                         if let Some(window) = app_handle.get_webview_window("main") {
                             if window.is_visible().unwrap_or(false) {
                                 let _ = window.hide();
+                                gpu::pause_metrics();
                             } else {
                                 let _ = window.show();
                                 let _ = window.set_focus();
+                                gpu::resume_metrics();
                             }
                         }
                     }
@@ -122,35 +137,101 @@ pub fn run() {
             // GPU & System metrics commands
             commands::get_gpu_info,
             commands::get_gpu_metrics,
+            commands::get_gpu_metrics_history,
+            commands::set_metrics_interval,
+            commands::pause_metrics,
+            commands::resume_metrics,
             commands::get_system_info,
             commands::get_all_metrics,
             commands::start_model,
             commands::stop_model,
             commands::get_model_status,
+            commands::get_model_resource_usage,
             // VFS Clean Architecture commands
             vfs::commands::vfs_init,
             vfs::commands::vfs_list_sources,
             vfs::commands::vfs_add_source,
             vfs::commands::vfs_remove_source,
+            vfs::commands::vfs_get_source_usage,
+            vfs::commands::vfs_selection_info,
+            vfs::commands::vfs_cancel_selection_info,
+            vfs::commands::vfs_set_source_quota,
+            vfs::commands::vfs_set_retry_policy,
+            vfs::commands::vfs_set_transfer_tuning,
+            vfs::commands::vfs_set_source_option,
+            vfs::commands::vfs_source_health,
+            vfs::commands::vfs_set_lifecycle_policy,
+            vfs::commands::vfs_list_lifecycle_candidates,
             vfs::commands::vfs_mount_local,
+            vfs::commands::vfs_mount_source,
+            vfs::commands::vfs_test_connection,
+            vfs::commands::vfs_forget_source,
+            vfs::commands::vfs_rename_source,
+            vfs::commands::vfs_store_credential,
+            vfs::commands::vfs_delete_credential,
+            vfs::commands::vfs_mount_memory,
             vfs::commands::vfs_eject,
+            vfs::commands::vfs_eject_all,
+            vfs::commands::vfs_remount_all_remote,
+            vfs::commands::vfs_list_system_volumes,
+            vfs::commands::vfs_set_volume_auto_add,
+            vfs::commands::vfs_get_automount_config,
+            vfs::commands::vfs_set_automount_config,
+            vfs::commands::vfs_block_device_info,
             vfs::commands::vfs_list_files,
+            vfs::commands::vfs_list_files_paged,
+            vfs::commands::vfs_list_files_stream,
+            vfs::commands::vfs_invalidate_listing,
+            vfs::commands::vfs_get_ignore_patterns,
+            vfs::commands::vfs_set_ignore_patterns,
             vfs::commands::vfs_warm_file,
+            vfs::commands::vfs_warm_status,
+            vfs::commands::vfs_warm_batch,
+            vfs::commands::vfs_hydrate_range,
+            vfs::commands::vfs_read_file_range,
+            vfs::commands::vfs_preview,
+            vfs::commands::vfs_tail,
+            vfs::commands::vfs_tail_follow_start,
+            vfs::commands::vfs_tail_follow_stop,
+            vfs::commands::vfs_get_signed_url,
+            vfs::commands::vfs_list_versions,
+            vfs::commands::vfs_restore_version,
+            vfs::commands::vfs_supported_transcode_formats,
             vfs::commands::vfs_transcode_video,
+            vfs::commands::vfs_list_transcode_jobs,
+            vfs::commands::vfs_pause_transcode_queue,
+            vfs::commands::vfs_resume_transcode_queue,
+            vfs::commands::vfs_cancel_transcode_job,
+            vfs::commands::vfs_create_proxies,
+            vfs::commands::vfs_get_proxy,
             vfs::commands::vfs_cache_stats,
             vfs::commands::vfs_clear_cache,
+            vfs::commands::vfs_cache_undo_delete,
             // VFS POSIX file operations
             vfs::commands::vfs_mkdir,
             vfs::commands::vfs_mkdir_p,
             vfs::commands::vfs_rmdir,
             vfs::commands::vfs_rename,
+            vfs::commands::vfs_batch_rename,
             vfs::commands::vfs_copy,
             vfs::commands::vfs_move,
             vfs::commands::vfs_delete,
             vfs::commands::vfs_delete_recursive,
+            vfs::commands::vfs_delete_many,
+            vfs::commands::vfs_move_to_trash,
+            vfs::commands::vfs_empty_trash,
+            vfs::commands::vfs_undo,
+            vfs::commands::vfs_redo,
             vfs::commands::vfs_chmod,
             vfs::commands::vfs_stat,
+            vfs::commands::vfs_get_info,
+            vfs::commands::vfs_set_scratch_dir,
+            vfs::commands::vfs_gc_temp,
+            vfs::commands::vfs_set_temp_ttl,
             vfs::commands::vfs_touch,
+            vfs::commands::vfs_new_from_template,
+            vfs::commands::vfs_register_template,
+            vfs::commands::vfs_list_templates,
             vfs::commands::vfs_exists,
             vfs::commands::vfs_read_text,
             vfs::commands::vfs_read_file_bytes,
@@ -166,11 +247,20 @@ pub fn run() {
             vfs::commands::vfs_clipboard_has_files,
             vfs::commands::vfs_clipboard_clear,
             vfs::commands::vfs_clipboard_paste_to_vfs,
+            vfs::commands::vfs_import_paths,
             vfs::commands::vfs_clipboard_paste_to_native,
             vfs::commands::vfs_clipboard_read_native,
             vfs::commands::vfs_clipboard_write_native,
+            vfs::commands::vfs_copy_path,
+            vfs::commands::vfs_clipboard_copy_content,
+            vfs::commands::vfs_clipboard_copy_image,
             // VFS Tags & Favorites commands
             vfs::commands::vfs_get_metadata,
+            vfs::commands::vfs_get_folder_view,
+            vfs::commands::vfs_set_folder_view,
+            vfs::commands::vfs_effective_tier,
+            vfs::commands::vfs_tier_history,
+            vfs::commands::vfs_vacuum_metadata,
             vfs::commands::vfs_add_tag,
             vfs::commands::vfs_remove_tag,
             vfs::commands::vfs_toggle_favorite,
@@ -182,25 +272,65 @@ pub fn run() {
             vfs::commands::vfs_list_by_tag,
             vfs::commands::vfs_list_by_color,
             vfs::commands::vfs_list_all_tags,
+            vfs::commands::vfs_create_smart_folder,
+            vfs::commands::vfs_list_smart_folders,
+            vfs::commands::vfs_eval_smart_folder,
+            vfs::commands::vfs_delete_smart_folder,
             // VFS Cross-Storage commands
             vfs::commands::vfs_copy_to_source,
+            vfs::commands::vfs_copy_to_multiple,
             vfs::commands::vfs_move_to_source,
             vfs::commands::vfs_get_transfer_targets,
+            vfs::commands::vfs_set_global_transfer_limit,
             vfs::commands::vfs_batch_copy_to_source,
             vfs::commands::vfs_batch_move_to_source,
             // VFS Sync commands
             vfs::commands::vfs_sync,
             vfs::commands::vfs_get_sync_targets,
             vfs::commands::vfs_change_tier,
+            vfs::commands::vfs_initiate_retrieval,
             vfs::commands::vfs_check_nvme_cache,
             vfs::commands::vfs_set_tags,
+            vfs::commands::vfs_set_tag_storage,
             vfs::commands::vfs_reveal_in_finder,
+            vfs::commands::vfs_open_terminal,
             // VFS Open file commands
+            vfs::commands::vfs_resolve_path,
             vfs::commands::vfs_open_file,
             vfs::commands::vfs_open_file_with,
             vfs::commands::vfs_get_apps_for_file,
             vfs::commands::vfs_get_os_preferences,
             vfs::commands::vfs_get_thumbnail,
+            vfs::commands::vfs_generate_sprite_sheet,
+            vfs::commands::vfs_generate_waveform,
+            vfs::commands::vfs_media_capabilities,
+            vfs::commands::vfs_diff_text,
+            vfs::commands::vfs_create_archive,
+            vfs::commands::vfs_extract_archive,
+            vfs::commands::vfs_generate_manifest,
+            vfs::commands::vfs_verify_manifest,
+            vfs::commands::vfs_tree_hash,
+            vfs::commands::vfs_find_duplicates,
+            vfs::commands::vfs_find_similar_images,
+            vfs::commands::vfs_search,
+            vfs::commands::vfs_recent_searches,
+            vfs::commands::vfs_clear_search_history,
+            vfs::commands::vfs_reindex_source,
+            vfs::commands::vfs_index_status,
+            vfs::commands::vfs_compare_dirs,
+            // VFS Recents commands
+            vfs::commands::vfs_list_recents,
+            vfs::commands::vfs_clear_recents,
+            vfs::commands::vfs_recent_destinations,
+            // VFS Bookmarks commands
+            vfs::commands::vfs_add_bookmark,
+            vfs::commands::vfs_remove_bookmark,
+            vfs::commands::vfs_list_bookmarks,
+            vfs::commands::vfs_reorder_bookmarks,
+            // VFS Audit log commands
+            vfs::commands::vfs_export_audit_log,
+            vfs::commands::vfs_set_log_level,
+            vfs::commands::vfs_export_logs,
             // VFS Transcription commands
             vfs::commands::vfs_start_transcription,
             vfs::commands::vfs_stop_transcription,
@@ -217,6 +347,12 @@ pub fn run() {
             vfs::commands::vfs_list_uploads,
             vfs::commands::vfs_list_operations,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Don't orphan a model process started from the app
+                commands::shutdown_model();
+            }
+        });
 }
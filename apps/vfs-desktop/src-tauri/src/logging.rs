@@ -0,0 +1,169 @@
+//! Logging - stdout plus a daily rolling file in the app log directory
+//!
+//! The level filter sits behind a reload handle so it can be raised to
+//! `debug` while reproducing a problem without restarting the app, and the
+//! most recent log files can be zipped up to attach to a bug report.
+
+use anyhow::{Context, Result};
+use once_cell::sync::OnceCell;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Bundle identifier, used for the log directory like Tauri's `app_log_dir`
+const APP_IDENTIFIER: &str = "io.ursly.vfs";
+
+/// Level used when `RUST_LOG` isn't set
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// Log files are `ursly-vfs.log.YYYY-MM-DD`
+pub const LOG_FILE_PREFIX: &str = "ursly-vfs.log";
+
+/// Days of logs kept on disk and included in an export
+pub const MAX_EXPORTED_LOG_FILES: usize = 7;
+
+static FILTER: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
+/// `~/Library/Logs/io.ursly.vfs` on macOS, `<local data>/io.ursly.vfs/logs` elsewhere
+pub fn log_dir() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join("Library").join("Logs").join(APP_IDENTIFIER);
+        }
+    }
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+        .join("logs")
+}
+
+/// Install the global subscriber. The returned guard flushes the log file
+/// when dropped, so it has to live as long as the app.
+pub fn init() -> Option<WorkerGuard> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(DEFAULT_LOG_LEVEL.into())
+        .from_env_lossy();
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER.set(handle);
+    
+    // Keep logging to stdout if the log file can't be created
+    let dir = log_dir();
+    let (file_layer, guard) = match file_appender(&dir) {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(fmt::layer().with_ansi(false).with_writer(writer)), Some(guard))
+        }
+        Err(e) => {
+            eprintln!("Failed to open log file in {:?}: {:#}", dir, e);
+            (None, None)
+        }
+    };
+    
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .try_init();
+    
+    guard
+}
+
+/// Daily log files in `dir`, deleting all but the newest
+/// `MAX_EXPORTED_LOG_FILES` as they roll over
+fn file_appender(dir: &Path) -> Result<RollingFileAppender> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create log directory {:?}", dir))?;
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_EXPORTED_LOG_FILES)
+        .build(dir)
+        .context("Failed to create log file appender")
+}
+
+/// Change the level of the running app's logs
+pub fn set_level(level: LevelFilter) -> Result<()> {
+    let handle = FILTER.get().context("Logging is not initialized")?;
+    reload_level(handle, level)?;
+    tracing::info!("Log level set to {}", level);
+    Ok(())
+}
+
+fn reload_level<S>(handle: &reload::Handle<EnvFilter, S>, level: LevelFilter) -> Result<()> {
+    handle.reload(EnvFilter::default().add_directive(level.into()))
+        .context("Failed to reload log filter")
+}
+
+/// Zip the newest log files in `dir` into `dest`, returning how many were added
+pub fn export_logs(dir: &Path, dest: &Path) -> Result<usize> {
+    let mut logs: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read log directory {:?}", dir))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_PREFIX))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some((metadata.modified().ok()?, entry.path()))
+        })
+        .collect();
+    logs.sort_by(|a, b| b.0.cmp(&a.0));
+    logs.truncate(MAX_EXPORTED_LOG_FILES);
+    
+    let mut zip = zip::ZipWriter::new(File::create(dest)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (_, path) in &logs {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        zip.start_file(name.as_ref(), options)?;
+        io::copy(&mut File::open(path)?, &mut zip)?;
+    }
+    zip.finish()?.flush()?;
+    
+    Ok(logs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+    
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_raising_level_to_debug_lets_debug_events_through() {
+        let captured = Captured::default();
+        let (filter, handle) = reload::Layer::new(EnvFilter::default().add_directive(LevelFilter::INFO.into()));
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt::layer().with_ansi(false).with_writer(move || writer.clone()));
+        
+        let output = || String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            let probe = || tracing::debug!("probing listing cache");
+            
+            probe();
+            assert!(!output().contains("probing listing cache"));
+            
+            reload_level(&handle, LevelFilter::DEBUG).unwrap();
+            probe();
+            assert!(output().contains("probing listing cache"));
+        });
+    }
+}
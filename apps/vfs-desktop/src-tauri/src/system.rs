@@ -3,7 +3,7 @@
 //! Provides CPU, memory, and system information using sysinfo crate.
 
 use serde::{Deserialize, Serialize};
-use sysinfo::{System, Networks};
+use sysinfo::{Networks, Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 use std::sync::Mutex;
 use std::time::Instant;
 use once_cell::sync::Lazy;
@@ -89,6 +89,16 @@ pub struct ProcessInfo {
     pub start_time: u64,
 }
 
+/// Resource usage of a single process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub cpu_usage: f32,
+    pub memory_mb: u64,
+    /// GPU memory held by the process, when the driver can attribute it
+    pub gpu_memory_mb: Option<u64>,
+}
+
 /// Get system information
 pub fn get_system_info() -> SystemInfo {
     let mut sys = System::new_all();
@@ -309,3 +319,23 @@ pub fn find_model_processes() -> Vec<ProcessInfo> {
     processes
 }
 
+/// Sample CPU, RSS and GPU memory for `pid`. Returns `None` if the process is gone.
+pub fn get_process_usage(pid: u32) -> Option<ProcessUsage> {
+    let sys_pid = Pid::from_u32(pid);
+    let refresh = ProcessRefreshKind::new().with_cpu().with_memory();
+    let mut sys = System::new();
+
+    // CPU usage is the delta between two refreshes
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), refresh);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), refresh);
+
+    let process = sys.process(sys_pid)?;
+
+    Some(ProcessUsage {
+        pid,
+        cpu_usage: process.cpu_usage(),
+        memory_mb: process.memory() / (1024 * 1024),
+        gpu_memory_mb: crate::gpu::process_gpu_memory_mb(pid),
+    })
+}
@@ -0,0 +1,111 @@
+//! Auto-mount Store Adapter - JSON file of folders mounted at startup
+//!
+//! Until the user changes it the list is Home plus the usual folders inside
+//! it (Desktop, Documents, ...). Saving an empty list mounts nothing.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::{debug, warn};
+
+/// A folder mounted as a local source at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomountEntry {
+    pub path: PathBuf,
+    /// Display name of the source
+    pub name: String,
+}
+
+impl AutomountEntry {
+    pub fn new(name: &str, path: PathBuf) -> Self {
+        Self { path, name: name.to_string() }
+    }
+    
+    /// Home and the common folders inside it
+    pub fn defaults() -> Vec<Self> {
+        let Some(home) = dirs::home_dir() else {
+            return Vec::new();
+        };
+        
+        let mut entries = vec![Self::new("Home", home.clone())];
+        for folder in ["Desktop", "Documents", "Downloads", "Pictures", "Music", "Videos"] {
+            entries.push(Self::new(folder, home.join(folder)));
+        }
+        entries
+    }
+}
+
+/// Auto-mount list backed by a JSON file
+pub struct AutomountStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    /// `None` until the user saves a list of their own
+    entries: RwLock<Option<Vec<AutomountEntry>>>,
+}
+
+impl AutomountStore {
+    /// Create a store at `store_path`, loading any saved list
+    pub async fn new(store_path: PathBuf) -> Result<Self> {
+        let store = Self {
+            store_path,
+            entries: RwLock::new(None),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app config directory
+    pub async fn default_store() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly");
+        
+        fs::create_dir_all(&config_dir).await?;
+        
+        Self::new(config_dir.join("automount.json")).await
+    }
+    
+    /// Load the saved list from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Auto-mount list not found, using defaults");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read auto-mount list")?;
+        
+        match serde_json::from_str(&content) {
+            Ok(entries) => *self.entries.write() = Some(entries),
+            Err(e) => warn!("Ignoring corrupt auto-mount list {:?}: {}", self.store_path, e),
+        }
+        
+        Ok(())
+    }
+    
+    /// Folders to mount: the saved list, or `AutomountEntry::defaults`
+    pub fn entries(&self) -> Vec<AutomountEntry> {
+        self.entries.read().clone().unwrap_or_else(AutomountEntry::defaults)
+    }
+    
+    /// Replace the list; it takes effect at the next startup
+    pub async fn set_entries(&self, entries: Vec<AutomountEntry>) -> Result<()> {
+        let content = serde_json::to_string_pretty(&entries)
+            .context("Failed to serialize auto-mount list")?;
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&self.store_path, content).await
+            .context("Failed to write auto-mount list")?;
+        
+        *self.entries.write() = Some(entries);
+        Ok(())
+    }
+}
@@ -0,0 +1,510 @@
+//! Block Storage Adapter
+//!
+//! Implements storage adapter for mounted block devices (EBS volumes, local
+//! NVMe drives, partitions). File access goes through the mount point, so it
+//! is delegated to a `LocalStorageAdapter`; on top of that the adapter
+//! reports itself as block storage and resolves the underlying device for
+//! device-level information (model, serial, queue depth, NVMe).
+//!
+//! Device discovery:
+//! - Linux: `/proc/mounts` for the backing device, `/sys/block` for details
+//! - macOS: `diskutil info <mount point>`
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use tracing::warn;
+
+use crate::vfs::adapters::LocalStorageAdapter;
+use crate::vfs::domain::{VirtualFile, StorageSourceType};
+use crate::vfs::ports::{
+    StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions, ListOptions
+};
+
+/// Information about the block device backing a mount point
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockDeviceInfo {
+    /// Device node (e.g., /dev/nvme0n1p1, /dev/disk3s1)
+    pub device: Option<String>,
+    /// Filesystem type (e.g., ext4, xfs, apfs)
+    pub filesystem: Option<String>,
+    /// Device model as reported by the OS
+    pub model: Option<String>,
+    /// Device serial number (where available)
+    pub serial: Option<String>,
+    /// Hardware queue depth (where available)
+    pub queue_depth: Option<u32>,
+    /// Whether the device is an NVMe drive
+    pub is_nvme: bool,
+    /// Total capacity of the mounted filesystem in bytes
+    pub total_bytes: u64,
+    /// Available capacity of the mounted filesystem in bytes
+    pub available_bytes: u64,
+}
+
+/// Block storage adapter for mounted block devices
+pub struct BlockStorageAdapter {
+    /// File access through the mount point
+    local: LocalStorageAdapter,
+    
+    /// Mount point of the block device
+    mount_point: PathBuf,
+}
+
+impl BlockStorageAdapter {
+    /// Create a new block storage adapter over a mount point
+    pub fn new(mount_point: PathBuf, name: String) -> Self {
+        Self {
+            local: LocalStorageAdapter::new(mount_point.clone(), name),
+            mount_point,
+        }
+    }
+    
+    /// Get information about the device backing this mount point
+    pub async fn device_info(&self) -> Result<BlockDeviceInfo> {
+        let mount_point = self.mount_point.clone();
+        
+        let mut info = tokio::task::spawn_blocking(move || detect_device_info(&mount_point))
+            .await
+            .context("Device detection task failed")?;
+        
+        if let Ok(space) = crate::vfs::platform::get_disk_space(&self.mount_point) {
+            info.total_bytes = space.total;
+            info.available_bytes = space.available;
+        }
+        
+        Ok(info)
+    }
+}
+
+// =============================================================================
+// Device Detection
+// =============================================================================
+
+/// Detect the block device backing a mount point
+fn detect_device_info(mount_point: &Path) -> BlockDeviceInfo {
+    #[cfg(target_os = "linux")]
+    {
+        detect_device_info_linux(mount_point)
+    }
+    
+    #[cfg(target_os = "macos")]
+    {
+        detect_device_info_macos(mount_point)
+    }
+    
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = mount_point;
+        BlockDeviceInfo::default()
+    }
+}
+
+/// Find the `/proc/mounts` entry with the longest mount point containing `path`
+///
+/// Returns `(device, mount_point, filesystem)`.
+pub fn find_mount_entry(mounts: &str, path: &Path) -> Option<(String, PathBuf, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            // /proc/mounts escapes spaces in mount points as \040
+            let mount = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            Some((device.to_string(), PathBuf::from(mount), fs_type.to_string()))
+        })
+        .filter(|(_, mount, _)| path.starts_with(mount))
+        .max_by_key(|(_, mount, _)| mount.components().count())
+}
+
+/// Strip the partition suffix from a kernel device name
+///
+/// `nvme0n1p2` -> `nvme0n1`, `sda1` -> `sda`, `xvdf` -> `xvdf`
+pub fn parent_block_device(device_name: &str) -> String {
+    if device_name.starts_with("nvme") || device_name.starts_with("mmcblk") {
+        if let Some(idx) = device_name.rfind('p') {
+            let (base, part) = device_name.split_at(idx);
+            if part.len() > 1 && part[1..].chars().all(|c| c.is_ascii_digit()) && base.ends_with(|c: char| c.is_ascii_digit()) {
+                return base.to_string();
+            }
+        }
+        return device_name.to_string();
+    }
+    
+    device_name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn detect_device_info_linux(mount_point: &Path) -> BlockDeviceInfo {
+    let mut info = BlockDeviceInfo::default();
+    
+    let canonical = std::fs::canonicalize(mount_point).unwrap_or_else(|_| mount_point.to_path_buf());
+    let mounts = match std::fs::read_to_string("/proc/mounts") {
+        Ok(m) => m,
+        Err(e) => {
+            warn!("Failed to read /proc/mounts: {}", e);
+            return info;
+        }
+    };
+    
+    let Some((device, _, fs_type)) = find_mount_entry(&mounts, &canonical) else {
+        return info;
+    };
+    
+    info.filesystem = Some(fs_type);
+    
+    // Resolve symlinks like /dev/disk/by-uuid/... or /dev/root
+    let device_path = std::fs::canonicalize(&device).unwrap_or_else(|_| PathBuf::from(&device));
+    info.device = Some(device_path.to_string_lossy().to_string());
+    
+    let Some(kernel_name) = device_path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+        return info;
+    };
+    
+    let disk = parent_block_device(&kernel_name);
+    let sys_block = PathBuf::from("/sys/block").join(&disk);
+    
+    let read_sys = |rel: &str| {
+        std::fs::read_to_string(sys_block.join(rel))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    
+    info.is_nvme = disk.starts_with("nvme");
+    info.model = read_sys("device/model");
+    info.serial = read_sys("device/serial").or_else(|| read_sys("serial"));
+    info.queue_depth = read_sys("device/queue_depth")
+        .or_else(|| read_sys("queue/nr_requests"))
+        .and_then(|s| s.parse().ok());
+    
+    info
+}
+
+#[cfg(target_os = "macos")]
+fn detect_device_info_macos(mount_point: &Path) -> BlockDeviceInfo {
+    use std::process::Command;
+    
+    let mut info = BlockDeviceInfo::default();
+    
+    let output = match Command::new("diskutil").arg("info").arg(mount_point).output() {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            warn!("diskutil info failed: {}", String::from_utf8_lossy(&o.stderr));
+            return info;
+        }
+        Err(e) => {
+            warn!("Failed to run diskutil: {}", e);
+            return info;
+        }
+    };
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim().to_string();
+        if value.is_empty() {
+            continue;
+        }
+        
+        match key.trim() {
+            "Device Node" => info.device = Some(value),
+            "Type (Bundle)" | "File System Personality" if info.filesystem.is_none() => info.filesystem = Some(value),
+            "Device / Media Name" => info.model = Some(value),
+            "Disk / Partition UUID" if info.serial.is_none() => info.serial = Some(value),
+            "Protocol" => info.is_nvme = value.eq_ignore_ascii_case("PCI-Express") || value.eq_ignore_ascii_case("NVMe"),
+            _ => {}
+        }
+    }
+    
+    info
+}
+
+// Everything but the storage type is the mounted filesystem's, so it's
+// passed through to the local adapter
+#[async_trait]
+impl StorageAdapter for BlockStorageAdapter {
+    fn storage_type(&self) -> StorageSourceType {
+        StorageSourceType::Block
+    }
+    
+    fn name(&self) -> &str {
+        self.local.name()
+    }
+    
+    async fn test_connection(&self) -> Result<bool> {
+        self.local.test_connection().await
+    }
+    
+    async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        self.local.list_files(path).await
+    }
+    
+    async fn list_files_with(&self, path: &Path, options: &ListOptions) -> Result<Vec<VirtualFile>> {
+        self.local.list_files_with(path, options).await
+    }
+    
+    async fn list_files_batched(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        batch_size: usize,
+    ) -> Result<BoxStream<'static, Result<Vec<VirtualFile>>>> {
+        self.local.list_files_batched(path, options, batch_size).await
+    }
+    
+    async fn list_files_paged(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<VirtualFile>, Option<String>)> {
+        self.local.list_files_paged(path, options, cursor, limit).await
+    }
+    
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        self.local.read_file(path).await
+    }
+    
+    async fn read_file_range(&self, path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
+        self.local.read_file_range(path, offset, length).await
+    }
+    
+    async fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.local.write_file(path, data).await
+    }
+    
+    async fn get_metadata(&self, path: &Path) -> Result<VirtualFile> {
+        self.local.get_metadata(path).await
+    }
+    
+    async fn exists(&self, path: &Path) -> Result<bool> {
+        StorageAdapter::exists(&self.local, path).await
+    }
+    
+    async fn delete(&self, path: &Path) -> Result<()> {
+        self.local.delete(path).await
+    }
+    
+    async fn create_dir(&self, path: &Path) -> Result<()> {
+        self.local.create_dir(path).await
+    }
+    
+    async fn file_size(&self, path: &Path) -> Result<u64> {
+        StorageAdapter::file_size(&self.local, path).await
+    }
+}
+
+#[async_trait]
+impl IFileOperations for BlockStorageAdapter {
+    async fn list(&self, path: &Path) -> Result<Vec<FileEntry>> {
+        self.local.list(path).await
+    }
+    
+    async fn stat(&self, path: &Path) -> Result<FileStat> {
+        self.local.stat(path).await
+    }
+    
+    async fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        self.local.read(path).await
+    }
+    
+    async fn read_range(&self, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+        self.local.read_range(path, offset, len).await
+    }
+    
+    async fn write(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.local.write(path, data).await
+    }
+    
+    async fn write_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.local.write_atomic(path, data).await
+    }
+    
+    async fn write_stream(&self, path: &Path, chunks: BoxStream<'_, Result<Vec<u8>>>) -> Result<u64> {
+        self.local.write_stream(path, chunks).await
+    }
+    
+    async fn append(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.local.append(path, data).await
+    }
+    
+    async fn write_at(&self, path: &Path, offset: u64, data: &[u8]) -> Result<()> {
+        self.local.write_at(path, offset, data).await
+    }
+    
+    async fn truncate(&self, path: &Path, len: u64) -> Result<()> {
+        self.local.truncate(path, len).await
+    }
+    
+    async fn mkdir(&self, path: &Path) -> Result<()> {
+        self.local.mkdir(path).await
+    }
+    
+    async fn mkdir_p(&self, path: &Path) -> Result<()> {
+        self.local.mkdir_p(path).await
+    }
+    
+    async fn rmdir(&self, path: &Path) -> Result<()> {
+        self.local.rmdir(path).await
+    }
+    
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.local.rename(from, to).await
+    }
+    
+    async fn copy(&self, from: &Path, to: &Path, options: CopyOptions) -> Result<()> {
+        self.local.copy(from, to, options).await
+    }
+    
+    async fn mv(&self, from: &Path, to: &Path, options: MoveOptions) -> Result<()> {
+        self.local.mv(from, to, options).await
+    }
+    
+    async fn rm(&self, path: &Path) -> Result<()> {
+        self.local.rm(path).await
+    }
+    
+    async fn rm_rf(&self, path: &Path) -> Result<()> {
+        self.local.rm_rf(path).await
+    }
+    
+    async fn symlink(&self, target: &Path, link: &Path) -> Result<()> {
+        self.local.symlink(target, link).await
+    }
+    
+    async fn readlink(&self, path: &Path) -> Result<String> {
+        self.local.readlink(path).await
+    }
+    
+    async fn exists(&self, path: &Path) -> Result<bool> {
+        IFileOperations::exists(&self.local, path).await
+    }
+    
+    async fn is_dir(&self, path: &Path) -> Result<bool> {
+        self.local.is_dir(path).await
+    }
+    
+    async fn is_file(&self, path: &Path) -> Result<bool> {
+        self.local.is_file(path).await
+    }
+    
+    async fn is_symlink(&self, path: &Path) -> Result<bool> {
+        self.local.is_symlink(path).await
+    }
+    
+    async fn chmod(&self, path: &Path, mode: u32) -> Result<()> {
+        self.local.chmod(path, mode).await
+    }
+    
+    async fn chown(&self, path: &Path, uid: u32, gid: u32) -> Result<()> {
+        self.local.chown(path, uid, gid).await
+    }
+    
+    async fn touch(&self, path: &Path) -> Result<()> {
+        self.local.touch(path).await
+    }
+    
+    async fn set_times(&self, path: &Path, atime: Option<SystemTime>, mtime: Option<SystemTime>) -> Result<()> {
+        self.local.set_times(path, atime, mtime).await
+    }
+    
+    async fn file_size(&self, path: &Path) -> Result<u64> {
+        IFileOperations::file_size(&self.local, path).await
+    }
+    
+    async fn available_space(&self) -> Result<u64> {
+        self.local.available_space().await
+    }
+    
+    async fn total_space(&self) -> Result<u64> {
+        self.local.total_space().await
+    }
+    
+    fn is_read_only(&self) -> bool {
+        self.local.is_read_only()
+    }
+    
+    fn root_path(&self) -> &Path {
+        self.local.root_path()
+    }
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::domain::StorageTier;
+    
+    #[tokio::test]
+    async fn test_temp_dir_reported_as_block() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("clip.mov"), b"frames").unwrap();
+        
+        let adapter = BlockStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "NVMe Cache".to_string(),
+        );
+        
+        assert_eq!(adapter.storage_type(), StorageSourceType::Block);
+        assert!(adapter.test_connection().await.unwrap());
+        
+        let files = adapter.list_files(Path::new("/")).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].tier_status.current_tier, StorageTier::Hot);
+        
+        let info = adapter.device_info().await.unwrap();
+        assert!(info.total_bytes > 0);
+    }
+    
+    #[tokio::test]
+    async fn test_file_ops_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = BlockStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "Scratch".to_string(),
+        );
+        
+        IFileOperations::write(&adapter, Path::new("/a/b.txt"), b"block").await.unwrap();
+        let content = IFileOperations::read(&adapter, Path::new("/a/b.txt")).await.unwrap();
+        assert_eq!(content, b"block");
+    }
+    
+    #[test]
+    fn test_find_mount_entry_longest_prefix() {
+        let mounts = "/dev/root / ext4 rw,relatime 0 0\n\
+                      /dev/nvme1n1p1 /mnt/nvme ext4 rw 0 0\n\
+                      /dev/xvdf /mnt/nvme/scratch\\040space xfs rw 0 0\n";
+        
+        let (device, mount, fs_type) = find_mount_entry(mounts, Path::new("/mnt/nvme/projects")).unwrap();
+        assert_eq!(device, "/dev/nvme1n1p1");
+        assert_eq!(mount, PathBuf::from("/mnt/nvme"));
+        assert_eq!(fs_type, "ext4");
+        
+        let (device, _, _) = find_mount_entry(mounts, Path::new("/mnt/nvme/scratch space/x")).unwrap();
+        assert_eq!(device, "/dev/xvdf");
+        
+        let (device, _, _) = find_mount_entry(mounts, Path::new("/home/user")).unwrap();
+        assert_eq!(device, "/dev/root");
+    }
+    
+    #[test]
+    fn test_parent_block_device() {
+        assert_eq!(parent_block_device("nvme0n1p2"), "nvme0n1");
+        assert_eq!(parent_block_device("nvme0n1"), "nvme0n1");
+        assert_eq!(parent_block_device("mmcblk0p1"), "mmcblk0");
+        assert_eq!(parent_block_device("sda1"), "sda");
+        assert_eq!(parent_block_device("xvdf"), "xvdf");
+    }
+}
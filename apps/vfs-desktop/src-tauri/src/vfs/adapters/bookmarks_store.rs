@@ -0,0 +1,205 @@
+//! Bookmarks Store Adapter - JSON file-based pinned folders
+//!
+//! Bookmarks are the sidebar's pinned locations: user-ordered folders on any
+//! source. Unlike favorites (per-file flags in `JsonMetadataStore`) they live
+//! in one ordered list in the app's config directory.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::vfs::domain::VfsError;
+
+/// A pinned location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    pub id: String,
+    
+    /// Storage source the location belongs to
+    pub source_id: String,
+    
+    /// Path within the source
+    pub path: PathBuf,
+    
+    /// Label shown in the sidebar
+    pub name: String,
+    
+    pub created_at: DateTime<Utc>,
+    
+    /// Target no longer exists; set when listed, never persisted as true
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// Ordered bookmark list backed by a JSON file
+pub struct BookmarksStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    /// Bookmarks in sidebar order
+    bookmarks: RwLock<Vec<Bookmark>>,
+}
+
+impl BookmarksStore {
+    /// Create a store at `store_path`, loading any existing bookmarks
+    pub async fn new(store_path: PathBuf) -> Result<Self> {
+        let store = Self {
+            store_path,
+            bookmarks: RwLock::new(Vec::new()),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app config directory
+    pub async fn default_store() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly");
+        
+        fs::create_dir_all(&config_dir).await?;
+        
+        Self::new(config_dir.join("bookmarks.json")).await
+    }
+    
+    /// Load bookmarks from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Bookmarks store not found, starting fresh");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read bookmarks store")?;
+        
+        let data: Vec<Bookmark> = match serde_json::from_str(&content) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Ignoring corrupt bookmarks store {:?}: {}", self.store_path, e);
+                Vec::new()
+            }
+        };
+        
+        info!("Loaded {} bookmarks", data.len());
+        *self.bookmarks.write() = data;
+        
+        Ok(())
+    }
+    
+    /// Save bookmarks to disk
+    async fn save(&self) -> Result<()> {
+        let content = {
+            let bookmarks = self.bookmarks.read();
+            serde_json::to_string_pretty(&*bookmarks)
+                .context("Failed to serialize bookmarks")?
+        };
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        fs::write(&self.store_path, content).await
+            .context("Failed to write bookmarks store")
+    }
+    
+    /// Pin a location at the end of the list
+    pub async fn add(&self, source_id: &str, path: &Path, name: &str) -> Result<Bookmark> {
+        if name.trim().is_empty() {
+            return Err(VfsError::InvalidInput("Bookmark name cannot be empty".to_string()).into());
+        }
+        
+        let bookmark = Bookmark {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_id: source_id.to_string(),
+            path: path.to_path_buf(),
+            name: name.to_string(),
+            created_at: Utc::now(),
+            stale: false,
+        };
+        self.bookmarks.write().push(bookmark.clone());
+        
+        self.save().await?;
+        Ok(bookmark)
+    }
+    
+    /// Unpin a location
+    pub async fn remove(&self, id: &str) -> Result<()> {
+        {
+            let mut bookmarks = self.bookmarks.write();
+            let before = bookmarks.len();
+            bookmarks.retain(|b| b.id != id);
+            if bookmarks.len() == before {
+                return Err(VfsError::NotFound(format!("Bookmark not found: {}", id)).into());
+            }
+        }
+        
+        self.save().await
+    }
+    
+    /// All bookmarks in sidebar order
+    pub fn list(&self) -> Vec<Bookmark> {
+        self.bookmarks.read().clone()
+    }
+    
+    /// Put the bookmarks with the given ids first, in that order; any not
+    /// mentioned keep their relative order after them
+    pub async fn reorder(&self, order: &[String]) -> Result<Vec<Bookmark>> {
+        let reordered = {
+            let mut bookmarks = self.bookmarks.write();
+            if let Some(unknown) = order.iter().find(|id| !bookmarks.iter().any(|b| &b.id == *id)) {
+                return Err(VfsError::NotFound(format!("Bookmark not found: {}", unknown)).into());
+            }
+            
+            let mut rest = std::mem::take(&mut *bookmarks);
+            for id in order {
+                if let Some(index) = rest.iter().position(|b| &b.id == id) {
+                    bookmarks.push(rest.remove(index));
+                }
+            }
+            bookmarks.extend(rest);
+            bookmarks.clone()
+        };
+        
+        self.save().await?;
+        Ok(reordered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_bookmarks_reordered_and_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("bookmarks.json");
+        
+        let (a, b, c) = {
+            let store = BookmarksStore::new(store_path.clone()).await.unwrap();
+            let a = store.add("local", Path::new("/a"), "A").await.unwrap();
+            let b = store.add("local", Path::new("/b"), "B").await.unwrap();
+            let c = store.add("s3", Path::new("/c"), "C").await.unwrap();
+            
+            // Only `c` mentioned: it moves to the front, the rest keep their order
+            store.reorder(&[c.id.clone()]).await.unwrap();
+            assert!(store.reorder(&["missing".to_string()]).await.is_err());
+            (a, b, c)
+        };
+        
+        let store = BookmarksStore::new(store_path).await.unwrap();
+        let ids: Vec<_> = store.list().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec![c.id, a.id.clone(), b.id]);
+        
+        store.remove(&a.id).await.unwrap();
+        assert_eq!(store.list().len(), 2);
+        assert!(store.remove(&a.id).await.is_err());
+    }
+}
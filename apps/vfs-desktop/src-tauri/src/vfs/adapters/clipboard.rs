@@ -12,12 +12,20 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::vfs::domain::VfsError;
 use crate::vfs::ports::clipboard::{
-    ClipboardContent, ClipboardOperation, ClipboardSource, IClipboardService, PasteResult,
+    ClipboardBackend, ClipboardContent, ClipboardOperation, ClipboardSource, IClipboardService, PasteResult,
 };
 use crate::vfs::ports::{CopyOptions, IFileOperations, MoveOptions};
+use crate::vfs::application::scratch::scratch_path;
 use crate::vfs::application::VfsService;
 
+/// Largest file whose contents can be copied as text
+pub const MAX_CLIPBOARD_TEXT_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Largest image file that can be copied as an image
+pub const MAX_CLIPBOARD_IMAGE_SIZE: u64 = 64 * 1024 * 1024;
+
 /// Clipboard adapter for cross-platform file operations
 pub struct ClipboardAdapter {
     /// Internal clipboard storage (for VFS-to-VFS operations)
@@ -25,6 +33,9 @@ pub struct ClipboardAdapter {
     
     /// Reference to VFS service for file operations
     vfs_service: Option<Arc<VfsService>>,
+    
+    /// Where text and images are written
+    backend: Arc<dyn ClipboardBackend>,
 }
 
 impl ClipboardAdapter {
@@ -33,6 +44,7 @@ impl ClipboardAdapter {
         Self {
             internal_clipboard: RwLock::new(None),
             vfs_service: None,
+            backend: Arc::new(SystemClipboard),
         }
     }
     
@@ -41,9 +53,16 @@ impl ClipboardAdapter {
         Self {
             internal_clipboard: RwLock::new(None),
             vfs_service: Some(vfs_service),
+            backend: Arc::new(SystemClipboard),
         }
     }
     
+    /// Write text and images somewhere other than the OS clipboard
+    pub fn with_backend(mut self, backend: Arc<dyn ClipboardBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+    
     /// Set VFS service after creation
     pub fn set_vfs_service(&mut self, vfs_service: Arc<VfsService>) {
         self.vfs_service = Some(vfs_service);
@@ -172,7 +191,7 @@ impl ClipboardAdapter {
         };
         
         // Create temp directory for exported files
-        let temp_dir = std::env::temp_dir().join("ursly-clipboard");
+        let temp_dir = scratch_path("clipboard");
         tokio::fs::create_dir_all(&temp_dir).await?;
         
         let mut exported_paths = Vec::new();
@@ -201,6 +220,61 @@ impl ClipboardAdapter {
     }
 }
 
+impl ClipboardAdapter {
+    /// The VFS service, once `path` is known to be a file of at most `limit` bytes
+    async fn checked_size(&self, source_id: &str, path: &Path, limit: u64) -> Result<&Arc<VfsService>> {
+        let vfs = self.vfs_service.as_ref()
+            .context("VFS service not initialized")?;
+        
+        let stat = vfs.stat(source_id, path).await?;
+        if stat.is_dir {
+            return Err(VfsError::InvalidInput(format!("{} is a folder", path.display())).into());
+        }
+        if stat.size > limit {
+            return Err(VfsError::InvalidInput(format!(
+                "{} is too large to copy to the clipboard ({} bytes, limit {})",
+                path.display(), stat.size, limit
+            )).into());
+        }
+        Ok(vfs)
+    }
+    
+    /// Put a text file's contents on the clipboard; returns the bytes copied.
+    /// Fails for files that aren't UTF-8 or are over `MAX_CLIPBOARD_TEXT_SIZE`.
+    pub async fn copy_file_contents(&self, source_id: &str, path: &Path) -> Result<usize> {
+        let vfs = self.checked_size(source_id, path, MAX_CLIPBOARD_TEXT_SIZE).await?;
+        let data = vfs.read_file(source_id, path).await?;
+        let text = String::from_utf8(data).map_err(|_| {
+            VfsError::Unsupported(format!("{} is not a UTF-8 text file", path.display()))
+        })?;
+        
+        self.backend.write_text(&text).await?;
+        debug!("Copied contents of {:?} ({} bytes) to clipboard", path, text.len());
+        Ok(text.len())
+    }
+    
+    /// Decode an image file and put it on the clipboard as a PNG; returns its
+    /// width and height
+    pub async fn copy_image(&self, source_id: &str, path: &Path) -> Result<(u32, u32)> {
+        let vfs = self.checked_size(source_id, path, MAX_CLIPBOARD_IMAGE_SIZE).await?;
+        let data = vfs.read_file(source_id, path).await?;
+        
+        let display = path.display().to_string();
+        let (png, width, height) = tokio::task::spawn_blocking(move || -> Result<(Vec<u8>, u32, u32)> {
+            let image = image::load_from_memory(&data)
+                .map_err(|_| VfsError::Unsupported(format!("{} is not a supported image", display)))?;
+            let mut png = std::io::Cursor::new(Vec::new());
+            image.write_to(&mut png, image::ImageOutputFormat::Png)
+                .context("Failed to encode image")?;
+            Ok((png.into_inner(), image.width(), image.height()))
+        }).await??;
+        
+        self.backend.write_image(&png).await?;
+        debug!("Copied image {:?} ({}x{}) to clipboard", path, width, height);
+        Ok((width, height))
+    }
+}
+
 impl Default for ClipboardAdapter {
     fn default() -> Self {
         Self::new()
@@ -465,6 +539,97 @@ impl IClipboardService for ClipboardAdapter {
             Ok(())
         }
     }
+    
+    /// Write plain text to OS clipboard
+    async fn write_text_clipboard(&self, text: &str) -> Result<()> {
+        self.backend.write_text(text).await
+    }
+    
+    async fn write_image_clipboard(&self, png: &[u8]) -> Result<()> {
+        self.backend.write_image(png).await
+    }
+}
+
+/// The operating system's clipboard
+pub struct SystemClipboard;
+
+#[async_trait]
+impl ClipboardBackend for SystemClipboard {
+    async fn write_text(&self, text: &str) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            write_with("pbcopy", &[], text.as_bytes())
+        }
+        
+        #[cfg(target_os = "windows")]
+        {
+            write_windows_text_clipboard(text).await
+        }
+        
+        #[cfg(target_os = "linux")]
+        {
+            write_with("xclip", &["-selection", "clipboard"], text.as_bytes())
+                .or_else(|_| write_with("xsel", &["--clipboard", "--input"], text.as_bytes()))
+                .context("Failed to open clipboard (install xclip or xsel)")
+        }
+        
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        {
+            let _ = text;
+            warn!("Native clipboard not supported on this platform");
+            Ok(())
+        }
+    }
+    
+    async fn write_image(&self, png: &[u8]) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            write_macos_image_clipboard(png).await
+        }
+        
+        #[cfg(target_os = "windows")]
+        {
+            write_windows_image_clipboard(png).await
+        }
+        
+        #[cfg(target_os = "linux")]
+        {
+            write_with("xclip", &["-selection", "clipboard", "-t", "image/png"], png)
+                .context("Failed to open clipboard (install xclip)")
+        }
+        
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        {
+            let _ = png;
+            warn!("Native clipboard not supported on this platform");
+            Ok(())
+        }
+    }
+}
+
+/// Pipe `data` into a clipboard tool's stdin
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn write_with(program: &str, args: &[&str], data: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(data)?;
+    }
+    
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("{} exited with {}", program, status));
+    }
+    
+    debug!("Wrote {} bytes to clipboard", data.len());
+    Ok(())
 }
 
 // =============================================================================
@@ -597,6 +762,33 @@ return "ok"
     Ok(())
 }
 
+#[cfg(target_os = "macos")]
+async fn write_macos_image_clipboard(png: &[u8]) -> Result<()> {
+    use std::process::Command;
+    
+    // AppleScript reads the PNG from a file; it only needs to outlive the call
+    let file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    std::fs::write(file.path(), png)?;
+    let script = format!(
+        r#"set the clipboard to (read (POSIX file "{}") as «class PNGf»)"#,
+        file.path().display()
+    );
+    
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(&script)
+        .output()
+        .context("Failed to write image to macOS clipboard")?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to write image to clipboard: {}", stderr));
+    }
+    
+    debug!("Wrote {} byte image to macOS clipboard", png.len());
+    Ok(())
+}
+
 // =============================================================================
 // Windows Clipboard Implementation
 // =============================================================================
@@ -705,6 +897,85 @@ async fn write_windows_clipboard(paths: &[PathBuf]) -> Result<()> {
     Ok(())
 }
 
+#[cfg(target_os = "windows")]
+async fn write_windows_text_clipboard(text: &str) -> Result<()> {
+    // Passed through an environment variable so no quoting/escaping is needed
+    let text = text.to_string();
+    let output = tokio::task::spawn_blocking(move || {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+        
+        let mut cmd = Command::new("powershell.exe");
+        cmd.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-WindowStyle", "Hidden",
+            "-Command", "Set-Clipboard -Value $env:URSLY_CLIPBOARD_TEXT",
+        ]);
+        cmd.env("URSLY_CLIPBOARD_TEXT", &text);
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        
+        // Set CREATE_NO_WINDOW flag to prevent window creation
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        
+        cmd.output()
+    })
+    .await
+    .context("Failed to spawn blocking task for clipboard write")?
+    .context("Failed to write to Windows clipboard")?;
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to write to clipboard: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+async fn write_windows_image_clipboard(png: &[u8]) -> Result<()> {
+    let file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    std::fs::write(file.path(), png)?;
+    let path = file.path().to_path_buf();
+    
+    let output = tokio::task::spawn_blocking(move || {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+        
+        let mut cmd = Command::new("powershell.exe");
+        cmd.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-STA",
+            "-WindowStyle", "Hidden",
+            "-Command",
+            "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; \
+             $image = [System.Drawing.Image]::FromFile($env:URSLY_CLIPBOARD_IMAGE); \
+             [System.Windows.Forms.Clipboard]::SetImage($image); \
+             $image.Dispose()",
+        ]);
+        cmd.env("URSLY_CLIPBOARD_IMAGE", &path);
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        
+        cmd.output()
+    })
+    .await
+    .context("Failed to spawn blocking task for clipboard write")?
+    .context("Failed to write image to Windows clipboard")?;
+    drop(file);
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to write image to clipboard: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    
+    Ok(())
+}
+
 // =============================================================================
 // Linux Clipboard Implementation
 // =============================================================================
@@ -842,6 +1113,62 @@ mod tests {
         assert!(internal.is_none());
     }
     
+    /// Keeps whatever is written instead of touching the OS clipboard
+    #[derive(Default)]
+    struct RecordingBackend {
+        text: parking_lot::Mutex<Option<String>>,
+    }
+    
+    #[async_trait]
+    impl ClipboardBackend for RecordingBackend {
+        async fn write_text(&self, text: &str) -> Result<()> {
+            *self.text.lock() = Some(text.to_string());
+            Ok(())
+        }
+        
+        async fn write_image(&self, _png: &[u8]) -> Result<()> {
+            Ok(())
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_copy_file_contents_as_text() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "Scene 4, take 2 — circle take").unwrap();
+        std::fs::write(temp_dir.path().join("clip.bin"), [0xff, 0xfe, 0x00, 0x80]).unwrap();
+        
+        let vfs = Arc::new(VfsService::new().await.unwrap());
+        let source = vfs.add_local_source("Local".to_string(), temp_dir.path().to_path_buf()).await.unwrap();
+        let backend = Arc::new(RecordingBackend::default());
+        let clipboard = ClipboardAdapter::with_vfs_service(vfs).with_backend(backend.clone());
+        
+        clipboard.copy_file_contents(&source.id, Path::new("/notes.txt")).await.unwrap();
+        assert_eq!(backend.text.lock().as_deref(), Some("Scene 4, take 2 — circle take"));
+        
+        let err = clipboard.copy_file_contents(&source.id, Path::new("/clip.bin")).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<VfsError>(), Some(VfsError::Unsupported(_))), "{}", err);
+        let err = clipboard.copy_image(&source.id, Path::new("/notes.txt")).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<VfsError>(), Some(VfsError::Unsupported(_))), "{}", err);
+    }
+    
+    #[tokio::test]
+    async fn test_vfs_export_lands_in_scratch_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("A001.mov"), b"clip").unwrap();
+        let scratch = TempDir::new().unwrap();
+        
+        let vfs = Arc::new(VfsService::new().await.unwrap());
+        let source = vfs.add_local_source("Local".to_string(), temp_dir.path().to_path_buf()).await.unwrap();
+        let clipboard = ClipboardAdapter::with_vfs_service(vfs);
+        
+        let roomy = |_: &Path| Ok::<_, anyhow::Error>(u64::MAX);
+        crate::vfs::application::scratch::set_scratch_dir(scratch.path(), &roomy).await.unwrap();
+        
+        let exported = clipboard.export_vfs_to_temp(&source.id, &[PathBuf::from("/A001.mov")]).await.unwrap();
+        assert_eq!(exported, vec![scratch.path().join("clipboard/A001.mov")]);
+        assert_eq!(std::fs::read(&exported[0]).unwrap(), b"clip");
+    }
+    
     #[tokio::test]
     async fn test_file_name_extraction() {
         assert_eq!(ClipboardAdapter::file_name(Path::new("/path/to/file.txt")), "file.txt");
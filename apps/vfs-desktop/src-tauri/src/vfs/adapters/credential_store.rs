@@ -0,0 +1,109 @@
+//! Credential Store Adapters - OS keychain and in-memory secret storage
+//!
+//! `KeychainCredentialStore` goes through the `keyring` crate: the macOS
+//! Keychain, Windows Credential Manager, or the Secret Service (libsecret) on
+//! Linux. `MemoryCredentialStore` keeps secrets for the session only, for
+//! tests and machines without a keychain.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+use crate::vfs::ports::CredentialStore;
+
+/// Keychain service name secrets moved out of mount configs are filed under
+pub const DEFAULT_CREDENTIAL_SERVICE: &str = "io.ursly.vfs";
+
+/// Secrets kept in the operating system's keychain
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeychainCredentialStore;
+
+impl KeychainCredentialStore {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CredentialStore for KeychainCredentialStore {
+    fn store(&self, service: &str, account: &str, secret: &str) -> Result<()> {
+        keyring::Entry::new(service, account)
+            .and_then(|entry| entry.set_password(secret))
+            .with_context(|| format!("Failed to save {}/{} to the keychain", service, account))
+    }
+    
+    fn retrieve(&self, service: &str, account: &str) -> Result<Option<String>> {
+        let entry = keyring::Entry::new(service, account)
+            .with_context(|| format!("Invalid keychain entry {}/{}", service, account))?;
+        match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}/{} from the keychain", service, account)),
+        }
+    }
+    
+    fn delete(&self, service: &str, account: &str) -> Result<bool> {
+        let entry = keyring::Entry::new(service, account)
+            .with_context(|| format!("Invalid keychain entry {}/{}", service, account))?;
+        match entry.delete_password() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {}/{} from the keychain", service, account)),
+        }
+    }
+}
+
+/// Secrets kept in memory for the life of the process
+#[derive(Debug, Default)]
+pub struct MemoryCredentialStore {
+    secrets: RwLock<HashMap<(String, String), String>>,
+}
+
+impl MemoryCredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CredentialStore for MemoryCredentialStore {
+    fn store(&self, service: &str, account: &str, secret: &str) -> Result<()> {
+        self.secrets.write().insert((service.to_string(), account.to_string()), secret.to_string());
+        Ok(())
+    }
+    
+    fn retrieve(&self, service: &str, account: &str) -> Result<Option<String>> {
+        Ok(self.secrets.read().get(&(service.to_string(), account.to_string())).cloned())
+    }
+    
+    fn delete(&self, service: &str, account: &str) -> Result<bool> {
+        Ok(self.secrets.write().remove(&(service.to_string(), account.to_string())).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn round_trip(store: &dyn CredentialStore) {
+        let (service, account) = ("io.ursly.vfs.test", "round-trip");
+        
+        store.store(service, account, "first").unwrap();
+        store.store(service, account, "second").unwrap();
+        assert_eq!(store.retrieve(service, account).unwrap().as_deref(), Some("second"));
+        
+        assert!(store.delete(service, account).unwrap());
+        assert_eq!(store.retrieve(service, account).unwrap(), None);
+        assert!(!store.delete(service, account).unwrap());
+    }
+    
+    #[test]
+    fn test_memory_store_round_trip() {
+        round_trip(&MemoryCredentialStore::new());
+    }
+    
+    /// Needs a real keychain; run with `--features keychain-tests`
+    #[cfg(feature = "keychain-tests")]
+    #[test]
+    fn test_keychain_round_trip() {
+        round_trip(&KeychainCredentialStore::new());
+    }
+}
@@ -0,0 +1,125 @@
+//! Error Mapping - anyhow errors from adapters to typed `VfsError`s
+//!
+//! Adapters return `anyhow::Error`s with context layered over the underlying
+//! cause (an `io::Error` from the filesystem, an `opendal::Error` from object
+//! stores, or one of the domain errors). The code comes from the first cause
+//! in the chain that says what went wrong; the message is the whole chain.
+
+use std::fmt;
+use std::io::ErrorKind as IoErrorKind;
+
+use opendal::ErrorKind as ObjectErrorKind;
+
+use crate::vfs::domain::{
+    ArchiveNotRetrievedError, InsufficientSpaceError, QuotaExceededError, ReadOnlyError, VfsError,
+    VolumeBusyError,
+};
+
+impl From<&anyhow::Error> for VfsError {
+    fn from(err: &anyhow::Error) -> Self {
+        let message = format!("{:#}", err);
+        let typed = err.chain().find_map(|cause| classify(cause, &message));
+        typed.unwrap_or(VfsError::Other(message))
+    }
+}
+
+impl From<anyhow::Error> for VfsError {
+    fn from(err: anyhow::Error) -> Self {
+        VfsError::from(&err)
+    }
+}
+
+impl From<std::io::Error> for VfsError {
+    fn from(err: std::io::Error) -> Self {
+        VfsError::from(anyhow::Error::from(err))
+    }
+}
+
+/// Variant for one link in an error chain, if it is one we recognize
+fn classify(cause: &(dyn std::error::Error + 'static), message: &str) -> Option<VfsError> {
+    let message = message.to_string();
+    
+    if let Some(err) = cause.downcast_ref::<VfsError>() {
+        return Some(err.clone().with_message(message));
+    }
+    if cause.is::<ReadOnlyError>() {
+        return Some(VfsError::ReadOnly(message));
+    }
+    if cause.is::<InsufficientSpaceError>() {
+        return Some(VfsError::InsufficientSpace(message));
+    }
+    if cause.is::<QuotaExceededError>() {
+        return Some(VfsError::QuotaExceeded(message));
+    }
+    if cause.is::<VolumeBusyError>() {
+        return Some(VfsError::VolumeBusy(message));
+    }
+    if let Some(err) = cause.downcast_ref::<ArchiveNotRetrievedError>() {
+        return Some(VfsError::ArchiveNotRetrieved {
+            message,
+            estimated_retrieval_secs: err.estimated_retrieval_secs,
+        });
+    }
+    if cause.is::<tokio::time::error::Elapsed>() {
+        return Some(VfsError::Timeout(message));
+    }
+    if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+        return match err.kind() {
+            IoErrorKind::NotFound => Some(VfsError::NotFound(message)),
+            IoErrorKind::PermissionDenied => Some(VfsError::PermissionDenied(message)),
+            IoErrorKind::AlreadyExists => Some(VfsError::AlreadyExists(message)),
+            IoErrorKind::TimedOut => Some(VfsError::Timeout(message)),
+            IoErrorKind::InvalidInput => Some(VfsError::InvalidInput(message)),
+            IoErrorKind::Unsupported => Some(VfsError::Unsupported(message)),
+            _ => None,
+        };
+    }
+    if let Some(err) = cause.downcast_ref::<opendal::Error>() {
+        return match err.kind() {
+            ObjectErrorKind::NotFound => Some(VfsError::NotFound(message)),
+            ObjectErrorKind::PermissionDenied => Some(VfsError::PermissionDenied(message)),
+            ObjectErrorKind::AlreadyExists => Some(VfsError::AlreadyExists(message)),
+            ObjectErrorKind::Unsupported => Some(VfsError::Unsupported(message)),
+            ObjectErrorKind::ConfigInvalid | ObjectErrorKind::InvalidInput => Some(VfsError::InvalidInput(message)),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Turn any error into a `VfsError`, prefixing `context` to its message
+pub trait VfsResultExt<T> {
+    fn vfs_context(self, context: impl fmt::Display) -> Result<T, VfsError>;
+}
+
+impl<T, E: Into<anyhow::Error>> VfsResultExt<T> for Result<T, E> {
+    fn vfs_context(self, context: impl fmt::Display) -> Result<T, VfsError> {
+        self.map_err(|e| VfsError::from(e.into()).context(context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    
+    #[test]
+    fn test_codes_come_from_the_cause() {
+        let missing = std::fs::read("/definitely/not/here")
+            .context("Failed to read file")
+            .unwrap_err();
+        let err = VfsError::from(missing);
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert!(err.message().starts_with("Failed to read file: "));
+        
+        let busy = anyhow::Error::new(VolumeBusyError { operations: vec!["copy".to_string()] })
+            .context("Cannot eject");
+        assert_eq!(VfsError::from(busy).code(), "VOLUME_BUSY");
+        
+        let plain = VfsError::from(anyhow::anyhow!("something odd"));
+        assert_eq!(plain, VfsError::Other("something odd".to_string()));
+        
+        let json = serde_json::to_value(VfsError::not_initialized()).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "NOT_INITIALIZED", "message": "VFS not initialized" }));
+    }
+}
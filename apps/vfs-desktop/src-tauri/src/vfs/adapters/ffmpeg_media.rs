@@ -2,23 +2,41 @@
 //!
 //! Uses FFmpeg command-line tool for thumbnail generation and transcoding.
 //! This approach doesn't require FFmpeg Rust bindings and is more portable.
+//!
+//! Transcodes run with `-progress pipe:1`, so a job's progress follows the
+//! output time FFmpeg reports, and cancelling a job kills its FFmpeg.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
-use tokio::io::AsyncBufReadExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt};
 use tokio::process::Command;
+use tokio::sync::Notify;
 use tracing::{debug, error, info, warn};
 
+use crate::vfs::domain::VfsError;
 use crate::vfs::ports::{
-    IMediaService, MediaInfo, ThumbnailData, StreamFormat,
-    TranscodeQuality, TranscodeJob, TranscodeStatus,
+    IMediaService, MediaInfo, ThumbnailData, SpriteSheet, StreamFormat,
+    TranscodeQuality, TranscodeJob, TranscodeJobs, TranscodeStatus, TranscodeExecutor,
 };
 
+/// Most cells a sprite sheet may have
+pub const MAX_SPRITE_CELLS: u32 = 400;
+
+/// Narrowest and widest sprite cell, in pixels
+pub const MIN_SPRITE_WIDTH: u32 = 16;
+pub const MAX_SPRITE_WIDTH: u32 = 1920;
+
+/// Most peaks a waveform may have
+pub const MAX_WAVEFORM_WIDTH: u32 = 16384;
+
+/// Mono sample rate audio is decoded at for waveforms
+const WAVEFORM_SAMPLE_RATE: u32 = 8000;
+
 /// FFmpeg-based media service
 pub struct FfmpegMediaAdapter {
     /// Path to ffmpeg binary
@@ -31,7 +49,10 @@ pub struct FfmpegMediaAdapter {
     output_dir: PathBuf,
     
     /// Active transcoding jobs
-    jobs: Arc<RwLock<HashMap<String, TranscodeJob>>>,
+    jobs: TranscodeJobs,
+    
+    /// Kill switches of the transcodes currently running, by job id
+    running: Arc<Mutex<HashMap<String, Arc<Notify>>>>,
     
     /// Whether FFmpeg is available
     available: bool,
@@ -60,12 +81,13 @@ impl FfmpegMediaAdapter {
             ffprobe_path: ffprobe_path.unwrap_or_else(|| PathBuf::from("ffprobe")),
             output_dir,
             jobs: Arc::new(RwLock::new(HashMap::new())),
+            running: Arc::new(Mutex::new(HashMap::new())),
             available,
         })
     }
     
     /// Find FFmpeg binary
-    async fn find_ffmpeg() -> Option<PathBuf> {
+    pub(crate) async fn find_ffmpeg() -> Option<PathBuf> {
         // Common FFmpeg locations
         let candidates = vec![
             PathBuf::from("/opt/homebrew/bin/ffmpeg"),
@@ -84,7 +106,7 @@ impl FfmpegMediaAdapter {
     }
     
     /// Find FFprobe binary
-    async fn find_ffprobe() -> Option<PathBuf> {
+    pub(crate) async fn find_ffprobe() -> Option<PathBuf> {
         let candidates = vec![
             PathBuf::from("/opt/homebrew/bin/ffprobe"),
             PathBuf::from("/usr/local/bin/ffprobe"),
@@ -201,6 +223,202 @@ impl FfmpegMediaAdapter {
         
         Ok(playlist_path)
     }
+    
+    /// Sample `columns * rows` frames evenly across the video and tile them
+    /// into one PNG for a scrub-preview strip.
+    ///
+    /// Sheets are kept under the output directory, keyed by the file (path,
+    /// size and modification time) and the layout, so asking again is free.
+    pub async fn generate_sprite_sheet(
+        &self,
+        path: &Path,
+        columns: u32,
+        rows: u32,
+        thumb_width: u32,
+    ) -> Result<SpriteSheet> {
+        if !self.available {
+            return Err(anyhow::anyhow!("FFmpeg not available"));
+        }
+        if columns == 0 || rows == 0 || columns.saturating_mul(rows) > MAX_SPRITE_CELLS {
+            return Err(VfsError::InvalidInput(format!(
+                "Sprite sheet needs between 1 and {} cells, got {}x{}",
+                MAX_SPRITE_CELLS, columns, rows
+            )).into());
+        }
+        if !(MIN_SPRITE_WIDTH..=MAX_SPRITE_WIDTH).contains(&thumb_width) {
+            return Err(VfsError::InvalidInput(format!(
+                "Sprite cell width must be between {} and {} pixels",
+                MIN_SPRITE_WIDTH, MAX_SPRITE_WIDTH
+            )).into());
+        }
+        
+        let metadata = tokio::fs::metadata(path).await
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        let modified = metadata.modified().ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let key = xxhash_rust::xxh3::xxh3_64(format!(
+            "{}|{}|{}|{}x{}@{}",
+            path.display(), metadata.len(), modified, columns, rows, thumb_width
+        ).as_bytes());
+        
+        let cache_dir = self.output_dir.join("sprites");
+        let image_path = cache_dir.join(format!("{:016x}.png", key));
+        let timestamps_path = cache_dir.join(format!("{:016x}.json", key));
+        
+        if let (Ok(data), Ok(timestamps)) = (
+            tokio::fs::read(&image_path).await,
+            tokio::fs::read(&timestamps_path).await,
+        ) {
+            if let Ok(timestamps) = serde_json::from_slice(&timestamps) {
+                debug!("Sprite sheet cache hit for {:?}", path);
+                return Ok(SpriteSheet { data, columns, rows, thumb_width, timestamps });
+            }
+        }
+        
+        let info = self.get_media_info(path).await?;
+        let duration = info.duration
+            .filter(|d| *d > 0.0)
+            .ok_or_else(|| anyhow::anyhow!("Cannot read the duration of {:?}", path))?;
+        
+        // fps=N/duration emits frame k at k*duration/N, so the cells cover the
+        // clip evenly from the first frame
+        let cells = columns * rows;
+        let timestamps: Vec<f64> = (0..cells)
+            .map(|k| duration * k as f64 / cells as f64)
+            .collect();
+        let filter = format!(
+            "fps={}/{:.6},scale={}:-2,tile={}x{}",
+            cells, duration, thumb_width, columns, rows
+        );
+        
+        tokio::fs::create_dir_all(&cache_dir).await?;
+        let temp_path = cache_dir.join(format!("sprite_{}.png", uuid::Uuid::new_v4()));
+        
+        let output = Command::new(&self.ffmpeg_path)
+            .args([
+                "-i", path.to_str().unwrap(),
+                "-an",
+                "-vf", &filter,
+                "-frames:v", "1",
+                "-y",
+                temp_path.to_str().unwrap(),
+            ])
+            .output()
+            .await
+            .context("Failed to generate sprite sheet")?;
+        
+        if !output.status.success() {
+            tokio::fs::remove_file(&temp_path).await.ok();
+            return Err(anyhow::anyhow!(
+                "FFmpeg sprite sheet failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        
+        let data = tokio::fs::read(&temp_path).await?;
+        tokio::fs::rename(&temp_path, &image_path).await?;
+        tokio::fs::write(&timestamps_path, serde_json::to_vec(&timestamps)?).await?;
+        
+        Ok(SpriteSheet { data, columns, rows, thumb_width, timestamps })
+    }
+    
+    /// Peak amplitude (0.0 to 1.0) of the audio in each of `width` equal
+    /// slices of the file, for drawing a waveform at any height.
+    ///
+    /// Audio is decoded to mono PCM and streamed, so long files aren't held
+    /// in memory. Results are kept under the output directory, keyed by the
+    /// file (path, size and modification time) and the width.
+    pub async fn generate_waveform(&self, path: &Path, width: u32) -> Result<Vec<f32>> {
+        if !self.available {
+            return Err(anyhow::anyhow!("FFmpeg not available"));
+        }
+        if width == 0 || width > MAX_WAVEFORM_WIDTH {
+            return Err(VfsError::InvalidInput(format!(
+                "Waveform width must be between 1 and {}",
+                MAX_WAVEFORM_WIDTH
+            )).into());
+        }
+        
+        let metadata = tokio::fs::metadata(path).await
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        let modified = metadata.modified().ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let key = xxhash_rust::xxh3::xxh3_64(format!(
+            "{}|{}|{}|{}",
+            path.display(), metadata.len(), modified, width
+        ).as_bytes());
+        
+        let cache_dir = self.output_dir.join("waveforms");
+        let cache_path = cache_dir.join(format!("{:016x}.json", key));
+        if let Ok(cached) = tokio::fs::read(&cache_path).await {
+            if let Ok(peaks) = serde_json::from_slice(&cached) {
+                debug!("Waveform cache hit for {:?}", path);
+                return Ok(peaks);
+            }
+        }
+        
+        let info = self.get_media_info(path).await?;
+        let duration = info.duration
+            .filter(|d| *d > 0.0)
+            .ok_or_else(|| anyhow::anyhow!("Cannot read the duration of {:?}", path))?;
+        if info.audio_codec.is_none() {
+            return Err(VfsError::Unsupported(format!("{:?} has no audio", path)).into());
+        }
+        
+        let mut child = Command::new(&self.ffmpeg_path)
+            .args([
+                "-i", path.to_str().unwrap(),
+                "-vn",
+                "-ac", "1",
+                "-ar", &WAVEFORM_SAMPLE_RATE.to_string(),
+                "-f", "f32le",
+                "-",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start FFmpeg for waveform")?;
+        let mut stdout = child.stdout.take()
+            .ok_or_else(|| anyhow::anyhow!("FFmpeg produced no output stream"))?;
+        
+        // Samples are bucketed by their position in the expected total
+        let expected = ((duration * WAVEFORM_SAMPLE_RATE as f64).ceil() as u64).max(1);
+        let mut peaks = vec![0f32; width as usize];
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut pending = Vec::new();
+        let mut index = 0u64;
+        
+        loop {
+            let read = stdout.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            pending.extend_from_slice(&buffer[..read]);
+            
+            let whole = pending.len() - pending.len() % 4;
+            for sample in pending[..whole].chunks_exact(4) {
+                let amplitude = f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]).abs();
+                let bucket = ((index * width as u64) / expected).min(width as u64 - 1) as usize;
+                peaks[bucket] = peaks[bucket].max(amplitude.min(1.0));
+                index += 1;
+            }
+            pending.drain(..whole);
+        }
+        
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("FFmpeg failed to decode audio from {:?}", path));
+        }
+        
+        tokio::fs::create_dir_all(&cache_dir).await?;
+        tokio::fs::write(&cache_path, serde_json::to_vec(&peaks)?).await?;
+        
+        Ok(peaks)
+    }
 }
 
 #[async_trait]
@@ -453,6 +671,8 @@ impl IMediaService for FfmpegMediaAdapter {
         self.jobs.write().insert(job_id.clone(), job.clone());
         
         // Start transcoding in background
+        let duration = self.get_media_info(path).await.ok().and_then(|info| info.duration);
+        let run = self.start_run(&job_id, duration);
         let ffmpeg_path = self.ffmpeg_path.clone();
         let jobs = self.jobs.clone();
         let source_path = path.to_path_buf();
@@ -466,7 +686,7 @@ impl IMediaService for FfmpegMediaAdapter {
             
             let result = match format {
                 StreamFormat::HLS => {
-                    Self::transcode_hls_static(&ffmpeg_path, &source_path, &output_dir, quality).await
+                    Self::transcode_hls_static(&ffmpeg_path, &source_path, &output_dir, quality, &run).await
                 }
                 _ => Err(anyhow::anyhow!("Unsupported format: {:?}", format)),
             };
@@ -482,7 +702,9 @@ impl IMediaService for FfmpegMediaAdapter {
                 }
                 Err(e) => {
                     error!("Transcoding failed: {}", e);
-                    if let Some(job) = jobs.write().get_mut(&job_id_clone) {
+                    if let Some(job) = jobs.write().get_mut(&job_id_clone)
+                        .filter(|job| job.status != TranscodeStatus::Cancelled)
+                    {
                         job.status = TranscodeStatus::Failed;
                         job.error = Some(e.to_string());
                     }
@@ -503,8 +725,8 @@ impl IMediaService for FfmpegMediaAdapter {
     async fn cancel_transcode(&self, job_id: &str) -> Result<()> {
         if let Some(job) = self.jobs.write().get_mut(job_id) {
             job.status = TranscodeStatus::Cancelled;
-            // TODO: Kill the FFmpeg process
         }
+        self.kill_run(job_id);
         Ok(())
     }
     
@@ -524,13 +746,202 @@ impl IMediaService for FfmpegMediaAdapter {
     }
 }
 
+#[async_trait]
+impl TranscodeExecutor for FfmpegMediaAdapter {
+    fn jobs(&self) -> TranscodeJobs {
+        self.jobs.clone()
+    }
+    
+    async fn execute(
+        &self,
+        job_id: &str,
+        source: &Path,
+        output_dir: &Path,
+        format: StreamFormat,
+        quality: TranscodeQuality,
+    ) -> Result<PathBuf> {
+        if !self.available {
+            return Err(VfsError::Unsupported("FFmpeg not available".to_string()).into());
+        }
+        
+        let duration = self.get_media_info(source).await.ok().and_then(|info| info.duration);
+        let run = self.start_run(job_id, duration);
+        // Cancelled while the source was being probed
+        if self.jobs.read().get(job_id).is_some_and(|job| job.status == TranscodeStatus::Cancelled) {
+            anyhow::bail!("Transcode cancelled");
+        }
+        
+        match format {
+            StreamFormat::HLS => Self::transcode_hls_static(&self.ffmpeg_path, source, output_dir, quality, &run).await,
+            StreamFormat::MP4 => Self::transcode_mp4(&self.ffmpeg_path, source, output_dir, quality, &run).await,
+            _ => Err(VfsError::Unsupported(format!("Unsupported transcode format: {:?}", format)).into()),
+        }
+    }
+    
+    async fn cancel(&self, job_id: &str) -> Result<()> {
+        self.kill_run(job_id);
+        Ok(())
+    }
+}
+
+/// Percent done from one line of FFmpeg's `-progress` output, for an input
+/// `duration` seconds long. Stops short of 100 until FFmpeg says it is done.
+fn progress_percent(line: &str, duration: Option<f64>) -> Option<u8> {
+    let (key, value) = line.split_once('=')?;
+    match key.trim() {
+        "progress" if value.trim() == "end" => Some(100),
+        // Both are microseconds, whatever the older key's name says
+        "out_time_us" | "out_time_ms" => {
+            let duration = duration.filter(|d| *d > 0.0)?;
+            let micros: f64 = value.trim().parse().ok()?;
+            Some((micros / 1_000_000.0 / duration * 100.0).clamp(0.0, 99.0) as u8)
+        }
+        _ => None,
+    }
+}
+
+/// A transcode in flight: where its progress goes and how it is stopped
+struct TranscodeRun {
+    job_id: String,
+    jobs: TranscodeJobs,
+    /// Input length in seconds, when known
+    duration: Option<f64>,
+    kill: Arc<Notify>,
+    running: Arc<Mutex<HashMap<String, Arc<Notify>>>>,
+}
+
+impl TranscodeRun {
+    /// An FFmpeg command that reports progress on stdout
+    fn command(&self, ffmpeg_path: &Path) -> Command {
+        let mut cmd = Command::new(ffmpeg_path);
+        cmd.args(["-hide_banner", "-nostats", "-progress", "pipe:1"]);
+        cmd
+    }
+    
+    /// Run `cmd` to the end, recording progress on the job; fails with
+    /// FFmpeg's error output, or if the job is cancelled
+    async fn run(&self, mut cmd: Command) -> Result<()> {
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to start FFmpeg")?;
+        
+        let stdout = child.stdout.take().context("FFmpeg stdout not captured")?;
+        let mut stderr = child.stderr.take().context("FFmpeg stderr not captured")?;
+        // Drained alongside stdout so a chatty FFmpeg never blocks on a full pipe
+        let errors = tokio::spawn(async move {
+            let mut errors = String::new();
+            let _ = stderr.read_to_string(&mut errors).await;
+            errors
+        });
+        
+        let status = tokio::select! {
+            status = async {
+                let mut lines = tokio::io::BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(percent) = progress_percent(&line, self.duration) {
+                        if let Some(job) = self.jobs.write().get_mut(&self.job_id) {
+                            job.progress = percent;
+                        }
+                    }
+                }
+                child.wait().await
+            } => Some(status?),
+            _ = self.kill.notified() => None,
+        };
+        
+        let Some(status) = status else {
+            child.kill().await.ok();
+            anyhow::bail!("Transcode cancelled");
+        };
+        if !status.success() {
+            let errors = errors.await.unwrap_or_default();
+            return Err(anyhow::anyhow!("FFmpeg transcode failed: {}", errors.trim()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TranscodeRun {
+    fn drop(&mut self) {
+        self.running.lock().remove(&self.job_id);
+    }
+}
+
 impl FfmpegMediaAdapter {
+    /// Register job `job_id` as running so it can be killed
+    fn start_run(&self, job_id: &str, duration: Option<f64>) -> TranscodeRun {
+        let kill = Arc::new(Notify::new());
+        self.running.lock().insert(job_id.to_string(), kill.clone());
+        TranscodeRun {
+            job_id: job_id.to_string(),
+            jobs: self.jobs.clone(),
+            duration,
+            kill,
+            running: self.running.clone(),
+        }
+    }
+    
+    /// Kill job `job_id`'s FFmpeg, if it is running
+    fn kill_run(&self, job_id: &str) {
+        if let Some(kill) = self.running.lock().get(job_id) {
+            // Stored if the run isn't waiting yet, so it can't be missed
+            kill.notify_one();
+        }
+    }
+    
+    /// H.264/AAC MP4 scaled to the quality's height, e.g. an editing proxy.
+    /// Written to `<output_dir>/<source stem>.mp4`.
+    async fn transcode_mp4(
+        ffmpeg_path: &Path,
+        source: &Path,
+        output_dir: &Path,
+        quality: TranscodeQuality,
+        run: &TranscodeRun,
+    ) -> Result<PathBuf> {
+        let (height, crf) = match quality {
+            TranscodeQuality::Low => (480, "28"),
+            TranscodeQuality::Medium => (720, "25"),
+            TranscodeQuality::High | TranscodeQuality::Adaptive => (1080, "23"),
+            TranscodeQuality::Ultra => (2160, "20"),
+        };
+        
+        tokio::fs::create_dir_all(output_dir).await?;
+        let stem = source.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "output".to_string());
+        let output_path = output_dir.join(format!("{}.mp4", stem));
+        
+        let mut cmd = run.command(ffmpeg_path);
+        cmd.arg("-i").arg(source)
+            .args([
+                "-c:v", "libx264",
+                "-preset", "fast",
+                "-crf", crf,
+                "-pix_fmt", "yuv420p",
+                // Never upscale; keep the width even for H.264
+                "-vf", &format!("scale=-2:'min({},ih)'", height),
+                "-c:a", "aac",
+                "-b:a", "192k",
+                "-movflags", "+faststart",
+                "-y",
+            ])
+            .arg(&output_path);
+        run.run(cmd).await.context("FFmpeg MP4 transcode failed")?;
+        
+        Ok(output_path)
+    }
+    
     /// Static version for background task
     async fn transcode_hls_static(
         ffmpeg_path: &Path,
         source: &Path,
         output_dir: &Path,
         quality: TranscodeQuality,
+        run: &TranscodeRun,
     ) -> Result<PathBuf> {
         let (resolution, video_bitrate, audio_bitrate) = match quality {
             TranscodeQuality::Low => ("640x360", "800k", "96k"),
@@ -545,30 +956,23 @@ impl FfmpegMediaAdapter {
         let playlist_path = output_dir.join("playlist.m3u8");
         let segment_pattern = output_dir.join("segment_%03d.ts");
         
-        let status = Command::new(ffmpeg_path)
-            .args([
-                "-i", source.to_str().unwrap(),
-                "-c:v", "libx264",
-                "-preset", "fast",
-                "-b:v", video_bitrate,
-                "-vf", &format!("scale={}", resolution),
-                "-c:a", "aac",
-                "-b:a", audio_bitrate,
-                "-f", "hls",
-                "-hls_time", "6",
-                "-hls_list_size", "0",
-                "-hls_segment_filename", segment_pattern.to_str().unwrap(),
-                "-y",
-                playlist_path.to_str().unwrap(),
-            ])
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await?;
-        
-        if !status.success() {
-            return Err(anyhow::anyhow!("FFmpeg transcoding failed"));
-        }
+        let mut cmd = run.command(ffmpeg_path);
+        cmd.args([
+            "-i", source.to_str().unwrap(),
+            "-c:v", "libx264",
+            "-preset", "fast",
+            "-b:v", video_bitrate,
+            "-vf", &format!("scale={}", resolution),
+            "-c:a", "aac",
+            "-b:a", audio_bitrate,
+            "-f", "hls",
+            "-hls_time", "6",
+            "-hls_list_size", "0",
+            "-hls_segment_filename", segment_pattern.to_str().unwrap(),
+            "-y",
+            playlist_path.to_str().unwrap(),
+        ]);
+        run.run(cmd).await.context("FFmpeg transcoding failed")?;
         
         Ok(playlist_path)
     }
@@ -592,6 +996,21 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_progress_follows_output_time() {
+        let duration = Some(20.0);
+        assert_eq!(progress_percent("out_time_us=5000000", duration), Some(25));
+        assert_eq!(progress_percent("out_time_ms=10000000", duration), Some(50));
+        // Never reports done before FFmpeg does
+        assert_eq!(progress_percent("out_time_us=20000000", duration), Some(99));
+        assert_eq!(progress_percent("progress=continue", duration), None);
+        assert_eq!(progress_percent("progress=end", duration), Some(100));
+        
+        assert_eq!(progress_percent("out_time_us=N/A", duration), None);
+        assert_eq!(progress_percent("out_time_us=5000000", None), None);
+        assert_eq!(progress_percent("frame=120", duration), None);
+    }
+    
     #[test]
     fn test_get_quality_params_low() {
         // Test quality parameter mapping
@@ -617,6 +1036,79 @@ mod tests {
         assert_eq!(abr, "256k");
     }
     
+    #[tokio::test]
+    async fn test_sprite_sheet_tiles_evenly_spaced_frames() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = FfmpegMediaAdapter::new(temp_dir.path().join("out")).await.unwrap();
+        if !adapter.is_available() {
+            println!("FFmpeg is NOT available - skipping sprite sheet test");
+            return;
+        }
+        
+        let clip = temp_dir.path().join("clip.mp4");
+        let status = Command::new(&adapter.ffmpeg_path)
+            .args([
+                "-f", "lavfi", "-i", "testsrc=duration=2:size=160x120:rate=10",
+                "-pix_fmt", "yuv420p", "-y",
+                clip.to_str().unwrap(),
+            ])
+            .output()
+            .await
+            .unwrap()
+            .status;
+        assert!(status.success());
+        
+        let sheet = adapter.generate_sprite_sheet(&clip, 4, 2, 80).await.unwrap();
+        assert_eq!(sheet.timestamps.len(), 8);
+        assert!(sheet.timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(*sheet.timestamps.last().unwrap() < 2.0);
+        
+        // A PNG whose header says 4 cells of 80px across and 2 of 60px down
+        assert_eq!(&sheet.data[..8], b"\x89PNG\r\n\x1a\n");
+        let width = u32::from_be_bytes(sheet.data[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(sheet.data[20..24].try_into().unwrap());
+        assert_eq!((width, height), (320, 120));
+        
+        // Same file and layout comes from the cache
+        let again = adapter.generate_sprite_sheet(&clip, 4, 2, 80).await.unwrap();
+        assert_eq!(again.data, sheet.data);
+        assert_eq!(again.timestamps, sheet.timestamps);
+        
+        assert!(adapter.generate_sprite_sheet(&clip, 0, 2, 80).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_waveform_peaks_of_sine_wave() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = FfmpegMediaAdapter::new(temp_dir.path().join("out")).await.unwrap();
+        if !adapter.is_available() {
+            println!("FFmpeg is NOT available - skipping waveform test");
+            return;
+        }
+        
+        let tone = temp_dir.path().join("tone.wav");
+        let status = Command::new(&adapter.ffmpeg_path)
+            .args([
+                "-f", "lavfi", "-i", "sine=frequency=440:duration=2",
+                "-y",
+                tone.to_str().unwrap(),
+            ])
+            .output()
+            .await
+            .unwrap()
+            .status;
+        assert!(status.success());
+        
+        let peaks = adapter.generate_waveform(&tone, 200).await.unwrap();
+        assert_eq!(peaks.len(), 200);
+        assert!(peaks.iter().all(|p| (0.0..=1.0).contains(p)));
+        // A steady tone fills nearly every slice
+        assert!(peaks.iter().filter(|p| **p > 0.01).count() > 190);
+        
+        assert_eq!(adapter.generate_waveform(&tone, 200).await.unwrap(), peaks);
+        assert!(adapter.generate_waveform(&tone, 0).await.is_err());
+    }
+    
     #[tokio::test]
     async fn test_output_dir_creation() {
         let temp_dir = TempDir::new().unwrap();
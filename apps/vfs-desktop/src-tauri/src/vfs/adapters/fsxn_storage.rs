@@ -15,7 +15,8 @@ use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tracing::{debug, info, warn};
 
-use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier};
+use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier, VfsError};
+use crate::vfs::platform::{copy_dir_tree, ensure_dir_all};
 use crate::vfs::ports::{
     StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions
 };
@@ -168,28 +169,6 @@ impl FsxOntapAdapter {
             0
         }
     }
-    
-    /// Copy directory recursively
-    async fn copy_dir_recursive(&self, from: &Path, to: &Path, options: &CopyOptions) -> Result<()> {
-        fs::create_dir_all(to).await?;
-        
-        let mut entries = fs::read_dir(from).await?;
-        
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
-            let dest_path = to.join(entry.file_name());
-            
-            let metadata = entry.metadata().await?;
-            
-            if metadata.is_dir() {
-                Box::pin(self.copy_dir_recursive(&entry_path, &dest_path, options)).await?;
-            } else {
-                fs::copy(&entry_path, &dest_path).await?;
-            }
-        }
-        
-        Ok(())
-    }
 }
 
 #[async_trait]
@@ -474,7 +453,7 @@ impl IFileOperations for FsxOntapAdapter {
     
     async fn mkdir_p(&self, path: &Path) -> Result<()> {
         let full_path = self.resolve_path(path);
-        fs::create_dir_all(&full_path).await?;
+        ensure_dir_all(&full_path).await?;
         Ok(())
     }
     
@@ -499,7 +478,7 @@ impl IFileOperations for FsxOntapAdapter {
         let to_path = self.resolve_path(to);
         
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists"));
+            return Err(VfsError::AlreadyExists("Destination already exists".to_string()).into());
         }
         
         if let Some(parent) = to_path.parent() {
@@ -512,7 +491,7 @@ impl IFileOperations for FsxOntapAdapter {
             if !options.recursive {
                 return Err(anyhow::anyhow!("Cannot copy directory without recursive option"));
             }
-            self.copy_dir_recursive(&from_path, &to_path, &options).await?;
+            copy_dir_tree(&from_path, &to_path, &options).await?;
         } else {
             fs::copy(&from_path, &to_path).await?;
         }
@@ -525,7 +504,7 @@ impl IFileOperations for FsxOntapAdapter {
         let to_path = self.resolve_path(to);
         
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists"));
+            return Err(VfsError::AlreadyExists("Destination already exists".to_string()).into());
         }
         
         if let Some(parent) = to_path.parent() {
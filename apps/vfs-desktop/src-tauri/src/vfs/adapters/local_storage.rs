@@ -6,11 +6,14 @@ use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use tracing::{debug, error, info, warn};
 
-use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier};
-use crate::vfs::ports::StorageAdapter;
+use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier, VfsError};
+use crate::vfs::ports::{StorageAdapter, ListOptions, page_by_offset};
 use crate::vfs::ports::{IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions};
+use crate::vfs::platform::{copy_attributes, copy_dir_tree, copy_file, ensure_dir_all, is_hidden, lock_for_write, reset_copied_mode, try_lock_for_write, FileWriteLock};
 
 /// Local filesystem storage adapter
 pub struct LocalStorageAdapter {
@@ -26,6 +29,14 @@ impl LocalStorageAdapter {
         Self { base_path, name }
     }
     
+    /// Take the write lock on `path` for an interactive edit, failing with
+    /// `VfsError::Locked` instead of waiting if another writer has it. Writes
+    /// through this adapter wait for the lock, so don't write `path` while
+    /// holding it.
+    pub fn try_lock(&self, path: &Path) -> Result<FileWriteLock> {
+        try_lock_for_write(&self.resolve_path(path))
+    }
+    
     /// Resolve a VFS path to an actual filesystem path
     /// Handles cross-platform path separators (Unix / and Windows \)
     fn resolve_path(&self, path: &Path) -> PathBuf {
@@ -50,6 +61,48 @@ impl LocalStorageAdapter {
         // Join with base path - PathBuf::join handles platform separators
         self.base_path.join(normalized)
     }
+    
+    /// The listing entry for `entry` in the directory at `path`, or `None` if
+    /// `options` filter it out
+    async fn list_entry(path: &Path, entry: &fs::DirEntry, options: &ListOptions) -> Result<Option<VirtualFile>> {
+        let name = entry.file_name().to_string_lossy().to_string();
+        
+        // Filter before stat'ing so hidden/non-matching entries cost nothing
+        if !options.includes_name(&name, false) {
+            return Ok(None);
+        }
+        
+        let metadata = entry.metadata().await?;
+        let hidden = is_hidden(&name, &metadata);
+        if hidden && !options.show_hidden {
+            return Ok(None);
+        }
+        let file_path = path.join(&name);
+        
+        let mut vfile = VirtualFile::new(
+            name,
+            file_path,
+            metadata.len(),
+            metadata.is_dir(),
+        );
+        vfile.is_hidden = Some(hidden);
+        
+        // Local files are always "hot" (immediately accessible)
+        vfile.tier_status = TierStatus {
+            current_tier: StorageTier::Hot,
+            is_cached: true,
+            can_warm: false,
+            retrieval_time_estimate: Some(0),
+        };
+        
+        vfile.transcodable = vfile.can_transcode();
+        
+        if let Ok(modified) = metadata.modified() {
+            vfile.last_modified = modified;
+        }
+        
+        Ok(Some(vfile))
+    }
 }
 
 #[async_trait]
@@ -67,6 +120,10 @@ impl StorageAdapter for LocalStorageAdapter {
     }
     
     async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        self.list_files_with(path, &ListOptions::default()).await
+    }
+    
+    async fn list_files_with(&self, path: &Path, options: &ListOptions) -> Result<Vec<VirtualFile>> {
         let full_path = self.resolve_path(path);
         debug!("Listing files at: {:?}", full_path);
         
@@ -76,44 +133,104 @@ impl StorageAdapter for LocalStorageAdapter {
             .with_context(|| format!("Failed to read directory: {:?}", full_path))?;
         
         while let Some(entry) = entries.next_entry().await? {
-            let metadata = entry.metadata().await?;
+            if let Some(vfile) = Self::list_entry(path, &entry, options).await? {
+                files.push(vfile);
+            }
+        }
+        
+        files.sort_by(|a, b| options.compare(a, b));
+        
+        Ok(files)
+    }
+    
+    async fn list_files_batched(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        batch_size: usize,
+    ) -> Result<BoxStream<'static, Result<Vec<VirtualFile>>>> {
+        let full_path = self.resolve_path(path);
+        debug!("Listing files in batches at: {:?}", full_path);
+        
+        let entries = fs::read_dir(&full_path)
+            .await
+            .with_context(|| format!("Failed to read directory: {:?}", full_path))?;
+        let (path, options, batch_size) = (path.to_path_buf(), options.clone(), batch_size.max(1));
+        
+        // The state is `None` once the directory has been read to the end
+        Ok(futures::stream::try_unfold(Some(entries), move |entries| {
+            let (path, options) = (path.clone(), options.clone());
+            async move {
+                let Some(mut entries) = entries else {
+                    return Ok::<_, anyhow::Error>(None);
+                };
+                let mut batch = Vec::with_capacity(batch_size);
+                while batch.len() < batch_size {
+                    let Some(entry) = entries.next_entry().await? else {
+                        return Ok((!batch.is_empty()).then_some((batch, None)));
+                    };
+                    if let Some(vfile) = Self::list_entry(&path, &entry, &options).await? {
+                        batch.push(vfile);
+                    }
+                }
+                Ok(Some((batch, Some(entries))))
+            }
+        }).boxed())
+    }
+    
+    async fn list_files_paged(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<VirtualFile>, Option<String>)> {
+        // Size/date ordering needs every entry's metadata anyway
+        if options.needs_metadata() {
+            let files = self.list_files_with(path, options).await?;
+            return page_by_offset(files, cursor.as_deref(), limit);
+        }
+        
+        let full_path = self.resolve_path(path);
+        debug!("Listing page of files at: {:?} (cursor: {:?})", full_path, cursor);
+        
+        // Collect names and types only (no stat) so the order is stable across pages;
+        // full metadata is fetched just for the entries on the requested page
+        let mut stubs = Vec::new();
+        let mut entries = fs::read_dir(&full_path)
+            .await
+            .with_context(|| format!("Failed to read directory: {:?}", full_path))?;
+        
+        while let Some(entry) = entries.next_entry().await? {
             let name = entry.file_name().to_string_lossy().to_string();
-            let file_path = path.join(&name);
-            
-            let mut vfile = VirtualFile::new(
-                name,
-                file_path,
-                metadata.len(),
-                metadata.is_dir(),
-            );
-            
-            // Local files are always "hot" (immediately accessible)
-            vfile.tier_status = TierStatus {
-                current_tier: StorageTier::Hot,
-                is_cached: true,
-                can_warm: false,
-                retrieval_time_estimate: Some(0),
-            };
-            
-            vfile.transcodable = vfile.can_transcode();
-            
-            if let Ok(modified) = metadata.modified() {
-                vfile.last_modified = modified;
+            if !options.includes_name(&name, false) {
+                continue;
             }
-            
-            files.push(vfile);
+            // Attribute-hidden entries need a stat to spot; dot-prefixed ones are already gone
+            if !options.show_hidden && matches!(entry.metadata().await, Ok(metadata) if is_hidden(&name, &metadata)) {
+                continue;
+            }
+            let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+            stubs.push(VirtualFile::new(name.clone(), path.join(&name), 0, is_dir));
         }
         
-        // Sort: directories first, then by name
-        files.sort_by(|a, b| {
-            match (a.is_directory, b.is_directory) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        stubs.sort_by(|a, b| options.compare(a, b));
+        
+        let (page, next_cursor) = page_by_offset(stubs, cursor.as_deref(), limit)?;
+        
+        let mut files = Vec::with_capacity(page.len());
+        for stub in page {
+            match self.get_metadata(&stub.path).await {
+                Ok(mut vfile) => {
+                    vfile.transcodable = vfile.can_transcode();
+                    files.push(vfile);
+                }
+                // Entry vanished between read_dir and stat
+                Err(e) => debug!("Skipping {}: {}", stub.name, e),
             }
-        });
+        }
         
-        Ok(files)
+        Ok((files, next_cursor))
     }
     
     async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
@@ -151,6 +268,7 @@ impl StorageAdapter for LocalStorageAdapter {
             fs::create_dir_all(parent).await?;
         }
         
+        let _lock = lock_for_write(&full_path).await?;
         fs::write(&full_path, data)
             .await
             .with_context(|| format!("Failed to write file: {:?}", full_path))
@@ -173,6 +291,7 @@ impl StorageAdapter for LocalStorageAdapter {
             metadata.len(),
             metadata.is_dir(),
         );
+        vfile.is_hidden = Some(is_hidden(&vfile.name, &metadata));
         
         vfile.tier_status = TierStatus {
             current_tier: StorageTier::Hot,
@@ -331,11 +450,47 @@ impl IFileOperations for LocalStorageAdapter {
             fs::create_dir_all(parent).await?;
         }
         
+        let _lock = lock_for_write(&full_path).await?;
         fs::write(&full_path, data)
             .await
             .with_context(|| format!("Failed to write file: {:?}", full_path))
     }
     
+    async fn write_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        let full_path = self.resolve_path(path);
+        debug!("Atomically writing file: {:?}", full_path);
+        
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        let _lock = lock_for_write(&full_path).await?;
+        write_via_temp(&full_path, data, || Ok(())).await
+    }
+    
+    async fn write_stream(&self, path: &Path, mut chunks: BoxStream<'_, Result<Vec<u8>>>) -> Result<u64> {
+        let full_path = self.resolve_path(path);
+        debug!("Streaming write to file: {:?}", full_path);
+        
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        let _lock = lock_for_write(&full_path).await?;
+        let mut file = fs::File::create(&full_path)
+            .await
+            .with_context(|| format!("Failed to create file: {:?}", full_path))?;
+        
+        let mut written = 0u64;
+        while let Some(chunk) = chunks.try_next().await? {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        
+        Ok(written)
+    }
+    
     async fn append(&self, path: &Path, data: &[u8]) -> Result<()> {
         let full_path = self.resolve_path(path);
         debug!("Appending to file: {:?}", full_path);
@@ -345,6 +500,7 @@ impl IFileOperations for LocalStorageAdapter {
             fs::create_dir_all(parent).await?;
         }
         
+        let _lock = lock_for_write(&full_path).await?;
         let mut file = fs::OpenOptions::new()
             .create(true)
             .append(true)
@@ -401,7 +557,7 @@ impl IFileOperations for LocalStorageAdapter {
         let full_path = self.resolve_path(path);
         debug!("Creating directory recursively: {:?}", full_path);
         
-        fs::create_dir_all(&full_path)
+        ensure_dir_all(&full_path)
             .await
             .with_context(|| format!("Failed to create directory: {:?}", full_path))
     }
@@ -441,7 +597,7 @@ impl IFileOperations for LocalStorageAdapter {
         
         // Check if destination exists
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists: {:?}", to_path));
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {:?}", to_path)).into());
         }
         
         // Ensure parent directory exists
@@ -455,12 +611,14 @@ impl IFileOperations for LocalStorageAdapter {
             if !options.recursive {
                 return Err(anyhow::anyhow!("Cannot copy directory without recursive option"));
             }
-            self.copy_dir_recursive(&from_path, &to_path, &options).await?;
+            copy_dir_tree(&from_path, &to_path, &options).await?;
         } else {
-            fs::copy(&from_path, &to_path).await?;
+            copy_file(&from_path, &to_path).await?;
             
             if options.preserve_attributes {
-                self.preserve_attributes(&from_path, &to_path).await?;
+                copy_attributes(&from_path, &to_path).await?;
+            } else {
+                reset_copied_mode(&from_path, &to_path).await?;
             }
         }
         
@@ -474,7 +632,7 @@ impl IFileOperations for LocalStorageAdapter {
         
         // Check if destination exists
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists: {:?}", to_path));
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {:?}", to_path)).into());
         }
         
         // Ensure parent directory exists
@@ -705,6 +863,54 @@ impl IFileOperations for LocalStorageAdapter {
     }
 }
 
+/// Write `data` to a hidden temp file next to `target`, then rename it over
+/// `target`. The rename is atomic on the same filesystem, so a crash leaves
+/// either the old file or the new one. A symlinked `target` keeps its link:
+/// the file it points to is the one replaced. `before_rename` runs once the
+/// temp file is synced; if it fails the temp file is removed and `target` is
+/// untouched.
+async fn write_via_temp(
+    target: &Path,
+    data: &[u8],
+    before_rename: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    let resolved = fs::canonicalize(target).await.ok();
+    let target = resolved.as_deref().unwrap_or(target);
+    let file_name = target.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Cannot write to {:?}", target))?
+        .to_string_lossy();
+    let temp_path = target.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name,
+        uuid::Uuid::new_v4().simple()
+    ));
+    
+    let result = async {
+        let mut file = fs::File::create(&temp_path)
+            .await
+            .with_context(|| format!("Failed to create temp file: {:?}", temp_path))?;
+        file.write_all(data).await?;
+        file.sync_all().await?;
+        drop(file);
+        
+        // Keep the permissions of the file being replaced
+        if let Ok(metadata) = fs::metadata(target).await {
+            fs::set_permissions(&temp_path, metadata.permissions()).await?;
+        }
+        
+        before_rename()?;
+        
+        fs::rename(&temp_path, target)
+            .await
+            .with_context(|| format!("Failed to replace file: {:?}", target))
+    }.await;
+    
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path).await;
+    }
+    result
+}
+
 // =============================================================================
 // Helper methods for LocalStorageAdapter
 // =============================================================================
@@ -754,60 +960,6 @@ impl LocalStorageAdapter {
             0
         }
     }
-    
-    /// Copy directory recursively
-    async fn copy_dir_recursive(&self, from: &Path, to: &Path, options: &CopyOptions) -> Result<()> {
-        fs::create_dir_all(to).await?;
-        
-        let mut entries = fs::read_dir(from).await?;
-        
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
-            let dest_path = to.join(entry.file_name());
-            
-            let metadata = entry.metadata().await?;
-            
-            if metadata.is_dir() {
-                Box::pin(self.copy_dir_recursive(&entry_path, &dest_path, options)).await?;
-            } else if metadata.is_symlink() && !options.follow_symlinks {
-                let target = fs::read_link(&entry_path).await?;
-                #[cfg(unix)]
-                tokio::fs::symlink(&target, &dest_path).await?;
-                #[cfg(windows)]
-                tokio::fs::symlink_file(&target, &dest_path).await?;
-            } else {
-                fs::copy(&entry_path, &dest_path).await?;
-            }
-            
-            if options.preserve_attributes {
-                self.preserve_attributes(&entry_path, &dest_path).await.ok();
-            }
-        }
-        
-        Ok(())
-    }
-    
-    /// Preserve file attributes (mode, times)
-    async fn preserve_attributes(&self, from: &Path, to: &Path) -> Result<()> {
-        let metadata = fs::metadata(from).await?;
-        
-        // Preserve permissions
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let perms = std::fs::Permissions::from_mode(metadata.permissions().mode());
-            fs::set_permissions(to, perms).await?;
-        }
-        
-        // Preserve times
-        if let (Ok(mtime), Ok(atime)) = (metadata.modified(), metadata.accessed()) {
-            let mtime = filetime::FileTime::from_system_time(mtime);
-            let atime = filetime::FileTime::from_system_time(atime);
-            filetime::set_file_times(to, atime, mtime)?;
-        }
-        
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -837,6 +989,26 @@ mod tests {
         assert_eq!(files[1].name, "test.txt");
     }
     
+    #[tokio::test]
+    async fn test_show_hidden_filters_dotfiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = LocalStorageAdapter::new(temp_dir.path().to_path_buf(), "Test".to_string());
+        std::fs::write(temp_dir.path().join(".config"), "theme=dark").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "hello").unwrap();
+        
+        let hidden = ListOptions { show_hidden: false, ..ListOptions::default() };
+        let files = adapter.list_files_with(Path::new("/"), &hidden).await.unwrap();
+        assert_eq!(files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(), vec!["notes.txt"]);
+        let (page, _) = adapter.list_files_paged(Path::new("/"), &hidden, None, 10).await.unwrap();
+        assert_eq!(page.len(), 1);
+        
+        let shown = ListOptions { show_hidden: true, ..ListOptions::default() };
+        let files = adapter.list_files_with(Path::new("/"), &shown).await.unwrap();
+        let config = files.iter().find(|f| f.name == ".config").unwrap();
+        assert_eq!(config.is_hidden, Some(true));
+        assert_eq!(files.iter().find(|f| f.name == "notes.txt").unwrap().is_hidden, Some(false));
+    }
+    
     #[tokio::test]
     async fn test_local_adapter_read_write() {
         let temp_dir = TempDir::new().unwrap();
@@ -852,6 +1024,46 @@ mod tests {
         assert_eq!(read_data, data);
     }
     
+    #[tokio::test]
+    async fn test_interrupted_atomic_write_keeps_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("notes.txt");
+        std::fs::write(&target, "original").unwrap();
+        
+        let result = write_via_temp(&target, b"replacement", || {
+            Err(anyhow::anyhow!("simulated crash before rename"))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "original");
+        
+        // No temp file left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+        
+        let adapter = LocalStorageAdapter::new(temp_dir.path().to_path_buf(), "Test".to_string());
+        adapter.write_atomic(Path::new("/notes.txt"), b"replacement").await.unwrap();
+        adapter.append_atomic(Path::new("/notes.txt"), b" + more").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "replacement + more");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_atomic_write_through_symlink_keeps_the_link() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("shared")).unwrap();
+        let real = temp_dir.path().join("shared/settings.conf");
+        std::fs::write(&real, "old").unwrap();
+        let link = temp_dir.path().join("settings.conf");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        
+        let adapter = LocalStorageAdapter::new(temp_dir.path().to_path_buf(), "Test".to_string());
+        adapter.write_atomic(Path::new("/settings.conf"), b"new").await.unwrap();
+        
+        assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&real).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(temp_dir.path().join("shared")).unwrap().count(), 1);
+    }
+    
     #[tokio::test]
     async fn test_local_adapter_tier_status() {
         let temp_dir = TempDir::new().unwrap();
@@ -903,6 +1115,28 @@ mod tests {
         assert!(IFileOperations::is_dir(&adapter, Path::new("/a/b/c/d")).await.unwrap());
     }
     
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_mkdir_p_of_overlapping_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = std::sync::Arc::new(LocalStorageAdapter::new(temp_dir.path().to_path_buf(), "Test".to_string()));
+        
+        for round in 0..20 {
+            let (a, b) = (adapter.clone(), adapter.clone());
+            let day = format!("/shoot{}/day1", round);
+            let (left, right) = (Path::new(&day).join("cam_a/audio"), Path::new(&day).join("cam_b"));
+            let (first, second) = tokio::join!(
+                tokio::spawn(async move { IFileOperations::mkdir_p(a.as_ref(), &left).await }),
+                tokio::spawn(async move { IFileOperations::mkdir_p(b.as_ref(), &right).await }),
+            );
+            first.unwrap().unwrap();
+            second.unwrap().unwrap();
+            
+            let day_dir = temp_dir.path().join(format!("shoot{}/day1", round));
+            assert!(day_dir.join("cam_a/audio").is_dir());
+            assert!(day_dir.join("cam_b").is_dir());
+        }
+    }
+    
     #[tokio::test]
     async fn test_file_ops_rename() {
         let temp_dir = TempDir::new().unwrap();
@@ -948,6 +1182,39 @@ mod tests {
         assert_eq!(content, b"hello world");
     }
     
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_ops_copy_preserves_mode_only_when_asked() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = LocalStorageAdapter::new(temp_dir.path().to_path_buf(), "Test".to_string());
+        let private = temp_dir.path().join("private.key");
+        std::fs::write(&private, b"secret").unwrap();
+        std::fs::set_permissions(&private, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let mtime = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(&private, mtime).unwrap();
+        let mode = |name: &str| std::fs::metadata(temp_dir.path().join(name)).unwrap().permissions().mode() & 0o777;
+        
+        let preserve = CopyOptions { preserve_attributes: true, ..Default::default() };
+        IFileOperations::copy(&adapter, Path::new("/private.key"), Path::new("/kept.key"), preserve).await.unwrap();
+        assert_eq!(mode("kept.key"), 0o600);
+        let kept = std::fs::metadata(temp_dir.path().join("kept.key")).unwrap();
+        assert_eq!(filetime::FileTime::from_last_modification_time(&kept), mtime);
+        
+        // Without preserving, the umask applies but no bit is ever added
+        IFileOperations::copy(&adapter, Path::new("/private.key"), Path::new("/fresh.key"), CopyOptions::default())
+            .await.unwrap();
+        assert_eq!(mode("fresh.key"), 0o600 & !crate::vfs::platform::umask());
+        
+        let script = temp_dir.path().join("render.sh");
+        std::fs::write(&script, b"#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o777)).unwrap();
+        IFileOperations::copy(&adapter, Path::new("/render.sh"), Path::new("/copy.sh"), CopyOptions::default())
+            .await.unwrap();
+        assert_eq!(mode("copy.sh"), 0o777 & !crate::vfs::platform::umask());
+    }
+    
     #[tokio::test]
     async fn test_file_ops_move() {
         let temp_dir = TempDir::new().unwrap();
@@ -1019,6 +1286,37 @@ mod tests {
         assert_eq!(content, b"line1\nline2\n");
     }
     
+    #[tokio::test]
+    async fn test_concurrent_writes_do_not_interleave() {
+        use futures::StreamExt;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = LocalStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "Test".to_string(),
+        );
+        
+        // Each writer yields between chunks so the two would interleave unlocked
+        let slow_chunks = |byte: u8| {
+            futures::stream::iter(0..8)
+                .then(move |_| async move {
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    Ok::<_, anyhow::Error>(vec![byte; 1024])
+                })
+                .boxed()
+        };
+        let path = Path::new("/edit.txt");
+        let (a, b) = tokio::join!(
+            IFileOperations::write_stream(&adapter, path, slow_chunks(b'a')),
+            IFileOperations::write_stream(&adapter, path, slow_chunks(b'b')),
+        );
+        assert_eq!((a.unwrap(), b.unwrap()), (8192, 8192));
+        
+        let content = std::fs::read(temp_dir.path().join("edit.txt")).unwrap();
+        assert_eq!(content.len(), 8192);
+        assert!(content.iter().all(|&c| c == content[0]), "writes were mixed");
+    }
+    
     #[tokio::test]
     async fn test_file_ops_list() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,138 @@
+//! Media Capabilities - what the installed FFmpeg can do, probed once
+//!
+//! Thumbnails for video, transcoding, waveforms, sprite sheets and
+//! transcription all shell out to FFmpeg. Probing it once at startup lets
+//! those commands fail up front with `VfsError::FfmpegUnavailable` and
+//! install instructions, and lets the frontend hide what can't work.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+use tracing::{info, warn};
+
+use crate::vfs::adapters::FfmpegMediaAdapter;
+use crate::vfs::domain::VfsError;
+
+/// FFmpeg binaries found on this machine and the encoders they were built with
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaCapabilities {
+    /// Both `ffmpeg` and `ffprobe` were found and run
+    pub ffmpeg_available: bool,
+    pub ffmpeg_path: Option<PathBuf>,
+    pub ffprobe_path: Option<PathBuf>,
+    /// First line of `ffmpeg -version`
+    pub version: Option<String>,
+    /// Encoder names from `ffmpeg -encoders` (`libx264`, `aac`, `prores_ks`, ...)
+    pub encoders: Vec<String>,
+}
+
+impl MediaCapabilities {
+    /// Look for FFmpeg in the usual places and ask it what it supports
+    pub async fn probe() -> Self {
+        let capabilities = Self::probe_with(FfmpegMediaAdapter::find_ffmpeg().await, FfmpegMediaAdapter::find_ffprobe().await).await;
+        match &capabilities.version {
+            Some(version) if capabilities.ffmpeg_available => {
+                info!("{} with {} encoders", version, capabilities.encoders.len());
+            }
+            _ => warn!("FFmpeg not found; video thumbnails, transcoding, waveforms and transcription are disabled"),
+        }
+        capabilities
+    }
+    
+    /// Capabilities of the given binaries; `None` for one that wasn't found
+    pub async fn probe_with(ffmpeg_path: Option<PathBuf>, ffprobe_path: Option<PathBuf>) -> Self {
+        let (Some(ffmpeg), Some(_)) = (&ffmpeg_path, &ffprobe_path) else {
+            return Self { ffmpeg_path, ffprobe_path, ..Self::default() };
+        };
+        
+        let version = run(ffmpeg, &["-hide_banner", "-version"]).await
+            .and_then(|output| output.lines().next().map(str::to_string));
+        let encoders = run(ffmpeg, &["-hide_banner", "-encoders"]).await
+            .map(|output| parse_encoders(&output))
+            .unwrap_or_default();
+        
+        Self {
+            ffmpeg_available: version.is_some(),
+            ffmpeg_path,
+            ffprobe_path,
+            version,
+            encoders,
+        }
+    }
+    
+    pub fn has_encoder(&self, name: &str) -> bool {
+        self.encoders.iter().any(|encoder| encoder == name)
+    }
+    
+    /// `FfmpegUnavailable` naming `feature` unless FFmpeg is installed
+    pub fn require_ffmpeg(&self, feature: &str) -> Result<(), VfsError> {
+        if self.ffmpeg_available {
+            return Ok(());
+        }
+        Err(VfsError::FfmpegUnavailable(format!(
+            "{} needs FFmpeg, which isn't installed. {}",
+            feature,
+            install_hint()
+        )))
+    }
+}
+
+/// How to install FFmpeg on this platform
+pub fn install_hint() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Install it with `brew install ffmpeg`, then restart the app."
+    } else if cfg!(target_os = "windows") {
+        "Install it with `winget install ffmpeg` and make sure it's on your PATH, then restart the app."
+    } else {
+        "Install the `ffmpeg` package from your distribution (e.g. `sudo apt install ffmpeg`), then restart the app."
+    }
+}
+
+/// Stdout of a successful run
+async fn run(binary: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Encoder names from `ffmpeg -encoders`: a legend, a `------` rule, then
+/// one ` V....D libx264   description` line per encoder
+fn parse_encoders(output: &str) -> Vec<String> {
+    output.lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_encoders() {
+        let output = concat!(
+            "Encoders:\n",
+            " V..... = Video\n",
+            " A..... = Audio\n",
+            " ------\n",
+            " V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC (codec h264)\n",
+            " V..... prores_ks            Apple ProRes (iCodec Pro) (codec prores)\n",
+            " A....D aac                  AAC (Advanced Audio Coding)\n",
+        );
+        let capabilities = MediaCapabilities { encoders: parse_encoders(output), ..MediaCapabilities::default() };
+        
+        assert_eq!(capabilities.encoders, vec!["libx264", "prores_ks", "aac"]);
+        assert!(capabilities.has_encoder("prores_ks"));
+        assert!(!capabilities.has_encoder("Video"));
+    }
+}
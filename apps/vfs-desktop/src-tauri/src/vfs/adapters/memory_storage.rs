@@ -0,0 +1,704 @@
+//! In-Memory Storage Adapter - a "RAM disk" source
+//!
+//! Files live in a `HashMap<PathBuf, Vec<u8>>` keyed by normalized absolute
+//! paths; directories are the set of path prefixes files were written under
+//! plus any created explicitly, so empty directories survive. Modification
+//! times are synthetic: a clock that never repeats, so every change is newer
+//! than the one before. Contents are gone when the source is removed.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+use crate::vfs::domain::{
+    VirtualFile, StorageSourceType, TierStatus, StorageTier, InsufficientSpaceError, VfsError,
+};
+use crate::vfs::ports::{
+    StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions, ListOptions,
+};
+
+/// Provider id of memory sources (`StorageSourceType::Custom`)
+pub const MEMORY_PROVIDER_ID: &str = "memory";
+
+/// Most bytes a memory source holds unless created with another capacity
+pub const DEFAULT_MEMORY_CAPACITY: u64 = 1024 * 1024 * 1024;
+
+#[derive(Default)]
+struct MemoryTree {
+    /// File contents by normalized path
+    files: HashMap<PathBuf, Vec<u8>>,
+    
+    /// Directories other than the root
+    dirs: HashSet<PathBuf>,
+    
+    /// Synthetic modification times of files and directories
+    modified: HashMap<PathBuf, SystemTime>,
+    
+    /// Last time handed out, so the next one is strictly later
+    clock: Option<SystemTime>,
+}
+
+impl MemoryTree {
+    fn is_dir(&self, key: &Path) -> bool {
+        key == Path::new("/") || self.dirs.contains(key)
+    }
+    
+    fn exists(&self, key: &Path) -> bool {
+        self.is_dir(key) || self.files.contains_key(key)
+    }
+    
+    fn used(&self) -> u64 {
+        self.files.values().map(|data| data.len() as u64).sum()
+    }
+    
+    fn modified(&self, key: &Path) -> SystemTime {
+        self.modified.get(key).copied().unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+    
+    /// Stamp `key` and its parent directory as modified now
+    fn touch(&mut self, key: &Path) {
+        let now = SystemTime::now();
+        let now = match self.clock {
+            Some(last) if now <= last => last + Duration::from_micros(1),
+            _ => now,
+        };
+        self.clock = Some(now);
+        
+        self.modified.insert(key.to_path_buf(), now);
+        if let Some(parent) = key.parent() {
+            self.modified.insert(parent.to_path_buf(), now);
+        }
+    }
+    
+    /// Create the directories above `key`, like `mkdir -p` of its parent
+    fn add_parents(&mut self, key: &Path) -> Result<()> {
+        let mut ancestors: Vec<&Path> = key.ancestors().skip(1).collect();
+        ancestors.reverse();
+        for dir in ancestors.into_iter().skip(1) {
+            if self.files.contains_key(dir) {
+                return Err(anyhow::anyhow!("Not a directory: {:?}", dir));
+            }
+            if self.dirs.insert(dir.to_path_buf()) {
+                self.touch(dir);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Immediate children of `dir`: (path, is_dir)
+    fn children(&self, dir: &Path) -> Vec<(PathBuf, bool)> {
+        let dirs = self.dirs.iter().map(|path| (path, true));
+        let files = self.files.keys().map(|path| (path, false));
+        dirs.chain(files)
+            .filter(|(path, _)| path.parent() == Some(dir))
+            .map(|(path, is_dir)| (path.clone(), is_dir))
+            .collect()
+    }
+    
+    /// Everything at or below `key`
+    fn subtree(&self, key: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let dirs = self.dirs.iter().filter(|p| p.starts_with(key)).cloned().collect();
+        let files = self.files.keys().filter(|p| p.starts_with(key)).cloned().collect();
+        (dirs, files)
+    }
+    
+    /// Remove `key` and everything below it
+    fn remove_tree(&mut self, key: &Path) {
+        self.files.retain(|path, _| !path.starts_with(key));
+        self.dirs.retain(|path| !path.starts_with(key));
+        self.modified.retain(|path, _| !path.starts_with(key));
+        if let Some(parent) = key.parent() {
+            self.touch(parent);
+        }
+    }
+    
+    /// Copy the file or directory tree at `from` to `to`
+    fn copy_tree(&mut self, from: &Path, to: &Path) {
+        let (dirs, files) = self.subtree(from);
+        for dir in dirs {
+            let target = to.join(dir.strip_prefix(from).unwrap_or(&dir));
+            self.dirs.insert(target.clone());
+            self.touch(&target);
+        }
+        for file in files {
+            let target = to.join(file.strip_prefix(from).unwrap_or(&file));
+            let data = self.files[&file].clone();
+            self.files.insert(target.clone(), data);
+            self.touch(&target);
+        }
+    }
+}
+
+/// Storage adapter keeping everything in memory
+pub struct InMemoryStorageAdapter {
+    /// Display name
+    name: String,
+    
+    /// Most bytes of file data the source holds
+    capacity: u64,
+    
+    tree: RwLock<MemoryTree>,
+}
+
+impl InMemoryStorageAdapter {
+    pub fn new(name: String) -> Self {
+        Self::with_capacity(name, DEFAULT_MEMORY_CAPACITY)
+    }
+    
+    pub fn with_capacity(name: String, capacity: u64) -> Self {
+        Self {
+            name,
+            capacity,
+            tree: RwLock::new(MemoryTree::default()),
+        }
+    }
+    
+    /// Normalize a VFS path to the key it is stored under (`/a/b`)
+    fn key(path: &Path) -> PathBuf {
+        let mut key = PathBuf::from("/");
+        for component in path.components() {
+            match component {
+                Component::Normal(part) => key.push(part),
+                Component::ParentDir => {
+                    key.pop();
+                }
+                _ => {}
+            }
+        }
+        key
+    }
+    
+    fn not_found(path: &Path) -> anyhow::Error {
+        VfsError::NotFound(format!("No such file or directory: {:?}", path)).into()
+    }
+    
+    /// Fail if replacing `key`'s contents with `new_len` bytes would go over capacity
+    fn check_capacity(&self, tree: &MemoryTree, key: &Path, new_len: u64) -> Result<()> {
+        let current = tree.files.get(key).map_or(0, |data| data.len() as u64);
+        let available = self.capacity.saturating_sub(tree.used() - current);
+        if new_len > available {
+            return Err(InsufficientSpaceError { needed: new_len, available }.into());
+        }
+        Ok(())
+    }
+    
+    /// Replace a file's contents, creating it and its parents if needed
+    fn put(&self, path: &Path, data: Vec<u8>) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        if tree.is_dir(&key) {
+            return Err(anyhow::anyhow!("Is a directory: {:?}", path));
+        }
+        self.check_capacity(&tree, &key, data.len() as u64)?;
+        tree.add_parents(&key)?;
+        tree.files.insert(key.clone(), data);
+        tree.touch(&key);
+        Ok(())
+    }
+    
+    /// Change an existing file's contents in place
+    fn update(&self, path: &Path, change: impl FnOnce(&mut Vec<u8>)) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        let mut data = tree.files.get(&key).cloned().ok_or_else(|| Self::not_found(path))?;
+        change(&mut data);
+        self.check_capacity(&tree, &key, data.len() as u64)?;
+        tree.files.insert(key.clone(), data);
+        tree.touch(&key);
+        Ok(())
+    }
+    
+    fn virtual_file(path: &Path, size: u64, is_dir: bool, modified: SystemTime) -> VirtualFile {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        
+        let mut vfile = VirtualFile::new(name, path.to_path_buf(), size, is_dir);
+        
+        // Memory is as hot as it gets
+        vfile.tier_status = TierStatus {
+            current_tier: StorageTier::Hot,
+            is_cached: true,
+            can_warm: false,
+            retrieval_time_estimate: Some(0),
+        };
+        vfile.transcodable = vfile.can_transcode();
+        vfile.last_modified = modified;
+        vfile
+    }
+    
+    /// Read `len` bytes at `offset` (fewer at the end of the file)
+    fn slice(&self, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+        let tree = self.tree.read();
+        let data = tree.files.get(&Self::key(path)).ok_or_else(|| Self::not_found(path))?;
+        let start = (offset as usize).min(data.len());
+        let end = start.saturating_add(len as usize).min(data.len());
+        Ok(data[start..end].to_vec())
+    }
+}
+
+#[async_trait]
+impl StorageAdapter for InMemoryStorageAdapter {
+    fn storage_type(&self) -> StorageSourceType {
+        StorageSourceType::Custom(MEMORY_PROVIDER_ID.to_string())
+    }
+    
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    async fn test_connection(&self) -> Result<bool> {
+        Ok(true)
+    }
+    
+    async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        self.list_files_with(path, &ListOptions::default()).await
+    }
+    
+    async fn list_files_with(&self, path: &Path, options: &ListOptions) -> Result<Vec<VirtualFile>> {
+        let key = Self::key(path);
+        let tree = self.tree.read();
+        if !tree.is_dir(&key) {
+            return Err(Self::not_found(path));
+        }
+        
+        let mut files: Vec<VirtualFile> = tree.children(&key)
+            .into_iter()
+            .filter_map(|(child, is_dir)| {
+                let name = child.file_name()?.to_string_lossy().to_string();
+                if !options.includes_name(&name, false) {
+                    return None;
+                }
+                let size = if is_dir { 0 } else { tree.files[&child].len() as u64 };
+                Some(Self::virtual_file(&path.join(&name), size, is_dir, tree.modified(&child)))
+            })
+            .collect();
+        
+        files.sort_by(|a, b| options.compare(a, b));
+        Ok(files)
+    }
+    
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        IFileOperations::read(self, path).await
+    }
+    
+    async fn read_file_range(&self, path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
+        self.slice(path, offset, length)
+    }
+    
+    async fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.put(path, data.to_vec())
+    }
+    
+    async fn get_metadata(&self, path: &Path) -> Result<VirtualFile> {
+        let key = Self::key(path);
+        let tree = self.tree.read();
+        
+        if tree.is_dir(&key) {
+            return Ok(Self::virtual_file(path, 0, true, tree.modified(&key)));
+        }
+        let data = tree.files.get(&key).ok_or_else(|| Self::not_found(path))?;
+        Ok(Self::virtual_file(path, data.len() as u64, false, tree.modified(&key)))
+    }
+    
+    async fn exists(&self, path: &Path) -> Result<bool> {
+        Ok(self.tree.read().exists(&Self::key(path)))
+    }
+    
+    async fn delete(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        if !tree.exists(&key) {
+            return Err(Self::not_found(path));
+        }
+        tree.remove_tree(&key);
+        Ok(())
+    }
+    
+    async fn create_dir(&self, path: &Path) -> Result<()> {
+        IFileOperations::mkdir_p(self, path).await
+    }
+    
+    async fn file_size(&self, path: &Path) -> Result<u64> {
+        IFileOperations::file_size(self, path).await
+    }
+    
+    fn supports_range_reads(&self) -> bool {
+        true
+    }
+}
+
+// =============================================================================
+// IFileOperations Implementation
+// =============================================================================
+
+#[async_trait]
+impl IFileOperations for InMemoryStorageAdapter {
+    async fn list(&self, path: &Path) -> Result<Vec<FileEntry>> {
+        let key = Self::key(path);
+        let tree = self.tree.read();
+        if !tree.is_dir(&key) {
+            return Err(Self::not_found(path));
+        }
+        
+        let mut entries: Vec<FileEntry> = tree.children(&key)
+            .into_iter()
+            .map(|(child, is_dir)| {
+                let name = child.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let modified = Some(tree.modified(&child));
+                FileEntry {
+                    path: path.join(&name).to_string_lossy().to_string(),
+                    name,
+                    size: if is_dir { 0 } else { tree.files[&child].len() as u64 },
+                    is_dir,
+                    is_file: !is_dir,
+                    is_symlink: false,
+                    modified,
+                    created: modified,
+                    accessed: modified,
+                    mode: Some(if is_dir { 0o755 } else { 0o644 }),
+                    mime_type: None,
+                }
+            })
+            .collect();
+        
+        // Sort: directories first, then by name
+        entries.sort_by(|a, b| {
+            match (a.is_dir, b.is_dir) {
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            }
+        });
+        
+        Ok(entries)
+    }
+    
+    async fn stat(&self, path: &Path) -> Result<FileStat> {
+        let key = Self::key(path);
+        let tree = self.tree.read();
+        
+        let is_dir = tree.is_dir(&key);
+        let size = match tree.files.get(&key) {
+            Some(data) => data.len() as u64,
+            None if is_dir => 0,
+            None => return Err(Self::not_found(path)),
+        };
+        let mtime = Some(tree.modified(&key));
+        
+        Ok(FileStat {
+            size,
+            is_dir,
+            is_file: !is_dir,
+            is_symlink: false,
+            mtime,
+            atime: mtime,
+            ctime: mtime,
+            mode: if is_dir { 0o755 } else { 0o644 },
+            blocks: (size + 511) / 512,
+            ..FileStat::default()
+        })
+    }
+    
+    async fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        let tree = self.tree.read();
+        tree.files.get(&Self::key(path)).cloned().ok_or_else(|| Self::not_found(path))
+    }
+    
+    async fn read_range(&self, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+        self.slice(path, offset, len)
+    }
+    
+    async fn write(&self, path: &Path, data: &[u8]) -> Result<()> {
+        debug!("Writing {} bytes to memory file {:?}", data.len(), path);
+        self.put(path, data.to_vec())
+    }
+    
+    async fn append(&self, path: &Path, data: &[u8]) -> Result<()> {
+        let exists = self.tree.read().files.contains_key(&Self::key(path));
+        if !exists {
+            return self.put(path, data.to_vec());
+        }
+        self.update(path, |contents| contents.extend_from_slice(data))
+    }
+    
+    async fn write_at(&self, path: &Path, offset: u64, data: &[u8]) -> Result<()> {
+        let offset = offset as usize;
+        self.update(path, |contents| {
+            if contents.len() < offset + data.len() {
+                contents.resize(offset + data.len(), 0);
+            }
+            contents[offset..offset + data.len()].copy_from_slice(data);
+        })
+    }
+    
+    async fn truncate(&self, path: &Path, len: u64) -> Result<()> {
+        self.update(path, |contents| contents.resize(len as usize, 0))
+    }
+    
+    async fn mkdir(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        if tree.exists(&key) {
+            return Err(VfsError::AlreadyExists(format!("Already exists: {:?}", path)).into());
+        }
+        if !key.parent().is_some_and(|parent| tree.is_dir(parent)) {
+            return Err(Self::not_found(key.parent().unwrap_or(&key)));
+        }
+        tree.dirs.insert(key.clone());
+        tree.touch(&key);
+        Ok(())
+    }
+    
+    async fn mkdir_p(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        if tree.files.contains_key(&key) {
+            return Err(VfsError::AlreadyExists(format!("A file exists at {:?}", path)).into());
+        }
+        tree.add_parents(&key)?;
+        if key != Path::new("/") && tree.dirs.insert(key.clone()) {
+            tree.touch(&key);
+        }
+        Ok(())
+    }
+    
+    async fn rmdir(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        if key == Path::new("/") || !tree.dirs.contains(&key) {
+            return Err(Self::not_found(path));
+        }
+        if !tree.children(&key).is_empty() {
+            return Err(anyhow::anyhow!("Directory not empty: {:?}", path));
+        }
+        tree.remove_tree(&key);
+        Ok(())
+    }
+    
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let (from_key, to_key) = (Self::key(from), Self::key(to));
+        debug!("Renaming memory path {:?} to {:?}", from_key, to_key);
+        if from_key == to_key {
+            return Ok(());
+        }
+        
+        let mut tree = self.tree.write();
+        if from_key == Path::new("/") || !tree.exists(&from_key) {
+            return Err(Self::not_found(from));
+        }
+        if to_key.starts_with(&from_key) {
+            return Err(anyhow::anyhow!("Cannot move {:?} into itself", from));
+        }
+        if tree.is_dir(&to_key) && !tree.children(&to_key).is_empty() {
+            return Err(anyhow::anyhow!("Directory not empty: {:?}", to));
+        }
+        
+        // Like rename(2), a file replaces a file at the destination
+        tree.add_parents(&to_key)?;
+        tree.remove_tree(&to_key);
+        tree.copy_tree(&from_key, &to_key);
+        tree.remove_tree(&from_key);
+        Ok(())
+    }
+    
+    async fn copy(&self, from: &Path, to: &Path, options: CopyOptions) -> Result<()> {
+        let (from_key, to_key) = (Self::key(from), Self::key(to));
+        let mut tree = self.tree.write();
+        
+        if !tree.exists(&from_key) {
+            return Err(Self::not_found(from));
+        }
+        if tree.exists(&to_key) && !options.overwrite {
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {:?}", to)).into());
+        }
+        if tree.is_dir(&from_key) {
+            if !options.recursive {
+                return Err(anyhow::anyhow!("Cannot copy directory without recursive option"));
+            }
+            if to_key.starts_with(&from_key) {
+                return Err(anyhow::anyhow!("Cannot copy {:?} into itself", from));
+            }
+        }
+        
+        let (_, files) = tree.subtree(&from_key);
+        let needed: u64 = files.iter().map(|file| tree.files[file].len() as u64).sum();
+        let available = self.capacity.saturating_sub(tree.used());
+        if needed > available {
+            return Err(InsufficientSpaceError { needed, available }.into());
+        }
+        
+        tree.add_parents(&to_key)?;
+        tree.copy_tree(&from_key, &to_key);
+        Ok(())
+    }
+    
+    async fn mv(&self, from: &Path, to: &Path, options: MoveOptions) -> Result<()> {
+        if !options.overwrite && IFileOperations::exists(self, to).await? {
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {:?}", to)).into());
+        }
+        self.rename(from, to).await
+    }
+    
+    async fn rm(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        
+        if tree.is_dir(&key) {
+            return Err(anyhow::anyhow!("Is a directory: {:?}", path));
+        }
+        if tree.files.remove(&key).is_none() {
+            return Err(Self::not_found(path));
+        }
+        tree.modified.remove(&key);
+        if let Some(parent) = key.parent() {
+            tree.touch(parent);
+        }
+        Ok(())
+    }
+    
+    async fn rm_rf(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        if tree.exists(&key) {
+            tree.remove_tree(&key);
+        }
+        Ok(())
+    }
+    
+    async fn symlink(&self, _target: &Path, link: &Path) -> Result<()> {
+        Err(VfsError::Unsupported(format!("Memory sources have no symlinks: {:?}", link)).into())
+    }
+    
+    async fn readlink(&self, path: &Path) -> Result<String> {
+        Err(VfsError::Unsupported(format!("Memory sources have no symlinks: {:?}", path)).into())
+    }
+    
+    async fn exists(&self, path: &Path) -> Result<bool> {
+        Ok(self.tree.read().exists(&Self::key(path)))
+    }
+    
+    async fn is_dir(&self, path: &Path) -> Result<bool> {
+        Ok(self.tree.read().is_dir(&Self::key(path)))
+    }
+    
+    async fn is_file(&self, path: &Path) -> Result<bool> {
+        Ok(self.tree.read().files.contains_key(&Self::key(path)))
+    }
+    
+    async fn is_symlink(&self, _path: &Path) -> Result<bool> {
+        Ok(false)
+    }
+    
+    async fn chmod(&self, path: &Path, mode: u32) -> Result<()> {
+        debug!("chmod {:o} ignored for memory path {:?}", mode, path);
+        Ok(())
+    }
+    
+    async fn chown(&self, path: &Path, uid: u32, gid: u32) -> Result<()> {
+        debug!("chown {}:{} ignored for memory path {:?}", uid, gid, path);
+        Ok(())
+    }
+    
+    async fn touch(&self, path: &Path) -> Result<()> {
+        let key = Self::key(path);
+        let exists = self.tree.read().exists(&key);
+        if !exists {
+            return self.put(path, Vec::new());
+        }
+        self.tree.write().touch(&key);
+        Ok(())
+    }
+    
+    async fn set_times(&self, path: &Path, _atime: Option<SystemTime>, mtime: Option<SystemTime>) -> Result<()> {
+        let key = Self::key(path);
+        let mut tree = self.tree.write();
+        if !tree.exists(&key) {
+            return Err(Self::not_found(path));
+        }
+        if let Some(mtime) = mtime {
+            tree.modified.insert(key, mtime);
+        }
+        Ok(())
+    }
+    
+    async fn file_size(&self, path: &Path) -> Result<u64> {
+        let tree = self.tree.read();
+        let data = tree.files.get(&Self::key(path)).ok_or_else(|| Self::not_found(path))?;
+        Ok(data.len() as u64)
+    }
+    
+    async fn available_space(&self) -> Result<u64> {
+        Ok(self.capacity.saturating_sub(self.tree.read().used()))
+    }
+    
+    async fn total_space(&self) -> Result<u64> {
+        Ok(self.capacity)
+    }
+    
+    fn is_read_only(&self) -> bool {
+        false
+    }
+    
+    fn root_path(&self) -> &Path {
+        Path::new("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_directories_and_listing_order() {
+        let adapter = InMemoryStorageAdapter::new("Scratch".to_string());
+        
+        adapter.write(Path::new("/shoot/b.mov"), b"bb").await.unwrap();
+        adapter.write(Path::new("/shoot/A.mov"), b"a").await.unwrap();
+        adapter.mkdir(Path::new("/shoot/selects")).await.unwrap();
+        
+        // Parents are created by writes; empty directories stay around
+        assert!(adapter.is_dir(Path::new("/shoot")).await.unwrap());
+        let names: Vec<String> = adapter.list_files(Path::new("/shoot")).await.unwrap()
+            .into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["selects", "A.mov", "b.mov"]);
+        
+        assert!(adapter.mkdir(Path::new("/missing/dir")).await.is_err());
+        assert!(adapter.rmdir(Path::new("/shoot")).await.is_err());
+        adapter.rmdir(Path::new("/shoot/selects")).await.unwrap();
+        
+        // Renaming a directory takes its contents along
+        adapter.rename(Path::new("/shoot"), Path::new("/archive/shoot")).await.unwrap();
+        assert_eq!(adapter.read(Path::new("/archive/shoot/b.mov")).await.unwrap(), b"bb");
+        assert!(!IFileOperations::exists(&adapter, Path::new("/shoot/b.mov")).await.unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_synthetic_mtimes_and_capacity() {
+        let adapter = InMemoryStorageAdapter::with_capacity("Scratch".to_string(), 8);
+        
+        adapter.write(Path::new("/a.txt"), b"1234").await.unwrap();
+        let first = adapter.stat(Path::new("/a.txt")).await.unwrap().mtime.unwrap();
+        adapter.append(Path::new("/a.txt"), b"56").await.unwrap();
+        let second = adapter.stat(Path::new("/a.txt")).await.unwrap().mtime.unwrap();
+        assert!(second > first);
+        assert_eq!(adapter.read_range(Path::new("/a.txt"), 2, 10).await.unwrap(), b"3456");
+        
+        let err = adapter.write(Path::new("/b.txt"), b"123").await.unwrap_err();
+        assert!(err.downcast_ref::<InsufficientSpaceError>().is_some());
+        assert_eq!(adapter.available_space().await.unwrap(), 2);
+        
+        let err = VfsError::from(adapter.read(Path::new("/nope")).await.unwrap_err());
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+}
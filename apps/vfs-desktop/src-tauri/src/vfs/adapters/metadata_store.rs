@@ -1,7 +1,8 @@
 //! Metadata Store Adapter - JSON file-based metadata persistence
 //!
 //! Stores file metadata (tags, favorites, ratings) in a JSON file
-//! within the app's data directory.
+//! within the app's data directory. Smart folders (saved metadata queries)
+//! live in a sibling `smart_folders.json`.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -12,7 +13,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, info};
 
 use crate::vfs::domain::{ColorLabel, FileTag};
-use crate::vfs::ports::metadata::{FileMetadata, IMetadataStore};
+use crate::vfs::ports::metadata::{FileMetadata, IMetadataStore, MetadataQuery, SmartFolder};
 
 /// Key for metadata storage: "source_id:path"
 fn make_key(source_id: &str, path: &Path) -> String {
@@ -29,6 +30,9 @@ pub struct JsonMetadataStore {
     
     /// Dirty flag for write-back
     dirty: RwLock<bool>,
+    
+    /// Saved smart folders
+    smart_folders: RwLock<Vec<SmartFolder>>,
 }
 
 impl JsonMetadataStore {
@@ -38,10 +42,12 @@ impl JsonMetadataStore {
             store_path,
             cache: RwLock::new(HashMap::new()),
             dirty: RwLock::new(false),
+            smart_folders: RwLock::new(Vec::new()),
         };
         
         // Load existing data
         store.load().await?;
+        store.load_smart_folders().await?;
         
         Ok(store)
     }
@@ -111,6 +117,46 @@ impl JsonMetadataStore {
         Ok(())
     }
     
+    /// Smart folders are stored next to the metadata file
+    fn smart_folders_path(&self) -> PathBuf {
+        self.store_path.with_file_name("smart_folders.json")
+    }
+    
+    /// Load smart folders from disk
+    async fn load_smart_folders(&self) -> Result<()> {
+        let path = self.smart_folders_path();
+        if !path.exists() {
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&path).await
+            .context("Failed to read smart folders")?;
+        
+        let folders: Vec<SmartFolder> = serde_json::from_str(&content)
+            .context("Failed to parse smart folders")?;
+        
+        info!("Loaded {} smart folders", folders.len());
+        *self.smart_folders.write().await = folders;
+        Ok(())
+    }
+    
+    /// Save smart folders to disk
+    async fn save_smart_folders(&self) -> Result<()> {
+        let content = {
+            let folders = self.smart_folders.read().await;
+            serde_json::to_string_pretty(&*folders)
+                .context("Failed to serialize smart folders")?
+        };
+        
+        let path = self.smart_folders_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        fs::write(&path, content).await
+            .context("Failed to write smart folders")
+    }
+    
     /// Mark as dirty (needs saving)
     async fn mark_dirty(&self) {
         *self.dirty.write().await = true;
@@ -280,6 +326,68 @@ impl IMetadataStore for JsonMetadataStore {
         
         Ok(all_tags)
     }
+    
+    async fn query(&self, source_id: &str, query: &MetadataQuery) -> Result<Vec<String>> {
+        let prefix = format!("{}:", source_id);
+        let cache = self.cache.read().await;
+        
+        let mut files: Vec<String> = cache
+            .iter()
+            .filter(|(k, m)| k.starts_with(&prefix) && query.matches(m))
+            .map(|(k, _)| k.strip_prefix(&prefix).unwrap_or(k).to_string())
+            .collect();
+        files.sort();
+        
+        Ok(files)
+    }
+    
+    async fn create_smart_folder(&self, name: String, source_id: String, query: MetadataQuery) -> Result<SmartFolder> {
+        let folder = SmartFolder {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            source_id,
+            query,
+        };
+        
+        self.smart_folders.write().await.push(folder.clone());
+        self.save_smart_folders().await?;
+        
+        debug!("Created smart folder: {}", folder.name);
+        Ok(folder)
+    }
+    
+    async fn list_smart_folders(&self) -> Result<Vec<SmartFolder>> {
+        Ok(self.smart_folders.read().await.clone())
+    }
+    
+    async fn delete_smart_folder(&self, id: &str) -> Result<()> {
+        self.smart_folders.write().await.retain(|f| f.id != id);
+        self.save_smart_folders().await
+    }
+    
+    async fn vacuum(&self, source_id: &str, exists: &(dyn Fn(&Path) -> bool + Send + Sync)) -> Result<usize> {
+        let prefix = format!("{}:", source_id);
+        let mut cache = self.cache.write().await;
+        
+        let before = cache.len();
+        cache.retain(|k, _| match k.strip_prefix(&prefix) {
+            Some(path) => exists(Path::new(path)),
+            None => true,
+        });
+        let pruned = before - cache.len();
+        drop(cache);
+        
+        if pruned > 0 {
+            info!("Pruned {} metadata entries for deleted files on {}", pruned, source_id);
+            self.mark_dirty().await;
+            self.save().await?;
+        }
+        Ok(pruned)
+    }
+    
+    async fn flush(&self) -> Result<()> {
+        self.save().await
+    }
 }
 
 // =============================================================================
@@ -359,6 +467,69 @@ mod tests {
         assert_eq!(tags.len(), 2); // work and personal (deduplicated)
     }
     
+    #[tokio::test]
+    async fn test_smart_folder_persisted_and_evaluated() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("metadata.json");
+        
+        let id = {
+            let store = JsonMetadataStore::new(store_path.clone()).await.unwrap();
+            store.set_favorite("local", Path::new("/fav.txt"), true).await.unwrap();
+            store.create_smart_folder(
+                "Favorites".to_string(),
+                "local".to_string(),
+                MetadataQuery { favorites_only: true, ..Default::default() },
+            ).await.unwrap().id
+        };
+        
+        let store = JsonMetadataStore::new(store_path).await.unwrap();
+        assert_eq!(store.list_smart_folders().await.unwrap().len(), 1);
+        assert_eq!(store.eval_smart_folder(&id).await.unwrap(), vec!["/fav.txt".to_string()]);
+        
+        store.delete_smart_folder(&id).await.unwrap();
+        assert!(store.eval_smart_folder(&id).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_vacuum_prunes_deleted_files() {
+        let (store, dir) = create_test_store().await;
+        let source = TempDir::new().unwrap();
+        std::fs::write(source.path().join("keep.mov"), b"clip").unwrap();
+        std::fs::write(source.path().join("gone.mov"), b"clip").unwrap();
+        
+        store.add_tag("local", Path::new("/keep.mov"), FileTag::new("select")).await.unwrap();
+        store.add_tag("local", Path::new("/gone.mov"), FileTag::new("select")).await.unwrap();
+        store.add_tag("nas", Path::new("/gone.mov"), FileTag::new("select")).await.unwrap();
+        std::fs::remove_file(source.path().join("gone.mov")).unwrap();
+        
+        let exists = |path: &Path| source.path().join(path.strip_prefix("/").unwrap()).exists();
+        assert_eq!(store.vacuum("local", &exists).await.unwrap(), 1);
+        
+        assert!(store.get("local", Path::new("/gone.mov")).await.unwrap().is_none());
+        assert_eq!(store.get("local", Path::new("/keep.mov")).await.unwrap().unwrap().tags[0].name, "select");
+        assert!(store.get("nas", Path::new("/gone.mov")).await.unwrap().is_some(), "other sources untouched");
+        
+        let reloaded = JsonMetadataStore::new(dir.path().join("metadata.json")).await.unwrap();
+        assert!(reloaded.get("local", Path::new("/gone.mov")).await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_folder_view_overrides_default() {
+        use crate::vfs::ports::{FolderView, ListSortBy, ViewMode};
+        
+        let (store, _dir) = create_test_store().await;
+        let default = FolderView { view_mode: ViewMode::List, sort_by: ListSortBy::Name, sort_ascending: true };
+        let gallery = FolderView { view_mode: ViewMode::Gallery, sort_by: ListSortBy::Modified, sort_ascending: false };
+        
+        store.set_folder_view("local", Path::new("/shoot/stills"), Some(gallery)).await.unwrap();
+        
+        assert_eq!(store.folder_view("local", Path::new("/shoot/stills"), default).await.unwrap(), gallery);
+        assert_eq!(store.folder_view("local", Path::new("/shoot/audio"), default).await.unwrap(), default);
+        
+        store.set_folder_view("local", Path::new("/shoot/stills"), None).await.unwrap();
+        assert_eq!(store.folder_view("local", Path::new("/shoot/stills"), default).await.unwrap(), default);
+    }
+    
     #[tokio::test]
     async fn test_persistence() {
         let temp_dir = TempDir::new().unwrap();
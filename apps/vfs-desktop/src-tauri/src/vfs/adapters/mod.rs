@@ -6,27 +6,54 @@
 pub mod local_storage;
 pub mod s3_storage;
 pub mod s3_tiering;
+pub mod s3_versioning;
 pub mod object_storage_tiering;
 pub mod nvme_cache;
 pub mod tauri_event_bus;
 pub mod ffmpeg_media;
+pub mod media_capabilities;
 pub mod fsxn_storage;
 pub mod gcs_storage;
 pub mod nas_storage;
+pub mod block_storage;
+pub mod memory_storage;
 pub mod clipboard;
 pub mod metadata_store;
+pub mod xattr_metadata_store;
+pub mod recents_store;
+pub mod search_history_store;
+pub mod bookmarks_store;
+pub mod sources_store;
+pub mod settings_store;
+pub mod automount_store;
+pub mod credential_store;
+pub mod template_store;
 pub mod native_thumbnail;
 pub mod transcription;
+pub mod error_mapping;
 
 pub use local_storage::LocalStorageAdapter;
 pub use s3_storage::S3StorageAdapter;
 pub use nvme_cache::NvmeCacheAdapter;
 pub use tauri_event_bus::TauriEventBus;
 pub use ffmpeg_media::FfmpegMediaAdapter;
+pub use media_capabilities::MediaCapabilities;
 pub use fsxn_storage::FsxOntapAdapter;
 pub use gcs_storage::GcsStorageAdapter;
 pub use nas_storage::{NasStorageAdapter, NasProtocol};
+pub use block_storage::{BlockStorageAdapter, BlockDeviceInfo};
+pub use memory_storage::{InMemoryStorageAdapter, MEMORY_PROVIDER_ID};
 pub use clipboard::ClipboardAdapter;
 pub use metadata_store::JsonMetadataStore;
+pub use xattr_metadata_store::XattrMetadataStore;
+pub use recents_store::{RecentsStore, RecentEntry, DEFAULT_RECENTS_CAPACITY, DEFAULT_RECENT_DESTINATIONS_CAPACITY};
+pub use search_history_store::{SearchHistoryStore, RecentSearch, DEFAULT_SEARCH_HISTORY_CAPACITY};
+pub use bookmarks_store::{BookmarksStore, Bookmark};
+pub use sources_store::{SourcesStore, PersistedSource};
+pub use settings_store::{SettingsStore, VfsSettings};
+pub use automount_store::{AutomountStore, AutomountEntry};
+pub use credential_store::{KeychainCredentialStore, MemoryCredentialStore, DEFAULT_CREDENTIAL_SERVICE};
+pub use template_store::TemplateStore;
 pub use native_thumbnail::{NativeThumbnailAdapter, ThumbnailType};
+pub use error_mapping::VfsResultExt;
 
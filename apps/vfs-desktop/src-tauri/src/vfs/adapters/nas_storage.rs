@@ -3,16 +3,23 @@
 //! Implements storage adapter for NAS devices accessed via NFS or SMB mounts.
 //! This is essentially a specialized local storage adapter optimized for
 //! network-mounted filesystems.
+//!
+//! Shares are always read through the OS mount, so the kernel owns the SMB
+//! or NFS session and keeps it alive between operations. There is no client
+//! session here to pool or reuse; `ConnectionMonitor` only tracks whether
+//! the mount point still answers.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use parking_lot::RwLock;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tracing::{debug, info, warn};
 
-use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier};
+use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier, RetryPolicy, VfsError};
+use crate::vfs::platform::{copy_dir_tree, ensure_dir_all, is_transient, retry_with_policy};
 use crate::vfs::ports::{
     StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions
 };
@@ -42,6 +49,9 @@ pub struct NasStorageAdapter {
     
     /// Connection monitor for timeout and reconnection
     connection_monitor: crate::vfs::platform::ConnectionMonitor,
+    
+    /// How transient failures (dropped connections, stale handles) are retried
+    retry: RwLock<RetryPolicy>,
 }
 
 impl NasStorageAdapter {
@@ -70,6 +80,7 @@ impl NasStorageAdapter {
             protocol,
             server,
             connection_monitor: crate::vfs::platform::ConnectionMonitor::new(endpoint),
+            retry: RwLock::new(RetryPolicy::default()),
         }
     }
     
@@ -83,6 +94,70 @@ impl NasStorageAdapter {
         Self::new(mount_point, name, NasProtocol::SMB, server)
     }
     
+    fn retry_policy(&self) -> RetryPolicy {
+        *self.retry.read()
+    }
+    
+    /// A connection-type failure counts against the share's connection
+    /// monitor, so health checks see the mount dropping
+    async fn check_connection_failure<T>(&self, result: &Result<T>) {
+        if let Err(e) = result {
+            if is_transient(e) {
+                self.connection_monitor.mark_failed(&e.to_string()).await;
+            }
+        }
+    }
+    
+    /// One attempt at listing a directory
+    async fn list_files_once(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        let full_path = self.resolve_path(path);
+        debug!("Listing NAS files at: {:?}", full_path);
+        
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&full_path)
+            .await
+            .with_context(|| format!("Failed to read directory: {:?}", full_path))?;
+        
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let file_path = path.join(&name);
+            
+            let mut vfile = VirtualFile::new(
+                name,
+                file_path,
+                metadata.len(),
+                metadata.is_dir(),
+            );
+            
+            // NAS files are "warm" by default - accessible but slower than local
+            vfile.tier_status = TierStatus {
+                current_tier: StorageTier::Warm,
+                is_cached: false,
+                can_warm: true, // Can be cached locally
+                retrieval_time_estimate: Some(1), // ~1 second for network access
+            };
+            
+            vfile.transcodable = vfile.can_transcode();
+            
+            if let Ok(modified) = metadata.modified() {
+                vfile.last_modified = modified;
+            }
+            
+            files.push(vfile);
+        }
+        
+        files.sort_by(|a, b| {
+            match (a.is_directory, b.is_directory) {
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            }
+        });
+        
+        Ok(files)
+    }
+    
     /// Resolve a VFS path to the actual filesystem path
     fn resolve_path(&self, path: &Path) -> PathBuf {
         if path.is_absolute() && path.starts_with(&self.mount_point) {
@@ -133,26 +208,6 @@ impl NasStorageAdapter {
             0
         }
     }
-    
-    /// Copy directory recursively
-    async fn copy_dir_recursive(&self, from: &Path, to: &Path, options: &CopyOptions) -> Result<()> {
-        fs::create_dir_all(to).await?;
-        let mut entries = fs::read_dir(from).await?;
-        
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
-            let dest_path = to.join(entry.file_name());
-            let metadata = entry.metadata().await?;
-            
-            if metadata.is_dir() {
-                Box::pin(self.copy_dir_recursive(&entry_path, &dest_path, options)).await?;
-            } else {
-                fs::copy(&entry_path, &dest_path).await?;
-            }
-        }
-        
-        Ok(())
-    }
 }
 
 #[async_trait]
@@ -171,81 +226,45 @@ impl StorageAdapter for NasStorageAdapter {
     }
     
     async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
-        let full_path = self.resolve_path(path);
-        debug!("Listing NAS files at: {:?}", full_path);
-        
-        let mut files = Vec::new();
-        let mut entries = fs::read_dir(&full_path)
-            .await
-            .with_context(|| format!("Failed to read directory: {:?}", full_path))?;
-        
-        while let Some(entry) = entries.next_entry().await? {
-            let metadata = entry.metadata().await?;
-            let name = entry.file_name().to_string_lossy().to_string();
-            let file_path = path.join(&name);
-            
-            let mut vfile = VirtualFile::new(
-                name,
-                file_path,
-                metadata.len(),
-                metadata.is_dir(),
-            );
-            
-            // NAS files are "warm" by default - accessible but slower than local
-            vfile.tier_status = TierStatus {
-                current_tier: StorageTier::Warm,
-                is_cached: false,
-                can_warm: true, // Can be cached locally
-                retrieval_time_estimate: Some(1), // ~1 second for network access
-            };
-            
-            vfile.transcodable = vfile.can_transcode();
-            
-            if let Ok(modified) = metadata.modified() {
-                vfile.last_modified = modified;
-            }
-            
-            files.push(vfile);
-        }
-        
-        files.sort_by(|a, b| {
-            match (a.is_directory, b.is_directory) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            }
-        });
-        
-        Ok(files)
+        let result = retry_with_policy(&self.retry_policy(), || self.list_files_once(path)).await;
+        self.check_connection_failure(&result).await;
+        result
     }
     
     async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
-        let full_path = self.resolve_path(path);
-        fs::read(&full_path).await.with_context(|| format!("Failed to read: {:?}", full_path))
+        let full_path = &self.resolve_path(path);
+        retry_with_policy(&self.retry_policy(), || async move {
+            fs::read(full_path).await.with_context(|| format!("Failed to read: {:?}", full_path))
+        }).await
     }
     
     async fn read_file_range(&self, path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
-        let full_path = self.resolve_path(path);
-        let mut file = fs::File::open(&full_path).await?;
-        file.seek(std::io::SeekFrom::Start(offset)).await?;
-        let mut buffer = vec![0u8; length as usize];
-        let bytes_read = file.read(&mut buffer).await?;
-        buffer.truncate(bytes_read);
-        Ok(buffer)
+        let full_path = &self.resolve_path(path);
+        retry_with_policy(&self.retry_policy(), || async move {
+            let mut file = fs::File::open(full_path).await?;
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+            let mut buffer = vec![0u8; length as usize];
+            let bytes_read = file.read(&mut buffer).await?;
+            buffer.truncate(bytes_read);
+            Ok(buffer)
+        }).await
     }
     
     async fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
-        let full_path = self.resolve_path(path);
-        if let Some(parent) = full_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-        fs::write(&full_path, data).await?;
-        Ok(())
+        let full_path = &self.resolve_path(path);
+        retry_with_policy(&self.retry_policy(), || async move {
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(full_path, data).await?;
+            Ok(())
+        }).await
     }
     
     async fn get_metadata(&self, path: &Path) -> Result<VirtualFile> {
         let full_path = self.resolve_path(path);
-        let metadata = fs::metadata(&full_path).await?;
+        let target = &full_path;
+        let metadata = retry_with_policy(&self.retry_policy(), || async move { Ok(fs::metadata(target).await?) }).await?;
         
         let name = full_path
             .file_name()
@@ -299,6 +318,14 @@ impl StorageAdapter for NasStorageAdapter {
         let metadata = fs::metadata(&full_path).await?;
         Ok(metadata.len())
     }
+    
+    fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry.write() = policy;
+    }
+    
+    fn connection_failures(&self) -> u64 {
+        self.connection_monitor.failure_count()
+    }
 }
 
 // IFileOperations implementation (similar to LocalStorageAdapter and FsxOntapAdapter)
@@ -418,7 +445,7 @@ impl IFileOperations for NasStorageAdapter {
     
     async fn mkdir_p(&self, path: &Path) -> Result<()> {
         let full_path = self.resolve_path(path);
-        fs::create_dir_all(&full_path).await?;
+        ensure_dir_all(&full_path).await?;
         Ok(())
     }
     
@@ -443,7 +470,7 @@ impl IFileOperations for NasStorageAdapter {
         let to_path = self.resolve_path(to);
         
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists"));
+            return Err(VfsError::AlreadyExists("Destination already exists".to_string()).into());
         }
         
         if let Some(parent) = to_path.parent() {
@@ -455,7 +482,7 @@ impl IFileOperations for NasStorageAdapter {
             if !options.recursive {
                 return Err(anyhow::anyhow!("Cannot copy directory without recursive option"));
             }
-            self.copy_dir_recursive(&from_path, &to_path, &options).await?;
+            copy_dir_tree(&from_path, &to_path, &options).await?;
         } else {
             fs::copy(&from_path, &to_path).await?;
         }
@@ -468,7 +495,7 @@ impl IFileOperations for NasStorageAdapter {
         let to_path = self.resolve_path(to);
         
         if to_path.exists() && !options.overwrite {
-            return Err(anyhow::anyhow!("Destination already exists"));
+            return Err(VfsError::AlreadyExists("Destination already exists".to_string()).into());
         }
         
         if let Some(parent) = to_path.parent() {
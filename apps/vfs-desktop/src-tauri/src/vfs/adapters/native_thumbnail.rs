@@ -54,6 +54,27 @@ impl ThumbnailType {
     }
 }
 
+/// Still formats `perceptual_hash` can decode
+const HASHABLE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "bmp", "tiff", "tif", "webp"];
+
+/// Shrink to 9x8 grayscale and set one bit per pixel that is brighter than
+/// its right-hand neighbour
+fn difference_hash(image: &image::DynamicImage) -> u64 {
+    let small = image
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small.get_pixel(x, y)[0];
+            let right = small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+    hash
+}
+
 /// Native thumbnail generator using OS APIs
 pub struct NativeThumbnailAdapter {
     /// Cache directory for generated thumbnails
@@ -487,6 +508,26 @@ impl NativeThumbnailAdapter {
             "mpg", "mpeg", "3gp", "mxf", "prores", "r3d", "braw",
         ]
     }
+    
+    /// Whether `perceptual_hash` can decode files with this extension
+    pub fn is_hashable_extension(ext: &str) -> bool {
+        HASHABLE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+    }
+    
+    /// 64-bit difference hash (dHash) of an image file.
+    /// Re-exports, rescales and small edits of the same picture land a few
+    /// bits apart; compare hashes with `count_ones` of their XOR.
+    pub fn perceptual_hash(path: &Path) -> Result<u64> {
+        let image = image::open(path)
+            .with_context(|| format!("Failed to decode image {:?}", path))?;
+        Ok(difference_hash(&image))
+    }
+    
+    /// `perceptual_hash` of an encoded image already in memory
+    pub fn perceptual_hash_bytes(data: &[u8]) -> Result<u64> {
+        let image = image::load_from_memory(data).context("Failed to decode image")?;
+        Ok(difference_hash(&image))
+    }
 }
 
 #[cfg(test)]
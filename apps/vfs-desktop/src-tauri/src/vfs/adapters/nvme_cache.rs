@@ -3,15 +3,57 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tracing::{debug, info, warn};
 
-use crate::vfs::domain::{CacheEntry, CacheConfig, EvictionPolicy};
-use crate::vfs::ports::{CacheAdapter, CacheStats};
+use crate::vfs::domain::{CacheEntry, CacheConfig, EvictionPolicy, VfsError};
+use crate::vfs::ports::{CacheAdapter, CacheStats, CACHE_BLOCK_SIZE};
+
+/// Blocks cached for a file that isn't cached in full
+#[derive(Debug, Clone)]
+struct RangeEntry {
+    /// Sparse file holding each cached block at its original offset
+    cache_path: PathBuf,
+    
+    /// Size of the whole file at the source
+    file_size: u64,
+    
+    /// Indexes of the cached blocks
+    blocks: BTreeSet<u64>,
+    
+    last_accessed: SystemTime,
+}
+
+impl RangeEntry {
+    /// Bytes in `block`; only the last block of the file is short
+    fn block_len(&self, block: u64) -> u64 {
+        CACHE_BLOCK_SIZE.min(self.file_size.saturating_sub(block * CACHE_BLOCK_SIZE))
+    }
+    
+    /// Bytes of cached blocks
+    fn size(&self) -> u64 {
+        self.blocks.iter().map(|block| self.block_len(*block)).sum()
+    }
+    
+    fn covers(&self, offset: u64, len: u64) -> bool {
+        blocks_for(offset, len, self.file_size).all(|block| self.blocks.contains(&block))
+    }
+}
+
+/// Indexes of the blocks holding `offset..offset + len` of a `file_size`-byte file
+fn blocks_for(offset: u64, len: u64, file_size: u64) -> std::ops::Range<u64> {
+    let end = offset.saturating_add(len).min(file_size);
+    if end <= offset {
+        return 0..0;
+    }
+    offset / CACHE_BLOCK_SIZE..(end + CACHE_BLOCK_SIZE - 1) / CACHE_BLOCK_SIZE
+}
 
 /// NVMe-optimized cache adapter
 pub struct NvmeCacheAdapter {
@@ -23,6 +65,13 @@ pub struct NvmeCacheAdapter {
     
     /// Statistics
     stats: Arc<RwLock<CacheStats>>,
+    
+    /// Entries of deleted files still within the delete grace window, with
+    /// when they were deleted; their blobs stay on disk until purged
+    pending_evict: Arc<RwLock<HashMap<PathBuf, (CacheEntry, Instant)>>>,
+    
+    /// Partially cached files (path -> cached blocks)
+    ranges: Arc<RwLock<HashMap<PathBuf, RangeEntry>>>,
 }
 
 impl NvmeCacheAdapter {
@@ -36,6 +85,8 @@ impl NvmeCacheAdapter {
             config,
             entries: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(CacheStats::default())),
+            pending_evict: Arc::new(RwLock::new(HashMap::new())),
+            ranges: Arc::new(RwLock::new(HashMap::new())),
         })
     }
     
@@ -51,21 +102,132 @@ impl NvmeCacheAdapter {
         self.config.path.join(format!("{}{}", hash, extension))
     }
     
-    /// Get current cache size
+    /// Sparse file for the cached blocks of a VFS path
+    fn range_path_for(&self, path: &Path) -> PathBuf {
+        let mut ranges = self.cache_path_for(path).into_os_string();
+        ranges.push(".ranges");
+        PathBuf::from(ranges)
+    }
+    
+    /// Get current cache size, including deleted entries not yet purged and
+    /// partially cached files
     fn current_size(&self) -> u64 {
-        self.entries.read().values().map(|e| e.size).sum()
+        let live: u64 = self.entries.read().values().map(|e| e.size).sum();
+        let pending: u64 = self.pending_evict.read().values().map(|(e, _)| e.size).sum();
+        let partial: u64 = self.ranges.read().values().map(|e| e.size()).sum();
+        live + pending + partial
     }
     
-    /// Select entries for eviction based on policy
-    fn select_for_eviction(&self, required_space: u64) -> Vec<PathBuf> {
-        let entries = self.entries.read();
-        let current_size = self.current_size();
+    /// Forget the cached blocks of `path`, e.g. once the whole file is cached
+    async fn drop_ranges(&self, path: &Path) -> u64 {
+        let removed = self.ranges.write().remove(path);
+        self.remove_range_files(removed.into_iter().collect()).await
+    }
+    
+    /// Delete the sparse files of dropped range entries
+    async fn remove_range_files(&self, removed: Vec<RangeEntry>) -> u64 {
+        let mut freed = 0u64;
+        for entry in removed {
+            fs::remove_file(&entry.cache_path).await.ok();
+            freed += entry.size();
+        }
         
-        if current_size + required_space <= self.config.max_size {
-            return vec![];
+        let mut stats = self.stats.write();
+        stats.total_size = stats.total_size.saturating_sub(freed);
+        freed
+    }
+    
+    /// Drop partially cached files, least recently used first, until
+    /// `space_needed` bytes are freed
+    async fn evict_ranges(&self, space_needed: u64) -> u64 {
+        let evicted: Vec<RangeEntry> = {
+            let mut ranges = self.ranges.write();
+            let mut candidates: Vec<(PathBuf, SystemTime)> = ranges.iter()
+                .map(|(path, entry)| (path.clone(), entry.last_accessed))
+                .collect();
+            candidates.sort_by_key(|(_, last_accessed)| *last_accessed);
+            
+            let mut freed = 0u64;
+            let mut evicted = Vec::new();
+            for (path, _) in candidates {
+                if freed >= space_needed {
+                    break;
+                }
+                if let Some(entry) = ranges.remove(&path) {
+                    freed += entry.size();
+                    evicted.push(entry);
+                }
+            }
+            evicted
+        };
+        self.remove_range_files(evicted).await
+    }
+    
+    /// Free the blobs of deleted entries whose grace window has passed
+    async fn purge_expired(&self) -> u64 {
+        let grace = self.config.delete_grace;
+        let expired: Vec<CacheEntry> = {
+            let mut pending = self.pending_evict.write();
+            let paths: Vec<PathBuf> = pending.iter()
+                .filter(|(_, (_, deleted_at))| deleted_at.elapsed() >= grace)
+                .map(|(path, _)| path.clone())
+                .collect();
+            paths.iter().filter_map(|path| pending.remove(path)).map(|(entry, _)| entry).collect()
+        };
+        self.remove_blobs(expired).await
+    }
+    
+    /// Free deleted entries, oldest deletion first, until `space_needed` bytes
+    /// are freed - they go before anything still in use
+    async fn purge_pending(&self, space_needed: u64) -> u64 {
+        let purged: Vec<CacheEntry> = {
+            let mut pending = self.pending_evict.write();
+            let mut candidates: Vec<(PathBuf, Instant, u64)> = pending.iter()
+                .map(|(path, (entry, deleted_at))| (path.clone(), *deleted_at, entry.size))
+                .collect();
+            candidates.sort_by_key(|(_, deleted_at, _)| *deleted_at);
+            
+            let mut freed = 0u64;
+            let mut purged = Vec::new();
+            for (path, _, size) in candidates {
+                if freed >= space_needed {
+                    break;
+                }
+                if let Some((entry, _)) = pending.remove(&path) {
+                    purged.push(entry);
+                    freed += size;
+                }
+            }
+            purged
+        };
+        self.remove_blobs(purged).await
+    }
+    
+    /// Delete the blobs of purged entries
+    async fn remove_blobs(&self, removed: Vec<CacheEntry>) -> u64 {
+        let mut freed = 0u64;
+        for entry in removed {
+            fs::remove_file(&entry.cache_path).await.ok();
+            freed += entry.size;
+            debug!("Purged deleted cache entry: {:?}", entry.path);
         }
         
-        let space_needed = current_size + required_space - self.config.max_size;
+        let mut stats = self.stats.write();
+        stats.total_size = stats.total_size.saturating_sub(freed);
+        freed
+    }
+    
+    /// Forget a deleted entry whose blob is about to be overwritten
+    fn discard_pending(&self, path: &Path) {
+        if let Some((entry, _)) = self.pending_evict.write().remove(path) {
+            let mut stats = self.stats.write();
+            stats.total_size = stats.total_size.saturating_sub(entry.size);
+        }
+    }
+    
+    /// Pick entries in eviction-policy order until they add up to `space_needed`
+    fn select_by_policy(&self, space_needed: u64) -> Vec<PathBuf> {
+        let entries = self.entries.read();
         let mut eviction_candidates: Vec<_> = entries.iter().collect();
         
         // Sort based on eviction policy
@@ -94,6 +256,43 @@ impl NvmeCacheAdapter {
         
         to_evict
     }
+    
+    /// Remove the given entries from the index and delete their files
+    async fn evict_paths(&self, to_evict: Vec<PathBuf>) -> Result<u64> {
+        if to_evict.is_empty() {
+            return Ok(0);
+        }
+        
+        let mut freed = 0u64;
+        
+        // First, collect entries to remove without holding lock across await
+        let mut entries_to_delete: Vec<(PathBuf, u64)> = Vec::new();
+        
+        for path in to_evict {
+            let entry_opt = self.entries.write().remove(&path);
+            if let Some(entry) = entry_opt {
+                entries_to_delete.push((entry.cache_path.clone(), entry.size));
+                freed += entry.size;
+                
+                // Update stats synchronously
+                let mut stats = self.stats.write();
+                stats.total_size = stats.total_size.saturating_sub(entry.size);
+                stats.entry_count = stats.entry_count.saturating_sub(1);
+                stats.eviction_count += 1;
+            }
+        }
+        
+        // Now delete files without holding any locks
+        for (cache_path, _) in entries_to_delete {
+            if cache_path.exists() {
+                fs::remove_file(&cache_path).await.ok();
+            }
+        }
+        
+        info!("Evicted {} bytes from cache", freed);
+        
+        Ok(freed)
+    }
 }
 
 #[async_trait]
@@ -113,6 +312,14 @@ impl CacheAdapter for NvmeCacheAdapter {
         self.entries.read().contains_key(path)
     }
     
+    async fn is_range_cached(&self, path: &Path, offset: u64, len: u64) -> bool {
+        if self.is_cached(path).await {
+            return true;
+        }
+        
+        self.ranges.read().get(path).is_some_and(|entry| entry.covers(offset, len))
+    }
+    
     async fn get_cached_path(&self, path: &Path) -> Option<PathBuf> {
         if self.is_cached(path).await {
             Some(self.cache_path_for(path))
@@ -131,7 +338,9 @@ impl CacheAdapter for NvmeCacheAdapter {
         }
         
         // Write to cache
+        self.discard_pending(path);
         fs::write(&cache_path, data).await?;
+        self.drop_ranges(path).await;
         
         let now = SystemTime::now();
         let entry = CacheEntry {
@@ -178,10 +387,115 @@ impl CacheAdapter for NvmeCacheAdapter {
             .with_context(|| format!("Failed to read from cache: {:?}", cache_path))
     }
     
+    async fn cache_range(&self, path: &Path, file_size: u64, offset: u64, data: &[u8]) -> Result<()> {
+        let len = data.len() as u64;
+        let end = offset + len;
+        if offset % CACHE_BLOCK_SIZE != 0 || end > file_size || (len % CACHE_BLOCK_SIZE != 0 && end != file_size) {
+            return Err(VfsError::InvalidInput(format!(
+                "Range {}..{} of {:?} is not whole cache blocks", offset, end, path
+            )).into());
+        }
+        if len == 0 || self.is_cached(path).await {
+            return Ok(());
+        }
+        
+        // Blocks of an older version of the file are no good
+        let changed = self.ranges.read().get(path).is_some_and(|entry| entry.file_size != file_size);
+        if changed {
+            self.drop_ranges(path).await;
+        }
+        
+        if self.config.max_size > 0 {
+            self.evict_if_needed(len).await?;
+        }
+        
+        // Each block goes at its own offset; the gaps stay sparse on disk
+        let cache_path = self.range_path_for(path);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&cache_path)
+            .await
+            .with_context(|| format!("Failed to open {:?}", cache_path))?;
+        file.set_len(file_size).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        file.write_all(data).await?;
+        file.flush().await?;
+        
+        let added = {
+            let mut ranges = self.ranges.write();
+            let entry = ranges.entry(path.to_path_buf()).or_insert_with(|| RangeEntry {
+                cache_path,
+                file_size,
+                blocks: BTreeSet::new(),
+                last_accessed: SystemTime::now(),
+            });
+            entry.last_accessed = SystemTime::now();
+            
+            let mut added = 0u64;
+            for block in blocks_for(offset, len, file_size) {
+                if entry.blocks.insert(block) {
+                    added += entry.block_len(block);
+                }
+            }
+            added
+        };
+        self.stats.write().total_size += added;
+        
+        debug!("Cached range {}..{} of {:?}", offset, end, path);
+        
+        Ok(())
+    }
+    
+    async fn read_range_from_cache(&self, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+        let now = SystemTime::now();
+        let full = {
+            let mut entries = self.entries.write();
+            entries.get_mut(path).map(|entry| {
+                entry.last_accessed = now;
+                entry.access_count += 1;
+                (entry.cache_path.clone(), entry.size)
+            })
+        };
+        let (cache_path, file_size) = match full {
+            Some(found) => found,
+            None => {
+                let mut ranges = self.ranges.write();
+                let entry = ranges.get_mut(path)
+                    .filter(|entry| entry.covers(offset, len))
+                    .ok_or_else(|| VfsError::NotFound(format!(
+                        "Range {}..{} of {:?} is not cached", offset, offset.saturating_add(len), path
+                    )))?;
+                entry.last_accessed = now;
+                (entry.cache_path.clone(), entry.file_size)
+            }
+        };
+        
+        self.stats.write().hit_count += 1;
+        
+        let end = offset.saturating_add(len).min(file_size);
+        if end <= offset {
+            return Ok(Vec::new());
+        }
+        
+        let mut file = fs::File::open(&cache_path)
+            .await
+            .with_context(|| format!("Failed to read from cache: {:?}", cache_path))?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        let mut data = vec![0u8; (end - offset) as usize];
+        file.read_exact(&mut data)
+            .await
+            .with_context(|| format!("Failed to read from cache: {:?}", cache_path))?;
+        
+        Ok(data)
+    }
+    
     async fn invalidate(&self, path: &Path) -> Result<()> {
         let cache_path = self.cache_path_for(path);
         
         // Remove from index
+        self.discard_pending(path);
+        self.drop_ranges(path).await;
         if let Some(entry) = self.entries.write().remove(path) {
             // Update stats
             let mut stats = self.stats.write();
@@ -202,6 +516,8 @@ impl CacheAdapter for NvmeCacheAdapter {
     async fn clear(&self) -> Result<()> {
         // Clear index
         self.entries.write().clear();
+        self.pending_evict.write().clear();
+        self.ranges.write().clear();
         
         // Reset stats
         *self.stats.write() = CacheStats::default();
@@ -226,51 +542,149 @@ impl CacheAdapter for NvmeCacheAdapter {
     }
     
     async fn evict_if_needed(&self, required_space: u64) -> Result<u64> {
-        let to_evict = self.select_for_eviction(required_space);
+        let expired = self.purge_expired().await;
         
-        if to_evict.is_empty() {
-            return Ok(0);
+        let current_size = self.current_size();
+        if current_size + required_space <= self.config.max_size {
+            return Ok(expired);
         }
         
-        let mut freed = 0u64;
-        
-        // First, collect entries to remove without holding lock across await
-        let mut entries_to_delete: Vec<(PathBuf, u64)> = Vec::new();
+        self.evict_bytes(current_size + required_space - self.config.max_size).await
+            .map(|freed| freed + expired)
+    }
+    
+    async fn evict_bytes(&self, bytes: u64) -> Result<u64> {
+        let purged = self.purge_pending(bytes).await;
+        // Partial copies are cheap to fetch again, so they go before whole files
+        let dropped = self.evict_ranges(bytes.saturating_sub(purged)).await;
+        let to_evict = self.select_by_policy(bytes.saturating_sub(purged + dropped));
+        Ok(purged + dropped + self.evict_paths(to_evict).await?)
+    }
+    
+    async fn touch(&self, path: &Path) -> Result<()> {
+        let mut entries = self.entries.write();
+        if let Some(entry) = entries.get_mut(path) {
+            entry.last_accessed = SystemTime::now();
+            entry.access_count += 1;
+        }
+        Ok(())
+    }
+    
+    fn partial_path(&self, path: &Path) -> PathBuf {
+        let mut part = self.cache_path_for(path).into_os_string();
+        part.push(".part");
+        PathBuf::from(part)
+    }
+    
+    async fn commit_partial(&self, path: &Path) -> Result<CacheEntry> {
+        let part_path = self.partial_path(path);
+        let cache_path = self.cache_path_for(path);
+        let size = fs::metadata(&part_path)
+            .await
+            .with_context(|| format!("No partial download at {:?}", part_path))?
+            .len();
         
-        for path in to_evict {
-            let entry_opt = self.entries.write().remove(&path);
-            if let Some(entry) = entry_opt {
-                entries_to_delete.push((entry.cache_path.clone(), entry.size));
-                freed += entry.size;
-                
-                // Update stats synchronously
-                let mut stats = self.stats.write();
-                stats.total_size = stats.total_size.saturating_sub(entry.size);
-                stats.entry_count = stats.entry_count.saturating_sub(1);
-                stats.eviction_count += 1;
-            }
+        // Evict if necessary
+        if self.config.max_size > 0 {
+            self.evict_if_needed(size).await?;
         }
         
-        // Now delete files without holding any locks
-        for (cache_path, _) in entries_to_delete {
-            if cache_path.exists() {
-                fs::remove_file(&cache_path).await.ok();
+        self.discard_pending(path);
+        fs::rename(&part_path, &cache_path)
+            .await
+            .with_context(|| format!("Failed to move {:?} into cache", part_path))?;
+        self.drop_ranges(path).await;
+        
+        let now = SystemTime::now();
+        let entry = CacheEntry {
+            path: path.to_path_buf(),
+            cache_path,
+            size,
+            cached_at: now,
+            last_accessed: now,
+            access_count: 1,
+        };
+        
+        // Replace any previous entry for the same path
+        let previous = self.entries.write().insert(path.to_path_buf(), entry.clone());
+        {
+            let mut stats = self.stats.write();
+            if let Some(previous) = previous {
+                stats.total_size = stats.total_size.saturating_sub(previous.size);
+            } else {
+                stats.entry_count += 1;
             }
+            stats.total_size += size;
         }
         
-        info!("Evicted {} bytes from cache", freed);
+        debug!("Committed partial download: {:?} ({} bytes)", path, size);
         
-        Ok(freed)
+        Ok(entry)
     }
     
-    async fn touch(&self, path: &Path) -> Result<()> {
-        let mut entries = self.entries.write();
-        if let Some(entry) = entries.get_mut(path) {
-            entry.last_accessed = SystemTime::now();
-            entry.access_count += 1;
+    async fn soft_delete(&self, path: &Path) -> Result<()> {
+        self.purge_expired().await;
+        
+        // Partial copies aren't worth keeping for an undo
+        let partial: Vec<RangeEntry> = {
+            let mut ranges = self.ranges.write();
+            let paths: Vec<PathBuf> = ranges.keys().filter(|p| p.starts_with(path)).cloned().collect();
+            paths.iter().filter_map(|p| ranges.remove(p)).collect()
+        };
+        self.remove_range_files(partial).await;
+        
+        let deleted: Vec<CacheEntry> = {
+            let mut entries = self.entries.write();
+            let paths: Vec<PathBuf> = entries.keys().filter(|p| p.starts_with(path)).cloned().collect();
+            paths.iter().filter_map(|p| entries.remove(p)).collect()
+        };
+        if deleted.is_empty() {
+            return Ok(());
+        }
+        
+        {
+            let mut stats = self.stats.write();
+            stats.entry_count = stats.entry_count.saturating_sub(deleted.len() as u64);
+        }
+        
+        if self.config.delete_grace.is_zero() {
+            self.remove_blobs(deleted).await;
+            return Ok(());
+        }
+        
+        debug!("Keeping {} deleted cache entries under {:?} for {:?}", deleted.len(), path, self.config.delete_grace);
+        let now = Instant::now();
+        let mut pending = self.pending_evict.write();
+        for entry in deleted {
+            pending.insert(entry.path.clone(), (entry, now));
         }
+        
         Ok(())
     }
+    
+    async fn undo_delete(&self, path: &Path) -> Result<usize> {
+        self.purge_expired().await;
+        
+        let restored: Vec<CacheEntry> = {
+            let mut pending = self.pending_evict.write();
+            let paths: Vec<PathBuf> = pending.keys().filter(|p| p.starts_with(path)).cloned().collect();
+            paths.iter().filter_map(|p| pending.remove(p)).map(|(entry, _)| entry).collect()
+        };
+        
+        let count = restored.len();
+        {
+            let mut entries = self.entries.write();
+            for entry in restored {
+                entries.insert(entry.path.clone(), entry);
+            }
+        }
+        self.stats.write().entry_count += count as u64;
+        
+        if count > 0 {
+            info!("Restored {} deleted cache entries under {:?}", count, path);
+        }
+        Ok(count)
+    }
 }
 
 #[cfg(test)]
@@ -286,6 +700,7 @@ mod tests {
             max_size: 1024 * 1024, // 1 MB
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -316,6 +731,7 @@ mod tests {
             max_size: 100, // Very small cache
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -341,6 +757,7 @@ mod tests {
             max_size: 1024 * 1024,
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -371,6 +788,7 @@ mod tests {
             max_size: 1024 * 1024,
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -399,6 +817,7 @@ mod tests {
             max_size: 1024 * 1024,
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -425,6 +844,7 @@ mod tests {
             max_size: 1024 * 1024,
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -443,6 +863,68 @@ mod tests {
         assert!(cached_path.unwrap().exists());
     }
     
+    #[tokio::test]
+    async fn test_deleted_entries_purged_first_under_pressure() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 100,
+            eviction_policy: EvictionPolicy::LRU,
+            nvme_optimized: false,
+            ..Default::default()
+        };
+        let cache = NvmeCacheAdapter::new(config).await.unwrap();
+        
+        cache.cache_file(Path::new("/deleted.bin"), &[0u8; 40]).await.unwrap();
+        cache.cache_file(Path::new("/kept.bin"), &[1u8; 40]).await.unwrap();
+        cache.soft_delete(Path::new("/deleted.bin")).await.unwrap();
+        assert!(!cache.is_cached(Path::new("/deleted.bin")).await);
+        
+        // The deleted file's bytes make room before the live entry is touched
+        cache.cache_file(Path::new("/new.bin"), &[2u8; 40]).await.unwrap();
+        assert!(cache.is_cached(Path::new("/kept.bin")).await);
+        assert_eq!(cache.undo_delete(Path::new("/deleted.bin")).await.unwrap(), 0);
+        assert_eq!(cache.stats().await.total_size, 80);
+    }
+    
+    #[tokio::test]
+    async fn test_ranges_tracked_per_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 0,
+            eviction_policy: EvictionPolicy::LRU,
+            nvme_optimized: false,
+            ..Default::default()
+        };
+        let cache = NvmeCacheAdapter::new(config).await.unwrap();
+        
+        let block = CACHE_BLOCK_SIZE as usize;
+        let file: Vec<u8> = (0..block * 2 + 100).map(|i| (i % 251) as u8).collect();
+        let size = file.len() as u64;
+        let path = Path::new("/clip.mov");
+        
+        // Unaligned ranges are refused
+        assert!(cache.cache_range(path, size, 10, &file[10..20]).await.is_err());
+        
+        // First block and the short last block; the middle one stays missing
+        cache.cache_range(path, size, 0, &file[..block]).await.unwrap();
+        cache.cache_range(path, size, 2 * CACHE_BLOCK_SIZE, &file[block * 2..]).await.unwrap();
+        
+        assert!(!cache.is_cached(path).await);
+        assert!(cache.is_range_cached(path, 5, 100).await);
+        assert!(cache.is_range_cached(path, 2 * CACHE_BLOCK_SIZE + 10, 1000).await);
+        assert!(!cache.is_range_cached(path, CACHE_BLOCK_SIZE - 10, 20).await);
+        assert_eq!(cache.read_range_from_cache(path, 5, 100).await.unwrap(), &file[5..105]);
+        assert!(cache.read_range_from_cache(path, CACHE_BLOCK_SIZE, 1).await.is_err());
+        assert_eq!(cache.stats().await.total_size, CACHE_BLOCK_SIZE + 100);
+        
+        // Caching the whole file replaces the blocks
+        cache.cache_file(path, &file).await.unwrap();
+        assert!(cache.is_range_cached(path, CACHE_BLOCK_SIZE, 1).await);
+        assert_eq!(cache.stats().await.total_size, size);
+    }
+    
     #[test]
     fn test_eviction_policies() {
         // Test that eviction policy enum is properly defined
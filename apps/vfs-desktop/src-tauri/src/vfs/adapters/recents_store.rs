@@ -0,0 +1,198 @@
+//! Recents Store Adapter - JSON file-based "Recents" list
+//!
+//! Keeps a bounded, newest-first list of recently opened/accessed files
+//! (like Finder's Recents) and persists it to the app's data directory. A
+//! second list in the same format remembers the folders copies and moves
+//! went to, for the transfer dialogs.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::vfs::platform::replace_file;
+
+/// Default number of entries kept in the Recents list
+pub const DEFAULT_RECENTS_CAPACITY: usize = 50;
+
+/// Default number of recent copy/move destinations kept
+pub const DEFAULT_RECENT_DESTINATIONS_CAPACITY: usize = 10;
+
+/// A recently accessed file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentEntry {
+    /// Storage source the file belongs to
+    pub source_id: String,
+    
+    /// Path within the source
+    pub path: PathBuf,
+    
+    /// When the file was last opened/read (or the folder last used as a
+    /// destination)
+    pub accessed_at: DateTime<Utc>,
+}
+
+/// Recents list backed by a JSON file
+pub struct RecentsStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    /// Entries, newest first
+    entries: RwLock<VecDeque<RecentEntry>>,
+    
+    /// Maximum number of entries kept
+    capacity: RwLock<usize>,
+}
+
+impl RecentsStore {
+    /// Create a store at `store_path`, loading any existing entries
+    pub async fn new(store_path: PathBuf, capacity: usize) -> Result<Self> {
+        let store = Self {
+            store_path,
+            entries: RwLock::new(VecDeque::new()),
+            capacity: RwLock::new(capacity.max(1)),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app data directory
+    pub async fn default_store() -> Result<Self> {
+        Self::in_data_dir("recents.json", DEFAULT_RECENTS_CAPACITY).await
+    }
+    
+    /// Create the recent copy/move destinations list in the app data directory
+    pub async fn default_destinations_store() -> Result<Self> {
+        Self::in_data_dir("recent_destinations.json", DEFAULT_RECENT_DESTINATIONS_CAPACITY).await
+    }
+    
+    async fn in_data_dir(file_name: &str, capacity: usize) -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly")
+            .join("vfs");
+        
+        fs::create_dir_all(&data_dir).await?;
+        
+        Self::new(data_dir.join(file_name), capacity).await
+    }
+    
+    /// Load entries from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Recents store not found, starting fresh");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read recents store")?;
+        
+        let mut data: VecDeque<RecentEntry> = match serde_json::from_str(&content) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Ignoring corrupt recents store {:?}: {}", self.store_path, e);
+                VecDeque::new()
+            }
+        };
+        data.truncate(*self.capacity.read());
+        
+        info!("Loaded {} recent entries", data.len());
+        *self.entries.write() = data;
+        
+        Ok(())
+    }
+    
+    /// Save entries to disk
+    async fn save(&self) -> Result<()> {
+        let content = {
+            let entries = self.entries.read();
+            serde_json::to_string_pretty(&*entries)
+                .context("Failed to serialize recents")?
+        };
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        replace_file(&self.store_path, content.as_bytes()).await
+            .context("Failed to write recents store")
+    }
+    
+    /// Record an access, moving an existing entry for the same file to the front
+    pub async fn record(&self, source_id: &str, path: &Path) -> Result<()> {
+        {
+            let mut entries = self.entries.write();
+            entries.retain(|e| !(e.source_id == source_id && e.path == path));
+            entries.push_front(RecentEntry {
+                source_id: source_id.to_string(),
+                path: path.to_path_buf(),
+                accessed_at: Utc::now(),
+            });
+            entries.truncate(*self.capacity.read());
+        }
+        
+        self.save().await
+    }
+    
+    /// All entries, newest first
+    pub fn list(&self) -> Vec<RecentEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+    
+    /// Drop specific entries (e.g. files that no longer exist)
+    pub async fn remove(&self, stale: &[RecentEntry]) -> Result<()> {
+        if stale.is_empty() {
+            return Ok(());
+        }
+        
+        self.entries.write().retain(|e| {
+            !stale.iter().any(|s| s.source_id == e.source_id && s.path == e.path)
+        });
+        
+        self.save().await
+    }
+    
+    /// Remove all entries
+    pub async fn clear(&self) -> Result<()> {
+        self.entries.write().clear();
+        self.save().await
+    }
+    
+    /// Change the maximum number of entries kept
+    pub async fn set_capacity(&self, capacity: usize) -> Result<()> {
+        let capacity = capacity.max(1);
+        *self.capacity.write() = capacity;
+        self.entries.write().truncate(capacity);
+        self.save().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_recents_bounded_and_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("recents.json");
+        
+        {
+            let store = RecentsStore::new(store_path.clone(), 2).await.unwrap();
+            store.record("local", Path::new("/a.txt")).await.unwrap();
+            store.record("local", Path::new("/b.txt")).await.unwrap();
+            store.record("local", Path::new("/c.txt")).await.unwrap();
+        }
+        
+        let store = RecentsStore::new(store_path, 2).await.unwrap();
+        let paths: Vec<_> = store.list().into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec![PathBuf::from("/c.txt"), PathBuf::from("/b.txt")]);
+    }
+}
@@ -2,17 +2,29 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
 use opendal::services::S3;
 use opendal::Operator;
+use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, info, warn};
 
-use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier};
+use crate::vfs::domain::{VirtualFile, StorageSourceType, TierStatus, StorageTier, RetrievalTier, RetryPolicy, TransferTuning, VfsError, SseConfig, ArchiveNotRetrievedError};
+use crate::vfs::platform::{retry_with_policy, HttpStatusError};
 use crate::vfs::ports::{
-    StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions
+    StorageAdapter, IFileOperations, FileEntry, FileStat, CopyOptions, MoveOptions,
+    SignedUrlProvider, ListOptions, ListSortBy, page_by_offset, ObjectVersioning, ObjectVersion, ServerSideCopy,
 };
+use crate::vfs::adapters::s3_versioning::{
+    sign_request, parse_list_objects, parse_list_versions, parse_versioning_enabled, copy_source, copy_source_latest,
+    BucketEndpoint, SigV4Credentials, MAX_COPY_OBJECT_SIZE,
+};
+
+/// Days a restored copy of an archived object stays readable
+const RESTORED_COPY_DAYS: u32 = 7;
 
 /// S3 storage adapter using OpenDAL
 pub struct S3StorageAdapter {
@@ -27,22 +39,65 @@ pub struct S3StorageAdapter {
     
     /// Region
     region: String,
+    
+    /// S3-compatible endpoint; `None` for AWS
+    endpoint: Option<String>,
+    
+    /// How transient request failures are retried
+    retry: RwLock<RetryPolicy>,
+    
+    /// Listing page and part sizes
+    tuning: RwLock<TransferTuning>,
+    
+    /// List every object under a prefix rather than one level of folders
+    flat_listing: RwLock<bool>,
+    
+    /// Keys for requests OpenDAL can't make (object versions); `None` for
+    /// anonymous access
+    credentials: Option<SigV4Credentials>,
+    
+    /// Server-side encryption for every object written
+    sse: Option<SseConfig>,
+    
+    /// Refuse writes while `sse` is `None`
+    require_encryption: bool,
 }
 
+/// Region used when an S3-compatible endpoint doesn't care about regions (MinIO, Ceph)
+const DEFAULT_COMPATIBLE_REGION: &str = "us-east-1";
+
 impl S3StorageAdapter {
+    /// Create an S3 adapter.
+    ///
+    /// `endpoint` points the adapter at an S3-compatible service (MinIO, Wasabi, Ceph).
+    /// With a custom endpoint, `path_style` selects `endpoint/bucket/key` addressing
+    /// instead of `bucket.endpoint/key`. When no credentials are supplied (and none are
+    /// found in the environment), requests are sent unsigned for public buckets.
+    /// `sse` adds server-side encryption headers to every upload.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         bucket: String,
         region: String,
         access_key: Option<String>,
         secret_key: Option<String>,
         endpoint: Option<String>,
+        path_style: bool,
         name: String,
+        sse: Option<SseConfig>,
     ) -> Result<Self> {
+        // Compatible services often ignore regions, but SigV4 still needs one
+        let region = if region.trim().is_empty() && endpoint.is_some() {
+            DEFAULT_COMPATIBLE_REGION.to_string()
+        } else {
+            region
+        };
+        
         let mut builder = S3::default();
         builder.bucket(&bucket);
         builder.region(&region);
         
         // Use provided credentials, or fall back to environment variables
+        let keys_from_env = access_key.is_none();
         let access_key = access_key.or_else(|| std::env::var("AWS_ACCESS_KEY_ID").ok());
         let secret_key = secret_key.or_else(|| std::env::var("AWS_SECRET_ACCESS_KEY").ok());
         
@@ -54,6 +109,38 @@ impl S3StorageAdapter {
         }
         if let Some(ref ep) = endpoint {
             builder.endpoint(ep);
+            
+            // OpenDAL defaults to path-style addressing
+            if !path_style {
+                builder.enable_virtual_host_style();
+            }
+        }
+        
+        // Public buckets: fall back to unsigned requests if no credential can be loaded
+        let anonymous = access_key.is_none() && secret_key.is_none();
+        if anonymous {
+            builder.allow_anonymous();
+        }
+        
+        // OpenDAL sends these on PutObject, multipart uploads and copies (and
+        // the SSE-C key on reads too)
+        match &sse {
+            None => {}
+            Some(SseConfig::S3) => {
+                builder.server_side_encryption_with_s3_key();
+            }
+            Some(SseConfig::Kms { key_id }) => match key_id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+                Some(key_id) => {
+                    builder.server_side_encryption_with_customer_managed_kms_key(key_id);
+                }
+                None => {
+                    builder.server_side_encryption_with_aws_managed_kms_key();
+                }
+            },
+            Some(customer_key @ SseConfig::CustomerKey { .. }) => {
+                let key = customer_key.customer_key()?.unwrap_or_default();
+                builder.server_side_encryption_with_customer_key("AES256", &key);
+            }
         }
         
         let operator = Operator::new(builder)
@@ -66,24 +153,238 @@ impl S3StorageAdapter {
             })?
             .finish();
         
+        let credentials = match (&access_key, &secret_key) {
+            (Some(access_key_id), Some(secret_access_key)) => Some(SigV4Credentials {
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                // A session token only belongs to keys from the same environment
+                session_token: keys_from_env.then(|| std::env::var("AWS_SESSION_TOKEN").ok()).flatten(),
+            }),
+            _ => None,
+        };
+        
         let has_access_key = access_key.is_some();
         let has_secret_key = secret_key.is_some();
-        info!("S3 adapter initialized - bucket: {}, region: {}, has_access_key: {}, has_secret_key: {}, endpoint: {:?}", 
-            bucket, region, has_access_key, has_secret_key, endpoint);
+        info!("S3 adapter initialized - bucket: {}, region: {}, has_access_key: {}, has_secret_key: {}, endpoint: {:?}, path_style: {}, anonymous: {}", 
+            bucket, region, has_access_key, has_secret_key, endpoint, path_style, anonymous);
         
         Ok(Self {
             operator,
             bucket,
             name,
             region,
+            endpoint,
+            retry: RwLock::new(RetryPolicy::default()),
+            tuning: RwLock::new(TransferTuning::default()),
+            flat_listing: RwLock::new(false),
+            credentials,
+            sse,
+            require_encryption: false,
         })
     }
     
+    /// Refuse writes unless server-side encryption is configured, for buckets
+    /// whose policy demands it
+    pub fn with_required_encryption(mut self, required: bool) -> Self {
+        self.require_encryption = required;
+        self
+    }
+    
+    /// Fail with `PermissionDenied` before writing `key` unencrypted to a
+    /// bucket that requires encryption
+    fn ensure_encryption(&self, key: &str) -> Result<()> {
+        if self.require_encryption && self.sse.is_none() {
+            return Err(VfsError::PermissionDenied(format!(
+                "Bucket '{}' requires server-side encryption, but none is configured for this source; '{}' was not written",
+                self.bucket, key
+            )).into());
+        }
+        Ok(())
+    }
+    
+    /// Encryption headers for CopyObject requests we sign ourselves. An SSE-C
+    /// copy reads the source with the same key it writes the copy with.
+    fn sse_headers(&self) -> Vec<(&'static str, String)> {
+        match &self.sse {
+            None => Vec::new(),
+            Some(SseConfig::S3) => vec![("x-amz-server-side-encryption", "AES256".to_string())],
+            Some(SseConfig::Kms { key_id }) => {
+                let mut headers = vec![("x-amz-server-side-encryption", "aws:kms".to_string())];
+                if let Some(key_id) = key_id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+                    headers.push(("x-amz-server-side-encryption-aws-kms-key-id", key_id.to_string()));
+                }
+                headers
+            }
+            Some(customer_key @ SseConfig::CustomerKey { .. }) => {
+                let Ok(Some(key)) = customer_key.customer_key() else {
+                    return Vec::new();
+                };
+                let key_md5 = data_encoding::BASE64.encode(&md5::compute(&key).0);
+                let key = data_encoding::BASE64.encode(&key);
+                vec![
+                    ("x-amz-server-side-encryption-customer-algorithm", "AES256".to_string()),
+                    ("x-amz-server-side-encryption-customer-key", key.clone()),
+                    ("x-amz-server-side-encryption-customer-key-md5", key_md5.clone()),
+                    ("x-amz-copy-source-server-side-encryption-customer-algorithm", "AES256".to_string()),
+                    ("x-amz-copy-source-server-side-encryption-customer-key", key),
+                    ("x-amz-copy-source-server-side-encryption-customer-key-md5", key_md5),
+                ]
+            }
+        }
+    }
+    
+    /// CopyObject of `source` onto `key`, encrypted like any other write
+    async fn copy_object(&self, key: &str, source: &str) -> Result<String> {
+        self.ensure_encryption(key)?;
+        let sse_headers = self.sse_headers();
+        let mut headers = vec![("x-amz-copy-source", source)];
+        headers.extend(sse_headers.iter().map(|(name, value)| (*name, value.as_str())));
+        self.send_signed(reqwest::Method::PUT, key, &[], &headers)
+            .await?
+            .text()
+            .await
+            .context("Failed to read CopyObject response")
+    }
+    
+    /// Issue a HeadBucket request to verify the bucket exists and is reachable
+    pub async fn head_bucket(&self) -> Result<()> {
+        // A HEAD on the bucket root is HeadBucket; presigning reuses the operator's
+        // endpoint, addressing style and credentials
+        let request = self.operator.presign_stat("/", Duration::from_secs(60))
+            .await
+            .with_context(|| format!("Failed to sign HeadBucket request for '{}'", self.bucket))?;
+        
+        let client = reqwest::Client::new();
+        let mut builder = client.head(request.uri().to_string());
+        for (name, value) in request.header() {
+            builder = builder.header(name.as_str(), value.as_bytes());
+        }
+        
+        let response = builder.send()
+            .await
+            .with_context(|| format!("HeadBucket request failed for '{}'", self.bucket))?;
+        
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!(
+                "HeadBucket for '{}' in region '{}' returned HTTP {}",
+                self.bucket, self.region, status.as_u16()
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Scheme, host and bucket path requests for this bucket go to
+    async fn bucket_endpoint(&self) -> Result<BucketEndpoint> {
+        // The presigned HeadBucket URL carries the operator's endpoint and addressing style
+        let request = self.operator.presign_stat("/", Duration::from_secs(60))
+            .await
+            .with_context(|| format!("Failed to resolve endpoint for bucket '{}'", self.bucket))?;
+        let uri = request.uri();
+        let host = uri.authority()
+            .map(|authority| authority.as_str().to_string())
+            .ok_or_else(|| anyhow::anyhow!("S3 endpoint for '{}' has no host", self.bucket))?;
+        let mut base_path = uri.path().to_string();
+        if !base_path.ends_with('/') {
+            base_path.push('/');
+        }
+        
+        Ok(BucketEndpoint {
+            origin: format!("{}://{}", uri.scheme_str().unwrap_or("https"), host),
+            host,
+            base_path,
+            region: self.region.clone(),
+        })
+    }
+    
+    /// Sign and send a request for `key` (empty for the bucket itself)
+    async fn send_signed(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+    ) -> Result<reqwest::Response> {
+        self.send_signed_with_body(method, key, query, headers, None).await
+    }
+    
+    /// `send_signed` with a request body (sent as an unsigned payload)
+    async fn send_signed_with_body(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+        body: Option<String>,
+    ) -> Result<reqwest::Response> {
+        let endpoint = self.bucket_endpoint().await?;
+        let request = sign_request(
+            method.as_str(), &endpoint, key, query, headers, self.credentials.as_ref(), chrono::Utc::now(),
+        );
+        
+        let mut builder = reqwest::Client::new().request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = body {
+            builder = builder.body(body);
+        }
+        let response = builder.send()
+            .await
+            .with_context(|| format!("S3 request for '{}' in bucket '{}' failed", key, self.bucket))?;
+        
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let message = format!(
+            "S3 request for '{}' in bucket '{}' returned HTTP {}",
+            key, self.bucket, status.as_u16()
+        );
+        let body = response.text().await.unwrap_or_default();
+        Err(match status.as_u16() {
+            404 => VfsError::NotFound(message).into(),
+            // e.g. CopyObject from an archived object that hasn't been restored
+            403 if body.contains("<Code>InvalidObjectState</Code>") => ArchiveNotRetrievedError {
+                path: format!("/{}", key),
+                estimated_retrieval_secs: StorageTier::Archive.retrieval_secs(),
+            }.into(),
+            403 => VfsError::PermissionDenied(message).into(),
+            status => HttpStatusError { status, message }.into(),
+        })
+    }
+    
+    /// Fail with `Unsupported` unless the bucket keeps object versions
+    async fn ensure_versioning(&self) -> Result<()> {
+        let body = self.send_signed(reqwest::Method::GET, "", &[("versioning", "")], &[])
+            .await?
+            .text()
+            .await
+            .context("Failed to read GetBucketVersioning response")?;
+        
+        if !parse_versioning_enabled(&body)? {
+            return Err(VfsError::Unsupported(format!(
+                "Bucket '{}' does not have versioning enabled",
+                self.bucket
+            )).into());
+        }
+        Ok(())
+    }
+    
     /// Get the OpenDAL operator (for multipart uploads)
     pub fn operator(&self) -> &Operator {
         &self.operator
     }
     
+    fn retry_policy(&self) -> RetryPolicy {
+        *self.retry.read()
+    }
+    
+    fn tuning(&self) -> TransferTuning {
+        *self.tuning.read()
+    }
+    
     /// Convert path to S3 key
     fn to_key(&self, path: &Path) -> String {
         path.strip_prefix("/")
@@ -92,6 +393,12 @@ impl S3StorageAdapter {
             .to_string()
     }
     
+    /// Key of the zero-byte object that keeps a folder around while it's
+    /// empty (`folder/`), the convention the S3 console and most tools use
+    fn folder_marker(&self, path: &Path) -> String {
+        format!("{}/", self.to_key(path).trim_end_matches('/'))
+    }
+    
     /// Detect storage tier from S3 storage class
     pub fn detect_tier(storage_class: Option<&str>) -> StorageTier {
         match storage_class {
@@ -103,30 +410,14 @@ impl S3StorageAdapter {
             _ => StorageTier::Cold,
         }
     }
-}
-
-#[async_trait]
-impl StorageAdapter for S3StorageAdapter {
-    fn storage_type(&self) -> StorageSourceType {
-        StorageSourceType::S3
-    }
-    
-    fn name(&self) -> &str {
-        &self.name
-    }
     
-    async fn test_connection(&self) -> Result<bool> {
-        // Try to list bucket root to verify access
-        match self.operator.list("/").await {
-            Ok(_) => Ok(true),
-            Err(e) => {
-                error!("S3 connection test failed: {}", e);
-                Ok(false)
-            }
+    /// Immediate children of `path`, with ETags as checksums if `with_checksums`.
+    /// With flat listing on, every object under `path` instead.
+    async fn list_children(&self, path: &Path, with_checksums: bool) -> Result<Vec<VirtualFile>> {
+        if *self.flat_listing.read() {
+            return self.list_flat(path, with_checksums).await;
         }
-    }
-    
-    async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        
         let key = self.to_key(path);
         // For root path, use empty string; otherwise add trailing slash for prefix
         let prefix = if key.is_empty() { String::new() } else { format!("{}/", key) };
@@ -136,7 +427,15 @@ impl StorageAdapter for S3StorageAdapter {
         
         // OpenDAL's list() returns all entries with the given prefix
         // We need to filter to only immediate children
-        let entries = self.operator.list(&prefix).await
+        let (operator, list_prefix, page_size) = (&self.operator, prefix.as_str(), self.tuning().list_page_size);
+        let mut metakey = opendal::Metakey::Mode | opendal::Metakey::ContentLength;
+        if with_checksums {
+            metakey |= opendal::Metakey::Etag;
+        }
+        let entries = retry_with_policy(&self.retry_policy(), || async move {
+            Ok(operator.list_with(list_prefix).metakey(metakey).limit(page_size).await?)
+        })
+            .await
             .with_context(|| {
                 format!(
                     "Failed to list S3 objects in bucket '{}' (region: {}) with prefix '{}'. \
@@ -152,7 +451,8 @@ impl StorageAdapter for S3StorageAdapter {
         let mut seen_names = HashSet::new();
         
         for (idx, entry) in entries.iter().enumerate() {
-            let entry_name = entry.name().to_string();
+            // The full key, e.g. "folder/file.txt"; `name()` is only its last segment
+            let entry_name = entry.path().to_string();
             let metadata = entry.metadata();
             let is_dir = metadata.is_dir();
             let size = metadata.content_length();
@@ -165,14 +465,13 @@ impl StorageAdapter for S3StorageAdapter {
                 continue;
             }
             
-            // Skip if entry name exactly matches prefix (this is the directory itself)
+            // Skip the folder's own marker object ("folder/")
             if entry_name == prefix {
-                debug!("[S3] Skipping prefix directory: '{}'", entry_name);
+                debug!("[S3] Skipping folder marker: '{}'", entry_name);
                 continue;
             }
             
             // Extract immediate child name
-            // OpenDAL returns full paths from bucket root
             // At root (prefix=""), entries are like "file.txt" or "folder/"
             // In subdirectory (prefix="folder/"), entries are like "folder/file.txt" or "folder/subfolder/"
             let child_name = if !prefix.is_empty() && entry_name.starts_with(&prefix) {
@@ -229,6 +528,9 @@ impl StorageAdapter for S3StorageAdapter {
             };
             
             vfile.transcodable = vfile.can_transcode();
+            if with_checksums && !is_dir {
+                vfile.checksum = Self::etag_checksum(metadata);
+            }
             
             files.push(vfile);
         }
@@ -247,11 +549,226 @@ impl StorageAdapter for S3StorageAdapter {
         Ok(files)
     }
     
+    /// Every object under `path` without a delimiter, named by its key
+    /// relative to `path` (`day1/A001.mov`). Folder marker objects are left out.
+    async fn list_flat(&self, path: &Path, with_checksums: bool) -> Result<Vec<VirtualFile>> {
+        let key = self.to_key(path);
+        let prefix = if key.is_empty() { String::new() } else { format!("{}/", key) };
+        
+        let (operator, list_prefix, page_size) = (&self.operator, prefix.as_str(), self.tuning().list_page_size);
+        let mut metakey = opendal::Metakey::Mode | opendal::Metakey::ContentLength;
+        if with_checksums {
+            metakey |= opendal::Metakey::Etag;
+        }
+        let entries = retry_with_policy(&self.retry_policy(), || async move {
+            Ok(operator.list_with(list_prefix).metakey(metakey).limit(page_size).recursive(true).await?)
+        })
+            .await
+            .with_context(|| format!("Failed to list S3 objects in bucket '{}' with prefix '{}'", self.bucket, prefix))?;
+        
+        let mut files = Vec::new();
+        for entry in &entries {
+            let metadata = entry.metadata();
+            let relative = entry.path().strip_prefix(&prefix).unwrap_or(entry.path());
+            if metadata.is_dir() || relative.is_empty() || relative.ends_with('/') {
+                continue;
+            }
+            
+            let mut vfile = VirtualFile::new(
+                relative.to_string(),
+                PathBuf::from("/").join(format!("{}{}", prefix, relative)),
+                metadata.content_length(),
+                false,
+            );
+            vfile.tier_status = TierStatus {
+                current_tier: StorageTier::Cold,
+                is_cached: false,
+                can_warm: true,
+                retrieval_time_estimate: Some(5),
+            };
+            vfile.transcodable = vfile.can_transcode();
+            if with_checksums {
+                vfile.checksum = Self::etag_checksum(metadata);
+            }
+            files.push(vfile);
+        }
+        
+        debug!("[S3] Flat listing of '{}' returned {} objects", prefix, files.len());
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        Ok(files)
+    }
+    
+    /// An object's ETag without its quotes. Single-part uploads use the MD5 of
+    /// the content; multipart ETags look like `<hash>-<parts>`.
+    fn etag_checksum(metadata: &opendal::Metadata) -> Option<String> {
+        metadata.etag().map(|etag| etag.trim_matches('"').to_string())
+    }
+    
+    /// S3 answers a read of an archived object that hasn't been restored with
+    /// `403 InvalidObjectState`; report that as `ArchiveNotRetrievedError`
+    /// rather than a permission problem
+    fn archived_read_error(path: &Path, err: opendal::Error) -> anyhow::Error {
+        if Self::is_unrestored(&err) {
+            return Self::not_retrieved(path);
+        }
+        err.into()
+    }
+    
+    fn not_retrieved(path: &Path) -> anyhow::Error {
+        ArchiveNotRetrievedError {
+            path: path.display().to_string(),
+            estimated_retrieval_secs: StorageTier::Archive.retrieval_secs(),
+        }.into()
+    }
+    
+    fn is_unrestored(err: &opendal::Error) -> bool {
+        err.kind() == opendal::ErrorKind::PermissionDenied && err.to_string().contains("InvalidObjectState")
+    }
+}
+
+#[async_trait]
+impl SignedUrlProvider for S3StorageAdapter {
+    async fn signed_url(&self, path: &Path, expiry: Duration) -> Result<String> {
+        let key = self.to_key(path);
+        
+        // SigV4 query-string presign (X-Amz-Expires)
+        let request = self.operator.presign_read(&key, expiry)
+            .await
+            .with_context(|| format!("Failed to presign S3 URL for '{}' in bucket '{}'", key, self.bucket))?;
+        
+        Ok(request.uri().to_string())
+    }
+}
+
+#[async_trait]
+impl StorageAdapter for S3StorageAdapter {
+    fn storage_type(&self) -> StorageSourceType {
+        StorageSourceType::S3
+    }
+    
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    async fn test_connection(&self) -> Result<bool> {
+        match self.head_bucket().await {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                error!("S3 connection test failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
+    
+    async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>> {
+        self.list_children(path, false).await
+    }
+    
+    async fn list_files_with(&self, path: &Path, options: &ListOptions) -> Result<Vec<VirtualFile>> {
+        Ok(options.apply(self.list_children(path, options.with_checksums).await?))
+    }
+    
+    async fn list_files_paged(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<VirtualFile>, Option<String>)> {
+        // S3 returns keys in ascending order; any other ordering needs the full listing
+        if options.sort_by != ListSortBy::Name || !options.ascending {
+            let files = self.list_files_with(path, options).await?;
+            return page_by_offset(files, cursor.as_deref(), limit);
+        }
+        
+        let key = self.to_key(path);
+        let prefix = if key.is_empty() { String::new() } else { format!("{}/", key) };
+        // S3 returns at most 1000 keys a request
+        let limit = limit.clamp(1, 1000);
+        // A flat listing pages through every key under the prefix, in the
+        // same order `list_flat` sorts them
+        let flat = *self.flat_listing.read();
+        
+        // The cursor is ListObjectsV2's continuation token, which OpenDAL's lister
+        // keeps to itself, so the page is requested directly. Folder markers and
+        // filtered names count toward `max-keys`, so a page may come back short.
+        let max_keys = limit.to_string();
+        let mut query = vec![("list-type", "2"), ("prefix", prefix.as_str()), ("max-keys", max_keys.as_str())];
+        if !flat {
+            query.push(("delimiter", "/"));
+        }
+        if let Some(token) = cursor.as_deref() {
+            query.push(("continuation-token", token));
+        }
+        let query = &query;
+        let body = retry_with_policy(&self.retry_policy(), || async move {
+            self.send_signed(reqwest::Method::GET, "", query, &[])
+                .await?
+                .text()
+                .await
+                .context("Failed to read ListObjectsV2 response")
+        })
+            .await
+            .with_context(|| format!("Failed to list S3 objects in bucket '{}' with prefix '{}'", self.bucket, prefix))?;
+        let page = parse_list_objects(&body)?;
+        
+        let mut files = Vec::new();
+        for object in page.objects {
+            // Flat listings are named by the key below the prefix and leave
+            // out folder markers at every depth
+            let relative = object.key.strip_prefix(&prefix).unwrap_or(&object.key);
+            let child_name = if flat { relative } else { relative.trim_end_matches('/') };
+            let is_marker = flat && child_name.ends_with('/');
+            
+            // Skip the directory marker itself
+            if object.key == prefix || child_name.is_empty() || is_marker || !options.includes_name(child_name, false) {
+                continue;
+            }
+            
+            let file_path = if flat {
+                PathBuf::from("/").join(&object.key)
+            } else if path.as_os_str().is_empty() || path == Path::new("/") {
+                PathBuf::from("/").join(child_name)
+            } else {
+                path.join(child_name)
+            };
+            
+            let mut vfile = VirtualFile::new(
+                child_name.to_string(),
+                file_path,
+                object.size,
+                object.is_prefix,
+            );
+            
+            vfile.tier_status = TierStatus {
+                current_tier: StorageTier::Cold,
+                is_cached: false,
+                can_warm: true,
+                retrieval_time_estimate: Some(5),
+            };
+            
+            vfile.transcodable = vfile.can_transcode();
+            if options.with_checksums && !vfile.is_directory {
+                vfile.checksum = object.etag;
+            }
+            
+            files.push(vfile);
+        }
+        
+        debug!("[S3] Listed page of {} entries under '{}' (more: {})", files.len(), prefix, page.next.is_some());
+        
+        Ok((files, page.next))
+    }
+    
     async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
         let key = self.to_key(path);
         debug!("Reading S3 object: {}", key);
         
-        let data = self.operator.read(&key).await?;
+        let (operator, key) = (&self.operator, &key);
+        let data = retry_with_policy(&self.retry_policy(), || async move {
+            operator.read(key).await.map_err(|e| Self::archived_read_error(path, e))
+        }).await?;
         Ok(data.to_vec())
     }
     
@@ -260,24 +777,26 @@ impl StorageAdapter for S3StorageAdapter {
         debug!("Reading S3 object range: {} (offset={}, length={})", key, offset, length);
         
         // Use range read with opendal
-        let data = self.operator
-            .read_with(&key)
-            .range(offset..offset + length)
-            .await?;
+        let (operator, key) = (&self.operator, &key);
+        let data = retry_with_policy(&self.retry_policy(), || async move {
+            operator.read_with(key).range(offset..offset + length).await.map_err(|e| Self::archived_read_error(path, e))
+        }).await?;
         Ok(data.to_vec())
     }
     
     async fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
         let key = self.to_key(path);
         debug!("Writing S3 object: {}", key);
+        self.ensure_encryption(&key)?;
         
-        self.operator.write(&key, data.to_vec()).await?;
-        Ok(())
+        let (operator, key) = (&self.operator, &key);
+        retry_with_policy(&self.retry_policy(), || async move { Ok(operator.write(key, data.to_vec()).await?) }).await
     }
     
     async fn get_metadata(&self, path: &Path) -> Result<VirtualFile> {
         let key = self.to_key(path);
-        let metadata = self.operator.stat(&key).await?;
+        let (operator, key) = (&self.operator, &key);
+        let metadata = retry_with_policy(&self.retry_policy(), || async move { Ok(operator.stat(key).await?) }).await?;
         
         let name = path
             .file_name()
@@ -299,6 +818,9 @@ impl StorageAdapter for S3StorageAdapter {
         };
         
         vfile.transcodable = vfile.can_transcode();
+        if !vfile.is_directory {
+            vfile.checksum = Self::etag_checksum(&metadata);
+        }
         
         Ok(vfile)
     }
@@ -315,10 +837,7 @@ impl StorageAdapter for S3StorageAdapter {
     }
     
     async fn create_dir(&self, path: &Path) -> Result<()> {
-        let key = format!("{}/", self.to_key(path));
-        // S3 doesn't have real directories, but we can create a zero-byte object
-        self.operator.write(&key, vec![]).await?;
-        Ok(())
+        self.mkdir(path).await
     }
     
     async fn file_size(&self, path: &Path) -> Result<u64> {
@@ -326,6 +845,208 @@ impl StorageAdapter for S3StorageAdapter {
         let metadata = self.operator.stat(&key).await?;
         Ok(metadata.content_length())
     }
+    
+    fn supports_range_reads(&self) -> bool {
+        true
+    }
+    
+    async fn checksum_md5(&self, path: &Path) -> Result<Option<String>> {
+        let key = self.to_key(path);
+        let metadata = self.operator.stat(&key).await?;
+        
+        // Single-part uploads use the MD5 as ETag; multipart ETags ("<hash>-<parts>") aren't MD5s
+        Ok(metadata.etag()
+            .map(|etag| etag.trim_matches('"').to_ascii_lowercase())
+            .filter(|etag| etag.len() == 32 && etag.chars().all(|c| c.is_ascii_hexdigit())))
+    }
+    
+    fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry.write() = policy;
+    }
+    
+    fn transfer_tuning(&self) -> Option<TransferTuning> {
+        Some(self.tuning())
+    }
+    
+    fn set_transfer_tuning(&self, tuning: TransferTuning) {
+        *self.tuning.write() = tuning;
+    }
+    
+    fn flat_listing(&self) -> Option<bool> {
+        Some(*self.flat_listing.read())
+    }
+    
+    fn set_flat_listing(&self, flat: bool) {
+        *self.flat_listing.write() = flat;
+    }
+    
+    fn supports_tier_changes(&self) -> bool {
+        true
+    }
+    
+    async fn change_tier(&self, path: &Path, tier: StorageTier) -> Result<()> {
+        crate::vfs::adapters::object_storage_tiering::change_object_storage_tier(
+            &self.operator,
+            &self.storage_type(),
+            &self.to_key(path),
+            tier,
+        ).await.map_err(|e| match e.downcast_ref::<opendal::Error>() {
+            // The object has to be restored before it can be copied to a new class
+            Some(err) if Self::is_unrestored(err) => Self::not_retrieved(path),
+            _ => e,
+        })
+    }
+    
+    async fn initiate_retrieval(&self, path: &Path, tier: RetrievalTier) -> Result<()> {
+        let key = self.to_key(path);
+        let body = format!(
+            "<RestoreRequest><Days>{}</Days><GlacierJobParameters><Tier>{}</Tier></GlacierJobParameters></RestoreRequest>",
+            RESTORED_COPY_DAYS,
+            tier.as_s3_str()
+        );
+        
+        // 202 starts a restore, 200 means the restored copy already exists
+        match self.send_signed_with_body(reqwest::Method::POST, &key, &[("restore", "")], &[], Some(body)).await {
+            Ok(_) => {}
+            // RestoreAlreadyInProgress: one under way is as good as a new one
+            Err(e) if e.downcast_ref::<HttpStatusError>().is_some_and(|e| e.status == 409) => {}
+            Err(e) => return Err(e.context(format!("Failed to start retrieving '{}'", key))),
+        }
+        
+        info!("Started {:?} retrieval of '{}' in bucket '{}'", tier, key, self.bucket);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ObjectVersioning for S3StorageAdapter {
+    async fn list_versions(&self, path: &Path) -> Result<Vec<ObjectVersion>> {
+        self.ensure_versioning().await?;
+        
+        let key = self.to_key(path);
+        let mut versions = Vec::new();
+        let mut marker: Option<(String, String)> = None;
+        
+        loop {
+            let mut query = vec![("versions", ""), ("prefix", key.as_str())];
+            if let Some((key_marker, version_marker)) = &marker {
+                query.push(("key-marker", key_marker.as_str()));
+                query.push(("version-id-marker", version_marker.as_str()));
+            }
+            
+            let body = self.send_signed(reqwest::Method::GET, "", &query, &[])
+                .await?
+                .text()
+                .await
+                .context("Failed to read ListObjectVersions response")?;
+            let page = parse_list_versions(&body, &key)?;
+            versions.extend(page.versions);
+            
+            // Pages past the key itself only hold longer keys sharing the prefix
+            match page.next {
+                Some(next) if next.0 == key => marker = Some(next),
+                _ => break,
+            }
+        }
+        
+        if versions.is_empty() {
+            return Err(VfsError::NotFound(format!("No versions of '{}' in bucket '{}'", key, self.bucket)).into());
+        }
+        versions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        Ok(versions)
+    }
+    
+    async fn read_version(&self, path: &Path, version_id: &str) -> Result<Vec<u8>> {
+        self.ensure_versioning().await?;
+        
+        let key = self.to_key(path);
+        let bytes = self.send_signed(reqwest::Method::GET, &key, &[("versionId", version_id)], &[])
+            .await?
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to read version {} of '{}'", version_id, key))?;
+        Ok(bytes.to_vec())
+    }
+    
+    async fn restore_version(&self, path: &Path, version_id: &str) -> Result<()> {
+        let key = self.to_key(path);
+        let versions = self.list_versions(path).await?;
+        let version = versions.iter()
+            .find(|v| v.version_id == version_id)
+            .ok_or_else(|| VfsError::NotFound(format!("Version {} of '{}' not found", version_id, key)))?;
+        
+        if version.is_delete_marker {
+            return Err(VfsError::InvalidInput(format!(
+                "Version {} of '{}' is a delete marker and has no contents to restore",
+                version_id, key
+            )).into());
+        }
+        if version.is_latest {
+            debug!("Version {} of '{}' is already current", version_id, key);
+            return Ok(());
+        }
+        if version.size > MAX_COPY_OBJECT_SIZE {
+            return Err(VfsError::Unsupported(format!(
+                "Version {} of '{}' is larger than the 5 GiB S3 can copy in one request",
+                version_id, key
+            )).into());
+        }
+        
+        // CopyObject onto the same key makes the old version the newest one;
+        // no data passes through the app
+        let source = copy_source(&self.bucket, &key, version_id);
+        let body = self.copy_object(&key, &source).await?;
+        
+        // CopyObject can fail after sending 200, with the error in the body
+        if body.contains("<Error>") {
+            anyhow::bail!("Failed to restore version {} of '{}': {}", version_id, key, body);
+        }
+        
+        info!("Restored version {} of '{}' in bucket '{}'", version_id, key, self.bucket);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ServerSideCopy for S3StorageAdapter {
+    fn copy_scope(&self) -> Option<String> {
+        // CopyObject is signed by the destination, so its keys must be able
+        // to read the source bucket: same keys, same endpoint and region.
+        // An SSE-C source can't be read with another source's key.
+        if matches!(self.sse, Some(SseConfig::CustomerKey { .. })) {
+            return None;
+        }
+        let credentials = self.credentials.as_ref()?;
+        Some(format!(
+            "s3|{}|{}|{}",
+            self.endpoint.as_deref().unwrap_or("aws"), self.region, credentials.access_key_id
+        ))
+    }
+    
+    fn bucket(&self) -> &str {
+        &self.bucket
+    }
+    
+    async fn copy_object_from(&self, source_bucket: &str, from: &Path, to: &Path, size: u64) -> Result<()> {
+        let from_key = self.to_key(from);
+        let to_key = self.to_key(to);
+        if size > MAX_COPY_OBJECT_SIZE {
+            return Err(VfsError::Unsupported(format!(
+                "'{}' is larger than the 5 GiB S3 can copy in one request", from_key
+            )).into());
+        }
+        
+        let source = copy_source_latest(source_bucket, &from_key);
+        let body = self.copy_object(&to_key, &source).await?;
+        
+        // CopyObject can fail after sending 200, with the error in the body
+        if body.contains("<Error>") {
+            anyhow::bail!("Failed to copy '{}' from bucket '{}': {}", from_key, source_bucket, body);
+        }
+        
+        info!("Copied s3://{}/{} to s3://{}/{} server-side", source_bucket, from_key, self.bucket, to_key);
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -410,7 +1131,7 @@ impl IFileOperations for S3StorageAdapter {
     async fn read(&self, path: &Path) -> Result<Vec<u8>> {
         let key = self.to_key(path);
         debug!("Reading S3 object: {}", key);
-        let data = self.operator.read(&key).await?;
+        let data = self.operator.read(&key).await.map_err(|e| Self::archived_read_error(path, e))?;
         Ok(data.to_vec())
     }
     
@@ -419,20 +1140,59 @@ impl IFileOperations for S3StorageAdapter {
         let data = self.operator
             .read_with(&key)
             .range(offset..offset + len)
-            .await?;
+            .await
+            .map_err(|e| Self::archived_read_error(path, e))?;
         Ok(data.to_vec())
     }
     
     async fn write(&self, path: &Path, data: &[u8]) -> Result<()> {
         let key = self.to_key(path);
         debug!("Writing S3 object: {}", key);
+        self.ensure_encryption(&key)?;
         self.operator.write(&key, data.to_vec()).await?;
         Ok(())
     }
     
+    async fn write_stream(&self, path: &Path, mut chunks: BoxStream<'_, Result<Vec<u8>>>) -> Result<u64> {
+        let key = self.to_key(path);
+        debug!("Streaming write to S3 object: {}", key);
+        self.ensure_encryption(&key)?;
+        
+        // Buffered writer uploads each full buffer as a multipart part
+        let mut writer = self.operator
+            .writer_with(&key)
+            .buffer(self.tuning().upload_chunk_size)
+            .await
+            .with_context(|| format!("Failed to start upload for: {}", key))?;
+        
+        let mut written = 0u64;
+        loop {
+            let chunk = match chunks.try_next().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = writer.abort().await;
+                    return Err(e);
+                }
+            };
+            written += chunk.len() as u64;
+            if let Err(e) = writer.write(chunk).await {
+                let _ = writer.abort().await;
+                return Err(anyhow::anyhow!("Upload of '{}' failed: {}", key, e));
+            }
+        }
+        
+        writer.close()
+            .await
+            .with_context(|| format!("Failed to finalize upload for: {}", key))?;
+        
+        Ok(written)
+    }
+    
     async fn append(&self, path: &Path, data: &[u8]) -> Result<()> {
         // S3 doesn't support append, so we need to read + append + write
         let key = self.to_key(path);
+        self.ensure_encryption(&key)?;
         let mut existing = match self.operator.read(&key).await {
             Ok(d) => d.to_vec(),
             Err(_) => Vec::new(),
@@ -445,6 +1205,7 @@ impl IFileOperations for S3StorageAdapter {
     async fn write_at(&self, path: &Path, offset: u64, data: &[u8]) -> Result<()> {
         // S3 doesn't support partial writes
         let key = self.to_key(path);
+        self.ensure_encryption(&key)?;
         let mut existing = self.operator.read(&key).await?.to_vec();
         
         let end = offset as usize + data.len();
@@ -459,6 +1220,7 @@ impl IFileOperations for S3StorageAdapter {
     
     async fn truncate(&self, path: &Path, len: u64) -> Result<()> {
         let key = self.to_key(path);
+        self.ensure_encryption(&key)?;
         let mut existing = self.operator.read(&key).await?.to_vec();
         existing.truncate(len as usize);
         self.operator.write(&key, existing).await?;
@@ -466,19 +1228,25 @@ impl IFileOperations for S3StorageAdapter {
     }
     
     async fn mkdir(&self, path: &Path) -> Result<()> {
-        // S3 doesn't have real directories - create a placeholder
-        let key = format!("{}/", self.to_key(path));
-        self.operator.write(&key, vec![]).await?;
+        // S3 doesn't have real directories - write a folder marker so an
+        // empty folder still lists. OpenDAL refuses `write` to keys ending in `/`.
+        let key = self.folder_marker(path);
+        self.ensure_encryption(&key)?;
+        self.operator.create_dir(&key)
+            .await
+            .with_context(|| format!("Failed to create folder marker '{}' in bucket '{}'", key, self.bucket))?;
         Ok(())
     }
     
     async fn mkdir_p(&self, path: &Path) -> Result<()> {
-        // Same as mkdir for S3
+        // Same as mkdir for S3: parents are implied by the key, and writing a
+        // marker that's already there just writes it again, so concurrent
+        // callers both succeed
         self.mkdir(path).await
     }
     
     async fn rmdir(&self, path: &Path) -> Result<()> {
-        let key = format!("{}/", self.to_key(path));
+        let key = self.folder_marker(path);
         self.operator.delete(&key).await?;
         Ok(())
     }
@@ -507,15 +1275,16 @@ impl IFileOperations for S3StorageAdapter {
         let to_key = self.to_key(to);
         
         info!("Copying S3 object: {} -> {}", from_key, to_key);
+        self.ensure_encryption(&to_key)?;
         
         // Check if destination exists
         if !options.overwrite && self.operator.is_exist(&to_key).await? {
-            return Err(anyhow::anyhow!("Destination already exists: {}", to_key));
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {}", to_key)).into());
         }
         
         // Check if source exists
         if !self.operator.is_exist(&from_key).await? {
-            return Err(anyhow::anyhow!("Source does not exist: {}", from_key));
+            return Err(VfsError::NotFound(format!("Source does not exist: {}", from_key)).into());
         }
         
         let metadata = self.operator.stat(&from_key).await?;
@@ -802,6 +1571,7 @@ impl IFileOperations for S3StorageAdapter {
     async fn touch(&self, path: &Path) -> Result<()> {
         let key = self.to_key(path);
         if !self.operator.is_exist(&key).await? {
+            self.ensure_encryption(&key)?;
             self.operator.write(&key, vec![]).await?;
         }
         // S3 doesn't support updating mtime without rewriting
@@ -896,5 +1666,471 @@ mod tests {
     fn test_detect_tier_unknown() {
         assert_eq!(S3StorageAdapter::detect_tier(Some("UNKNOWN")), StorageTier::Cold);
     }
+    
+    #[test]
+    fn test_invalid_object_state_means_not_retrieved() {
+        let path = Path::new("/masters/A001.mov");
+        let archived = opendal::Error::new(
+            opendal::ErrorKind::PermissionDenied,
+            "S3Error { code: \"InvalidObjectState\", message: \"The operation is not valid for the object's storage class\" }",
+        );
+        let err = S3StorageAdapter::archived_read_error(path, archived);
+        assert!(matches!(VfsError::from(err), VfsError::ArchiveNotRetrieved { .. }));
+        
+        let denied = opendal::Error::new(opendal::ErrorKind::PermissionDenied, "S3Error { code: \"AccessDenied\" }");
+        let err = S3StorageAdapter::archived_read_error(path, denied);
+        assert!(matches!(VfsError::from(err), VfsError::PermissionDenied(_)));
+    }
+    
+    #[tokio::test]
+    async fn test_signed_url_has_expiry() {
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some("http://127.0.0.1:9000".to_string()),
+            true,
+            "Test".to_string(),
+            None,
+        ).await.unwrap();
+        
+        let url = adapter.signed_url(Path::new("/clips/shot 01.mov"), Duration::from_secs(900)).await.unwrap();
+        
+        assert!(url.starts_with("http://127.0.0.1:9000/"), "unexpected url: {}", url);
+        assert!(url.contains("X-Amz-Expires=900"), "missing expiry: {}", url);
+        assert!(url.contains("X-Amz-Signature="), "missing signature: {}", url);
+    }
+    
+    #[tokio::test]
+    async fn test_custom_endpoint_uses_path_style() {
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            String::new(),
+            Some("minioadmin".to_string()),
+            Some("minioadmin".to_string()),
+            Some("http://minio.local:9000".to_string()),
+            true,
+            "MinIO".to_string(),
+            None,
+        ).await.unwrap();
+        
+        let request = adapter.operator().presign_stat("clips/a.mov", Duration::from_secs(60)).await.unwrap();
+        let url = request.uri().to_string();
+        
+        assert!(url.starts_with("http://minio.local:9000/media/clips/a.mov"), "expected path-style url: {}", url);
+        assert_eq!(adapter.region, DEFAULT_COMPATIBLE_REGION);
+    }
+    
+    /// A local endpoint answering every request with a one-object
+    /// ListObjectsV2 result; request lines are pushed to `requests`
+    async fn mock_s3(requests: std::sync::Arc<parking_lot::Mutex<Vec<String>>>) -> String {
+        const LISTING: &str = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>media</Name><Prefix>clips/</Prefix><KeyCount>1</KeyCount><Delimiter>/</Delimiter>",
+            "<IsTruncated>false</IsTruncated>",
+            "<Contents><Key>clips/a.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;9b2cf535f27731c974343645a3985328&quot;</ETag><Size>5</Size>",
+            "<StorageClass>STANDARD</StorageClass></Contents>",
+            "</ListBucketResult>",
+        );
+        
+        mock_s3_with(requests, |_| LISTING).await
+    }
+    
+    /// A local endpoint answering each request with `respond(request_line)`
+    async fn mock_s3_with(
+        requests: std::sync::Arc<parking_lot::Mutex<Vec<String>>>,
+        respond: fn(&str) -> &'static str,
+    ) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let request_line = String::from_utf8_lossy(&head).lines().next().unwrap_or_default().to_string();
+                    let body = respond(&request_line);
+                    requests.lock().push(request_line);
+                    
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        endpoint
+    }
+    
+    #[tokio::test]
+    async fn test_listing_requests_tuned_page_size() {
+        let requests = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(mock_s3(requests.clone()).await),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap();
+        assert_eq!(adapter.transfer_tuning(), Some(TransferTuning::default()));
+        
+        adapter.set_transfer_tuning(TransferTuning { list_page_size: 250, ..TransferTuning::default() });
+        let files = adapter.list_files(Path::new("/clips")).await.unwrap();
+        assert!(files.iter().any(|f| f.name == "a.mov"));
+        
+        let requests = requests.lock();
+        let listing = requests.iter()
+            .find(|line| line.contains("list-type=2"))
+            .expect("a ListObjectsV2 request");
+        assert!(listing.starts_with("GET /media"), "unexpected request: {}", listing);
+        assert!(listing.contains("max-keys=250"), "page size not sent: {}", listing);
+    }
+    
+    #[tokio::test]
+    async fn test_listing_with_checksums_passes_etags() {
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(mock_s3(Default::default()).await),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap();
+        
+        let plain = adapter.list_files_with(Path::new("/clips"), &ListOptions::default()).await.unwrap();
+        assert_eq!(plain[0].checksum, None);
+        
+        let options = ListOptions { with_checksums: true, ..ListOptions::default() };
+        let files = adapter.list_files_with(Path::new("/clips"), &options).await.unwrap();
+        let clip = files.iter().find(|f| f.name == "a.mov").unwrap();
+        assert_eq!(clip.checksum.as_deref(), Some("9b2cf535f27731c974343645a3985328"));
+        
+        let (page, _) = adapter.list_files_paged(Path::new("/clips"), &options, None, 10).await.unwrap();
+        assert_eq!(page[0].checksum, clip.checksum);
+    }
+    
+    #[tokio::test]
+    async fn test_flat_and_folder_listings_of_nested_keys() {
+        const FOLDERS: &str = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>media</Name><Prefix>shoot/</Prefix><KeyCount>3</KeyCount><Delimiter>/</Delimiter>",
+            "<IsTruncated>false</IsTruncated>",
+            "<Contents><Key>shoot/notes.txt</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;a1&quot;</ETag><Size>3</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<CommonPrefixes><Prefix>shoot/day1/</Prefix></CommonPrefixes>",
+            "<CommonPrefixes><Prefix>shoot/day2/</Prefix></CommonPrefixes>",
+            "</ListBucketResult>",
+        );
+        const FLAT: &str = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>media</Name><Prefix>shoot/</Prefix><KeyCount>5</KeyCount>",
+            "<IsTruncated>false</IsTruncated>",
+            "<Contents><Key>shoot/day1/</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;d0&quot;</ETag><Size>0</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/day1/A001.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;b1&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/day1/A002.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;b2&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/day2/raw/B001.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;c1&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/notes.txt</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;a1&quot;</ETag><Size>3</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "</ListBucketResult>",
+        );
+        // The first page of three keys (marker included), then a token for the rest
+        const FLAT_FIRST: &str = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>media</Name><Prefix>shoot/</Prefix><KeyCount>3</KeyCount><MaxKeys>3</MaxKeys>",
+            "<IsTruncated>true</IsTruncated><NextContinuationToken>page-2</NextContinuationToken>",
+            "<Contents><Key>shoot/day1/</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;d0&quot;</ETag><Size>0</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/day1/A001.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;b1&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/day1/A002.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;b2&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "</ListBucketResult>",
+        );
+        const FLAT_REST: &str = concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>media</Name><Prefix>shoot/</Prefix><KeyCount>2</KeyCount><MaxKeys>3</MaxKeys>",
+            "<IsTruncated>false</IsTruncated>",
+            "<Contents><Key>shoot/day2/raw/B001.mov</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;c1&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "<Contents><Key>shoot/notes.txt</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified>",
+            "<ETag>&quot;a1&quot;</ETag><Size>3</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            "</ListBucketResult>",
+        );
+        
+        // Like S3, only answer with folders when a delimiter is asked for
+        let requests = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let endpoint = mock_s3_with(requests.clone(), |request| {
+            if request.contains("delimiter=") {
+                FOLDERS
+            } else if request.contains("continuation-token=page-2") {
+                FLAT_REST
+            } else if request.contains("max-keys=3") {
+                FLAT_FIRST
+            } else {
+                FLAT
+            }
+        }).await;
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(endpoint),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap();
+        assert_eq!(adapter.flat_listing(), Some(false));
+        
+        let folders = adapter.list_files(Path::new("/shoot")).await.unwrap();
+        let names: Vec<(&str, bool)> = folders.iter().map(|f| (f.name.as_str(), f.is_directory)).collect();
+        assert_eq!(names, vec![("day1", true), ("day2", true), ("notes.txt", false)]);
+        
+        adapter.set_flat_listing(true);
+        let flat = adapter.list_files(Path::new("/shoot")).await.unwrap();
+        let names: Vec<&str> = flat.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["day1/A001.mov", "day1/A002.mov", "day2/raw/B001.mov", "notes.txt"]);
+        assert!(flat.iter().all(|f| !f.is_directory));
+        assert_eq!(flat[2].path, PathBuf::from("/shoot/day2/raw/B001.mov"));
+        
+        // Pages follow S3's continuation token, markers left out
+        let (page, next) = adapter.list_files_paged(Path::new("/shoot"), &ListOptions::default(), None, 3).await.unwrap();
+        let names: Vec<&str> = page.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["day1/A001.mov", "day1/A002.mov"]);
+        assert_eq!(next.as_deref(), Some("page-2"));
+        
+        let (page, next) = adapter.list_files_paged(Path::new("/shoot"), &ListOptions::default(), next, 3).await.unwrap();
+        let names: Vec<&str> = page.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["day2/raw/B001.mov", "notes.txt"]);
+        assert_eq!(next, None);
+        assert!(requests.lock().iter().all(|line| !line.contains("start-after")));
+    }
+    
+    /// A local bucket keeping keys in memory: PUT and DELETE objects, HEAD
+    /// them and ListObjectsV2 with or without a `/` delimiter. Bodies are
+    /// ignored, so every object is empty.
+    async fn mock_bucket() -> (String, std::sync::Arc<parking_lot::Mutex<std::collections::BTreeSet<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        let keys: std::sync::Arc<parking_lot::Mutex<std::collections::BTreeSet<String>>> = Default::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let bucket_keys = keys.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let keys = bucket_keys.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let request_line = String::from_utf8_lossy(&head).lines().next().unwrap_or_default().to_string();
+                    let mut parts = request_line.split(' ');
+                    let (method, target) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+                    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+                    let key = urlencoding::decode(path.trim_start_matches("/media").trim_start_matches('/')).unwrap().to_string();
+                    let param = |name: &str| query.split('&')
+                        .find_map(|pair| pair.strip_prefix(&format!("{}=", name)))
+                        .map(|value| urlencoding::decode(value).unwrap().to_string());
+                    
+                    let (status, body) = match method {
+                        "PUT" => {
+                            keys.lock().insert(key);
+                            ("200 OK", String::new())
+                        }
+                        "DELETE" => {
+                            keys.lock().remove(&key);
+                            ("204 No Content", String::new())
+                        }
+                        "HEAD" if keys.lock().contains(&key) => ("200 OK", String::new()),
+                        "HEAD" => ("404 Not Found", String::new()),
+                        _ => {
+                            let prefix = param("prefix").unwrap_or_default();
+                            let delimited = param("delimiter").is_some_and(|d| !d.is_empty());
+                            let mut contents = String::new();
+                            let mut folders = std::collections::BTreeSet::new();
+                            for key in keys.lock().iter().filter(|k| k.starts_with(&prefix)) {
+                                match key[prefix.len()..].find('/') {
+                                    Some(slash) if delimited => {
+                                        folders.insert(key[..prefix.len() + slash + 1].to_string());
+                                    }
+                                    _ => contents.push_str(&format!(
+                                        "<Contents><Key>{}</Key><LastModified>2024-05-17T14:03:09.000Z</LastModified><ETag>&quot;d41d8cd98f00b204e9800998ecf8427e&quot;</ETag><Size>0</Size></Contents>",
+                                        key
+                                    )),
+                                }
+                            }
+                            let folders: String = folders.iter()
+                                .map(|folder| format!("<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>", folder))
+                                .collect();
+                            ("200 OK", format!(
+                                r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>media</Name><Prefix>{}</Prefix><IsTruncated>false</IsTruncated>{}{}</ListBucketResult>"#,
+                                prefix, contents, folders
+                            ))
+                        }
+                    };
+                    
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/xml\r\nETag: \"d41d8cd98f00b204e9800998ecf8427e\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        (endpoint, keys)
+    }
+    
+    #[tokio::test]
+    async fn test_mkdir_writes_a_folder_marker_that_lists_as_empty_folder() {
+        let (endpoint, keys) = mock_bucket().await;
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(endpoint),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap();
+        
+        adapter.mkdir(Path::new("/shoot/selects")).await.unwrap();
+        assert!(keys.lock().contains("shoot/selects/"));
+        
+        let listing = adapter.list_files(Path::new("/shoot")).await.unwrap();
+        assert_eq!(listing.len(), 1);
+        assert_eq!((listing[0].name.as_str(), listing[0].is_directory), ("selects", true));
+        assert!(adapter.list_files(Path::new("/shoot/selects")).await.unwrap().is_empty(), "the marker itself is hidden");
+        
+        adapter.rmdir(Path::new("/shoot/selects")).await.unwrap();
+        assert!(keys.lock().is_empty());
+        assert!(adapter.list_files(Path::new("/shoot")).await.unwrap().is_empty());
+    }
+    
+    /// Accepts every request with an empty 200, logging each request's line
+    /// and headers
+    async fn mock_s3_recording_headers(heads: std::sync::Arc<parking_lot::Mutex<Vec<String>>>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let heads = heads.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    let head_end = loop {
+                        if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break end + 4;
+                        }
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&request[..head_end]).to_string();
+                    let body_len: usize = head.lines()
+                        .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|len| len.trim().to_string()))
+                        .and_then(|len| len.parse().ok())
+                        .unwrap_or(0);
+                    // Take the body before answering so closing doesn't reset the connection
+                    while request.len() < head_end + body_len {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    heads.lock().push(head);
+                    
+                    let response = "HTTP/1.1 200 OK\r\nETag: \"d41d8cd98f00b204e9800998ecf8427e\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        endpoint
+    }
+    
+    #[tokio::test]
+    async fn test_sse_kms_source_sends_encryption_headers_on_upload() {
+        const KEY_ID: &str = "arn:aws:kms:us-east-1:111122223333:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+        let heads = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let endpoint = mock_s3_recording_headers(heads.clone()).await;
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(endpoint.clone()),
+            true,
+            "Mock".to_string(),
+            Some(SseConfig::Kms { key_id: Some(KEY_ID.to_string()) }),
+        ).await.unwrap().with_required_encryption(true);
+        
+        adapter.write_file(Path::new("/dailies/A001.mov"), b"frames").await.unwrap();
+        {
+            let heads = heads.lock();
+            let put = heads.iter()
+                .find(|head| head.starts_with("PUT /media/dailies/A001.mov"))
+                .expect("a PutObject request")
+                .to_ascii_lowercase();
+            assert!(put.contains("\r\nx-amz-server-side-encryption: aws:kms\r\n"), "no SSE header: {}", put);
+            assert!(
+                put.contains(&format!("\r\nx-amz-server-side-encryption-aws-kms-key-id: {}\r\n", KEY_ID)),
+                "no KMS key id: {}", put
+            );
+        }
+        
+        // A source that must encrypt but has no SSE configured writes nothing
+        let unencrypted = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(endpoint),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap().with_required_encryption(true);
+        let sent = heads.lock().len();
+        
+        let err = unencrypted.write_file(Path::new("/dailies/A002.mov"), b"frames").await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<VfsError>(), Some(VfsError::PermissionDenied(_))), "{:#}", err);
+        assert!(unencrypted.mkdir(Path::new("/dailies/selects")).await.is_err());
+        assert_eq!(heads.lock().len(), sent);
+    }
 }
-
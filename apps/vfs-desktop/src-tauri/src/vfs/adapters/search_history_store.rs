@@ -0,0 +1,187 @@
+//! Search History Store - JSON file-based list of recent searches
+//!
+//! Keeps a bounded, newest-first list of the searches the user ran, with the
+//! source and folder each searched, so they can be run again. Running the
+//! same search again moves it back to the top instead of adding a duplicate.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::vfs::platform::replace_file;
+
+/// Default number of searches kept
+pub const DEFAULT_SEARCH_HISTORY_CAPACITY: usize = 25;
+
+/// A search the user ran
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentSearch {
+    /// Storage source that was searched
+    pub source_id: String,
+    
+    /// Folder the search was limited to
+    pub root: PathBuf,
+    
+    pub query: String,
+    
+    /// Maximum number of results asked for
+    pub limit: usize,
+    
+    /// When the search was last run
+    pub searched_at: DateTime<Utc>,
+}
+
+impl RecentSearch {
+    /// Same query on the same folder; the result limit doesn't make it a different search
+    fn is_same_search(&self, source_id: &str, root: &Path, query: &str) -> bool {
+        self.source_id == source_id && self.root == root && self.query == query
+    }
+}
+
+/// Search history backed by a JSON file
+pub struct SearchHistoryStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    /// Searches, newest first
+    entries: RwLock<VecDeque<RecentSearch>>,
+    
+    /// Maximum number of searches kept
+    capacity: usize,
+}
+
+impl SearchHistoryStore {
+    /// Create a store at `store_path`, loading any existing searches
+    pub async fn new(store_path: PathBuf, capacity: usize) -> Result<Self> {
+        let store = Self {
+            store_path,
+            entries: RwLock::new(VecDeque::new()),
+            capacity: capacity.max(1),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app data directory
+    pub async fn default_store() -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly")
+            .join("vfs");
+        
+        fs::create_dir_all(&data_dir).await?;
+        
+        Self::new(data_dir.join("search_history.json"), DEFAULT_SEARCH_HISTORY_CAPACITY).await
+    }
+    
+    /// Load searches from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Search history not found, starting fresh");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read search history")?;
+        
+        let mut data: VecDeque<RecentSearch> = match serde_json::from_str(&content) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Ignoring corrupt search history {:?}: {}", self.store_path, e);
+                VecDeque::new()
+            }
+        };
+        data.truncate(self.capacity);
+        
+        info!("Loaded {} recent searches", data.len());
+        *self.entries.write() = data;
+        
+        Ok(())
+    }
+    
+    /// Save searches to disk
+    async fn save(&self) -> Result<()> {
+        let content = {
+            let entries = self.entries.read();
+            serde_json::to_string_pretty(&*entries)
+                .context("Failed to serialize search history")?
+        };
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        replace_file(&self.store_path, content.as_bytes()).await
+            .context("Failed to write search history")
+    }
+    
+    /// Record a search, moving an earlier run of the same search to the front.
+    /// Blank queries aren't recorded.
+    pub async fn record(&self, source_id: &str, root: &Path, query: &str, limit: usize) -> Result<()> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(());
+        }
+        
+        {
+            let mut entries = self.entries.write();
+            entries.retain(|e| !e.is_same_search(source_id, root, query));
+            entries.push_front(RecentSearch {
+                source_id: source_id.to_string(),
+                root: root.to_path_buf(),
+                query: query.to_string(),
+                limit,
+                searched_at: Utc::now(),
+            });
+            entries.truncate(self.capacity);
+        }
+        
+        self.save().await
+    }
+    
+    /// All searches, newest first
+    pub fn list(&self) -> Vec<RecentSearch> {
+        self.entries.read().iter().cloned().collect()
+    }
+    
+    /// Forget every search
+    pub async fn clear(&self) -> Result<()> {
+        self.entries.write().clear();
+        self.save().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_search_history_bounded_and_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("search_history.json");
+        
+        {
+            let store = SearchHistoryStore::new(store_path.clone(), 2).await.unwrap();
+            store.record("local", Path::new("/"), "A001", 100).await.unwrap();
+            store.record("local", Path::new("/"), "  ", 100).await.unwrap();
+            store.record("local", Path::new("/"), "A002", 100).await.unwrap();
+            store.record("local", Path::new("/dailies"), "A002", 100).await.unwrap();
+        }
+        
+        let store = SearchHistoryStore::new(store_path, 2).await.unwrap();
+        let searches: Vec<_> = store.list().into_iter().map(|s| (s.root, s.query)).collect();
+        assert_eq!(searches, vec![
+            (PathBuf::from("/dailies"), "A002".to_string()),
+            (PathBuf::from("/"), "A002".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,140 @@
+//! Settings Store Adapter - JSON file of app-wide VFS preferences
+//!
+//! Holds the settings that apply across sources and should survive a restart,
+//! such as the ignore list. A setting the user never changed is left out, so
+//! its default can move with the app.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::{debug, warn};
+
+use crate::vfs::domain::IgnorePatterns;
+
+/// App-wide preferences; `None` means the built-in default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VfsSettings {
+    /// Junk files hidden from listings and skipped by recursive operations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_patterns: Option<IgnorePatterns>,
+    
+    /// Seconds clipboard exports and thumbnail leftovers are kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_ttl_secs: Option<u64>,
+}
+
+/// Preferences backed by a JSON file
+pub struct SettingsStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    settings: RwLock<VfsSettings>,
+}
+
+impl SettingsStore {
+    /// Create a store at `store_path`, loading any saved settings
+    pub async fn new(store_path: PathBuf) -> Result<Self> {
+        let store = Self {
+            store_path,
+            settings: RwLock::new(VfsSettings::default()),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app config directory
+    pub async fn default_store() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly");
+        
+        fs::create_dir_all(&config_dir).await?;
+        
+        Self::new(config_dir.join("settings.json")).await
+    }
+    
+    /// Load settings from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Settings store not found, using defaults");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read settings store")?;
+        
+        let data: VfsSettings = match serde_json::from_str(&content) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Ignoring corrupt settings store {:?}: {}", self.store_path, e);
+                VfsSettings::default()
+            }
+        };
+        
+        *self.settings.write() = data;
+        
+        Ok(())
+    }
+    
+    /// Save settings to disk
+    async fn save(&self) -> Result<()> {
+        let content = {
+            let settings = self.settings.read();
+            serde_json::to_string_pretty(&*settings)
+                .context("Failed to serialize settings")?
+        };
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        fs::write(&self.store_path, content).await
+            .context("Failed to write settings store")
+    }
+    
+    /// The saved settings
+    pub fn get(&self) -> VfsSettings {
+        self.settings.read().clone()
+    }
+    
+    /// Change settings in place and save them
+    pub async fn update(&self, change: impl FnOnce(&mut VfsSettings)) -> Result<()> {
+        change(&mut self.settings.write());
+        self.save().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_settings_are_remembered() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("settings.json");
+        
+        let store = SettingsStore::new(store_path.clone()).await.unwrap();
+        assert_eq!(store.get(), VfsSettings::default());
+        
+        let patterns = IgnorePatterns::new(vec!["*.tmp".to_string()]);
+        store.update(|settings| settings.ignore_patterns = Some(patterns.clone())).await.unwrap();
+        
+        let reloaded = SettingsStore::new(store_path.clone()).await.unwrap();
+        assert_eq!(reloaded.get().ignore_patterns, Some(patterns));
+        
+        reloaded.update(|settings| settings.temp_ttl_secs = Some(600)).await.unwrap();
+        let reloaded = SettingsStore::new(store_path.clone()).await.unwrap();
+        assert_eq!(reloaded.get().temp_ttl_secs, Some(600));
+        assert!(reloaded.get().ignore_patterns.is_some());
+        
+        // A corrupt file falls back to the defaults
+        std::fs::write(&store_path, "{ not json").unwrap();
+        assert_eq!(SettingsStore::new(store_path).await.unwrap().get(), VfsSettings::default());
+    }
+}
@@ -0,0 +1,241 @@
+//! Sources Store Adapter - JSON file of sources to re-mount at startup
+//!
+//! Records the `MountConfig` of each source the user mounted so buckets and
+//! shares come back after a restart under the same id. Secrets are stripped
+//! before anything is written.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::vfs::domain::MountConfig;
+
+/// A mounted source as remembered between sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedSource {
+    /// Source id, kept so bookmarks and recents still resolve after a restart
+    pub id: String,
+    
+    pub config: MountConfig,
+}
+
+/// Persisted sources backed by a JSON file
+pub struct SourcesStore {
+    /// Path to the JSON file
+    store_path: PathBuf,
+    
+    /// Sources in the order they were mounted
+    sources: RwLock<Vec<PersistedSource>>,
+}
+
+impl SourcesStore {
+    /// Create a store at `store_path`, loading any existing entries
+    pub async fn new(store_path: PathBuf) -> Result<Self> {
+        let store = Self {
+            store_path,
+            sources: RwLock::new(Vec::new()),
+        };
+        
+        store.load().await?;
+        
+        Ok(store)
+    }
+    
+    /// Create with default path in app config directory
+    pub async fn default_store() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly");
+        
+        fs::create_dir_all(&config_dir).await?;
+        
+        Self::new(config_dir.join("sources.json")).await
+    }
+    
+    /// Load entries from disk
+    async fn load(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            debug!("Sources store not found, starting fresh");
+            return Ok(());
+        }
+        
+        let content = fs::read_to_string(&self.store_path).await
+            .context("Failed to read sources store")?;
+        
+        let data: Vec<PersistedSource> = match serde_json::from_str(&content) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Ignoring corrupt sources store {:?}: {}", self.store_path, e);
+                Vec::new()
+            }
+        };
+        
+        info!("Loaded {} persisted sources", data.len());
+        *self.sources.write() = data;
+        
+        Ok(())
+    }
+    
+    /// Save entries to disk
+    async fn save(&self) -> Result<()> {
+        let content = {
+            let sources = self.sources.read();
+            serde_json::to_string_pretty(&*sources)
+                .context("Failed to serialize sources")?
+        };
+        
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        fs::write(&self.store_path, content).await
+            .context("Failed to write sources store")
+    }
+    
+    /// Remember a source, replacing any entry with the same id
+    pub async fn upsert(&self, id: &str, config: &MountConfig) -> Result<()> {
+        {
+            let mut sources = self.sources.write();
+            let entry = PersistedSource {
+                id: id.to_string(),
+                config: config.without_secrets(),
+            };
+            match sources.iter_mut().find(|s| s.id == id) {
+                Some(existing) => *existing = entry,
+                None => sources.push(entry),
+            }
+        }
+        
+        self.save().await
+    }
+    
+    /// Change a remembered source's display name; returns whether it was
+    /// remembered
+    pub async fn rename(&self, id: &str, name: &str) -> Result<bool> {
+        self.update(id, |config| config.set_name(name.to_string())).await
+    }
+    
+    /// Change a remembered source's config in place; returns whether it was
+    /// remembered
+    pub async fn update(&self, id: &str, change: impl FnOnce(&mut MountConfig)) -> Result<bool> {
+        let updated = {
+            let mut sources = self.sources.write();
+            match sources.iter_mut().find(|s| s.id == id) {
+                Some(existing) => {
+                    change(&mut existing.config);
+                    true
+                }
+                None => false,
+            }
+        };
+        
+        if updated {
+            self.save().await?;
+        }
+        Ok(updated)
+    }
+    
+    /// Forget a source; returns whether it was remembered
+    pub async fn remove(&self, id: &str) -> Result<bool> {
+        let removed = {
+            let mut sources = self.sources.write();
+            let before = sources.len();
+            sources.retain(|s| s.id != id);
+            sources.len() != before
+        };
+        
+        if removed {
+            self.save().await?;
+        }
+        Ok(removed)
+    }
+    
+    /// All remembered sources, in mount order
+    pub fn list(&self) -> Vec<PersistedSource> {
+        self.sources.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_secrets_never_written() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("sources.json");
+        let store = SourcesStore::new(store_path.clone()).await.unwrap();
+        
+        let config = MountConfig::S3 {
+            name: "Footage".to_string(),
+            bucket: "footage".to_string(),
+            region: Some("us-east-1".to_string()),
+            access_key_id: Some("AKIAEXAMPLE".to_string()),
+            secret_access_key: Some("super-secret".to_string()),
+            credential: None,
+            endpoint: None,
+            path_style: None,
+            sse: None,
+            require_encryption: false,
+            transfer_tuning: None,
+            flat_listing: false,
+            read_only: false,
+            quota_bytes: None,
+            lifecycle_policy: None,
+            retry_policy: None,
+        };
+        store.upsert("s3-1", &config).await.unwrap();
+        
+        let written = std::fs::read_to_string(&store_path).unwrap();
+        assert!(written.contains("AKIAEXAMPLE"));
+        assert!(!written.contains("super-secret"));
+        
+        assert!(store.remove("s3-1").await.unwrap());
+        assert!(!store.remove("s3-1").await.unwrap());
+        assert!(SourcesStore::new(store_path).await.unwrap().list().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_update_is_remembered() {
+        use crate::vfs::domain::{LifecyclePolicy, StorageTier, TransferTuning};
+        
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("sources.json");
+        let store = SourcesStore::new(store_path.clone()).await.unwrap();
+        let config = MountConfig::Gcs {
+            name: "Dailies".to_string(),
+            bucket: "dailies".to_string(),
+            credentials_path: None,
+            transfer_tuning: None,
+            read_only: false,
+            quota_bytes: None,
+            lifecycle_policy: None,
+            retry_policy: None,
+        };
+        store.upsert("gcs-1", &config).await.unwrap();
+        
+        let tuning = TransferTuning { list_page_size: 250, ..TransferTuning::default() };
+        let policy = LifecyclePolicy { idle_days: 30, target_tier: StorageTier::Archive };
+        let updated = store.update("gcs-1", |config| {
+            if let Some(saved) = config.transfer_tuning_mut() {
+                *saved = Some(tuning);
+            }
+            if let Some(saved) = config.lifecycle_policy_mut() {
+                *saved = Some(policy);
+            }
+            *config.quota_bytes_mut() = Some(1024);
+        }).await.unwrap();
+        assert!(updated);
+        assert!(!store.update("missing", |_| {}).await.unwrap());
+        
+        let reloaded = SourcesStore::new(store_path).await.unwrap().list();
+        assert_eq!(reloaded[0].config.transfer_tuning(), Some(tuning));
+        assert_eq!(reloaded[0].config.lifecycle_policy(), Some(policy));
+        assert_eq!(reloaded[0].config.quota_bytes(), Some(1024));
+    }
+}
@@ -0,0 +1,91 @@
+//! Template Store Adapter - user "New Document" templates on disk
+//!
+//! Each user template is a file in the app's config directory; its file name
+//! is both the template name and the name given to files created from it.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tokio::fs;
+use tracing::info;
+
+use crate::vfs::application::templates::{builtin_templates, validate_template_name, FileTemplate};
+
+/// Directory of user templates
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    /// Store templates in `dir`
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+    
+    /// Create with default path in app config directory
+    pub fn default_store() -> Self {
+        let dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly")
+            .join("templates");
+        Self::new(dir)
+    }
+    
+    /// Save a user template, replacing one with the same name
+    pub async fn register(&self, name: &str, contents: &[u8]) -> Result<FileTemplate> {
+        validate_template_name(name)?;
+        
+        fs::create_dir_all(&self.dir).await?;
+        fs::write(self.dir.join(name), contents).await
+            .with_context(|| format!("Failed to save template {:?}", name))?;
+        
+        info!("Registered template {:?} ({} bytes)", name, contents.len());
+        Ok(FileTemplate {
+            name: name.to_string(),
+            file_name: name.to_string(),
+            contents: contents.to_vec(),
+        })
+    }
+    
+    /// Built-in templates followed by user templates (contents not loaded)
+    pub async fn list(&self) -> Result<Vec<FileTemplate>> {
+        let mut templates = builtin_templates();
+        for template in &mut templates {
+            template.contents.clear();
+        }
+        
+        let mut entries = match fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(templates),
+            Err(e) => return Err(e.into()),
+        };
+        
+        let mut user = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_file() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                user.push(FileTemplate { name: name.clone(), file_name: name, contents: Vec::new() });
+            }
+        }
+        user.sort_by(|a, b| a.name.cmp(&b.name));
+        templates.extend(user);
+        
+        Ok(templates)
+    }
+    
+    /// Look up a template by name; user templates can't shadow built-ins
+    pub async fn get(&self, name: &str) -> Result<FileTemplate> {
+        if let Some(template) = builtin_templates().into_iter().find(|t| t.name == name) {
+            return Ok(template);
+        }
+        
+        validate_template_name(name)?;
+        let contents = fs::read(self.dir.join(name)).await
+            .with_context(|| format!("Template not found: {}", name))?;
+        
+        Ok(FileTemplate {
+            name: name.to_string(),
+            file_name: name.to_string(),
+            contents,
+        })
+    }
+}
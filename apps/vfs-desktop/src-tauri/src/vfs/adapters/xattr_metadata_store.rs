@@ -0,0 +1,387 @@
+//! Xattr Metadata Store Adapter - tags stored on the files themselves
+//!
+//! Tags on local files are kept in extended attributes so they interoperate
+//! with the OS and travel with the file across renames:
+//! - macOS: Finder tags (`com.apple.metadata:_kMDItemUserTags`, binary plist)
+//! - Linux: `user.xdg.tags` (comma-separated, as used by KDE/Baloo)
+//! - Windows: an `ursly.tags` alternate data stream
+//!
+//! Everything else (favorites, ratings, comments) and all files on remote
+//! sources go to the wrapped JSON store. The JSON store also mirrors the tags
+//! of local files as an index, so tag listings and queries don't need to walk
+//! the disk; the index is refreshed whenever a file's tags are read.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::vfs::adapters::JsonMetadataStore;
+use crate::vfs::domain::{ColorLabel, FileTag, StorageTier};
+use crate::vfs::ports::metadata::{FileMetadata, FolderView, IMetadataStore, MetadataQuery, SmartFolder};
+
+/// Resolves `(source_id, vfs path)` to a local file, `None` for remote sources
+pub type LocalPathResolver = dyn Fn(&str, &Path) -> Option<PathBuf> + Send + Sync;
+
+/// Extended attribute holding the tags
+#[cfg(target_os = "macos")]
+pub const TAGS_ATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+#[cfg(all(unix, not(target_os = "macos")))]
+pub const TAGS_ATTR: &str = "user.xdg.tags";
+#[cfg(windows)]
+pub const TAGS_ATTR: &str = "ursly.tags";
+
+/// Finder label colors, indexed as stored after the `\n` in a tag entry
+#[cfg(target_os = "macos")]
+const FINDER_COLORS: [Option<ColorLabel>; 8] = [
+    None,
+    Some(ColorLabel::Gray),
+    Some(ColorLabel::Green),
+    Some(ColorLabel::Purple),
+    Some(ColorLabel::Blue),
+    Some(ColorLabel::Yellow),
+    Some(ColorLabel::Red),
+    Some(ColorLabel::Orange),
+];
+
+/// Encode tags in the platform's attribute format
+#[cfg(target_os = "macos")]
+pub fn encode_tags(tags: &[FileTag]) -> Result<Vec<u8>> {
+    let entries: Vec<String> = tags.iter().map(|tag| {
+        let index = tag.color.as_deref()
+            .and_then(ColorLabel::from_str)
+            .and_then(|c| FINDER_COLORS.iter().position(|f| *f == Some(c)));
+        match index {
+            Some(i) => format!("{}\n{}", tag.name, i),
+            None => tag.name.clone(),
+        }
+    }).collect();
+    
+    let mut buf = Vec::new();
+    plist::to_writer_binary(&mut buf, &entries).context("Failed to encode Finder tags")?;
+    Ok(buf)
+}
+
+/// Decode tags from the platform's attribute format
+#[cfg(target_os = "macos")]
+pub fn decode_tags(data: &[u8]) -> Result<Vec<FileTag>> {
+    let entries: Vec<String> = plist::from_bytes(data).context("Failed to decode Finder tags")?;
+    
+    Ok(entries.into_iter().map(|entry| match entry.split_once('\n') {
+        Some((name, index)) => {
+            let color = index.parse::<usize>().ok()
+                .and_then(|i| FINDER_COLORS.get(i).copied().flatten());
+            match color {
+                Some(c) => FileTag::with_color(name, c.as_str()),
+                None => FileTag::new(name),
+            }
+        }
+        None => FileTag::new(entry),
+    }).collect())
+}
+
+/// Encode tags in the platform's attribute format
+#[cfg(not(target_os = "macos"))]
+pub fn encode_tags(tags: &[FileTag]) -> Result<Vec<u8>> {
+    // Colors can't be represented; tag names containing the separator are dropped
+    let names: Vec<&str> = tags.iter()
+        .map(|t| t.name.as_str())
+        .filter(|n| !n.contains(','))
+        .collect();
+    Ok(names.join(",").into_bytes())
+}
+
+/// Decode tags from the platform's attribute format
+#[cfg(not(target_os = "macos"))]
+pub fn decode_tags(data: &[u8]) -> Result<Vec<FileTag>> {
+    Ok(String::from_utf8_lossy(data)
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(FileTag::new)
+        .collect())
+}
+
+/// Read the raw tag attribute, `None` if the file has none
+#[cfg(unix)]
+fn read_attr(file: &Path) -> Result<Option<Vec<u8>>> {
+    xattr::get(file, TAGS_ATTR).with_context(|| format!("Failed to read tags of {:?}", file))
+}
+
+/// Write the raw tag attribute, removing it when `data` is `None`
+#[cfg(unix)]
+fn write_attr(file: &Path, data: Option<&[u8]>) -> Result<()> {
+    let result = match data {
+        Some(data) => xattr::set(file, TAGS_ATTR, data),
+        // Removing an absent attribute is an error, so check first
+        None => match xattr::get(file, TAGS_ATTR) {
+            Ok(Some(_)) => xattr::remove(file, TAGS_ATTR),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        },
+    };
+    result.with_context(|| format!("Failed to write tags of {:?}", file))
+}
+
+/// Alternate data stream path (`file:stream`)
+#[cfg(windows)]
+fn stream_path(file: &Path) -> PathBuf {
+    let mut path = file.as_os_str().to_os_string();
+    path.push(":");
+    path.push(TAGS_ATTR);
+    PathBuf::from(path)
+}
+
+/// Read the raw tag stream, `None` if the file has none
+#[cfg(windows)]
+fn read_attr(file: &Path) -> Result<Option<Vec<u8>>> {
+    match std::fs::read(stream_path(file)) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && file.exists() => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read tags of {:?}", file)),
+    }
+}
+
+/// Write the raw tag stream, removing it when `data` is `None`
+#[cfg(windows)]
+fn write_attr(file: &Path, data: Option<&[u8]>) -> Result<()> {
+    let result = match data {
+        Some(data) => std::fs::write(stream_path(file), data),
+        None => match std::fs::remove_file(stream_path(file)) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        },
+    };
+    result.with_context(|| format!("Failed to write tags of {:?}", file))
+}
+
+/// Read the tags stored on a local file
+pub fn read_file_tags(file: &Path) -> Result<Vec<FileTag>> {
+    match read_attr(file)? {
+        Some(data) if !data.is_empty() => decode_tags(&data),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Replace the tags stored on a local file
+pub fn write_file_tags(file: &Path, tags: &[FileTag]) -> Result<()> {
+    if tags.is_empty() {
+        write_attr(file, None)
+    } else {
+        write_attr(file, Some(&encode_tags(tags)?))
+    }
+}
+
+/// Metadata store keeping local file tags in extended attributes
+pub struct XattrMetadataStore {
+    /// Non-tag metadata, remote sources and the tag index
+    fallback: JsonMetadataStore,
+    
+    /// Maps source paths to local files
+    resolve: Box<LocalPathResolver>,
+}
+
+impl XattrMetadataStore {
+    /// Wrap `fallback`, storing tags on files that `resolve` maps to local paths
+    pub fn new(
+        fallback: JsonMetadataStore,
+        resolve: impl Fn(&str, &Path) -> Option<PathBuf> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            fallback,
+            resolve: Box::new(resolve),
+        }
+    }
+    
+    /// Local file for a source path, if the source is local and the file exists
+    fn local_file(&self, source_id: &str, path: &Path) -> Option<PathBuf> {
+        (self.resolve)(source_id, path).filter(|p| p.exists())
+    }
+    
+    /// Mirror a local file's tags into the index
+    async fn index_tags(&self, source_id: &str, path: &Path, tags: Vec<FileTag>) -> Result<()> {
+        let mut metadata = self.fallback.get(source_id, path).await?.unwrap_or_default();
+        if metadata.tags != tags {
+            metadata.tags = tags;
+            self.fallback.set(source_id, path, metadata).await?;
+        }
+        Ok(())
+    }
+    
+    /// Replace a local file's tags on disk and in the index
+    async fn store_tags(&self, source_id: &str, path: &Path, file: &Path, tags: Vec<FileTag>) -> Result<()> {
+        write_file_tags(file, &tags)?;
+        debug!("Wrote {} tags to {:?}", tags.len(), file);
+        self.index_tags(source_id, path, tags).await
+    }
+}
+
+#[async_trait]
+impl IMetadataStore for XattrMetadataStore {
+    async fn get(&self, source_id: &str, path: &Path) -> Result<Option<FileMetadata>> {
+        let Some(file) = self.local_file(source_id, path) else {
+            return self.fallback.get(source_id, path).await;
+        };
+        
+        // The file is the source of truth (tags may have been set in Finder)
+        let tags = read_file_tags(&file)?;
+        self.index_tags(source_id, path, tags).await?;
+        
+        let metadata = self.fallback.get(source_id, path).await?;
+        Ok(metadata.filter(|m| !m.is_empty()))
+    }
+    
+    async fn set(&self, source_id: &str, path: &Path, metadata: FileMetadata) -> Result<()> {
+        if let Some(file) = self.local_file(source_id, path) {
+            write_file_tags(&file, &metadata.tags)?;
+        }
+        self.fallback.set(source_id, path, metadata).await
+    }
+    
+    async fn delete(&self, source_id: &str, path: &Path) -> Result<()> {
+        if let Some(file) = self.local_file(source_id, path) {
+            write_file_tags(&file, &[])?;
+        }
+        self.fallback.delete(source_id, path).await
+    }
+    
+    async fn add_tag(&self, source_id: &str, path: &Path, tag: FileTag) -> Result<()> {
+        let Some(file) = self.local_file(source_id, path) else {
+            return self.fallback.add_tag(source_id, path, tag).await;
+        };
+        
+        let mut tags = read_file_tags(&file)?;
+        if !tags.iter().any(|t| t.name == tag.name) {
+            tags.push(tag);
+        }
+        self.store_tags(source_id, path, &file, tags).await
+    }
+    
+    async fn remove_tag(&self, source_id: &str, path: &Path, tag_name: &str) -> Result<()> {
+        let Some(file) = self.local_file(source_id, path) else {
+            return self.fallback.remove_tag(source_id, path, tag_name).await;
+        };
+        
+        let mut tags = read_file_tags(&file)?;
+        tags.retain(|t| t.name != tag_name);
+        self.store_tags(source_id, path, &file, tags).await
+    }
+    
+    async fn set_favorite(&self, source_id: &str, path: &Path, is_favorite: bool) -> Result<()> {
+        self.fallback.set_favorite(source_id, path, is_favorite).await
+    }
+    
+    async fn toggle_favorite(&self, source_id: &str, path: &Path) -> Result<bool> {
+        self.fallback.toggle_favorite(source_id, path).await
+    }
+    
+    async fn set_color_label(&self, source_id: &str, path: &Path, color: Option<ColorLabel>) -> Result<()> {
+        self.fallback.set_color_label(source_id, path, color).await
+    }
+    
+    async fn set_rating(&self, source_id: &str, path: &Path, rating: Option<u8>) -> Result<()> {
+        self.fallback.set_rating(source_id, path, rating).await
+    }
+    
+    async fn set_comment(&self, source_id: &str, path: &Path, comment: Option<String>) -> Result<()> {
+        self.fallback.set_comment(source_id, path, comment).await
+    }
+    
+    async fn list_favorites(&self, source_id: &str) -> Result<Vec<String>> {
+        self.fallback.list_favorites(source_id).await
+    }
+    
+    async fn list_by_tag(&self, source_id: &str, tag_name: &str) -> Result<Vec<String>> {
+        self.fallback.list_by_tag(source_id, tag_name).await
+    }
+    
+    async fn list_by_color(&self, source_id: &str, color: ColorLabel) -> Result<Vec<String>> {
+        self.fallback.list_by_color(source_id, color).await
+    }
+    
+    async fn list_all_tags(&self, source_id: &str) -> Result<Vec<FileTag>> {
+        self.fallback.list_all_tags(source_id).await
+    }
+    
+    async fn query(&self, source_id: &str, query: &MetadataQuery) -> Result<Vec<String>> {
+        self.fallback.query(source_id, query).await
+    }
+    
+    async fn create_smart_folder(&self, name: String, source_id: String, query: MetadataQuery) -> Result<SmartFolder> {
+        self.fallback.create_smart_folder(name, source_id, query).await
+    }
+    
+    async fn list_smart_folders(&self) -> Result<Vec<SmartFolder>> {
+        self.fallback.list_smart_folders().await
+    }
+    
+    async fn delete_smart_folder(&self, id: &str) -> Result<()> {
+        self.fallback.delete_smart_folder(id).await
+    }
+    
+    async fn record_tier_transition(&self, source_id: &str, path: &Path, tier: StorageTier) -> Result<()> {
+        // Tier history never goes to the file's attributes
+        self.fallback.record_tier_transition(source_id, path, tier).await
+    }
+    
+    async fn set_folder_view(&self, source_id: &str, path: &Path, view: Option<FolderView>) -> Result<()> {
+        // Folder views never go to the folder's attributes
+        self.fallback.set_folder_view(source_id, path, view).await
+    }
+    
+    async fn vacuum(&self, source_id: &str, exists: &(dyn Fn(&Path) -> bool + Send + Sync)) -> Result<usize> {
+        // Tags in attributes go away with their file; only the fallback can go stale
+        self.fallback.vacuum(source_id, exists).await
+    }
+    
+    async fn flush(&self) -> Result<()> {
+        self.fallback.flush().await
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    /// Whether the temp filesystem accepts our attribute (tmpfs before Linux 6.6 doesn't)
+    fn xattrs_supported(dir: &Path) -> bool {
+        let probe = dir.join(".probe");
+        std::fs::write(&probe, "").unwrap();
+        xattr::set(&probe, TAGS_ATTR, b"probe").is_ok()
+    }
+    
+    #[tokio::test]
+    async fn test_tags_written_to_xattr_and_follow_rename() {
+        let dir = TempDir::new().unwrap();
+        if !xattrs_supported(dir.path()) {
+            eprintln!("Skipping: filesystem does not support extended attributes");
+            return;
+        }
+        std::fs::write(dir.path().join("shot.mov"), "frames").unwrap();
+        
+        let root = dir.path().to_path_buf();
+        let json = JsonMetadataStore::new(dir.path().join("metadata.json")).await.unwrap();
+        let store = XattrMetadataStore::new(json, move |source_id, path| {
+            (source_id == "local").then(|| root.join(path.strip_prefix("/").unwrap_or(path)))
+        });
+        
+        store.add_tag("local", Path::new("/shot.mov"), FileTag::new("approved")).await.unwrap();
+        store.set_rating("local", Path::new("/shot.mov"), Some(4)).await.unwrap();
+        
+        // Visible to other tools through the attribute itself
+        let raw = xattr::get(dir.path().join("shot.mov"), TAGS_ATTR).unwrap().unwrap();
+        assert_eq!(decode_tags(&raw).unwrap(), vec![FileTag::new("approved")]);
+        
+        // The attribute travels with the inode
+        std::fs::rename(dir.path().join("shot.mov"), dir.path().join("final.mov")).unwrap();
+        let meta = store.get("local", Path::new("/final.mov")).await.unwrap().unwrap();
+        assert_eq!(meta.tags, vec![FileTag::new("approved")]);
+        assert!(store.list_by_tag("local", "approved").await.unwrap().contains(&"/final.mov".to_string()));
+        
+        // Remote sources fall back to the JSON store
+        store.add_tag("s3", Path::new("/remote.mov"), FileTag::new("proxy")).await.unwrap();
+        let meta = store.get("s3", Path::new("/remote.mov")).await.unwrap().unwrap();
+        assert_eq!(meta.tags, vec![FileTag::new("proxy")]);
+    }
+}
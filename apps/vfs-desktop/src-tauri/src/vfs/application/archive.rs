@@ -0,0 +1,292 @@
+//! Archives - zip and tar.gz creation/extraction for local-backed sources
+//!
+//! Both directions stream file contents through the encoder/decoder, so large
+//! selections never need to fit in memory. Extraction rejects any entry whose
+//! target would land outside the destination directory (zip-slip).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::vfs::domain::IgnorePatterns;
+use crate::vfs::platform::paths::is_within;
+
+/// Progress callback - receives total bytes processed so far
+pub type ArchiveProgress = dyn Fn(u64) + Send + Sync;
+
+/// Supported archive formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from an archive's file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collect `(file, entry name, is_dir)` for the inputs, recursing into directories.
+/// Entry names are relative to each input's parent, using `/` separators.
+/// Children matching `ignore` (by path below their input) are left out.
+fn collect_entries(inputs: &[PathBuf], ignore: &IgnorePatterns) -> Result<Vec<(PathBuf, String, bool)>> {
+    fn walk(
+        path: &Path,
+        name: String,
+        relative: &Path,
+        ignore: &IgnorePatterns,
+        out: &mut Vec<(PathBuf, String, bool)>,
+    ) -> Result<()> {
+        let metadata = fs::symlink_metadata(path)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        
+        if metadata.is_dir() {
+            out.push((path.to_path_buf(), format!("{}/", name), true));
+            let mut children: Vec<_> = fs::read_dir(path)?.collect::<io::Result<_>>()?;
+            children.sort_by_key(|e| e.file_name());
+            for child in children {
+                let child_relative = relative.join(child.file_name());
+                if ignore.matches(&child_relative) {
+                    debug!("Leaving ignored {:?} out of archive", child.path());
+                    continue;
+                }
+                let child_name = format!("{}/{}", name, child.file_name().to_string_lossy());
+                walk(&child.path(), child_name, &child_relative, ignore, out)?;
+            }
+        } else if metadata.is_file() {
+            out.push((path.to_path_buf(), name, false));
+        } else {
+            warn!("Skipping non-regular file in archive: {:?}", path);
+        }
+        
+        Ok(())
+    }
+    
+    let mut entries = Vec::new();
+    for input in inputs {
+        let name = input.file_name()
+            .ok_or_else(|| anyhow::anyhow!("Cannot archive path without a name: {:?}", input))?
+            .to_string_lossy()
+            .to_string();
+        walk(input, name, Path::new(""), ignore, &mut entries)?;
+    }
+    
+    Ok(entries)
+}
+
+/// Create an archive at `dest` containing `inputs` (files or directories).
+/// Returns the number of uncompressed bytes written.
+pub fn create_archive(
+    inputs: &[PathBuf],
+    dest: &Path,
+    format: ArchiveFormat,
+    ignore: &IgnorePatterns,
+    progress: &ArchiveProgress,
+) -> Result<u64> {
+    let entries = collect_entries(inputs, ignore)?;
+    let out = BufWriter::new(
+        File::create(dest).with_context(|| format!("Failed to create archive {:?}", dest))?
+    );
+    let mut processed = 0u64;
+    
+    match format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipWriter::new(out);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .large_file(true);
+            
+            for (path, name, is_dir) in &entries {
+                if *is_dir {
+                    zip.add_directory(name.as_str(), options)?;
+                    continue;
+                }
+                zip.start_file(name.as_str(), options)?;
+                processed += io::copy(&mut BufReader::new(File::open(path)?), &mut zip)?;
+                progress(processed);
+            }
+            
+            zip.finish()?;
+        }
+        ArchiveFormat::TarGz => {
+            let encoder = flate2::write::GzEncoder::new(out, flate2::Compression::default());
+            let mut tar = tar::Builder::new(encoder);
+            
+            for (path, name, is_dir) in &entries {
+                if *is_dir {
+                    tar.append_dir(name.trim_end_matches('/'), path)?;
+                    continue;
+                }
+                tar.append_path_with_name(path, name)?;
+                processed += fs::metadata(path)?.len();
+                progress(processed);
+            }
+            
+            tar.into_inner()?.finish()?;
+        }
+    }
+    
+    debug!("Created {:?} archive {:?} ({} entries)", format, dest, entries.len());
+    Ok(processed)
+}
+
+/// Resolve an archive entry under `dest_dir`, refusing anything that escapes it
+fn safe_target(dest_dir: &Path, entry_name: &str) -> Result<PathBuf> {
+    let target = dest_dir.join(entry_name);
+    if entry_name.is_empty() || !is_within(&target, dest_dir) {
+        anyhow::bail!("Refusing to extract '{}': path escapes the destination directory", entry_name);
+    }
+    Ok(target)
+}
+
+/// Extract `archive` into `dest_dir`. Returns the extracted file paths.
+pub fn extract_archive(
+    archive: &Path,
+    dest_dir: &Path,
+    progress: &ArchiveProgress,
+) -> Result<Vec<PathBuf>> {
+    let format = ArchiveFormat::from_path(archive)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported archive format: {:?}", archive))?;
+    let file = File::open(archive).with_context(|| format!("Failed to open archive {:?}", archive))?;
+    
+    fs::create_dir_all(dest_dir)?;
+    let mut extracted = Vec::new();
+    let mut processed = 0u64;
+    
+    match format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipArchive::new(BufReader::new(file))?;
+            
+            // Validate every name up front so a malicious archive writes nothing
+            for i in 0..zip.len() {
+                safe_target(dest_dir, zip.by_index(i)?.name())?;
+            }
+            
+            for i in 0..zip.len() {
+                let mut entry = zip.by_index(i)?;
+                let target = safe_target(dest_dir, entry.name())?;
+                
+                if entry.is_dir() {
+                    fs::create_dir_all(&target)?;
+                    continue;
+                }
+                if entry.is_symlink() {
+                    warn!("Skipping symlink entry: {}", entry.name());
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                processed += io::copy(&mut entry, &mut BufWriter::new(File::create(&target)?))?;
+                progress(processed);
+                extracted.push(target);
+            }
+        }
+        ArchiveFormat::TarGz => {
+            let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(BufReader::new(file)));
+            
+            for entry in tar.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().to_string();
+                let target = safe_target(dest_dir, &name)?;
+                let entry_type = entry.header().entry_type();
+                
+                if entry_type.is_dir() {
+                    fs::create_dir_all(&target)?;
+                    continue;
+                }
+                if !entry_type.is_file() {
+                    warn!("Skipping non-regular tar entry: {}", name);
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                processed += io::copy(&mut entry, &mut BufWriter::new(File::create(&target)?))?;
+                progress(processed);
+                extracted.push(target);
+            }
+        }
+    }
+    
+    debug!("Extracted {} files from {:?}", extracted.len(), archive);
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_zip_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.edl");
+        fs::write(&a, "alpha").unwrap();
+        fs::write(&b, "TITLE: bravo").unwrap();
+        
+        let archive = dir.path().join("selection.zip");
+        let written = create_archive(&[a, b], &archive, ArchiveFormat::Zip, &IgnorePatterns::default(), &|_| {}).unwrap();
+        assert_eq!(written, 17);
+        
+        let out = dir.path().join("out");
+        let files = extract_archive(&archive, &out, &|_| {}).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(fs::read_to_string(out.join("a.txt")).unwrap(), "alpha");
+        assert_eq!(fs::read_to_string(out.join("b.edl")).unwrap(), "TITLE: bravo");
+    }
+    
+    #[test]
+    fn test_tar_gz_round_trip_with_directory() {
+        let dir = TempDir::new().unwrap();
+        let project = dir.path().join("project");
+        fs::create_dir_all(project.join("media")).unwrap();
+        fs::write(project.join("media/clip.txt"), "frames").unwrap();
+        
+        let archive = dir.path().join("project.tar.gz");
+        create_archive(&[project], &archive, ArchiveFormat::TarGz, &IgnorePatterns::default(), &|_| {}).unwrap();
+        
+        let out = dir.path().join("out");
+        extract_archive(&archive, &out, &|_| {}).unwrap();
+        assert_eq!(fs::read_to_string(out.join("project/media/clip.txt")).unwrap(), "frames");
+    }
+    
+    #[test]
+    fn test_zip_slip_rejected() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("evil.zip");
+        
+        {
+            let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            zip.start_file("ok.txt", options).unwrap();
+            zip.write_all(b"fine").unwrap();
+            zip.start_file("../escaped.txt", options).unwrap();
+            zip.write_all(b"pwned").unwrap();
+            zip.finish().unwrap();
+        }
+        
+        let out = dir.path().join("out");
+        let result = extract_archive(&archive, &out, &|_| {});
+        
+        assert!(result.is_err());
+        assert!(!dir.path().join("escaped.txt").exists());
+        assert!(!out.join("ok.txt").exists(), "nothing should be written from a malicious archive");
+    }
+}
@@ -0,0 +1,114 @@
+//! Batch Rename - name templates like `clip_{index:03}{ext}`
+//!
+//! Supported tokens:
+//! - `{index}` / `{index:03}` - position in the list, optionally zero-padded
+//! - `{name}` - original file name without extension
+//! - `{ext}` - original extension including the dot (empty if none)
+//! - `{date}` - file's modification date as `YYYY-MM-DD`
+//!
+//! The whole batch is planned before anything is renamed, so a template that
+//! maps two files to the same name fails without touching either.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// One planned or performed rename
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameMapping {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Expand `template` for one file
+pub fn render_template(template: &str, index: u64, path: &Path, date: NaiveDate) -> Result<String> {
+    let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = path.extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let close = rest[open..].find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed '{{' in template: {}", template))?;
+        let token = &rest[open + 1..open + close];
+        
+        match token.split_once(':') {
+            Some(("index", width)) => {
+                let width: usize = width.parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid index width in {{{}}}", token))?;
+                out.push_str(&format!("{:0width$}", index, width = width));
+            }
+            None if token == "index" => out.push_str(&index.to_string()),
+            None if token == "name" => out.push_str(&name),
+            None if token == "ext" => out.push_str(&ext),
+            None if token == "date" => out.push_str(&date.format("%Y-%m-%d").to_string()),
+            _ => return Err(anyhow::anyhow!("Unknown template token {{{}}}", token)),
+        }
+        
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    
+    if out.is_empty() || out == "." || out == ".." || out.contains('/') || out.contains('\\') {
+        return Err(anyhow::anyhow!("Template produced an invalid file name: {:?}", out));
+    }
+    
+    Ok(out)
+}
+
+/// Plan renames for `files` (`(path, modification date)`, in listed order).
+/// Fails if two files would end up with the same path.
+pub fn plan_renames(files: &[(PathBuf, NaiveDate)], template: &str, start_index: u64) -> Result<Vec<RenameMapping>> {
+    let mut plan = Vec::with_capacity(files.len());
+    let mut targets = HashSet::new();
+    
+    for (i, (path, date)) in files.iter().enumerate() {
+        let new_name = render_template(template, start_index + i as u64, path, *date)?;
+        let to = path.parent().unwrap_or(Path::new("/")).join(new_name);
+        
+        if !targets.insert(to.clone()) {
+            return Err(anyhow::anyhow!("Template maps more than one file to {:?}", to));
+        }
+        plan.push(RenameMapping { from: path.clone(), to });
+    }
+    
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 9).unwrap()
+    }
+    
+    #[test]
+    fn test_tokens() {
+        let path = Path::new("/ingest/A001_C002.mov");
+        assert_eq!(render_template("clip_{index:03}.mov", 7, path, day()).unwrap(), "clip_007.mov");
+        assert_eq!(render_template("{date}_{name}_{index}{ext}", 12, path, day()).unwrap(), "2024-03-09_A001_C002_12.mov");
+        assert!(render_template("{bogus}", 1, path, day()).is_err());
+        assert!(render_template("{index", 1, path, day()).is_err());
+        assert!(render_template("../{name}", 1, path, day()).is_err());
+    }
+    
+    #[test]
+    fn test_plan_detects_collisions() {
+        let files = vec![
+            (PathBuf::from("/a.mov"), day()),
+            (PathBuf::from("/b.mov"), day()),
+        ];
+        
+        let plan = plan_renames(&files, "shot_{index:02}{ext}", 1).unwrap();
+        assert_eq!(plan[1].to, PathBuf::from("/shot_02.mov"));
+        
+        assert!(plan_renames(&files, "shot{ext}", 1).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Case Sensitivity - names that collide where case is ignored
+//!
+//! macOS and Windows volumes usually ignore case; Linux filesystems and
+//! object stores don't. "A001.MOV" and "a001.mov" are two files on a Linux
+//! NAS but one on an APFS volume, so copying both there merges them into
+//! whichever lands last. A source's behavior is asked of its filesystem
+//! where possible; otherwise, right before a copy writes there, it's probed
+//! by writing a file and looking it up again under a different case.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::vfs::ports::IFileOperations;
+
+/// Name prefix of the probe file, which is removed straight away
+pub const CASE_PROBE_PREFIX: &str = ".ursly-case-probe-";
+
+/// Whether `dir` tells names apart by case: writes `<probe>A`, checks
+/// whether `<probe>a` exists, then removes the probe
+pub async fn probe_case_sensitivity(file_ops: &dyn IFileOperations, dir: &Path) -> Result<bool> {
+    let stem = format!("{}{}-", CASE_PROBE_PREFIX, uuid::Uuid::new_v4().simple());
+    let upper = dir.join(format!("{}A", stem));
+    let lower = dir.join(format!("{}a", stem));
+    
+    file_ops.write(&upper, &[]).await?;
+    let found = file_ops.exists(&lower).await;
+    if let Err(e) = file_ops.rm(&upper).await {
+        debug!("Failed to remove case probe {:?}: {:#}", upper, e);
+    }
+    Ok(!found?)
+}
+
+/// Groups of paths that would name the same file on a case-insensitive
+/// destination, in the order given. Empty when the destination is
+/// case-sensitive.
+pub fn case_collisions<'a>(paths: impl IntoIterator<Item = &'a Path>, case_sensitive: bool) -> Vec<Vec<PathBuf>> {
+    if case_sensitive {
+        return Vec::new();
+    }
+    
+    let mut order = Vec::new();
+    let mut groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for path in paths {
+        let folded = path.to_string_lossy().to_lowercase();
+        groups.entry(folded.clone())
+            .or_insert_with(|| {
+                order.push(folded);
+                Vec::new()
+            })
+            .push(path.to_path_buf());
+    }
+    
+    order.into_iter()
+        .filter_map(|folded| groups.remove(&folded))
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[test]
+    fn test_names_differing_only_in_case_collide_when_case_is_ignored() {
+        let paths = [
+            Path::new("/Project/File.MOV"),
+            Path::new("/Project/notes.txt"),
+            Path::new("/Project/file.mov"),
+            Path::new("/Project/Renders"),
+            Path::new("/Project/renders"),
+            Path::new("/Project/FILE.mov"),
+        ];
+        
+        let collisions = case_collisions(paths, false);
+        assert_eq!(collisions, vec![
+            vec![PathBuf::from("/Project/File.MOV"), PathBuf::from("/Project/file.mov"), PathBuf::from("/Project/FILE.mov")],
+            vec![PathBuf::from("/Project/Renders"), PathBuf::from("/Project/renders")],
+        ]);
+        
+        assert!(case_collisions(paths, true).is_empty());
+        assert!(case_collisions([Path::new("/a.mov"), Path::new("/b.mov")], false).is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_probe_leaves_nothing_behind() {
+        let dir = TempDir::new().unwrap();
+        let file_ops = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        
+        let sensitive = probe_case_sensitivity(&file_ops, Path::new("/")).await.unwrap();
+        // Linux filesystems tell case apart; APFS and NTFS usually don't
+        if cfg!(target_os = "linux") {
+            assert!(sensitive);
+        }
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
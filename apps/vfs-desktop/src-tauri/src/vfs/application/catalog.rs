@@ -0,0 +1,384 @@
+//! Catalog Indexer - searchable index of every file on a source
+//!
+//! Searching by walking a bucket or share costs a listing per folder on every
+//! query. The indexer crawls a source once in the background and keeps one row
+//! per entry; changes reported through `VfsService::invalidate_listing` (writes,
+//! deletes, the file watcher) are collected for a moment and then re-list just
+//! the affected paths. Sources that haven't been indexed yet are searched by
+//! crawling, as before.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::vfs::application::lifecycle::list_tree;
+use crate::vfs::domain::{StorageTier, VirtualFile};
+use crate::vfs::ports::StorageAdapter;
+
+/// Results returned by a search when the caller doesn't say
+pub const DEFAULT_SEARCH_LIMIT: usize = 500;
+
+/// How long changes are collected before the index re-lists them, so a burst
+/// of writes (a copy, an unzip) costs one refresh per folder
+pub const REFRESH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// One indexed file or folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub modified: DateTime<Utc>,
+    pub tier: StorageTier,
+    /// Lowercased, without the dot
+    pub extension: Option<String>,
+    pub is_directory: bool,
+}
+
+impl From<&VirtualFile> for CatalogEntry {
+    fn from(file: &VirtualFile) -> Self {
+        Self {
+            path: file.path.clone(),
+            name: file.name.clone(),
+            size: file.size.bytes(),
+            modified: file.last_modified.into(),
+            tier: file.tier_status.current_tier,
+            extension: (!file.is_directory)
+                .then(|| file.path.extension().map(|e| e.to_string_lossy().to_lowercase()))
+                .flatten(),
+            is_directory: file.is_directory,
+        }
+    }
+}
+
+/// Where a source's index stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexState {
+    NotIndexed,
+    Indexing,
+    Ready,
+    Failed,
+}
+
+/// Progress and size of a source's index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexStatus {
+    pub state: IndexState,
+    /// Rows in the index (the previous index's while re-indexing)
+    pub entries: usize,
+    /// When the last full crawl finished
+    pub indexed_at: Option<DateTime<Utc>>,
+    /// Why the last crawl failed
+    pub error: Option<String>,
+}
+
+struct SourceCatalog {
+    state: IndexState,
+    entries: BTreeMap<PathBuf, CatalogEntry>,
+    indexed_at: Option<DateTime<Utc>>,
+    error: Option<String>,
+    /// Paths changed while a crawl was running, refreshed once it finishes
+    pending: Vec<PathBuf>,
+    /// Paths changed since the last `flush`
+    dirty: Vec<PathBuf>,
+    /// Whether a `flush` is already on its way
+    flush_scheduled: bool,
+}
+
+/// Whether `name` matches a search `query` (case-insensitive substring)
+pub fn name_matches(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Per-source file catalogs
+#[derive(Default)]
+pub struct CatalogIndexer {
+    catalogs: RwLock<HashMap<String, SourceCatalog>>,
+}
+
+impl CatalogIndexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn status(&self, source_id: &str) -> IndexStatus {
+        match self.catalogs.read().get(source_id) {
+            Some(catalog) => IndexStatus {
+                state: catalog.state,
+                entries: catalog.entries.len(),
+                indexed_at: catalog.indexed_at,
+                error: catalog.error.clone(),
+            },
+            None => IndexStatus { state: IndexState::NotIndexed, entries: 0, indexed_at: None, error: None },
+        }
+    }
+    
+    /// Mark a source as being indexed; `false` if a crawl is already running
+    pub fn begin(&self, source_id: &str) -> bool {
+        let mut catalogs = self.catalogs.write();
+        let catalog = catalogs.entry(source_id.to_string()).or_insert_with(|| SourceCatalog {
+            state: IndexState::NotIndexed,
+            entries: BTreeMap::new(),
+            indexed_at: None,
+            error: None,
+            pending: Vec::new(),
+            dirty: Vec::new(),
+            flush_scheduled: false,
+        });
+        if catalog.state == IndexState::Indexing {
+            return false;
+        }
+        catalog.state = IndexState::Indexing;
+        catalog.error = None;
+        true
+    }
+    
+    /// Crawl the whole source and replace its index. Call `begin` first.
+    pub async fn index(&self, source_id: &str, adapter: &dyn StorageAdapter) -> Result<usize> {
+        let crawled = list_tree(adapter, Path::new("/")).await;
+        
+        let pending = {
+            let mut catalogs = self.catalogs.write();
+            let Some(catalog) = catalogs.get_mut(source_id) else {
+                // Source removed mid-crawl
+                return Ok(0);
+            };
+            match &crawled {
+                Ok(files) => {
+                    catalog.entries = files.iter().map(|f| (f.path.clone(), CatalogEntry::from(f))).collect();
+                    catalog.state = IndexState::Ready;
+                    catalog.indexed_at = Some(Utc::now());
+                }
+                Err(e) => {
+                    catalog.state = IndexState::Failed;
+                    catalog.error = Some(e.to_string());
+                }
+            }
+            std::mem::take(&mut catalog.pending)
+        };
+        let files = crawled?;
+        
+        for path in outermost(pending) {
+            self.refresh(source_id, adapter, &path).await?;
+        }
+        Ok(files.len())
+    }
+    
+    /// Note a change at `path` for the next `flush`. `true` when the caller
+    /// should schedule one: the source is indexed and none is scheduled yet.
+    pub fn mark_dirty(&self, source_id: &str, path: &Path) -> bool {
+        let mut catalogs = self.catalogs.write();
+        let Some(catalog) = catalogs.get_mut(source_id) else {
+            return false;
+        };
+        match catalog.state {
+            IndexState::Ready => {}
+            IndexState::Indexing => {
+                catalog.pending.push(path.to_path_buf());
+                return false;
+            }
+            IndexState::NotIndexed | IndexState::Failed => return false,
+        }
+        catalog.dirty.push(path.to_path_buf());
+        !std::mem::replace(&mut catalog.flush_scheduled, true)
+    }
+    
+    /// Refresh every path marked since the last flush, once each, skipping
+    /// paths under another marked path
+    pub async fn flush(&self, source_id: &str, adapter: &dyn StorageAdapter) {
+        let dirty = match self.catalogs.write().get_mut(source_id) {
+            Some(catalog) => {
+                catalog.flush_scheduled = false;
+                std::mem::take(&mut catalog.dirty)
+            }
+            None => return,
+        };
+        
+        for path in outermost(dirty) {
+            if let Err(e) = self.refresh(source_id, adapter, &path).await {
+                warn!("Failed to refresh index of {:?} on {}: {}", path, source_id, e);
+            }
+        }
+    }
+    
+    /// Re-list `path` after a change. A file's row is replaced; a folder that
+    /// was already indexed is re-listed one level deep, crawling only folders
+    /// that are new in it; anything else is crawled.
+    pub async fn refresh(&self, source_id: &str, adapter: &dyn StorageAdapter, path: &Path) -> Result<()> {
+        let known_dir = |catalogs: &HashMap<String, SourceCatalog>, dir: &Path| {
+            catalogs.get(source_id)
+                .and_then(|catalog| catalog.entries.get(dir))
+                .is_some_and(|entry| entry.is_directory)
+        };
+        
+        let was_indexed_dir = {
+            let mut catalogs = self.catalogs.write();
+            match catalogs.get_mut(source_id) {
+                Some(catalog) if catalog.state == IndexState::Ready => {}
+                Some(catalog) if catalog.state == IndexState::Indexing => {
+                    catalog.pending.push(path.to_path_buf());
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            }
+            known_dir(&catalogs, path)
+        };
+        
+        let mut current = Vec::new();
+        // Indexed folders whose rows below them are still current
+        let mut kept_dirs = HashSet::new();
+        if adapter.exists(path).await? {
+            let file = adapter.get_metadata(path).await?;
+            if file.is_directory && was_indexed_dir {
+                for child in adapter.list_files(path).await? {
+                    if child.is_directory {
+                        if known_dir(&self.catalogs.read(), &child.path) {
+                            kept_dirs.insert(child.path.clone());
+                        } else {
+                            current.extend(list_tree(adapter, &child.path).await?);
+                        }
+                    }
+                    current.push(child);
+                }
+            } else if file.is_directory {
+                current = list_tree(adapter, path).await?;
+            }
+            current.push(file);
+        }
+        
+        let mut catalogs = self.catalogs.write();
+        if let Some(catalog) = catalogs.get_mut(source_id) {
+            catalog.entries.retain(|p, _| match p.strip_prefix(path) {
+                Err(_) => true,
+                Ok(rel) => {
+                    let mut components = rel.components();
+                    match components.next() {
+                        // `path` itself and its direct children are replaced
+                        None => false,
+                        Some(child) => components.next().is_some() && kept_dirs.contains(&path.join(child)),
+                    }
+                }
+            });
+            catalog.entries.extend(current.iter().map(|f| (f.path.clone(), CatalogEntry::from(f))));
+        }
+        Ok(())
+    }
+    
+    /// Indexed entries under `root` whose name contains `query`, sorted by
+    /// path. `None` when the source has no usable index.
+    pub fn search(&self, source_id: &str, root: &Path, query: &str, limit: usize) -> Option<Vec<CatalogEntry>> {
+        let catalogs = self.catalogs.read();
+        let catalog = catalogs.get(source_id)?;
+        // A re-index keeps serving the previous rows until it finishes
+        if catalog.indexed_at.is_none() {
+            return None;
+        }
+        
+        Some(catalog.entries
+            .range(root.to_path_buf()..)
+            .take_while(|(path, _)| path.starts_with(root))
+            .map(|(_, entry)| entry)
+            .filter(|entry| entry.path != root && name_matches(&entry.name, query))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+    
+    /// Drop a source's index
+    pub fn remove(&self, source_id: &str) {
+        self.catalogs.write().remove(source_id);
+    }
+}
+
+/// `paths` without duplicates or paths under another of them
+fn outermost(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    // Component order puts a folder's descendants right after it
+    paths.sort();
+    paths.dedup();
+    let mut kept: Vec<PathBuf> = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !kept.last().is_some_and(|last| path.starts_with(last)) {
+            kept.push(path);
+        }
+    }
+    kept
+}
+
+/// Search by crawling `root`, for sources without an index
+pub async fn search_live(adapter: &dyn StorageAdapter, root: &Path, query: &str, limit: usize) -> Result<Vec<CatalogEntry>> {
+    let mut results: Vec<_> = list_tree(adapter, root).await?
+        .iter()
+        .filter(|file| name_matches(&file.name, query))
+        .map(CatalogEntry::from)
+        .collect();
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results.truncate(limit);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_refresh_replaces_changed_subtree() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("Day1")).unwrap();
+        std::fs::write(dir.path().join("Day1/A001.mov"), "frames").unwrap();
+        std::fs::write(dir.path().join("Day1/A002.mov"), "frames").unwrap();
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let catalog = CatalogIndexer::new();
+        assert!(catalog.search("local", Path::new("/"), "a00", 10).is_none());
+        
+        assert!(catalog.begin("local"));
+        assert_eq!(catalog.index("local", &adapter).await.unwrap(), 3);
+        assert_eq!(catalog.search("local", Path::new("/"), "a00", 10).unwrap().len(), 2);
+        
+        std::fs::remove_file(dir.path().join("Day1/A002.mov")).unwrap();
+        std::fs::write(dir.path().join("Day1/A003.MOV"), "frames").unwrap();
+        catalog.refresh("local", &adapter, Path::new("/Day1")).await.unwrap();
+        
+        let names: Vec<_> = catalog.search("local", Path::new("/Day1"), "a00", 10).unwrap()
+            .into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["A001.mov", "A003.MOV"]);
+        assert_eq!(catalog.status("local").entries, 3);
+    }
+    
+    #[tokio::test]
+    async fn test_flush_refreshes_each_change_once() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("Day1")).unwrap();
+        std::fs::write(dir.path().join("Day1/A001.mov"), "frames").unwrap();
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let catalog = CatalogIndexer::new();
+        assert!(!catalog.mark_dirty("local", Path::new("/Day1")));
+        assert!(catalog.begin("local"));
+        catalog.index("local", &adapter).await.unwrap();
+        
+        std::fs::create_dir_all(dir.path().join("Day1/Sound")).unwrap();
+        std::fs::write(dir.path().join("Day1/Sound/A001.wav"), "audio").unwrap();
+        std::fs::write(dir.path().join("Day1/A002.mov"), "frames").unwrap();
+        assert!(catalog.mark_dirty("local", Path::new("/Day1/A002.mov")));
+        assert!(!catalog.mark_dirty("local", Path::new("/Day1")));
+        assert!(!catalog.mark_dirty("local", Path::new("/Day1/Sound")));
+        catalog.flush("local", &adapter).await;
+        
+        let names: Vec<_> = catalog.search("local", Path::new("/Day1"), "a00", 10).unwrap()
+            .into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["A001.mov", "A002.mov", "A001.wav"]);
+        assert!(catalog.mark_dirty("local", Path::new("/Day1")));
+    }
+}
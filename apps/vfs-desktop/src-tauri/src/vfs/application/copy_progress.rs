@@ -0,0 +1,156 @@
+//! Copy Progress - bytes done, rate and ETA while one large file copies
+//!
+//! Copies within a source always go through the adapter's own copy
+//! (`fs::copy`, a copy-on-write clone, or a server-side copy on object
+//! stores). From `PROGRESS_COPY_THRESHOLD` up, the destination's size is
+//! polled while that copy runs and reported; the rate is averaged since the
+//! copy started. Streamed cross-source copies report each chunk instead.
+
+use anyhow::Result;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::vfs::ports::{CopyOptions, IFileOperations};
+
+/// Files smaller than this are copied in one call, without progress
+pub const PROGRESS_COPY_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// How often the destination of a reported copy is checked
+pub const COPY_PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How far a copy has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    pub done: u64,
+    pub total: u64,
+    
+    /// Average since the copy started
+    pub bytes_per_sec: u64,
+    
+    /// `None` until a rate is known
+    pub eta_secs: Option<u64>,
+}
+
+/// Callback receiving progress as a copy goes
+pub type CopyProgressFn = dyn Fn(CopyProgress) + Send + Sync;
+
+/// Turns bytes copied into `CopyProgress`
+pub struct ProgressMeter {
+    done: u64,
+    total: u64,
+    started: Instant,
+}
+
+impl ProgressMeter {
+    pub fn new(total: u64) -> Self {
+        Self { done: 0, total, started: Instant::now() }
+    }
+    
+    /// Count `bytes` more as copied
+    pub fn advance(&mut self, bytes: u64) -> CopyProgress {
+        self.set_done(self.done + bytes)
+    }
+    
+    /// Count `done` bytes as copied in all
+    pub fn set_done(&mut self, done: u64) -> CopyProgress {
+        self.done = done;
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let bytes_per_sec = if elapsed > 0.0 { (self.done as f64 / elapsed) as u64 } else { 0 };
+        
+        CopyProgress {
+            done: self.done,
+            total: self.total,
+            bytes_per_sec,
+            eta_secs: (bytes_per_sec > 0).then(|| self.total.saturating_sub(self.done) / bytes_per_sec),
+        }
+    }
+}
+
+/// Pass `chunks` through, reporting each one to `progress` as it goes by
+pub fn report_chunks(
+    chunks: BoxStream<'static, Result<Vec<u8>>>,
+    total: u64,
+    progress: Arc<CopyProgressFn>,
+) -> BoxStream<'static, Result<Vec<u8>>> {
+    let mut meter = ProgressMeter::new(total);
+    chunks.inspect_ok(move |chunk| progress(meter.advance(chunk.len() as u64))).boxed()
+}
+
+/// Run the adapter's own copy of `from` (`size` bytes) to `to`, reporting
+/// the destination's size every `interval` until it finishes
+pub async fn copy_polling_destination(
+    file_ops: &Arc<dyn IFileOperations>,
+    from: &Path,
+    to: &Path,
+    options: CopyOptions,
+    size: u64,
+    interval: Duration,
+    progress: Arc<CopyProgressFn>,
+) -> Result<()> {
+    let copy = file_ops.copy(from, to, options);
+    tokio::pin!(copy);
+    let mut meter = ProgressMeter::new(size);
+    let mut ticks = tokio::time::interval(interval);
+    ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    
+    loop {
+        tokio::select! {
+            result = &mut copy => {
+                if result.is_ok() {
+                    progress(meter.set_done(size));
+                }
+                return result;
+            }
+            _ = ticks.tick() => {
+                // Not there yet (or still being created under a temporary name)
+                let Ok(stat) = file_ops.stat(to).await else { continue };
+                if !stat.is_dir && stat.size > meter.done && stat.size < size {
+                    progress(meter.set_done(stat.size));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_polled_copy_ends_with_the_full_size() {
+        let dir = TempDir::new().unwrap();
+        let data: Vec<u8> = (0..5 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(dir.path().join("A001.mov"), &data).unwrap();
+        
+        let file_ops: Arc<dyn IFileOperations> = Arc::new(LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string()));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorder = events.clone();
+        
+        copy_polling_destination(
+            &file_ops,
+            Path::new("/A001.mov"),
+            Path::new("/A001 copy.mov"),
+            CopyOptions::default(),
+            data.len() as u64,
+            Duration::from_millis(1),
+            Arc::new(move |progress: CopyProgress| recorder.lock().push(progress)),
+        ).await.unwrap();
+        
+        assert_eq!(std::fs::read(dir.path().join("A001 copy.mov")).unwrap(), data);
+        
+        let events = events.lock();
+        assert!(events.windows(2).all(|pair| pair[0].done < pair[1].done));
+        assert!(events.iter().all(|event| event.total == data.len() as u64));
+        assert_eq!(events.last().unwrap().done, data.len() as u64);
+        assert_eq!(events.last().unwrap().eta_secs.unwrap_or(0), 0);
+    }
+}
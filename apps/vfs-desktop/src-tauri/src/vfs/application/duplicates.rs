@@ -0,0 +1,151 @@
+//! Duplicate Finder - groups identical files under a folder
+//!
+//! Files are grouped by size first; only sizes shared by two or more files are
+//! read. Candidates of the same size are then compared block by block in
+//! lockstep, and any file whose block differs from all the others drops out
+//! immediately, so unrelated files of equal size usually cost a single read.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use xxhash_rust::xxh3::{xxh3_128, Xxh3};
+
+use crate::vfs::application::manifest::collect_files;
+use crate::vfs::ports::StorageAdapter;
+
+/// Bytes compared per step; small so mismatches are found early
+pub const COMPARE_BLOCK_SIZE: u64 = 1024 * 1024;
+
+/// Progress callback - receives total bytes read so far
+pub type DuplicateProgress = dyn Fn(u64) + Send + Sync;
+
+/// Two or more files with identical contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    /// XXH3 digest shared by every file in the group
+    pub hash: String,
+    /// Size of each file in bytes
+    pub size: u64,
+    /// Full paths, sorted
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateGroup {
+    /// Bytes that could be reclaimed by keeping a single copy
+    pub fn wasted_bytes(&self) -> u64 {
+        self.size * (self.paths.len() as u64 - 1)
+    }
+}
+
+/// A file still in the running, with the hash of everything read so far
+struct Candidate {
+    path: PathBuf,
+    hasher: Box<Xxh3>,
+}
+
+/// Read exactly `len` bytes at `offset`, retrying short reads
+async fn read_block(adapter: &dyn StorageAdapter, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+    let mut block = Vec::with_capacity(len as usize);
+    
+    while (block.len() as u64) < len {
+        let pos = offset + block.len() as u64;
+        let chunk = adapter.read_file_range(path, pos, len - block.len() as u64).await?;
+        if chunk.is_empty() {
+            anyhow::bail!("Unexpected end of {:?} at byte {}", path, pos);
+        }
+        block.extend_from_slice(&chunk);
+    }
+    
+    Ok(block)
+}
+
+/// Find groups of identical files under `root`, largest waste first.
+/// Empty files and directories are ignored.
+pub async fn find_duplicates(
+    adapter: &dyn StorageAdapter,
+    root: &Path,
+    progress: &DuplicateProgress,
+) -> Result<Vec<DuplicateGroup>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for (_, full_path, size) in collect_files(adapter, root).await? {
+        if size > 0 {
+            by_size.entry(size).or_default().push(full_path);
+        }
+    }
+    
+    let mut read = 0u64;
+    let mut duplicates = Vec::new();
+    
+    for (size, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let candidates = paths.into_iter()
+            .map(|path| Candidate { path, hasher: Box::new(Xxh3::new()) })
+            .collect();
+        let mut groups: Vec<Vec<Candidate>> = vec![candidates];
+        let mut offset = 0u64;
+        
+        while offset < size && !groups.is_empty() {
+            let len = COMPARE_BLOCK_SIZE.min(size - offset);
+            let mut next = Vec::with_capacity(groups.len());
+            
+            for group in groups {
+                // Split the group by this block's contents; singletons are unique
+                let mut by_block: HashMap<u128, Vec<Candidate>> = HashMap::new();
+                for mut candidate in group {
+                    let block = read_block(adapter, &candidate.path, offset, len).await?;
+                    candidate.hasher.update(&block);
+                    read += len;
+                    progress(read);
+                    by_block.entry(xxh3_128(&block)).or_default().push(candidate);
+                }
+                next.extend(by_block.into_values().filter(|g| g.len() > 1));
+            }
+            
+            groups = next;
+            offset += len;
+        }
+        
+        for group in groups {
+            let hash = format!("{:016x}", group[0].hasher.digest());
+            let mut paths: Vec<PathBuf> = group.into_iter().map(|c| c.path).collect();
+            paths.sort();
+            duplicates.push(DuplicateGroup { hash, size, paths });
+        }
+    }
+    
+    duplicates.sort_by(|a, b| {
+        b.wasted_bytes().cmp(&a.wasted_bytes()).then_with(|| a.paths.cmp(&b.paths))
+    });
+    
+    debug!("Found {} duplicate groups under {:?} ({} bytes read)", duplicates.len(), root, read);
+    Ok(duplicates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_same_size_different_content_is_not_a_duplicate() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.bin"), vec![1u8; 3 * 1024 * 1024]).unwrap();
+        let mut b = vec![1u8; 3 * 1024 * 1024];
+        b[0] = 2;
+        std::fs::write(dir.path().join("b.bin"), b).unwrap();
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let reads = std::sync::atomic::AtomicU64::new(0);
+        let groups = find_duplicates(&adapter, Path::new("/"), &|n| {
+            reads.store(n, std::sync::atomic::Ordering::Relaxed);
+        }).await.unwrap();
+        
+        assert!(groups.is_empty());
+        // The first block differs, so nothing past it is read
+        assert_eq!(reads.load(std::sync::atomic::Ordering::Relaxed), 2 * COMPARE_BLOCK_SIZE);
+    }
+}
@@ -0,0 +1,125 @@
+//! Fan-out - one read of a file feeding writes to several destinations
+//!
+//! Delivering an export to three places by copying it three times reads it
+//! (and for remote sources, downloads it) three times. A fan-out reads each
+//! chunk once and hands it to every destination's writer. The writers run
+//! concurrently with only a few chunks buffered each, so the slowest one
+//! sets the pace. A destination that fails drops out without stopping the
+//! others.
+
+use anyhow::Result;
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Chunks buffered per destination before reading waits for it
+pub const FAN_OUT_BUFFER_CHUNKS: usize = 4;
+
+/// Outcome of the copy to one destination
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FanOutResult {
+    pub source_id: String,
+    /// Folder the file was copied into
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub error: Option<String>,
+}
+
+impl FanOutResult {
+    pub fn new(source_id: &str, path: &Path, result: &Result<u64>) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            path: path.to_path_buf(),
+            bytes: *result.as_ref().unwrap_or(&0),
+            error: result.as_ref().err().map(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+/// Feed `chunks` to every writer, pulling each chunk from `chunks` once.
+/// Returns each writer's result in order. A read error is passed to every
+/// writer still running, and reading stops once no writer is left.
+pub async fn tee<W, Fut>(mut chunks: BoxStream<'_, Result<Vec<u8>>>, writers: Vec<W>) -> Vec<Result<u64>>
+where
+    W: FnOnce(BoxStream<'static, Result<Vec<u8>>>) -> Fut,
+    Fut: Future<Output = Result<u64>>,
+{
+    let mut senders = Vec::with_capacity(writers.len());
+    let mut writes = Vec::with_capacity(writers.len());
+    for writer in writers {
+        let (sender, receiver) = mpsc::channel(FAN_OUT_BUFFER_CHUNKS);
+        senders.push(Some(sender));
+        writes.push(writer(receiver.boxed()));
+    }
+    
+    let feed = async move {
+        while let Some(chunk) = chunks.next().await {
+            // Errors aren't `Clone`, so each writer gets its own copy of the message
+            let chunk = chunk.map_err(|e| format!("{:#}", e));
+            let mut open = 0;
+            for slot in senders.iter_mut() {
+                let Some(sender) = slot else {
+                    continue;
+                };
+                let item = chunk.clone().map_err(anyhow::Error::msg);
+                if sender.send(item).await.is_ok() {
+                    open += 1;
+                } else {
+                    // The writer failed and hung up
+                    *slot = None;
+                }
+            }
+            if chunk.is_err() || open == 0 {
+                break;
+            }
+        }
+        // Dropping the senders ends every writer's stream
+    };
+    
+    let ((), results) = futures::join!(feed, futures::future::join_all(writes));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{FutureExt, TryStreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    
+    #[tokio::test]
+    async fn test_each_chunk_read_once_for_all_writers() {
+        let reads = AtomicUsize::new(0);
+        let chunks = futures::stream::iter(0..10u8)
+            .map(|i| {
+                reads.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, anyhow::Error>(vec![i; 100])
+            })
+            .boxed();
+        
+        let collect = |stream: BoxStream<'static, Result<Vec<u8>>>| async move {
+            let data: Vec<Vec<u8>> = stream.try_collect().await?;
+            Ok::<_, anyhow::Error>(data.concat().len() as u64)
+        };
+        let fail = |_: BoxStream<'static, Result<Vec<u8>>>| async move {
+            Err::<u64, _>(anyhow::anyhow!("destination offline"))
+        };
+        
+        // Closures have distinct types, so box them as one writer type
+        type Writer = Box<dyn FnOnce(BoxStream<'static, Result<Vec<u8>>>) -> futures::future::BoxFuture<'static, Result<u64>>>;
+        let writers: Vec<Writer> = vec![
+            Box::new(move |s| collect(s).boxed()),
+            Box::new(move |s| fail(s).boxed()),
+            Box::new(move |s| collect(s).boxed()),
+        ];
+        
+        let results = tee(chunks, writers).await;
+        assert_eq!(results[0].as_ref().unwrap(), &1000);
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap(), &1000);
+        assert_eq!(reads.load(Ordering::SeqCst), 10);
+    }
+}
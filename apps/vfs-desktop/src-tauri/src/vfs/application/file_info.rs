@@ -0,0 +1,71 @@
+//! File Info - everything the Get Info panel shows about one file or folder
+//!
+//! Stat, tier and cache state, user metadata, type and media details come
+//! from different places; `VfsService::get_info` gathers them into one
+//! `FileInfo` so the panel makes a single call and never shows a mix of
+//! before-and-after values.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::vfs::application::selection::SelectionInfo;
+use crate::vfs::domain::{ColorLabel, FileTag, TierStatus, WarmStatus};
+use crate::vfs::platform::FilePermissions;
+use crate::vfs::ports::{FileMetadata, MediaInfo};
+
+/// One file or folder as shown in the info panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    pub path: PathBuf,
+    pub name: String,
+    
+    /// Bytes of the file itself (folders: see `contents`)
+    pub size: u64,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub modified: Option<DateTime<Utc>>,
+    pub accessed: Option<DateTime<Utc>>,
+    pub created: Option<DateTime<Utc>>,
+    pub mode: u32,
+    
+    /// Read from disk for sources backed by a local path
+    pub permissions: Option<FilePermissions>,
+    
+    pub tier_status: TierStatus,
+    pub cache_status: WarmStatus,
+    
+    /// `None` for folders
+    pub mime_type: Option<String>,
+    
+    pub tags: Vec<FileTag>,
+    pub is_favorite: bool,
+    pub color_label: Option<ColorLabel>,
+    pub rating: Option<u8>,
+    pub comment: Option<String>,
+    
+    /// Probed details of audio, video and images that are on local disk or
+    /// already cached
+    pub media: Option<MediaInfo>,
+    
+    /// Everything inside a folder, not counting the folder itself
+    pub contents: Option<SelectionInfo>,
+}
+
+impl FileInfo {
+    /// Copy the user-set fields of `metadata` in
+    pub(crate) fn with_metadata(mut self, metadata: FileMetadata) -> Self {
+        self.tags = metadata.tags;
+        self.is_favorite = metadata.is_favorite;
+        self.color_label = metadata.color_label;
+        self.rating = metadata.rating;
+        self.comment = metadata.comment;
+        self
+    }
+}
+
+/// Whether files of `mime_type` are worth probing for media details
+pub fn is_probeable(mime_type: &str) -> bool {
+    ["video/", "audio/", "image/"].iter().any(|prefix| mime_type.starts_with(prefix))
+}
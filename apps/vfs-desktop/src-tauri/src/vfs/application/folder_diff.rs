@@ -0,0 +1,138 @@
+//! Folder Diff - recursive comparison of two directory trees
+//!
+//! Both sides are walked through the storage port, so a local folder can be
+//! compared with its copy on S3. Files are matched by relative path; matches
+//! of different size differ outright, and a deep comparison also hashes
+//! equal-sized pairs (XXH3, streamed with ranged reads) to catch the rest.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::vfs::application::manifest::{collect_files, hash_file, HashAlgorithm};
+use crate::vfs::ports::StorageAdapter;
+
+/// Progress callback - receives total bytes hashed so far (both sides)
+pub type DirDiffProgress = dyn Fn(u64) + Send + Sync;
+
+/// Why a file present on both sides doesn't match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffReason {
+    Size,
+    Checksum,
+}
+
+/// A file present on both sides with different contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DifferingFile {
+    /// Path relative to each root, `/`-separated
+    pub path: PathBuf,
+    pub size_a: u64,
+    pub size_b: u64,
+    pub reason: DiffReason,
+}
+
+/// Result of comparing two folders; all paths are relative to their root
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirDiff {
+    pub only_in_a: Vec<PathBuf>,
+    pub only_in_b: Vec<PathBuf>,
+    pub differing: Vec<DifferingFile>,
+    /// Files present on both sides that matched
+    pub identical: usize,
+}
+
+impl DirDiff {
+    /// Whether both folders hold the same files
+    pub fn is_identical(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.differing.is_empty()
+    }
+}
+
+/// Compare the trees under `root_a` and `root_b`. With `deep`, files of equal
+/// size are hashed on both sides; otherwise only sizes are compared.
+pub async fn compare_dirs(
+    adapter_a: &dyn StorageAdapter,
+    root_a: &Path,
+    adapter_b: &dyn StorageAdapter,
+    root_b: &Path,
+    deep: bool,
+    progress: &DirDiffProgress,
+) -> Result<DirDiff> {
+    let files_a = collect_files(adapter_a, root_a).await?;
+    let mut files_b: HashMap<PathBuf, (PathBuf, u64)> = collect_files(adapter_b, root_b).await?
+        .into_iter()
+        .map(|(relative, full_path, size)| (relative, (full_path, size)))
+        .collect();
+    
+    let mut diff = DirDiff::default();
+    let mut hashed = 0u64;
+    
+    for (relative, full_a, size_a) in files_a {
+        let Some((full_b, size_b)) = files_b.remove(&relative) else {
+            diff.only_in_a.push(relative);
+            continue;
+        };
+        
+        let reason = if size_a != size_b {
+            Some(DiffReason::Size)
+        } else if deep {
+            let mut on_chunk = |n| {
+                hashed += n;
+                progress(hashed);
+            };
+            let digest_a = hash_file(adapter_a, &full_a, size_a, HashAlgorithm::XxHash, &mut on_chunk).await?;
+            let digest_b = hash_file(adapter_b, &full_b, size_b, HashAlgorithm::XxHash, &mut on_chunk).await?;
+            (digest_a != digest_b).then_some(DiffReason::Checksum)
+        } else {
+            None
+        };
+        
+        match reason {
+            Some(reason) => diff.differing.push(DifferingFile { path: relative, size_a, size_b, reason }),
+            None => diff.identical += 1,
+        }
+    }
+    
+    diff.only_in_b = files_b.into_keys().collect();
+    diff.only_in_b.sort();
+    
+    debug!(
+        "Compared {:?} with {:?}: {} only in A, {} only in B, {} differing, {} identical",
+        root_a, root_b, diff.only_in_a.len(), diff.only_in_b.len(), diff.differing.len(), diff.identical
+    );
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_shallow_compare_misses_same_size_edits() {
+        let a = TempDir::new().unwrap();
+        let b = TempDir::new().unwrap();
+        std::fs::write(a.path().join("clip.mov"), "aaaa").unwrap();
+        std::fs::write(b.path().join("clip.mov"), "bbbb").unwrap();
+        
+        let adapter_a = LocalStorageAdapter::new(a.path().to_path_buf(), "A".to_string());
+        let adapter_b = LocalStorageAdapter::new(b.path().to_path_buf(), "B".to_string());
+        let root = Path::new("/");
+        
+        let shallow = compare_dirs(&adapter_a, root, &adapter_b, root, false, &|_| {}).await.unwrap();
+        assert!(shallow.is_identical());
+        assert_eq!(shallow.identical, 1);
+        
+        let deep = compare_dirs(&adapter_a, root, &adapter_b, root, true, &|_| {}).await.unwrap();
+        assert_eq!(deep.differing.len(), 1);
+        assert_eq!(deep.differing[0].reason, DiffReason::Checksum);
+    }
+}
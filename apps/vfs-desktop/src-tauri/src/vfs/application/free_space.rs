@@ -0,0 +1,58 @@
+//! Free-Space Preflight - refuse writes that cannot fit before starting them
+//!
+//! A copy that runs out of space halfway leaves a partial file behind and
+//! wastes however long it took to get there. Local destinations are checked
+//! up front instead; the space lookup is injectable so tests can pretend a
+//! volume is nearly full.
+
+use anyhow::Result;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::vfs::domain::InsufficientSpaceError;
+
+/// Space kept free on the destination so the OS and other apps don't hit zero
+pub const FREE_SPACE_MARGIN: u64 = 256 * 1024 * 1024;
+
+/// Returns the free bytes on the volume containing a path
+pub type SpaceProvider = dyn Fn(&Path) -> Result<u64> + Send + Sync;
+
+/// Space provider backed by the real filesystem
+pub fn system_space_provider() -> Arc<SpaceProvider> {
+    Arc::new(|path: &Path| {
+        // The destination folder may not exist yet; ask about its nearest ancestor
+        let existing = path.ancestors()
+            .find(|p| p.exists())
+            .unwrap_or(path);
+        crate::vfs::platform::get_available_space(existing)
+    })
+}
+
+/// Fail with `InsufficientSpaceError` unless `needed` bytes fit at `path`
+/// with `FREE_SPACE_MARGIN` to spare
+pub fn check_free_space(provider: &SpaceProvider, path: &Path, needed: u64) -> Result<()> {
+    let available = provider(path)?.saturating_sub(FREE_SPACE_MARGIN);
+    
+    if needed > available {
+        return Err(InsufficientSpaceError { needed, available }.into());
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_margin_is_held_back() {
+        let provider = |_: &Path| Ok::<_, anyhow::Error>(FREE_SPACE_MARGIN + 1000);
+        
+        assert!(check_free_space(&provider, Path::new("/vol"), 1000).is_ok());
+        
+        let err = check_free_space(&provider, Path::new("/vol"), 1001).unwrap_err();
+        let err = err.downcast_ref::<InsufficientSpaceError>().unwrap();
+        assert_eq!(err.needed, 1001);
+        assert_eq!(err.available, 1000);
+    }
+}
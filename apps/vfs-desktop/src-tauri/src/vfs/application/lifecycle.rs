@@ -0,0 +1,213 @@
+//! Lifecycle Policies - move idle files to a colder tier
+//!
+//! A source's `LifecyclePolicy` names a target tier and how many idle days a
+//! file may sit before it goes there. Idle time is measured from the last
+//! access, or the last modification when the backend doesn't track access.
+//! Selection is a pure function of the listing so previews and the background
+//! evaluator agree on what moves.
+//!
+//! The evaluator doesn't walk a whole bucket each time it runs: every pass
+//! lists a bounded number of folders and carries on where the last pass left
+//! off, starting over from the root once the walk is done.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::vfs::domain::{LifecyclePolicy, StorageTier, VirtualFile};
+use crate::vfs::ports::StorageAdapter;
+
+/// How often the background evaluator applies lifecycle policies
+pub const DEFAULT_LIFECYCLE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Folders the evaluator lists per source in one pass
+pub const LIFECYCLE_FOLDERS_PER_PASS: usize = 250;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A file a lifecycle policy would move
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleCandidate {
+    pub path: PathBuf,
+    pub size: u64,
+    pub current_tier: StorageTier,
+    /// Last access, or last modification when access isn't tracked
+    pub last_access: DateTime<Utc>,
+    /// Whole days since `last_access`
+    pub idle_days: u64,
+}
+
+/// Outcome of moving a batch of files between tiers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TierChangeReport {
+    pub changed: Vec<PathBuf>,
+    /// Archived files being restored first; they move once readable
+    #[serde(default)]
+    pub restoring: Vec<PathBuf>,
+    /// `path: error` for each file that could not be moved
+    pub failed: Vec<String>,
+}
+
+/// Files in `files` that `policy` would move as of `now`, most idle first.
+/// Directories and files already at or colder than the target are skipped.
+pub fn select_candidates(files: &[VirtualFile], policy: &LifecyclePolicy, now: SystemTime) -> Vec<LifecycleCandidate> {
+    let threshold = u64::from(policy.idle_days) * SECS_PER_DAY;
+    
+    let mut candidates: Vec<_> = files
+        .iter()
+        .filter(|file| !file.is_directory)
+        .filter(|file| file.tier_status.current_tier.coldness() < policy.target_tier.coldness())
+        .filter_map(|file| {
+            let last_access = file.last_accessed.unwrap_or(file.last_modified);
+            // Timestamps in the future count as fresh
+            let idle = now.duration_since(last_access).ok()?.as_secs();
+            (idle >= threshold).then(|| LifecycleCandidate {
+                path: file.path.clone(),
+                size: file.size.bytes(),
+                current_tier: file.tier_status.current_tier,
+                last_access: last_access.into(),
+                idle_days: idle / SECS_PER_DAY,
+            })
+        })
+        .collect();
+    
+    candidates.sort_by(|a, b| a.last_access.cmp(&b.last_access).then_with(|| a.path.cmp(&b.path)));
+    candidates
+}
+
+/// Where a source's lifecycle walk left off: the folders still to list
+#[derive(Debug, Default)]
+pub struct WalkCursor {
+    pending: Vec<PathBuf>,
+}
+
+impl WalkCursor {
+    /// Whether the walk has covered the whole tree
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Entries of up to `max_folders` folders, carrying on from `cursor`. A
+/// finished cursor starts over from the root. A folder that fails to list is
+/// kept for the next step.
+pub async fn walk_step(adapter: &dyn StorageAdapter, cursor: &mut WalkCursor, max_folders: usize) -> Result<Vec<VirtualFile>> {
+    if cursor.pending.is_empty() {
+        cursor.pending.push(PathBuf::from("/"));
+    }
+    
+    let mut entries = Vec::new();
+    for _ in 0..max_folders {
+        let Some(dir) = cursor.pending.pop() else {
+            break;
+        };
+        let listed = match adapter.list_files(&dir).await {
+            Ok(listed) => listed,
+            Err(e) => {
+                cursor.pending.push(dir);
+                return Err(e);
+            }
+        };
+        for entry in listed {
+            if entry.is_directory {
+                cursor.pending.push(entry.path.clone());
+            }
+            entries.push(entry);
+        }
+    }
+    
+    Ok(entries)
+}
+
+/// Every file and folder under `root`
+pub async fn list_tree(adapter: &dyn StorageAdapter, root: &Path) -> Result<Vec<VirtualFile>> {
+    let mut entries = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    
+    while let Some(dir) = pending.pop() {
+        for entry in adapter.list_files(&dir).await? {
+            if entry.is_directory {
+                pending.push(entry.path.clone());
+            }
+            entries.push(entry);
+        }
+    }
+    
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn file(name: &str, tier: StorageTier, accessed_days_ago: Option<u64>, modified_days_ago: u64, now: SystemTime) -> VirtualFile {
+        let days = |n: u64| now - Duration::from_secs(n * SECS_PER_DAY);
+        let mut file = VirtualFile::new(
+            name.to_string(),
+            PathBuf::from(format!("/{}", name)),
+            100,
+            false,
+        );
+        file.tier_status.current_tier = tier;
+        file.last_modified = days(modified_days_ago);
+        file.last_accessed = accessed_days_ago.map(days);
+        file
+    }
+    
+    #[test]
+    fn test_candidates_selected_by_idle_days() {
+        let now = SystemTime::now();
+        let files = vec![
+            file("fresh.mov", StorageTier::Hot, Some(2), 90, now),
+            file("idle.mov", StorageTier::Hot, Some(45), 90, now),
+            file("exactly.mov", StorageTier::Nearline, Some(30), 90, now),
+            // No access time: falls back to the modification time
+            file("untracked.mov", StorageTier::Hot, None, 60, now),
+            file("archived.mov", StorageTier::Archive, Some(400), 400, now),
+        ];
+        let policy = LifecyclePolicy { idle_days: 30, target_tier: StorageTier::Archive };
+        
+        let candidates = select_candidates(&files, &policy, now);
+        let paths: Vec<_> = candidates.iter().map(|c| c.path.to_string_lossy().to_string()).collect();
+        assert_eq!(paths, vec!["/untracked.mov", "/idle.mov", "/exactly.mov"]);
+        assert_eq!(candidates[0].idle_days, 60);
+        
+        // Nearline files are already as cold as a nearline policy wants
+        let policy = LifecyclePolicy { idle_days: 30, target_tier: StorageTier::Nearline };
+        let candidates = select_candidates(&files, &policy, now);
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates.iter().all(|c| c.current_tier == StorageTier::Hot));
+    }
+    
+    #[tokio::test]
+    async fn test_walk_resumes_where_it_left_off() {
+        use crate::vfs::adapters::InMemoryStorageAdapter;
+        use crate::vfs::ports::IFileOperations;
+        
+        let adapter = InMemoryStorageAdapter::new("Walk".to_string());
+        for path in ["/a/one.mov", "/b/two.mov", "/b/c/three.mov"] {
+            adapter.write(Path::new(path), b"x").await.unwrap();
+        }
+        
+        let mut cursor = WalkCursor::default();
+        let mut seen = Vec::new();
+        let mut steps = 0;
+        loop {
+            seen.extend(walk_step(&adapter, &mut cursor, 2).await.unwrap().into_iter().map(|f| f.path));
+            steps += 1;
+            if cursor.is_done() {
+                break;
+            }
+        }
+        
+        assert!(steps > 1);
+        seen.sort();
+        let all: Vec<PathBuf> = list_tree(&adapter, Path::new("/")).await.unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(seen.len(), all.len());
+        assert!(seen.contains(&PathBuf::from("/b/c/three.mov")));
+    }
+}
@@ -0,0 +1,131 @@
+//! Listing Cache - short-lived memo of directory listings
+//!
+//! Navigating back and forth re-lists the same directories, which for object
+//! stores means a round trip per prefix. Listings of remote sources are kept
+//! for a few seconds per `(source, path)` and dropped as soon as something
+//! under them changes; local volumes are always listed afresh.
+
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::vfs::domain::VirtualFile;
+use crate::vfs::ports::ListOptions;
+
+/// How long a listing is served from memory
+pub const DEFAULT_LISTING_TTL: Duration = Duration::from_secs(10);
+
+struct CachedListing {
+    listed_at: Instant,
+    options: ListOptions,
+    files: Vec<VirtualFile>,
+}
+
+/// Directory listings keyed by `(source_id, path)`
+pub struct ListingCache {
+    ttl: RwLock<Duration>,
+    entries: RwLock<HashMap<(String, PathBuf), CachedListing>>,
+}
+
+impl Default for ListingCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_LISTING_TTL)
+    }
+}
+
+impl ListingCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl: RwLock::new(ttl),
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+    
+    /// Change the TTL; zero disables caching
+    pub fn set_ttl(&self, ttl: Duration) {
+        *self.ttl.write() = ttl;
+        if ttl.is_zero() {
+            self.entries.write().clear();
+        }
+    }
+    
+    /// A fresh listing of `path` made with the same `options`, if there is one
+    pub fn get(&self, source_id: &str, path: &Path, options: &ListOptions) -> Option<Vec<VirtualFile>> {
+        let ttl = *self.ttl.read();
+        let entries = self.entries.read();
+        let cached = entries.get(&(source_id.to_string(), path.to_path_buf()))?;
+        
+        if cached.listed_at.elapsed() >= ttl || cached.options != *options {
+            return None;
+        }
+        Some(cached.files.clone())
+    }
+    
+    pub fn insert(&self, source_id: &str, path: &Path, options: &ListOptions, files: Vec<VirtualFile>) {
+        let ttl = *self.ttl.read();
+        if ttl.is_zero() {
+            return;
+        }
+        
+        let mut entries = self.entries.write();
+        entries.retain(|_, cached| cached.listed_at.elapsed() < ttl);
+        entries.insert(
+            (source_id.to_string(), path.to_path_buf()),
+            CachedListing { listed_at: Instant::now(), options: options.clone(), files },
+        );
+    }
+    
+    /// Drop listings affected by a change at `path`: the path itself, every
+    /// directory above it (their entries' sizes and dates change) and anything
+    /// below it (a renamed or deleted directory takes its children along)
+    pub fn invalidate(&self, source_id: &str, path: &Path) {
+        self.entries.write().retain(|(cached_source, cached_path), _| {
+            cached_source != source_id
+                || !(path.starts_with(cached_path) || cached_path.starts_with(path))
+        });
+    }
+    
+    /// Drop every listing of a source
+    pub fn invalidate_source(&self, source_id: &str) {
+        self.entries.write().retain(|(cached_source, _), _| cached_source != source_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_invalidation_scope() {
+        let cache = ListingCache::default();
+        let options = ListOptions::default();
+        for path in ["/", "/shoot", "/shoot/day1", "/other"] {
+            cache.insert("s3", Path::new(path), &options, Vec::new());
+        }
+        cache.insert("local", Path::new("/shoot"), &options, Vec::new());
+        
+        cache.invalidate("s3", Path::new("/shoot/day1/A001.mov"));
+        
+        assert!(cache.get("s3", Path::new("/"), &options).is_none());
+        assert!(cache.get("s3", Path::new("/shoot"), &options).is_none());
+        assert!(cache.get("s3", Path::new("/shoot/day1"), &options).is_none());
+        assert!(cache.get("s3", Path::new("/other"), &options).is_some());
+        assert!(cache.get("local", Path::new("/shoot"), &options).is_some());
+        
+        // Different sort/filter options are listed again
+        let descending = ListOptions { ascending: false, ..ListOptions::default() };
+        assert!(cache.get("s3", Path::new("/other"), &descending).is_none());
+    }
+    
+    #[test]
+    fn test_entries_expire() {
+        let cache = ListingCache::new(Duration::from_millis(20));
+        let options = ListOptions::default();
+        cache.insert("s3", Path::new("/"), &options, Vec::new());
+        assert!(cache.get("s3", Path::new("/"), &options).is_some());
+        
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get("s3", Path::new("/"), &options).is_none());
+    }
+}
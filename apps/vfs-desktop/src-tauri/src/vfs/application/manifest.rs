@@ -0,0 +1,196 @@
+//! Checksum Manifests - md5deep-style delivery verification
+//!
+//! Walks a folder through the storage port and hashes every file with ranged
+//! reads, so cloud sources are streamed rather than downloaded whole. Manifest
+//! paths are relative to the root and always use `/` separators, so a manifest
+//! generated on one platform verifies on another.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::vfs::ports::StorageAdapter;
+
+pub use crate::vfs::domain::hashing::{HashAlgorithm, Hasher};
+
+/// Bytes read per ranged request while hashing
+pub const HASH_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Progress callback - receives total bytes hashed so far
+pub type ManifestProgress = dyn Fn(u64) + Send + Sync;
+
+/// `(relative path, hex digest)` for every file under the root
+pub type Manifest = Vec<(PathBuf, String)>;
+
+/// Result of checking a folder against a manifest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestVerification {
+    /// Files whose digest differs from the manifest
+    pub mismatched: Vec<PathBuf>,
+    /// Files listed in the manifest but not found
+    pub missing: Vec<PathBuf>,
+    /// Files found that the manifest does not list
+    pub extra: Vec<PathBuf>,
+}
+
+impl ManifestVerification {
+    /// Whether the folder matches the manifest exactly
+    pub fn is_ok(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Recursively collect `(relative path, full path, size)` for every file under `root`
+pub(crate) async fn collect_files(adapter: &dyn StorageAdapter, root: &Path) -> Result<Vec<(PathBuf, PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    
+    while let Some(dir) = pending.pop() {
+        for entry in adapter.list_files(&dir).await? {
+            if entry.is_directory {
+                pending.push(entry.path);
+                continue;
+            }
+            
+            let relative = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+            let relative: PathBuf = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+                .into();
+            files.push((relative, entry.path.clone(), entry.size.bytes()));
+        }
+    }
+    
+    files.sort();
+    Ok(files)
+}
+
+/// Hash one file with ranged reads
+pub(crate) async fn hash_file(
+    adapter: &dyn StorageAdapter,
+    path: &Path,
+    size: u64,
+    algo: HashAlgorithm,
+    mut on_chunk: impl FnMut(u64),
+) -> Result<String> {
+    let mut hasher = Hasher::new(algo);
+    let mut offset = 0u64;
+    
+    while offset < size {
+        let chunk = adapter.read_file_range(path, offset, HASH_CHUNK_SIZE.min(size - offset)).await?;
+        if chunk.is_empty() {
+            anyhow::bail!("Unexpected end of {:?} at byte {} of {}", path, offset, size);
+        }
+        hasher.update(&chunk);
+        offset += chunk.len() as u64;
+        on_chunk(chunk.len() as u64);
+    }
+    
+    Ok(hasher.finish())
+}
+
+/// Hash every file under `root`
+pub async fn generate_manifest(
+    adapter: &dyn StorageAdapter,
+    root: &Path,
+    algo: HashAlgorithm,
+    progress: &ManifestProgress,
+) -> Result<Manifest> {
+    let files = collect_files(adapter, root).await?;
+    let mut manifest = Vec::with_capacity(files.len());
+    let mut hashed = 0u64;
+    
+    for (relative, full_path, size) in files {
+        let digest = hash_file(adapter, &full_path, size, algo, |n| {
+            hashed += n;
+            progress(hashed);
+        }).await?;
+        manifest.push((relative, digest));
+    }
+    
+    debug!("Generated {:?} manifest for {:?} ({} files)", algo, root, manifest.len());
+    Ok(manifest)
+}
+
+/// Re-hash the files under `root` and compare them with `manifest`
+pub async fn verify_manifest(
+    adapter: &dyn StorageAdapter,
+    root: &Path,
+    manifest: &[(PathBuf, String)],
+    algo: HashAlgorithm,
+    progress: &ManifestProgress,
+) -> Result<ManifestVerification> {
+    let current: HashMap<PathBuf, (PathBuf, u64)> = collect_files(adapter, root).await?
+        .into_iter()
+        .map(|(relative, full_path, size)| (relative, (full_path, size)))
+        .collect();
+    
+    let mut result = ManifestVerification::default();
+    let mut hashed = 0u64;
+    
+    for (relative, expected) in manifest {
+        let Some((full_path, size)) = current.get(relative) else {
+            result.missing.push(relative.clone());
+            continue;
+        };
+        
+        let actual = hash_file(adapter, full_path, *size, algo, |n| {
+            hashed += n;
+            progress(hashed);
+        }).await?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            result.mismatched.push(relative.clone());
+        }
+    }
+    
+    let listed: HashSet<&PathBuf> = manifest.iter().map(|(p, _)| p).collect();
+    result.extra = current.keys().filter(|p| !listed.contains(p)).cloned().collect();
+    result.extra.sort();
+    
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_verify_flags_corrupted_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("delivery/reels")).unwrap();
+        std::fs::write(dir.path().join("delivery/notes.txt"), "final cut").unwrap();
+        std::fs::write(dir.path().join("delivery/reels/r1.mov"), vec![7u8; 4096]).unwrap();
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let root = Path::new("/delivery");
+        
+        for algo in [HashAlgorithm::Md5, HashAlgorithm::Sha1, HashAlgorithm::XxHash] {
+            let manifest = generate_manifest(&adapter, root, algo, &|_| {}).await.unwrap();
+            assert_eq!(manifest.len(), 2);
+            assert_eq!(manifest[0].0, PathBuf::from("notes.txt"));
+            assert_eq!(manifest[1].0, PathBuf::from("reels/r1.mov"));
+            
+            let clean = verify_manifest(&adapter, root, &manifest, algo, &|_| {}).await.unwrap();
+            assert!(clean.is_ok());
+        }
+        
+        let manifest = generate_manifest(&adapter, root, HashAlgorithm::Md5, &|_| {}).await.unwrap();
+        assert_eq!(manifest[0].1, format!("{:x}", md5::compute("final cut")));
+        
+        // Corrupt one file
+        std::fs::write(dir.path().join("delivery/reels/r1.mov"), vec![8u8; 4096]).unwrap();
+        
+        let result = verify_manifest(&adapter, root, &manifest, HashAlgorithm::Md5, &|_| {}).await.unwrap();
+        assert_eq!(result.mismatched, vec![PathBuf::from("reels/r1.mov")]);
+        assert!(result.missing.is_empty());
+        assert!(result.extra.is_empty());
+    }
+}
@@ -6,6 +6,37 @@
 
 pub mod vfs_service;
 pub mod use_cases;
+pub mod resumable_download;
+pub mod text_diff;
+pub mod archive;
+pub mod manifest;
+pub mod throttle;
+pub mod parallel_copy;
+pub mod duplicates;
+pub mod similar_images;
+pub mod free_space;
+pub mod path_format;
+pub mod batch_rename;
+pub mod transfers;
+pub mod listing_cache;
+pub mod templates;
+pub mod folder_diff;
+pub mod lifecycle;
+pub mod catalog;
+pub mod transcode_queue;
+pub mod proxies;
+pub mod operation_history;
+pub mod selection;
+pub mod file_info;
+pub mod scratch;
+pub mod temp_gc;
+pub mod preview;
+pub mod fan_out;
+pub mod tree_hash;
+pub mod tail;
+pub mod copy_progress;
+pub mod remote_thumbnail;
+pub mod case_sensitivity;
 
 pub use vfs_service::VfsService;
 pub use use_cases::*;
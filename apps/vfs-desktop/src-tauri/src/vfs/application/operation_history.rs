@@ -0,0 +1,172 @@
+//! Operation History - the Undo / Redo stacks behind Cmd+Z
+//!
+//! Renames, moves, trashing and new folders are recorded as they succeed so
+//! the service can invert them. Operations that can't be inverted (permanent
+//! deletes, overwrites) leave a barrier instead: undo stops there, since the
+//! older operations may refer to files that no longer exist.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Operations kept for undo; the oldest are dropped beyond this
+pub const MAX_UNDO_HISTORY: usize = 100;
+
+/// A file operation that can be inverted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FileOperation {
+    Rename { from: PathBuf, to: PathBuf },
+    Move { from: PathBuf, to: PathBuf },
+    /// `trash_id` is where the item now sits inside the OS trash
+    #[serde(rename_all = "camelCase")]
+    Trash { path: PathBuf, trash_id: String },
+    CreateFolder { path: PathBuf },
+}
+
+/// An operation and the source it ran on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedOperation {
+    pub source_id: String,
+    pub operation: FileOperation,
+}
+
+enum HistoryEntry {
+    Operation(RecordedOperation),
+    Barrier,
+}
+
+#[derive(Default)]
+struct Stacks {
+    undo: VecDeque<HistoryEntry>,
+    redo: Vec<RecordedOperation>,
+}
+
+/// Bounded undo and redo stacks
+pub struct OperationHistory {
+    stacks: Mutex<Stacks>,
+    limit: usize,
+}
+
+impl Default for OperationHistory {
+    fn default() -> Self {
+        Self::new(MAX_UNDO_HISTORY)
+    }
+}
+
+impl OperationHistory {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            stacks: Mutex::new(Stacks::default()),
+            limit: limit.max(1),
+        }
+    }
+    
+    /// Record a new operation; anything that could be redone is forgotten
+    pub fn record(&self, source_id: &str, operation: FileOperation) {
+        let mut stacks = self.stacks.lock();
+        stacks.redo.clear();
+        self.push_undo(&mut stacks, HistoryEntry::Operation(RecordedOperation {
+            source_id: source_id.to_string(),
+            operation,
+        }));
+    }
+    
+    /// Record an operation that can't be undone
+    pub fn barrier(&self) {
+        let mut stacks = self.stacks.lock();
+        stacks.redo.clear();
+        if !matches!(stacks.undo.back(), Some(HistoryEntry::Barrier)) {
+            self.push_undo(&mut stacks, HistoryEntry::Barrier);
+        }
+    }
+    
+    /// Take the operation to undo next, if any isn't behind a barrier
+    pub fn take_undo(&self) -> Option<RecordedOperation> {
+        let mut stacks = self.stacks.lock();
+        match stacks.undo.pop_back()? {
+            HistoryEntry::Operation(recorded) => Some(recorded),
+            HistoryEntry::Barrier => {
+                stacks.undo.push_back(HistoryEntry::Barrier);
+                None
+            }
+        }
+    }
+    
+    /// Take the operation to redo next
+    pub fn take_redo(&self) -> Option<RecordedOperation> {
+        self.stacks.lock().redo.pop()
+    }
+    
+    /// An operation was undone (or failed to redo); it can be redone
+    pub fn undone(&self, recorded: RecordedOperation) {
+        self.stacks.lock().redo.push(recorded);
+    }
+    
+    /// An operation was redone (or failed to undo); it can be undone again
+    /// without touching the redo stack
+    pub fn redone(&self, recorded: RecordedOperation) {
+        let mut stacks = self.stacks.lock();
+        self.push_undo(&mut stacks, HistoryEntry::Operation(recorded));
+    }
+    
+    pub fn can_undo(&self) -> bool {
+        matches!(self.stacks.lock().undo.back(), Some(HistoryEntry::Operation(_)))
+    }
+    
+    pub fn can_redo(&self) -> bool {
+        !self.stacks.lock().redo.is_empty()
+    }
+    
+    /// Forget a source's operations, e.g. when it is removed
+    pub fn forget_source(&self, source_id: &str) {
+        let mut stacks = self.stacks.lock();
+        stacks.undo.retain(|entry| match entry {
+            HistoryEntry::Operation(recorded) => recorded.source_id != source_id,
+            HistoryEntry::Barrier => true,
+        });
+        stacks.redo.retain(|recorded| recorded.source_id != source_id);
+    }
+    
+    fn push_undo(&self, stacks: &mut Stacks, entry: HistoryEntry) {
+        stacks.undo.push_back(entry);
+        while stacks.undo.len() > self.limit {
+            stacks.undo.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn rename(from: &str, to: &str) -> FileOperation {
+        FileOperation::Rename { from: PathBuf::from(from), to: PathBuf::from(to) }
+    }
+    
+    #[test]
+    fn test_barrier_stops_undo_and_stack_is_bounded() {
+        let history = OperationHistory::new(2);
+        history.record("local", rename("/a", "/b"));
+        history.record("local", rename("/b", "/c"));
+        history.record("local", rename("/c", "/d"));
+        
+        // Only the two newest survive
+        assert_eq!(history.take_undo().unwrap().operation, rename("/c", "/d"));
+        assert_eq!(history.take_undo().unwrap().operation, rename("/b", "/c"));
+        assert!(history.take_undo().is_none());
+        
+        history.record("local", rename("/x", "/y"));
+        let undone = history.take_undo().unwrap();
+        history.undone(undone);
+        assert!(history.can_redo());
+        
+        // A permanent delete drops the redo stack and blocks older undos
+        history.barrier();
+        assert!(!history.can_redo());
+        assert!(history.take_undo().is_none());
+        assert!(!history.can_undo());
+    }
+}
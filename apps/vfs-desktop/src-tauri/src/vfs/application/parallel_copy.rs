@@ -0,0 +1,69 @@
+//! Parallel Copy - bounded concurrency for multi-file copies
+//!
+//! Directory copies first walk the source tree and create every destination
+//! directory in order (parents before children), then copy the files with a
+//! bounded number in flight. Copying thousands of small files is dominated by
+//! per-file latency, so overlapping them is far faster than awaiting each in turn.
+
+use anyhow::Result;
+use futures::{StreamExt, TryStreamExt};
+use std::future::Future;
+
+/// Files copied concurrently when the caller doesn't choose
+pub const DEFAULT_COPY_CONCURRENCY: usize = 8;
+
+/// Run `jobs` with at most `concurrency` in flight, summing the bytes each reports.
+/// Stops at the first error.
+pub async fn run_bounded<I, F>(jobs: I, concurrency: usize) -> Result<u64>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<u64>>,
+{
+    futures::stream::iter(jobs)
+        .buffer_unordered(concurrency.max(1))
+        .try_fold(0u64, |total, bytes| async move { Ok(total + bytes) })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    
+    #[tokio::test]
+    async fn test_run_bounded_overlaps_up_to_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        
+        let jobs = (0..20u64).map(|i| {
+            let in_flight = &in_flight;
+            let peak = &peak;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, anyhow::Error>(i)
+            }
+        });
+        
+        let total = run_bounded(jobs, 4).await.unwrap();
+        
+        assert_eq!(total, (0..20).sum::<u64>());
+        assert!(peak.load(Ordering::SeqCst) > 1, "jobs should overlap");
+        assert!(peak.load(Ordering::SeqCst) <= 4, "limit must be respected");
+    }
+    
+    #[tokio::test]
+    async fn test_run_bounded_propagates_errors() {
+        let jobs = (0..5u64).map(|i| async move {
+            if i == 3 {
+                anyhow::bail!("disk full");
+            }
+            Ok::<_, anyhow::Error>(1)
+        });
+        
+        assert!(run_bounded(jobs, 2).await.is_err());
+    }
+}
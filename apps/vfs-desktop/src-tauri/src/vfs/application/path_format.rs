@@ -0,0 +1,114 @@
+//! Path Formatting - the different ways a file's location can be written down
+//!
+//! "Copy path" offers the logical VFS path, the real path on disk, or a URI
+//! another tool can open (`file://` for local volumes, `s3://` / `gs://` for
+//! object stores).
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::vfs::domain::{StorageSource, StorageSourceType};
+
+/// How a path should be written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PathStyle {
+    /// Logical path inside the source, e.g. `/shoot/A001.mov`
+    #[default]
+    Vfs,
+    /// Resolved path on the local filesystem
+    Native,
+    /// `file://`, `s3://bucket/key` or `gs://bucket/key`
+    Uri,
+}
+
+/// URI for `path` in `source`; `local_path` is the resolved real path, if any
+pub fn format_uri(source: &StorageSource, path: &Path, local_path: Option<&Path>) -> Result<String> {
+    let scheme = match source.source_type {
+        StorageSourceType::S3 | StorageSourceType::S3Compatible => Some("s3"),
+        StorageSourceType::Gcs => Some("gs"),
+        _ => None,
+    };
+    
+    if let Some(scheme) = scheme {
+        let bucket = source.config.path_or_bucket.trim_matches('/');
+        let key = path.to_string_lossy().replace('\\', "/");
+        let key = key.trim_start_matches('/');
+        return Ok(format!("{}://{}/{}", scheme, bucket, key));
+    }
+    
+    match local_path {
+        Some(local_path) => Ok(file_uri(local_path)),
+        None => Err(anyhow::anyhow!("No URI form for paths in '{}'", source.name)),
+    }
+}
+
+/// `file://` URI with each path segment percent-encoded
+pub fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let encoded = path
+        .split('/')
+        .map(|segment| {
+            // Keep Windows drive letters (`C:`) readable
+            if segment.len() == 2 && segment.ends_with(':') {
+                segment.to_string()
+            } else {
+                urlencoding::encode(segment).into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::domain::{ConnectionStatus, StorageConfig};
+    use std::path::PathBuf;
+    
+    fn source(source_type: StorageSourceType, path_or_bucket: &str) -> StorageSource {
+        StorageSource {
+            id: "src".to_string(),
+            name: "Source".to_string(),
+            source_type,
+            status: ConnectionStatus::Connected,
+            mounted: true,
+            mount_point: None,
+            config: StorageConfig {
+                path_or_bucket: path_or_bucket.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+    
+    #[test]
+    fn test_object_store_uris() {
+        let s3 = source(StorageSourceType::S3, "bucket");
+        assert_eq!(
+            format_uri(&s3, Path::new("/prefix/key.mov"), None).unwrap(),
+            "s3://bucket/prefix/key.mov"
+        );
+        
+        let gcs = source(StorageSourceType::Gcs, "media");
+        assert_eq!(format_uri(&gcs, Path::new("/a/b.wav"), None).unwrap(), "gs://media/a/b.wav");
+    }
+    
+    #[test]
+    fn test_local_file_uri() {
+        let local = source(StorageSourceType::Local, "/Volumes/Media");
+        let real = PathBuf::from("/Volumes/Media/Day 1/A001.mov");
+        assert_eq!(
+            format_uri(&local, Path::new("/Day 1/A001.mov"), Some(&real)).unwrap(),
+            "file:///Volumes/Media/Day%201/A001.mov"
+        );
+        
+        assert_eq!(file_uri(Path::new(r"C:\Media\clip.mov")), "file:///C:/Media/clip.mov");
+        assert!(format_uri(&local, Path::new("/x"), None).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+//! Preview - the first bytes of a file for a quick look
+//!
+//! Opening a file normally hydrates it into the cache, which is wasted work
+//! (and cache space) for a glance at a text or image. A preview reads at most
+//! `max_bytes` straight from the source, or from the cache if the file is
+//! already there, and leaves the cache untouched.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::vfs::application::text_diff::is_binary;
+use crate::vfs::ports::media::detect_mime;
+
+/// How the frontend should render a preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreviewKind {
+    Text,
+    Image,
+    Binary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewData {
+    pub kind: PreviewKind,
+    pub mime_type: String,
+    /// Up to `max_bytes` from the start of the file
+    pub data: Vec<u8>,
+    /// Size of the whole file
+    pub size: u64,
+    /// Whether `data` stops short of the end of the file
+    pub truncated: bool,
+}
+
+impl PreviewData {
+    pub fn new(path: &Path, data: Vec<u8>, size: u64) -> Self {
+        let mime_type = detect_mime(path, Some(&data));
+        Self {
+            kind: preview_kind(mime_type, &data),
+            mime_type: mime_type.to_string(),
+            truncated: (data.len() as u64) < size,
+            data,
+            size,
+        }
+    }
+}
+
+/// Images by MIME type; text by MIME type, or for untyped files, by the
+/// absence of NUL bytes
+pub fn preview_kind(mime_type: &str, head: &[u8]) -> PreviewKind {
+    if mime_type.starts_with("image/") && mime_type != "image/svg+xml" {
+        return PreviewKind::Image;
+    }
+    
+    let is_text = mime_type.starts_with("text/")
+        || matches!(mime_type, "application/json" | "application/xml" | "application/javascript" | "image/svg+xml")
+        || (mime_type == "application/octet-stream" && !is_binary(head));
+    if is_text { PreviewKind::Text } else { PreviewKind::Binary }
+}
@@ -0,0 +1,83 @@
+//! Editing Proxies - low-res copies of camera originals for offline editing
+//!
+//! A proxy lives next to its original in a `Proxies/` subfolder, named after
+//! the original with a `_Proxy` suffix (the NLE relink convention), so it
+//! travels with the footage. The metadata store links the two files both ways.
+
+use anyhow::Result;
+use futures::stream::{BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncReadExt;
+
+/// Subfolder proxies are written to, next to their originals
+pub const PROXY_FOLDER: &str = "Proxies";
+
+/// Bytes read per chunk when copying a finished proxy onto its source
+const PROXY_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Progress callback - receives files finished so far and the total
+pub type ProxyProgress = dyn Fn(usize, usize) + Send + Sync;
+
+/// An original and its proxy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyLink {
+    pub original: PathBuf,
+    pub proxy: PathBuf,
+}
+
+/// Outcome of creating proxies for a selection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyReport {
+    pub created: Vec<ProxyLink>,
+    /// `path: error` for each file that got no proxy
+    pub failed: Vec<String>,
+}
+
+/// Where the proxy of `original` goes: `<dir>/Proxies/<stem>_Proxy.mp4`
+pub fn proxy_path(original: &Path) -> PathBuf {
+    let parent = original.parent().unwrap_or_else(|| Path::new("/"));
+    let stem = original.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    parent.join(PROXY_FOLDER).join(format!("{}_Proxy.mp4", stem))
+}
+
+/// Whether `path` is itself inside a proxy folder
+pub fn is_proxy(path: &Path) -> bool {
+    path.parent()
+        .and_then(|p| p.file_name())
+        .is_some_and(|name| name == PROXY_FOLDER)
+}
+
+/// A local file as a stream of chunks, for `IFileOperations::write_stream`
+pub(crate) fn read_local_chunks(path: PathBuf) -> BoxStream<'static, Result<Vec<u8>>> {
+    futures::stream::try_unfold(None, move |file: Option<tokio::fs::File>| {
+        let path = path.clone();
+        async move {
+            let mut file = match file {
+                Some(file) => file,
+                None => tokio::fs::File::open(&path).await?,
+            };
+            let mut chunk = Vec::with_capacity(PROXY_CHUNK_SIZE);
+            (&mut file).take(PROXY_CHUNK_SIZE as u64).read_to_end(&mut chunk).await?;
+            Ok((!chunk.is_empty()).then(|| (chunk, Some(file))))
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_proxy_path_sits_in_proxies_folder() {
+        let proxy = proxy_path(Path::new("/Day1/A001_C002.mov"));
+        assert_eq!(proxy, PathBuf::from("/Day1/Proxies/A001_C002_Proxy.mp4"));
+        assert!(is_proxy(&proxy));
+        assert!(!is_proxy(Path::new("/Day1/A001_C002.mov")));
+    }
+}
@@ -0,0 +1,178 @@
+//! Remote Thumbnails - thumbnails for images on object storage
+//!
+//! A bucket has no local file for the OS thumbnailer to read, so images up
+//! to `MAX_REMOTE_IMAGE_BYTES` are downloaded, decoded and shrunk here. The
+//! PNG is cached under a key that includes the object's ETag, so a replaced
+//! object gets a new thumbnail and an unchanged one is never downloaded
+//! again. Stores without ETags fall back to size and modification time.
+//! Video gets no thumbnail: a frame needs the moov atom plus a whole GOP and
+//! a decoder, so the frontend shows the type icon instead.
+
+use anyhow::{Context, Result};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tokio::fs;
+use tracing::debug;
+
+use crate::vfs::adapters::native_thumbnail::NativeThumbnailAdapter;
+use crate::vfs::application::manifest::{HashAlgorithm, Hasher};
+use crate::vfs::ports::StorageAdapter;
+
+/// Larger images are left to the type icon rather than downloaded
+pub const MAX_REMOTE_IMAGE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// PNG thumbnail of the image at `path`, at most `size` pixels on a side.
+/// `None` for files that aren't decodable images or are too large.
+pub async fn remote_thumbnail(
+    adapter: &dyn StorageAdapter,
+    source_id: &str,
+    path: &Path,
+    size: u32,
+    cache_dir: &Path,
+) -> Result<Option<Vec<u8>>> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if !NativeThumbnailAdapter::is_hashable_extension(ext) {
+        return Ok(None);
+    }
+    
+    let stat = adapter.get_metadata(path).await?;
+    let bytes = stat.size.bytes();
+    if stat.is_directory || bytes > MAX_REMOTE_IMAGE_BYTES {
+        return Ok(None);
+    }
+    
+    let version = stat.checksum.clone().unwrap_or_else(|| {
+        let modified = stat.last_modified.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        format!("{}-{}", bytes, modified)
+    });
+    let cached = cache_file(cache_dir, source_id, path, &version, size);
+    if let Ok(png) = fs::read(&cached).await {
+        return Ok(Some(png));
+    }
+    
+    let data = adapter.read_file(path).await?;
+    let png = tokio::task::spawn_blocking(move || encode_thumbnail(&data, size)).await?
+        .with_context(|| format!("Failed to decode {:?}", path))?;
+    
+    // A thumbnail that can't be cached is still worth returning
+    if let Err(e) = write_cached(&cached, &png).await {
+        debug!("Failed to cache thumbnail at {:?}: {}", cached, e);
+    }
+    Ok(Some(png))
+}
+
+/// `<cache_dir>/<digest>.png`, the digest covering everything that changes the image
+fn cache_file(cache_dir: &Path, source_id: &str, path: &Path, version: &str, size: u32) -> PathBuf {
+    let mut hasher = Hasher::new(HashAlgorithm::XxHash);
+    hasher.update(format!("{}\0{}\0{}\0{}", source_id, path.display(), version, size).as_bytes());
+    cache_dir.join(format!("{}.png", hasher.finish()))
+}
+
+async fn write_cached(file: &Path, png: &[u8]) -> Result<()> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+    fs::write(file, png).await?;
+    Ok(())
+}
+
+fn encode_thumbnail(data: &[u8], size: u32) -> Result<Vec<u8>> {
+    let thumbnail = image::load_from_memory(data)?.thumbnail(size, size);
+    let mut png = Cursor::new(Vec::new());
+    thumbnail.write_to(&mut png, image::ImageOutputFormat::Png)?;
+    Ok(png.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    
+    use crate::vfs::adapters::S3StorageAdapter;
+    
+    /// A bucket holding one object whose body and ETag the test controls,
+    /// logging each request line
+    async fn mock_object(object: Arc<Mutex<(Vec<u8>, &'static str)>>, requests: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let (object, requests) = (object.clone(), requests.clone());
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let request_line = String::from_utf8_lossy(&head).lines().next().unwrap_or_default().to_string();
+                    let is_head = request_line.starts_with("HEAD");
+                    requests.lock().push(request_line);
+                    
+                    let (body, etag) = object.lock().clone();
+                    let headers = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nETag: \"{}\"\r\nLast-Modified: Fri, 17 May 2024 14:03:09 GMT\r\nConnection: close\r\n\r\n",
+                        body.len(),
+                        etag
+                    );
+                    let _ = socket.write_all(headers.as_bytes()).await;
+                    if !is_head {
+                        let _ = socket.write_all(&body).await;
+                    }
+                });
+            }
+        });
+        endpoint
+    }
+    
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| image::Rgb([x as u8, y as u8, 128])));
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageOutputFormat::Png).unwrap();
+        png.into_inner()
+    }
+    
+    #[tokio::test]
+    async fn test_s3_image_thumbnail_is_cached_by_etag() {
+        let cache = TempDir::new().unwrap();
+        let object = Arc::new(Mutex::new((png(64, 48), "9b2cf535f27731c974343645a3985328")));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let adapter = S3StorageAdapter::new(
+            "media".to_string(),
+            "us-east-1".to_string(),
+            Some("AKIAEXAMPLEKEY".to_string()),
+            Some("example-secret".to_string()),
+            Some(mock_object(object.clone(), requests.clone()).await),
+            true,
+            "Mock".to_string(),
+            None,
+        ).await.unwrap();
+        let path = Path::new("/stills/frame.png");
+        let downloads = || requests.lock().iter().filter(|line| line.starts_with("GET")).count();
+        
+        let thumb = remote_thumbnail(&adapter, "s3", path, 16, cache.path()).await.unwrap().unwrap();
+        assert!(thumb.starts_with(b"\x89PNG"));
+        let decoded = image::load_from_memory(&thumb).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (16, 12));
+        assert_eq!(downloads(), 1);
+        
+        // Same ETag: served from the cache without downloading again
+        assert_eq!(remote_thumbnail(&adapter, "s3", path, 16, cache.path()).await.unwrap().unwrap(), thumb);
+        assert_eq!(downloads(), 1);
+        
+        // A new ETag means the object changed
+        *object.lock() = (png(32, 32), "0f343b0931126a20f133d67c2b018a3b");
+        let thumb = remote_thumbnail(&adapter, "s3", path, 16, cache.path()).await.unwrap().unwrap();
+        assert_eq!(image::load_from_memory(&thumb).unwrap().width(), 16);
+        assert_eq!(downloads(), 2);
+        assert_eq!(std::fs::read_dir(cache.path()).unwrap().count(), 2);
+        
+        assert!(remote_thumbnail(&adapter, "s3", Path::new("/clips/A001.mov"), 16, cache.path()).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,370 @@
+//! Resumable Downloads - `.part` staging for interrupted hydrations
+//!
+//! Large objects are downloaded into a `.part` file next to their final cache
+//! location. After every chunk the committed byte count is persisted to a JSON
+//! sidecar (`<file>.part.json`), so a retry - even after an app restart - picks
+//! up from the last committed offset with a ranged read instead of starting over.
+//! The caller only moves the `.part` file into place once the download is complete
+//! and its checksum matches.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
+
+use crate::vfs::ports::StorageAdapter;
+
+/// Bytes requested per ranged read (and committed per sidecar update), for
+/// adapters without transfer tuning
+pub const RESUME_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Persisted progress of a partial download
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialDownloadState {
+    /// Source path being downloaded
+    pub source_path: PathBuf,
+    
+    /// Object size when the download started
+    pub total_size: u64,
+    
+    /// Bytes durably written to the `.part` file
+    pub committed: u64,
+    
+    /// Expected hex MD5, if the backend reported one
+    pub checksum: Option<String>,
+}
+
+impl PartialDownloadState {
+    /// Sidecar file that records progress for `part_path`
+    pub fn sidecar_path(part_path: &Path) -> PathBuf {
+        let mut sidecar = part_path.as_os_str().to_os_string();
+        sidecar.push(".json");
+        PathBuf::from(sidecar)
+    }
+    
+    /// Load the persisted state for `part_path`, if any
+    pub async fn load(part_path: &Path) -> Option<Self> {
+        let json = fs::read(Self::sidecar_path(part_path)).await.ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+    
+    /// Persist the state (write-then-rename so a crash never leaves a torn sidecar)
+    async fn save(&self, part_path: &Path) -> Result<()> {
+        let sidecar = Self::sidecar_path(part_path);
+        let mut tmp = sidecar.clone().into_os_string();
+        tmp.push(".tmp");
+        
+        fs::write(&tmp, serde_json::to_vec(self)?).await?;
+        fs::rename(&tmp, &sidecar)
+            .await
+            .with_context(|| format!("Failed to persist download state: {:?}", sidecar))
+    }
+    
+    /// Whether a previous attempt can be resumed for the object as it is now
+    fn matches(&self, source_path: &Path, total_size: u64, checksum: &Option<String>) -> bool {
+        self.source_path == source_path
+            && self.total_size == total_size
+            && &self.checksum == checksum
+            && self.committed <= total_size
+    }
+}
+
+/// Download `path` into `part_path`, resuming a previous attempt when possible.
+///
+/// Chunk size and the number of ranged reads in flight come from the
+/// adapter's transfer tuning. Returns the total number of bytes once the
+/// `.part` file holds the complete, checksum-verified object. On error,
+/// progress up to the last committed chunk is kept for the next attempt.
+pub async fn download_to_part(
+    adapter: &dyn StorageAdapter,
+    path: &Path,
+    part_path: &Path,
+) -> Result<u64> {
+    let (chunk_size, concurrency) = match adapter.transfer_tuning() {
+        Some(tuning) => (tuning.download_chunk_size as u64, tuning.max_concurrency),
+        None => (RESUME_CHUNK_SIZE, 1),
+    };
+    download_to_part_chunked(adapter, path, part_path, chunk_size, concurrency).await
+}
+
+async fn download_to_part_chunked(
+    adapter: &dyn StorageAdapter,
+    path: &Path,
+    part_path: &Path,
+    chunk_size: u64,
+    concurrency: usize,
+) -> Result<u64> {
+    let (chunk_size, concurrency) = (chunk_size.max(1), concurrency.max(1));
+    let total_size = adapter.file_size(path).await?;
+    let checksum = adapter.checksum_md5(path).await.unwrap_or_else(|e| {
+        warn!("Could not fetch checksum for {:?}: {}", path, e);
+        None
+    });
+    
+    if let Some(parent) = part_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    
+    // Resume only if the object is unchanged and the backend can serve ranges
+    let previous = PartialDownloadState::load(part_path).await
+        .filter(|state| adapter.supports_range_reads() && state.matches(path, total_size, &checksum));
+    
+    let mut state = match previous {
+        Some(state) => {
+            info!("Resuming download of {:?} at {}/{} bytes", path, state.committed, total_size);
+            state
+        }
+        None => PartialDownloadState {
+            source_path: path.to_path_buf(),
+            total_size,
+            committed: 0,
+            checksum: checksum.clone(),
+        },
+    };
+    
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(part_path)
+        .await
+        .with_context(|| format!("Failed to open partial download: {:?}", part_path))?;
+    
+    // Discard anything written after the last committed offset
+    file.set_len(state.committed).await?;
+    state.save(part_path).await?;
+    
+    if adapter.supports_range_reads() {
+        let mut file = fs::OpenOptions::new().append(true).open(part_path).await?;
+        
+        while state.committed < total_size {
+            // Fetch several ranges at once, then commit them in order
+            let mut ranges = Vec::with_capacity(concurrency);
+            let mut offset = state.committed;
+            while ranges.len() < concurrency && offset < total_size {
+                let length = chunk_size.min(total_size - offset);
+                ranges.push((offset, length));
+                offset += length;
+            }
+            let chunks = futures::future::join_all(
+                ranges.iter().map(|&(offset, length)| adapter.read_file_range(path, offset, length))
+            ).await;
+            
+            for ((_, length), chunk) in ranges.into_iter().zip(chunks) {
+                let chunk = chunk
+                    .with_context(|| format!("Download of {:?} interrupted at byte {}", path, state.committed))?;
+                
+                if chunk.is_empty() {
+                    anyhow::bail!("Unexpected end of {:?} at byte {} of {}", path, state.committed, total_size);
+                }
+                
+                file.write_all(&chunk).await?;
+                file.sync_data().await?;
+                
+                state.committed += chunk.len() as u64;
+                state.save(part_path).await?;
+                
+                debug!("Downloaded {}/{} bytes of {:?}", state.committed, total_size, path);
+                
+                // A short read shifts every later range; request them again
+                if (chunk.len() as u64) < length {
+                    break;
+                }
+            }
+        }
+    } else {
+        let data = adapter.read_file(path).await?;
+        file.write_all(&data).await?;
+        file.sync_data().await?;
+        state.committed = data.len() as u64;
+    }
+    
+    let written = fs::metadata(part_path).await?.len();
+    if written != total_size {
+        anyhow::bail!("Incomplete download of {:?}: {} of {} bytes", path, written, total_size);
+    }
+    
+    if let Some(expected) = &checksum {
+        let actual = md5_file(part_path.to_path_buf()).await?;
+        if &actual != expected {
+            // Corrupt data can't be resumed; start from scratch next time
+            discard_partial(part_path).await;
+            anyhow::bail!("Checksum mismatch for {:?}: expected {}, got {}", path, expected, actual);
+        }
+    }
+    
+    fs::remove_file(PartialDownloadState::sidecar_path(part_path)).await.ok();
+    
+    Ok(total_size)
+}
+
+/// Remove a `.part` file and its progress sidecar
+pub async fn discard_partial(part_path: &Path) {
+    fs::remove_file(part_path).await.ok();
+    fs::remove_file(PartialDownloadState::sidecar_path(part_path)).await.ok();
+}
+
+/// Hex MD5 of a file, streamed so large downloads aren't loaded into memory
+async fn md5_file(path: PathBuf) -> Result<String> {
+    tokio::task::spawn_blocking(move || -> Result<String> {
+        let mut file = std::fs::File::open(&path)?;
+        let mut context = md5::Context::new();
+        let mut buffer = vec![0u8; 1024 * 1024];
+        
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            context.consume(&buffer[..read]);
+        }
+        
+        Ok(format!("{:x}", context.compute()))
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tempfile::TempDir;
+    
+    use crate::vfs::domain::{StorageSourceType, VirtualFile};
+    
+    /// Range-capable source that fails reads at or beyond `fail_at`
+    struct FlakyAdapter {
+        data: Vec<u8>,
+        fail_at: AtomicU64,
+        first_offset: AtomicU64,
+    }
+    
+    #[async_trait]
+    impl StorageAdapter for FlakyAdapter {
+        fn storage_type(&self) -> StorageSourceType {
+            StorageSourceType::S3
+        }
+        
+        fn name(&self) -> &str {
+            "flaky"
+        }
+        
+        async fn test_connection(&self) -> Result<bool> {
+            Ok(true)
+        }
+        
+        async fn list_files(&self, _path: &Path) -> Result<Vec<VirtualFile>> {
+            Ok(vec![])
+        }
+        
+        async fn read_file(&self, _path: &Path) -> Result<Vec<u8>> {
+            Ok(self.data.clone())
+        }
+        
+        async fn read_file_range(&self, _path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
+            let _ = self.first_offset.compare_exchange(u64::MAX, offset, Ordering::SeqCst, Ordering::SeqCst);
+            if offset >= self.fail_at.load(Ordering::SeqCst) {
+                anyhow::bail!("connection reset");
+            }
+            let end = (offset + length).min(self.data.len() as u64) as usize;
+            Ok(self.data[offset as usize..end].to_vec())
+        }
+        
+        async fn write_file(&self, _path: &Path, _data: &[u8]) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn get_metadata(&self, _path: &Path) -> Result<VirtualFile> {
+            anyhow::bail!("not needed")
+        }
+        
+        async fn exists(&self, _path: &Path) -> Result<bool> {
+            Ok(true)
+        }
+        
+        async fn delete(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn create_dir(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn file_size(&self, _path: &Path) -> Result<u64> {
+            Ok(self.data.len() as u64)
+        }
+        
+        fn supports_range_reads(&self) -> bool {
+            true
+        }
+        
+        async fn checksum_md5(&self, _path: &Path) -> Result<Option<String>> {
+            Ok(Some(format!("{:x}", md5::compute(&self.data))))
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_truncated_download_resumes_from_committed_offset() {
+        let temp_dir = TempDir::new().unwrap();
+        let part_path = temp_dir.path().join("archive.tar.part");
+        let source = Path::new("/archive.tar");
+        
+        let adapter = FlakyAdapter {
+            data: (0..=255u8).cycle().take(40).collect(),
+            fail_at: AtomicU64::new(24),
+            first_offset: AtomicU64::new(u64::MAX),
+        };
+        
+        // First attempt drops the connection at byte 24
+        let err = download_to_part_chunked(&adapter, source, &part_path, 8, 1).await;
+        assert!(err.is_err());
+        
+        let state = PartialDownloadState::load(&part_path).await.unwrap();
+        assert_eq!(state.committed, 24);
+        assert_eq!(std::fs::metadata(&part_path).unwrap().len(), 24);
+        
+        // Simulate a stray write past the committed offset (crash before sidecar update)
+        {
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new().append(true).open(&part_path).unwrap();
+            file.write_all(b"junk").unwrap();
+        }
+        
+        // Retry picks up at the committed offset and completes
+        adapter.fail_at.store(u64::MAX, Ordering::SeqCst);
+        adapter.first_offset.store(u64::MAX, Ordering::SeqCst);
+        
+        let total = download_to_part_chunked(&adapter, source, &part_path, 8, 1).await.unwrap();
+        
+        assert_eq!(total, 40);
+        assert_eq!(adapter.first_offset.load(Ordering::SeqCst), 24);
+        assert_eq!(std::fs::read(&part_path).unwrap(), adapter.data);
+        assert!(PartialDownloadState::load(&part_path).await.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_ranges_commit_up_to_first_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let part_path = temp_dir.path().join("clip.mov.part");
+        let source = Path::new("/clip.mov");
+        
+        let adapter = FlakyAdapter {
+            data: (0..=255u8).cycle().take(40).collect(),
+            fail_at: AtomicU64::new(32),
+            first_offset: AtomicU64::new(u64::MAX),
+        };
+        
+        // Ranges 24..32 and 32..40 are fetched together; only the first lands
+        assert!(download_to_part_chunked(&adapter, source, &part_path, 8, 3).await.is_err());
+        assert_eq!(PartialDownloadState::load(&part_path).await.unwrap().committed, 32);
+        
+        adapter.fail_at.store(u64::MAX, Ordering::SeqCst);
+        download_to_part_chunked(&adapter, source, &part_path, 8, 3).await.unwrap();
+        assert_eq!(std::fs::read(&part_path).unwrap(), adapter.data);
+    }
+}
@@ -0,0 +1,102 @@
+//! Scratch Space - where clipboard exports, transcodes and other temporary
+//! files are written
+//!
+//! The system temp dir is often a small tmpfs that can't hold a camera
+//! original, so once the service is running scratch files default to a
+//! folder beside its file cache (usually a large local volume), and can be
+//! moved anywhere writable with enough room.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::fs;
+
+use crate::vfs::application::free_space::{check_free_space, SpaceProvider};
+use crate::vfs::domain::VfsError;
+
+/// Free space a new scratch directory must have, on top of the usual margin
+pub const MIN_SCRATCH_SPACE: u64 = 1024 * 1024 * 1024;
+
+static SCRATCH_DIR: Lazy<RwLock<PathBuf>> = Lazy::new(|| RwLock::new(std::env::temp_dir().join("ursly-scratch")));
+
+/// Set once the user picks a scratch directory, which the cache default never overrides
+static SCRATCH_DIR_CHOSEN: AtomicBool = AtomicBool::new(false);
+
+/// `scratch` next to the file cache at `cache_dir`
+pub fn scratch_dir_beside(cache_dir: &Path) -> PathBuf {
+    match cache_dir.parent() {
+        Some(parent) => parent.join("scratch"),
+        None => std::env::temp_dir().join("ursly-scratch"),
+    }
+}
+
+/// Default to the folder beside the running service's file cache, unless
+/// the user already picked a scratch directory
+pub fn use_cache_dir(cache_dir: &Path) {
+    if !SCRATCH_DIR_CHOSEN.load(Ordering::SeqCst) {
+        *SCRATCH_DIR.write() = scratch_dir_beside(cache_dir);
+    }
+}
+
+/// The scratch directory in use
+pub fn scratch_dir() -> PathBuf {
+    SCRATCH_DIR.read().clone()
+}
+
+/// Folder for one kind of scratch file, e.g. `scratch_path("clipboard")`
+pub fn scratch_path(purpose: &str) -> PathBuf {
+    SCRATCH_DIR.read().join(purpose)
+}
+
+/// Write scratch files under `dir` from now on, once it's known to be
+/// writable and to have `MIN_SCRATCH_SPACE` free. Files already written
+/// stay where they are.
+pub async fn set_scratch_dir(dir: &Path, space: &SpaceProvider) -> Result<()> {
+    if !dir.is_absolute() {
+        return Err(VfsError::InvalidInput(format!("Scratch directory must be an absolute path: {:?}", dir)).into());
+    }
+    
+    fs::create_dir_all(dir).await
+        .with_context(|| format!("Failed to create scratch directory {:?}", dir))?;
+    
+    let probe = dir.join(format!(".ursly-scratch-{}", uuid::Uuid::new_v4()));
+    fs::write(&probe, b"").await
+        .map_err(|e| VfsError::PermissionDenied(format!("Scratch directory {:?} is not writable: {}", dir, e)))?;
+    fs::remove_file(&probe).await.ok();
+    
+    check_free_space(space, dir, MIN_SCRATCH_SPACE)?;
+    
+    *SCRATCH_DIR.write() = dir.to_path_buf();
+    SCRATCH_DIR_CHOSEN.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::domain::InsufficientSpaceError;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_rejects_relative_and_full_dirs() {
+        let temp = TempDir::new().unwrap();
+        let roomy = |_: &Path| Ok::<_, anyhow::Error>(u64::MAX);
+        let full = |_: &Path| Ok::<_, anyhow::Error>(MIN_SCRATCH_SPACE);
+        
+        assert!(set_scratch_dir(Path::new("scratch"), &roomy).await.is_err());
+        
+        let err = set_scratch_dir(temp.path(), &full).await.unwrap_err();
+        assert!(err.downcast_ref::<InsufficientSpaceError>().is_some());
+        assert_ne!(scratch_dir(), temp.path());
+    }
+    
+    #[test]
+    fn test_scratch_dir_sits_beside_the_cache() {
+        assert_eq!(
+            scratch_dir_beside(Path::new("/Volumes/Fast/ursly/cache")),
+            PathBuf::from("/Volumes/Fast/ursly/scratch")
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Selection Info - item counts and total size of a selection, for the status bar
+//!
+//! Selected folders are measured by walking them, which on a large tree takes
+//! a while: partial totals are reported as the walk goes, the walk stops as
+//! soon as its token is cancelled (the selection changed), and on remote
+//! sources finished folder totals are remembered until something under them
+//! changes.
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::vfs::domain::{FileSize, VfsError};
+use crate::vfs::ports::IFileOperations;
+
+/// Partial totals are reported at most this often
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress callback - receives the totals counted so far
+pub type SelectionProgress = dyn Fn(&SelectionInfo) + Send + Sync;
+
+/// What a selection adds up to. Counts include everything inside selected
+/// folders, and the folders themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionInfo {
+    pub file_count: u64,
+    pub dir_count: u64,
+    pub total_bytes: u64,
+    /// `total_bytes` formatted for display, e.g. `4.30 GB`
+    pub human: String,
+}
+
+impl SelectionInfo {
+    fn add(&mut self, totals: DirTotals) {
+        self.file_count += totals.files;
+        self.dir_count += totals.dirs;
+        self.total_bytes += totals.bytes;
+    }
+    
+    fn with_human(mut self) -> Self {
+        self.human = FileSize::from_bytes(self.total_bytes).as_human_readable();
+        self
+    }
+}
+
+/// Contents of one folder, not counting the folder itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirTotals {
+    pub files: u64,
+    pub dirs: u64,
+    pub bytes: u64,
+}
+
+/// Measured folder totals keyed by `(source_id, path)`
+#[derive(Default)]
+pub struct DirSizeCache {
+    entries: RwLock<HashMap<(String, PathBuf), DirTotals>>,
+}
+
+impl DirSizeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn get(&self, source_id: &str, path: &Path) -> Option<DirTotals> {
+        self.entries.read().get(&(source_id.to_string(), path.to_path_buf())).copied()
+    }
+    
+    pub fn insert(&self, source_id: &str, path: &Path, totals: DirTotals) {
+        self.entries.write().insert((source_id.to_string(), path.to_path_buf()), totals);
+    }
+    
+    /// Forget totals of every folder containing, or inside, a changed `path`
+    pub fn invalidate(&self, source_id: &str, path: &Path) {
+        self.entries.write().retain(|(cached_source, cached_path), _| {
+            cached_source != source_id
+                || !(path.starts_with(cached_path) || cached_path.starts_with(path))
+        });
+    }
+    
+    pub fn invalidate_source(&self, source_id: &str) {
+        self.entries.write().retain(|(cached_source, _), _| cached_source != source_id);
+    }
+}
+
+/// Count and size `paths`, walking into folders. Folder totals are looked up
+/// in and added to `cache` when one is given.
+pub async fn measure_selection(
+    file_ops: &dyn IFileOperations,
+    source_id: &str,
+    paths: &[PathBuf],
+    cache: Option<&DirSizeCache>,
+    cancel: &CancellationToken,
+    progress: &SelectionProgress,
+) -> Result<SelectionInfo> {
+    let mut info = SelectionInfo::default();
+    let mut last_report = Instant::now();
+    
+    for path in paths {
+        let stat = file_ops.stat(path).await?;
+        if !stat.is_dir {
+            info.file_count += 1;
+            info.total_bytes += stat.size;
+            continue;
+        }
+        info.dir_count += 1;
+        
+        if let Some(totals) = cache.and_then(|cache| cache.get(source_id, path)) {
+            info.add(totals);
+            continue;
+        }
+        
+        let mut totals = DirTotals::default();
+        let mut pending = vec![path.clone()];
+        while let Some(dir) = pending.pop() {
+            if cancel.is_cancelled() {
+                return Err(VfsError::Cancelled("Selection size canceled".to_string()).into());
+            }
+            
+            for entry in file_ops.list(&dir).await? {
+                if entry.is_dir {
+                    totals.dirs += 1;
+                    pending.push(dir.join(&entry.name));
+                } else {
+                    totals.files += 1;
+                    totals.bytes += entry.size;
+                }
+            }
+            
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                let mut partial = info.clone();
+                partial.add(totals);
+                progress(&partial.with_human());
+                last_report = Instant::now();
+            }
+        }
+        
+        if let Some(cache) = cache {
+            cache.insert(source_id, path, totals);
+        }
+        info.add(totals);
+    }
+    
+    Ok(info.with_human())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_invalidation_drops_containing_folders() {
+        let cache = DirSizeCache::new();
+        for path in ["/shoot", "/shoot/day1", "/other"] {
+            cache.insert("nas", Path::new(path), DirTotals { files: 1, dirs: 0, bytes: 10 });
+        }
+        cache.insert("local", Path::new("/shoot"), DirTotals::default());
+        
+        cache.invalidate("nas", Path::new("/shoot/day1/A001.mov"));
+        
+        assert!(cache.get("nas", Path::new("/shoot")).is_none());
+        assert!(cache.get("nas", Path::new("/shoot/day1")).is_none());
+        assert!(cache.get("nas", Path::new("/other")).is_some());
+        assert!(cache.get("local", Path::new("/shoot")).is_some());
+    }
+}
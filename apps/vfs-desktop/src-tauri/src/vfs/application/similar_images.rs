@@ -0,0 +1,245 @@
+//! Similar Image Finder - groups near-duplicate stills under a folder
+//!
+//! Complements the exact duplicate finder: every decodable image gets a 64-bit
+//! perceptual hash, and images whose hashes differ in at most `threshold` bits
+//! are grouped. Grouping is transitive, so a chain of small edits ends up in
+//! one group even when its ends are further apart than the threshold.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::vfs::adapters::NativeThumbnailAdapter;
+use crate::vfs::application::manifest::collect_files;
+use crate::vfs::ports::StorageAdapter;
+
+/// Differing hash bits still counted as the same picture
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 10;
+
+/// Images larger than this are skipped rather than read into memory
+pub const MAX_HASHED_IMAGE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Progress callback - receives images looked at so far and how many there are
+pub type SimilarImageProgress = dyn Fn(usize, usize) + Send + Sync;
+
+/// Two or more images that look alike
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarImageGroup {
+    /// Full paths, sorted
+    pub paths: Vec<PathBuf>,
+    /// Largest Hamming distance between any two hashes in the group
+    pub max_distance: u32,
+}
+
+/// Number of bits two perceptual hashes differ in
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Group hashed images whose hashes are within `threshold` bits of another
+/// member, largest groups first. Single images are dropped.
+pub fn group_similar(hashes: &[(PathBuf, u64)], threshold: u32) -> Vec<SimilarImageGroup> {
+    // Union-find over indices into `hashes`
+    let mut parent: Vec<usize> = (0..hashes.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    
+    for i in 0..hashes.len() {
+        for j in (i + 1)..hashes.len() {
+            if hamming_distance(hashes[i].1, hashes[j].1) <= threshold {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                if a != b {
+                    parent[b] = a;
+                }
+            }
+        }
+    }
+    
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..hashes.len() {
+        let r = root(&mut parent, i);
+        members.entry(r).or_default().push(i);
+    }
+    
+    let mut groups: Vec<_> = members
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .map(|indices| {
+            let mut max_distance = 0;
+            for (n, &i) in indices.iter().enumerate() {
+                for &j in &indices[n + 1..] {
+                    max_distance = max_distance.max(hamming_distance(hashes[i].1, hashes[j].1));
+                }
+            }
+            let mut paths: Vec<_> = indices.iter().map(|&i| hashes[i].0.clone()).collect();
+            paths.sort();
+            SimilarImageGroup { paths, max_distance }
+        })
+        .collect();
+    
+    groups.sort_by(|a, b| b.paths.len().cmp(&a.paths.len()).then_with(|| a.paths.cmp(&b.paths)));
+    groups
+}
+
+/// Find groups of similar images under `root`.
+/// Files that aren't stills, can't be read or fail to decode are skipped.
+pub async fn find_similar_images(
+    adapter: &dyn StorageAdapter,
+    root: &Path,
+    threshold: u32,
+    progress: &SimilarImageProgress,
+) -> Result<Vec<SimilarImageGroup>> {
+    let images: Vec<PathBuf> = collect_files(adapter, root).await?
+        .into_iter()
+        .filter(|(_, full_path, size)| {
+            let hashable = full_path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(NativeThumbnailAdapter::is_hashable_extension);
+            hashable && *size > 0 && *size <= MAX_HASHED_IMAGE_SIZE
+        })
+        .map(|(_, full_path, _)| full_path)
+        .collect();
+    let mut hashes = Vec::new();
+    
+    progress(0, images.len());
+    for (done, full_path) in images.iter().enumerate() {
+        // One unreadable file (permissions, an archived object) shouldn't
+        // end the whole scan
+        match adapter.read_file(full_path).await {
+            Ok(data) => {
+                let hash = tokio::task::spawn_blocking(move || NativeThumbnailAdapter::perceptual_hash_bytes(&data)).await?;
+                match hash {
+                    Ok(hash) => hashes.push((full_path.clone(), hash)),
+                    Err(e) => debug!("Skipping {:?} in similarity scan: {}", full_path, e),
+                }
+            }
+            Err(e) => warn!("Skipping unreadable {:?} in similarity scan: {:#}", full_path, e),
+        }
+        progress(done + 1, images.len());
+    }
+    
+    Ok(group_similar(&hashes, threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    /// A soft blob on a gradient, rendered at any size
+    fn render(width: u32, height: u32, brightness: i32) -> GrayImage {
+        GrayImage::from_fn(width, height, |x, y| {
+            let (u, v) = (x as f32 / width as f32, y as f32 / height as f32);
+            let blob = 1.0 - ((u - 0.3).powi(2) + (v - 0.6).powi(2)).sqrt() * 2.5;
+            let value = 60.0 + 120.0 * u + 80.0 * blob.max(0.0) + brightness as f32;
+            Luma([value.clamp(0.0, 255.0) as u8])
+        })
+    }
+    
+    #[tokio::test]
+    async fn test_rerenders_group_and_unrelated_image_does_not() {
+        let dir = TempDir::new().unwrap();
+        render(320, 240, 0).save(dir.path().join("shot.png")).unwrap();
+        // Re-export at a different size, a little brighter
+        render(200, 150, 12).save(dir.path().join("shot_export.png")).unwrap();
+        // Unrelated: banded gradient darkening to the right
+        GrayImage::from_fn(320, 240, |x, y| Luma([(230 - x * 3 / 5 + (y / 30) % 2 * 20) as u8]))
+            .save(dir.path().join("bands.png")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not an image").unwrap();
+        
+        let hash = |name: &str| NativeThumbnailAdapter::perceptual_hash(&dir.path().join(name)).unwrap();
+        assert!(hamming_distance(hash("shot.png"), hash("shot_export.png")) <= DEFAULT_SIMILARITY_THRESHOLD);
+        assert!(hamming_distance(hash("shot.png"), hash("bands.png")) > DEFAULT_SIMILARITY_THRESHOLD);
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let reports = parking_lot::Mutex::new(Vec::new());
+        let groups = find_similar_images(&adapter, Path::new("/"), DEFAULT_SIMILARITY_THRESHOLD, &|done, total| {
+            reports.lock().push((done, total));
+        }).await.unwrap();
+        
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, vec![PathBuf::from("/shot.png"), PathBuf::from("/shot_export.png")]);
+        assert_eq!(reports.lock().last(), Some(&(3, 3)));
+    }
+    
+    /// Local folder whose `.png` named `locked` can't be read
+    struct LockedAdapter(LocalStorageAdapter);
+    
+    #[async_trait::async_trait]
+    impl StorageAdapter for LockedAdapter {
+        fn storage_type(&self) -> crate::vfs::domain::StorageSourceType {
+            self.0.storage_type()
+        }
+        
+        fn name(&self) -> &str {
+            self.0.name()
+        }
+        
+        async fn test_connection(&self) -> Result<bool> {
+            self.0.test_connection().await
+        }
+        
+        async fn list_files(&self, path: &Path) -> Result<Vec<crate::vfs::domain::VirtualFile>> {
+            self.0.list_files(path).await
+        }
+        
+        async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+            if path.ends_with("locked.png") {
+                anyhow::bail!("Permission denied: {:?}", path);
+            }
+            self.0.read_file(path).await
+        }
+        
+        async fn read_file_range(&self, path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
+            self.0.read_file_range(path, offset, length).await
+        }
+        
+        async fn write_file(&self, path: &Path, data: &[u8]) -> Result<()> {
+            self.0.write_file(path, data).await
+        }
+        
+        async fn get_metadata(&self, path: &Path) -> Result<crate::vfs::domain::VirtualFile> {
+            self.0.get_metadata(path).await
+        }
+        
+        async fn exists(&self, path: &Path) -> Result<bool> {
+            self.0.exists(path).await
+        }
+        
+        async fn delete(&self, path: &Path) -> Result<()> {
+            self.0.delete(path).await
+        }
+        
+        async fn create_dir(&self, path: &Path) -> Result<()> {
+            self.0.create_dir(path).await
+        }
+        
+        async fn file_size(&self, path: &Path) -> Result<u64> {
+            self.0.file_size(path).await
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_unreadable_image_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        render(320, 240, 0).save(dir.path().join("shot.png")).unwrap();
+        render(200, 150, 12).save(dir.path().join("shot_export.png")).unwrap();
+        render(320, 240, 6).save(dir.path().join("locked.png")).unwrap();
+        
+        let adapter = LockedAdapter(LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string()));
+        let groups = find_similar_images(&adapter, Path::new("/"), DEFAULT_SIMILARITY_THRESHOLD, &|_, _| {}).await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, vec![PathBuf::from("/shot.png"), PathBuf::from("/shot_export.png")]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Tail - the last lines of a text file, and lines appended to it since
+//!
+//! Works through the storage port so a log on a NAS or in a bucket tails the
+//! same as a local one: following checks the file's size and reads only the
+//! bytes past the last read, when the OS reports a change for local files and
+//! on a timer for the rest. A file that gets smaller was rotated (truncated
+//! or replaced), so reading starts again from its beginning. A replacement
+//! that has already grown past the old size by the next check isn't noticed.
+
+use anyhow::Result;
+use std::path::Path;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::vfs::ports::StorageAdapter;
+
+/// How often a followed file the OS can't watch is checked for new lines
+pub const TAIL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bytes read per request, backwards for `last_lines` and forwards when following
+pub const TAIL_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// `last_lines` gives up looking for line breaks this far from the end
+pub const MAX_TAIL_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Callback receiving each batch of lines appended to a followed file;
+/// returns false once nobody is listening, which ends the follow
+pub type TailLines = dyn Fn(Vec<String>) -> bool + Send + Sync;
+
+/// The last `count` lines of the file at `path`
+pub async fn last_lines(adapter: &dyn StorageAdapter, path: &Path, count: usize) -> Result<Vec<String>> {
+    let size = adapter.file_size(path).await?;
+    let mut start = size;
+    let mut tail = Vec::new();
+    
+    // Read backwards until the chunk holds `count` full lines
+    while start > 0 && size - start < MAX_TAIL_BYTES && count > 0 {
+        let len = TAIL_CHUNK_SIZE.min(start);
+        start -= len;
+        let mut chunk = adapter.read_file_range(path, start, len).await?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        
+        let body = tail.strip_suffix(b"\n").unwrap_or(&tail);
+        if body.iter().filter(|b| **b == b'\n').count() >= count {
+            break;
+        }
+    }
+    
+    let body = tail.strip_suffix(b"\n").unwrap_or(&tail);
+    if body.is_empty() {
+        return Ok(Vec::new());
+    }
+    let lines: Vec<&[u8]> = body.split(|b| *b == b'\n').collect();
+    Ok(lines[lines.len().saturating_sub(count)..].iter().map(|line| decode_line(line)).collect())
+}
+
+/// Read position in a followed file, holding back a line until it's complete
+pub struct TailCursor {
+    offset: u64,
+    pending: Vec<u8>,
+}
+
+impl TailCursor {
+    /// Follow from byte `offset`, normally the file's size when following starts
+    pub fn at(offset: u64) -> Self {
+        Self { offset, pending: Vec::new() }
+    }
+    
+    /// Start again from the beginning of the file, e.g. after it was replaced
+    pub fn rewind(&mut self) {
+        self.offset = 0;
+        self.pending.clear();
+    }
+    
+    /// Complete lines appended since the last call
+    pub async fn read_new(&mut self, adapter: &dyn StorageAdapter, path: &Path) -> Result<Vec<String>> {
+        let size = adapter.file_size(path).await?;
+        if size < self.offset {
+            debug!("{:?} shrank from {} to {} bytes, assuming it was rotated", path, self.offset, size);
+            self.rewind();
+        }
+        
+        while self.offset < size {
+            let chunk = adapter.read_file_range(path, self.offset, TAIL_CHUNK_SIZE.min(size - self.offset)).await?;
+            if chunk.is_empty() {
+                break;
+            }
+            self.offset += chunk.len() as u64;
+            self.pending.extend_from_slice(&chunk);
+        }
+        
+        let mut lines = Vec::new();
+        while let Some(end) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            lines.push(decode_line(&line[..end]));
+        }
+        Ok(lines)
+    }
+}
+
+fn decode_line(line: &[u8]) -> String {
+    String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    use crate::vfs::adapters::LocalStorageAdapter;
+    
+    #[tokio::test]
+    async fn test_last_lines_and_rotation() {
+        let dir = TempDir::new().unwrap();
+        let log: String = (1..=5000).map(|i| format!("request {}\r\n", i)).collect();
+        std::fs::write(dir.path().join("render.log"), &log).unwrap();
+        
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let path = Path::new("/render.log");
+        
+        let last = last_lines(&adapter, path, 3).await.unwrap();
+        assert_eq!(last, vec!["request 4998", "request 4999", "request 5000"]);
+        assert_eq!(last_lines(&adapter, path, 10_000).await.unwrap().len(), 5000);
+        
+        // After truncation the cursor starts over instead of waiting for the old size
+        let mut cursor = TailCursor::at(log.len() as u64);
+        std::fs::write(dir.path().join("render.log"), "fresh\npartial").unwrap();
+        assert_eq!(cursor.read_new(&adapter, path).await.unwrap(), vec!["fresh"]);
+        std::fs::write(dir.path().join("render.log"), "fresh\npartial line\n").unwrap();
+        assert_eq!(cursor.read_new(&adapter, path).await.unwrap(), vec!["partial line"]);
+    }
+}
@@ -0,0 +1,130 @@
+//! Temp GC - removes clipboard exports and thumbnail leftovers nobody will
+//! read again
+//!
+//! Clipboard exports have to outlive the copy (the paste can come much
+//! later), so nothing deletes them at the time and the folder grows without
+//! bound. Thumbnail generators delete their output once it's read, so
+//! anything left in the thumbnail folder is from a generator that died
+//! partway. Both are swept on startup and then on a timer, keeping files
+//! younger than the TTL so a pending paste or running generator isn't
+//! pulled out from under.
+
+use anyhow::Result;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::vfs::application::scratch::scratch_path;
+
+/// How long clipboard exports and thumbnail leftovers are kept
+pub const DEFAULT_TEMP_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How often the background sweep runs
+pub const DEFAULT_TEMP_GC_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Scratch folders that are swept
+pub const SWEPT_SCRATCH_DIRS: &[&str] = &["clipboard", "thumbnails"];
+
+static TEMP_TTL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_TEMP_TTL.as_secs());
+
+pub fn temp_ttl() -> Duration {
+    Duration::from_secs(TEMP_TTL_SECS.load(Ordering::Relaxed))
+}
+
+/// Keep temp files for `ttl` from the next sweep on
+pub fn set_temp_ttl(ttl: Duration) {
+    TEMP_TTL_SECS.store(ttl.as_secs(), Ordering::Relaxed);
+}
+
+/// Sweep every folder in `SWEPT_SCRATCH_DIRS`, returning the bytes reclaimed
+pub async fn collect_temp() -> u64 {
+    let ttl = temp_ttl();
+    let mut reclaimed = 0;
+    
+    for purpose in SWEPT_SCRATCH_DIRS {
+        let dir = scratch_path(purpose);
+        match remove_older_than(&dir, ttl).await {
+            Ok(bytes) => reclaimed += bytes,
+            Err(e) => warn!("Failed to clean up {:?}: {}", dir, e),
+        }
+    }
+    
+    if reclaimed > 0 {
+        info!("Reclaimed {} bytes of temp files", reclaimed);
+    }
+    reclaimed
+}
+
+/// Sweep now and then every `interval`
+pub fn start_temp_gc(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            collect_temp().await;
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+/// Delete files directly in `dir` last modified more than `ttl` ago,
+/// returning their total size. A missing folder has nothing to delete.
+pub async fn remove_older_than(dir: &Path, ttl: Duration) -> Result<u64> {
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let now = SystemTime::now();
+    let mut reclaimed = 0;
+    
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        
+        let age = metadata.modified().ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+        if age <= ttl {
+            continue;
+        }
+        
+        match fs::remove_file(entry.path()).await {
+            Ok(()) => {
+                debug!("Removed stale temp file {:?}", entry.path());
+                reclaimed += metadata.len();
+            }
+            Err(e) => warn!("Failed to remove {:?}: {}", entry.path(), e),
+        }
+    }
+    
+    Ok(reclaimed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_old_exports_removed_recent_kept() {
+        let temp = TempDir::new().unwrap();
+        let old = temp.path().join("A001.mov");
+        let recent = temp.path().join("A002.mov");
+        std::fs::write(&old, b"yesterday").unwrap();
+        std::fs::write(&recent, b"today").unwrap();
+        
+        let two_hours_ago = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+        let mtime = filetime::FileTime::from_system_time(two_hours_ago);
+        filetime::set_file_mtime(&old, mtime).unwrap();
+        
+        let reclaimed = remove_older_than(temp.path(), DEFAULT_TEMP_TTL).await.unwrap();
+        assert_eq!(reclaimed, 9);
+        assert!(!old.exists());
+        assert!(recent.exists());
+        
+        assert_eq!(remove_older_than(&temp.path().join("missing"), DEFAULT_TEMP_TTL).await.unwrap(), 0);
+    }
+}
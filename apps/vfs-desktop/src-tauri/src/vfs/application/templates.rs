@@ -0,0 +1,101 @@
+//! New File Templates - Finder-style "New Document"
+//!
+//! Built-in templates create `Untitled.<ext>` files; user templates (stored by
+//! `TemplateStore`) create a file named after the template. Names that are
+//! taken get a number, the way copies do: `Untitled.txt`, `Untitled 2.txt`, ...
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Contents and default file name for a new file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTemplate {
+    /// Name the template is picked by (`text`, `markdown`, or a user template)
+    pub name: String,
+    
+    /// File name given to new files before collision numbering
+    pub file_name: String,
+    
+    #[serde(skip)]
+    pub contents: Vec<u8>,
+}
+
+/// Templates that are always available
+pub fn builtin_templates() -> Vec<FileTemplate> {
+    let template = |name: &str, file_name: &str, contents: &str| FileTemplate {
+        name: name.to_string(),
+        file_name: file_name.to_string(),
+        contents: contents.as_bytes().to_vec(),
+    };
+    
+    vec![
+        template("text", "Untitled.txt", ""),
+        template("markdown", "Untitled.md", "# Untitled\n\n"),
+        template("json", "Untitled.json", "{}\n"),
+    ]
+}
+
+/// Next name to try after `original_name` is taken, marking it with `label`:
+/// with `" copy"`, `file.txt` -> `file copy.txt` -> `file copy 2.txt` -> `file copy 3.txt`;
+/// with `""`, `Untitled.txt` -> `Untitled 2.txt` -> `Untitled 3.txt`
+pub fn next_name(original_name: &str, label: &str) -> String {
+    let (name, ext) = match original_name.rfind('.') {
+        Some(dot) if dot > 0 => original_name.split_at(dot),
+        _ => (original_name, ""),
+    };
+    
+    // "file copy 2" -> "file copy 3"
+    if let Some((head, num)) = name.rsplit_once(' ') {
+        if let Ok(num) = num.parse::<u32>() {
+            if head.ends_with(label) {
+                return format!("{} {}{}", head, num + 1, ext);
+            }
+        }
+    }
+    // "file copy" -> "file copy 2"
+    if name.ends_with(label) {
+        return format!("{} 2{}", name, ext);
+    }
+    format!("{}{}{}", name, label, ext)
+}
+
+/// Generate a copy name for files/folders (e.g., "file.txt" -> "file copy.txt")
+pub fn generate_copy_name(original_name: &str) -> String {
+    next_name(original_name, " copy")
+}
+
+/// Reject template names that can't be used as a single file name
+pub fn validate_template_name(name: &str) -> Result<()> {
+    if name.trim().is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains('/')
+        || name.contains('\\')
+    {
+        return Err(anyhow::anyhow!("Invalid template name: {:?}", name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_numbered_names() {
+        assert_eq!(next_name("Untitled.txt", ""), "Untitled 2.txt");
+        assert_eq!(next_name("Untitled 2.txt", ""), "Untitled 3.txt");
+        assert_eq!(next_name("Shot List 2", ""), "Shot List 3");
+        assert_eq!(next_name(".env", ""), ".env 2");
+        
+        assert_eq!(generate_copy_name("file.txt"), "file copy.txt");
+        assert_eq!(generate_copy_name("file copy.txt"), "file copy 2.txt");
+        assert_eq!(generate_copy_name("file copy 2.txt"), "file copy 3.txt");
+        assert_eq!(generate_copy_name("Take 2.mov"), "Take 2 copy.mov");
+        assert_eq!(generate_copy_name("Renders"), "Renders copy");
+        
+        assert!(validate_template_name("Call Sheet.md").is_ok());
+        assert!(validate_template_name("../escape").is_err());
+    }
+}
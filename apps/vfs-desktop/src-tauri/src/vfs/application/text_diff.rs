@@ -0,0 +1,153 @@
+//! Text Diff - unified-diff-style line comparison of two text files
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+/// Largest file (per side) accepted for diffing
+pub const MAX_DIFF_FILE_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Lines of unchanged context around each change
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Bytes inspected by the binary-file heuristic
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// Marker for a diff line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineKind {
+    /// Unchanged line (` `)
+    Context,
+    /// Line only in the new file (`+`)
+    Added,
+    /// Line only in the old file (`-`)
+    Removed,
+}
+
+/// A single line in a hunk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    
+    /// Line text without the trailing newline
+    pub content: String,
+    
+    /// 1-based line number in the old file (None for added lines)
+    pub old_line: Option<usize>,
+    
+    /// 1-based line number in the new file (None for removed lines)
+    pub new_line: Option<usize>,
+}
+
+/// A group of nearby changes, like a `@@ -a,b +c,d @@` block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+impl DiffHunk {
+    /// Unified diff header, e.g. `@@ -1,3 +1,4 @@`
+    pub fn header(&self) -> String {
+        format!("@@ -{},{} +{},{} @@", self.old_start, self.old_lines, self.new_start, self.new_lines)
+    }
+}
+
+/// Null-byte heuristic (same approach as git): binary if the first 8000 bytes contain NUL
+pub fn is_binary(data: &[u8]) -> bool {
+    data.iter().take(BINARY_SNIFF_LEN).any(|&b| b == 0)
+}
+
+/// Decode a file for diffing, refusing binary content
+pub fn decode_text(data: &[u8], label: &str) -> Result<String> {
+    if is_binary(data) {
+        anyhow::bail!("Cannot diff binary file: {}", label);
+    }
+    
+    Ok(String::from_utf8_lossy(data).into_owned())
+}
+
+/// Line diff of `old` against `new`, grouped into hunks with context
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    
+    diff.grouped_ops(DIFF_CONTEXT_LINES)
+        .iter()
+        .filter_map(|group| {
+            let first = group.first()?;
+            let last = group.last()?;
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+            
+            let lines = group.iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Context,
+                        ChangeTag::Insert => DiffLineKind::Added,
+                        ChangeTag::Delete => DiffLineKind::Removed,
+                    },
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                })
+                .collect();
+            
+            Some(DiffHunk {
+                // Unified diff convention: an empty range starts at the line before it
+                old_start: if old_range.is_empty() { old_range.start } else { old_range.start + 1 },
+                old_lines: old_range.len(),
+                new_start: if new_range.is_empty() { new_range.start } else { new_range.start + 1 },
+                new_lines: new_range.len(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_diff_marks_inserted_and_deleted_lines() {
+        let old = "TITLE: Reel 1\n001 AX V C 00:00:00:00\n002 AX V C 00:00:05:00\n";
+        let new = "TITLE: Reel 1\n001 AX V C 00:00:00:00\n002 BX V C 00:00:06:00\n003 AX V C 00:00:09:00\n";
+        
+        let hunks = diff_lines(old, new);
+        assert_eq!(hunks.len(), 1);
+        
+        let hunk = &hunks[0];
+        assert_eq!(hunk.header(), "@@ -1,3 +1,4 @@");
+        
+        let removed: Vec<_> = hunk.lines.iter().filter(|l| l.kind == DiffLineKind::Removed).collect();
+        let added: Vec<_> = hunk.lines.iter().filter(|l| l.kind == DiffLineKind::Added).collect();
+        
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].content, "002 AX V C 00:00:05:00");
+        assert_eq!(removed[0].old_line, Some(3));
+        
+        assert_eq!(added.len(), 2);
+        assert_eq!(added[0].content, "002 BX V C 00:00:06:00");
+        assert_eq!(added[1].content, "003 AX V C 00:00:09:00");
+        assert_eq!(added[1].new_line, Some(4));
+        
+        assert_eq!(hunk.lines[0].kind, DiffLineKind::Context);
+    }
+    
+    #[test]
+    fn test_identical_text_has_no_hunks() {
+        assert!(diff_lines("a\nb\n", "a\nb\n").is_empty());
+    }
+    
+    #[test]
+    fn test_binary_detection() {
+        assert!(is_binary(b"PK\x03\x04\x00\x00"));
+        assert!(!is_binary(b"plain text"));
+        assert!(decode_text(b"\x00\x01", "clip.mov").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Transfer Throttle - token bucket for capping transfer bandwidth
+//!
+//! Every chunk of a transfer is debited from the bucket before it is handed to
+//! the destination. When the bucket runs dry the caller sleeps until enough
+//! tokens have refilled, so a single throttle shared by concurrent transfers
+//! caps their combined throughput rather than each one individually.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Chunk size for unthrottled transfers
+pub const TRANSFER_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Smallest chunk used when throttling, so very low limits still make progress
+const MIN_THROTTLED_CHUNK: u64 = 16 * 1024;
+
+struct Bucket {
+    /// Bytes per second, `None` = unlimited
+    rate: Option<u64>,
+    /// Available bytes; negative while callers are sleeping off a deficit
+    tokens: f64,
+    /// When `tokens` was last refilled
+    last_refill: Instant,
+}
+
+/// Token-bucket bandwidth limiter (at most one second of burst)
+pub struct TransferThrottle {
+    bucket: Mutex<Bucket>,
+}
+
+impl TransferThrottle {
+    /// Create a throttle; `None` disables limiting
+    pub fn new(bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                rate: bytes_per_sec.filter(|r| *r > 0),
+                tokens: 0.0,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+    
+    /// Change the limit; takes effect for the next chunk of every transfer
+    pub fn set_limit(&self, bytes_per_sec: Option<u64>) {
+        let mut bucket = self.bucket.lock();
+        bucket.rate = bytes_per_sec.filter(|r| *r > 0);
+        bucket.tokens = 0.0;
+        bucket.last_refill = Instant::now();
+    }
+    
+    /// Current limit in bytes per second
+    pub fn limit(&self) -> Option<u64> {
+        self.bucket.lock().rate
+    }
+    
+    /// Chunk size that keeps pacing smooth at the current limit
+    pub fn chunk_size(&self) -> u64 {
+        match self.limit() {
+            Some(rate) => (rate / 4).clamp(MIN_THROTTLED_CHUNK, TRANSFER_CHUNK_SIZE),
+            None => TRANSFER_CHUNK_SIZE,
+        }
+    }
+    
+    /// Debit `bytes` and wait until the bucket can cover them
+    pub async fn acquire(&self, bytes: u64) {
+        let wait = {
+            let mut bucket = self.bucket.lock();
+            let Some(rate) = bucket.rate else {
+                return;
+            };
+            let rate = rate as f64;
+            
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+            bucket.last_refill = now;
+            bucket.tokens -= bytes as f64;
+            
+            if bucket.tokens < 0.0 {
+                Duration::from_secs_f64(-bucket.tokens / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+        
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl Default for TransferThrottle {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    
+    #[tokio::test]
+    async fn test_unlimited_does_not_wait() {
+        let throttle = TransferThrottle::default();
+        let start = Instant::now();
+        throttle.acquire(100 * 1024 * 1024).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+    
+    #[tokio::test]
+    async fn test_limit_shared_across_transfers() {
+        // Two concurrent 40 KiB transfers at 80 KiB/s together need ~1s
+        let throttle = Arc::new(TransferThrottle::new(Some(80 * 1024)));
+        let start = Instant::now();
+        
+        let tasks: Vec<_> = (0..2).map(|_| {
+            let throttle = throttle.clone();
+            tokio::spawn(async move {
+                for _ in 0..10 {
+                    throttle.acquire(4 * 1024).await;
+                }
+            })
+        }).collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,329 @@
+//! Transcode Queue - runs transcodes a few at a time, in submission order
+//!
+//! Jobs wait as `Pending` until a slot is free. Pausing stops new jobs from
+//! being dispatched so the machine can be used for editing; jobs already
+//! running are left to finish, and resuming picks up where the queue left off.
+//!
+//! Jobs live in the executor's registry, so their progress is whatever the
+//! executor last reported and cancelling a running job stops the executor.
+
+use anyhow::Result;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::{error, info};
+
+use crate::vfs::ports::{StreamFormat, TranscodeExecutor, TranscodeJob, TranscodeJobs, TranscodeQuality, TranscodeStatus};
+
+/// Transcodes run at once; each one already keeps every core busy
+pub const DEFAULT_TRANSCODE_CONCURRENCY: usize = 1;
+
+/// The queue as shown to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscodeQueueStatus {
+    pub paused: bool,
+    /// Every job, in submission order
+    pub jobs: Vec<TranscodeJob>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Ids of the queue's jobs, in submission order
+    order: Vec<String>,
+    running: usize,
+    paused: bool,
+}
+
+pub struct TranscodeQueue {
+    executor: Arc<dyn TranscodeExecutor>,
+    jobs: TranscodeJobs,
+    output_dir: RwLock<PathBuf>,
+    concurrency: usize,
+    state: Mutex<QueueState>,
+    /// Woken whenever a job finishes
+    finished: Notify,
+}
+
+impl TranscodeQueue {
+    pub fn new(executor: Arc<dyn TranscodeExecutor>, output_dir: PathBuf, concurrency: usize) -> Self {
+        Self {
+            jobs: executor.jobs(),
+            executor,
+            output_dir: RwLock::new(output_dir),
+            concurrency: concurrency.max(1),
+            state: Mutex::new(QueueState::default()),
+            finished: Notify::new(),
+        }
+    }
+    
+    /// Write the output of jobs enqueued from now on under `dir`
+    pub fn set_output_dir(&self, dir: PathBuf) {
+        *self.output_dir.write() = dir;
+    }
+    
+    /// Add a job; it starts as soon as a slot is free and the queue isn't paused
+    pub fn enqueue(self: &Arc<Self>, source: &Path, format: StreamFormat, quality: TranscodeQuality) -> TranscodeJob {
+        let id = uuid::Uuid::new_v4().to_string();
+        let job = TranscodeJob {
+            output_path: self.output_dir.read().join(&id),
+            id,
+            source_path: source.to_path_buf(),
+            format,
+            quality,
+            status: TranscodeStatus::Pending,
+            progress: 0,
+            error: None,
+            stream_url: None,
+        };
+        
+        {
+            let mut state = self.state.lock();
+            self.jobs.write().insert(job.id.clone(), job.clone());
+            state.order.push(job.id.clone());
+        }
+        self.dispatch();
+        job
+    }
+    
+    /// Stop starting new jobs; running ones finish
+    pub fn pause(&self) {
+        self.state.lock().paused = true;
+        info!("Transcode queue paused");
+    }
+    
+    pub fn resume(self: &Arc<Self>) {
+        self.state.lock().paused = false;
+        info!("Transcode queue resumed");
+        self.dispatch();
+    }
+    
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().paused
+    }
+    
+    pub fn status(&self) -> TranscodeQueueStatus {
+        let state = self.state.lock();
+        let jobs = self.jobs.read();
+        TranscodeQueueStatus {
+            paused: state.paused,
+            jobs: state.order.iter().filter_map(|id| jobs.get(id).cloned()).collect(),
+        }
+    }
+    
+    /// Cancel a job that is waiting or running; returns whether there was
+    /// one to cancel
+    pub async fn cancel(&self, job_id: &str) -> Result<bool> {
+        let running = {
+            let state = self.state.lock();
+            if !state.order.iter().any(|id| id == job_id) {
+                return Ok(false);
+            }
+            let mut jobs = self.jobs.write();
+            let Some(job) = jobs.get_mut(job_id) else {
+                return Ok(false);
+            };
+            let running = match job.status {
+                TranscodeStatus::Pending => false,
+                TranscodeStatus::Processing => true,
+                _ => return Ok(false),
+            };
+            job.status = TranscodeStatus::Cancelled;
+            running
+        };
+        
+        // The slot frees up once the executor gives up on the job
+        if running {
+            self.executor.cancel(job_id).await?;
+        }
+        info!("Transcode job {} cancelled", job_id);
+        self.finished.notify_waiters();
+        Ok(true)
+    }
+    
+    /// Wait for a job to complete, fail or be cancelled; `None` if there is
+    /// no such job
+    pub async fn wait(&self, job_id: &str) -> Option<TranscodeJob> {
+        loop {
+            let notified = self.finished.notified();
+            tokio::pin!(notified);
+            // Registered before checking, so a job finishing in between still wakes us
+            notified.as_mut().enable();
+            
+            let job = self.jobs.read().get(job_id).cloned()?;
+            if !matches!(job.status, TranscodeStatus::Pending | TranscodeStatus::Processing) {
+                return Some(job);
+            }
+            notified.await;
+        }
+    }
+    
+    /// Start pending jobs while there are free slots
+    fn dispatch(self: &Arc<Self>) {
+        let mut state = self.state.lock();
+        while !state.paused && state.running < self.concurrency {
+            let job = {
+                let mut jobs = self.jobs.write();
+                let pending = state.order.iter()
+                    .find(|id| jobs.get(*id).is_some_and(|job| job.status == TranscodeStatus::Pending));
+                let Some(job) = pending.and_then(|id| jobs.get_mut(id)) else {
+                    break;
+                };
+                job.status = TranscodeStatus::Processing;
+                job.clone()
+            };
+            state.running += 1;
+            
+            let queue = self.clone();
+            tokio::spawn(async move {
+                let result = queue.executor
+                    .execute(&job.id, &job.source_path, &job.output_path, job.format, job.quality)
+                    .await;
+                
+                {
+                    let mut state = queue.state.lock();
+                    state.running -= 1;
+                    let mut jobs = queue.jobs.write();
+                    // A cancelled job stays cancelled, however the executor ended
+                    if let Some(entry) = jobs.get_mut(&job.id).filter(|entry| entry.status != TranscodeStatus::Cancelled) {
+                        match result {
+                            Ok(output) => {
+                                entry.status = TranscodeStatus::Completed;
+                                entry.progress = 100;
+                                entry.stream_url = Some(format!("/stream/{}/{}", job.id, output.file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default()));
+                                entry.output_path = output;
+                            }
+                            Err(e) => {
+                                error!("Transcoding {:?} failed: {}", job.source_path, e);
+                                entry.status = TranscodeStatus::Failed;
+                                entry.error = Some(e.to_string());
+                            }
+                        }
+                    }
+                }
+                queue.finished.notify_waiters();
+                queue.dispatch();
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+    
+    /// Each job reports itself half done, then blocks until the test hands
+    /// out a permit or cancels it
+    struct GatedExecutor {
+        jobs: TranscodeJobs,
+        started: AtomicUsize,
+        gate: Semaphore,
+        cancelled: Notify,
+    }
+    
+    impl GatedExecutor {
+        fn new() -> Self {
+            Self {
+                jobs: TranscodeJobs::default(),
+                started: AtomicUsize::new(0),
+                gate: Semaphore::new(0),
+                cancelled: Notify::new(),
+            }
+        }
+    }
+    
+    #[async_trait]
+    impl TranscodeExecutor for GatedExecutor {
+        fn jobs(&self) -> TranscodeJobs {
+            self.jobs.clone()
+        }
+        
+        async fn execute(&self, job_id: &str, _: &Path, output_dir: &Path, _: StreamFormat, _: TranscodeQuality) -> anyhow::Result<PathBuf> {
+            self.started.fetch_add(1, Ordering::SeqCst);
+            if let Some(job) = self.jobs.write().get_mut(job_id) {
+                job.progress = 50;
+            }
+            tokio::select! {
+                permit = self.gate.acquire() => permit?.forget(),
+                _ = self.cancelled.notified() => anyhow::bail!("killed"),
+            }
+            Ok(output_dir.join("playlist.m3u8"))
+        }
+        
+        async fn cancel(&self, _: &str) -> anyhow::Result<()> {
+            self.cancelled.notify_one();
+            Ok(())
+        }
+    }
+    
+    async fn wait_until(mut condition: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("condition not reached");
+    }
+    
+    #[tokio::test]
+    async fn test_paused_queue_starts_nothing_until_resumed() {
+        let executor = Arc::new(GatedExecutor::new());
+        let queue = Arc::new(TranscodeQueue::new(executor.clone(), PathBuf::from("/tmp/transcodes"), 1));
+        
+        let first = queue.enqueue(Path::new("/a.mov"), StreamFormat::HLS, TranscodeQuality::High);
+        let second = queue.enqueue(Path::new("/b.mov"), StreamFormat::HLS, TranscodeQuality::High);
+        wait_until(|| executor.started.load(Ordering::SeqCst) == 1).await;
+        
+        // The running job is allowed to finish while paused
+        queue.pause();
+        executor.gate.add_permits(1);
+        let status_of = |id: &str| queue.status().jobs.into_iter().find(|j| j.id == id).unwrap().status;
+        wait_until(|| status_of(&first.id) == TranscodeStatus::Completed).await;
+        
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(executor.started.load(Ordering::SeqCst), 1);
+        assert_eq!(status_of(&second.id), TranscodeStatus::Pending);
+        assert!(queue.status().paused);
+        
+        queue.resume();
+        wait_until(|| executor.started.load(Ordering::SeqCst) == 2).await;
+        executor.gate.add_permits(1);
+        wait_until(|| status_of(&second.id) == TranscodeStatus::Completed).await;
+        assert!(!queue.is_paused());
+    }
+    
+    #[tokio::test]
+    async fn test_cancelling_running_job_stops_it_and_frees_the_slot() {
+        let executor = Arc::new(GatedExecutor::new());
+        let queue = Arc::new(TranscodeQueue::new(executor.clone(), PathBuf::from("/tmp/transcodes"), 1));
+        
+        let first = queue.enqueue(Path::new("/a.mov"), StreamFormat::HLS, TranscodeQuality::High);
+        let second = queue.enqueue(Path::new("/b.mov"), StreamFormat::HLS, TranscodeQuality::High);
+        let job = |id: &str| queue.status().jobs.into_iter().find(|j| j.id == id).unwrap();
+        
+        // Progress is what the executor reported to the shared registry
+        wait_until(|| job(&first.id).progress == 50).await;
+        assert_eq!(job(&first.id).status, TranscodeStatus::Processing);
+        
+        assert!(queue.cancel(&first.id).await.unwrap());
+        assert_eq!(queue.wait(&first.id).await.unwrap().status, TranscodeStatus::Cancelled);
+        wait_until(|| executor.started.load(Ordering::SeqCst) == 2).await;
+        
+        // Finished and unknown jobs have nothing to cancel
+        assert!(!queue.cancel(&first.id).await.unwrap());
+        assert!(!queue.cancel("no-such-job").await.unwrap());
+        
+        executor.gate.add_permits(1);
+        assert_eq!(queue.wait(&second.id).await.unwrap().status, TranscodeStatus::Completed);
+        assert_eq!(job(&first.id).status, TranscodeStatus::Cancelled);
+    }
+}
@@ -0,0 +1,152 @@
+//! In-flight Transfers - what is currently reading from or writing to each source
+//!
+//! Hydrations and cross-storage copies register here for as long as they run.
+//! Ejecting a volume consults the registry so it doesn't pull the disk out from
+//! under a transfer, and a forced eject cancels them through their tokens.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::vfs::domain::VfsError;
+
+/// Public view of a running transfer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferInfo {
+    pub id: String,
+    
+    /// Sources the transfer reads from or writes to
+    pub source_ids: Vec<String>,
+    
+    /// Human-readable description, e.g. `Copy "/shoot/A001.mov"`
+    pub description: String,
+    
+    pub started_at: DateTime<Utc>,
+}
+
+struct ActiveTransfer {
+    info: TransferInfo,
+    cancel: CancellationToken,
+}
+
+/// Registry of running transfers, keyed by transfer ID
+#[derive(Default)]
+pub struct TransferRegistry {
+    active: RwLock<HashMap<String, ActiveTransfer>>,
+}
+
+impl TransferRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Register a transfer; it stays registered until the guard is dropped
+    pub fn register(self: &Arc<Self>, source_ids: &[&str], description: impl Into<String>) -> TransferGuard {
+        let info = TransferInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_ids: source_ids.iter().map(|s| s.to_string()).collect(),
+            description: description.into(),
+            started_at: Utc::now(),
+        };
+        let cancel = CancellationToken::new();
+        
+        let id = info.id.clone();
+        self.active.write().insert(id.clone(), ActiveTransfer { info, cancel: cancel.clone() });
+        
+        TransferGuard {
+            registry: self.clone(),
+            id,
+            cancel,
+        }
+    }
+    
+    /// Run `work` as a registered transfer, abandoning it if it gets cancelled
+    pub async fn run<T, F>(self: &Arc<Self>, source_ids: &[&str], description: impl Into<String>, work: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let guard = self.register(source_ids, description);
+        tokio::select! {
+            result = work => result,
+            _ = guard.cancel.cancelled() => Err(VfsError::Cancelled("Transfer canceled".to_string()).into()),
+        }
+    }
+    
+    /// Running transfers that touch `source_id`
+    pub fn active_for(&self, source_id: &str) -> Vec<TransferInfo> {
+        self.active.read()
+            .values()
+            .filter(|t| t.info.source_ids.iter().any(|id| id == source_id))
+            .map(|t| t.info.clone())
+            .collect()
+    }
+    
+    /// Cancel every transfer touching `source_id`, returning how many were signalled
+    pub fn cancel_for(&self, source_id: &str) -> usize {
+        let active = self.active.read();
+        let mut canceled = 0;
+        for transfer in active.values().filter(|t| t.info.source_ids.iter().any(|id| id == source_id)) {
+            transfer.cancel.cancel();
+            canceled += 1;
+        }
+        canceled
+    }
+}
+
+/// Keeps a transfer registered; dropping it clears the entry
+pub struct TransferGuard {
+    registry: Arc<TransferRegistry>,
+    id: String,
+    cancel: CancellationToken,
+}
+
+impl TransferGuard {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+    
+    /// Whether the transfer has been asked to stop
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+impl Drop for TransferGuard {
+    fn drop(&mut self) {
+        self.registry.active.write().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_cancel_stops_running_transfer() {
+        let registry = Arc::new(TransferRegistry::new());
+        
+        let guard = registry.register(&["nas", "s3"], "Copy /a.mov");
+        assert_eq!(registry.active_for("s3").len(), 1);
+        assert!(registry.active_for("local").is_empty());
+        drop(guard);
+        assert!(registry.active_for("s3").is_empty());
+        
+        let running = registry.run(&["nas"], "Hydrate /b.mov", std::future::pending::<Result<()>>());
+        let canceller = async {
+            while registry.active_for("nas").is_empty() {
+                tokio::task::yield_now().await;
+            }
+            assert_eq!(registry.cancel_for("nas"), 1);
+        };
+        let (result, _) = tokio::join!(running, canceller);
+        
+        assert!(result.is_err());
+        assert!(registry.active_for("nas").is_empty());
+    }
+}
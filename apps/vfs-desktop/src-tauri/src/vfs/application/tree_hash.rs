@@ -0,0 +1,197 @@
+//! Tree Hash - Merkle hashes of folders for cheap change detection
+//!
+//! A file's hash is the XXH3 digest of its contents; a folder's hash is the
+//! digest of its children's names, kinds and hashes. File hashes are
+//! remembered per `(source, path)` along with the size, modification time and
+//! ETag they were computed for, so a file changed behind the app's back is
+//! read again while unchanged files are not. Folders are listed on every walk,
+//! which costs far less than reading contents. Two trees with the same root
+//! hash are identical; where roots differ, hashing their subfolders (from
+//! cached file hashes) shows which branch changed.
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::vfs::application::manifest::{hash_file, HashAlgorithm, Hasher};
+use crate::vfs::domain::VirtualFile;
+use crate::vfs::ports::StorageAdapter;
+
+/// Root hash of a tree, and how much work it took
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeHash {
+    /// Hex digest of the file or folder
+    pub hash: String,
+    /// Files read to compute it; zero when every hash was cached
+    pub hashed_files: u64,
+}
+
+/// What a file's cached hash was computed for; a file that no longer
+/// matches is read again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeafStamp {
+    pub size: u64,
+    pub modified: SystemTime,
+    /// ETag or checksum reported by the source, if any
+    pub etag: Option<String>,
+}
+
+impl From<&VirtualFile> for LeafStamp {
+    fn from(file: &VirtualFile) -> Self {
+        Self {
+            size: file.size.bytes(),
+            modified: file.last_modified,
+            etag: file.checksum.clone(),
+        }
+    }
+}
+
+/// File hashes keyed by `(source_id, path)`, with the stamp they're valid for
+#[derive(Default)]
+pub struct TreeHashCache {
+    entries: RwLock<HashMap<(String, PathBuf), (LeafStamp, String)>>,
+}
+
+impl TreeHashCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// The cached hash of `path`, if it was computed for the same `stamp`
+    pub fn get(&self, source_id: &str, path: &Path, stamp: &LeafStamp) -> Option<String> {
+        self.entries.read()
+            .get(&(source_id.to_string(), path.to_path_buf()))
+            .filter(|(cached, _)| cached == stamp)
+            .map(|(_, hash)| hash.clone())
+    }
+    
+    pub fn insert(&self, source_id: &str, path: &Path, stamp: LeafStamp, hash: String) {
+        self.entries.write().insert((source_id.to_string(), path.to_path_buf()), (stamp, hash));
+    }
+    
+    /// Forget the hashes of a changed `path` and of anything inside it
+    pub fn invalidate(&self, source_id: &str, path: &Path) {
+        self.entries.write().retain(|(cached_source, cached_path), _| {
+            cached_source != source_id || !cached_path.starts_with(path)
+        });
+    }
+    
+    pub fn invalidate_source(&self, source_id: &str) {
+        self.entries.write().retain(|(cached_source, _), _| cached_source != source_id);
+    }
+}
+
+/// Hash the file or folder at `path`, reusing cached hashes below it
+pub async fn tree_hash(
+    adapter: &dyn StorageAdapter,
+    source_id: &str,
+    path: &Path,
+    cache: &TreeHashCache,
+) -> Result<TreeHash> {
+    let mut hashed_files = 0;
+    let stat = adapter.get_metadata(path).await?;
+    let hash = if stat.is_directory {
+        hash_dir(adapter, source_id, path, cache, &mut hashed_files).await?
+    } else {
+        hash_leaf(adapter, source_id, &stat, cache, &mut hashed_files).await?
+    };
+    
+    Ok(TreeHash { hash, hashed_files })
+}
+
+async fn hash_leaf(
+    adapter: &dyn StorageAdapter,
+    source_id: &str,
+    file: &VirtualFile,
+    cache: &TreeHashCache,
+    hashed_files: &mut u64,
+) -> Result<String> {
+    let stamp = LeafStamp::from(file);
+    if let Some(hash) = cache.get(source_id, &file.path, &stamp) {
+        return Ok(hash);
+    }
+    
+    let hash = hash_file(adapter, &file.path, stamp.size, HashAlgorithm::XxHash, |_| {}).await?;
+    *hashed_files += 1;
+    cache.insert(source_id, &file.path, stamp, hash.clone());
+    Ok(hash)
+}
+
+/// Boxed because folders recurse into folders
+fn hash_dir<'a>(
+    adapter: &'a dyn StorageAdapter,
+    source_id: &'a str,
+    path: &'a Path,
+    cache: &'a TreeHashCache,
+    hashed_files: &'a mut u64,
+) -> BoxFuture<'a, Result<String>> {
+    async move {
+        let mut entries = adapter.list_files(path).await?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        let mut hasher = Hasher::new(HashAlgorithm::XxHash);
+        for entry in entries {
+            let (kind, hash) = if entry.is_directory {
+                ("d", hash_dir(adapter, source_id, &entry.path, cache, hashed_files).await?)
+            } else {
+                ("f", hash_leaf(adapter, source_id, &entry, cache, hashed_files).await?)
+            };
+            hasher.update(format!("{}\0{}\0{}\n", entry.name, kind, hash).as_bytes());
+        }
+        
+        Ok(hasher.finish())
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn stamp(size: u64) -> LeafStamp {
+        LeafStamp { size, modified: SystemTime::UNIX_EPOCH, etag: None }
+    }
+    
+    #[test]
+    fn test_invalidation_drops_the_changed_branch() {
+        let cache = TreeHashCache::new();
+        for path in ["/shoot/day1/A001.mov", "/shoot/day1/A002.mov", "/other/B001.mov"] {
+            cache.insert("nas", Path::new(path), stamp(6), path.to_string());
+        }
+        
+        cache.invalidate("nas", Path::new("/shoot/day1"));
+        
+        assert!(cache.get("nas", Path::new("/shoot/day1/A001.mov"), &stamp(6)).is_none());
+        assert!(cache.get("nas", Path::new("/shoot/day1/A002.mov"), &stamp(6)).is_none());
+        assert!(cache.get("nas", Path::new("/other/B001.mov"), &stamp(6)).is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_file_changed_behind_the_cache_is_read_again() {
+        use crate::vfs::adapters::LocalStorageAdapter;
+        
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("day1")).unwrap();
+        std::fs::write(dir.path().join("day1/A001.mov"), "frames").unwrap();
+        std::fs::write(dir.path().join("day1/A002.mov"), "frames").unwrap();
+        let adapter = LocalStorageAdapter::new(dir.path().to_path_buf(), "Local".to_string());
+        let cache = TreeHashCache::new();
+        
+        let first = tree_hash(&adapter, "local", Path::new("/"), &cache).await.unwrap();
+        assert_eq!(first.hashed_files, 2);
+        let again = tree_hash(&adapter, "local", Path::new("/"), &cache).await.unwrap();
+        assert_eq!(again, TreeHash { hash: first.hash.clone(), hashed_files: 0 });
+        
+        // No invalidation: the new size alone gives the change away
+        std::fs::write(dir.path().join("day1/A002.mov"), "more frames").unwrap();
+        let changed = tree_hash(&adapter, "local", Path::new("/"), &cache).await.unwrap();
+        assert_eq!(changed.hashed_files, 1);
+        assert_ne!(changed.hash, first.hash);
+    }
+}
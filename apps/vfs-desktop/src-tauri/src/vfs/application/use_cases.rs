@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use crate::vfs::application::VfsService;
 use crate::vfs::domain::{VirtualFile, StorageSource, TranscodeFormat};
+use crate::vfs::ports::ListOptions;
 
 /// Use case: List files in a storage source
 pub struct ListFilesUseCase {
@@ -18,7 +19,7 @@ impl ListFilesUseCase {
     }
     
     pub async fn execute(&self, source_id: &str, path: &Path) -> Result<Vec<VirtualFile>> {
-        self.vfs_service.list_files(source_id, path).await
+        self.vfs_service.list_files(source_id, path, &ListOptions::default()).await
     }
 }
 
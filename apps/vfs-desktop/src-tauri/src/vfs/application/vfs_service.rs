@@ -2,22 +2,111 @@
 
 use anyhow::Result;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 use tracing::{debug, error, info, warn};
+use tokio_util::sync::CancellationToken;
 
-use crate::vfs::adapters::{LocalStorageAdapter, NvmeCacheAdapter};
+use crate::vfs::adapters::{
+    LocalStorageAdapter, NvmeCacheAdapter, RecentsStore, RecentEntry, TemplateStore, BookmarksStore, Bookmark,
+    SourcesStore, SettingsStore, KeychainCredentialStore, MemoryCredentialStore, DEFAULT_CREDENTIAL_SERVICE, AutomountEntry,
+    SearchHistoryStore, RecentSearch, DEFAULT_RECENTS_CAPACITY, DEFAULT_RECENT_DESTINATIONS_CAPACITY,
+    DEFAULT_SEARCH_HISTORY_CAPACITY,
+};
+use crate::vfs::infrastructure::{AuditLog, AuditEntry, AuditOperation};
+use crate::vfs::domain::hashing::{hash_chunks, Hasher};
+use crate::vfs::infrastructure::audit_log::DEFAULT_AUDIT_LOG_MAX_BYTES;
 use crate::vfs::domain::{
     StorageSource, StorageSourceType, ConnectionStatus, StorageConfig,
-    VirtualFile, CacheConfig, StorageTier,
+    VirtualFile, CacheConfig, StorageTier, ReadOnlyError, InsufficientSpaceError, QuotaExceededError,
+    VolumeBusyError, VfsError, RetryPolicy, TransferTuning, StorageCategory, MountConfig, CredentialRef, LifecyclePolicy,
+    IgnorePatterns, WarmStatus, SourceHealth, ConnectionTestResult, ArchiveNotRetrievedError, RetrievalTier,
+    DeleteSummary, SourceBatchResult, ResolvedPath, SourceOption,
 };
 use crate::vfs::domain::events::*;
+use crate::vfs::application::resumable_download::{download_to_part, PartialDownloadState};
+use crate::vfs::application::text_diff::{self, DiffHunk, MAX_DIFF_FILE_SIZE};
+use crate::vfs::application::archive::{self, ArchiveFormat, ArchiveProgress};
+use crate::vfs::application::throttle::TransferThrottle;
+use crate::vfs::application::parallel_copy::{run_bounded, DEFAULT_COPY_CONCURRENCY};
+use crate::vfs::application::manifest::{self, HashAlgorithm, Manifest, ManifestProgress, ManifestVerification};
+use crate::vfs::application::duplicates::{self, DuplicateGroup, DuplicateProgress};
+use crate::vfs::application::similar_images::{self, SimilarImageGroup, SimilarImageProgress};
+use crate::vfs::application::folder_diff::{self, DirDiff, DirDiffProgress};
+use crate::vfs::application::path_format::{self, PathStyle};
+use crate::vfs::application::batch_rename::{self, RenameMapping};
+use crate::vfs::application::free_space::{check_free_space, system_space_provider, SpaceProvider};
+use crate::vfs::application::transfers::TransferRegistry;
+use crate::vfs::application::copy_progress::{
+    self, CopyProgressFn, ProgressMeter, COPY_PROGRESS_POLL_INTERVAL, PROGRESS_COPY_THRESHOLD,
+};
+use crate::vfs::application::listing_cache::ListingCache;
+use crate::vfs::application::templates::{self, FileTemplate};
+use crate::vfs::application::lifecycle::{self, LifecycleCandidate, TierChangeReport, WalkCursor, LIFECYCLE_FOLDERS_PER_PASS};
+use crate::vfs::application::catalog::{self, CatalogEntry, CatalogIndexer, IndexStatus};
+use crate::vfs::application::transcode_queue::TranscodeQueue;
+use crate::vfs::application::proxies::{self, ProxyLink, ProxyProgress, ProxyReport};
+use crate::vfs::application::operation_history::{FileOperation, OperationHistory, RecordedOperation};
+use crate::vfs::application::selection::{self, DirSizeCache, SelectionInfo, SelectionProgress};
+use crate::vfs::application::tree_hash::{self, TreeHash, TreeHashCache};
+use crate::vfs::application::file_info::{self, FileInfo};
+use crate::vfs::application::preview::PreviewData;
+use crate::vfs::application::remote_thumbnail;
+use crate::vfs::application::case_sensitivity::{self, case_collisions};
+use crate::vfs::application::tail::{self, TailCursor, TailLines};
+use crate::vfs::application::fan_out::{self, FanOutResult};
+use crate::vfs::application::temp_gc;
 use crate::vfs::ports::{
-    StorageAdapter, CacheAdapter, EventBus, CacheStats,
+    StorageAdapter, CacheAdapter, EventBus, CacheStats, CredentialStore, CACHE_BLOCK_SIZE,
+    IMetadataStore, FileMetadata, TierTransition,
     IFileOperations, FileStat, CopyOptions, MoveOptions,
+    SignedUrlProvider, ObjectVersioning, ObjectVersion, ServerSideCopy, MAX_SIGNED_URL_EXPIRY, ListOptions,
+    CrossStorageOptions,
+    StreamFormat, TranscodeQuality, TranscodeStatus, IMediaService, MediaInfo,
 };
+use crate::vfs::platform::get_permissions;
+use crate::vfs::platform::watch_file;
+use crate::vfs::ports::media::{detect_mime, mime_from_extension, MIME_SNIFF_BYTES};
+
+/// How long a forced eject waits for cancelled transfers to stop
+const EJECT_CANCEL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Files hydrated at once by a batch warm when the caller doesn't choose
+pub const DEFAULT_WARM_CONCURRENCY: usize = 4;
+
+/// Most keys S3 returns per listing call
+pub const MAX_LIST_PAGE_SIZE: usize = 1000;
+
+/// Smallest multipart part S3 accepts (except the last)
+pub const MIN_UPLOAD_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+
+/// Most bytes one range hydration or ranged read covers; longer requests are
+/// cut short
+pub const MAX_HYDRATE_RANGE: u64 = 256 * 1024 * 1024;
+
+/// Most bytes fetched (and held in memory) by one ranged read while hydrating
+const MAX_HYDRATE_RUN: u64 = 16 * CACHE_BLOCK_SIZE;
+
+/// Source id recorded in the audit log for operations on the system trash
+pub const SYSTEM_TRASH_SOURCE: &str = "system-trash";
+
+fn source_not_found(source_id: &str) -> anyhow::Error {
+    VfsError::NotFound(format!("Storage source not found: {}", source_id)).into()
+}
+
+/// Key of a source's file in the shared cache. Qualified by the source, so
+/// the same path on two sources never shares a cached copy.
+fn cache_key(source_id: &str, path: &Path) -> PathBuf {
+    Path::new("/").join(source_id).join(path.strip_prefix("/").unwrap_or(path))
+}
+
+/// Keychain account a source's SSE-C key is saved under
+fn sse_key_account(source_id: &str) -> String {
+    format!("{}/sse-c", source_id)
+}
 
 /// VFS Service - Orchestrates storage, caching, and hydration
 pub struct VfsService {
@@ -29,6 +118,91 @@ pub struct VfsService {
     
     /// Event bus (optional, for Tauri integration)
     event_bus: Option<Arc<dyn EventBus>>,
+    
+    /// Recently opened/accessed files
+    recents: Arc<RecentsStore>,
+    
+    /// Folders recently copied or moved into
+    recent_destinations: Arc<RecentsStore>,
+    
+    /// Searches recently run
+    search_history: Arc<SearchHistoryStore>,
+    
+    /// Pinned folders shown in the sidebar
+    bookmarks: Arc<BookmarksStore>,
+    
+    /// Sources re-mounted at startup
+    sources_store: Arc<SourcesStore>,
+    
+    /// App-wide preferences kept between sessions
+    settings: Arc<SettingsStore>,
+    
+    /// Where mount secrets are kept (the OS keychain in the app)
+    credentials: Arc<dyn CredentialStore>,
+    
+    /// Bandwidth limit shared by all cross-storage transfers
+    transfer_throttle: Arc<TransferThrottle>,
+    
+    /// Free-space lookup for local destinations and the cache volume
+    space_provider: Arc<SpaceProvider>,
+    
+    /// Trash directory used instead of the system trash, laid out like a
+    /// freedesktop trash (`files/` and `info/`)
+    trash_dir: Option<PathBuf>,
+    
+    /// Record of deletes, moves and renames
+    audit_log: Arc<AuditLog>,
+    
+    /// Bytes stored per source, computed on first use and dropped on writes/deletes
+    usage: Arc<RwLock<HashMap<String, u64>>>,
+    
+    /// Hydrations and copies currently running, per source
+    transfers: Arc<TransferRegistry>,
+    
+    /// Files queued or downloading into the cache, by source and path
+    warming: Arc<RwLock<HashSet<(String, PathBuf)>>>,
+    
+    /// Tier changes under way, by source and path, with the tier each file is moving to
+    tier_changes: Arc<RwLock<HashMap<(String, PathBuf), StorageTier>>>,
+    
+    /// Tier changes of archived files waiting for their restore, by source
+    /// and path, with the tier each file moves to once readable
+    pending_restores: Arc<RwLock<HashMap<(String, PathBuf), StorageTier>>>,
+    
+    /// Whether each source tells names apart by case, once probed
+    case_probes: Arc<RwLock<HashMap<String, bool>>>,
+    
+    /// Where each source's background lifecycle walk left off
+    lifecycle_cursors: Arc<RwLock<HashMap<String, WalkCursor>>>,
+    
+    /// Recent directory listings, dropped on changes under them
+    listings: Arc<ListingCache>,
+    
+    /// User "New Document" templates
+    templates: Arc<TemplateStore>,
+    
+    /// Where tier history is recorded; replaceable at runtime when the user
+    /// switches tag storage
+    metadata: RwLock<Option<Arc<dyn IMetadataStore>>>,
+    
+    /// Junk files hidden from listings and skipped by recursive operations
+    ignore: RwLock<IgnorePatterns>,
+    
+    /// Searchable index of each source's files
+    catalog: Arc<CatalogIndexer>,
+    
+    /// Undo / redo stacks for renames, moves, trashing and new folders
+    history: Arc<OperationHistory>,
+    
+    /// Measured folder sizes, dropped on changes under them
+    dir_sizes: Arc<DirSizeCache>,
+    
+    /// Merkle hashes of files and folders, dropped on changes under them
+    tree_hashes: Arc<TreeHashCache>,
+    
+    /// Temporary directory holding the throwaway stores of a service made
+    /// by `with_cache_config`; deleted along with the service
+    scratch_dir: Option<tempfile::TempDir>,
 }
 
 struct StorageSourceState {
@@ -36,29 +210,432 @@ struct StorageSourceState {
     adapter: Arc<dyn StorageAdapter>,
     /// Optional reference to file operations (same adapter, different trait)
     file_ops: Option<Arc<dyn IFileOperations>>,
+    /// Optional signed URL support (object storage adapters only)
+    signed_urls: Option<Arc<dyn SignedUrlProvider>>,
+    /// Optional object version history (S3 only)
+    versions: Option<Arc<dyn ObjectVersioning>>,
+    /// Optional copying from other buckets without downloading (S3 only)
+    server_copy: Option<Arc<dyn ServerSideCopy>>,
 }
 
-impl VfsService {
-    /// Create a new VFS service with default cache configuration
-    pub async fn new() -> Result<Self> {
-        let cache_config = CacheConfig::default();
-        let cache = Arc::new(NvmeCacheAdapter::new(cache_config).await?);
-        
+/// The tier a file is in once everything the app knows is taken into
+/// account. A cached file is read from local NVMe whatever its storage
+/// class; otherwise a tier change under way wins over the tier the source
+/// last reported.
+fn reconcile_tier(reported: StorageTier, moving_to: Option<StorageTier>, cached: bool) -> StorageTier {
+    if cached {
+        return StorageTier::Hot;
+    }
+    moving_to.unwrap_or(reported)
+}
+
+/// Whether `err` says the file sits in an archive tier and hasn't been restored
+fn is_unrestored(err: &anyhow::Error) -> bool {
+    matches!(VfsError::from(err), VfsError::ArchiveNotRetrieved { .. })
+}
+
+/// Whether listings of a source are worth keeping in memory. Remote sources
+/// cost a round trip per listing; local volumes are cheap to list and are
+/// changed by other apps without the VFS hearing about it, so a cached
+/// listing of one would go stale.
+fn caches_listings(source: &StorageSource) -> bool {
+    matches!(
+        source.source_type.category(),
+        StorageCategory::Cloud | StorageCategory::Network | StorageCategory::Hybrid
+    )
+}
+
+/// Files per listing whose first bytes may be read to tell their type; the
+/// rest are typed when they're previewed or inspected
+const LISTING_SNIFF_LIMIT: usize = 64;
+
+/// Sniffing reads in flight at once
+const SNIFF_CONCURRENCY: usize = 8;
+
+/// Fill in the MIME type of listed files. Files whose extension says nothing
+/// get their first few KB read (a range read on remote storage), a few at a
+/// time and at most `limit` of them; any beyond that are left untyped.
+/// Archived objects, which can't be read without a restore, are not read.
+async fn sniff_content_types(adapter: &dyn StorageAdapter, files: &mut [VirtualFile], limit: usize) {
+    let mut to_read = Vec::new();
+    for (index, file) in files.iter_mut().enumerate() {
+        if file.is_directory || file.content_type.is_some() {
+            continue;
+        }
+        let readable = mime_from_extension(&file.path).is_none() && file.size.bytes() > 0
+            && file.tier_status.current_tier != StorageTier::Archive;
+        if !readable {
+            file.content_type = Some(detect_mime(&file.path, None).to_string());
+        } else if to_read.len() < limit {
+            to_read.push((index, file.path.clone()));
+        }
+    }
+    
+    let heads: Vec<(usize, Option<Vec<u8>>)> = futures::stream::iter(to_read)
+        .map(|(index, path)| async move {
+            match adapter.read_file_range(&path, 0, MIME_SNIFF_BYTES).await {
+                Ok(head) => (index, Some(head)),
+                Err(e) => {
+                    debug!("Could not sniff {:?}: {:#}", path, e);
+                    (index, None)
+                }
+            }
+        })
+        .buffer_unordered(SNIFF_CONCURRENCY)
+        .collect()
+        .await;
+    for (index, head) in heads {
+        let file = &mut files[index];
+        file.content_type = Some(detect_mime(&file.path, head.as_deref()).to_string());
+    }
+}
+
+/// Copy one object with `copier` (the destination's, and the source bucket).
+/// `false` when there is no copier or the object is too large to copy
+/// server-side, so it has to be streamed.
+async fn copy_server_side(
+    copier: Option<&(Arc<dyn ServerSideCopy>, String)>,
+    from: &Path,
+    to: &Path,
+    size: u64,
+) -> Result<bool> {
+    let Some((copier, source_bucket)) = copier else {
+        return Ok(false);
+    };
+    match copier.copy_object_from(source_bucket, from, to, size).await {
+        Ok(()) => Ok(true),
+        Err(e) if matches!(e.downcast_ref::<VfsError>(), Some(VfsError::Unsupported(_))) => {
+            debug!("Streaming {:?} instead of copying server-side: {}", from, e);
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Copy within a source with the adapter's own copy, reporting the
+/// destination's size to `progress` while a file big enough for that to
+/// matter copies
+async fn copy_reporting(
+    file_ops: &Arc<dyn IFileOperations>,
+    from: &Path,
+    to: &Path,
+    options: CopyOptions,
+    progress: Arc<CopyProgressFn>,
+) -> Result<()> {
+    let stat = file_ops.stat(from).await?;
+    if stat.is_dir || stat.size < PROGRESS_COPY_THRESHOLD {
+        return file_ops.copy(from, to, options).await;
+    }
+    copy_progress::copy_polling_destination(
+        file_ops, from, to, options, stat.size, COPY_PROGRESS_POLL_INTERVAL, progress,
+    ).await
+}
+
+/// Digest of the `size` bytes at `path`, read back with ranged reads
+async fn hash_written(file_ops: &Arc<dyn IFileOperations>, path: &Path, size: u64, algo: HashAlgorithm) -> Result<String> {
+    let mut hasher = Hasher::new(algo);
+    let mut offset = 0u64;
+    while offset < size {
+        let chunk = file_ops.read_range(path, offset, manifest::HASH_CHUNK_SIZE.min(size - offset)).await?;
+        if chunk.is_empty() {
+            anyhow::bail!("Unexpected end of {:?} at byte {} of {}", path, offset, size);
+        }
+        hasher.update(&chunk);
+        offset += chunk.len() as u64;
+    }
+    Ok(hasher.finish())
+}
+
+/// Validate `config`, build its adapter and check the storage is reachable
+async fn connect_source(config: &MountConfig) -> Result<StorageSourceState> {
+    use crate::vfs::adapters::{
+        S3StorageAdapter, GcsStorageAdapter, NasStorageAdapter, NasProtocol, FsxOntapAdapter,
+        BlockStorageAdapter,
+    };
+    
+    config.validate()?;
+    
+    let name = config.name().to_string();
+    let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+    let source = |source_type: StorageSourceType, mount_point: Option<PathBuf>, storage_config: StorageConfig| {
+        StorageSource {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.clone(),
+            source_type,
+            status: ConnectionStatus::Connected,
+            mounted: true,
+            mount_point,
+            config: StorageConfig {
+                read_only: config.read_only(),
+                quota_bytes: config.quota_bytes(),
+                ..storage_config
+            },
+        }
+    };
+    
+    let mut state = match config {
+        MountConfig::Local { path, .. } => {
+            let adapter = Arc::new(LocalStorageAdapter::new(path.clone(), name.clone()));
+            StorageSourceState {
+                source: source(StorageSourceType::Local, Some(path.clone()), StorageConfig {
+                    path_or_bucket: path.to_string_lossy().to_string(),
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter),
+                signed_urls: None,
+                versions: None,
+                server_copy: None,
+            }
+        }
+        MountConfig::S3 { bucket, region, access_key_id, secret_access_key, endpoint, path_style, sse, require_encryption, flat_listing, .. } => {
+            let region = non_empty(region);
+            let endpoint = non_empty(endpoint);
+            let (access_key, secret_key) = (non_empty(access_key_id), non_empty(secret_access_key));
+            // MinIO/Ceph generally need path-style; default to it whenever an endpoint is set
+            let path_style = path_style.unwrap_or(endpoint.is_some());
+            
+            let adapter = Arc::new(S3StorageAdapter::new(
+                bucket.clone(),
+                region.clone().unwrap_or_default(),
+                access_key.clone(),
+                secret_key.clone(),
+                endpoint.clone(),
+                path_style,
+                name.clone(),
+                sse.clone(),
+            ).await?.with_required_encryption(*require_encryption));
+            adapter.set_flat_listing(*flat_listing);
+            StorageSourceState {
+                source: source(StorageSourceType::S3, None, StorageConfig {
+                    path_or_bucket: bucket.clone(),
+                    region,
+                    endpoint,
+                    access_key,
+                    secret_key,
+                    path_style,
+                    sse: sse.clone(),
+                    require_encryption: *require_encryption,
+                    flat_listing: *flat_listing,
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter.clone()),
+                signed_urls: Some(adapter.clone()),
+                versions: Some(adapter.clone()),
+                server_copy: Some(adapter),
+            }
+        }
+        MountConfig::Gcs { bucket, credentials_path, .. } => {
+            let credentials_path = non_empty(credentials_path);
+            let adapter = Arc::new(
+                GcsStorageAdapter::new(bucket.clone(), credentials_path.clone(), name.clone()).await?
+            );
+            StorageSourceState {
+                source: source(StorageSourceType::Gcs, None, StorageConfig {
+                    path_or_bucket: bucket.clone(),
+                    // GCS operators read the credentials path from the endpoint field
+                    endpoint: credentials_path,
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter.clone()),
+                signed_urls: Some(adapter),
+                versions: None,
+                server_copy: None,
+            }
+        }
+        MountConfig::Nas { host, share, protocol, mount_point, username, password, .. } => {
+            let protocol = protocol.to_ascii_lowercase();
+            let (nas_protocol, source_type) = match protocol.as_str() {
+                "nfs" => (NasProtocol::NFS, StorageSourceType::Nfs),
+                "smb" => (NasProtocol::SMB, StorageSourceType::Smb),
+                "afp" => (NasProtocol::AFP, StorageSourceType::Nas),
+                _ => (NasProtocol::Unknown, StorageSourceType::Nas),
+            };
+            let adapter = Arc::new(NasStorageAdapter::new(
+                mount_point.clone(),
+                name.clone(),
+                nas_protocol,
+                Some(host.clone()),
+            ));
+            StorageSourceState {
+                source: source(source_type, Some(mount_point.clone()), StorageConfig {
+                    path_or_bucket: mount_point.to_string_lossy().to_string(),
+                    endpoint: Some(format!("{}://{}/{}", protocol, host, share.trim_start_matches('/'))),
+                    access_key: non_empty(username),
+                    secret_key: non_empty(password),
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter),
+                signed_urls: None,
+                versions: None,
+                server_copy: None,
+            }
+        }
+        MountConfig::Fsx { endpoint, mount_point, s3_access_point, .. } => {
+            let adapter = Arc::new(FsxOntapAdapter::new(
+                mount_point.clone(),
+                name.clone(),
+                non_empty(s3_access_point),
+                Some(endpoint.clone()),
+            ));
+            StorageSourceState {
+                source: source(StorageSourceType::FsxOntap, Some(mount_point.clone()), StorageConfig {
+                    path_or_bucket: mount_point.to_string_lossy().to_string(),
+                    endpoint: Some(endpoint.clone()),
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter),
+                signed_urls: None,
+                versions: None,
+                server_copy: None,
+            }
+        }
+        MountConfig::Block { mount_point, .. } => {
+            let adapter = Arc::new(BlockStorageAdapter::new(mount_point.clone(), name.clone()));
+            StorageSourceState {
+                source: source(StorageSourceType::Block, Some(mount_point.clone()), StorageConfig {
+                    path_or_bucket: mount_point.to_string_lossy().to_string(),
+                    ..Default::default()
+                }),
+                adapter: adapter.clone(),
+                file_ops: Some(adapter),
+                signed_urls: None,
+                versions: None,
+                server_copy: None,
+            }
+        }
+    };
+    
+    if let Some(tuning) = config.transfer_tuning() {
+        state.adapter.set_transfer_tuning(tuning);
+        state.source.config.transfer_tuning = Some(tuning);
+    }
+    state.source.config.lifecycle_policy = config.lifecycle_policy();
+    if let Some(policy) = config.retry_policy() {
+        state.adapter.set_retry_policy(policy);
+        state.source.config.retry_policy = Some(policy);
+    }
+    
+    match state.adapter.test_connection().await {
+        Ok(true) => Ok(state),
+        Ok(false) => Err(anyhow::anyhow!("Cannot connect to {}: storage is not reachable", name)),
+        Err(e) => Err(e.context(format!("Cannot connect to {}", name))),
+    }
+}
+
+/// Where the service keeps what the user builds up between sessions:
+/// Recents, bookmarks, remembered sources, settings, templates, the audit
+/// log and mount secrets
+pub struct UserStores {
+    pub recents: Arc<RecentsStore>,
+    pub recent_destinations: Arc<RecentsStore>,
+    pub search_history: Arc<SearchHistoryStore>,
+    pub bookmarks: Arc<BookmarksStore>,
+    pub sources_store: Arc<SourcesStore>,
+    pub settings: Arc<SettingsStore>,
+    pub templates: Arc<TemplateStore>,
+    pub audit_log: Arc<AuditLog>,
+    pub credentials: Arc<dyn CredentialStore>,
+}
+
+impl UserStores {
+    /// The user's own stores in the app data and config directories, with
+    /// secrets in the OS keychain
+    pub async fn default_stores() -> Result<Self> {
         Ok(Self {
-            sources: Arc::new(RwLock::new(HashMap::new())),
-            cache,
-            event_bus: None,
+            recents: Arc::new(RecentsStore::default_store().await?),
+            recent_destinations: Arc::new(RecentsStore::default_destinations_store().await?),
+            search_history: Arc::new(SearchHistoryStore::default_store().await?),
+            bookmarks: Arc::new(BookmarksStore::default_store().await?),
+            sources_store: Arc::new(SourcesStore::default_store().await?),
+            settings: Arc::new(SettingsStore::default_store().await?),
+            templates: Arc::new(TemplateStore::default_store()),
+            audit_log: Arc::new(AuditLog::default_log().await?),
+            credentials: Arc::new(KeychainCredentialStore::new()),
         })
     }
     
-    /// Create with custom cache configuration
+    /// Stores under `dir`, with secrets kept in memory
+    pub async fn in_dir(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            recents: Arc::new(RecentsStore::new(dir.join("recents.json"), DEFAULT_RECENTS_CAPACITY).await?),
+            recent_destinations: Arc::new(
+                RecentsStore::new(dir.join("recent_destinations.json"), DEFAULT_RECENT_DESTINATIONS_CAPACITY).await?
+            ),
+            search_history: Arc::new(
+                SearchHistoryStore::new(dir.join("search_history.json"), DEFAULT_SEARCH_HISTORY_CAPACITY).await?
+            ),
+            bookmarks: Arc::new(BookmarksStore::new(dir.join("bookmarks.json")).await?),
+            sources_store: Arc::new(SourcesStore::new(dir.join("sources.json")).await?),
+            settings: Arc::new(SettingsStore::new(dir.join("settings.json")).await?),
+            templates: Arc::new(TemplateStore::new(dir.join("templates"))),
+            audit_log: Arc::new(AuditLog::new(dir.join("audit.log"), DEFAULT_AUDIT_LOG_MAX_BYTES)),
+            credentials: Arc::new(MemoryCredentialStore::new()),
+        })
+    }
+}
+
+impl VfsService {
+    /// Create a new VFS service with default cache configuration and
+    /// throwaway stores (see `with_cache_config`)
+    pub async fn new() -> Result<Self> {
+        Self::with_cache_config(CacheConfig::default()).await
+    }
+    
+    /// Create with custom cache configuration and throwaway stores.
+    ///
+    /// Recents, sources, settings and the rest are kept in a temporary
+    /// directory deleted along with the service, and secrets in memory, so
+    /// tests and tools never touch the user's own data or keychain. The app
+    /// builds its service with `with_stores` and `UserStores::default_stores()`.
     pub async fn with_cache_config(cache_config: CacheConfig) -> Result<Self> {
+        let scratch_dir = tempfile::Builder::new().prefix("ursly-vfs-").tempdir()?;
+        let stores = UserStores::in_dir(scratch_dir.path()).await?;
+        let mut service = Self::with_stores(cache_config, stores).await?;
+        service.scratch_dir = Some(scratch_dir);
+        Ok(service)
+    }
+    
+    /// Create with custom cache configuration, keeping what the user builds
+    /// up between sessions in `stores`
+    pub async fn with_stores(cache_config: CacheConfig, stores: UserStores) -> Result<Self> {
         let cache = Arc::new(NvmeCacheAdapter::new(cache_config).await?);
         
         Ok(Self {
             sources: Arc::new(RwLock::new(HashMap::new())),
             cache,
             event_bus: None,
+            recents: stores.recents,
+            recent_destinations: stores.recent_destinations,
+            search_history: stores.search_history,
+            bookmarks: stores.bookmarks,
+            sources_store: stores.sources_store,
+            ignore: RwLock::new(stores.settings.get().ignore_patterns.unwrap_or_default()),
+            settings: stores.settings,
+            credentials: stores.credentials,
+            transfer_throttle: Arc::new(TransferThrottle::default()),
+            space_provider: system_space_provider(),
+            trash_dir: None,
+            audit_log: stores.audit_log,
+            usage: Arc::new(RwLock::new(HashMap::new())),
+            transfers: Arc::new(TransferRegistry::new()),
+            warming: Arc::new(RwLock::new(HashSet::new())),
+            tier_changes: Arc::new(RwLock::new(HashMap::new())),
+            pending_restores: Arc::new(RwLock::new(HashMap::new())),
+            case_probes: Arc::new(RwLock::new(HashMap::new())),
+            lifecycle_cursors: Arc::new(RwLock::new(HashMap::new())),
+            listings: Arc::new(ListingCache::default()),
+            templates: stores.templates,
+            metadata: RwLock::new(None),
+            catalog: Arc::new(CatalogIndexer::new()),
+            history: Arc::new(OperationHistory::default()),
+            dir_sizes: Arc::new(DirSizeCache::new()),
+            tree_hashes: Arc::new(TreeHashCache::new()),
+            scratch_dir: None,
         })
     }
     
@@ -67,197 +644,805 @@ impl VfsService {
         self.event_bus = Some(event_bus);
     }
     
-    /// Register a local storage source
+    /// Replace the free-space lookup (e.g. to simulate a nearly full volume)
+    pub fn set_space_provider(&mut self, space_provider: Arc<SpaceProvider>) {
+        self.space_provider = space_provider;
+    }
+    
+    /// Trash into `trash_dir` instead of the system trash (e.g. so tests
+    /// never touch the user's own trash)
+    pub fn set_trash_dir(&mut self, trash_dir: PathBuf) {
+        self.trash_dir = Some(trash_dir);
+    }
+    
+    /// Registry of in-flight transfers
+    pub fn transfers(&self) -> &Arc<TransferRegistry> {
+        &self.transfers
+    }
+    
+    /// Register a local storage source, connected the way `mount_source`
+    /// would connect it but not remembered for the next launch
     pub async fn add_local_source(&self, name: String, path: PathBuf) -> Result<StorageSource> {
-        let adapter = Arc::new(LocalStorageAdapter::new(path.clone(), name.clone()));
+        let config = MountConfig::Local { name: name.clone(), path: path.clone(), read_only: false, quota_bytes: None };
+        let source = self.register_source(&config).await?;
+        
+        info!("Added local storage source: {} at {:?}", name, path);
+        
+        Ok(source)
+    }
+    
+    /// Register an empty in-memory source ("RAM disk"); its contents are lost
+    /// when it is removed or the app quits
+    pub async fn add_memory_source(&self, name: String) -> Result<StorageSource> {
+        use crate::vfs::adapters::{InMemoryStorageAdapter, MEMORY_PROVIDER_ID};
         
-        // LocalStorageAdapter implements both StorageAdapter and IFileOperations
+        let adapter = Arc::new(InMemoryStorageAdapter::new(name.clone()));
         let file_ops: Arc<dyn IFileOperations> = adapter.clone();
         
         let source = StorageSource {
             id: uuid::Uuid::new_v4().to_string(),
             name: name.clone(),
-            source_type: StorageSourceType::Local,
+            source_type: StorageSourceType::Custom(MEMORY_PROVIDER_ID.to_string()),
             status: ConnectionStatus::Connected,
             mounted: true,
-            mount_point: Some(path.clone()),
-            config: StorageConfig {
-                path_or_bucket: path.to_string_lossy().to_string(),
-                ..Default::default()
-            },
+            mount_point: None,
+            config: StorageConfig::default(),
         };
         
         self.sources.write().insert(source.id.clone(), StorageSourceState {
             source: source.clone(),
             adapter,
             file_ops: Some(file_ops),
+            signed_urls: None,
+            versions: None,
+            server_copy: None,
         });
         
-        info!("Added local storage source: {} at {:?}", name, path);
+        info!("Added memory storage source: {}", name);
         
         Ok(source)
     }
     
-    /// Register an S3 storage source
-    pub async fn add_s3_source(
-        &self,
-        name: String,
-        bucket: String,
-        region: String,
-        access_key: Option<String>,
-        secret_key: Option<String>,
-        endpoint: Option<String>,
-    ) -> Result<StorageSource> {
-        use crate::vfs::adapters::S3StorageAdapter;
+    /// Register a block storage source (mounted EBS volume, NVMe drive,
+    /// partition), connected the way `mount_source` would connect it but not
+    /// remembered for the next launch
+    pub async fn add_block_source(&self, name: String, mount_point: PathBuf) -> Result<StorageSource> {
+        let config = MountConfig::Block { name: name.clone(), mount_point: mount_point.clone(), read_only: false, quota_bytes: None };
+        let source = self.register_source(&config).await.map_err(|e| {
+            anyhow::anyhow!("Block device mount point not accessible: {:?}: {:#}", mount_point, e)
+        })?;
         
-        info!("[add_s3_source] Creating S3 source - name: {}, bucket: {}, region: {}, has_access_key: {}, has_secret_key: {}", 
-            name, bucket, region, access_key.is_some(), secret_key.is_some());
+        info!("Added block storage source: {} at {:?}", name, mount_point);
         
-        let adapter = Arc::new(
-            S3StorageAdapter::new(
-                bucket.clone(),
-                region.clone(),
-                access_key.clone(),
-                secret_key.clone(),
-                endpoint.clone(),
-                name.clone(),
-            ).await
-            .map_err(|e| {
-                anyhow::anyhow!(
-                    "Failed to create S3 adapter for bucket '{}' in region '{}': {}. \
-                    Verify bucket name, region, and credentials are correct.",
-                    bucket, region, e
-                )
-            })?
-        );
+        Ok(source)
+    }
+    
+    /// Register the S3 bucket described by `config` (a `MountConfig::S3`),
+    /// connected the way `mount_source` would connect it but not remembered
+    /// for the next launch
+    pub async fn add_s3_source(&self, config: MountConfig) -> Result<StorageSource> {
+        let MountConfig::S3 { name, bucket, region, endpoint, .. } = &config else {
+            return Err(VfsError::InvalidInput(format!("{} is not an S3 bucket", config.name())).into());
+        };
+        info!("[add_s3_source] Creating S3 source - name: {}, bucket: {}, region: {:?}, endpoint: {:?}",
+            name, bucket, region, endpoint);
         
-        // Test connection to catch credential/permission issues early
-        info!("[add_s3_source] Testing S3 connection...");
-        match adapter.test_connection().await {
-            Ok(true) => {
-                info!("[add_s3_source] S3 connection test successful");
-            }
-            Ok(false) => {
-                warn!("[add_s3_source] S3 connection test returned false - credentials or permissions may be invalid");
-            }
-            Err(e) => {
-                warn!("[add_s3_source] S3 connection test failed: {} - continuing anyway", e);
-            }
-        }
+        let source = self.register_source(&config).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to add S3 bucket '{}': {:#}. \
+                Verify bucket name, region, and credentials are correct.",
+                bucket, e
+            )
+        })?;
         
-        let source = StorageSource {
-            id: uuid::Uuid::new_v4().to_string(),
+        info!("Added S3 storage source: {}", name);
+        
+        Ok(source)
+    }
+    
+    /// Register a Google Cloud Storage source, connected the way
+    /// `mount_source` would connect it but not remembered for the next launch
+    pub async fn add_gcs_source(
+        &self,
+        name: String,
+        bucket: String,
+        credentials_path: Option<String>,
+    ) -> Result<StorageSource> {
+        let config = MountConfig::Gcs {
             name: name.clone(),
-            source_type: StorageSourceType::S3,
-            status: ConnectionStatus::Connected,
-            mounted: true,
-            mount_point: None,
-            config: StorageConfig {
-                path_or_bucket: bucket,
-                region: Some(region),
-                endpoint,
-                access_key,
-                secret_key,
-            },
+            bucket: bucket.clone(),
+            credentials_path,
+            transfer_tuning: None,
+            read_only: false,
+            quota_bytes: None,
+            lifecycle_policy: None,
+            retry_policy: None,
         };
+        let source = self.register_source(&config).await.map_err(|e| {
+            anyhow::anyhow!("Failed to add GCS bucket '{}': {:#}", bucket, e)
+        })?;
         
-        // S3StorageAdapter implements IFileOperations, so we can use it for file operations
-        let file_ops: Arc<dyn IFileOperations> = adapter.clone();
-        
-        self.sources.write().insert(source.id.clone(), StorageSourceState {
-            source: source.clone(),
-            adapter,
-            file_ops: Some(file_ops),
-        });
-        
-        info!("Added S3 storage source: {}", name);
+        info!("Added GCS storage source: {}", name);
         
         Ok(source)
     }
     
-    /// List all registered storage sources
-    pub fn list_sources(&self) -> Vec<StorageSource> {
-        self.sources.read()
-            .values()
-            .map(|s| s.source.clone())
-            .collect()
+    /// Connect the source described by `config`, register it and remember it
+    /// for the next launch.
+    ///
+    /// Required fields are validated and the connection tested first, so a
+    /// bad bucket or missing mount point fails here rather than on first
+    /// listing. An inline secret is moved to the credential store and only a
+    /// reference to it is written to disk.
+    pub async fn mount_source(&self, config: MountConfig) -> Result<StorageSource> {
+        let source = self.register_source(&config).await?;
+        
+        let persisted = self.move_secret_to_store(&source.id, config);
+        if let Err(e) = self.sources_store.upsert(&source.id, &persisted).await {
+            warn!("Failed to remember source {}: {}", source.name, e);
+        }
+        info!("Mounted {:?} source: {}", source.source_type, source.name);
+        
+        Ok(source)
     }
     
-    /// Get a storage source by ID
-    pub fn get_source(&self, source_id: &str) -> Option<StorageSource> {
-        self.sources.read()
-            .get(source_id)
-            .map(|s| s.source.clone())
+    /// Connect the source described by `config` and register it for this
+    /// session
+    async fn register_source(&self, config: &MountConfig) -> Result<StorageSource> {
+        let state = connect_source(&self.resolve_secret(config.clone())?).await?;
+        let source = state.source.clone();
+        self.sources.write().insert(source.id.clone(), state);
+        Ok(source)
     }
     
-    /// List files in a storage source
-    pub async fn list_files(&self, source_id: &str, path: &Path) -> Result<Vec<VirtualFile>> {
-        // Clone the adapter Arc before releasing the lock to avoid holding it across await
-        let adapter = {
-            let sources = self.sources.read();
-            let state = sources.get(source_id)
-                .ok_or_else(|| anyhow::anyhow!("Storage source not found: {}", source_id))?;
-            state.adapter.clone()
+    /// Try `config` the way `mount_source` would - validate it, build the
+    /// adapter and test the connection - without registering or remembering
+    /// anything. Failures are reported in the result rather than as an error.
+    pub async fn test_mount_config(&self, config: MountConfig) -> ConnectionTestResult {
+        let started = std::time::Instant::now();
+        let connected = match self.resolve_secret(config) {
+            Ok(config) => connect_source(&config).await.map(|_| ()),
+            Err(e) => Err(e),
         };
+        let latency_ms = started.elapsed().as_millis() as u64;
         
-        let mut files = adapter.list_files(path).await?;
+        match connected {
+            Ok(()) => ConnectionTestResult { ok: true, latency_ms, error: None },
+            Err(e) => ConnectionTestResult { ok: false, latency_ms, error: Some(format!("{:#}", e)) },
+        }
+    }
+    
+    /// Mount each folder of an auto-mount list as a local source. Folders that
+    /// don't exist (or fail to mount) are skipped with a warning.
+    pub async fn mount_automounts(&self, entries: &[AutomountEntry]) -> Vec<StorageSource> {
+        let mut mounted = Vec::new();
         
-        // Update tier status for cached files
-        for file in &mut files {
-            if !file.is_directory {
-                let file_path = file.path.clone();
-                if self.cache.is_cached(&file_path).await {
-                    file.tier_status.current_tier = StorageTier::Hot;
-                    file.tier_status.is_cached = true;
-                    file.tier_status.can_warm = false;
-                }
+        for entry in entries {
+            if !entry.path.is_dir() {
+                warn!("Not auto-mounting {}: {:?} is not a folder", entry.name, entry.path);
+                continue;
+            }
+            match self.add_local_source(entry.name.clone(), entry.path.clone()).await {
+                Ok(source) => mounted.push(source),
+                Err(e) => warn!("Failed to mount {}: {}", entry.name, e),
             }
         }
         
-        Ok(files)
+        mounted
     }
     
-    /// Hydrate (warm) a file from cold storage to cache
-    pub async fn hydrate_file(&self, source_id: &str, path: &Path) -> Result<PathBuf> {
-        let start_time = std::time::Instant::now();
+    /// Re-mount the sources remembered from earlier sessions, under their old
+    /// ids. Sources that can't connect are logged and skipped but stay
+    /// remembered, so an offline NAS comes back once it is reachable.
+    pub async fn restore_sources(&self) -> Vec<StorageSource> {
+        let mut restored = Vec::new();
         
-        let (adapter, source_tier) = {
-            let sources = self.sources.read();
-            let state = sources.get(source_id)
-                .ok_or_else(|| anyhow::anyhow!("Storage source not found: {}", source_id))?;
+        for persisted in self.sources_store.list() {
+            if self.sources.read().contains_key(&persisted.id) {
+                continue;
+            }
             
-            // Get current tier based on storage category
-            let tier = match state.source.source_type.category() {
-                crate::vfs::domain::StorageCategory::Local => StorageTier::Hot,
-                crate::vfs::domain::StorageCategory::Block => StorageTier::Hot,
-                crate::vfs::domain::StorageCategory::Cloud => StorageTier::Cold,
-                crate::vfs::domain::StorageCategory::Network => StorageTier::Warm,
-                crate::vfs::domain::StorageCategory::Hybrid => StorageTier::Cold,
-                crate::vfs::domain::StorageCategory::Custom => StorageTier::Cold,
+            let connected = match self.resolve_secret(persisted.config.clone()) {
+                Ok(config) => connect_source(&config).await,
+                Err(e) => Err(e),
             };
-            
-            (state.adapter.clone(), tier)
-        };
-        
-        // Publish hydration started event
-        if let Some(event_bus) = &self.event_bus {
-            let file_size = adapter.file_size(path).await.unwrap_or(0);
-            event_bus.publish_hydration_started(FileHydrationStarted {
-                file_path: path.to_path_buf(),
-                source_tier,
-                file_size,
-                timestamp: SystemTime::now(),
-            }).await?;
+            match connected {
+                Ok(mut state) => {
+                    state.source.id = persisted.id.clone();
+                    restored.push(state.source.clone());
+                    self.sources.write().insert(persisted.id, state);
+                }
+                Err(e) => warn!("Failed to restore source {}: {:#}", persisted.config.name(), e),
+            }
         }
         
-        // Read file from source
-        let data = adapter.read_file(path).await?;
-        let bytes_transferred = data.len() as u64;
-        
-        // Cache the file
-        let entry = self.cache.cache_file(path, &data).await?;
-        
-        let duration_ms = start_time.elapsed().as_millis() as u64;
+        info!("Restored {} persisted source(s)", restored.len());
+        restored
+    }
+    
+    /// Test the connection of every remote source, rebuilding the adapter of
+    /// any that fail from its remembered config. Each source's status is set
+    /// to `Connected` or to the error that kept it offline.
+    pub async fn remount_all_remote(&self) -> Vec<SourceBatchResult> {
+        let remote: Vec<(StorageSource, Arc<dyn StorageAdapter>)> = self.sources.read()
+            .values()
+            .filter(|state| !matches!(state.source.source_type, StorageSourceType::Local | StorageSourceType::Block))
+            .map(|state| (state.source.clone(), state.adapter.clone()))
+            .collect();
+        
+        let mut results = Vec::new();
+        for (source, adapter) in remote {
+            let mut error = match adapter.test_connection().await {
+                Ok(true) => None,
+                Ok(false) => Some(format!("{} is not reachable", source.name)),
+                Err(e) => Some(format!("{:#}", e)),
+            };
+            
+            // A fresh adapter gets new sessions and credentials
+            let remembered = self.sources_store.list().into_iter().find(|s| s.id == source.id);
+            if let (Some(_), Some(persisted)) = (&error, remembered) {
+                let reconnected = match self.resolve_secret(persisted.config) {
+                    Ok(config) => connect_source(&config).await,
+                    Err(e) => Err(e),
+                };
+                match reconnected {
+                    Ok(mut state) => {
+                        info!("Reconnected source {}", source.name);
+                        state.source.id = source.id.clone();
+                        self.sources.write().insert(source.id.clone(), state);
+                        self.listings.invalidate_source(&source.id);
+                        error = None;
+                    }
+                    Err(e) => error = Some(format!("{:#}", e)),
+                }
+            }
+            
+            if let Some(state) = self.sources.write().get_mut(&source.id) {
+                state.source.status = match &error {
+                    None => ConnectionStatus::Connected,
+                    Some(e) => ConnectionStatus::Error(e.clone()),
+                };
+            }
+            if let Some(e) = &error {
+                warn!("Source {} is offline: {}", source.name, e);
+            }
+            
+            results.push(SourceBatchResult {
+                source_id: source.id,
+                name: source.name,
+                ok: error.is_none(),
+                error,
+            });
+        }
+        
+        results
+    }
+    
+    /// Fill in secrets the config only references
+    fn resolve_secret(&self, mut config: MountConfig) -> Result<MountConfig> {
+        if let Some(credential) = config.credential().cloned() {
+            if let Some(secret) = config.secret_mut().filter(|secret| secret.is_none()) {
+                *secret = Some(self.retrieve_credential(&credential)?);
+            }
+        }
+        if let Some((key @ None, Some(credential))) = config.sse_key_mut() {
+            *key = Some(self.retrieve_credential(credential)?);
+        }
+        Ok(config)
+    }
+    
+    fn retrieve_credential(&self, credential: &CredentialRef) -> Result<String> {
+        self.credentials.retrieve(&credential.service, &credential.account)?
+            .ok_or_else(|| VfsError::NotFound(format!(
+                "No saved credential {}/{}", credential.service, credential.account
+            )).into())
+    }
+    
+    /// Save inline secrets (the secret key or password, and an SSE-C key) to
+    /// the credential store under the source id and reference them instead. If
+    /// the store refuses, the secret is dropped and the source will need it
+    /// again next launch.
+    fn move_secret_to_store(&self, source_id: &str, mut config: MountConfig) -> MountConfig {
+        let name = config.name().to_string();
+        
+        if config.credential().is_none() {
+            if let Some(secret) = config.secret_mut().and_then(|secret| secret.take()) {
+                let stored = self.store_owned_secret(source_id, &secret, &name);
+                if let Some(credential) = config.credential_mut() {
+                    *credential = stored;
+                }
+            }
+        }
+        if let Some((key, credential @ None)) = config.sse_key_mut() {
+            if let Some(secret) = key.take() {
+                *credential = self.store_owned_secret(&sse_key_account(source_id), &secret, &name);
+            }
+        }
+        config
+    }
+    
+    /// Save a secret the app keeps on a source's behalf
+    fn store_owned_secret(&self, account: &str, secret: &str, name: &str) -> Option<CredentialRef> {
+        match self.credentials.store(DEFAULT_CREDENTIAL_SERVICE, account, secret) {
+            Ok(()) => Some(CredentialRef {
+                service: DEFAULT_CREDENTIAL_SERVICE.to_string(),
+                account: account.to_string(),
+            }),
+            Err(e) => {
+                warn!("Secret for {} not saved; it will be asked for again: {:#}", name, e);
+                None
+            }
+        }
+    }
+    
+    /// Save a secret so mount configs can reference it
+    pub fn store_credential(&self, service: &str, account: &str, secret: &str) -> Result<CredentialRef> {
+        if service.trim().is_empty() || account.trim().is_empty() {
+            return Err(VfsError::InvalidInput("Credential service and account are required".to_string()).into());
+        }
+        self.credentials.store(service, account, secret)?;
+        Ok(CredentialRef { service: service.to_string(), account: account.to_string() })
+    }
+    
+    /// Delete a saved secret; returns whether there was one
+    pub fn delete_credential(&self, service: &str, account: &str) -> Result<bool> {
+        self.credentials.delete(service, account)
+    }
+    
+    /// Stop re-mounting a source at startup; it stays mounted for this session.
+    /// Returns whether the source was remembered.
+    pub async fn forget_source(&self, source_id: &str) -> Result<bool> {
+        // Secrets saved on the source's behalf go with it
+        let remembered = self.sources_store.list().into_iter().find(|s| s.id == source_id);
+        if let Some(remembered) = remembered {
+            let owned = [
+                (remembered.config.credential(), source_id.to_string()),
+                (remembered.config.sse_credential(), sse_key_account(source_id)),
+            ];
+            for (credential, account) in owned {
+                let Some(credential) = credential.filter(|c| c.service == DEFAULT_CREDENTIAL_SERVICE && c.account == account) else {
+                    continue;
+                };
+                if let Err(e) = self.credentials.delete(&credential.service, &credential.account) {
+                    warn!("Failed to delete saved secret for {}: {:#}", source_id, e);
+                }
+            }
+        }
+        
+        self.sources_store.remove(source_id).await
+    }
+    
+    /// Change the name a source is shown under. Only the registry and the
+    /// remembered config change; the storage itself is untouched.
+    pub async fn rename_source(&self, source_id: &str, new_name: &str) -> Result<StorageSource> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(VfsError::InvalidInput("Source name cannot be empty".to_string()).into());
+        }
+        
+        let source = {
+            let mut sources = self.sources.write();
+            if sources.values().any(|s| s.source.id != source_id && s.source.name.eq_ignore_ascii_case(new_name)) {
+                return Err(VfsError::AlreadyExists(format!("A source named {} already exists", new_name)).into());
+            }
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            info!("Renaming source {} to {}", state.source.name, new_name);
+            state.source.name = new_name.to_string();
+            state.source.clone()
+        };
+        
+        if let Err(e) = self.sources_store.rename(source_id, new_name).await {
+            warn!("Failed to remember new name of source {}: {}", new_name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// List all registered storage sources
+    pub fn list_sources(&self) -> Vec<StorageSource> {
+        self.sources.read()
+            .values()
+            .map(|s| s.source.clone())
+            .collect()
+    }
+    
+    /// Get a storage source by ID
+    pub fn get_source(&self, source_id: &str) -> Option<StorageSource> {
+        self.sources.read()
+            .get(source_id)
+            .map(|s| s.source.clone())
+    }
+    
+    /// Check a source is reachable and report its connection counters
+    pub async fn source_health(&self, source_id: &str) -> Result<SourceHealth> {
+        let adapter = {
+            let sources = self.sources.read();
+            sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?
+                .adapter.clone()
+        };
+        
+        let connected = adapter.test_connection().await.unwrap_or(false);
+        Ok(SourceHealth {
+            source_id: source_id.to_string(),
+            connected,
+            failure_count: adapter.connection_failures(),
+        })
+    }
+    
+    /// List files in a storage source, filtered and sorted by `options`
+    pub async fn list_files(
+        &self,
+        source_id: &str,
+        path: &Path,
+        options: &ListOptions,
+    ) -> Result<Vec<VirtualFile>> {
+        // Clone the adapter Arc before releasing the lock to avoid holding it across await
+        let (adapter, cached) = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            (state.adapter.clone(), caches_listings(&state.source))
+        };
+        
+        let hit = if cached { self.listings.get(source_id, path, options) } else { None };
+        let mut files = match hit {
+            Some(files) => {
+                debug!("Listing cache hit: {}:{:?}", source_id, path);
+                files
+            }
+            None => {
+                let mut files = adapter.list_files_with(path, options).await?;
+                sniff_content_types(adapter.as_ref(), &mut files, LISTING_SNIFF_LIMIT).await;
+                if cached {
+                    self.listings.insert(source_id, path, options, files.clone());
+                }
+                files
+            }
+        };
+        // Hydration state is local, so refresh it even for cached listings
+        self.apply_effective_tiers(source_id, &mut files).await;
+        self.hide_ignored(&mut files, options);
+        
+        Ok(files)
+    }
+    
+    /// File name patterns hidden from listings and skipped by recursive copy,
+    /// sync and archive
+    pub fn ignore_patterns(&self) -> IgnorePatterns {
+        self.ignore.read().clone()
+    }
+    
+    /// Replace the ignore list and remember it for the next session
+    pub async fn set_ignore_patterns(&self, patterns: IgnorePatterns) -> Result<()> {
+        *self.ignore.write() = patterns.clone();
+        self.settings.update(|settings| settings.ignore_patterns = Some(patterns)).await
+    }
+    
+    /// How long clipboard exports and thumbnail leftovers are kept, if the
+    /// user changed it in an earlier session
+    pub fn saved_temp_ttl(&self) -> Option<std::time::Duration> {
+        self.settings.get().temp_ttl_secs.map(std::time::Duration::from_secs)
+    }
+    
+    /// Keep clipboard exports and thumbnail leftovers for `ttl` and remember
+    /// it for the next session
+    pub async fn set_temp_ttl(&self, ttl: std::time::Duration) -> Result<()> {
+        temp_gc::set_temp_ttl(ttl);
+        self.settings.update(|settings| settings.temp_ttl_secs = Some(ttl.as_secs())).await
+    }
+    
+    fn hide_ignored(&self, files: &mut Vec<VirtualFile>, options: &ListOptions) {
+        if options.show_ignored {
+            return;
+        }
+        let ignore = self.ignore.read();
+        files.retain(|file| !ignore.matches(&file.path));
+    }
+    
+    /// Forget cached listings affected by a change at `path` (e.g. from a file
+    /// watcher, or a manual refresh)
+    pub fn invalidate_listing(&self, source_id: &str, path: &Path) {
+        self.listings.invalidate(source_id, path);
+        self.dir_sizes.invalidate(source_id, path);
+        self.tree_hashes.invalidate(source_id, path);
+        self.refresh_catalog(source_id, path);
+    }
+    
+    // =========================================================================
+    // Catalog
+    // =========================================================================
+    
+    fn adapter(&self, source_id: &str) -> Result<Arc<dyn StorageAdapter>> {
+        self.sources.read()
+            .get(source_id)
+            .map(|state| state.adapter.clone())
+            .ok_or_else(|| source_not_found(source_id))
+    }
+    
+    /// Re-list a changed path in the source's index, in the background. Changes
+    /// arriving within `REFRESH_DEBOUNCE` share one refresh.
+    fn refresh_catalog(&self, source_id: &str, path: &Path) {
+        let Ok(adapter) = self.adapter(source_id) else {
+            return;
+        };
+        // Without a runtime (e.g. during shutdown) the next re-index catches up
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if !self.catalog.mark_dirty(source_id, path) {
+            return;
+        }
+        
+        let catalog = self.catalog.clone();
+        let source_id = source_id.to_string();
+        runtime.spawn(async move {
+            tokio::time::sleep(catalog::REFRESH_DEBOUNCE).await;
+            catalog.flush(&source_id, adapter.as_ref()).await;
+        });
+    }
+    
+    pub fn index_status(&self, source_id: &str) -> Result<IndexStatus> {
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
+        }
+        Ok(self.catalog.status(source_id))
+    }
+    
+    /// Crawl a source and rebuild its index; returns the number of entries,
+    /// or zero if a crawl was already running
+    pub async fn index_source(&self, source_id: &str) -> Result<usize> {
+        let adapter = self.adapter(source_id)?;
+        if !self.catalog.begin(source_id) {
+            return Ok(0);
+        }
+        
+        let started = std::time::Instant::now();
+        let count = self.catalog.index(source_id, adapter.as_ref()).await?;
+        info!("Indexed {} entries on {} in {:?}", count, source_id, started.elapsed());
+        Ok(count)
+    }
+    
+    /// Start re-indexing a source in the background; follow it with `index_status`
+    pub fn reindex_source(self: &Arc<Self>, source_id: &str) -> Result<()> {
+        self.adapter(source_id)?;
+        
+        let service = self.clone();
+        let source_id = source_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = service.index_source(&source_id).await {
+                warn!("Failed to index {}: {}", source_id, e);
+            }
+        });
+        Ok(())
+    }
+    
+    /// Files and folders under `root` whose name contains `query`. Served from
+    /// the index when the source has one; otherwise `root` is crawled and an
+    /// index is started for next time.
+    pub async fn search(self: &Arc<Self>, source_id: &str, root: &Path, query: &str, limit: usize) -> Result<Vec<CatalogEntry>> {
+        let mut results = match self.catalog.search(source_id, root, query, limit) {
+            Some(results) => results,
+            None => {
+                let adapter = self.adapter(source_id)?;
+                if self.catalog.status(source_id).state == catalog::IndexState::NotIndexed {
+                    self.reindex_source(source_id)?;
+                }
+                catalog::search_live(adapter.as_ref(), root, query, limit).await?
+            }
+        };
+        
+        {
+            let ignore = self.ignore.read();
+            results.retain(|entry| !ignore.matches(&entry.path));
+        }
+        
+        if let Err(e) = self.search_history.record(source_id, root, query, limit).await {
+            warn!("Failed to record search {:?}: {}", query, e);
+        }
+        Ok(results)
+    }
+    
+    /// Searches recently run, newest first
+    pub fn recent_searches(&self) -> Vec<RecentSearch> {
+        self.search_history.list()
+    }
+    
+    /// Forget every recorded search
+    pub async fn clear_search_history(&self) -> Result<()> {
+        self.search_history.clear().await
+    }
+    
+    /// Use `metadata` for tier history from now on
+    pub fn set_metadata_store(&self, metadata: Arc<dyn IMetadataStore>) {
+        *self.metadata.write() = Some(metadata);
+    }
+    
+    /// Note that a file moved to `tier`; failures only cost the history entry
+    async fn record_tier(&self, source_id: &str, path: &Path, tier: StorageTier) {
+        let Some(metadata) = self.metadata.read().clone() else {
+            return;
+        };
+        if let Err(e) = metadata.record_tier_transition(source_id, path, tier).await {
+            warn!("Failed to record tier history for {:?}: {}", path, e);
+        }
+    }
+    
+    /// Tiers a file has moved through, oldest first
+    pub async fn tier_history(&self, source_id: &str, path: &Path) -> Result<Vec<TierTransition>> {
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
+        }
+        
+        let Some(metadata) = self.metadata.read().clone() else {
+            return Ok(Vec::new());
+        };
+        Ok(metadata.get(source_id, path).await?
+            .map(|m| m.tier_history)
+            .unwrap_or_default())
+    }
+    
+    /// How long listings of remote sources are reused; zero turns the cache off
+    pub fn set_listing_ttl(&self, ttl: std::time::Duration) {
+        self.listings.set_ttl(ttl);
+    }
+    
+    /// List one page of a directory.
+    ///
+    /// Pass the returned cursor back to fetch the next page; `None` means the
+    /// listing is complete. For S3 the cursor is the last key seen, for local
+    /// sources it is an offset into the sorted directory. Keep `options` the same
+    /// for every page of one listing.
+    pub async fn list_files_paged(
+        &self,
+        source_id: &str,
+        path: &Path,
+        options: &ListOptions,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<VirtualFile>, Option<String>)> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        let (mut files, next_cursor) = adapter.list_files_paged(path, options, cursor, limit).await?;
+        sniff_content_types(adapter.as_ref(), &mut files, LISTING_SNIFF_LIMIT).await;
+        self.apply_effective_tiers(source_id, &mut files).await;
+        self.hide_ignored(&mut files, options);
+        
+        Ok((files, next_cursor))
+    }
+    
+    /// List a directory in unsorted batches of up to `batch_size` entries as
+    /// the source reads them. Batches left empty by the ignore patterns are
+    /// skipped.
+    pub async fn list_files_batched(
+        &self,
+        source_id: &str,
+        path: &Path,
+        options: &ListOptions,
+        batch_size: usize,
+    ) -> Result<futures::stream::BoxStream<'_, Result<Vec<VirtualFile>>>> {
+        use futures::TryStreamExt;
+        
+        let adapter = self.adapter(source_id)?;
+        let batches = adapter.list_files_batched(path, options, batch_size).await?;
+        let options = options.clone();
+        
+        Ok(batches
+            .and_then(move |mut files| {
+                let (adapter, options) = (adapter.clone(), options.clone());
+                async move {
+                    sniff_content_types(adapter.as_ref(), &mut files, LISTING_SNIFF_LIMIT).await;
+                    self.apply_effective_tiers(source_id, &mut files).await;
+                    self.hide_ignored(&mut files, &options);
+                    Ok(files)
+                }
+            })
+            .try_filter(|files| futures::future::ready(!files.is_empty()))
+            .boxed())
+    }
+    
+    /// Replace the tiers the source reported with the effective ones
+    async fn apply_effective_tiers(&self, source_id: &str, files: &mut [VirtualFile]) {
+        for file in files.iter_mut() {
+            if file.is_directory {
+                continue;
+            }
+            let cached = self.cache.is_cached(&cache_key(source_id, &file.path)).await;
+            let moving_to = self.tier_change_target(source_id, &file.path);
+            file.tier_status.current_tier = reconcile_tier(file.tier_status.current_tier, moving_to, cached);
+            if cached {
+                file.tier_status.is_cached = true;
+                file.tier_status.can_warm = false;
+            }
+        }
+    }
+    
+    /// The tier a file is effectively in, reconciling the tier the source
+    /// reports (an S3 storage class), a tier change under way and the cache
+    pub async fn effective_tier(&self, source_id: &str, path: &Path) -> Result<StorageTier> {
+        let adapter = self.adapter(source_id)?;
+        // Reads are served locally, so there's no need to ask the source
+        if self.cache.is_cached(&cache_key(source_id, path)).await {
+            return Ok(StorageTier::Hot);
+        }
+        
+        let reported = adapter.get_metadata(path).await?.tier_status.current_tier;
+        Ok(reconcile_tier(reported, self.tier_change_target(source_id, path), false))
+    }
+    
+    /// Tier `path` is being moved to, while `change_tier` is moving it or
+    /// its restore is under way
+    fn tier_change_target(&self, source_id: &str, path: &Path) -> Option<StorageTier> {
+        let key = (source_id.to_string(), path.to_path_buf());
+        let moving = self.tier_changes.read().get(&key).copied();
+        moving.or_else(|| self.pending_restores.read().get(&key).copied())
+    }
+    
+    /// Hydrate (warm) a file from cold storage to cache
+    pub async fn hydrate_file(&self, source_id: &str, path: &Path) -> Result<PathBuf> {
+        let key = (source_id.to_string(), path.to_path_buf());
+        self.warming.write().insert(key.clone());
+        let result = self.hydrate_into_cache(source_id, path).await;
+        self.warming.write().remove(&key);
+        result
+    }
+    
+    async fn hydrate_into_cache(&self, source_id: &str, path: &Path) -> Result<PathBuf> {
+        let start_time = std::time::Instant::now();
+        
+        let (adapter, source_tier) = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            // Get current tier based on storage category
+            let tier = match state.source.source_type.category() {
+                crate::vfs::domain::StorageCategory::Local => StorageTier::Hot,
+                crate::vfs::domain::StorageCategory::Block => StorageTier::Hot,
+                crate::vfs::domain::StorageCategory::Cloud => StorageTier::Cold,
+                crate::vfs::domain::StorageCategory::Network => StorageTier::Warm,
+                crate::vfs::domain::StorageCategory::Hybrid => StorageTier::Cold,
+                crate::vfs::domain::StorageCategory::Custom => StorageTier::Cold,
+            };
+            
+            (state.adapter.clone(), tier)
+        };
+        
+        self.ensure_retrieved(source_id, path).await?;
+        let file_size = adapter.file_size(path).await.unwrap_or(0);
+        
+        // Only the part not already downloaded by a previous attempt needs room
+        let key = cache_key(source_id, path);
+        let part_path = self.cache.partial_path(&key);
+        let already = tokio::fs::metadata(&part_path).await.map(|m| m.len()).unwrap_or(0);
+        self.ensure_cache_space(file_size.saturating_sub(already)).await?;
+        
+        // Publish hydration started event
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.publish_hydration_started(FileHydrationStarted {
+                file_path: path.to_path_buf(),
+                source_tier,
+                file_size,
+                timestamp: SystemTime::now(),
+            }).await?;
+        }
+        
+        // Download into a .part file (resuming a previous attempt if one exists),
+        // then move it into the cache once complete and verified
+        let bytes_transferred = self.transfers.run(
+            &[source_id],
+            format!("Hydrate {:?}", path),
+            download_to_part(adapter.as_ref(), path, &part_path),
+        ).await?;
+        let entry = self.cache.commit_partial(&key).await?;
+        
+        let duration_ms = start_time.elapsed().as_millis() as u64;
         
         // Publish hydration completed event
         if let Some(event_bus) = &self.event_bus {
@@ -272,78 +1457,1389 @@ impl VfsService {
         }
         
         info!("Hydrated file: {:?} ({} bytes in {}ms)", path, bytes_transferred, duration_ms);
+        self.record_tier(source_id, path, StorageTier::Hot).await;
         
         Ok(entry.cache_path)
     }
     
-    /// Read a file (from cache if available, otherwise from source)
-    pub async fn read_file(&self, source_id: &str, path: &Path) -> Result<Vec<u8>> {
-        // Check cache first
-        if self.cache.is_cached(path).await {
-            debug!("Cache hit: {:?}", path);
-            return self.cache.read_from_cache(path).await;
+    /// Whether a file is cached, being hydrated (with progress), or neither
+    pub async fn warm_status(&self, source_id: &str, path: &Path) -> Result<WarmStatus> {
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
         }
         
-        debug!("Cache miss: {:?}", path);
+        if self.warming.read().contains(&(source_id.to_string(), path.to_path_buf())) {
+            // Resumable downloads record committed bytes after every chunk
+            let progress = match PartialDownloadState::load(&self.cache.partial_path(&cache_key(source_id, path))).await {
+                Some(state) if state.total_size > 0 => {
+                    (state.committed as f64 / state.total_size as f64).min(1.0)
+                }
+                _ => 0.0,
+            };
+            return Ok(WarmStatus::Warming { progress });
+        }
         
-        // Read from source
-        let sources = self.sources.read();
-        let state = sources.get(source_id)
-            .ok_or_else(|| anyhow::anyhow!("Storage source not found: {}", source_id))?;
+        if self.cache.is_cached(&cache_key(source_id, path)).await {
+            Ok(WarmStatus::Warmed)
+        } else {
+            Ok(WarmStatus::NotWarmed)
+        }
+    }
+    
+    /// Queue files for hydration, at most `concurrency` at a time. Files
+    /// already cached or warming are skipped. Returns the number queued and a
+    /// handle that resolves to the number warmed; follow progress with
+    /// `warm_status`.
+    pub async fn warm_batch(
+        self: &Arc<Self>,
+        source_id: &str,
+        paths: Vec<PathBuf>,
+        concurrency: usize,
+    ) -> Result<(usize, tokio::task::JoinHandle<usize>)> {
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
+        }
         
-        let data = state.adapter.read_file(path).await?;
+        let mut queued = Vec::new();
+        for path in paths {
+            if self.cache.is_cached(&cache_key(source_id, &path)).await {
+                continue;
+            }
+            // Marked now so the whole batch reports as warming straight away
+            if self.warming.write().insert((source_id.to_string(), path.clone())) {
+                queued.push(path);
+            }
+        }
         
-        // Cache the file for future reads
-        self.cache.cache_file(path, &data).await?;
+        let count = queued.len();
+        let service = self.clone();
+        let source_id = source_id.to_string();
+        let handle = tokio::spawn(async move {
+            let results: Vec<bool> = futures::stream::iter(queued)
+                .map(|path| {
+                    let service = service.clone();
+                    let source_id = source_id.clone();
+                    async move {
+                        let result = service.hydrate_into_cache(&source_id, &path).await;
+                        service.warming.write().remove(&(source_id, path.clone()));
+                        if let Err(e) = &result {
+                            warn!("Failed to warm {:?}: {}", path, e);
+                        }
+                        result.is_ok()
+                    }
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+            
+            let warmed = results.into_iter().filter(|ok| *ok).count();
+            info!("Batch warm finished: {} of {} files", warmed, count);
+            warmed
+        });
         
-        Ok(data)
-    }
-    
-    /// Get cache statistics
-    pub async fn cache_stats(&self) -> CacheStats {
-        self.cache.stats().await
-    }
-    
-    /// Clear the cache
-    pub async fn clear_cache(&self) -> Result<()> {
-        self.cache.clear().await
+        Ok((count, handle))
     }
     
-    /// Remove a storage source
-    pub fn remove_source(&self, source_id: &str) -> Option<StorageSource> {
-        self.sources.write()
+    /// Fetch and cache only bytes `offset..offset + len` of a file, rounded out
+    /// to whole cache blocks, e.g. to scrub a long clip without warming all of
+    /// it. Blocks already cached are not fetched again. `len` is cut to the
+    /// end of the file and to `MAX_HYDRATE_RANGE`.
+    pub async fn hydrate_range(&self, source_id: &str, path: &Path, offset: u64, len: u64) -> Result<()> {
+        let len = len.min(MAX_HYDRATE_RANGE);
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        let key = cache_key(source_id, path);
+        if self.cache.is_range_cached(&key, offset, len).await {
+            return Ok(());
+        }
+        
+        self.ensure_retrieved(source_id, path).await?;
+        let file_size = adapter.file_size(path).await?;
+        let end = offset.saturating_add(len).min(file_size);
+        if end <= offset {
+            return Ok(());
+        }
+        
+        // Group the missing blocks into runs so each run is one ranged read,
+        // no bigger than `MAX_HYDRATE_RUN`
+        let mut runs: Vec<(u64, u64)> = Vec::new();
+        for block in offset / CACHE_BLOCK_SIZE..(end + CACHE_BLOCK_SIZE - 1) / CACHE_BLOCK_SIZE {
+            let start = block * CACHE_BLOCK_SIZE;
+            let block_end = (start + CACHE_BLOCK_SIZE).min(file_size);
+            if self.cache.is_range_cached(&key, start, block_end - start).await {
+                continue;
+            }
+            match runs.last_mut() {
+                Some((run_start, run_end)) if *run_end == start && *run_end - *run_start < MAX_HYDRATE_RUN => {
+                    *run_end = block_end;
+                }
+                _ => runs.push((start, block_end)),
+            }
+        }
+        
+        let fetch = async {
+            for (start, run_end) in runs {
+                self.ensure_cache_space(run_end - start).await?;
+                
+                let mut data = Vec::with_capacity((run_end - start) as usize);
+                while (data.len() as u64) < run_end - start {
+                    let pos = start + data.len() as u64;
+                    let chunk = adapter.read_file_range(path, pos, run_end - pos).await?;
+                    if chunk.is_empty() {
+                        anyhow::bail!("Unexpected end of {:?} at byte {}", path, pos);
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                
+                self.cache.cache_range(&key, file_size, start, &data).await?;
+            }
+            Ok(())
+        };
+        self.transfers.run(&[source_id], format!("Hydrate range of {:?}", path), fetch).await?;
+        
+        debug!("Hydrated {}..{} of {:?}", offset, end, path);
+        Ok(())
+    }
+    
+    /// Read bytes `offset..offset + len` of a file through the cache, fetching
+    /// only the blocks that aren't cached yet. At most `MAX_HYDRATE_RANGE`
+    /// bytes are returned.
+    pub async fn read_range(&self, source_id: &str, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+        let len = len.min(MAX_HYDRATE_RANGE);
+        self.hydrate_range(source_id, path, offset, len).await?;
+        self.cache.read_range_from_cache(&cache_key(source_id, path), offset, len).await
+    }
+    
+    /// Read a file (from cache if available, otherwise from source)
+    pub async fn read_file(&self, source_id: &str, path: &Path) -> Result<Vec<u8>> {
+        // Check cache first
+        let key = cache_key(source_id, path);
+        if self.cache.is_cached(&key).await {
+            debug!("Cache hit: {:?}", path);
+            return self.cache.read_from_cache(&key).await;
+        }
+        
+        debug!("Cache miss: {:?}", path);
+        self.ensure_retrieved(source_id, path).await?;
+        
+        // Read from source
+        let sources = self.sources.read();
+        let state = sources.get(source_id)
+            .ok_or_else(|| source_not_found(source_id))?;
+        
+        let data = state.adapter.read_file(path).await?;
+        
+        // Cache the file for future reads
+        self.cache.cache_file(&key, &data).await?;
+        
+        Ok(data)
+    }
+    
+    /// Get cache statistics
+    pub async fn cache_stats(&self) -> CacheStats {
+        self.cache.stats().await
+    }
+    
+    /// Folder the file cache is kept in
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache.config().path
+    }
+    
+    /// Clear the cache
+    pub async fn clear_cache(&self) -> Result<()> {
+        self.cache.clear().await
+    }
+    
+    /// Keep the cached copy of a deleted path around for the delete grace window
+    async fn soft_delete_cached(&self, source_id: &str, path: &Path) {
+        if let Err(e) = self.cache.soft_delete(&cache_key(source_id, path)).await {
+            warn!("Failed to release cache entries for deleted {:?}: {}", path, e);
+        }
+    }
+    
+    /// Put back cache entries for a path deleted within the grace window;
+    /// returns how many were restored
+    pub async fn cache_undo_delete(&self, source_id: &str, path: &Path) -> Result<usize> {
+        self.cache.undo_delete(&cache_key(source_id, path)).await
+    }
+    
+    // =========================================================================
+    // Text Diff
+    // =========================================================================
+    
+    /// Line diff of two text files, which may live on different sources
+    pub async fn diff_text(
+        &self,
+        source_id_a: &str,
+        path_a: &Path,
+        source_id_b: &str,
+        path_b: &Path,
+    ) -> Result<Vec<DiffHunk>> {
+        let old = self.read_text_for_diff(source_id_a, path_a).await?;
+        let new = self.read_text_for_diff(source_id_b, path_b).await?;
+        
+        Ok(text_diff::diff_lines(&old, &new))
+    }
+    
+    /// Read one side of a diff, enforcing the size cap and rejecting binary files
+    async fn read_text_for_diff(&self, source_id: &str, path: &Path) -> Result<String> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        let size = adapter.file_size(path).await?;
+        if size > MAX_DIFF_FILE_SIZE {
+            return Err(anyhow::anyhow!(
+                "File too large to diff: {:?} ({} bytes, limit {} bytes)",
+                path, size, MAX_DIFF_FILE_SIZE
+            ));
+        }
+        
+        let data = adapter.read_file(path).await?;
+        text_diff::decode_text(&data, &path.to_string_lossy())
+    }
+    
+    // =========================================================================
+    // Archives
+    // =========================================================================
+    
+    /// Compress files/directories into a zip or tar.gz archive at `dest_path`.
+    /// Returns the number of uncompressed bytes archived.
+    pub async fn create_archive(
+        &self,
+        source_id: &str,
+        paths: &[PathBuf],
+        dest_path: &Path,
+        format: ArchiveFormat,
+        progress: Arc<ArchiveProgress>,
+    ) -> Result<u64> {
+        self.ensure_writable(source_id)?;
+        
+        let mut inputs = Vec::with_capacity(paths.len());
+        for path in paths {
+            inputs.push(self.get_real_path(source_id, path).await?);
+        }
+        let dest = self.get_real_path(source_id, dest_path).await?;
+        let ignore = self.ignore_patterns();
+        
+        let result = tokio::task::spawn_blocking(move || {
+            archive::create_archive(&inputs, &dest, format, &ignore, progress.as_ref())
+        })
+        .await?;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, dest_path);
+        result
+    }
+    
+    /// Extract a zip or tar.gz archive into `dest_dir`, rejecting entries that
+    /// would escape it. Returns the number of files extracted.
+    pub async fn extract_archive(
+        &self,
+        source_id: &str,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress: Arc<ArchiveProgress>,
+    ) -> Result<usize> {
+        self.ensure_writable(source_id)?;
+        
+        let archive_file = self.get_real_path(source_id, archive_path).await?;
+        let dest = self.get_real_path(source_id, dest_dir).await?;
+        
+        let extracted = tokio::task::spawn_blocking(move || {
+            archive::extract_archive(&archive_file, &dest, progress.as_ref())
+        })
+        .await?;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, dest_dir);
+        
+        Ok(extracted?.len())
+    }
+    
+    /// Recursively hash every file under `root` (streamed with ranged reads).
+    /// Paths in the manifest are relative to `root`.
+    pub async fn generate_manifest(
+        &self,
+        source_id: &str,
+        root: &Path,
+        algo: HashAlgorithm,
+        progress: Arc<ManifestProgress>,
+    ) -> Result<Manifest> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        manifest::generate_manifest(adapter.as_ref(), root, algo, progress.as_ref()).await
+    }
+    
+    /// Check the files under `root` against a manifest, reporting mismatched,
+    /// missing and extra files
+    pub async fn verify_manifest(
+        &self,
+        source_id: &str,
+        root: &Path,
+        manifest: &[(PathBuf, String)],
+        algo: HashAlgorithm,
+        progress: Arc<ManifestProgress>,
+    ) -> Result<ManifestVerification> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        manifest::verify_manifest(adapter.as_ref(), root, manifest, algo, progress.as_ref()).await
+    }
+    
+    /// Find groups of identical files under `root`, largest waste first.
+    /// Only files sharing a size are read, and reading stops at the first
+    /// block that tells them apart.
+    pub async fn find_duplicates(
+        &self,
+        source_id: &str,
+        root: &Path,
+        progress: Arc<DuplicateProgress>,
+    ) -> Result<Vec<DuplicateGroup>> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        duplicates::find_duplicates(adapter.as_ref(), root, progress.as_ref()).await
+    }
+    
+    /// Find groups of near-duplicate stills under `root` whose perceptual
+    /// hashes differ in at most `threshold` bits
+    pub async fn find_similar_images(
+        &self,
+        source_id: &str,
+        root: &Path,
+        threshold: u32,
+        progress: Arc<SimilarImageProgress>,
+    ) -> Result<Vec<SimilarImageGroup>> {
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        similar_images::find_similar_images(adapter.as_ref(), root, threshold, progress.as_ref()).await
+    }
+    
+    /// Compare two folders, possibly on different sources: files only on one
+    /// side, and files on both whose size (or, with `deep`, checksum) differs
+    pub async fn compare_dirs(
+        &self,
+        source_a: &str,
+        path_a: &Path,
+        source_b: &str,
+        path_b: &Path,
+        deep: bool,
+        progress: Arc<DirDiffProgress>,
+    ) -> Result<DirDiff> {
+        let (adapter_a, adapter_b) = {
+            let sources = self.sources.read();
+            let adapter = |source_id: &str| {
+                sources.get(source_id)
+                    .map(|state| state.adapter.clone())
+                    .ok_or_else(|| source_not_found(source_id))
+            };
+            (adapter(source_a)?, adapter(source_b)?)
+        };
+        
+        folder_diff::compare_dirs(adapter_a.as_ref(), path_a, adapter_b.as_ref(), path_b, deep, progress.as_ref()).await
+    }
+    
+    // =========================================================================
+    // Audit Log
+    // =========================================================================
+    
+    /// Record a finished mutating operation (failures are logged, not returned)
+    async fn audit(&self, entry: AuditEntry) {
+        if let Err(e) = self.audit_log.append(&entry).await {
+            warn!("Failed to write audit entry for {:?} {:?}: {}", entry.operation, entry.paths, e);
+        }
+    }
+    
+    /// Audit entries between `from` and `to` (inclusive, either end open), oldest first
+    pub async fn export_audit_log(
+        &self,
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<AuditEntry>> {
+        self.audit_log.export(from, to).await
+    }
+    
+    // =========================================================================
+    // Recents
+    // =========================================================================
+    
+    /// Record that the user opened a file (failures are logged, not returned).
+    ///
+    /// Only user-initiated opens belong here: reads done on the way to a
+    /// copy, a thumbnail or a transcode would bury what the user opened.
+    pub async fn record_recent(&self, source_id: &str, path: &Path) {
+        if let Err(e) = self.recents.record(source_id, path).await {
+            warn!("Failed to record recent file {:?}: {}", path, e);
+        }
+    }
+    
+    /// Recently accessed files, newest first.
+    ///
+    /// Entries whose file no longer exists are dropped here rather than on delete.
+    /// Entries for sources that aren't mounted are skipped but kept.
+    pub async fn list_recents(&self) -> Result<Vec<RecentEntry>> {
+        self.existing_entries(&self.recents).await
+    }
+    
+    /// Clear the Recents list
+    pub async fn clear_recents(&self) -> Result<()> {
+        self.recents.clear().await
+    }
+    
+    /// Change how many Recents entries are kept
+    pub async fn set_recents_capacity(&self, capacity: usize) -> Result<()> {
+        self.recents.set_capacity(capacity).await
+    }
+    
+    /// Folders recently copied or moved into across sources, newest first.
+    ///
+    /// Like Recents, folders that no longer exist are dropped and those on
+    /// sources that aren't mounted are skipped.
+    pub async fn recent_destinations(&self) -> Result<Vec<RecentEntry>> {
+        self.existing_entries(&self.recent_destinations).await
+    }
+    
+    /// Entries of `store` on mounted sources, dropping those whose path is gone
+    async fn existing_entries(&self, store: &RecentsStore) -> Result<Vec<RecentEntry>> {
+        let mut recents = Vec::new();
+        let mut stale = Vec::new();
+        
+        for entry in store.list() {
+            let adapter = {
+                let sources = self.sources.read();
+                sources.get(&entry.source_id).map(|state| state.adapter.clone())
+            };
+            
+            match adapter {
+                Some(adapter) => match adapter.exists(&entry.path).await {
+                    Ok(false) => stale.push(entry),
+                    _ => recents.push(entry),
+                },
+                None => debug!("Skipping recent from unmounted source: {}", entry.source_id),
+            }
+        }
+        
+        store.remove(&stale).await?;
+        
+        Ok(recents)
+    }
+    
+    // =========================================================================
+    // Bookmarks
+    // =========================================================================
+    
+    /// Pin a folder to the sidebar
+    pub async fn add_bookmark(&self, source_id: &str, path: &Path, name: &str) -> Result<Bookmark> {
+        if !self.sources.read().contains_key(source_id) {
+            return Err(source_not_found(source_id));
+        }
+        self.bookmarks.add(source_id, path, name).await
+    }
+    
+    /// Unpin a bookmark
+    pub async fn remove_bookmark(&self, id: &str) -> Result<()> {
+        self.bookmarks.remove(id).await
+    }
+    
+    /// Bookmarks in sidebar order, with `stale` set on those whose target is gone.
+    ///
+    /// Unlike Recents, stale bookmarks are kept so the user can fix or remove
+    /// them. Bookmarks on sources that aren't mounted can't be checked and
+    /// are returned as-is.
+    pub async fn list_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        let mut bookmarks = self.bookmarks.list();
+        
+        for bookmark in &mut bookmarks {
+            let adapter = {
+                let sources = self.sources.read();
+                sources.get(&bookmark.source_id).map(|state| state.adapter.clone())
+            };
+            
+            match adapter {
+                Some(adapter) => bookmark.stale = matches!(adapter.exists(&bookmark.path).await, Ok(false)),
+                None => debug!("Not checking bookmark on unmounted source: {}", bookmark.source_id),
+            }
+        }
+        
+        Ok(bookmarks)
+    }
+    
+    /// Reorder bookmarks; ids not in `order` keep their relative order after those that are
+    pub async fn reorder_bookmarks(&self, order: &[String]) -> Result<Vec<Bookmark>> {
+        self.bookmarks.reorder(order).await?;
+        self.list_bookmarks().await
+    }
+    
+    /// Mount a source read-only, or make it writable again
+    pub fn set_read_only(&self, source_id: &str, read_only: bool) -> Result<StorageSource> {
+        let mut sources = self.sources.write();
+        let state = sources.get_mut(source_id)
+            .ok_or_else(|| source_not_found(source_id))?;
+        
+        state.source.config.read_only = read_only;
+        info!("Source {} is now {}", state.source.name, if read_only { "read-only" } else { "writable" });
+        
+        Ok(state.source.clone())
+    }
+    
+    /// Cap how many bytes a source may hold (`None` removes the cap), and
+    /// remember the cap for the next launch
+    pub async fn set_quota(&self, source_id: &str, quota_bytes: Option<u64>) -> Result<StorageSource> {
+        let source = {
+            let mut sources = self.sources.write();
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            state.source.config.quota_bytes = quota_bytes;
+            info!("Source {} quota set to {:?} bytes", state.source.name, quota_bytes);
+            state.source.clone()
+        };
+        
+        let remembered = self.sources_store.update(source_id, |config| {
+            *config.quota_bytes_mut() = quota_bytes;
+        }).await;
+        if let Err(e) = remembered {
+            warn!("Failed to remember quota of source {}: {}", source.name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// Change how a remote source retries transient failures, and remember
+    /// the policy for the next launch
+    pub async fn set_retry_policy(&self, source_id: &str, policy: RetryPolicy) -> Result<StorageSource> {
+        if policy.max_attempts == 0 {
+            return Err(VfsError::InvalidInput("Retry policy needs at least one attempt".to_string()).into());
+        }
+        if policy.base_delay > policy.max_delay {
+            return Err(VfsError::InvalidInput(format!(
+                "Retry base delay {:?} is longer than the max delay {:?}",
+                policy.base_delay, policy.max_delay
+            )).into());
+        }
+        
+        let source = {
+            let mut sources = self.sources.write();
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            if matches!(state.source.source_type.category(), StorageCategory::Local | StorageCategory::Block) {
+                return Err(VfsError::Unsupported(format!(
+                    "{} is not a remote source; retry policies apply to cloud and network storage",
+                    state.source.name
+                )).into());
+            }
+            
+            state.adapter.set_retry_policy(policy);
+            state.source.config.retry_policy = Some(policy);
+            info!("Source {} retry policy set to {:?}", state.source.name, policy);
+            state.source.clone()
+        };
+        
+        let remembered = self.sources_store.update(source_id, |config| {
+            if let Some(saved) = config.retry_policy_mut() {
+                *saved = Some(policy);
+            }
+        }).await;
+        if let Err(e) = remembered {
+            warn!("Failed to remember retry policy of source {}: {}", source.name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// Change listing page and transfer chunk sizes of an object storage
+    /// source, and remember them for the next launch
+    pub async fn set_transfer_tuning(&self, source_id: &str, tuning: TransferTuning) -> Result<StorageSource> {
+        if !(1..=MAX_LIST_PAGE_SIZE).contains(&tuning.list_page_size) {
+            return Err(VfsError::InvalidInput(format!(
+                "List page size must be between 1 and {}", MAX_LIST_PAGE_SIZE
+            )).into());
+        }
+        // S3 parts must be at least 5 MiB; GCS chunks a multiple of 256 KiB
+        if tuning.upload_chunk_size < MIN_UPLOAD_CHUNK_SIZE || tuning.upload_chunk_size % (256 * 1024) != 0 {
+            return Err(VfsError::InvalidInput(
+                "Upload chunk size must be a multiple of 256 KiB and at least 5 MiB".to_string()
+            ).into());
+        }
+        if tuning.download_chunk_size == 0 || tuning.max_concurrency == 0 {
+            return Err(VfsError::InvalidInput(
+                "Download chunk size and concurrency must be at least 1".to_string()
+            ).into());
+        }
+        
+        let source = {
+            let mut sources = self.sources.write();
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            if state.adapter.transfer_tuning().is_none() {
+                return Err(VfsError::Unsupported(format!(
+                    "{} is not an object storage source; transfer tuning applies to S3 and GCS",
+                    state.source.name
+                )).into());
+            }
+            
+            state.adapter.set_transfer_tuning(tuning);
+            state.source.config.transfer_tuning = Some(tuning);
+            info!("Source {} transfer tuning set to {:?}", state.source.name, tuning);
+            state.source.clone()
+        };
+        
+        let remembered = self.sources_store.update(source_id, |config| {
+            if let Some(saved) = config.transfer_tuning_mut() {
+                *saved = Some(tuning);
+            }
+        }).await;
+        if let Err(e) = remembered {
+            warn!("Failed to remember transfer tuning of source {}: {}", source.name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// Change one of a source's options, dropping its cached listings when
+    /// they would now look different, and remember it for the next launch
+    pub async fn set_source_option(&self, source_id: &str, option: SourceOption) -> Result<StorageSource> {
+        let source = {
+            let mut sources = self.sources.write();
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            match option {
+                SourceOption::FlatListing(flat) => {
+                    if state.adapter.flat_listing().is_none() {
+                        return Err(VfsError::Unsupported(format!(
+                            "{} has real folders; flat listing applies to S3",
+                            state.source.name
+                        )).into());
+                    }
+                    state.adapter.set_flat_listing(flat);
+                    state.source.config.flat_listing = flat;
+                    self.listings.invalidate_source(source_id);
+                }
+            }
+            info!("Source {} option set: {:?}", state.source.name, option);
+            state.source.clone()
+        };
+        
+        let remembered = self.sources_store.update(source_id, |config| match option {
+            SourceOption::FlatListing(flat) => {
+                if let MountConfig::S3 { flat_listing, .. } = config {
+                    *flat_listing = flat;
+                }
+            }
+        }).await;
+        if let Err(e) = remembered {
+            warn!("Failed to remember options of source {}: {}", source.name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// Move idle files of a source to a colder tier in the background
+    /// (`None` turns lifecycle management off), and remember the policy for
+    /// the next launch
+    pub async fn set_lifecycle_policy(&self, source_id: &str, policy: Option<LifecyclePolicy>) -> Result<StorageSource> {
+        if policy.is_some_and(|p| p.idle_days == 0) {
+            return Err(VfsError::InvalidInput("Lifecycle policy needs at least one idle day".to_string()).into());
+        }
+        
+        let source = {
+            let mut sources = self.sources.write();
+            let state = sources.get_mut(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            
+            if policy.is_some() && !state.adapter.supports_tier_changes() {
+                return Err(VfsError::Unsupported(format!(
+                    "{} does not support tier changes; lifecycle policies apply to object storage",
+                    state.source.name
+                )).into());
+            }
+            
+            state.source.config.lifecycle_policy = policy;
+            info!("Source {} lifecycle policy set to {:?}", state.source.name, policy);
+            state.source.clone()
+        };
+        
+        let remembered = self.sources_store.update(source_id, |config| {
+            if let Some(saved) = config.lifecycle_policy_mut() {
+                *saved = policy;
+            }
+        }).await;
+        if let Err(e) = remembered {
+            warn!("Failed to remember lifecycle policy of source {}: {}", source.name, e);
+        }
+        
+        Ok(source)
+    }
+    
+    /// Files the source's lifecycle policy would move right now
+    pub async fn lifecycle_candidates(&self, source_id: &str) -> Result<Vec<LifecycleCandidate>> {
+        let (adapter, policy) = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            (state.adapter.clone(), state.source.config.lifecycle_policy)
+        };
+        let Some(policy) = policy else {
+            return Ok(Vec::new());
+        };
+        
+        let files = lifecycle::list_tree(adapter.as_ref(), Path::new("/")).await?;
+        Ok(lifecycle::select_candidates(&files, &policy, SystemTime::now()))
+    }
+    
+    /// Move files to another storage tier; failures are reported per file
+    pub async fn change_tier(&self, source_id: &str, paths: &[PathBuf], tier: StorageTier) -> Result<TierChangeReport> {
+        self.ensure_writable(source_id)?;
+        
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            if !state.adapter.supports_tier_changes() {
+                return Err(VfsError::Unsupported(format!(
+                    "{} does not support tier changes",
+                    state.source.name
+                )).into());
+            }
+            state.adapter.clone()
+        };
+        
+        let mut report = TierChangeReport::default();
+        for path in paths {
+            let key = (source_id.to_string(), path.clone());
+            // A new request replaces one still waiting for its restore
+            self.pending_restores.write().remove(&key);
+            self.tier_changes.write().insert(key.clone(), tier);
+            let result = adapter.change_tier(path, tier).await;
+            self.tier_changes.write().remove(&key);
+            
+            match result {
+                Ok(()) => {
+                    self.invalidate_listing(source_id, path);
+                    self.record_tier(source_id, path, tier).await;
+                    report.changed.push(path.clone());
+                }
+                // Archived files can only be copied to another class once restored
+                Err(e) if is_unrestored(&e) => {
+                    match adapter.initiate_retrieval(path, RetrievalTier::default()).await {
+                        Ok(()) => {
+                            self.pending_restores.write().insert(key, tier);
+                            report.restoring.push(path.clone());
+                        }
+                        Err(e) => {
+                            warn!("Failed to restore {:?} to move it to {:?}: {}", path, tier, e);
+                            report.failed.push(format!("{}: {}", path.display(), e));
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to move {:?} to {:?}: {}", path, tier, e);
+                    report.failed.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+        
+        info!(
+            "Moved {} files to {:?} ({} restoring first, {} failed)",
+            report.changed.len(), tier, report.restoring.len(), report.failed.len()
+        );
+        Ok(report)
+    }
+    
+    /// Finish the tier changes that were waiting for an archive restore, for
+    /// files whose restore is done. The rest stay pending.
+    pub async fn finish_restored_tier_changes(&self) -> TierChangeReport {
+        let pending: Vec<((String, PathBuf), StorageTier)> = self.pending_restores.read()
+            .iter()
+            .map(|(key, tier)| (key.clone(), *tier))
+            .collect();
+        
+        let mut report = TierChangeReport::default();
+        for (key, tier) in pending {
+            let (source_id, path) = &key;
+            let result = match self.adapter(source_id) {
+                Ok(adapter) => adapter.change_tier(path, tier).await,
+                Err(e) => Err(e),
+            };
+            
+            match result {
+                Ok(()) => {
+                    self.pending_restores.write().remove(&key);
+                    self.invalidate_listing(source_id, path);
+                    self.record_tier(source_id, path, tier).await;
+                    report.changed.push(path.clone());
+                }
+                Err(e) if is_unrestored(&e) => report.restoring.push(path.clone()),
+                Err(e) => {
+                    self.pending_restores.write().remove(&key);
+                    warn!("Failed to move restored {:?} to {:?}: {}", path, tier, e);
+                    report.failed.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+        report
+    }
+    
+    /// Move what the source's lifecycle policy selects among the next
+    /// `LIFECYCLE_FOLDERS_PER_PASS` folders. Each call carries on where the
+    /// last one left off, so a large bucket is covered over several calls
+    /// instead of being walked in full every time.
+    pub async fn apply_lifecycle(&self, source_id: &str) -> Result<TierChangeReport> {
+        let Some(policy) = self.get_source(source_id).and_then(|s| s.config.lifecycle_policy) else {
+            return Ok(TierChangeReport::default());
+        };
+        self.ensure_writable(source_id)?;
+        let adapter = self.adapter(source_id)?;
+        
+        let mut cursor = self.lifecycle_cursors.write().remove(source_id).unwrap_or_default();
+        let listed = lifecycle::walk_step(adapter.as_ref(), &mut cursor, LIFECYCLE_FOLDERS_PER_PASS).await;
+        self.lifecycle_cursors.write().insert(source_id.to_string(), cursor);
+        
+        let paths: Vec<PathBuf> = lifecycle::select_candidates(&listed?, &policy, SystemTime::now())
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+        if paths.is_empty() {
+            return Ok(TierChangeReport::default());
+        }
+        
+        self.change_tier(source_id, &paths, policy.target_tier).await
+    }
+    
+    /// Apply every writable source's lifecycle policy each `interval`, and
+    /// finish tier changes whose restore is done. The task stops once the
+    /// service is dropped.
+    pub fn start_lifecycle_evaluator(self: &Arc<Self>, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        let service = Arc::downgrade(self);
+        
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(service) = service.upgrade() else {
+                    break;
+                };
+                
+                let source_ids: Vec<String> = service.list_sources()
+                    .into_iter()
+                    .filter(|s| s.config.lifecycle_policy.is_some() && !s.config.read_only)
+                    .map(|s| s.id)
+                    .collect();
+                for source_id in source_ids {
+                    if let Err(e) = service.apply_lifecycle(&source_id).await {
+                        warn!("Lifecycle evaluation failed for {}: {}", source_id, e);
+                    }
+                }
+                service.finish_restored_tier_changes().await;
+            }
+        })
+    }
+    
+    /// Configured quota for a source, if any
+    fn quota(&self, source_id: &str) -> Option<u64> {
+        self.sources.read().get(source_id)?.source.config.quota_bytes
+    }
+    
+    /// Bytes stored in a source. Walked on first request, then cached until the
+    /// next write or delete in that source.
+    pub async fn source_usage(&self, source_id: &str) -> Result<u64> {
+        if let Some(used) = self.usage.read().get(source_id) {
+            return Ok(*used);
+        }
+        
+        let adapter = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.adapter.clone()
+        };
+        
+        let used: u64 = manifest::collect_files(adapter.as_ref(), Path::new("/")).await?
+            .iter()
+            .map(|(_, _, size)| size)
+            .sum();
+        
+        self.usage.write().insert(source_id.to_string(), used);
+        Ok(used)
+    }
+    
+    /// Forget the cached usage of a source after its contents changed
+    fn invalidate_usage(&self, source_id: &str) {
+        self.usage.write().remove(source_id);
+    }
+    
+    /// Merkle hash of the file or folder at `path`. Only files changed since
+    /// the last call (or never hashed) are read; equal hashes mean equal trees.
+    pub async fn tree_hash(&self, source_id: &str, path: &Path) -> Result<TreeHash> {
+        let adapter = self.adapter(source_id)?;
+        tree_hash::tree_hash(adapter.as_ref(), source_id, path, &self.tree_hashes).await
+    }
+    
+    /// File and folder counts and total bytes of a selection, walking into
+    /// folders. Stops with `VfsError::Cancelled` once `cancel` fires. Folder
+    /// totals are reused only on sources whose listings are cached, since a
+    /// local folder can change without the VFS hearing about it.
+    pub async fn selection_info(
+        &self,
+        source_id: &str,
+        paths: &[PathBuf],
+        cancel: &CancellationToken,
+        progress: &SelectionProgress,
+    ) -> Result<SelectionInfo> {
+        let file_ops = self.get_file_ops(source_id)?;
+        let cache = self.get_source(source_id)
+            .filter(caches_listings)
+            .map(|_| self.dir_sizes.as_ref());
+        selection::measure_selection(file_ops.as_ref(), source_id, paths, cache, cancel, progress).await
+    }
+    
+    /// Everything the info panel shows about `path`, gathered in one call.
+    ///
+    /// Folders are walked for their recursive totals. Audio, video and images
+    /// are probed with `media` when given, provided they are on local disk or
+    /// already cached - a cold file is not downloaded just to be described.
+    pub async fn get_info(
+        &self,
+        source_id: &str,
+        path: &Path,
+        media: Option<&dyn IMediaService>,
+    ) -> Result<FileInfo> {
+        let file_ops = self.get_file_ops(source_id)?;
+        let adapter = self.adapter(source_id)?;
+        let stat = file_ops.stat(path).await?;
+        let mut file = adapter.get_metadata(path).await?;
+        sniff_content_types(adapter.as_ref(), std::slice::from_mut(&mut file), 1).await;
+        let local = self.local_path(source_id, path);
+        
+        let metadata = self.metadata.read().clone();
+        let user_metadata = match metadata {
+            Some(metadata) => metadata.get(source_id, path).await?.unwrap_or_default(),
+            None => FileMetadata::default(),
+        };
+        
+        let media = match (media, &file.content_type) {
+            (Some(media), Some(mime)) if file_info::is_probeable(mime) => {
+                let probe_path = match &local {
+                    Some(local) => Some(local.clone()),
+                    None => self.cache.get_cached_path(&cache_key(source_id, path)).await,
+                };
+                match probe_path {
+                    Some(probe_path) => match media.get_media_info(&probe_path).await {
+                        Ok(info) => Some(MediaInfo { path: path.to_path_buf(), ..info }),
+                        Err(e) => {
+                            debug!("Could not probe {:?}: {:#}", path, e);
+                            None
+                        }
+                    },
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        
+        let contents = if stat.is_dir {
+            let mut totals = self.selection_info(
+                source_id, &[path.to_path_buf()], &CancellationToken::new(), &|_| {},
+            ).await?;
+            totals.dir_count = totals.dir_count.saturating_sub(1);
+            Some(totals)
+        } else {
+            None
+        };
+        
+        let info = FileInfo {
+            path: path.to_path_buf(),
+            name: file.name,
+            size: stat.size,
+            is_dir: stat.is_dir,
+            is_symlink: stat.is_symlink,
+            modified: stat.mtime.map(chrono::DateTime::<chrono::Utc>::from),
+            accessed: stat.atime.map(chrono::DateTime::<chrono::Utc>::from),
+            created: stat.ctime.map(chrono::DateTime::<chrono::Utc>::from),
+            mode: stat.mode,
+            permissions: local.as_deref().and_then(|local| get_permissions(local).ok()),
+            tier_status: file.tier_status,
+            cache_status: self.warm_status(source_id, path).await?,
+            mime_type: file.content_type,
+            tags: Vec::new(),
+            is_favorite: false,
+            color_label: None,
+            rating: None,
+            comment: None,
+            media,
+            contents,
+        };
+        Ok(info.with_metadata(user_metadata))
+    }
+    
+    /// Up to `max_bytes` from the start of a file, for a quick look. Read from
+    /// the cache when those bytes are already there and straight from the
+    /// source otherwise, without caching them or counting as a recent open.
+    pub async fn preview(&self, source_id: &str, path: &Path, max_bytes: u64) -> Result<PreviewData> {
+        let adapter = self.adapter(source_id)?;
+        let file = adapter.get_metadata(path).await?;
+        if file.is_directory {
+            return Err(VfsError::InvalidInput(format!("{} is a folder", path.display())).into());
+        }
+        
+        let size = file.size.bytes();
+        let len = size.min(max_bytes);
+        let data = if len == 0 {
+            Vec::new()
+        } else if self.cache.is_range_cached(&cache_key(source_id, path), 0, len).await {
+            self.cache.read_range_from_cache(&cache_key(source_id, path), 0, len).await?
+        } else {
+            adapter.read_file_range(path, 0, len).await?
+        };
+        
+        Ok(PreviewData::new(path, data, size))
+    }
+    
+    /// PNG thumbnail of an image on object storage, cached in `cache_dir`
+    /// until the object's ETag changes
+    pub async fn remote_thumbnail(&self, source_id: &str, path: &Path, size: u32, cache_dir: &Path) -> Result<Option<Vec<u8>>> {
+        let adapter = self.adapter(source_id)?;
+        remote_thumbnail::remote_thumbnail(adapter.as_ref(), source_id, path, size, cache_dir).await
+    }
+    
+    /// The last `count` lines of a text file
+    pub async fn tail(&self, source_id: &str, path: &Path, count: usize) -> Result<Vec<String>> {
+        let adapter = self.adapter(source_id)?;
+        tail::last_lines(adapter.as_ref(), path, count).await
+    }
+    
+    /// Call `on_lines` with each batch of lines appended to a file until
+    /// `cancel` fires or `on_lines` returns false. Local files are checked
+    /// when the OS reports a change, others every `interval`. Following
+    /// starts at the current end of the file. If the file goes missing
+    /// (mid-rotation) it's waited for and read from its start once it's back.
+    pub async fn follow_tail(
+        &self,
+        source_id: &str,
+        path: &Path,
+        interval: std::time::Duration,
+        cancel: CancellationToken,
+        on_lines: Arc<TailLines>,
+    ) -> Result<tokio::task::JoinHandle<()>> {
+        let adapter = self.adapter(source_id)?;
+        let mut cursor = TailCursor::at(adapter.file_size(path).await?);
+        let mut watch = self.local_path(source_id, path).and_then(|local| match watch_file(&local) {
+            Ok(watch) => Some(watch),
+            Err(e) => {
+                debug!("Polling {:?} instead of watching it: {:#}", local, e);
+                None
+            }
+        });
+        let path = path.to_path_buf();
+        
+        Ok(tokio::spawn(async move {
+            loop {
+                let changed = async {
+                    match watch.as_mut() {
+                        Some(watch) => watch.changed().await,
+                        None => tokio::time::sleep(interval).await,
+                    }
+                };
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = changed => {}
+                }
+                
+                match cursor.read_new(adapter.as_ref(), &path).await {
+                    Ok(lines) if lines.is_empty() => {}
+                    Ok(lines) => {
+                        if !on_lines(lines) {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Failed to read {:?} while following it: {}", path, e);
+                        if !adapter.exists(&path).await.unwrap_or(true) {
+                            cursor.rewind();
+                        }
+                    }
+                }
+            }
+        }))
+    }
+    
+    /// Fail with `ArchiveNotRetrievedError` if the source already knows that
+    /// `path` has to be retrieved from an archive tier before it can be read.
+    /// Otherwise the read goes ahead, and fails with the same error itself if
+    /// the object turns out to be archived.
+    async fn ensure_retrieved(&self, source_id: &str, path: &Path) -> Result<()> {
+        let adapter = self.adapter(source_id)?;
+        if let Some(estimated_retrieval_secs) = adapter.pending_retrieval(path).await? {
+            return Err(ArchiveNotRetrievedError {
+                path: path.display().to_string(),
+                estimated_retrieval_secs,
+            }.into());
+        }
+        Ok(())
+    }
+    
+    /// Start restoring an archived file. Reads keep failing with
+    /// `ArchiveNotRetrievedError` until the restore finishes.
+    pub async fn initiate_retrieval(&self, source_id: &str, path: &Path, tier: RetrievalTier) -> Result<()> {
+        self.adapter(source_id)?.initiate_retrieval(path, tier).await?;
+        info!("Requested {:?} retrieval of {:?} on {}", tier, path, source_id);
+        Ok(())
+    }
+    
+    /// Fail with `QuotaExceededError` if `additional` bytes would take the
+    /// source past its quota
+    async fn ensure_quota(&self, source_id: &str, additional: u64) -> Result<()> {
+        let Some(quota) = self.quota(source_id) else {
+            return Ok(());
+        };
+        
+        let used = self.source_usage(source_id).await?;
+        if used.saturating_add(additional) > quota {
+            return Err(QuotaExceededError { used, quota }.into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Fail with `ReadOnlyError` if the source is mounted read-only
+    fn ensure_writable(&self, source_id: &str) -> Result<()> {
+        let sources = self.sources.read();
+        let state = sources.get(source_id)
+            .ok_or_else(|| source_not_found(source_id))?;
+        
+        if state.source.config.read_only {
+            return Err(ReadOnlyError {
+                source_id: source_id.to_string(),
+                source_name: state.source.name.clone(),
+            }.into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Fail with `InsufficientSpaceError` if `needed` bytes won't fit at `path`.
+    /// Only sources backed by a local volume are checked.
+    fn ensure_local_space(&self, source_id: &str, path: &Path, needed: u64) -> Result<()> {
+        match self.local_path(source_id, path) {
+            Some(real_path) => check_free_space(self.space_provider.as_ref(), &real_path, needed),
+            None => Ok(()),
+        }
+    }
+    
+    /// Make sure the cache volume can take `needed` more bytes, evicting cached
+    /// files first when the cache is allowed to evict (`max_size > 0`)
+    async fn ensure_cache_space(&self, needed: u64) -> Result<()> {
+        let cache_dir = self.cache.config().path.clone();
+        
+        let Err(e) = check_free_space(self.space_provider.as_ref(), &cache_dir, needed) else {
+            return Ok(());
+        };
+        let shortfall = match e.downcast_ref::<InsufficientSpaceError>() {
+            Some(err) if self.cache.config().max_size > 0 => err.needed - err.available,
+            _ => return Err(e),
+        };
+        
+        let freed = self.cache.evict_bytes(shortfall).await?;
+        info!("Evicted {} bytes from cache to make room for {} byte hydration", freed, needed);
+        
+        check_free_space(self.space_provider.as_ref(), &cache_dir, needed)
+    }
+    
+    /// Drop entries in `store` for files and folders no longer on the source.
+    /// The whole source is listed first, so an unreachable source prunes nothing.
+    pub async fn vacuum_metadata(&self, source_id: &str, store: &dyn IMetadataStore) -> Result<usize> {
+        let file_ops = self.get_file_ops(source_id)?;
+        let mut live = HashSet::new();
+        let mut pending = vec![PathBuf::from("/")];
+        
+        while let Some(dir) = pending.pop() {
+            for entry in file_ops.list(&dir).await? {
+                let path = dir.join(&entry.name);
+                if entry.is_dir {
+                    pending.push(path.clone());
+                }
+                live.insert(path);
+            }
+        }
+        live.insert(PathBuf::from("/"));
+        
+        store.vacuum(source_id, &|path: &Path| live.contains(path)).await
+    }
+    
+    /// Total bytes of all files under `path` (or the file itself)
+    async fn tree_size(&self, file_ops: &Arc<dyn IFileOperations>, path: &Path) -> Result<u64> {
+        let stat = file_ops.stat(path).await?;
+        if !stat.is_dir {
+            return Ok(stat.size);
+        }
+        
+        let mut total = 0u64;
+        let mut pending = vec![path.to_path_buf()];
+        
+        while let Some(dir) = pending.pop() {
+            for entry in file_ops.list(&dir).await? {
+                if entry.is_dir {
+                    pending.push(dir.join(&entry.name));
+                } else {
+                    total += entry.size;
+                }
+            }
+        }
+        
+        Ok(total)
+    }
+    
+    /// Remove a storage source.
+    ///
+    /// Only the VFS side goes away: transfers using the source are cancelled and
+    /// its adapter and cached usage are dropped, but whatever backs it (a local
+    /// folder, a mounted volume, a bucket) is left untouched.
+    pub fn remove_source(&self, source_id: &str) -> Option<StorageSource> {
+        let canceled = self.transfers.cancel_for(source_id);
+        if canceled > 0 {
+            warn!("Canceled {} transfer(s) using removed source {}", canceled, source_id);
+        }
+        
+        self.invalidate_usage(source_id);
+        self.listings.invalidate_source(source_id);
+        self.dir_sizes.invalidate_source(source_id);
+        self.tree_hashes.invalidate_source(source_id);
+        self.catalog.remove(source_id);
+        self.history.forget_source(source_id);
+        self.case_probes.write().remove(source_id);
+        self.lifecycle_cursors.write().remove(source_id);
+        self.sources.write()
             .remove(source_id)
             .map(|s| s.source)
     }
     
+    /// Make sure nothing is using a source before its volume is ejected.
+    ///
+    /// Fails with `VolumeBusyError` while transfers touch the source, unless
+    /// `force` is set, in which case they are cancelled and waited for.
+    pub async fn prepare_eject(&self, source_id: &str, force: bool) -> Result<()> {
+        let active = self.transfers.active_for(source_id);
+        if active.is_empty() {
+            return Ok(());
+        }
+        
+        if !force {
+            return Err(VolumeBusyError {
+                operations: active.into_iter().map(|t| t.description).collect(),
+            }.into());
+        }
+        
+        let canceled = self.transfers.cancel_for(source_id);
+        warn!("Canceling {} transfer(s) on {} before eject", canceled, source_id);
+        
+        // Cancelled transfers deregister once their task next wakes up
+        let deadline = std::time::Instant::now() + EJECT_CANCEL_TIMEOUT;
+        loop {
+            let remaining = self.transfers.active_for(source_id);
+            if remaining.is_empty() {
+                return Ok(());
+            }
+            if std::time::Instant::now() >= deadline {
+                return Err(VolumeBusyError {
+                    operations: remaining.into_iter().map(|t| t.description).collect(),
+                }.into());
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    }
+    
     /// Get the real filesystem path for a file in a storage source
     /// This resolves VFS paths to actual filesystem paths for opening with native apps
     pub async fn get_real_path(&self, source_id: &str, path: &Path) -> Result<PathBuf> {
-        let sources = self.sources.read();
-        let state = sources.get(source_id)
-            .ok_or_else(|| anyhow::anyhow!("Storage source not found: {}", source_id))?;
-        
-        // Get mount point from the source
-        if let Some(mount_point) = &state.source.mount_point {
-            // For local sources, combine mount point with relative path
-            let real_path = if path.is_absolute() {
-                // If path already starts with mount point, use as-is
-                if path.starts_with(mount_point) {
-                    path.to_path_buf()
-                } else {
-                    // Strip leading slash and append to mount point
-                    let relative = path.strip_prefix("/").unwrap_or(path);
-                    mount_point.join(relative)
-                }
-            } else {
-                mount_point.join(path)
-            };
-            return Ok(real_path);
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
         }
         
         // For non-local sources (S3, etc.), we may need to download first
         // For now, return an error - future: use cache path
-        Err(anyhow::anyhow!("Cannot get real path for non-local storage source"))
+        self.local_path(source_id, path)
+            .ok_or_else(|| anyhow::anyhow!("Cannot get real path for non-local storage source"))
+    }
+    
+    /// Where `path` can be opened natively: its real path on a local source,
+    /// its cached copy for a hydrated remote file, or nowhere
+    pub async fn resolve_path(&self, source_id: &str, path: &Path) -> Result<ResolvedPath> {
+        if self.get_source(source_id).is_none() {
+            return Err(source_not_found(source_id));
+        }
+        
+        if let Some(real_path) = self.local_path(source_id, path) {
+            return Ok(ResolvedPath::Local { real_path });
+        }
+        
+        match self.cache.get_cached_path(&cache_key(source_id, path)).await {
+            Some(cache_path) => Ok(ResolvedPath::Cached { cache_path }),
+            None => Ok(ResolvedPath::Remote),
+        }
+    }
+    
+    /// Write a path the way the user asked for: logical VFS path, real path on
+    /// disk, or a URI (`file://`, `s3://bucket/key`, `gs://bucket/key`)
+    pub async fn format_path(&self, source_id: &str, path: &Path, style: PathStyle) -> Result<String> {
+        match style {
+            PathStyle::Vfs => Ok(path.to_string_lossy().to_string()),
+            PathStyle::Native => Ok(self.get_real_path(source_id, path).await?.to_string_lossy().to_string()),
+            PathStyle::Uri => {
+                let source = self.get_source(source_id)
+                    .ok_or_else(|| source_not_found(source_id))?;
+                let local_path = self.local_path(source_id, path);
+                path_format::format_uri(&source, path, local_path.as_deref())
+            }
+        }
+    }
+    
+    /// Resolve a path to the local filesystem, `None` for unknown or non-local sources
+    pub fn local_path(&self, source_id: &str, path: &Path) -> Option<PathBuf> {
+        let sources = self.sources.read();
+        let mount_point = sources.get(source_id)?.source.mount_point.as_ref()?;
+        
+        // For local sources, combine mount point with relative path
+        let real_path = if path.is_absolute() {
+            // If path already starts with mount point, use as-is
+            if path.starts_with(mount_point) {
+                path.to_path_buf()
+            } else {
+                // Strip leading slash and append to mount point
+                let relative = path.strip_prefix("/").unwrap_or(path);
+                mount_point.join(relative)
+            }
+        } else {
+            mount_point.join(path)
+        };
+        
+        Some(real_path)
     }
     
     // =========================================================================
@@ -354,64 +2850,553 @@ impl VfsService {
     fn get_file_ops(&self, source_id: &str) -> Result<Arc<dyn IFileOperations>> {
         let sources = self.sources.read();
         let state = sources.get(source_id)
-            .ok_or_else(|| anyhow::anyhow!("Storage source not found: {}", source_id))?;
+            .ok_or_else(|| source_not_found(source_id))?;
         
         state.file_ops.clone()
-            .ok_or_else(|| anyhow::anyhow!("Source does not support file operations"))
+            .ok_or_else(|| VfsError::Unsupported("Source does not support file operations".to_string()).into())
+    }
+    
+    /// Get file operations for a modification, rejecting read-only sources
+    fn get_writable_file_ops(&self, source_id: &str) -> Result<Arc<dyn IFileOperations>> {
+        self.ensure_writable(source_id)?;
+        self.get_file_ops(source_id)
     }
     
     /// Create a directory
     pub async fn mkdir(&self, source_id: &str, path: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.mkdir(path).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let result = file_ops.mkdir(path).await;
+        self.invalidate_listing(source_id, path);
+        if result.is_ok() {
+            self.history.record(source_id, FileOperation::CreateFolder { path: path.to_path_buf() });
+        }
+        result
     }
     
     /// Create directory and all parents
     pub async fn mkdir_p(&self, source_id: &str, path: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.mkdir_p(path).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let result = file_ops.mkdir_p(path).await;
+        self.invalidate_listing(source_id, path);
+        result
     }
     
     /// Remove empty directory
     pub async fn rmdir(&self, source_id: &str, path: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.rmdir(path).await
+        let result = async {
+            let file_ops = self.get_writable_file_ops(source_id)?;
+            file_ops.rmdir(path).await
+        }.await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        if result.is_ok() {
+            self.history.barrier();
+        }
+        self.audit(AuditEntry::new(AuditOperation::Delete, source_id, &[path], &result)).await;
+        result
     }
     
     /// Rename file or directory
     pub async fn rename(&self, source_id: &str, from: &Path, to: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.rename(from, to).await
+        let result = async {
+            let file_ops = self.get_writable_file_ops(source_id)?;
+            file_ops.rename(from, to).await
+        }.await;
+        self.invalidate_listing(source_id, from);
+        self.invalidate_listing(source_id, to);
+        if result.is_ok() {
+            self.history.record(source_id, FileOperation::Rename { from: from.to_path_buf(), to: to.to_path_buf() });
+        }
+        self.audit(AuditEntry::new(AuditOperation::Rename, source_id, &[from, to], &result)).await;
+        result
+    }
+    
+    /// Rename `paths` (in listed order) from a name template such as
+    /// `shot_{index:02}{ext}`; see `batch_rename` for the tokens.
+    ///
+    /// Nothing is renamed unless every target is unique and none would
+    /// overwrite a file outside the batch. If a rename fails partway, the
+    /// ones already made are undone in reverse, so no file is left under a
+    /// temporary name. Returns the old -> new mapping.
+    pub async fn batch_rename(
+        &self,
+        source_id: &str,
+        paths: &[PathBuf],
+        template: &str,
+        start_index: u64,
+    ) -> Result<Vec<RenameMapping>> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        
+        let batch: std::collections::HashSet<&PathBuf> = paths.iter().collect();
+        if batch.len() != paths.len() {
+            return Err(anyhow::anyhow!("The same file is listed more than once"));
+        }
+        
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let stat = file_ops.stat(path).await?;
+            let modified = stat.mtime
+                .map(chrono::DateTime::<chrono::Local>::from)
+                .unwrap_or_else(chrono::Local::now);
+            files.push((path.clone(), modified.date_naive()));
+        }
+        
+        let plan = batch_rename::plan_renames(&files, template, start_index)?;
+        
+        // Never rename onto an unrelated file
+        for mapping in &plan {
+            if !batch.contains(&mapping.to) && file_ops.exists(&mapping.to).await? {
+                return Err(anyhow::anyhow!("{:?} already exists", mapping.to));
+            }
+        }
+        
+        let pending: Vec<&RenameMapping> = plan.iter().filter(|m| m.from != m.to).collect();
+        
+        // When a target is another file's current name (e.g. renumbering),
+        // move everything to temporary names first so nothing is clobbered
+        let steps: Vec<(PathBuf, PathBuf)> = if pending.iter().any(|m| batch.contains(&m.to)) {
+            let stage = uuid::Uuid::new_v4().simple().to_string();
+            let staged: Vec<PathBuf> = pending.iter().enumerate()
+                .map(|(i, m)| m.from.with_file_name(format!(".ursly-rename-{}-{}", stage, i)))
+                .collect();
+            pending.iter().zip(&staged).map(|(m, temp)| (m.from.clone(), temp.clone()))
+                .chain(pending.iter().zip(&staged).map(|(m, temp)| (temp.clone(), m.to.clone())))
+                .collect()
+        } else {
+            pending.iter().map(|m| (m.from.clone(), m.to.clone())).collect()
+        };
+        
+        for (done, (from, to)) in steps.iter().enumerate() {
+            if let Err(e) = self.rename(source_id, from, to).await {
+                self.undo_renames(source_id, &file_ops, &steps[..done]).await;
+                return Err(e.context("Batch rename stopped; the files already renamed were put back"));
+            }
+        }
+        
+        info!("Batch renamed {} files with template {:?}", plan.len(), template);
+        Ok(plan)
+    }
+    
+    /// Reverse `steps` (renames that succeeded, in the order they were made)
+    /// so everything is back where it started. The undo stack can't replay
+    /// them any more, so it gets a barrier.
+    async fn undo_renames(&self, source_id: &str, file_ops: &Arc<dyn IFileOperations>, steps: &[(PathBuf, PathBuf)]) {
+        for (from, to) in steps.iter().rev() {
+            if let Err(e) = file_ops.rename(to, from).await {
+                warn!("Failed to put {:?} back as {:?} after a failed batch rename: {}", to, from, e);
+            }
+            self.invalidate_listing(source_id, from);
+            self.invalidate_listing(source_id, to);
+        }
+        self.history.barrier();
+    }
+    
+    /// Create a file in `dest_dir` from a template, numbering the name if it is
+    /// taken (`Untitled.txt`, `Untitled 2.txt`, ...). Returns the new file's path.
+    pub async fn new_from_template(&self, source_id: &str, dest_dir: &Path, template_name: &str) -> Result<PathBuf> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let template = self.templates.get(template_name).await?;
+        
+        let mut file_name = template.file_name.clone();
+        while file_ops.exists(&dest_dir.join(&file_name)).await? {
+            file_name = templates::next_name(&file_name, "");
+        }
+        let path = dest_dir.join(&file_name);
+        
+        self.write(source_id, &path, &template.contents).await?;
+        info!("Created {:?} from template {:?}", path, template_name);
+        Ok(path)
+    }
+    
+    /// Save a user template for `new_from_template`
+    pub async fn register_template(&self, name: &str, contents: &[u8]) -> Result<FileTemplate> {
+        self.templates.register(name, contents).await
+    }
+    
+    /// Built-in and user templates
+    pub async fn list_templates(&self) -> Result<Vec<FileTemplate>> {
+        self.templates.list().await
     }
     
     /// Copy file or directory
     pub async fn copy(&self, source_id: &str, from: &Path, to: &Path, options: CopyOptions) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.copy(from, to, options).await
+        self.copy_with_progress(source_id, from, to, options, None).await
+    }
+    
+    /// `copy`, reporting bytes, rate and ETA to `progress` when a single file
+    /// of at least `PROGRESS_COPY_THRESHOLD` is copied
+    pub async fn copy_with_progress(
+        &self,
+        source_id: &str,
+        from: &Path,
+        to: &Path,
+        options: CopyOptions,
+        progress: Option<Arc<CopyProgressFn>>,
+    ) -> Result<()> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        self.ensure_retrieved(source_id, from).await?;
+        if self.local_path(source_id, to).is_some() || self.quota(source_id).is_some() {
+            let needed = self.tree_size(&file_ops, from).await?;
+            self.ensure_local_space(source_id, to, needed)?;
+            self.ensure_quota(source_id, needed).await?;
+        }
+        let ignore = self.ignore_patterns();
+        let overwrite = options.overwrite;
+        let result = if options.recursive && !ignore.is_empty() && file_ops.is_dir(from).await? {
+            self.copy_tree_skipping(&file_ops, from, to, options, &ignore).await
+        } else if let Some(progress) = progress {
+            copy_reporting(&file_ops, from, to, options, progress).await
+        } else {
+            file_ops.copy(from, to, options).await
+        };
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, to);
+        if result.is_ok() {
+            if let Some(folder) = to.parent() {
+                self.record_destination(source_id, folder).await;
+            }
+            // Whatever was overwritten can't be brought back
+            if overwrite {
+                self.history.barrier();
+            }
+        }
+        result
+    }
+    
+    /// Copy a folder within one source file by file, leaving out entries that
+    /// match `ignore`
+    async fn copy_tree_skipping(
+        &self,
+        file_ops: &Arc<dyn IFileOperations>,
+        from: &Path,
+        to: &Path,
+        options: CopyOptions,
+        ignore: &IgnorePatterns,
+    ) -> Result<()> {
+        if !options.overwrite && file_ops.exists(to).await? {
+            return Err(VfsError::AlreadyExists(format!("Destination already exists: {:?}", to)).into());
+        }
+        
+        let mut pending = vec![(from.to_path_buf(), to.to_path_buf())];
+        while let Some((src_dir, dest_dir)) = pending.pop() {
+            file_ops.mkdir_p(&dest_dir).await?;
+            
+            for entry in file_ops.list(&src_dir).await? {
+                let src = src_dir.join(&entry.name);
+                let dest = dest_dir.join(&entry.name);
+                if ignore.matches(src.strip_prefix(from).unwrap_or(&src)) {
+                    debug!("Skipping ignored {:?}", src);
+                    continue;
+                }
+                if entry.is_symlink && !options.follow_symlinks {
+                    // The link itself is copied; where links can't be created it is left out
+                    let target = file_ops.readlink(&src).await?;
+                    if let Err(e) = file_ops.symlink(Path::new(&target), &dest).await {
+                        warn!("Skipping symlink {:?}: {:#}", src, e);
+                    }
+                } else if entry.is_dir {
+                    pending.push((src, dest));
+                } else {
+                    file_ops.copy(&src, &dest, options.clone()).await?;
+                }
+            }
+        }
+        
+        Ok(())
     }
     
     /// Move file or directory
     pub async fn mv(&self, source_id: &str, from: &Path, to: &Path, options: MoveOptions) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.mv(from, to, options).await
+        let overwrite = options.overwrite;
+        let result = async {
+            let file_ops = self.get_writable_file_ops(source_id)?;
+            file_ops.mv(from, to, options).await
+        }.await;
+        self.invalidate_listing(source_id, from);
+        self.invalidate_listing(source_id, to);
+        if result.is_ok() {
+            if let Some(folder) = to.parent() {
+                self.record_destination(source_id, folder).await;
+            }
+            if overwrite {
+                self.history.barrier();
+            } else {
+                self.history.record(source_id, FileOperation::Move { from: from.to_path_buf(), to: to.to_path_buf() });
+            }
+        }
+        self.audit(AuditEntry::new(AuditOperation::Move, source_id, &[from, to], &result)).await;
+        result
     }
     
     /// Remove file
     pub async fn rm(&self, source_id: &str, path: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.rm(path).await
+        let result = async {
+            let file_ops = self.get_writable_file_ops(source_id)?;
+            file_ops.rm(path).await
+        }.await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        if result.is_ok() {
+            self.soft_delete_cached(source_id, path).await;
+            self.history.barrier();
+        }
+        self.audit(AuditEntry::new(AuditOperation::Delete, source_id, &[path], &result)).await;
+        result
     }
     
     /// Remove file or directory recursively
     pub async fn rm_rf(&self, source_id: &str, path: &Path) -> Result<()> {
+        let result = async {
+            let file_ops = self.get_writable_file_ops(source_id)?;
+            file_ops.rm_rf(path).await
+        }.await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        if result.is_ok() {
+            self.soft_delete_cached(source_id, path).await;
+            self.history.barrier();
+        }
+        self.audit(AuditEntry::new(AuditOperation::Delete, source_id, &[path], &result)).await;
+        result
+    }
+    
+    /// Delete several items, carrying on past the ones that fail. Items go to
+    /// the trash unless `permanent` is set; folders are only deleted when
+    /// `recursive` is set.
+    ///
+    /// Only sources with files on this machine have a trash, so on object
+    /// storage and other unmounted sources a non-permanent delete is refused
+    /// with `VfsError::Unsupported` before anything is touched.
+    pub async fn delete_many(&self, source_id: &str, paths: &[PathBuf], recursive: bool, permanent: bool) -> Result<DeleteSummary> {
         let file_ops = self.get_file_ops(source_id)?;
-        file_ops.rm_rf(path).await
+        if !permanent && !self.has_trash(source_id) {
+            let name = self.get_source(source_id).map(|s| s.name).unwrap_or_else(|| source_id.to_string());
+            return Err(VfsError::Unsupported(format!(
+                "{} has no trash; delete the items permanently instead", name
+            )).into());
+        }
+        let mut summary = DeleteSummary::default();
+        
+        for path in paths {
+            let result = async {
+                let stat = file_ops.stat(path).await?;
+                if stat.is_dir && !recursive {
+                    return Err(VfsError::InvalidInput(format!("{} is a folder", path.display())).into());
+                }
+                let size = self.tree_size(&file_ops, path).await?;
+                
+                match (permanent, stat.is_dir) {
+                    (false, _) => self.trash(source_id, path).await?,
+                    (true, true) => self.rm_rf(source_id, path).await?,
+                    (true, false) => self.rm(source_id, path).await?,
+                }
+                Ok::<_, anyhow::Error>(size)
+            }.await;
+            
+            match result {
+                Ok(size) => {
+                    summary.deleted += 1;
+                    summary.total_bytes_freed += size;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    summary.errors.push(format!("{}: {:#}", path.display(), e));
+                }
+            }
+        }
+        
+        info!("Deleted {} of {} item(s) on {}", summary.deleted, paths.len(), source_id);
+        Ok(summary)
+    }
+    
+    /// Move a file or directory to the system Trash / Recycle Bin.
+    ///
+    /// Only local sources have an OS trash; for anything else, or when the OS
+    /// refuses, this returns an error so callers can fall back to deleting.
+    pub async fn trash(&self, source_id: &str, path: &Path) -> Result<()> {
+        let trashed = self.trash_item(source_id, path).await?;
+        self.record_trash(source_id, path, trashed);
+        Ok(())
+    }
+    
+    /// Whether items of the source can go to the system trash, which takes
+    /// files with a path on this machine
+    fn has_trash(&self, source_id: &str) -> bool {
+        self.local_path(source_id, Path::new("/")).is_some()
+    }
+    
+    /// Trash without touching the undo history; returns where the item sits
+    /// in the trash when the OS says
+    async fn trash_item(&self, source_id: &str, path: &Path) -> Result<Option<PathBuf>> {
+        let result = async {
+            self.ensure_writable(source_id)?;
+            let real_path = self.local_path(source_id, path)
+                .ok_or_else(|| VfsError::Unsupported("The system trash is only available for local files".to_string()))?;
+            
+            let trash_dir = self.trash_dir.clone();
+            tokio::task::spawn_blocking(move || match trash_dir {
+                Some(trash_dir) => crate::vfs::platform::trash_freedesktop(
+                    &real_path,
+                    &trash_dir,
+                    chrono::Local::now().naive_local(),
+                ).map(Some),
+                None => crate::vfs::platform::trash_native(&real_path),
+            }).await?
+        }.await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        if result.is_ok() {
+            self.soft_delete_cached(source_id, path).await;
+        }
+        self.audit(AuditEntry::new(AuditOperation::Delete, source_id, &[path], &result)).await;
+        result
+    }
+    
+    /// Permanently delete everything in the system Trash / Recycle Bin
+    pub async fn empty_trash(&self) -> Result<()> {
+        let trash_dir = self.trash_dir.clone();
+        let result = tokio::task::spawn_blocking(move || match trash_dir {
+            Some(trash_dir) => crate::vfs::platform::empty_freedesktop_trash(&trash_dir).map(|_| ()),
+            None => crate::vfs::platform::empty_trash(),
+        }).await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        self.audit(AuditEntry::new(AuditOperation::EmptyTrash, SYSTEM_TRASH_SOURCE, &[], &result)).await;
+        // Trashed items recorded for Undo are gone for good
+        if result.is_ok() {
+            self.history.barrier();
+        }
+        result
+    }
+    
+    /// Trashed items can only be undone when we know where they went
+    fn record_trash(&self, source_id: &str, path: &Path, trashed: Option<PathBuf>) {
+        match trashed {
+            Some(trashed) => self.history.record(source_id, FileOperation::Trash {
+                path: path.to_path_buf(),
+                trash_id: trashed.to_string_lossy().to_string(),
+            }),
+            None => self.history.barrier(),
+        }
+    }
+    
+    /// Invert the most recent rename, move, trash or new folder. Returns the
+    /// operation undone, or `None` when there is nothing to undo.
+    pub async fn undo(&self) -> Result<Option<RecordedOperation>> {
+        let Some(recorded) = self.history.take_undo() else {
+            return Ok(None);
+        };
+        
+        match self.apply_undo(&recorded).await {
+            Ok(()) => {
+                info!("Undid {:?}", recorded.operation);
+                self.history.undone(recorded.clone());
+                Ok(Some(recorded))
+            }
+            Err(e) => {
+                self.history.redone(recorded);
+                Err(e)
+            }
+        }
+    }
+    
+    /// Repeat the most recently undone operation
+    pub async fn redo(&self) -> Result<Option<RecordedOperation>> {
+        let Some(recorded) = self.history.take_redo() else {
+            return Ok(None);
+        };
+        
+        let source_id = recorded.source_id.clone();
+        let result = async {
+            let file_ops = self.get_writable_file_ops(&source_id)?;
+            match &recorded.operation {
+                FileOperation::Rename { from, to } => {
+                    file_ops.rename(from, to).await?;
+                    self.invalidate_listing(&source_id, from);
+                    self.invalidate_listing(&source_id, to);
+                }
+                FileOperation::Move { from, to } => {
+                    file_ops.mv(from, to, MoveOptions::default()).await?;
+                    self.invalidate_listing(&source_id, from);
+                    self.invalidate_listing(&source_id, to);
+                }
+                FileOperation::CreateFolder { path } => {
+                    file_ops.mkdir(path).await?;
+                    self.invalidate_listing(&source_id, path);
+                }
+                FileOperation::Trash { path, .. } => {
+                    // The item lands somewhere new in the trash
+                    let trashed = self.trash_item(&source_id, path).await?;
+                    return Ok(trashed.map(|trashed| RecordedOperation {
+                        source_id: source_id.clone(),
+                        operation: FileOperation::Trash {
+                            path: path.clone(),
+                            trash_id: trashed.to_string_lossy().to_string(),
+                        },
+                    }));
+                }
+            }
+            Ok::<_, anyhow::Error>(Some(recorded.clone()))
+        }.await;
+        
+        match result {
+            Ok(Some(redone)) => {
+                info!("Redid {:?}", redone.operation);
+                self.history.redone(redone.clone());
+                Ok(Some(redone))
+            }
+            Ok(None) => {
+                self.history.barrier();
+                Ok(Some(recorded))
+            }
+            Err(e) => {
+                self.history.undone(recorded);
+                Err(e)
+            }
+        }
+    }
+    
+    async fn apply_undo(&self, recorded: &RecordedOperation) -> Result<()> {
+        let source_id = recorded.source_id.as_str();
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        
+        match &recorded.operation {
+            FileOperation::Rename { from, to } | FileOperation::Move { from, to } => {
+                // Never put a file back over one that took its old name
+                if file_ops.exists(from).await? {
+                    return Err(VfsError::AlreadyExists(format!("{:?} already exists", from)).into());
+                }
+                if matches!(recorded.operation, FileOperation::Rename { .. }) {
+                    file_ops.rename(to, from).await?;
+                } else {
+                    file_ops.mv(to, from, MoveOptions::default()).await?;
+                }
+                self.invalidate_listing(source_id, to);
+                self.invalidate_listing(source_id, from);
+            }
+            FileOperation::CreateFolder { path } => {
+                // Only while still empty; anything added since would be lost
+                file_ops.rmdir(path).await?;
+                self.invalidate_listing(source_id, path);
+            }
+            FileOperation::Trash { path, trash_id } => {
+                let original = self.local_path(source_id, path)
+                    .ok_or_else(|| anyhow::anyhow!("{:?} is not a local file", path))?;
+                let trashed = PathBuf::from(trash_id);
+                tokio::task::spawn_blocking(move || crate::vfs::platform::restore_trashed(&trashed, &original)).await??;
+                self.invalidate_usage(source_id);
+                self.invalidate_listing(source_id, path);
+            }
+        }
+        Ok(())
     }
     
     /// Change file permissions
     pub async fn chmod(&self, source_id: &str, path: &Path, mode: u32) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.chmod(path, mode).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let result = file_ops.chmod(path, mode).await;
+        self.invalidate_listing(source_id, path);
+        result
     }
     
     /// Get file statistics
@@ -422,8 +3407,10 @@ impl VfsService {
     
     /// Touch file (create or update timestamp)
     pub async fn touch(&self, source_id: &str, path: &Path) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.touch(path).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let result = file_ops.touch(path).await;
+        self.invalidate_listing(source_id, path);
+        result
     }
     
     /// Check if path exists
@@ -432,28 +3419,231 @@ impl VfsService {
         file_ops.exists(path).await
     }
     
-    /// Read file contents
+    /// Read file contents for the user, adding the file to Recents
     pub async fn read(&self, source_id: &str, path: &Path) -> Result<Vec<u8>> {
         let file_ops = self.get_file_ops(source_id)?;
-        file_ops.read(path).await
+        self.ensure_retrieved(source_id, path).await?;
+        let data = file_ops.read(path).await?;
+        self.record_recent(source_id, path).await;
+        Ok(data)
     }
     
     /// Write file contents
     pub async fn write(&self, source_id: &str, path: &Path, data: &[u8]) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.write(path, data).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        if self.quota(source_id).is_some() {
+            // Overwriting only adds the difference
+            let existing = file_ops.stat(path).await.map(|s| s.size).unwrap_or(0);
+            self.ensure_quota(source_id, (data.len() as u64).saturating_sub(existing)).await?;
+        }
+        let result = file_ops.write(path, data).await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        result
+    }
+    
+    /// Write file contents so a crash mid-write can't leave a torn file, on
+    /// sources whose adapter supports it (see `IFileOperations::write_atomic`)
+    pub async fn write_atomic(&self, source_id: &str, path: &Path, data: &[u8]) -> Result<()> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        if self.quota(source_id).is_some() {
+            let existing = file_ops.stat(path).await.map(|s| s.size).unwrap_or(0);
+            self.ensure_quota(source_id, (data.len() as u64).saturating_sub(existing)).await?;
+        }
+        let result = file_ops.write_atomic(path, data).await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        result
+    }
+    
+    /// Append to file by atomically rewriting it
+    pub async fn append_atomic(&self, source_id: &str, path: &Path, data: &[u8]) -> Result<()> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        self.ensure_quota(source_id, data.len() as u64).await?;
+        let result = file_ops.append_atomic(path, data).await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        result
     }
     
     /// Append to file
     pub async fn append(&self, source_id: &str, path: &Path, data: &[u8]) -> Result<()> {
-        let file_ops = self.get_file_ops(source_id)?;
-        file_ops.append(path, data).await
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        self.ensure_quota(source_id, data.len() as u64).await?;
+        let result = file_ops.append(path, data).await;
+        self.invalidate_usage(source_id);
+        self.invalidate_listing(source_id, path);
+        result
+    }
+    
+    /// Generate a time-limited URL for streaming an object directly from the bucket
+    pub async fn signed_url(&self, source_id: &str, path: &Path, expiry: std::time::Duration) -> Result<String> {
+        if expiry.is_zero() || expiry > MAX_SIGNED_URL_EXPIRY {
+            return Err(anyhow::anyhow!(
+                "Signed URL expiry must be between 1 and {} seconds",
+                MAX_SIGNED_URL_EXPIRY.as_secs()
+            ));
+        }
+        
+        let provider = {
+            let sources = self.sources.read();
+            let state = sources.get(source_id)
+                .ok_or_else(|| source_not_found(source_id))?;
+            state.signed_urls.clone()
+                .ok_or_else(|| anyhow::anyhow!("Source does not support signed URLs: {}", state.source.name))?
+        };
+        
+        provider.signed_url(path, expiry).await
+    }
+    
+    fn versioning(&self, source_id: &str) -> Result<Arc<dyn ObjectVersioning>> {
+        let sources = self.sources.read();
+        let state = sources.get(source_id)
+            .ok_or_else(|| source_not_found(source_id))?;
+        state.versions.clone()
+            .ok_or_else(|| VfsError::Unsupported(format!(
+                "Source does not keep object versions: {}", state.source.name
+            )).into())
+    }
+    
+    /// Stored versions of a file, newest first
+    pub async fn list_versions(&self, source_id: &str, path: &Path) -> Result<Vec<ObjectVersion>> {
+        self.versioning(source_id)?.list_versions(path).await
+    }
+    
+    /// Make an earlier version of a file the current one
+    pub async fn restore_version(&self, source_id: &str, path: &Path, version_id: &str) -> Result<()> {
+        let result = async {
+            self.ensure_writable(source_id)?;
+            self.versioning(source_id)?.restore_version(path, version_id).await
+        }.await;
+        
+        if result.is_ok() {
+            // The cached copy and listed size/date belong to the replaced version
+            if let Err(e) = self.cache.invalidate(&cache_key(source_id, path)).await {
+                warn!("Failed to drop cached copy of {:?}: {}", path, e);
+            }
+            self.listings.invalidate(source_id, path);
+            self.dir_sizes.invalidate(source_id, path);
+            self.tree_hashes.invalidate(source_id, path);
+        }
+        self.audit(AuditEntry::new(AuditOperation::RestoreVersion, source_id, &[path], &result).with_version(version_id)).await;
+        result
+    }
+    
+    // =========================================================================
+    // Editing Proxies
+    // =========================================================================
+    
+    /// Transcode each of `paths` to a proxy in a `Proxies/` folder beside it,
+    /// one job at a time on `queue`, and link proxy and original in metadata.
+    /// Files that fail are reported and the rest carry on.
+    pub async fn create_proxies(
+        &self,
+        source_id: &str,
+        paths: &[PathBuf],
+        quality: TranscodeQuality,
+        queue: &Arc<TranscodeQueue>,
+        progress: &ProxyProgress,
+    ) -> Result<ProxyReport> {
+        let file_ops = self.get_writable_file_ops(source_id)?;
+        let mut report = ProxyReport::default();
+        
+        for (done, original) in paths.iter().enumerate() {
+            match self.create_proxy(source_id, original, quality, queue, &file_ops).await {
+                Ok(proxy) => report.created.push(ProxyLink { original: original.clone(), proxy }),
+                Err(e) => {
+                    warn!("Failed to create proxy for {:?}: {}", original, e);
+                    report.failed.push(format!("{}: {}", original.display(), e));
+                }
+            }
+            progress(done + 1, paths.len());
+        }
+        
+        self.invalidate_usage(source_id);
+        Ok(report)
+    }
+    
+    async fn create_proxy(
+        &self,
+        source_id: &str,
+        original: &Path,
+        quality: TranscodeQuality,
+        queue: &Arc<TranscodeQueue>,
+        file_ops: &Arc<dyn IFileOperations>,
+    ) -> Result<PathBuf> {
+        if proxies::is_proxy(original) {
+            anyhow::bail!("Already a proxy");
+        }
+        
+        let local = match self.local_path(source_id, original) {
+            Some(local) => local,
+            None => self.hydrate_file(source_id, original).await?,
+        };
+        
+        let job = queue.enqueue(&local, StreamFormat::MP4, quality);
+        let job = queue.wait(&job.id).await
+            .ok_or_else(|| anyhow::anyhow!("Transcode job {} disappeared", job.id))?;
+        match job.status {
+            TranscodeStatus::Completed => {}
+            TranscodeStatus::Cancelled => anyhow::bail!("Transcode was cancelled"),
+            _ => anyhow::bail!(job.error.unwrap_or_else(|| "Transcode failed".to_string())),
+        }
+        
+        let proxy = proxies::proxy_path(original);
+        if let Some(folder) = proxy.parent() {
+            file_ops.mkdir_p(folder).await?;
+        }
+        let written = file_ops.write_stream(&proxy, proxies::read_local_chunks(job.output_path.clone())).await;
+        if let Some(scratch) = job.output_path.parent() {
+            if let Err(e) = tokio::fs::remove_dir_all(scratch).await {
+                debug!("Failed to remove transcode output {:?}: {}", scratch, e);
+            }
+        }
+        written?;
+        self.invalidate_listing(source_id, &proxy);
+        
+        let metadata = self.metadata.read().clone();
+        if let Some(metadata) = metadata {
+            metadata.link_proxy(source_id, original, &proxy).await?;
+        }
+        Ok(proxy)
+    }
+    
+    /// The proxy of `original`, if one exists: the one linked in metadata, or
+    /// failing that one in the conventional `Proxies/` location
+    pub async fn get_proxy(&self, source_id: &str, original: &Path) -> Result<Option<PathBuf>> {
+        let adapter = self.adapter(source_id)?;
+        
+        let metadata = self.metadata.read().clone();
+        let linked = match metadata {
+            Some(metadata) => metadata.get(source_id, original).await?.and_then(|m| m.proxy),
+            None => None,
+        };
+        
+        for candidate in linked.into_iter().chain(std::iter::once(proxies::proxy_path(original))) {
+            if adapter.exists(&candidate).await? {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
     }
     
     // =========================================================================
     // Cross-Storage Operations
     // =========================================================================
     
+    /// Cap the combined bandwidth of all cross-storage transfers (`None` = unlimited)
+    pub fn set_global_transfer_limit(&self, bytes_per_sec: Option<u64>) {
+        self.transfer_throttle.set_limit(bytes_per_sec);
+        info!("Global transfer limit set to {:?} bytes/s", self.transfer_throttle.limit());
+    }
+    
+    /// Current global transfer limit in bytes per second
+    pub fn global_transfer_limit(&self) -> Option<u64> {
+        self.transfer_throttle.limit()
+    }
+    
     /// Copy files from one storage source to another
     pub async fn copy_to_source(
         &self,
@@ -461,44 +3651,189 @@ impl VfsService {
         from_path: &Path,
         to_source_id: &str,
         to_path: &Path,
+    ) -> Result<u64> {
+        self.copy_to_source_with(from_source_id, from_path, to_source_id, to_path, &CrossStorageOptions::copy()).await
+    }
+    
+    /// Copy files from one storage source to another with explicit options
+    pub async fn copy_to_source_with(
+        &self,
+        from_source_id: &str,
+        from_path: &Path,
+        to_source_id: &str,
+        to_path: &Path,
+        options: &CrossStorageOptions,
+    ) -> Result<u64> {
+        self.copy_to_source_with_progress(from_source_id, from_path, to_source_id, to_path, options, None).await
+    }
+    
+    /// `copy_to_source_with`, reporting bytes, rate and ETA to `progress`
+    /// while a single file is copied
+    pub async fn copy_to_source_with_progress(
+        &self,
+        from_source_id: &str,
+        from_path: &Path,
+        to_source_id: &str,
+        to_path: &Path,
+        options: &CrossStorageOptions,
+        progress: Option<Arc<CopyProgressFn>>,
+    ) -> Result<u64> {
+        let work = async {
+            self.copy_to_source_inner(from_source_id, from_path, to_source_id, to_path, options, progress).await
+        };
+        let bytes = self.transfers.run(&[from_source_id, to_source_id], format!("Copy {:?}", from_path), work).await?;
+        
+        self.record_destination(to_source_id, to_path).await;
+        
+        Ok(bytes)
+    }
+    
+    /// Offer `folder` in the copy/move dialogs' recent destinations
+    async fn record_destination(&self, source_id: &str, folder: &Path) {
+        if let Err(e) = self.recent_destinations.record(source_id, folder).await {
+            warn!("Failed to record recent destination {:?}: {}", folder, e);
+        }
+    }
+    
+    async fn copy_to_source_inner(
+        &self,
+        from_source_id: &str,
+        from_path: &Path,
+        to_source_id: &str,
+        to_path: &Path,
+        options: &CrossStorageOptions,
+        progress: Option<Arc<CopyProgressFn>>,
     ) -> Result<u64> {
         let from_file_ops = self.get_file_ops(from_source_id)?;
-        let to_file_ops = self.get_file_ops(to_source_id)?;
+        let to_file_ops = self.get_writable_file_ops(to_source_id)?;
         
         // Get source file info
         let stat = from_file_ops.stat(from_path).await?;
         
         if stat.is_dir {
             // Recursive directory copy
-            self.copy_dir_to_source(from_source_id, from_path, to_source_id, to_path).await
+            self.copy_dir_to_source(from_source_id, from_path, to_source_id, to_path, options).await
         } else {
             // Single file copy
-            let data = from_file_ops.read(from_path).await?;
             let file_name = from_path.file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_else(|| "file".to_string());
             let dest_path = to_path.join(&file_name);
             
-            to_file_ops.write(&dest_path, &data).await?;
+            self.ensure_retrieved(from_source_id, from_path).await?;
+            self.ensure_local_space(to_source_id, to_path, stat.size)?;
+            self.ensure_quota(to_source_id, stat.size).await?;
+            
+            let copier = self.server_side_copier(from_source_id, to_source_id);
+            let result = match copy_server_side(copier.as_ref(), from_path, &dest_path, stat.size).await {
+                Ok(true) => {
+                    // Server-side copies finish in one request
+                    if let Some(progress) = &progress {
+                        progress(ProgressMeter::new(stat.size).advance(stat.size));
+                    }
+                    Ok(stat.size)
+                }
+                Ok(false) => self.transfer_file(
+                    &from_file_ops, from_path, &to_file_ops, &dest_path, stat.size, options, progress,
+                ).await,
+                Err(e) => Err(e),
+            };
+            self.invalidate_usage(to_source_id);
+            self.invalidate_listing(to_source_id, &dest_path);
+            let bytes = result?;
             
             info!("Copied {} to {} ({}:{:?})", 
                 from_path.display(), 
                 to_source_id, 
                 dest_path.display(),
-                stat.size
+                bytes
             );
             
-            Ok(stat.size)
+            Ok(bytes)
+        }
+    }
+    
+    /// Copy one file into several `(source_id, folder)` destinations at once,
+    /// reading the source a single time. Each destination succeeds or fails on
+    /// its own. Folders are copied to each destination in turn instead.
+    pub async fn copy_to_multiple(
+        &self,
+        from_source_id: &str,
+        from_path: &Path,
+        destinations: &[(String, PathBuf)],
+    ) -> Result<Vec<FanOutResult>> {
+        let from_file_ops = self.get_file_ops(from_source_id)?;
+        let stat = from_file_ops.stat(from_path).await?;
+        
+        if stat.is_dir {
+            let mut results = Vec::with_capacity(destinations.len());
+            for (to_source_id, to_path) in destinations {
+                let result = self.copy_to_source(from_source_id, from_path, to_source_id, to_path).await;
+                results.push(FanOutResult::new(to_source_id, to_path, &result));
+            }
+            return Ok(results);
         }
+        self.ensure_retrieved(from_source_id, from_path).await?;
+        
+        let file_name = from_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "file".to_string());
+        
+        // Destinations that can't take the file are reported without being written
+        let mut results: Vec<FanOutResult> = destinations.iter()
+            .map(|(to_source_id, to_path)| FanOutResult::new(to_source_id, to_path, &Ok(0)))
+            .collect();
+        let mut targets = Vec::new();
+        for (index, (to_source_id, to_path)) in destinations.iter().enumerate() {
+            let prepared = async {
+                let to_file_ops = self.get_writable_file_ops(to_source_id)?;
+                self.ensure_local_space(to_source_id, to_path, stat.size)?;
+                self.ensure_quota(to_source_id, stat.size).await?;
+                Ok::<_, anyhow::Error>(to_file_ops)
+            }.await;
+            match prepared {
+                Ok(to_file_ops) => targets.push((index, to_file_ops, to_path.join(&file_name))),
+                Err(e) => results[index] = FanOutResult::new(to_source_id, to_path, &Err(e)),
+            }
+        }
+        
+        let source_ids: Vec<&str> = std::iter::once(from_source_id)
+            .chain(targets.iter().map(|(index, _, _)| destinations[*index].0.as_str()))
+            .collect();
+        let writers: Vec<_> = targets.iter().map(|(_, to_file_ops, dest_path)| {
+            let (to_file_ops, dest_path) = (to_file_ops.clone(), dest_path.clone());
+            move |chunks: futures::stream::BoxStream<'static, Result<Vec<u8>>>| async move {
+                to_file_ops.write_stream(&dest_path, chunks).await
+            }
+        }).collect();
+        let chunks = self.source_chunks(&from_file_ops, from_path, stat.size, &CrossStorageOptions::copy());
+        let work = async { Ok(fan_out::tee(chunks, writers).await) };
+        let written = self.transfers.run(&source_ids, format!("Copy {:?}", from_path), work).await?;
+        
+        for ((index, _, dest_path), result) in targets.iter().zip(written) {
+            let (to_source_id, to_path) = &destinations[*index];
+            self.invalidate_usage(to_source_id);
+            self.invalidate_listing(to_source_id, dest_path);
+            if result.is_ok() {
+                self.record_destination(to_source_id, to_path).await;
+            }
+            results[*index] = FanOutResult::new(to_source_id, to_path, &result);
+        }
+        
+        Ok(results)
     }
     
-    /// Copy directory recursively between sources
+    /// Copy directory recursively between sources.
+    ///
+    /// The whole tree is created at the destination first (parents before
+    /// children), then files are copied with bounded concurrency.
     async fn copy_dir_to_source(
         &self,
         from_source_id: &str,
         from_path: &Path,
         to_source_id: &str,
         to_path: &Path,
+        options: &CrossStorageOptions,
     ) -> Result<u64> {
         let from_file_ops = self.get_file_ops(from_source_id)?;
         let to_file_ops = self.get_file_ops(to_source_id)?;
@@ -506,35 +3841,278 @@ impl VfsService {
         let dir_name = from_path.file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "dir".to_string());
-        let dest_dir = to_path.join(&dir_name);
+        let dest_root = to_path.join(&dir_name);
+        let ignore = self.ignore_patterns();
+        
+        // Walk breadth-first so every directory is listed after its parent
+        let mut dirs = vec![(from_path.to_path_buf(), dest_root)];
+        let mut files = Vec::new();
+        let mut next = 0;
+        while next < dirs.len() {
+            let (src_dir, dest_dir) = dirs[next].clone();
+            next += 1;
+            
+            for entry in from_file_ops.list(&src_dir).await? {
+                let src = src_dir.join(&entry.name);
+                let dest = dest_dir.join(&entry.name);
+                if ignore.matches(src.strip_prefix(from_path).unwrap_or(&src)) {
+                    debug!("Skipping ignored {:?}", src);
+                    continue;
+                }
+                if entry.is_dir {
+                    dirs.push((src, dest));
+                } else {
+                    files.push((src, dest, entry.size));
+                }
+            }
+        }
         
-        // Create destination directory
-        to_file_ops.mkdir_p(&dest_dir).await?;
+        // Refuse before creating anything if the whole tree won't fit
+        let dest_paths = dirs.iter().map(|(_, dest)| dest.as_path())
+            .chain(files.iter().map(|(_, dest, _)| dest.as_path()));
+        self.check_case_collisions(to_source_id, dest_paths, options).await?;
         
-        let mut total_bytes = 0u64;
+        let total: u64 = files.iter().map(|(_, _, size)| size).sum();
+        self.ensure_local_space(to_source_id, to_path, total)?;
+        self.ensure_quota(to_source_id, total).await?;
         
-        // List source directory
-        let entries = from_file_ops.list(from_path).await?;
+        for (_, dest_dir) in &dirs {
+            to_file_ops.mkdir_p(dest_dir).await?;
+        }
         
-        for entry in entries {
-            let entry_path = from_path.join(&entry.name);
-            
-            if entry.is_dir {
-                total_bytes += Box::pin(self.copy_dir_to_source(
-                    from_source_id,
-                    &entry_path,
-                    to_source_id,
-                    &dest_dir,
-                )).await?;
-            } else {
-                let data = from_file_ops.read(&entry_path).await?;
-                let dest_file = dest_dir.join(&entry.name);
-                to_file_ops.write(&dest_file, &data).await?;
-                total_bytes += entry.size;
+        let concurrency = options.concurrency.unwrap_or(DEFAULT_COPY_CONCURRENCY);
+        debug!("Copying {} files in {} directories ({} in flight)", files.len(), dirs.len(), concurrency);
+        
+        let copier = self.server_side_copier(from_source_id, to_source_id);
+        let jobs = files.iter().map(|(src, dest, size)| {
+            let copier = copier.as_ref();
+            let (from_file_ops, to_file_ops) = (&from_file_ops, &to_file_ops);
+            async move {
+                self.ensure_retrieved(from_source_id, src).await?;
+                if copy_server_side(copier, src, dest, *size).await? {
+                    return Ok(*size);
+                }
+                self.transfer_file(from_file_ops, src, to_file_ops, dest, *size, options, None).await
+            }
+        });
+        
+        let result = run_bounded(jobs, concurrency).await;
+        self.invalidate_usage(to_source_id);
+        self.invalidate_listing(to_source_id, to_path);
+        result
+    }
+    
+    /// Warn about destination paths that differ only in case when the
+    /// destination ignores case, or refuse them with `fail_on_case_collision`
+    async fn check_case_collisions<'a>(
+        &self,
+        to_source_id: &str,
+        dest_paths: impl Iterator<Item = &'a Path>,
+        options: &CrossStorageOptions,
+    ) -> Result<()> {
+        let Some(case_sensitive) = self.probe_case_sensitivity(to_source_id).await else {
+            return Ok(());
+        };
+        let collisions = case_collisions(dest_paths, case_sensitive);
+        if collisions.is_empty() {
+            return Ok(());
+        }
+        
+        let groups: Vec<String> = collisions.iter()
+            .map(|group| group.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", "))
+            .collect();
+        if options.fail_on_case_collision {
+            return Err(VfsError::AlreadyExists(format!(
+                "These names differ only in case and would be merged on a case-insensitive destination: {}",
+                groups.join("; ")
+            )).into());
+        }
+        for group in &groups {
+            warn!("Names differ only in case and will be merged on {}: {}", to_source_id, group);
+        }
+        Ok(())
+    }
+    
+    /// Whether the source tells names apart by case, as far as can be told
+    /// without writing to it: object stores always do, and mounted volumes
+    /// are asked through their filesystem. `None` when neither says.
+    pub async fn case_sensitivity(&self, source_id: &str) -> Option<bool> {
+        if let Some(known) = self.case_probes.read().get(source_id) {
+            return Some(*known);
+        }
+        
+        let source = self.get_source(source_id)?;
+        let sensitive = if source.source_type.category() == StorageCategory::Cloud {
+            true
+        } else {
+            // A stalled network mount must not hold up the runtime
+            let mount_point = source.mount_point?;
+            tokio::task::spawn_blocking(move || crate::vfs::platform::volume_case_sensitivity(&mount_point))
+                .await
+                .ok()
+                .flatten()?
+        };
+        
+        self.case_probes.write().insert(source_id.to_string(), sensitive);
+        Some(sensitive)
+    }
+    
+    /// `case_sensitivity`, falling back to writing a probe file at the root
+    /// when the filesystem can't say. Only used right before a copy writes to
+    /// the source anyway. `None` when that isn't possible either (read-only,
+    /// or the probe failed).
+    async fn probe_case_sensitivity(&self, source_id: &str) -> Option<bool> {
+        if let Some(known) = self.case_sensitivity(source_id).await {
+            return Some(known);
+        }
+        
+        let source = self.get_source(source_id)?;
+        if source.config.read_only {
+            return None;
+        }
+        let file_ops = self.get_file_ops(source_id).ok()?;
+        match case_sensitivity::probe_case_sensitivity(file_ops.as_ref(), Path::new("/")).await {
+            Ok(sensitive) => {
+                self.case_probes.write().insert(source_id.to_string(), sensitive);
+                Some(sensitive)
+            }
+            Err(e) => {
+                debug!("Couldn't probe case sensitivity of {}: {:#}", source.name, e);
+                None
+            }
+        }
+    }
+    
+    /// Groups of `incoming` paths, about to be copied into `to_path`, that
+    /// would land on the same file on a case-insensitive destination - with
+    /// each other, or with an entry already there under a different case.
+    /// Empty when the destination tells case apart.
+    pub async fn incoming_case_collisions(
+        &self,
+        to_source_id: &str,
+        to_path: &Path,
+        incoming: &[PathBuf],
+    ) -> Result<Vec<Vec<PathBuf>>> {
+        if self.probe_case_sensitivity(to_source_id).await != Some(false) {
+            return Ok(Vec::new());
+        }
+        
+        // A folder that doesn't exist yet has nothing to collide with
+        let existing: Vec<PathBuf> = match self.get_file_ops(to_source_id)?.list(to_path).await {
+            Ok(entries) => entries.into_iter()
+                .map(|entry| to_path.join(entry.name))
+                // Same name, same case: replacing it is what the copy is for
+                .filter(|path| !incoming.contains(path))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        
+        Ok(case_collisions(incoming.iter().chain(&existing).map(PathBuf::as_path), false))
+    }
+    
+    /// The destination's server-side copier and the source bucket, when both
+    /// sources are buckets in the same account - objects then go straight
+    /// from one to the other instead of through the app
+    fn server_side_copier(&self, from_source_id: &str, to_source_id: &str) -> Option<(Arc<dyn ServerSideCopy>, String)> {
+        let sources = self.sources.read();
+        let from = sources.get(from_source_id)?.server_copy.clone()?;
+        let to = sources.get(to_source_id)?.server_copy.clone()?;
+        let scope = from.copy_scope()?;
+        (to.copy_scope() == Some(scope)).then(|| (to, from.bucket().to_string()))
+    }
+    
+    /// `size` bytes of `from_path`, read in chunks and paced by the global and
+    /// per-transfer bandwidth limits
+    fn source_chunks(
+        &self,
+        from_file_ops: &Arc<dyn IFileOperations>,
+        from_path: &Path,
+        size: u64,
+        options: &CrossStorageOptions,
+    ) -> futures::stream::BoxStream<'static, Result<Vec<u8>>> {
+        let global = self.transfer_throttle.clone();
+        let per_transfer = options.rate_limit_bytes_per_sec
+            .map(|limit| Arc::new(TransferThrottle::new(Some(limit))));
+        let chunk_size = match &per_transfer {
+            Some(throttle) => throttle.chunk_size().min(global.chunk_size()),
+            None => global.chunk_size(),
+        };
+        
+        let from_file_ops = from_file_ops.clone();
+        let from_path = from_path.to_path_buf();
+        
+        futures::stream::try_unfold(0u64, move |offset| {
+            let from_file_ops = from_file_ops.clone();
+            let from_path = from_path.clone();
+            let global = global.clone();
+            let per_transfer = per_transfer.clone();
+            
+            async move {
+                if offset >= size {
+                    return Ok(None);
+                }
+                
+                let chunk = from_file_ops.read_range(&from_path, offset, chunk_size.min(size - offset)).await?;
+                if chunk.is_empty() {
+                    anyhow::bail!("Unexpected end of {:?} at byte {} of {}", from_path, offset, size);
+                }
+                
+                let len = chunk.len() as u64;
+                if let Some(throttle) = &per_transfer {
+                    throttle.acquire(len).await;
+                }
+                global.acquire(len).await;
+                
+                Ok(Some((chunk, offset + len)))
             }
+        }).boxed()
+    }
+    
+    /// Stream one file between sources in chunks, sleeping between chunks as
+    /// needed to honour the global and per-transfer bandwidth limits
+    #[allow(clippy::too_many_arguments)]
+    async fn transfer_file(
+        &self,
+        from_file_ops: &Arc<dyn IFileOperations>,
+        from_path: &Path,
+        to_file_ops: &Arc<dyn IFileOperations>,
+        dest_path: &Path,
+        size: u64,
+        options: &CrossStorageOptions,
+        progress: Option<Arc<CopyProgressFn>>,
+    ) -> Result<u64> {
+        let mut chunks = self.source_chunks(from_file_ops, from_path, size, options);
+        if let Some(progress) = progress {
+            chunks = copy_progress::report_chunks(chunks, size, progress);
+        }
+        
+        if !options.verify {
+            return to_file_ops.write_stream(dest_path, chunks).await;
+        }
+        
+        // The source is hashed as it is read; the destination has to be read
+        // back, since only what actually landed there proves the copy
+        let (chunks, source_digest) = hash_chunks(chunks, HashAlgorithm::XxHash);
+        let written = to_file_ops.write_stream(dest_path, chunks).await?;
+        let source_hash = source_digest.finish();
+        
+        let landed = to_file_ops.stat(dest_path).await?.size;
+        if landed != written {
+            anyhow::bail!(
+                "Verification failed for {:?}: wrote {} bytes but the destination has {}",
+                dest_path, written, landed
+            );
+        }
+        let dest_hash = hash_written(to_file_ops, dest_path, landed, HashAlgorithm::XxHash).await?;
+        if source_hash != dest_hash {
+            anyhow::bail!(
+                "Verification failed for {:?}: source hash {}, destination hash {}",
+                dest_path, source_hash, dest_hash
+            );
         }
+        debug!("Verified {:?} ({} bytes, xxhash {})", dest_path, written, dest_hash);
         
-        Ok(total_bytes)
+        Ok(written)
     }
     
     /// Move files from one storage source to another (copy + delete)
@@ -545,21 +4123,50 @@ impl VfsService {
         to_source_id: &str,
         to_path: &Path,
     ) -> Result<u64> {
-        // Copy first
-        let bytes = self.copy_to_source(from_source_id, from_path, to_source_id, to_path).await?;
+        self.move_to_source_with(from_source_id, from_path, to_source_id, to_path, &CrossStorageOptions::r#move()).await
+    }
+    
+    /// Move files from one storage source to another with explicit options
+    pub async fn move_to_source_with(
+        &self,
+        from_source_id: &str,
+        from_path: &Path,
+        to_source_id: &str,
+        to_path: &Path,
+        options: &CrossStorageOptions,
+    ) -> Result<u64> {
+        let result = async {
+            // The source is deleted afterwards, so refuse before copying anything
+            self.ensure_writable(from_source_id)?;
+            
+            // Copy first
+            let bytes = self.copy_to_source_with(from_source_id, from_path, to_source_id, to_path, options).await?;
+            
+            // Delete source
+            let from_file_ops = self.get_file_ops(from_source_id)?;
+            let removed = from_file_ops.rm_rf(from_path).await;
+            self.invalidate_usage(from_source_id);
+            self.invalidate_listing(from_source_id, from_path);
+            removed?;
+            
+            Ok::<_, anyhow::Error>(bytes)
+        }.await;
         
-        // Delete source
-        let from_file_ops = self.get_file_ops(from_source_id)?;
-        from_file_ops.rm_rf(from_path).await?;
+        self.audit(
+            AuditEntry::new(AuditOperation::CrossStorageMove, from_source_id, &[from_path, to_path], &result)
+                .with_destination(to_source_id),
+        ).await;
         
-        info!("Moved {} from {} to {} ({} bytes)", 
-            from_path.display(), 
-            from_source_id, 
-            to_source_id,
-            bytes
-        );
+        if let Ok(bytes) = &result {
+            info!("Moved {} from {} to {} ({} bytes)", 
+                from_path.display(), 
+                from_source_id, 
+                to_source_id,
+                bytes
+            );
+        }
         
-        Ok(bytes)
+        result
     }
     
     /// Get list of available storage sources for transfer
@@ -579,8 +4186,433 @@ impl VfsService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use async_trait::async_trait;
     use tempfile::TempDir;
     
+    use crate::vfs::adapters::JsonMetadataStore;
+    
+    /// Object-storage stand-in: one file whose tier can be changed
+    struct TieredAdapter {
+        data: Vec<u8>,
+        tier: RwLock<StorageTier>,
+        restored: RwLock<bool>,
+    }
+    
+    #[async_trait]
+    impl StorageAdapter for TieredAdapter {
+        fn storage_type(&self) -> StorageSourceType {
+            StorageSourceType::S3
+        }
+        
+        fn name(&self) -> &str {
+            "tiered"
+        }
+        
+        async fn test_connection(&self) -> Result<bool> {
+            Ok(true)
+        }
+        
+        async fn list_files(&self, _path: &Path) -> Result<Vec<VirtualFile>> {
+            Ok(vec![])
+        }
+        
+        async fn read_file(&self, _path: &Path) -> Result<Vec<u8>> {
+            Ok(self.data.clone())
+        }
+        
+        async fn read_file_range(&self, _path: &Path, offset: u64, length: u64) -> Result<Vec<u8>> {
+            let end = (offset + length).min(self.data.len() as u64) as usize;
+            Ok(self.data[offset as usize..end].to_vec())
+        }
+        
+        async fn write_file(&self, _path: &Path, _data: &[u8]) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn get_metadata(&self, path: &Path) -> Result<VirtualFile> {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let mut file = VirtualFile::new(name, path.to_path_buf(), self.data.len() as u64, false);
+            file.tier_status.current_tier = *self.tier.read();
+            Ok(file)
+        }
+        
+        async fn exists(&self, _path: &Path) -> Result<bool> {
+            Ok(true)
+        }
+        
+        async fn delete(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn create_dir(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn file_size(&self, _path: &Path) -> Result<u64> {
+            Ok(self.data.len() as u64)
+        }
+        
+        fn supports_tier_changes(&self) -> bool {
+            true
+        }
+        
+        async fn change_tier(&self, path: &Path, tier: StorageTier) -> Result<()> {
+            if self.pending_retrieval(path).await?.is_some() && tier != StorageTier::Archive {
+                return Err(ArchiveNotRetrievedError {
+                    path: path.display().to_string(),
+                    estimated_retrieval_secs: StorageTier::Archive.retrieval_secs(),
+                }.into());
+            }
+            *self.tier.write() = tier;
+            *self.restored.write() = false;
+            Ok(())
+        }
+        
+        async fn pending_retrieval(&self, _path: &Path) -> Result<Option<u64>> {
+            let tier = *self.tier.read();
+            Ok((tier == StorageTier::Archive && !*self.restored.read()).then(|| tier.retrieval_secs()))
+        }
+        
+        async fn initiate_retrieval(&self, _path: &Path, _tier: RetrievalTier) -> Result<()> {
+            // Restores here finish as soon as the test says so
+            Ok(())
+        }
+    }
+    
+    /// Mount `adapter` as source `s3`
+    fn insert_tiered(service: &VfsService, adapter: Arc<TieredAdapter>) {
+        insert_tiered_as(service, "s3", adapter);
+    }
+    
+    fn insert_tiered_as(service: &VfsService, id: &str, adapter: Arc<TieredAdapter>) {
+        let source = StorageSource {
+            id: id.to_string(),
+            name: "Archive".to_string(),
+            source_type: StorageSourceType::S3,
+            status: ConnectionStatus::Connected,
+            mounted: true,
+            mount_point: None,
+            config: StorageConfig::default(),
+        };
+        service.sources.write().insert(source.id.clone(), StorageSourceState {
+            source,
+            adapter,
+            file_ops: None,
+            signed_urls: None,
+            versions: None,
+            server_copy: None,
+        });
+    }
+    
+    #[tokio::test]
+    async fn test_resolve_path_local_cached_and_remote() {
+        let cache_dir = TempDir::new().unwrap();
+        let local_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        let local = service.add_local_source("Local".to_string(), local_dir.path().to_path_buf()).await.unwrap();
+        insert_tiered(&service, Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Cold),
+            restored: RwLock::new(false),
+        }));
+        
+        let resolved = service.resolve_path(&local.id, Path::new("/edit.prproj")).await.unwrap();
+        assert_eq!(resolved, ResolvedPath::Local { real_path: local_dir.path().join("edit.prproj") });
+        
+        let path = Path::new("/A001.mov");
+        assert_eq!(service.resolve_path("s3", path).await.unwrap(), ResolvedPath::Remote);
+        
+        let cache_path = service.hydrate_file("s3", path).await.unwrap();
+        assert_eq!(service.resolve_path("s3", path).await.unwrap(), ResolvedPath::Cached { cache_path });
+        
+        // The same path on another bucket is a different file
+        insert_tiered_as(&service, "s3-b", Arc::new(TieredAdapter {
+            data: b"other frames".to_vec(),
+            tier: RwLock::new(StorageTier::Cold),
+            restored: RwLock::new(false),
+        }));
+        assert_eq!(service.resolve_path("s3-b", path).await.unwrap(), ResolvedPath::Remote);
+        assert_eq!(service.read_file("s3-b", path).await.unwrap(), b"other frames");
+    }
+    
+    #[tokio::test]
+    async fn test_tier_history_follows_hydrate_and_archive() {
+        let cache_dir = TempDir::new().unwrap();
+        let metadata_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        service.set_metadata_store(Arc::new(
+            JsonMetadataStore::new(metadata_dir.path().join("metadata.json")).await.unwrap()
+        ));
+        
+        let adapter = Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Cold),
+            restored: RwLock::new(false),
+        });
+        insert_tiered(&service, adapter.clone());
+        
+        let path = Path::new("/A001.mov");
+        assert!(service.tier_history("s3", path).await.unwrap().is_empty());
+        
+        // Cold -> hot by hydrating, then back to archive
+        service.hydrate_file("s3", path).await.unwrap();
+        let report = service.change_tier("s3", &[path.to_path_buf()], StorageTier::Archive).await.unwrap();
+        assert_eq!(report.changed.len(), 1);
+        assert_eq!(*adapter.tier.read(), StorageTier::Archive);
+        
+        let history = service.tier_history("s3", path).await.unwrap();
+        let tiers: Vec<StorageTier> = history.iter().map(|t| t.tier).collect();
+        assert_eq!(tiers, vec![StorageTier::Hot, StorageTier::Archive]);
+        assert!(history[0].timestamp <= history[1].timestamp);
+        
+        // A read-only source keeps its storage classes
+        service.set_read_only("s3", true).unwrap();
+        let err = service.change_tier("s3", &[path.to_path_buf()], StorageTier::Hot).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyError>().is_some(), "{}", err);
+        assert_eq!(*adapter.tier.read(), StorageTier::Archive);
+    }
+    
+    #[tokio::test]
+    async fn test_restore_version_of_read_only_source_is_refused_and_audited() {
+        use crate::vfs::infrastructure::AuditOperation;
+        
+        let service = VfsService::new().await.unwrap();
+        insert_tiered(&service, Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Hot),
+            restored: RwLock::new(false),
+        }));
+        service.set_read_only("s3", true).unwrap();
+        
+        let err = service.restore_version("s3", Path::new("/A001.mov"), "v1").await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyError>().is_some(), "{}", err);
+        
+        let entries = service.export_audit_log(None, None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].operation, AuditOperation::RestoreVersion);
+        assert_eq!(entries[0].version_id.as_deref(), Some("v1"));
+        assert!(!entries[0].success);
+    }
+    
+    #[tokio::test]
+    async fn test_cached_cold_file_is_effectively_hot() {
+        let cache_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        insert_tiered(&service, Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Cold),
+            restored: RwLock::new(false),
+        }));
+        let (cached, uncached) = (Path::new("/A001.mov"), Path::new("/A002.mov"));
+        
+        service.hydrate_file("s3", cached).await.unwrap();
+        assert_eq!(service.effective_tier("s3", cached).await.unwrap(), StorageTier::Hot);
+        assert_eq!(service.effective_tier("s3", uncached).await.unwrap(), StorageTier::Cold);
+        
+        // A change under way shows where the file is going
+        assert_eq!(reconcile_tier(StorageTier::Cold, Some(StorageTier::Archive), false), StorageTier::Archive);
+        assert_eq!(reconcile_tier(StorageTier::Cold, Some(StorageTier::Archive), true), StorageTier::Hot);
+    }
+    
+    #[tokio::test]
+    async fn test_archived_file_must_be_retrieved_first() {
+        let cache_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        let adapter = Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Archive),
+            restored: RwLock::new(false),
+        });
+        insert_tiered(&service, adapter.clone());
+        let path = Path::new("/A001.mov");
+        
+        for err in [
+            service.read_file("s3", path).await.unwrap_err(),
+            service.hydrate_file("s3", path).await.unwrap_err(),
+        ] {
+            match VfsError::from(err) {
+                VfsError::ArchiveNotRetrieved { estimated_retrieval_secs, .. } => assert!(estimated_retrieval_secs > 0),
+                other => panic!("expected ARCHIVE_NOT_RETRIEVED, got {:?}", other),
+            }
+        }
+        
+        service.initiate_retrieval("s3", path, RetrievalTier::Expedited).await.unwrap();
+        *adapter.restored.write() = true;
+        assert_eq!(service.read_file("s3", path).await.unwrap(), b"frames");
+    }
+    
+    #[tokio::test]
+    async fn test_tier_change_of_archived_file_waits_for_its_restore() {
+        let cache_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        let adapter = Arc::new(TieredAdapter {
+            data: b"frames".to_vec(),
+            tier: RwLock::new(StorageTier::Archive),
+            restored: RwLock::new(false),
+        });
+        insert_tiered(&service, adapter.clone());
+        let path = Path::new("/A001.mov");
+        
+        let report = service.change_tier("s3", &[path.to_path_buf()], StorageTier::Cold).await.unwrap();
+        assert_eq!(report.restoring, vec![path.to_path_buf()]);
+        assert!(report.changed.is_empty() && report.failed.is_empty());
+        
+        // Shown where it's going for as long as the restore takes
+        assert_eq!(service.effective_tier("s3", path).await.unwrap(), StorageTier::Cold);
+        assert_eq!(service.finish_restored_tier_changes().await.restoring, vec![path.to_path_buf()]);
+        assert_eq!(*adapter.tier.read(), StorageTier::Archive);
+        
+        *adapter.restored.write() = true;
+        assert_eq!(service.finish_restored_tier_changes().await.changed, vec![path.to_path_buf()]);
+        assert_eq!(*adapter.tier.read(), StorageTier::Cold);
+        assert_eq!(service.finish_restored_tier_changes().await, TierChangeReport::default());
+        assert_eq!(service.effective_tier("s3", path).await.unwrap(), StorageTier::Cold);
+    }
+    
+    /// A folder renamed in the same batch as a file inside it moves that
+    /// file's temporary name away, so the second phase fails partway
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_batch_rename_is_rolled_back() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("2")).unwrap();
+        std::fs::write(dir.path().join("2/x.mov"), "inside").unwrap();
+        std::fs::write(dir.path().join("1"), "one").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Ingest".to_string(), dir.path().to_path_buf()).await.unwrap();
+        
+        let paths: Vec<PathBuf> = ["/2/x.mov", "/1", "/2"].iter().map(PathBuf::from).collect();
+        assert!(service.batch_rename(&source.id, &paths, "{index}", 1).await.is_err());
+        
+        assert_eq!(std::fs::read_to_string(dir.path().join("1")).unwrap(), "one");
+        assert_eq!(std::fs::read_to_string(dir.path().join("2/x.mov")).unwrap(), "inside");
+        let leftovers = |path: &Path| std::fs::read_dir(path).unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with(".ursly-rename-"))
+            .count();
+        assert_eq!(leftovers(dir.path()) + leftovers(&dir.path().join("2")), 0);
+    }
+    
+    /// Remote source whose connectivity the test switches
+    #[derive(Default)]
+    struct ReachableAdapter {
+        online: std::sync::atomic::AtomicBool,
+        checks: std::sync::atomic::AtomicUsize,
+    }
+    
+    #[async_trait]
+    impl StorageAdapter for ReachableAdapter {
+        fn storage_type(&self) -> StorageSourceType {
+            StorageSourceType::Smb
+        }
+        
+        fn name(&self) -> &str {
+            "reachable"
+        }
+        
+        async fn test_connection(&self) -> Result<bool> {
+            self.checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.online.load(std::sync::atomic::Ordering::SeqCst))
+        }
+        
+        async fn list_files(&self, _path: &Path) -> Result<Vec<VirtualFile>> {
+            Ok(vec![])
+        }
+        
+        async fn read_file(&self, _path: &Path) -> Result<Vec<u8>> {
+            Ok(vec![])
+        }
+        
+        async fn read_file_range(&self, _path: &Path, _offset: u64, _length: u64) -> Result<Vec<u8>> {
+            Ok(vec![])
+        }
+        
+        async fn write_file(&self, _path: &Path, _data: &[u8]) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn get_metadata(&self, _path: &Path) -> Result<VirtualFile> {
+            anyhow::bail!("not needed")
+        }
+        
+        async fn exists(&self, _path: &Path) -> Result<bool> {
+            Ok(false)
+        }
+        
+        async fn delete(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn create_dir(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn file_size(&self, _path: &Path) -> Result<u64> {
+            Ok(0)
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_remount_all_remote_rechecks_connections() {
+        let service = VfsService::new().await.unwrap();
+        let local_dir = TempDir::new().unwrap();
+        let local = service.add_local_source("Local".to_string(), local_dir.path().to_path_buf()).await.unwrap();
+        
+        let nas = Arc::new(ReachableAdapter::default());
+        let bucket = Arc::new(ReachableAdapter::default());
+        nas.online.store(true, std::sync::atomic::Ordering::SeqCst);
+        for (id, source_type, adapter) in [("nas", StorageSourceType::Smb, nas.clone()), ("bucket", StorageSourceType::S3, bucket.clone())] {
+            let source = StorageSource {
+                id: id.to_string(),
+                name: id.to_uppercase(),
+                source_type,
+                status: ConnectionStatus::Connected,
+                mounted: true,
+                mount_point: None,
+                config: StorageConfig::default(),
+            };
+            service.sources.write().insert(source.id.clone(), StorageSourceState {
+                source,
+                adapter,
+                file_ops: None,
+                signed_urls: None,
+                versions: None,
+                server_copy: None,
+            });
+        }
+        
+        let mut results = service.remount_all_remote().await;
+        results.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+        
+        assert_eq!(results.len(), 2, "local sources are not re-checked");
+        assert_eq!((results[0].source_id.as_str(), results[0].ok), ("bucket", false));
+        assert_eq!((results[1].source_id.as_str(), results[1].ok), ("nas", true));
+        assert_eq!(nas.checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(bucket.checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(matches!(service.get_source("bucket").unwrap().status, ConnectionStatus::Error(_)));
+        assert_eq!(service.get_source(&local.id).unwrap().status, ConnectionStatus::Connected);
+        
+        // Once the bucket is back, the next pass clears its error
+        bucket.online.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(service.remount_all_remote().await.iter().all(|r| r.ok));
+        assert_eq!(service.get_source("bucket").unwrap().status, ConnectionStatus::Connected);
+    }
+    
     #[tokio::test]
     async fn test_vfs_service_local_source() {
         let temp_dir = TempDir::new().unwrap();
@@ -600,7 +4632,7 @@ mod tests {
         assert_eq!(source.status, ConnectionStatus::Connected);
         
         // List files
-        let files = service.list_files(&source.id, Path::new("/")).await.unwrap();
+        let files = service.list_files(&source.id, Path::new("/"), &ListOptions::default()).await.unwrap();
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].name, "test.txt");
         
@@ -10,10 +10,36 @@ use parking_lot::RwLock;
 use tauri::State;
 use tracing::{error, info, warn};
 use anyhow::{Context, Result};
-use tokio::fs;
 
 use crate::vfs::application::VfsService;
+use crate::vfs::application::vfs_service::{UserStores, DEFAULT_WARM_CONCURRENCY};
+use crate::vfs::adapters::VfsResultExt;
+use crate::vfs::domain::{CacheConfig, ConflictPolicy, ConnectionTestResult, CredentialRef, DeleteSummary, IgnorePatterns, LifecyclePolicy, MountConfig, ResolvedPath, RetrievalTier, RetryPolicy, SourceBatchResult, SourceHealth, SourceOption, SseConfig, TransferTuning, VfsError, WarmStatus};
+use crate::vfs::ports::{ListOptions, CrossStorageOptions, ObjectVersion, TranscodeOptions, TranscodeQuality, TranscodeRequest};
+use crate::vfs::application::manifest::{HashAlgorithm, ManifestVerification};
+use crate::vfs::application::tree_hash::TreeHash;
+use crate::vfs::application::copy_progress::{CopyProgress, CopyProgressFn};
+use crate::vfs::application::duplicates::DuplicateGroup;
+use crate::vfs::application::similar_images::{SimilarImageGroup, DEFAULT_SIMILARITY_THRESHOLD};
+use crate::vfs::application::catalog::{CatalogEntry, IndexStatus, DEFAULT_SEARCH_LIMIT};
+use crate::vfs::application::transcode_queue::{TranscodeQueue, TranscodeQueueStatus, DEFAULT_TRANSCODE_CONCURRENCY};
+use crate::vfs::application::proxies::ProxyReport;
+use crate::vfs::application::operation_history::RecordedOperation;
+use crate::vfs::application::selection::SelectionInfo;
+use crate::vfs::application::preview::PreviewData;
+use crate::vfs::application::fan_out::FanOutResult;
+use crate::vfs::application::scratch::scratch_path;
+use crate::vfs::application::temp_gc::{set_temp_ttl, start_temp_gc, DEFAULT_TEMP_GC_INTERVAL};
+use crate::vfs::application::folder_diff::DirDiff;
+use crate::vfs::application::path_format::PathStyle;
+use crate::vfs::application::batch_rename::RenameMapping;
+use crate::vfs::application::templates::{generate_copy_name, FileTemplate};
+use crate::vfs::application::lifecycle::{LifecycleCandidate, DEFAULT_LIFECYCLE_INTERVAL};
+use crate::vfs::infrastructure::AuditEntry;
+use crate::vfs::application::parallel_copy::{run_bounded, DEFAULT_COPY_CONCURRENCY};
 use crate::vfs::adapters::transcription::{TranscriptionService, TranscriptionSegment, TranscriptionStatus};
+use crate::vfs::adapters::{FfmpegMediaAdapter, MediaCapabilities};
+use crate::vfs::adapters::{AutomountEntry, AutomountStore};
 
 // ============================================================================
 // Response Types for Frontend
@@ -33,6 +59,13 @@ pub struct VfsStorageSourceResponse {
     pub is_ejectable: bool,
     /// Whether this is a system location (Home, Documents, etc.)
     pub is_system_location: bool,
+    /// Writes are rejected; the UI should hide write actions
+    pub read_only: bool,
+    /// Maximum bytes the source may hold, if capped
+    pub quota_bytes: Option<u64>,
+    /// Whether names differing only in case are different files; `None`
+    /// when it couldn't be detected
+    pub case_sensitive: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +86,8 @@ pub struct VfsFileMetadataResponse {
     pub transcode_progress: Option<u8>,
     pub thumbnail: Option<String>,  // Base64 data URL or API URL
     pub mime_type: Option<String>,
+    /// Provider checksum, present when listed with `withChecksums`
+    pub checksum: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,111 +134,82 @@ impl Default for VfsStateWrapper {
 // Tauri Commands (prefixed with vfs_ to avoid conflicts)
 // ============================================================================
 
+/// The temp sweep, started by the first `vfs_init`
+static TEMP_GC: OnceLock<tokio::task::JoinHandle<()>> = OnceLock::new();
+
 /// Initialize the VFS service and auto-mount default system folders
 #[tauri::command]
 pub async fn vfs_init(
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     // Check if already initialized
     if state.get_service().is_some() {
         info!("VFS already initialized, skipping");
         return Ok("VFS already initialized".to_string());
     }
     
-    let service = VfsService::new()
+    let stores = UserStores::default_stores()
         .await
-        .map_err(|e| format!("Failed to initialize VFS: {}", e))?;
-    
-    // Auto-mount default system folders
-    let home = dirs::home_dir();
+        .vfs_context("Failed to open the app's data stores")?;
+    let service = VfsService::with_stores(CacheConfig::default(), stores)
+        .await
+        .vfs_context("Failed to initialize VFS")?;
     
-    if let Some(home_path) = home {
-        // Mount home directory
-        if let Err(e) = service.add_local_source("Home".to_string(), home_path.clone()).await {
-            warn!("Failed to mount Home: {}", e);
-        }
-        
-        // Mount common folders if they exist
-        let common_folders = [
-            ("Desktop", home_path.join("Desktop")),
-            ("Documents", home_path.join("Documents")),
-            ("Downloads", home_path.join("Downloads")),
-            ("Pictures", home_path.join("Pictures")),
-            ("Music", home_path.join("Music")),
-            ("Videos", home_path.join("Videos")),
-        ];
-        
-        for (name, path) in common_folders {
-            if path.exists() && path.is_dir() {
-                if let Err(e) = service.add_local_source(name.to_string(), path).await {
-                    warn!("Failed to mount {}: {}", name, e);
-                }
-            }
+    // Folders on the auto-mount list (Home, Desktop, ... unless changed)
+    let automounts = match AutomountStore::default_store().await {
+        Ok(store) => store.entries(),
+        Err(e) => {
+            warn!("Failed to read auto-mount list, using defaults: {}", e);
+            AutomountEntry::defaults()
         }
-        
-        // Platform-specific mounts - enumerate external volumes
-        #[cfg(target_os = "macos")]
-        {
-            // Enumerate each volume in /Volumes separately (like Windows drive letters)
-            let volumes_dir = std::path::PathBuf::from("/Volumes");
-            if volumes_dir.exists() {
-                if let Ok(entries) = std::fs::read_dir(&volumes_dir) {
-                    for entry in entries.filter_map(Result::ok) {
-                        let vol_path = entry.path();
-                        let vol_name = entry.file_name().to_string_lossy().to_string();
-                        
-                        // Skip the main Macintosh HD symlink (already have Home folder)
-                        // Only include actual mounted external volumes
-                        if vol_path.is_dir() && !vol_name.starts_with('.') {
-                            // Check if it's a symlink to root (main HD)
-                            if let Ok(target) = std::fs::read_link(&vol_path) {
-                                if target == std::path::PathBuf::from("/") {
-                                    continue; // Skip symlink to root
-                                }
-                            }
-                            
-                            let display_name = format!("{}", vol_name);
-                            if let Err(e) = service.add_local_source(display_name.clone(), vol_path).await {
-                                warn!("Failed to mount volume {}: {}", display_name, e);
+    };
+    service.mount_automounts(&automounts).await;
+    
+    // Platform-specific mounts - enumerate external volumes
+    #[cfg(target_os = "macos")]
+    {
+        // Enumerate each volume in /Volumes separately (like Windows drive letters)
+        let volumes_dir = std::path::PathBuf::from("/Volumes");
+        if volumes_dir.exists() {
+            if let Ok(entries) = std::fs::read_dir(&volumes_dir) {
+                for entry in entries.filter_map(Result::ok) {
+                    let vol_path = entry.path();
+                    let vol_name = entry.file_name().to_string_lossy().to_string();
+                    
+                    // Skip the main Macintosh HD symlink (already have Home folder)
+                    // Only include actual mounted external volumes
+                    if vol_path.is_dir() && !vol_name.starts_with('.') {
+                        // Check if it's a symlink to root (main HD)
+                        if let Ok(target) = std::fs::read_link(&vol_path) {
+                            if target == std::path::PathBuf::from("/") {
+                                continue; // Skip symlink to root
                             }
                         }
-                    }
-                }
-            }
-        }
-        
-        #[cfg(target_os = "linux")]
-        {
-            // Enumerate user's media mounts (USB drives, etc.)
-            if let Some(username) = std::env::var("USER").ok() {
-                let media_dir = std::path::PathBuf::from(format!("/media/{}", username));
-                if media_dir.exists() {
-                    if let Ok(entries) = std::fs::read_dir(&media_dir) {
-                        for entry in entries.filter_map(Result::ok) {
-                            let mount_path = entry.path();
-                            let mount_name = entry.file_name().to_string_lossy().to_string();
-                            
-                            if mount_path.is_dir() && !mount_name.starts_with('.') {
-                                if let Err(e) = service.add_local_source(mount_name.clone(), mount_path).await {
-                                    warn!("Failed to mount media {}: {}", mount_name, e);
-                                }
-                            }
+                        
+                        let display_name = format!("{}", vol_name);
+                        if let Err(e) = service.add_local_source(display_name.clone(), vol_path).await {
+                            warn!("Failed to mount volume {}: {}", display_name, e);
                         }
                     }
                 }
             }
-            
-            // Also check /mnt for manually mounted drives
-            let mnt_dir = std::path::PathBuf::from("/mnt");
-            if mnt_dir.exists() {
-                if let Ok(entries) = std::fs::read_dir(&mnt_dir) {
+        }
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        // Enumerate user's media mounts (USB drives, etc.)
+        if let Some(username) = std::env::var("USER").ok() {
+            let media_dir = std::path::PathBuf::from(format!("/media/{}", username));
+            if media_dir.exists() {
+                if let Ok(entries) = std::fs::read_dir(&media_dir) {
                     for entry in entries.filter_map(Result::ok) {
                         let mount_path = entry.path();
                         let mount_name = entry.file_name().to_string_lossy().to_string();
                         
                         if mount_path.is_dir() && !mount_name.starts_with('.') {
                             if let Err(e) = service.add_local_source(mount_name.clone(), mount_path).await {
-                                warn!("Failed to mount {}: {}", mount_name, e);
+                                warn!("Failed to mount media {}: {}", mount_name, e);
                             }
                         }
                     }
@@ -211,18 +217,17 @@ pub async fn vfs_init(
             }
         }
         
-        #[cfg(target_os = "windows")]
-        {
-            // Enumerate all available drive letters (A-Z)
-            for drive in 'A'..='Z' {
-                let drive_path = std::path::PathBuf::from(format!("{}:\\", drive));
-                if drive_path.exists() {
-                    // Get volume label if available, otherwise use drive letter
-                    let name = format!("Drive ({}:)", drive);
-                    if let Err(e) = service.add_local_source(name.clone(), drive_path).await {
-                        // Only warn for drives that should be accessible
-                        if drive >= 'C' {
-                            warn!("Failed to mount {}: {}", name, e);
+        // Also check /mnt for manually mounted drives
+        let mnt_dir = std::path::PathBuf::from("/mnt");
+        if mnt_dir.exists() {
+            if let Ok(entries) = std::fs::read_dir(&mnt_dir) {
+                for entry in entries.filter_map(Result::ok) {
+                    let mount_path = entry.path();
+                    let mount_name = entry.file_name().to_string_lossy().to_string();
+                    
+                    if mount_path.is_dir() && !mount_name.starts_with('.') {
+                        if let Err(e) = service.add_local_source(mount_name.clone(), mount_path).await {
+                            warn!("Failed to mount {}: {}", mount_name, e);
                         }
                     }
                 }
@@ -230,8 +235,47 @@ pub async fn vfs_init(
         }
     }
     
+    #[cfg(target_os = "windows")]
+    {
+        // Enumerate all available drive letters (A-Z)
+        for drive in 'A'..='Z' {
+            let drive_path = std::path::PathBuf::from(format!("{}:\\", drive));
+            if drive_path.exists() {
+                // Get volume label if available, otherwise use drive letter
+                let name = format!("Drive ({}:)", drive);
+                if let Err(e) = service.add_local_source(name.clone(), drive_path).await {
+                    // Only warn for drives that should be accessible
+                    if drive >= 'C' {
+                        warn!("Failed to mount {}: {}", name, e);
+                    }
+                }
+            }
+        }
+    }
+    
+    // Buckets and shares mounted in earlier sessions
+    service.restore_sources().await;
+    
     let service_arc = Arc::new(service);
     
+    // Tier history goes to the same metadata store as tags
+    if let Some(metadata) = get_metadata_store().await?.read().await.clone() {
+        service_arc.set_metadata_store(metadata);
+    }
+    
+    // Scratch files go beside the configured cache, not the default one
+    crate::vfs::application::scratch::use_cache_dir(service_arc.cache_dir());
+    
+    // Move idle files on sources with a lifecycle policy
+    service_arc.start_lifecycle_evaluator(DEFAULT_LIFECYCLE_INTERVAL);
+    
+    // Old clipboard exports and thumbnail leftovers, swept by one loop however
+    // often the frontend re-initializes
+    if let Some(ttl) = service_arc.saved_temp_ttl() {
+        set_temp_ttl(ttl);
+    }
+    TEMP_GC.get_or_init(|| start_temp_gc(DEFAULT_TEMP_GC_INTERVAL));
+    
     // Initialize global clipboard with VFS service
     init_global_clipboard(service_arc.clone());
     
@@ -245,20 +289,18 @@ pub async fn vfs_init(
 #[tauri::command]
 pub async fn vfs_list_sources(
     state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<VfsStorageSourceResponse>, String> {
+) -> Result<Vec<VfsStorageSourceResponse>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized. Call vfs_init first.".to_string())?;
-    
-    let sources = service.list_sources();
+        .ok_or_else(VfsError::not_initialized)?;
     
-    Ok(sources.into_iter().map(|s| {
+    let mut responses = Vec::new();
+    for s in service.list_sources() {
+        let case_sensitive = service.case_sensitivity(&s.id).await;
         let path_str = s.mount_point.as_ref().map(|p| p.to_string_lossy().to_string());
         
         // Determine if this is an ejectable volume or a system location
         let (is_ejectable, is_system_location) = if let Some(ref path) = path_str {
-            // On macOS, /Volumes/ contains mounted volumes (except Macintosh HD)
-            // DMG mounts and external drives appear here
-            let is_volume_mount = path.starts_with("/Volumes/") && !path.contains("Macintosh HD");
+            let is_volume_mount = crate::vfs::platform::is_ejectable_mount(std::path::Path::new(path));
             
             // System locations are user home directories and their subdirectories
             let home_dir = std::env::var("HOME").unwrap_or_default();
@@ -271,7 +313,7 @@ pub async fn vfs_list_sources(
             (false, false)
         };
         
-        VfsStorageSourceResponse {
+        responses.push(VfsStorageSourceResponse {
             id: s.id,
             name: s.name,
             source_type: format!("{:?}", s.source_type),
@@ -282,8 +324,12 @@ pub async fn vfs_list_sources(
             region: s.config.region,
             is_ejectable,
             is_system_location,
-        }
-    }).collect())
+            read_only: s.config.read_only,
+            quota_bytes: s.config.quota_bytes,
+            case_sensitive,
+        });
+    }
+    Ok(responses)
 }
 
 /// Add a storage source (generic - handles all provider types)
@@ -291,61 +337,105 @@ pub async fn vfs_list_sources(
 pub async fn vfs_add_source(
     source: serde_json::Value,
     state: State<'_, VfsStateWrapper>,
-) -> Result<VfsStorageSourceResponse, String> {
+) -> Result<VfsStorageSourceResponse, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized. Call vfs_init first.".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     let provider_id = source.get("providerId")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing providerId".to_string())?;
+        .ok_or_else(|| VfsError::InvalidInput("Missing providerId".to_string()))?;
     
     let name = source.get("name")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing name".to_string())?
+        .ok_or_else(|| VfsError::InvalidInput("Missing name".to_string()))?
         .to_string();
     
     let config = source.get("config")
         .and_then(|v| v.as_object())
-        .ok_or_else(|| "Missing config".to_string())?;
+        .ok_or_else(|| VfsError::InvalidInput("Missing config".to_string()))?;
     
-    use crate::vfs::domain::StorageSource;
     use std::path::PathBuf;
     
-    let storage_source: StorageSource = match provider_id {
-        "s3" | "aws-s3" => {
+    let read_only = config.get("readOnly")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let non_empty = |key: &str| config.get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    
+    let mount_config = match provider_id {
+        "s3" | "aws-s3" | "s3-compatible" => {
             let bucket = config.get("bucket")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| "Missing bucket in config".to_string())?
+                .ok_or_else(|| VfsError::InvalidInput("Missing bucket in config".to_string()))?
                 .to_string();
-            let region = config.get("region")
+            let sse = config.get("sse")
+                .filter(|v| !v.is_null())
+                .map(|v| serde_json::from_value::<SseConfig>(v.clone()))
+                .transpose()
+                .map_err(|e| VfsError::InvalidInput(format!("Invalid sse in config: {}", e)))?;
+            
+            MountConfig::S3 {
+                name,
+                bucket,
+                region: non_empty("region"),
+                access_key_id: non_empty("accessKeyId"),
+                secret_access_key: non_empty("secretAccessKey"),
+                credential: None,
+                endpoint: non_empty("endpoint"),
+                // MinIO/Ceph generally need path-style; left unset, it follows the endpoint
+                path_style: config.get("pathStyle").and_then(|v| v.as_bool()),
+                sse,
+                require_encryption: config.get("requireEncryption")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                transfer_tuning: None,
+                flat_listing: false,
+                read_only,
+                quota_bytes: None,
+                lifecycle_policy: None,
+                retry_policy: None,
+            }
+        },
+        "gcs" => {
+            let bucket = config.get("bucket")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| "Missing region in config".to_string())?
+                .ok_or_else(|| VfsError::InvalidInput("Missing bucket in config".to_string()))?
                 .to_string();
-            let access_key = config.get("accessKeyId")
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-            let secret_key = config.get("secretAccessKey")
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-            let endpoint = config.get("endpoint")
+            
+            MountConfig::Gcs {
+                name,
+                bucket,
+                credentials_path: non_empty("credentialsPath"),
+                transfer_tuning: None,
+                read_only,
+                quota_bytes: None,
+                lifecycle_policy: None,
+                retry_policy: None,
+            }
+        },
+        "block" => {
+            let mount_point = config.get("mountPoint")
                 .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
+                .ok_or_else(|| VfsError::InvalidInput("Missing mountPoint in config".to_string()))?;
             
-            // Call add_s3_source - the method exists and should be accessible
-            service.add_s3_source(name, bucket.clone(), region.clone(), access_key, secret_key, endpoint)
-                .await
-                .map_err(|e| format!("Failed to add S3 source: {}", e))?
+            MountConfig::Block { name, mount_point: PathBuf::from(mount_point), read_only, quota_bytes: None }
         },
         _ => {
-            return Err(format!("Unsupported provider: {}", provider_id));
+            return Err(VfsError::Unsupported(format!("Unsupported provider: {}", provider_id)));
         }
     };
     
+    // Goes through the same path as `vfs_mount_source`, so the source is
+    // remembered for the next launch
+    let storage_source = service.mount_source(mount_config)
+        .await
+        .vfs_context("Failed to add storage source")?;
+    
     info!("Added storage source: {} ({})", storage_source.name, provider_id);
     
+    let case_sensitive = service.case_sensitivity(&storage_source.id).await;
     Ok(VfsStorageSourceResponse {
         id: storage_source.id,
         name: storage_source.name,
@@ -357,23 +447,141 @@ pub async fn vfs_add_source(
         region: storage_source.config.region.clone(),
         is_ejectable: false,
         is_system_location: false,
+        read_only: storage_source.config.read_only,
+        quota_bytes: storage_source.config.quota_bytes,
+        case_sensitive,
+    })
+}
+
+/// Mount any supported backend from a typed config (`{ "type": "s3", ... }`).
+///
+/// Missing fields are rejected with `INVALID_INPUT` and the connection is
+/// tested before the source is registered.
+#[tauri::command]
+pub async fn vfs_mount_source(
+    config: MountConfig,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<VfsStorageSourceResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let source = service.mount_source(config)
+        .await
+        .vfs_context("Failed to mount")?;
+    
+    info!("Mounted storage source: {} ({:?})", source.name, source.source_type);
+    
+    let case_sensitive = service.case_sensitivity(&source.id).await;
+    Ok(VfsStorageSourceResponse {
+        id: source.id,
+        name: source.name,
+        source_type: format!("{:?}", source.source_type),
+        mounted: source.mounted,
+        status: format!("{:?}", source.status),
+        path: source.mount_point.as_ref().map(|p| p.to_string_lossy().to_string()),
+        bucket: Some(source.config.path_or_bucket.clone()),
+        region: source.config.region.clone(),
+        is_ejectable: false,
+        is_system_location: false,
+        read_only: source.config.read_only,
+        quota_bytes: source.config.quota_bytes,
+        case_sensitive,
     })
 }
 
-/// Remove a storage source
+/// Check a mount config before connecting it: builds the adapter and tests
+/// the connection, but registers nothing. A bad config comes back as
+/// `ok: false` with the reason, not as an error.
+#[tauri::command]
+pub async fn vfs_test_connection(
+    config: MountConfig,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<ConnectionTestResult, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    Ok(service.test_mount_config(config).await)
+}
+
+/// Change the name a source is shown under in the sidebar. Empty names and
+/// names another source already has are rejected; the storage is untouched.
+#[tauri::command]
+pub async fn vfs_rename_source(
+    source_id: String,
+    new_name: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.rename_source(&source_id, &new_name)
+        .await
+        .vfs_context("Failed to rename source")?;
+    
+    Ok(())
+}
+
+/// Stop re-mounting a source on launch. It stays mounted until removed;
+/// returns whether it had been remembered.
+#[tauri::command]
+pub async fn vfs_forget_source(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<bool, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.forget_source(&source_id)
+        .await
+        .vfs_context("Failed to forget source")
+}
+
+/// Save a secret in the OS keychain, returning the reference to put in a
+/// `MountConfig` in place of the secret itself
+#[tauri::command]
+pub async fn vfs_store_credential(
+    service_name: String,
+    account: String,
+    secret: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<CredentialRef, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.store_credential(&service_name, &account, &secret)
+        .vfs_context("Failed to save credential")
+}
+
+/// Delete a secret from the OS keychain; returns whether there was one
+#[tauri::command]
+pub async fn vfs_delete_credential(
+    service_name: String,
+    account: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<bool, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.delete_credential(&service_name, &account)
+        .vfs_context("Failed to delete credential")
+}
+
+/// Remove a storage source from the VFS without touching the underlying mount.
+///
+/// Use `vfs_eject` to physically eject removable media.
 #[tauri::command]
 pub async fn vfs_remove_source(
     source_id: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     info!("vfs_remove_source: source_id={}", source_id);
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized. Call vfs_init first.".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     // Check if source exists
     let source = service.get_source(&source_id)
-        .ok_or_else(|| format!("Storage source not found: {}", source_id))?;
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
     
     // Remove the source from VFS internal state
     service.remove_source(&source_id);
@@ -382,26 +590,220 @@ pub async fn vfs_remove_source(
     Ok(format!("Removed storage source: {}", source.name))
 }
 
+/// Bytes a source holds and its quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceUsageResponse {
+    pub used_bytes: u64,
+    pub quota_bytes: Option<u64>,
+}
+
+/// How much a source holds against its quota
+#[tauri::command]
+pub async fn vfs_get_source_usage(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SourceUsageResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let source = service.get_source(&source_id)
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
+    let used_bytes = service.source_usage(&source_id)
+        .await
+        .vfs_context("Failed to compute usage")?;
+    
+    Ok(SourceUsageResponse {
+        used_bytes,
+        quota_bytes: source.config.quota_bytes,
+    })
+}
+
+/// Cap how many bytes a source may hold (`null` removes the cap)
+#[tauri::command]
+pub async fn vfs_set_source_quota(
+    source_id: String,
+    bytes: Option<u64>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SourceUsageResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_quota(&source_id, bytes)
+        .await
+        .vfs_context("Failed to set quota")?;
+    
+    vfs_get_source_usage(source_id, state).await
+}
+
+/// Cancels the selection being measured when the selection changes
+static SELECTION_SCAN: Lazy<SyncRwLock<Option<tokio_util::sync::CancellationToken>>> = Lazy::new(|| SyncRwLock::new(None));
+
+/// File and folder counts and total size of the selected items, for the
+/// status bar. Folders are walked; partial totals arrive as
+/// `selection:progress` events. Starting a new measurement cancels the
+/// previous one.
+#[tauri::command]
+pub async fn vfs_selection_info(
+    source_id: String,
+    paths: Vec<String>,
+    app: tauri::AppHandle,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SelectionInfo, VfsError> {
+    use tauri::Emitter;
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let cancel = tokio_util::sync::CancellationToken::new();
+    if let Some(previous) = SELECTION_SCAN.write().replace(cancel.clone()) {
+        previous.cancel();
+    }
+    
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let progress = move |partial: &SelectionInfo| {
+        let _ = app.emit("selection:progress", partial);
+    };
+    
+    service.selection_info(&source_id, &paths, &cancel, &progress)
+        .await
+        .vfs_context("Failed to measure selection")
+}
+
+/// Stop measuring the current selection
+#[tauri::command]
+pub async fn vfs_cancel_selection_info() -> Result<(), VfsError> {
+    if let Some(scan) = SELECTION_SCAN.write().take() {
+        scan.cancel();
+    }
+    Ok(())
+}
+
+/// Set how a cloud or network source retries transient failures
+/// (delays in milliseconds)
+#[tauri::command]
+pub async fn vfs_set_retry_policy(
+    source_id: String,
+    policy: RetryPolicy,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_retry_policy(&source_id, policy)
+        .await
+        .vfs_context("Failed to set retry policy")?;
+    
+    Ok(())
+}
+
+/// Set listing page size, upload/download chunk sizes and concurrency of an
+/// S3 or GCS source (sizes in bytes)
+#[tauri::command]
+pub async fn vfs_set_transfer_tuning(
+    source_id: String,
+    tuning: TransferTuning,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_transfer_tuning(&source_id, tuning)
+        .await
+        .vfs_context("Failed to set transfer tuning")?;
+    
+    Ok(())
+}
+
+/// Change a per-source option, e.g. `{ "option": "flatListing", "value": true }`
+/// to list an S3 bucket's objects flat instead of as folders
+#[tauri::command]
+pub async fn vfs_set_source_option(
+    source_id: String,
+    option: SourceOption,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_source_option(&source_id, option)
+        .await
+        .vfs_context("Failed to set source option")?;
+    
+    Ok(())
+}
+
+/// Whether a source is reachable and its failed checks in a row
+#[tauri::command]
+pub async fn vfs_source_health(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SourceHealth, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.source_health(&source_id).await
+        .vfs_context("Failed to check source health")
+}
+
+/// Move files idle for `policy.idleDays` to `policy.targetTier` in the
+/// background (`None` turns it off)
+#[tauri::command]
+pub async fn vfs_set_lifecycle_policy(
+    source_id: String,
+    policy: Option<LifecyclePolicy>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_lifecycle_policy(&source_id, policy)
+        .await
+        .vfs_context("Failed to set lifecycle policy")?;
+    
+    Ok(())
+}
+
+/// Preview the files the source's lifecycle policy would move
+#[tauri::command]
+pub async fn vfs_list_lifecycle_candidates(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<LifecycleCandidate>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.lifecycle_candidates(&source_id).await
+        .vfs_context("Failed to list lifecycle candidates")
+}
+
 /// Mount a local storage source (VFS version)
 #[tauri::command]
 pub async fn vfs_mount_local(
     name: String,
     path: String,
+    read_only: Option<bool>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<VfsStorageSourceResponse, String> {
+) -> Result<VfsStorageSourceResponse, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized. Call vfs_init first.".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let source = service.add_local_source(name, PathBuf::from(&path))
+    let config = MountConfig::Local {
+        name,
+        path: PathBuf::from(&path),
+        read_only: read_only.unwrap_or(false),
+        quota_bytes: None,
+    };
+    let source = service.mount_source(config)
         .await
-        .map_err(|e| format!("Failed to mount: {}", e))?;
+        .vfs_context("Failed to mount")?;
     
     info!("Mounted local storage: {} at {}", source.name, path);
     
     // Determine if this is an ejectable volume
-    let is_ejectable = path.starts_with("/Volumes/") && !path.contains("Macintosh HD");
+    let is_ejectable = crate::vfs::platform::is_ejectable_mount(std::path::Path::new(&path));
     let home_dir = std::env::var("HOME").unwrap_or_default();
     let is_system_location = (path.starts_with(&home_dir) || path == "/" || path == "/Applications") && !is_ejectable;
+    let case_sensitive = service.case_sensitivity(&source.id).await;
     
     Ok(VfsStorageSourceResponse {
         id: source.id,
@@ -414,56 +816,186 @@ pub async fn vfs_mount_local(
         region: None,
         is_ejectable,
         is_system_location,
+        read_only: source.config.read_only,
+        quota_bytes: source.config.quota_bytes,
+        case_sensitive,
     })
 }
 
-/// Eject/unmount a storage volume
+/// Mount an empty in-memory scratch source ("RAM disk"); its contents are
+/// discarded when it is removed or the app quits
 #[tauri::command]
-pub async fn vfs_eject(
-    source_id: String,
+pub async fn vfs_mount_memory(
+    name: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<(), String> {
-    info!("vfs_eject: source_id={}", source_id);
-    
+) -> Result<VfsStorageSourceResponse, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    // Find the source to get the path
-    let sources = service.list_sources();
-    let source = sources.iter()
-        .find(|s| s.id == source_id)
-        .ok_or_else(|| format!("Source not found: {}", source_id))?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let mount_path = source.mount_point.as_ref()
-        .ok_or_else(|| "No mount point for source".to_string())?;
+    let source = service.add_memory_source(name)
+        .await
+        .vfs_context("Failed to mount")?;
     
-    let path_str = mount_path.to_string_lossy().to_string();
+    info!("Mounted memory storage: {}", source.name);
     
-    // Perform platform-specific eject
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        
-        // Use diskutil to eject the volume
-        let output = Command::new("diskutil")
-            .args(["eject", &path_str])
-            .output()
-            .map_err(|e| format!("Failed to run diskutil: {}", e))?;
-        
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // Try alternative: osascript to eject
-            let alt_output = Command::new("osascript")
-                .args(["-e", &format!("tell application \"Finder\" to eject disk \"{}\"", 
-                    mount_path.file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| path_str.clone())
+    let case_sensitive = service.case_sensitivity(&source.id).await;
+    Ok(VfsStorageSourceResponse {
+        id: source.id,
+        name: source.name,
+        source_type: format!("{:?}", source.source_type),
+        mounted: true,
+        status: "Connected".to_string(),
+        path: None,
+        bucket: None,
+        region: None,
+        is_ejectable: false,
+        is_system_location: false,
+        read_only: source.config.read_only,
+        quota_bytes: source.config.quota_bytes,
+        case_sensitive,
+    })
+}
+
+/// Get device-level information for a block storage source
+#[tauri::command]
+pub async fn vfs_block_device_info(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<crate::vfs::adapters::BlockDeviceInfo, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+
+    let source = service.get_source(&source_id)
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
+
+    if source.source_type != crate::vfs::domain::StorageSourceType::Block {
+        return Err(VfsError::Unsupported(format!("Source is not a block device: {}", source.name)));
+    }
+
+    let mount_point = source.mount_point
+        .ok_or_else(|| VfsError::Unsupported("No mount point for source".to_string()))?;
+
+    crate::vfs::adapters::BlockStorageAdapter::new(mount_point, source.name)
+        .device_info()
+        .await
+        .vfs_context("Failed to get device info")
+}
+
+/// Eject/unmount a storage volume
+#[tauri::command]
+pub async fn vfs_eject(
+    source_id: String,
+    force: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    info!("vfs_eject: source_id={}", source_id);
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    eject_source(&service, &source_id, force.unwrap_or(false)).await
+}
+
+/// Eject every ejectable volume (external drives, disk images, block devices),
+/// e.g. before unplugging a dock. Volumes in use are left mounted and reported
+/// as failed rather than having their transfers cancelled. macOS only, since
+/// only there are external volumes told apart from the rest.
+#[tauri::command]
+pub async fn vfs_eject_all(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<SourceBatchResult>, VfsError> {
+    if !cfg!(target_os = "macos") {
+        return Err(VfsError::Unsupported("Eject all is only available on macOS".to_string()));
+    }
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let mut results = Vec::new();
+    for source in service.list_sources().into_iter().filter(is_ejectable_source) {
+        let error = eject_source(&service, &source.id, false).await.err();
+        results.push(SourceBatchResult {
+            source_id: source.id,
+            name: source.name,
+            ok: error.is_none(),
+            error: error.map(|e| e.message().to_string()),
+        });
+    }
+    
+    info!("Ejected {} of {} volume(s)", results.iter().filter(|r| r.ok).count(), results.len());
+    Ok(results)
+}
+
+/// Re-check every remote source (NAS, cloud buckets) and reconnect the ones
+/// whose connection went stale, e.g. after the machine wakes from sleep.
+/// Sources that still can't be reached are marked with the error.
+#[tauri::command]
+pub async fn vfs_remount_all_remote(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<SourceBatchResult>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    Ok(service.remount_all_remote().await)
+}
+
+/// Volumes under `/Volumes` (other than the boot disk) and block devices
+fn is_ejectable_source(source: &crate::vfs::domain::StorageSource) -> bool {
+    let is_volume_mount = source.mount_point.as_deref()
+        .is_some_and(crate::vfs::platform::is_ejectable_mount);
+    is_volume_mount || source.source_type == crate::vfs::domain::StorageSourceType::Block
+}
+
+/// Unmount a source's volume and remove the source
+async fn eject_source(service: &Arc<VfsService>, source_id: &str, force: bool) -> Result<(), VfsError> {
+    // Refuse while hydrations/copies use the volume, or cancel them when forced
+    service.prepare_eject(source_id, force)
+        .await
+        .vfs_context("Failed to eject")?;
+    
+    // Persist pending metadata before the volume disappears
+    if let Some(store) = get_metadata_store().await?.read().await.as_ref() {
+        if let Err(e) = store.flush().await {
+            warn!("Failed to flush metadata before eject: {}", e);
+        }
+    }
+    
+    // Find the source to get the path
+    let sources = service.list_sources();
+    let source = sources.iter()
+        .find(|s| s.id == source_id)
+        .ok_or_else(|| VfsError::NotFound(format!("Source not found: {}", source_id)))?;
+    
+    let mount_path = source.mount_point.as_ref()
+        .ok_or_else(|| VfsError::Unsupported("No mount point for source".to_string()))?;
+    
+    let path_str = mount_path.to_string_lossy().to_string();
+    
+    // Perform platform-specific eject
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        
+        // Use diskutil to eject the volume
+        let output = Command::new("diskutil")
+            .args(["eject", &path_str])
+            .output()
+            .vfs_context("Failed to run diskutil")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Try alternative: osascript to eject
+            let alt_output = Command::new("osascript")
+                .args(["-e", &format!("tell application \"Finder\" to eject disk \"{}\"", 
+                    mount_path.file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path_str.clone())
                 )])
                 .output()
-                .map_err(|e| format!("Failed to run osascript: {}", e))?;
+                .vfs_context("Failed to run osascript")?;
             
             if !alt_output.status.success() {
-                return Err(format!("Failed to eject volume: {}", stderr));
+                return Err(VfsError::Other(format!("Failed to eject volume: {}", stderr)));
             }
         }
         
@@ -502,12 +1034,12 @@ pub async fn vfs_eject(
             cmd.output()
         })
         .await
-        .map_err(|e| format!("Failed to spawn blocking task: {}", e))?
-        .map_err(|e| format!("Failed to eject: {}", e))?;
+        .vfs_context("Failed to spawn blocking task")?
+        .vfs_context("Failed to eject")?;
         
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to eject volume: {}", stderr));
+            return Err(VfsError::Other(format!("Failed to eject volume: {}", stderr)));
         }
         
         info!("Ejected volume: {}", path_str);
@@ -526,2160 +1058,4131 @@ pub async fn vfs_eject(
                     .args([&path_str])
                     .output()
             })
-            .map_err(|e| format!("Failed to unmount: {}", e))?;
+            .vfs_context("Failed to unmount")?;
         
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to unmount volume: {}", stderr));
+            return Err(VfsError::Other(format!("Failed to unmount volume: {}", stderr)));
         }
         
         info!("Unmounted volume: {}", path_str);
     }
     
     // Remove the source from VFS internal state
-    service.remove_source(&source_id);
+    service.remove_source(source_id);
     info!("Removed source {} from VFS", source_id);
     
     Ok(())
 }
 
+/// List the volumes mounted on this machine without registering them as sources.
+///
+/// Lets the UI refresh its drive list after a hotplug without re-running `vfs_init`.
+#[tauri::command]
+pub async fn vfs_list_system_volumes() -> Result<Vec<crate::vfs::platform::SystemVolume>, VfsError> {
+    tokio::task::spawn_blocking(crate::vfs::platform::list_system_volumes)
+        .await
+        .vfs_context("Failed to spawn blocking task")?
+        .vfs_context("Failed to list volumes")
+}
+
+/// Whether hotplugged volumes are registered as storage sources automatically
+static VOLUME_AUTO_ADD: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Automatically add newly mounted volumes as sources (and remove them on unmount)
+#[tauri::command]
+pub async fn vfs_set_volume_auto_add(enabled: bool) -> Result<(), VfsError> {
+    VOLUME_AUTO_ADD.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    info!("Volume auto-add {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Folders mounted as sources at startup
+#[tauri::command]
+pub async fn vfs_get_automount_config() -> Result<Vec<AutomountEntry>, VfsError> {
+    let store = AutomountStore::default_store()
+        .await
+        .vfs_context("Failed to read auto-mount list")?;
+    Ok(store.entries())
+}
+
+/// Replace the folders mounted at startup; takes effect on the next launch
+#[tauri::command]
+pub async fn vfs_set_automount_config(entries: Vec<AutomountEntry>) -> Result<(), VfsError> {
+    let store = AutomountStore::default_store()
+        .await
+        .vfs_context("Failed to read auto-mount list")?;
+    store.set_entries(entries)
+        .await
+        .vfs_context("Failed to save auto-mount list")
+}
+
+/// Poll for volumes being mounted or unmounted in the background, emitting
+/// `vfs-volume-added` / `vfs-volume-removed` with the `SystemVolume` as payload.
+///
+/// Volumes are described less often the longer nothing changes; in between,
+/// a cheap look at the mount points wakes the monitor early on a hotplug.
+pub fn start_volume_monitor(app: tauri::AppHandle) {
+    use crate::vfs::platform::{
+        list_system_volumes, mount_point_snapshot, VolumeChange, VolumeWatcher, VOLUME_POLL_MIN_INTERVAL,
+    };
+    use tauri::{Emitter, Manager};
+    
+    tauri::async_runtime::spawn(async move {
+        let initial = tokio::task::spawn_blocking(list_system_volumes)
+            .await
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_default();
+        let mut watcher = VolumeWatcher::new(initial);
+        let mut mount_points = mount_point_snapshot();
+        
+        loop {
+            let deadline = tokio::time::Instant::now() + watcher.poll_interval();
+            while tokio::time::Instant::now() < deadline {
+                tokio::time::sleep(VOLUME_POLL_MIN_INTERVAL).await;
+                let current = tokio::task::spawn_blocking(mount_point_snapshot).await.ok().flatten();
+                if current.is_some() && current != mount_points {
+                    mount_points = current;
+                    break;
+                }
+            }
+            
+            let volumes = match tokio::task::spawn_blocking(list_system_volumes).await {
+                Ok(Ok(volumes)) => volumes,
+                Ok(Err(e)) => {
+                    warn!("Failed to enumerate volumes: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    error!("Volume enumeration task failed: {}", e);
+                    continue;
+                }
+            };
+            
+            for change in watcher.observe(volumes) {
+                let auto_add = VOLUME_AUTO_ADD.load(std::sync::atomic::Ordering::Relaxed);
+                let service = app.state::<VfsStateWrapper>().get_service();
+                
+                match change {
+                    VolumeChange::Added(volume) => {
+                        info!("Volume mounted: {:?}", volume.path);
+                        if let (true, Some(service)) = (auto_add, &service) {
+                            let known = service.list_sources().iter()
+                                .any(|s| s.mount_point.as_ref() == Some(&volume.path));
+                            if !known {
+                                if let Err(e) = service.add_local_source(volume.name.clone(), volume.path.clone()).await {
+                                    warn!("Failed to add volume {}: {}", volume.name, e);
+                                }
+                            }
+                        }
+                        if let Err(e) = app.emit("vfs-volume-added", &volume) {
+                            warn!("Failed to emit volume event: {}", e);
+                        }
+                    }
+                    VolumeChange::Removed(volume) => {
+                        info!("Volume unmounted: {:?}", volume.path);
+                        if let (true, Some(service)) = (auto_add, &service) {
+                            for source in service.list_sources() {
+                                if source.mount_point.as_ref() == Some(&volume.path) {
+                                    service.remove_source(&source.id);
+                                }
+                            }
+                        }
+                        if let Err(e) = app.emit("vfs-volume-removed", &volume) {
+                            warn!("Failed to emit volume event: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Convert a domain file into the listing response shape
+fn to_file_response(f: crate::vfs::domain::VirtualFile) -> VfsFileMetadataResponse {
+    let last_modified = f.last_modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| chrono::DateTime::from_timestamp(d.as_secs() as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default())
+        .unwrap_or_default();
+    
+    // Calculate values before moving fields
+    let can_transcode = f.can_transcode();
+    let transcode_status = f.transcode_status.as_ref().map(|s| format!("{:?}", s.state));
+    let transcode_progress = f.transcode_status.as_ref().map(|s| s.progress);
+    
+    // Check if file is hidden (starts with . on Unix, or has hidden attribute)
+    let is_hidden = f.name.starts_with('.') || f.is_hidden.unwrap_or(false);
+    
+    // Sniffed during listing where the extension says nothing
+    let mime_type = Some(f.content_type.clone()
+        .unwrap_or_else(|| crate::vfs::ports::media::detect_mime(&f.path, None).to_string()));
+    
+    VfsFileMetadataResponse {
+        id: f.id,
+        name: f.name,
+        path: f.path.to_string_lossy().to_string(),
+        size: f.size.bytes(),
+        size_human: f.size.as_human_readable(),
+        last_modified,
+        is_directory: f.is_directory,
+        is_hidden,
+        tier_status: f.tier_status.current_tier.as_str().to_string(),
+        is_cached: f.tier_status.is_cached,
+        can_warm: f.tier_status.can_warm,
+        can_transcode,
+        transcode_status,
+        transcode_progress,
+        thumbnail: None, // Thumbnails loaded on demand via vfs_get_thumbnail
+        mime_type,
+        checksum: f.checksum,
+    }
+}
+
+/// Listing options from the frontend. Without any, hidden files follow the
+/// OS file manager's setting; `show_hidden` overrides either.
+fn listing_options(options: Option<ListOptions>, show_hidden: Option<bool>) -> ListOptions {
+    let mut options = options.unwrap_or_else(|| ListOptions {
+        show_hidden: *OS_SHOW_HIDDEN_FILES,
+        ..ListOptions::default()
+    });
+    if let Some(show_hidden) = show_hidden {
+        options.show_hidden = show_hidden;
+    }
+    options
+}
+
 /// List files in a storage source (VFS version)
 #[tauri::command]
 pub async fn vfs_list_files(
     source_id: String,
     path: String,
+    options: Option<ListOptions>,
+    show_hidden: Option<bool>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<VfsFileMetadataResponse>, String> {
+) -> Result<Vec<VfsFileMetadataResponse>, VfsError> {
     info!("vfs_list_files: source_id={}, path={}", source_id, path);
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     // Get source info for better error messages
     let source = service.get_source(&source_id)
-        .ok_or_else(|| format!("Storage source not found: {}", source_id))?;
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
     
     info!("[vfs_list_files] Source: {} (type: {:?}, bucket: {}, region: {:?})", 
         source.name, source.source_type, source.config.path_or_bucket, source.config.region);
     
-    let files = service.list_files(&source_id, std::path::Path::new(&path))
+    let options = listing_options(options, show_hidden);
+    let files = service.list_files(&source_id, std::path::Path::new(&path), &options)
         .await
         .map_err(|e| {
-            let error_msg = format!("Failed to list files: {}", e);
+            let error = VfsError::from(e).context("Failed to list files");
             // Add helpful IAM permission hints for S3 errors
             if matches!(source.source_type, crate::vfs::domain::StorageSourceType::S3) {
-                format!(
+                let message = format!(
                     "{}\n\nTroubleshooting:\n\
                     - Verify bucket name: '{}'\n\
                     - Verify region: '{}'\n\
                     - Check IAM permissions: s3:ListBucket on bucket, s3:GetObject on objects\n\
                     - Verify AWS credentials are valid and not expired\n\
                     - Check bucket exists and is accessible",
-                    error_msg, source.config.path_or_bucket, 
+                    error.message(), source.config.path_or_bucket, 
                     source.config.region.as_deref().unwrap_or("us-east-1")
-                )
+                );
+                error.with_message(message)
             } else {
-                error_msg
+                error
             }
         })?;
     
     info!("vfs_list_files: found {} files", files.len());
     
-    Ok(files.into_iter().map(|f| {
-        let last_modified = f.last_modified
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| chrono::DateTime::from_timestamp(d.as_secs() as i64, 0)
-                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-                .unwrap_or_default())
-            .unwrap_or_default();
-        
-        // Calculate values before moving fields
-        let can_transcode = f.can_transcode();
-        let transcode_status = f.transcode_status.as_ref().map(|s| format!("{:?}", s.state));
-        let transcode_progress = f.transcode_status.as_ref().map(|s| s.progress);
-        
-        // Check if file is hidden (starts with . on Unix, or has hidden attribute)
-        let is_hidden = f.name.starts_with('.') || f.is_hidden.unwrap_or(false);
-        
-        // Determine MIME type from extension
-        let mime_type = f.path.extension()
-            .and_then(|e| e.to_str())
-            .map(|ext| match ext.to_lowercase().as_str() {
-                "jpg" | "jpeg" => "image/jpeg",
-                "png" => "image/png",
-                "gif" => "image/gif",
-                "webp" => "image/webp",
-                "svg" => "image/svg+xml",
-                "heic" | "heif" => "image/heic",
-                "pdf" => "application/pdf",
-                "mp4" => "video/mp4",
-                "mov" => "video/quicktime",
-                "avi" => "video/x-msvideo",
-                "mkv" => "video/x-matroska",
-                "webm" => "video/webm",
-                "mp3" => "audio/mpeg",
-                "wav" => "audio/wav",
-                "flac" => "audio/flac",
-                "txt" => "text/plain",
-                "json" => "application/json",
-                "xml" => "application/xml",
-                "html" | "htm" => "text/html",
-                "css" => "text/css",
-                "js" => "application/javascript",
-                "ts" | "tsx" => "text/typescript",
-                "md" => "text/markdown",
-                "zip" => "application/zip",
-                "tar" | "gz" | "bz2" => "application/x-compressed",
-                _ => "application/octet-stream",
-            }.to_string());
-        
-        VfsFileMetadataResponse {
-            id: f.id,
-            name: f.name,
-            path: f.path.to_string_lossy().to_string(),
-            size: f.size.bytes(),
-            size_human: f.size.as_human_readable(),
-            last_modified,
-            is_directory: f.is_directory,
-            is_hidden,
-            tier_status: f.tier_status.current_tier.as_str().to_string(),
-            is_cached: f.tier_status.is_cached,
-            can_warm: f.tier_status.can_warm,
-            can_transcode,
-            transcode_status,
-            transcode_progress,
-            thumbnail: None, // Thumbnails loaded on demand via vfs_get_thumbnail
-            mime_type,
-        }
-    }).collect())
-}
-
-/// Hydrate (warm) a file from cold storage (VFS version)
-#[tauri::command]
-pub async fn vfs_warm_file(
-    source_id: String,
-    file_path: String,
-    state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    let cache_path = service.hydrate_file(&source_id, std::path::Path::new(&file_path))
-        .await
-        .map_err(|e| format!("Failed to hydrate file: {}", e))?;
-    
-    info!("File hydrated: {} -> {:?}", file_path, cache_path);
-    
-    Ok(cache_path.to_string_lossy().to_string())
+    Ok(files.into_iter().map(to_file_response).collect())
 }
 
-/// Transcode a video file (VFS version)
-#[tauri::command]
-pub async fn vfs_transcode_video(
-    _source_id: String,
-    file_path: String,
-    format: String,
-    _state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    // For POC, just return a placeholder
-    // Real implementation would use ffmpeg
-    info!("Transcode requested: {} -> {}", file_path, format);
-    
-    Ok(format!("Transcode job started for {} (format: {})", file_path, format))
-}
+/// Default number of entries per page for `vfs_list_files_paged`
+const DEFAULT_PAGE_SIZE: usize = 500;
 
-/// Get cache statistics (VFS version)
-#[tauri::command]
-pub async fn vfs_cache_stats(
-    state: State<'_, VfsStateWrapper>,
-) -> Result<VfsCacheStatsResponse, String> {
-    let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    let stats = service.cache_stats().await;
-    
-    Ok(VfsCacheStatsResponse {
-        total_size: stats.total_size,
-        max_size: stats.max_size,
-        entry_count: stats.entry_count,
-        hit_count: stats.hit_count,
-        miss_count: stats.miss_count,
-        hit_rate: stats.hit_rate(),
-        usage_percent: stats.usage_percent(),
-    })
+/// One page of a directory listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VfsFilesPageResponse {
+    pub files: Vec<VfsFileMetadataResponse>,
+    /// Pass back as `cursor` to fetch the next page; `None` when the listing is complete
+    pub next_cursor: Option<String>,
 }
 
-/// Clear the cache (VFS version)
+/// List a directory one page at a time (for huge buckets/directories)
 #[tauri::command]
-pub async fn vfs_clear_cache(
+pub async fn vfs_list_files_paged(
+    source_id: String,
+    path: String,
+    options: Option<ListOptions>,
+    show_hidden: Option<bool>,
+    cursor: Option<String>,
+    limit: Option<usize>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<VfsFilesPageResponse, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.clear_cache()
+    let options = listing_options(options, show_hidden);
+    let (files, next_cursor) = service
+        .list_files_paged(&source_id, std::path::Path::new(&path), &options, cursor, limit.unwrap_or(DEFAULT_PAGE_SIZE))
         .await
-        .map_err(|e| format!("Failed to clear cache: {}", e))?;
+        .vfs_context("Failed to list files")?;
     
-    Ok("Cache cleared".to_string())
+    Ok(VfsFilesPageResponse {
+        files: files.into_iter().map(to_file_response).collect(),
+        next_cursor,
+    })
 }
 
-// ============================================================================
-// POSIX File Operations Commands
-// ============================================================================
+/// Entries per `vfs-listing-batch` event
+const LISTING_BATCH_SIZE: usize = 500;
 
-/// Request types for file operations
-#[derive(Debug, Deserialize)]
-pub struct CopyRequest {
-    pub from: String,
-    pub to: String,
-    pub overwrite: Option<bool>,
-    pub recursive: Option<bool>,
+/// Payload of a `vfs-listing-batch` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingBatchEvent {
+    pub listing_id: String,
+    pub files: Vec<VfsFileMetadataResponse>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct MoveRequest {
-    pub from: String,
-    pub to: String,
-    pub overwrite: Option<bool>,
+/// Payload of the `vfs-listing-done` event that ends a streamed listing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingDoneEvent {
+    pub listing_id: String,
+    /// Entries sent across all batches
+    pub total: usize,
+    /// Set when reading stopped early; the batches already sent are still valid
+    pub error: Option<String>,
 }
 
-/// Response type for file stat
-#[derive(Debug, Serialize)]
-pub struct FileStatResponse {
-    pub size: u64,
-    pub is_dir: bool,
-    pub is_file: bool,
-    pub is_symlink: bool,
-    pub mode: u32,
-    pub mtime: Option<u64>,
-    pub atime: Option<u64>,
-    pub ctime: Option<u64>,
+/// Events of one streamed listing, in the order they're emitted
+#[derive(Debug, Clone)]
+enum ListingEvent {
+    Batch(ListingBatchEvent),
+    Done(ListingDoneEvent),
 }
 
-/// Create a directory (like mkdir)
+/// List a directory without waiting for all of it: entries arrive unsorted
+/// as `vfs-listing-batch` events while the folder is read, then a
+/// `vfs-listing-done`. Events carry the caller's `listing_id`; the command
+/// resolves with the total once the listing is done.
 #[tauri::command]
-pub async fn vfs_mkdir(
+pub async fn vfs_list_files_stream(
     source_id: String,
     path: String,
+    listing_id: String,
+    options: Option<ListOptions>,
+    show_hidden: Option<bool>,
+    app: tauri::AppHandle,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<usize, VfsError> {
+    use tauri::Emitter;
+    
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.mkdir(&source_id, std::path::Path::new(&path))
+    let options = listing_options(options, show_hidden);
+    stream_listing(&service, &source_id, std::path::Path::new(&path), &options, &listing_id, LISTING_BATCH_SIZE, |event| {
+        let _ = match event {
+            ListingEvent::Batch(batch) => app.emit("vfs-listing-batch", batch),
+            ListingEvent::Done(done) => app.emit("vfs-listing-done", done),
+        };
+    }).await
+}
+
+async fn stream_listing(
+    service: &VfsService,
+    source_id: &str,
+    path: &std::path::Path,
+    options: &ListOptions,
+    listing_id: &str,
+    batch_size: usize,
+    emit: impl Fn(ListingEvent),
+) -> Result<usize, VfsError> {
+    use futures::StreamExt;
+    
+    let mut batches = service.list_files_batched(source_id, path, options, batch_size)
         .await
-        .map_err(|e| format!("Failed to create directory: {}", e))?;
+        .vfs_context("Failed to list files")?;
+    
+    let mut total = 0;
+    let mut error = None;
+    while let Some(batch) = batches.next().await {
+        match batch {
+            Ok(files) => {
+                total += files.len();
+                emit(ListingEvent::Batch(ListingBatchEvent {
+                    listing_id: listing_id.to_string(),
+                    files: files.into_iter().map(to_file_response).collect(),
+                }));
+            }
+            Err(e) => {
+                error = Some(VfsError::from(e).context("Failed to list files"));
+                break;
+            }
+        }
+    }
     
-    info!("Created directory: {}", path);
-    Ok(format!("Directory created: {}", path))
+    emit(ListingEvent::Done(ListingDoneEvent {
+        listing_id: listing_id.to_string(),
+        total,
+        error: error.as_ref().map(|e| e.message().to_string()),
+    }));
+    error.map_or(Ok(total), Err)
 }
 
-/// Create directory and all parents (like mkdir -p)
+/// Drop cached listings for `path` (and the directories above and below it) so
+/// the next listing goes back to the storage source
 #[tauri::command]
-pub async fn vfs_mkdir_p(
+pub async fn vfs_invalidate_listing(
     source_id: String,
     path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    service.mkdir_p(&source_id, std::path::Path::new(&path))
-        .await
-        .map_err(|e| format!("Failed to create directories: {}", e))?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!("Created directory tree: {}", path);
-    Ok(format!("Directory tree created: {}", path))
+    service.invalidate_listing(&source_id, std::path::Path::new(&path));
+    Ok(())
 }
 
-/// Remove empty directory (like rmdir)
+/// Glob patterns hidden from listings and skipped by recursive copy, sync and archive
 #[tauri::command]
-pub async fn vfs_rmdir(
-    source_id: String,
-    path: String,
+pub async fn vfs_get_ignore_patterns(
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<Vec<String>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.rmdir(&source_id, std::path::Path::new(&path))
-        .await
-        .map_err(|e| format!("Failed to remove directory: {}", e))?;
-    
-    info!("Removed directory: {}", path);
-    Ok(format!("Directory removed: {}", path))
+    Ok(service.ignore_patterns().patterns().to_vec())
 }
 
-/// Rename file or directory
+/// Replace the ignore list; returns it with blank patterns dropped
 #[tauri::command]
-pub async fn vfs_rename(
-    source_id: String,
-    from: String,
-    to: String,
+pub async fn vfs_set_ignore_patterns(
+    patterns: Vec<String>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<Vec<String>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.rename(&source_id, std::path::Path::new(&from), std::path::Path::new(&to))
+    let patterns = IgnorePatterns::new(patterns);
+    service.set_ignore_patterns(patterns.clone())
         .await
-        .map_err(|e| format!("Failed to rename: {}", e))?;
-    
-    info!("Renamed: {} -> {}", from, to);
-    Ok(format!("Renamed {} to {}", from, to))
+        .vfs_context("Failed to save ignore patterns")?;
+    Ok(patterns.patterns().to_vec())
 }
 
-/// Copy file or directory
+/// Hydrate (warm) a file from cold storage (VFS version)
 #[tauri::command]
-pub async fn vfs_copy(
+pub async fn vfs_warm_file(
     source_id: String,
-    request: CopyRequest,
+    file_path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let options = crate::vfs::ports::CopyOptions {
-        overwrite: request.overwrite.unwrap_or(false),
-        recursive: request.recursive.unwrap_or(false),
-        preserve_attributes: true,
-        follow_symlinks: false,
-    };
-    
-    service.copy(
-        &source_id,
-        std::path::Path::new(&request.from),
-        std::path::Path::new(&request.to),
-        options,
-    )
+    let cache_path = service.hydrate_file(&source_id, std::path::Path::new(&file_path))
         .await
-        .map_err(|e| format!("Failed to copy: {}", e))?;
-    
-    info!("Copied: {} -> {}", request.from, request.to);
-    Ok(format!("Copied {} to {}", request.from, request.to))
-}
-
-/// Move file or directory
-#[tauri::command]
-pub async fn vfs_move(
-    source_id: String,
-    request: MoveRequest,
-    state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    let options = crate::vfs::ports::MoveOptions {
-        overwrite: request.overwrite.unwrap_or(false),
-    };
+        .vfs_context("Failed to hydrate file")?;
     
-    service.mv(
-        &source_id,
-        std::path::Path::new(&request.from),
-        std::path::Path::new(&request.to),
-        options,
-    )
-        .await
-        .map_err(|e| format!("Failed to move: {}", e))?;
+    info!("File hydrated: {} -> {:?}", file_path, cache_path);
     
-    info!("Moved: {} -> {}", request.from, request.to);
-    Ok(format!("Moved {} to {}", request.from, request.to))
+    Ok(cache_path.to_string_lossy().to_string())
 }
 
-/// Delete file (like rm)
+/// Whether a file is warmed into the cache, still warming (with progress), or not
 #[tauri::command]
-pub async fn vfs_delete(
+pub async fn vfs_warm_status(
     source_id: String,
     path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<WarmStatus, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Track delete operation
-    let tracker = get_operation_tracker();
-    let operation_id = tracker.create_operation(
-        OperationType::Delete,
-        source_id.clone(),
-        path.clone(),
-        None,
-        None,
-    );
-    
-    match service.rm(&source_id, std::path::Path::new(&path)).await {
-        Ok(_) => {
-            let _ = tracker.complete_operation(&operation_id);
-            info!("Deleted: {}", path);
-            Ok(format!("Deleted: {}", path))
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to delete: {}", e);
-            let _ = tracker.fail_operation(&operation_id, error_msg.clone());
-            Err(error_msg)
-        }
-    }
+    service.warm_status(&source_id, std::path::Path::new(&path)).await
+        .vfs_context("Failed to get warm status")
 }
 
-/// Delete file or directory recursively (like rm -rf)
+/// Queue several files for warming and return how many were queued; poll
+/// `vfs_warm_status` for progress
 #[tauri::command]
-pub async fn vfs_delete_recursive(
+pub async fn vfs_warm_batch(
     source_id: String,
-    path: String,
+    paths: Vec<String>,
+    concurrency: Option<usize>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    info!("vfs_delete_recursive called: source_id={}, path={}", source_id, path);
-    
+) -> Result<usize, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    // Track delete operation
-    let tracker = get_operation_tracker();
-    let operation_id = tracker.create_operation(
-        OperationType::Delete,
-        source_id.clone(),
-        path.clone(),
-        None,
-        None,
-    );
-    
-    // Normalize the path
-    let normalized_path = path.trim_start_matches('/');
-    let path_obj = std::path::Path::new(normalized_path);
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!("Attempting to delete: {:?}", path_obj);
+    let paths = paths.into_iter().map(PathBuf::from).collect();
+    let (queued, _) = service
+        .warm_batch(&source_id, paths, concurrency.unwrap_or(DEFAULT_WARM_CONCURRENCY))
+        .await
+        .vfs_context("Failed to start batch warm")?;
     
-    match service.rm_rf(&source_id, path_obj).await {
-        Ok(_) => {
-            let _ = tracker.complete_operation(&operation_id);
-            info!("Successfully deleted: {}", path);
-            Ok(format!("Deleted: {}", path))
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to delete '{}': {}", path, e);
-            error!("{}", error_msg);
-            let _ = tracker.fail_operation(&operation_id, error_msg.clone());
-            Err(error_msg)
-        }
-    }
+    info!("Queued {} files for warming on {}", queued, source_id);
+    Ok(queued)
 }
 
-/// Change file permissions (like chmod)
+/// Cache just a byte range of a file (e.g. the part of a clip being scrubbed)
 #[tauri::command]
-pub async fn vfs_chmod(
+pub async fn vfs_hydrate_range(
     source_id: String,
     path: String,
-    mode: u32,
+    offset: u64,
+    length: u64,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.chmod(&source_id, std::path::Path::new(&path), mode)
+    service.hydrate_range(&source_id, std::path::Path::new(&path), offset, length)
         .await
-        .map_err(|e| format!("Failed to chmod: {}", e))?;
-    
-    info!("Changed mode of {} to {:o}", path, mode);
-    Ok(format!("Changed permissions of {} to {:o}", path, mode))
+        .vfs_context("Failed to hydrate range")
 }
 
-/// Get file statistics (like stat)
+/// Read a byte range for previews, fetching only the blocks not cached yet
 #[tauri::command]
-pub async fn vfs_stat(
+pub async fn vfs_read_file_range(
     source_id: String,
     path: String,
+    offset: u64,
+    length: u64,
     state: State<'_, VfsStateWrapper>,
-) -> Result<FileStatResponse, String> {
+) -> Result<Vec<u8>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let stat = service.stat(&source_id, std::path::Path::new(&path))
+    service.read_range(&source_id, std::path::Path::new(&path), offset, length)
         .await
-        .map_err(|e| format!("Failed to stat: {}", e))?;
-    
-    Ok(FileStatResponse {
-        size: stat.size,
-        is_dir: stat.is_dir,
-        is_file: stat.is_file,
-        is_symlink: stat.is_symlink,
-        mode: stat.mode,
-        mtime: stat.mtime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
-        atime: stat.atime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
-        ctime: stat.ctime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
-    })
+        .vfs_context("Failed to read range")
 }
 
-/// Touch file (create or update timestamp)
+/// The first `max_bytes` of a file and how to render them, without caching
+/// the file
 #[tauri::command]
-pub async fn vfs_touch(
+pub async fn vfs_preview(
     source_id: String,
     path: String,
+    max_bytes: u64,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<PreviewData, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.touch(&source_id, std::path::Path::new(&path))
+    service.preview(&source_id, std::path::Path::new(&path), max_bytes)
         .await
-        .map_err(|e| format!("Failed to touch: {}", e))?;
-    
-    info!("Touched: {}", path);
-    Ok(format!("Touched: {}", path))
+        .vfs_context("Failed to preview file")
 }
 
-/// Check if path exists
+/// The last `lines` lines of a text file
 #[tauri::command]
-pub async fn vfs_exists(
+pub async fn vfs_tail(
     source_id: String,
     path: String,
+    lines: usize,
     state: State<'_, VfsStateWrapper>,
-) -> Result<bool, String> {
+) -> Result<Vec<String>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.exists(&source_id, std::path::Path::new(&path))
+    service.tail(&source_id, std::path::Path::new(&path), lines)
         .await
-        .map_err(|e| format!("Failed to check existence: {}", e))
+        .vfs_context("Failed to read end of file")
 }
 
-/// Read file as text
+/// Running `vfs_tail_follow_start` follows, by follow ID; a follow removes
+/// itself when it ends
+static TAIL_FOLLOWS: Lazy<SyncRwLock<std::collections::HashMap<String, tokio_util::sync::CancellationToken>>> =
+    Lazy::new(|| SyncRwLock::new(std::collections::HashMap::new()));
+
+/// Payload of a `vfs-tail` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TailEvent {
+    pub follow_id: String,
+    pub lines: Vec<String>,
+}
+
+/// Follow a text file like `tail -f`: lines appended from now on arrive as
+/// `vfs-tail` events. Returns the follow ID to pass to `vfs_tail_follow_stop`;
+/// the follow also stops when the window that started it is closed.
 #[tauri::command]
-pub async fn vfs_read_text(
+pub async fn vfs_tail_follow_start(
     source_id: String,
     path: String,
+    app: tauri::AppHandle,
+    window: tauri::Window,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
+    use crate::vfs::application::tail::TAIL_POLL_INTERVAL;
+    use tauri::Emitter;
+    
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let bytes = service.read(&source_id, std::path::Path::new(&path))
+    let follow_id = uuid::Uuid::new_v4().to_string();
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let event_id = follow_id.clone();
+    let on_lines = Arc::new(move |lines: Vec<String>| {
+        app.emit("vfs-tail", TailEvent { follow_id: event_id.clone(), lines }).is_ok()
+    });
+    
+    let follow = service.follow_tail(&source_id, std::path::Path::new(&path), TAIL_POLL_INTERVAL, cancel.clone(), on_lines)
         .await
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+        .vfs_context("Failed to follow file")?;
+    TAIL_FOLLOWS.write().insert(follow_id.clone(), cancel.clone());
     
-    String::from_utf8(bytes)
-        .map_err(|e| format!("File is not valid UTF-8: {}", e))
+    window.on_window_event(move |event| {
+        if matches!(event, tauri::WindowEvent::Destroyed) {
+            cancel.cancel();
+        }
+    });
+    let finished_id = follow_id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = follow.await {
+            warn!("Follow {} ended abnormally: {}", finished_id, e);
+        }
+        TAIL_FOLLOWS.write().remove(&finished_id);
+    });
+    
+    Ok(follow_id)
 }
 
-/// Read file as binary (for downloads)
+/// Stop a follow started with `vfs_tail_follow_start`; returns whether it
+/// was running
 #[tauri::command]
-pub async fn vfs_read_file_bytes(
+pub async fn vfs_tail_follow_stop(follow_id: String) -> Result<bool, VfsError> {
+    let Some(cancel) = TAIL_FOLLOWS.write().remove(&follow_id) else {
+        return Ok(false);
+    };
+    cancel.cancel();
+    Ok(true)
+}
+
+/// Get a time-limited signed URL so the frontend can stream directly from S3/GCS
+#[tauri::command]
+pub async fn vfs_get_signed_url(
     source_id: String,
     path: String,
+    expiry_secs: u64,
     state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<u8>, String> {
+) -> Result<String, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let bytes = service.read(&source_id, std::path::Path::new(&path))
+    service.signed_url(&source_id, std::path::Path::new(&path), std::time::Duration::from_secs(expiry_secs))
         .await
-        .map_err(|e| format!("Failed to read file: {}", e))?;
-    
-    info!("Read {} bytes from {}", bytes.len(), path);
-    Ok(bytes)
+        .vfs_context("Failed to generate signed URL")
 }
 
-/// Download file from storage source to local filesystem
+/// List stored versions of an object in a versioned S3 bucket, newest first
 #[tauri::command]
-pub async fn vfs_download_file(
+pub async fn vfs_list_versions(
     source_id: String,
     path: String,
-    destination_path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<Vec<ObjectVersion>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    info!("Downloading file: {} -> {}", path, destination_path);
-    
-    // Track download operation
-    let tracker = get_operation_tracker();
-    let operation_id = tracker.create_operation(
-        OperationType::Download,
-        source_id.clone(),
-        path.clone(),
-        Some(destination_path.clone()),
-        None, // File size will be set after download
-    );
-    
-    // Read file from source
-    let bytes = match service.read(&source_id, std::path::Path::new(&path)).await {
-        Ok(b) => b,
-        Err(e) => {
-            let error_msg = format!("Failed to read file: {}", e);
-            let _ = tracker.fail_operation(&operation_id, error_msg.clone());
-            return Err(error_msg);
-        }
-    };
-    
-    let bytes_len = bytes.len() as u64;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Update progress
-    let _ = tracker.update_progress(&operation_id, bytes_len);
-    
-    // Write to destination
-    match std::fs::write(&destination_path, bytes) {
-        Ok(_) => {
-            let _ = tracker.complete_operation(&operation_id);
-            info!("Successfully downloaded {} bytes to {}", bytes_len, destination_path);
-            Ok(format!("Downloaded {} bytes to {}", bytes_len, destination_path))
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to write file to '{}': {}", destination_path, e);
-            let _ = tracker.fail_operation(&operation_id, error_msg.clone());
-            Err(error_msg)
-        }
-    }
+    service.list_versions(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to list versions")
 }
 
-/// Write text to file
+/// Make an earlier version of an object the current one
 #[tauri::command]
-pub async fn vfs_write_text(
+pub async fn vfs_restore_version(
     source_id: String,
     path: String,
-    content: String,
+    version_id: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    service.write(&source_id, std::path::Path::new(&path), content.as_bytes())
+    service.restore_version(&source_id, std::path::Path::new(&path), &version_id)
         .await
-        .map_err(|e| format!("Failed to write file: {}", e))?;
-    
-    info!("Wrote {} bytes to {}", content.len(), path);
-    Ok(format!("Wrote {} bytes to {}", content.len(), path))
+        .vfs_context("Failed to restore version")
 }
 
-/// Append text to file
+/// Output formats and quality presets `vfs_transcode_video` accepts
 #[tauri::command]
-pub async fn vfs_append_text(
+pub async fn vfs_supported_transcode_formats() -> Result<TranscodeOptions, VfsError> {
+    Ok(TranscodeOptions::supported())
+}
+
+/// Queue a video for transcoding (VFS version); returns the job id.
+/// Files on object storage are hydrated into the cache first. `format` and
+/// `quality` (default high) are checked before anything is fetched or queued.
+#[tauri::command]
+pub async fn vfs_transcode_video(
     source_id: String,
-    path: String,
-    content: String,
+    file_path: String,
+    format: String,
+    quality: Option<String>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
-    
-    service.append(&source_id, std::path::Path::new(&path), content.as_bytes())
-        .await
-        .map_err(|e| format!("Failed to append to file: {}", e))?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!("Appended {} bytes to {}", content.len(), path);
-    Ok(format!("Appended {} bytes to {}", content.len(), path))
+    transcode_video(&service, media_capabilities().await, &source_id, &file_path, &format, quality.as_deref()).await
 }
 
-// ============================================================================
-// Clipboard Commands - Copy/Paste between Native FS and VFS
-// ============================================================================
+async fn transcode_video(
+    service: &VfsService,
+    capabilities: &MediaCapabilities,
+    source_id: &str,
+    file_path: &str,
+    format: &str,
+    quality: Option<&str>,
+) -> Result<String, VfsError> {
+    let path = std::path::Path::new(file_path);
+    
+    let request = TranscodeRequest::parse(format, quality)?;
+    capabilities.require_ffmpeg("Transcoding")?;
+    
+    let local = match service.local_path(source_id, path) {
+        Some(local) => local,
+        None => service.hydrate_file(source_id, path)
+            .await
+            .vfs_context("Failed to fetch video for transcoding")?,
+    };
+    
+    let job = get_transcode_queue().await?.enqueue(&local, request.format, request.quality);
+    info!("Transcode queued: {} -> {:?} {:?} (job {})", file_path, request.format, request.quality, job.id);
+    Ok(job.id)
+}
 
-use crate::vfs::adapters::ClipboardAdapter;
-use crate::vfs::ports::{IClipboardService, ClipboardSource};
-use once_cell::sync::Lazy;
-use parking_lot::RwLock as SyncRwLock;
+/// Transcode jobs in submission order, and whether the queue is paused
+#[tauri::command]
+pub async fn vfs_list_transcode_jobs() -> Result<TranscodeQueueStatus, VfsError> {
+    Ok(get_transcode_queue().await?.status())
+}
 
-/// Global clipboard adapter with VfsService
-static CLIPBOARD: Lazy<SyncRwLock<Option<Arc<ClipboardAdapter>>>> = Lazy::new(|| SyncRwLock::new(None));
+/// Stop starting queued transcodes; running ones finish
+#[tauri::command]
+pub async fn vfs_pause_transcode_queue() -> Result<(), VfsError> {
+    get_transcode_queue().await?.pause();
+    Ok(())
+}
 
-/// Initialize the global clipboard with VfsService
-pub fn init_global_clipboard(vfs_service: Arc<VfsService>) {
-    let mut clipboard_lock = CLIPBOARD.write();
-    *clipboard_lock = Some(Arc::new(ClipboardAdapter::with_vfs_service(vfs_service)));
-    info!("Global clipboard initialized with VFS service");
+/// Start dispatching queued transcodes again
+#[tauri::command]
+pub async fn vfs_resume_transcode_queue() -> Result<(), VfsError> {
+    get_transcode_queue().await?.resume();
+    Ok(())
 }
 
-/// Get the global clipboard, initializing if needed
-fn get_clipboard_with_vfs(state: &VfsStateWrapper) -> Result<Arc<ClipboardAdapter>, String> {
-    // Try to get existing clipboard
-    {
-        let clipboard_lock = CLIPBOARD.read();
-        if let Some(clipboard) = clipboard_lock.as_ref() {
-            return Ok(clipboard.clone());
-        }
-    }
-    
-    // Initialize with VFS service if not yet initialized
-    if let Some(vfs) = state.get_service() {
-        let mut clipboard_lock = CLIPBOARD.write();
-        if clipboard_lock.is_none() {
-            *clipboard_lock = Some(Arc::new(ClipboardAdapter::with_vfs_service(vfs)));
-            info!("Initialized clipboard with VFS service on demand");
-        }
-        Ok(clipboard_lock.as_ref().unwrap().clone())
-    } else {
-        Err("VFS not initialized".to_string())
-    }
-}
-
-/// Get clipboard without VFS (for read-only operations)
-fn get_clipboard_readonly() -> Arc<ClipboardAdapter> {
-    let clipboard_lock = CLIPBOARD.read();
-    clipboard_lock.as_ref().cloned().unwrap_or_else(|| Arc::new(ClipboardAdapter::new()))
-}
-
-/// Generate a copy name for files/folders (e.g., "file.txt" -> "file copy.txt")
-fn generate_copy_name(original_name: &str) -> String {
-    // Check if there's an extension
-    if let Some(dot_pos) = original_name.rfind('.') {
-        let name = &original_name[..dot_pos];
-        let ext = &original_name[dot_pos..];
-        
-        // Check if already has " copy" or " copy N" suffix
-        if let Some(copy_pos) = name.rfind(" copy") {
-            let after_copy = &name[copy_pos + 5..];
-            if after_copy.is_empty() {
-                // "file copy.txt" -> "file copy 2.txt"
-                return format!("{} 2{}", name, ext);
-            } else if after_copy.starts_with(' ') {
-                // "file copy 2.txt" -> "file copy 3.txt"
-                if let Ok(num) = after_copy.trim().parse::<u32>() {
-                    return format!("{}{}", &name[..copy_pos + 5], format!(" {}{}", num + 1, ext));
-                }
-            }
-        }
-        format!("{} copy{}", name, ext)
-    } else {
-        // No extension (probably a folder)
-        if let Some(copy_pos) = original_name.rfind(" copy") {
-            let after_copy = &original_name[copy_pos + 5..];
-            if after_copy.is_empty() {
-                return format!("{} 2", original_name);
-            } else if after_copy.starts_with(' ') {
-                if let Ok(num) = after_copy.trim().parse::<u32>() {
-                    return format!("{} {}", &original_name[..copy_pos + 5], num + 1);
-                }
-            }
-        }
-        format!("{} copy", original_name)
-    }
-}
-
-/// Response for clipboard content
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ClipboardContentResponse {
-    pub operation: String,  // "copy" or "cut"
-    pub source: String,     // "native" or "vfs:source_id"
-    pub paths: Vec<String>,
-    pub file_count: usize,
+/// Cancel a queued or running transcode, killing its FFmpeg; returns whether
+/// there was one to cancel
+#[tauri::command]
+pub async fn vfs_cancel_transcode_job(job_id: String) -> Result<bool, VfsError> {
+    get_transcode_queue().await?
+        .cancel(&job_id)
+        .await
+        .vfs_context("Failed to cancel transcode")
 }
 
-/// Response for paste operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PasteResponse {
-    pub files_pasted: usize,
-    pub files_failed: usize,
-    pub pasted_paths: Vec<String>,
-    pub errors: Vec<String>,
+/// Get cache statistics (VFS version)
+#[tauri::command]
+pub async fn vfs_cache_stats(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<VfsCacheStatsResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let stats = service.cache_stats().await;
+    
+    Ok(VfsCacheStatsResponse {
+        total_size: stats.total_size,
+        max_size: stats.max_size,
+        entry_count: stats.entry_count,
+        hit_count: stats.hit_count,
+        miss_count: stats.miss_count,
+        hit_rate: stats.hit_rate(),
+        usage_percent: stats.usage_percent(),
+    })
 }
 
-/// Copy files to clipboard from VFS
+/// Clear the cache (VFS version)
 #[tauri::command]
-pub async fn vfs_clipboard_copy(
-    source_id: String,
-    paths: Vec<String>,
+pub async fn vfs_clear_cache(
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    let clipboard = get_clipboard_with_vfs(&state)?;
-    
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    clipboard.copy_files(
-        ClipboardSource::Vfs { source_id: source_id.clone() },
-        pathbufs,
-    )
+    service.clear_cache()
         .await
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        .vfs_context("Failed to clear cache")?;
     
-    info!("Copied {} files to clipboard from source {}", paths.len(), source_id);
-    Ok(format!("Copied {} files to clipboard", paths.len()))
+    Ok("Cache cleared".to_string())
 }
 
-/// Cut files to clipboard from VFS
+/// Restore the cached copy of a path deleted within the grace window;
+/// returns how many cache entries came back (0 once the window has passed)
 #[tauri::command]
-pub async fn vfs_clipboard_cut(
+pub async fn vfs_cache_undo_delete(
     source_id: String,
-    paths: Vec<String>,
+    path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    let clipboard = get_clipboard_with_vfs(&state)?;
-    
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+) -> Result<usize, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    clipboard.cut_files(
-        ClipboardSource::Vfs { source_id: source_id.clone() },
-        pathbufs,
-    )
+    service.cache_undo_delete(&source_id, &PathBuf::from(&path))
         .await
-        .map_err(|e| format!("Failed to cut to clipboard: {}", e))?;
-    
-    info!("Cut {} files to clipboard from source {}", paths.len(), source_id);
-    Ok(format!("Cut {} files to clipboard", paths.len()))
+        .vfs_context("Failed to restore cache entry")
 }
 
-/// Copy files from native filesystem to clipboard
+// ============================================================================
+// POSIX File Operations Commands
+// ============================================================================
+
+/// Request types for file operations
+#[derive(Debug, Deserialize)]
+pub struct CopyRequest {
+    pub from: String,
+    pub to: String,
+    pub overwrite: Option<bool>,
+    pub recursive: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveRequest {
+    pub from: String,
+    pub to: String,
+    pub overwrite: Option<bool>,
+}
+
+/// Response type for file stat
+#[derive(Debug, Serialize)]
+pub struct FileStatResponse {
+    pub size: u64,
+    pub is_dir: bool,
+    pub is_file: bool,
+    pub is_symlink: bool,
+    pub mode: u32,
+    pub mtime: Option<u64>,
+    pub atime: Option<u64>,
+    pub ctime: Option<u64>,
+}
+
+/// Create a directory (like mkdir)
 #[tauri::command]
-pub async fn vfs_clipboard_copy_native(
-    paths: Vec<String>,
-) -> Result<String, String> {
-    let clipboard = get_clipboard_readonly();
-    
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+pub async fn vfs_mkdir(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    clipboard.copy_files(ClipboardSource::Native, pathbufs)
+    service.mkdir(&source_id, std::path::Path::new(&path))
         .await
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        .vfs_context("Failed to create directory")?;
     
-    info!("Copied {} native files to clipboard", paths.len());
-    Ok(format!("Copied {} files to clipboard", paths.len()))
+    info!("Created directory: {}", path);
+    Ok(format!("Directory created: {}", path))
 }
 
-/// Copy files from VFS to clipboard AND export to native clipboard
-/// This enables copy from VFS -> paste in Finder/Explorer
+/// Create directory and all parents (like mkdir -p)
 #[tauri::command]
-pub async fn vfs_clipboard_copy_for_native(
+pub async fn vfs_mkdir_p(
     source_id: String,
-    paths: Vec<String>,
+    path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
-    info!("vfs_clipboard_copy_for_native: source={}, paths={:?}", source_id, paths);
-    
-    let clipboard = get_clipboard_with_vfs(&state)?;
-    info!("vfs_clipboard_copy_for_native: got clipboard adapter");
-    
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Copy to VFS clipboard - this also exports to temp and writes to native clipboard
-    clipboard.copy_files(
-        ClipboardSource::Vfs { source_id: source_id.clone() },
-        pathbufs.clone(),
-    )
+    service.mkdir_p(&source_id, std::path::Path::new(&path))
         .await
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-    
-    // Verify the clipboard was updated
-    let content = clipboard.get_clipboard().await.map_err(|e| format!("Failed to verify: {}", e))?;
-    if let Some(ref c) = content {
-        info!("vfs_clipboard_copy_for_native: verified {} paths in clipboard", c.paths.len());
-    } else {
-        warn!("vfs_clipboard_copy_for_native: clipboard appears empty after copy!");
-    }
+        .vfs_context("Failed to create directories")?;
     
-    info!("Copied {} files to VFS and native clipboard from source {}", paths.len(), source_id);
-    Ok(format!("Copied {} files to clipboard (native-compatible)", paths.len()))
+    info!("Created directory tree: {}", path);
+    Ok(format!("Directory tree created: {}", path))
 }
 
-/// Get current clipboard content
+/// Remove empty directory (like rmdir)
 #[tauri::command]
-pub async fn vfs_clipboard_get() -> Result<Option<ClipboardContentResponse>, String> {
-    let clipboard = get_clipboard_readonly();
+pub async fn vfs_rmdir(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let content = clipboard.get_clipboard()
+    service.rmdir(&source_id, std::path::Path::new(&path))
         .await
-        .map_err(|e| format!("Failed to get clipboard: {}", e))?;
+        .vfs_context("Failed to remove directory")?;
     
-    Ok(content.map(|c| ClipboardContentResponse {
-        operation: if c.is_cut() { "cut".to_string() } else { "copy".to_string() },
-        source: match c.source {
-            ClipboardSource::Native => "native".to_string(),
-            ClipboardSource::Vfs { source_id } => format!("vfs:{}", source_id),
-        },
-        paths: c.paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
-        file_count: c.paths.len(),
-    }))
+    info!("Removed directory: {}", path);
+    Ok(format!("Directory removed: {}", path))
 }
 
-/// Check if clipboard has files
+/// Rename file or directory
 #[tauri::command]
-pub async fn vfs_clipboard_has_files() -> Result<bool, String> {
-    // Check if global clipboard is initialized
-    let is_initialized = {
-        let lock = CLIPBOARD.read();
-        lock.is_some()
-    };
-    info!("vfs_clipboard_has_files: global clipboard initialized={}", is_initialized);
-    
-    let clipboard = get_clipboard_readonly();
+pub async fn vfs_rename(
+    source_id: String,
+    from: String,
+    to: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Also log what's in the clipboard
-    let content = clipboard.get_clipboard()
+    service.rename(&source_id, std::path::Path::new(&from), std::path::Path::new(&to))
         .await
-        .map_err(|e| format!("Failed to get clipboard: {}", e))?;
-    
-    if let Some(ref c) = content {
-        info!("vfs_clipboard_has_files: found {} paths in clipboard", c.paths.len());
-    } else {
-        info!("vfs_clipboard_has_files: clipboard is empty");
-    }
+        .vfs_context("Failed to rename")?;
     
-    let result = content.map(|c| !c.paths.is_empty()).unwrap_or(false);
-    
-    info!("vfs_clipboard_has_files: result={}", result);
-    Ok(result)
+    info!("Renamed: {} -> {}", from, to);
+    Ok(format!("Renamed {} to {}", from, to))
 }
 
-/// Clear clipboard
+/// Rename several files from a template such as `clip_{index:03}{ext}`.
+/// Tokens: `{index}`, `{index:NN}`, `{name}`, `{ext}`, `{date}`.
+/// Nothing is renamed if two targets collide or a target already exists.
 #[tauri::command]
-pub async fn vfs_clipboard_clear() -> Result<String, String> {
-    let clipboard = get_clipboard_readonly();
+pub async fn vfs_batch_rename(
+    source_id: String,
+    paths: Vec<String>,
+    template: String,
+    start_index: Option<u64>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<RenameMapping>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    clipboard.clear_clipboard()
-        .await
-        .map_err(|e| format!("Failed to clear clipboard: {}", e))?;
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
     
-    Ok("Clipboard cleared".to_string())
+    service.batch_rename(&source_id, &paths, &template, start_index.unwrap_or(1))
+        .await
+        .vfs_context("Failed to batch rename")
 }
 
-/// Paste clipboard content to VFS destination
+/// Copy file or directory
 #[tauri::command]
-pub async fn vfs_clipboard_paste_to_vfs(
-    dest_source_id: String,
-    dest_path: String,
+pub async fn vfs_copy(
+    source_id: String,
+    request: CopyRequest,
     state: State<'_, VfsStateWrapper>,
-) -> Result<PasteResponse, String> {
-    info!("vfs_clipboard_paste_to_vfs: dest_source_id={}, dest_path={}", dest_source_id, dest_path);
-    
-    // Get clipboard with VFS service for paste operation
-    let clipboard = get_clipboard_with_vfs(&state)?;
-    let content = clipboard.get_clipboard()
-        .await
-        .map_err(|e| format!("Failed to get clipboard: {}", e))?
-        .ok_or_else(|| "Clipboard is empty".to_string())?;
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!("vfs_clipboard_paste_to_vfs: is_cut={}, source={:?}, paths={:?}", 
-          content.is_cut(), content.source, content.paths);
+    let options = crate::vfs::ports::CopyOptions {
+        overwrite: request.overwrite.unwrap_or(false),
+        recursive: request.recursive.unwrap_or(false),
+        preserve_attributes: true,
+        follow_symlinks: false,
+    };
     
-    // Get VFS service for actual paste operation
-    let vfs_service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+    // Large single files report bytes, rate and ETA as they copy
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Copy,
+        source_id.clone(),
+        request.from.clone(),
+        Some(request.to.clone()),
+        None,
+    );
+    let progress_id = operation_id.clone();
+    let progress: Arc<CopyProgressFn> = Arc::new(move |progress: CopyProgress| {
+        let _ = get_operation_tracker().update_copy_progress(&progress_id, &progress);
+    });
     
-    let dest = std::path::Path::new(&dest_path);
-    let mut pasted_paths = Vec::new();
-    let mut errors = Vec::new();
+    let result = service.copy_with_progress(
+        &source_id,
+        std::path::Path::new(&request.from),
+        std::path::Path::new(&request.to),
+        options,
+        Some(progress),
+    ).await;
     
-    for path in &content.paths {
-        let file_name = path.file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| "unnamed".to_string());
-        let dest_file_path = dest.join(&file_name);
-        
-        let result = match &content.source {
-            ClipboardSource::Native => {
-                // Native -> VFS: copy file/directory from native path to VFS
-                copy_native_to_vfs(&vfs_service, path, &dest_source_id, dest).await
-            }
-            ClipboardSource::Vfs { source_id } => {
-                // VFS -> VFS: check if same source or different
-                if source_id == &dest_source_id {
-                    // Same source - check if source and dest are the same
-                    if path == &dest_file_path {
-                        // Pasting to same location - create a copy with new name
-                        let new_name = generate_copy_name(&file_name);
-                        let new_dest = dest.join(&new_name);
-                        let opts = crate::vfs::ports::CopyOptions {
-                            recursive: true,
-                            ..Default::default()
-                        };
-                        vfs_service.copy(source_id, path, &new_dest, opts)
-                            .await
-                            .map(|_| new_dest)
-                    } else {
-                        // Different destination - normal copy
-                        let opts = crate::vfs::ports::CopyOptions {
-                            recursive: true,
-                            ..Default::default()
-                        };
-                        vfs_service.copy(source_id, path, &dest_file_path, opts)
-                            .await
-                            .map(|_| dest_file_path.clone())
-                    }
-                } else {
-                    // Different sources - use cross-storage copy
-                    vfs_service.copy_to_source(source_id, path, &dest_source_id, &dest_file_path)
-                        .await
-                        .map(|_| dest_file_path.clone())
-                }
-            }
-        };
-        
-        match result {
-            Ok(dest) => pasted_paths.push(dest),
-            Err(e) => errors.push(format!("{:?}: {}", path, e)),
+    match result {
+        Ok(()) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Copied: {} -> {}", request.from, request.to);
+            Ok(format!("Copied {} to {}", request.from, request.to))
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to copy");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
     }
+}
+
+/// Move file or directory
+#[tauri::command]
+pub async fn vfs_move(
+    source_id: String,
+    request: MoveRequest,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Note: Cut operation removed - simple copy/paste only
-    
-    let files_pasted = pasted_paths.len();
-    let files_failed = errors.len();
+    let options = crate::vfs::ports::MoveOptions {
+        overwrite: request.overwrite.unwrap_or(false),
+    };
     
-    info!("Pasted {} files to VFS {} at {} (failed: {})", files_pasted, dest_source_id, dest_path, files_failed);
+    service.mv(
+        &source_id,
+        std::path::Path::new(&request.from),
+        std::path::Path::new(&request.to),
+        options,
+    )
+        .await
+        .vfs_context("Failed to move")?;
     
-    Ok(PasteResponse {
-        files_pasted,
-        files_failed,
-        pasted_paths: pasted_paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
-        errors,
-    })
+    info!("Moved: {} -> {}", request.from, request.to);
+    Ok(format!("Moved {} to {}", request.from, request.to))
 }
 
-/// Helper to copy native file/directory to VFS
-async fn copy_native_to_vfs(
-    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
-    source_path: &std::path::Path,
-    dest_source_id: &str,
-    dest_path: &std::path::Path,
-) -> anyhow::Result<std::path::PathBuf> {
-    let file_name = source_path.file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unnamed".to_string());
-    let dest_file_path = dest_path.join(&file_name);
+/// Delete file (like rm)
+#[tauri::command]
+pub async fn vfs_delete(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let metadata = tokio::fs::metadata(source_path).await?;
+    // Track delete operation
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Delete,
+        source_id.clone(),
+        path.clone(),
+        None,
+        None,
+    );
     
-    if metadata.is_dir() {
-        // Create directory in VFS
-        vfs.mkdir_p(dest_source_id, &dest_file_path).await?;
-        
-        // Copy contents recursively
-        let mut entries = tokio::fs::read_dir(source_path).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
-            Box::pin(copy_native_to_vfs(vfs, &entry_path, dest_source_id, &dest_file_path)).await?;
+    match service.rm(&source_id, std::path::Path::new(&path)).await {
+        Ok(_) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Deleted: {}", path);
+            Ok(format!("Deleted: {}", path))
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to delete");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
-    } else {
-        // Copy file
-        let data = tokio::fs::read(source_path).await?;
-        vfs.write(dest_source_id, &dest_file_path, &data).await?;
     }
+}
+
+/// Move a local file or folder to the system Trash / Recycle Bin.
+///
+/// Fails for non-local sources or when the OS trash is unavailable, so the UI
+/// can offer a permanent delete instead.
+#[tauri::command]
+pub async fn vfs_move_to_trash(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    Ok(dest_file_path)
+    service.trash(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to move to trash")?;
+    
+    info!("Moved to trash: {}", path);
+    Ok(format!("Moved to trash: {}", path))
 }
 
-/// Paste clipboard content to native filesystem
+/// Permanently delete everything in the system Trash / Recycle Bin
 #[tauri::command]
-pub async fn vfs_clipboard_paste_to_native(
-    dest_path: String,
+pub async fn vfs_empty_trash(
     state: State<'_, VfsStateWrapper>,
-) -> Result<PasteResponse, String> {
-    // Get clipboard with VFS service for paste operation
-    let clipboard = get_clipboard_with_vfs(&state)?;
-    let content = clipboard.get_clipboard()
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.empty_trash()
         .await
-        .map_err(|e| format!("Failed to get clipboard: {}", e))?
-        .ok_or_else(|| "Clipboard is empty".to_string())?;
+        .vfs_context("Failed to empty the trash")?;
     
-    // Get VFS service (needed for VFS->native copies)
-    let vfs_service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+    info!("Emptied the trash");
+    Ok(())
+}
+
+/// Undo the last rename, move, trash or new folder (Cmd+Z).
+///
+/// Returns the operation that was undone, or `None` when there is nothing to
+/// undo (nothing recorded yet, or a permanent delete since).
+#[tauri::command]
+pub async fn vfs_undo(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Option<RecordedOperation>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let dest = std::path::Path::new(&dest_path);
-    let mut pasted_paths = Vec::new();
-    let mut errors = Vec::new();
+    service.undo()
+        .await
+        .vfs_context("Failed to undo")
+}
+
+/// Redo the last undone operation (Shift+Cmd+Z)
+#[tauri::command]
+pub async fn vfs_redo(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Option<RecordedOperation>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    for path in &content.paths {
-        let file_name = path.file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| "unnamed".to_string());
-        let dest_file_path = dest.join(&file_name);
-        
-        let result = match &content.source {
-            ClipboardSource::Native => {
-                // Native -> Native: simple copy
-                copy_native_to_native(path, dest).await
-            }
-            ClipboardSource::Vfs { source_id } => {
-                // VFS -> Native
-                copy_vfs_to_native(&vfs_service, source_id, path, dest).await
-            }
-        };
-        
-        match result {
-            Ok(dest) => pasted_paths.push(dest),
-            Err(e) => errors.push(format!("{:?}: {}", path, e)),
+    service.redo()
+        .await
+        .vfs_context("Failed to redo")
+}
+
+/// Delete file or directory recursively (like rm -rf)
+#[tauri::command]
+pub async fn vfs_delete_recursive(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    info!("vfs_delete_recursive called: source_id={}, path={}", source_id, path);
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    // Track delete operation
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Delete,
+        source_id.clone(),
+        path.clone(),
+        None,
+        None,
+    );
+    
+    // Normalize the path
+    let normalized_path = path.trim_start_matches('/');
+    let path_obj = std::path::Path::new(normalized_path);
+    
+    info!("Attempting to delete: {:?}", path_obj);
+    
+    match service.rm_rf(&source_id, path_obj).await {
+        Ok(_) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Successfully deleted: {}", path);
+            Ok(format!("Deleted: {}", path))
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context(format!("Failed to delete '{}'", path));
+            error!("{}", error);
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
     }
-    
-    // If cut operation and all succeeded, delete sources
-    if content.is_cut() && errors.is_empty() {
-        match &content.source {
-            ClipboardSource::Native => {
-                for path in &content.paths {
-                    if let Err(e) = tokio::fs::remove_file(path).await {
-                        if let Err(e2) = tokio::fs::remove_dir_all(path).await {
-                            warn!("Failed to delete cut source {:?}: {} / {}", path, e, e2);
-                        }
-                    }
-                }
-            }
-            ClipboardSource::Vfs { source_id } => {
-                for path in &content.paths {
-                    if let Err(e) = vfs_service.rm_rf(source_id, path).await {
-                        warn!("Failed to delete cut source {:?}: {}", path, e);
-                    }
-                }
-            }
+}
+
+/// Delete a multi-selection in one go, moving items to the trash unless
+/// `permanent` is set. Items that fail are listed in the summary instead of
+/// stopping the rest.
+#[tauri::command]
+pub async fn vfs_delete_many(
+    source_id: String,
+    paths: Vec<String>,
+    recursive: bool,
+    permanent: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<DeleteSummary, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    service.delete_many(&source_id, &paths, recursive, permanent.unwrap_or(false))
+        .await
+        .vfs_context("Failed to delete")
+}
+
+/// Change file permissions (like chmod)
+#[tauri::command]
+pub async fn vfs_chmod(
+    source_id: String,
+    path: String,
+    mode: u32,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.chmod(&source_id, std::path::Path::new(&path), mode)
+        .await
+        .vfs_context("Failed to chmod")?;
+    
+    info!("Changed mode of {} to {:o}", path, mode);
+    Ok(format!("Changed permissions of {} to {:o}", path, mode))
+}
+
+/// Get file statistics (like stat)
+#[tauri::command]
+pub async fn vfs_stat(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<FileStatResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let stat = service.stat(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to stat")?;
+    
+    Ok(FileStatResponse {
+        size: stat.size,
+        is_dir: stat.is_dir,
+        is_file: stat.is_file,
+        is_symlink: stat.is_symlink,
+        mode: stat.mode,
+        mtime: stat.mtime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
+        atime: stat.atime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
+        ctime: stat.ctime.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_secs()),
+    })
+}
+
+/// Everything the info panel shows about a file or folder in one call: stat,
+/// permissions, tier and cache state, tags and rating, type, media details and,
+/// for folders, recursive totals
+#[tauri::command]
+pub async fn vfs_get_info(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<crate::vfs::application::file_info::FileInfo, VfsError> {
+    use crate::vfs::ports::IMediaService;
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    // Without FFmpeg the rest of the info is still worth showing
+    let media = get_media_adapter().await.ok().filter(|adapter| adapter.is_available());
+    
+    service.get_info(&source_id, std::path::Path::new(&path), media.as_deref().map(|m| m as &dyn IMediaService))
+        .await
+        .vfs_context("Failed to get info")
+}
+
+/// Touch file (create or update timestamp)
+#[tauri::command]
+pub async fn vfs_touch(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.touch(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to touch")?;
+    
+    info!("Touched: {}", path);
+    Ok(format!("Touched: {}", path))
+}
+
+/// Create a new file from a template (`text`, `markdown`, `json` or a user
+/// template) in `dest_dir`, returning the path it was given
+#[tauri::command]
+pub async fn vfs_new_from_template(
+    source_id: String,
+    dest_dir: String,
+    template_name: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let path = service.new_from_template(&source_id, std::path::Path::new(&dest_dir), &template_name)
+        .await
+        .vfs_context("Failed to create file")?;
+    
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Save a user template for "New Document"
+#[tauri::command]
+pub async fn vfs_register_template(
+    name: String,
+    bytes: Vec<u8>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<FileTemplate, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.register_template(&name, &bytes)
+        .await
+        .vfs_context("Failed to register template")
+}
+
+/// List built-in and user templates
+#[tauri::command]
+pub async fn vfs_list_templates(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<FileTemplate>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.list_templates()
+        .await
+        .vfs_context("Failed to list templates")
+}
+
+/// Check if path exists
+#[tauri::command]
+pub async fn vfs_exists(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<bool, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.exists(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to check existence")
+}
+
+/// Read file as text
+#[tauri::command]
+pub async fn vfs_read_text(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let bytes = service.read(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to read file")?;
+    
+    String::from_utf8(bytes)
+        .vfs_context("File is not valid UTF-8")
+}
+
+/// Read file as binary (for downloads)
+#[tauri::command]
+pub async fn vfs_read_file_bytes(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<u8>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let bytes = service.read(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to read file")?;
+    
+    info!("Read {} bytes from {}", bytes.len(), path);
+    Ok(bytes)
+}
+
+/// Download file from storage source to local filesystem
+#[tauri::command]
+pub async fn vfs_download_file(
+    source_id: String,
+    path: String,
+    destination_path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    info!("Downloading file: {} -> {}", path, destination_path);
+    
+    // Track download operation
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Download,
+        source_id.clone(),
+        path.clone(),
+        Some(destination_path.clone()),
+        None, // File size will be set after download
+    );
+    
+    // Read file from source
+    let bytes = match service.read(&source_id, std::path::Path::new(&path)).await {
+        Ok(b) => b,
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to read file");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            return Err(error);
         }
-        
-        let _ = clipboard.clear_clipboard().await;
+    };
+    
+    let bytes_len = bytes.len() as u64;
+    
+    // Update progress
+    let _ = tracker.update_progress(&operation_id, bytes_len);
+    
+    // Write to destination
+    match std::fs::write(&destination_path, bytes) {
+        Ok(_) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Successfully downloaded {} bytes to {}", bytes_len, destination_path);
+            Ok(format!("Downloaded {} bytes to {}", bytes_len, destination_path))
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context(format!("Failed to write file to '{}'", destination_path));
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
+        }
+    }
+}
+
+/// Write text to file
+///
+/// Atomic by default: the new contents go to a temp file that replaces the
+/// target once complete. Pass `atomic: false` to write in place.
+#[tauri::command]
+pub async fn vfs_write_text(
+    source_id: String,
+    path: String,
+    content: String,
+    atomic: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let path_ref = std::path::Path::new(&path);
+    if atomic.unwrap_or(true) {
+        service.write_atomic(&source_id, path_ref, content.as_bytes()).await
+    } else {
+        service.write(&source_id, path_ref, content.as_bytes()).await
+    }
+    .vfs_context("Failed to write file")?;
+    
+    info!("Wrote {} bytes to {}", content.len(), path);
+    Ok(format!("Wrote {} bytes to {}", content.len(), path))
+}
+
+/// Append text to file
+///
+/// Atomic by default, which rewrites the whole file; pass `atomic: false` to
+/// append in place (better for large logs).
+#[tauri::command]
+pub async fn vfs_append_text(
+    source_id: String,
+    path: String,
+    content: String,
+    atomic: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let path_ref = std::path::Path::new(&path);
+    if atomic.unwrap_or(true) {
+        service.append_atomic(&source_id, path_ref, content.as_bytes()).await
+    } else {
+        service.append(&source_id, path_ref, content.as_bytes()).await
     }
+    .vfs_context("Failed to append to file")?;
+    
+    info!("Appended {} bytes to {}", content.len(), path);
+    Ok(format!("Appended {} bytes to {}", content.len(), path))
+}
+
+// ============================================================================
+// Clipboard Commands - Copy/Paste between Native FS and VFS
+// ============================================================================
+
+use crate::vfs::adapters::ClipboardAdapter;
+use crate::vfs::ports::{IClipboardService, ClipboardSource};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock as SyncRwLock;
+
+/// Global clipboard adapter with VfsService
+static CLIPBOARD: Lazy<SyncRwLock<Option<Arc<ClipboardAdapter>>>> = Lazy::new(|| SyncRwLock::new(None));
+
+/// Initialize the global clipboard with VfsService
+pub fn init_global_clipboard(vfs_service: Arc<VfsService>) {
+    let mut clipboard_lock = CLIPBOARD.write();
+    *clipboard_lock = Some(Arc::new(ClipboardAdapter::with_vfs_service(vfs_service)));
+    info!("Global clipboard initialized with VFS service");
+}
+
+/// Get the global clipboard, initializing if needed
+fn get_clipboard_with_vfs(state: &VfsStateWrapper) -> Result<Arc<ClipboardAdapter>, VfsError> {
+    // Try to get existing clipboard
+    {
+        let clipboard_lock = CLIPBOARD.read();
+        if let Some(clipboard) = clipboard_lock.as_ref() {
+            return Ok(clipboard.clone());
+        }
+    }
+    
+    // Initialize with VFS service if not yet initialized
+    if let Some(vfs) = state.get_service() {
+        let mut clipboard_lock = CLIPBOARD.write();
+        if clipboard_lock.is_none() {
+            *clipboard_lock = Some(Arc::new(ClipboardAdapter::with_vfs_service(vfs)));
+            info!("Initialized clipboard with VFS service on demand");
+        }
+        Ok(clipboard_lock.as_ref().unwrap().clone())
+    } else {
+        Err(VfsError::not_initialized())
+    }
+}
+
+/// Get clipboard without VFS (for read-only operations)
+fn get_clipboard_readonly() -> Arc<ClipboardAdapter> {
+    let clipboard_lock = CLIPBOARD.read();
+    clipboard_lock.as_ref().cloned().unwrap_or_else(|| Arc::new(ClipboardAdapter::new()))
+}
+
+/// Response for clipboard content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardContentResponse {
+    pub operation: String,  // "copy" or "cut"
+    pub source: String,     // "native" or "vfs:source_id"
+    pub paths: Vec<String>,
+    pub file_count: usize,
+}
+
+/// Response for paste operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasteResponse {
+    pub files_pasted: usize,
+    pub files_failed: usize,
+    pub pasted_paths: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Copy files to clipboard from VFS
+#[tauri::command]
+pub async fn vfs_clipboard_copy(
+    source_id: String,
+    paths: Vec<String>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    
+    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    
+    clipboard.copy_files(
+        ClipboardSource::Vfs { source_id: source_id.clone() },
+        pathbufs,
+    )
+        .await
+        .vfs_context("Failed to copy to clipboard")?;
+    
+    info!("Copied {} files to clipboard from source {}", paths.len(), source_id);
+    Ok(format!("Copied {} files to clipboard", paths.len()))
+}
+
+/// Cut files to clipboard from VFS
+#[tauri::command]
+pub async fn vfs_clipboard_cut(
+    source_id: String,
+    paths: Vec<String>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    
+    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    
+    clipboard.cut_files(
+        ClipboardSource::Vfs { source_id: source_id.clone() },
+        pathbufs,
+    )
+        .await
+        .vfs_context("Failed to cut to clipboard")?;
+    
+    info!("Cut {} files to clipboard from source {}", paths.len(), source_id);
+    Ok(format!("Cut {} files to clipboard", paths.len()))
+}
+
+/// Copy files from native filesystem to clipboard
+#[tauri::command]
+pub async fn vfs_clipboard_copy_native(
+    paths: Vec<String>,
+) -> Result<String, VfsError> {
+    let clipboard = get_clipboard_readonly();
+    
+    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    
+    clipboard.copy_files(ClipboardSource::Native, pathbufs)
+        .await
+        .vfs_context("Failed to copy to clipboard")?;
+    
+    info!("Copied {} native files to clipboard", paths.len());
+    Ok(format!("Copied {} files to clipboard", paths.len()))
+}
+
+/// Copy files from VFS to clipboard AND export to native clipboard
+/// This enables copy from VFS -> paste in Finder/Explorer
+#[tauri::command]
+pub async fn vfs_clipboard_copy_for_native(
+    source_id: String,
+    paths: Vec<String>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    info!("vfs_clipboard_copy_for_native: source={}, paths={:?}", source_id, paths);
+    
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    info!("vfs_clipboard_copy_for_native: got clipboard adapter");
+    
+    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    
+    // Copy to VFS clipboard - this also exports to temp and writes to native clipboard
+    clipboard.copy_files(
+        ClipboardSource::Vfs { source_id: source_id.clone() },
+        pathbufs.clone(),
+    )
+        .await
+        .vfs_context("Failed to copy to clipboard")?;
+    
+    // Verify the clipboard was updated
+    let content = clipboard.get_clipboard().await.vfs_context("Failed to verify")?;
+    if let Some(ref c) = content {
+        info!("vfs_clipboard_copy_for_native: verified {} paths in clipboard", c.paths.len());
+    } else {
+        warn!("vfs_clipboard_copy_for_native: clipboard appears empty after copy!");
+    }
+    
+    info!("Copied {} files to VFS and native clipboard from source {}", paths.len(), source_id);
+    Ok(format!("Copied {} files to clipboard (native-compatible)", paths.len()))
+}
+
+/// Get current clipboard content
+#[tauri::command]
+pub async fn vfs_clipboard_get() -> Result<Option<ClipboardContentResponse>, VfsError> {
+    let clipboard = get_clipboard_readonly();
+    
+    let content = clipboard.get_clipboard()
+        .await
+        .vfs_context("Failed to get clipboard")?;
+    
+    Ok(content.map(|c| ClipboardContentResponse {
+        operation: if c.is_cut() { "cut".to_string() } else { "copy".to_string() },
+        source: match c.source {
+            ClipboardSource::Native => "native".to_string(),
+            ClipboardSource::Vfs { source_id } => format!("vfs:{}", source_id),
+        },
+        paths: c.paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        file_count: c.paths.len(),
+    }))
+}
+
+/// Check if clipboard has files
+#[tauri::command]
+pub async fn vfs_clipboard_has_files() -> Result<bool, VfsError> {
+    // Check if global clipboard is initialized
+    let is_initialized = {
+        let lock = CLIPBOARD.read();
+        lock.is_some()
+    };
+    info!("vfs_clipboard_has_files: global clipboard initialized={}", is_initialized);
+    
+    let clipboard = get_clipboard_readonly();
+    
+    // Also log what's in the clipboard
+    let content = clipboard.get_clipboard()
+        .await
+        .vfs_context("Failed to get clipboard")?;
+    
+    if let Some(ref c) = content {
+        info!("vfs_clipboard_has_files: found {} paths in clipboard", c.paths.len());
+    } else {
+        info!("vfs_clipboard_has_files: clipboard is empty");
+    }
+    
+    let result = content.map(|c| !c.paths.is_empty()).unwrap_or(false);
+    
+    info!("vfs_clipboard_has_files: result={}", result);
+    Ok(result)
+}
+
+/// Clear clipboard
+#[tauri::command]
+pub async fn vfs_clipboard_clear() -> Result<String, VfsError> {
+    let clipboard = get_clipboard_readonly();
+    
+    clipboard.clear_clipboard()
+        .await
+        .vfs_context("Failed to clear clipboard")?;
+    
+    Ok("Clipboard cleared".to_string())
+}
+
+/// Paste clipboard content to VFS destination
+#[tauri::command]
+pub async fn vfs_clipboard_paste_to_vfs(
+    dest_source_id: String,
+    dest_path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<PasteResponse, VfsError> {
+    info!("vfs_clipboard_paste_to_vfs: dest_source_id={}, dest_path={}", dest_source_id, dest_path);
+    
+    // Get clipboard with VFS service for paste operation
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    let content = clipboard.get_clipboard()
+        .await
+        .vfs_context("Failed to get clipboard")?
+        .ok_or_else(|| VfsError::NotFound("Clipboard is empty".to_string()))?;
+    
+    info!("vfs_clipboard_paste_to_vfs: is_cut={}, source={:?}, paths={:?}", 
+          content.is_cut(), content.source, content.paths);
+    
+    // Get VFS service for actual paste operation
+    let vfs_service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let dest = std::path::Path::new(&dest_path);
+    let mut pasted_paths = Vec::new();
+    let mut errors = Vec::new();
+    
+    for path in &content.paths {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        let dest_file_path = dest.join(&file_name);
+        
+        let result = match &content.source {
+            ClipboardSource::Native => {
+                // Native -> VFS: copy file/directory from native path to VFS
+                copy_native_to_vfs(&vfs_service, path, &dest_source_id, dest).await
+            }
+            ClipboardSource::Vfs { source_id } => {
+                // VFS -> VFS: check if same source or different
+                if source_id == &dest_source_id {
+                    // Same source - check if source and dest are the same
+                    if path == &dest_file_path {
+                        // Pasting to same location - create a copy with new name
+                        let new_name = generate_copy_name(&file_name);
+                        let new_dest = dest.join(&new_name);
+                        let opts = crate::vfs::ports::CopyOptions {
+                            recursive: true,
+                            ..Default::default()
+                        };
+                        vfs_service.copy(source_id, path, &new_dest, opts)
+                            .await
+                            .map(|_| new_dest)
+                    } else {
+                        // Different destination - normal copy
+                        let opts = crate::vfs::ports::CopyOptions {
+                            recursive: true,
+                            ..Default::default()
+                        };
+                        vfs_service.copy(source_id, path, &dest_file_path, opts)
+                            .await
+                            .map(|_| dest_file_path.clone())
+                    }
+                } else {
+                    // Different sources - use cross-storage copy
+                    vfs_service.copy_to_source(source_id, path, &dest_source_id, &dest_file_path)
+                        .await
+                        .map(|_| dest_file_path.clone())
+                }
+            }
+        };
+        
+        match result {
+            Ok(dest) => pasted_paths.push(dest),
+            Err(e) => errors.push(format!("{:?}: {}", path, e)),
+        }
+    }
+    
+    // Note: Cut operation removed - simple copy/paste only
+    
+    let files_pasted = pasted_paths.len();
+    let files_failed = errors.len();
+    
+    info!("Pasted {} files to VFS {} at {} (failed: {})", files_pasted, dest_source_id, dest_path, files_failed);
+    
+    Ok(PasteResponse {
+        files_pasted,
+        files_failed,
+        pasted_paths: pasted_paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        errors,
+    })
+}
+
+/// Helper to copy native file/directory to VFS
+async fn copy_native_to_vfs(
+    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
+    source_path: &std::path::Path,
+    dest_source_id: &str,
+    dest_path: &std::path::Path,
+) -> anyhow::Result<std::path::PathBuf> {
+    let file_name = source_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed".to_string());
+    copy_native_to_vfs_as(vfs, source_path, dest_source_id, &dest_path.join(&file_name)).await
+}
+
+/// Copy a native file/directory to exactly `dest_file_path` in the VFS
+async fn copy_native_to_vfs_as(
+    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
+    source_path: &std::path::Path,
+    dest_source_id: &str,
+    dest_file_path: &std::path::Path,
+) -> anyhow::Result<std::path::PathBuf> {
+    let dest_file_path = dest_file_path.to_path_buf();
+    let metadata = tokio::fs::metadata(source_path).await?;
+    
+    if metadata.is_dir() {
+        // Create directory in VFS
+        vfs.mkdir_p(dest_source_id, &dest_file_path).await?;
+        
+        // Copy contents recursively
+        let mut entries = tokio::fs::read_dir(source_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+            Box::pin(copy_native_to_vfs(vfs, &entry_path, dest_source_id, &dest_file_path)).await?;
+        }
+    } else {
+        // Copy file
+        let data = tokio::fs::read(source_path).await?;
+        vfs.write(dest_source_id, &dest_file_path, &data).await?;
+    }
+    
+    Ok(dest_file_path)
+}
+
+/// Import files and folders dropped from the OS file manager into a VFS folder.
+/// `conflict` decides what happens to names already taken (default: keep both).
+#[tauri::command]
+pub async fn vfs_import_paths(
+    dest_source_id: String,
+    dest_path: String,
+    native_paths: Vec<String>,
+    conflict: Option<ConflictPolicy>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<PasteResponse, VfsError> {
+    let vfs_service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let native_paths: Vec<PathBuf> = native_paths.iter().map(PathBuf::from).collect();
+    Ok(import_native_paths(
+        &vfs_service,
+        &dest_source_id,
+        std::path::Path::new(&dest_path),
+        &native_paths,
+        conflict.unwrap_or_default(),
+    ).await)
+}
+
+/// Copy each native path into `dest`, resolving name conflicts by `conflict`
+async fn import_native_paths(
+    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
+    dest_source_id: &str,
+    dest: &std::path::Path,
+    native_paths: &[PathBuf],
+    conflict: ConflictPolicy,
+) -> PasteResponse {
+    let mut pasted_paths = Vec::new();
+    let mut errors = Vec::new();
+    
+    for path in native_paths {
+        let mut file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        
+        let result = async {
+            if vfs.exists(dest_source_id, &dest.join(&file_name)).await? {
+                match conflict {
+                    ConflictPolicy::Skip => {
+                        info!("Skipping import of {:?}: {} already exists", path, file_name);
+                        return Ok(None);
+                    }
+                    ConflictPolicy::Overwrite => {}
+                    ConflictPolicy::KeepBoth => {
+                        while vfs.exists(dest_source_id, &dest.join(&file_name)).await? {
+                            file_name = generate_copy_name(&file_name);
+                        }
+                    }
+                }
+            }
+            copy_native_to_vfs_as(vfs, path, dest_source_id, &dest.join(&file_name)).await.map(Some)
+        }.await;
+        
+        match result {
+            Ok(Some(dest)) => pasted_paths.push(dest),
+            Ok(None) => {}
+            Err(e) => errors.push(format!("{:?}: {}", path, e)),
+        }
+    }
+    
+    info!("Imported {} of {} dropped items to {} at {:?}", pasted_paths.len(), native_paths.len(), dest_source_id, dest);
+    
+    PasteResponse {
+        files_pasted: pasted_paths.len(),
+        files_failed: errors.len(),
+        pasted_paths: pasted_paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        errors,
+    }
+}
+
+/// Paste clipboard content to native filesystem
+#[tauri::command]
+pub async fn vfs_clipboard_paste_to_native(
+    dest_path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<PasteResponse, VfsError> {
+    // Get clipboard with VFS service for paste operation
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    let content = clipboard.get_clipboard()
+        .await
+        .vfs_context("Failed to get clipboard")?
+        .ok_or_else(|| VfsError::NotFound("Clipboard is empty".to_string()))?;
+    
+    // Get VFS service (needed for VFS->native copies)
+    let vfs_service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let dest = std::path::Path::new(&dest_path);
+    let mut pasted_paths = Vec::new();
+    let mut errors = Vec::new();
+    
+    for path in &content.paths {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        let dest_file_path = dest.join(&file_name);
+        
+        let result = match &content.source {
+            ClipboardSource::Native => {
+                // Native -> Native: simple copy
+                copy_native_to_native(path, dest).await
+            }
+            ClipboardSource::Vfs { source_id } => {
+                // VFS -> Native
+                copy_vfs_to_native(&vfs_service, source_id, path, dest).await
+            }
+        };
+        
+        match result {
+            Ok(dest) => pasted_paths.push(dest),
+            Err(e) => errors.push(format!("{:?}: {}", path, e)),
+        }
+    }
+    
+    // If cut operation and all succeeded, delete sources
+    if content.is_cut() && errors.is_empty() {
+        match &content.source {
+            ClipboardSource::Native => {
+                for path in &content.paths {
+                    if let Err(e) = tokio::fs::remove_file(path).await {
+                        if let Err(e2) = tokio::fs::remove_dir_all(path).await {
+                            warn!("Failed to delete cut source {:?}: {} / {}", path, e, e2);
+                        }
+                    }
+                }
+            }
+            ClipboardSource::Vfs { source_id } => {
+                for path in &content.paths {
+                    if let Err(e) = vfs_service.rm_rf(source_id, path).await {
+                        warn!("Failed to delete cut source {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+        
+        let _ = clipboard.clear_clipboard().await;
+    }
+    
+    let files_pasted = pasted_paths.len();
+    let files_failed = errors.len();
+    
+    info!("Pasted {} files to native {} (failed: {})", files_pasted, dest_path, files_failed);
+    
+    Ok(PasteResponse {
+        files_pasted,
+        files_failed,
+        pasted_paths: pasted_paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        errors,
+    })
+}
+
+/// Helper to copy native file/directory to native
+async fn copy_native_to_native(
+    source_path: &std::path::Path,
+    dest_path: &std::path::Path,
+) -> anyhow::Result<std::path::PathBuf> {
+    let file_name = source_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed".to_string());
+    let dest_file_path = dest_path.join(&file_name);
+    
+    let metadata = tokio::fs::metadata(source_path).await?;
+    
+    if metadata.is_dir() {
+        // Create the directory tree first (parents before children)
+        let mut dirs = vec![(source_path.to_path_buf(), dest_file_path.clone())];
+        let mut files = Vec::new();
+        let mut next = 0;
+        while next < dirs.len() {
+            let (src_dir, dest_dir) = dirs[next].clone();
+            next += 1;
+            
+            tokio::fs::create_dir_all(&dest_dir).await?;
+            
+            let mut entries = tokio::fs::read_dir(&src_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let dest = dest_dir.join(entry.file_name());
+                if entry.file_type().await?.is_dir() {
+                    dirs.push((entry.path(), dest));
+                } else {
+                    files.push((entry.path(), dest));
+                }
+            }
+        }
+        
+        // Then copy files with bounded concurrency
+        let jobs = files.into_iter().map(|(src, dest)| async move {
+            Ok::<_, anyhow::Error>(tokio::fs::copy(&src, &dest).await?)
+        });
+        run_bounded(jobs, DEFAULT_COPY_CONCURRENCY).await?;
+    } else {
+        // Copy file
+        if let Some(parent) = dest_file_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(source_path, &dest_file_path).await?;
+    }
+    
+    Ok(dest_file_path)
+}
+
+/// Helper to copy VFS file/directory to native
+async fn copy_vfs_to_native(
+    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
+    source_id: &str,
+    source_path: &std::path::Path,
+    dest_path: &std::path::Path,
+) -> anyhow::Result<std::path::PathBuf> {
+    let file_name = source_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed".to_string());
+    let dest_file_path = dest_path.join(&file_name);
+    
+    // Check if it's a directory by listing files
+    let is_dir = match vfs.list_files(source_id, source_path, &ListOptions::default()).await {
+        Ok(files) => !files.is_empty() || source_path.to_string_lossy().ends_with('/'),
+        Err(_) => false, // Assume file if listing fails
+    };
+    
+    if is_dir {
+        // Create the directory tree first (parents before children)
+        let mut dirs = vec![(source_path.to_path_buf(), dest_file_path.clone())];
+        let mut files = Vec::new();
+        let mut next = 0;
+        while next < dirs.len() {
+            let (src_dir, dest_dir) = dirs[next].clone();
+            next += 1;
+            
+            tokio::fs::create_dir_all(&dest_dir).await?;
+            
+            for file in vfs.list_files(source_id, &src_dir, &ListOptions::default()).await? {
+                let dest = dest_dir.join(&file.name);
+                if file.is_directory {
+                    dirs.push((file.path, dest));
+                } else {
+                    files.push((file.path, dest));
+                }
+            }
+        }
+        
+        // Then copy files with bounded concurrency
+        let jobs = files.into_iter().map(|(src, dest)| async move {
+            let data = vfs.read(source_id, &src).await?;
+            tokio::fs::write(&dest, &data).await?;
+            Ok::<_, anyhow::Error>(data.len() as u64)
+        });
+        run_bounded(jobs, DEFAULT_COPY_CONCURRENCY).await?;
+    } else {
+        // Copy file
+        if let Some(parent) = dest_file_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = vfs.read(source_id, source_path).await?;
+        tokio::fs::write(&dest_file_path, data).await?;
+    }
+    
+    Ok(dest_file_path)
+}
+
+/// Read files from OS clipboard (Finder/Explorer copy)
+#[tauri::command]
+pub async fn vfs_clipboard_read_native() -> Result<Vec<String>, VfsError> {
+    let clipboard = get_clipboard_readonly();
+    
+    let paths = clipboard.read_native_clipboard()
+        .await
+        .vfs_context("Failed to read native clipboard")?;
+    
+    Ok(paths.unwrap_or_default().iter().map(|p| p.to_string_lossy().to_string()).collect())
+}
+
+/// Write files to OS clipboard (so Finder/Explorer can paste)
+#[tauri::command]
+pub async fn vfs_clipboard_write_native(
+    paths: Vec<String>,
+) -> Result<String, VfsError> {
+    let clipboard = get_clipboard_readonly();
+    
+    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    
+    clipboard.write_native_clipboard(&pathbufs)
+        .await
+        .vfs_context("Failed to write native clipboard")?;
+    
+    Ok(format!("Wrote {} files to native clipboard", paths.len()))
+}
+
+/// Copy a file's path to the OS clipboard as text.
+/// `style` is `Vfs` (logical path, default), `Native` (real path on disk) or
+/// `Uri` (`file://`, `s3://bucket/key`, `gs://bucket/key`). Returns the copied string.
+#[tauri::command]
+pub async fn vfs_copy_path(
+    source_id: String,
+    path: String,
+    style: Option<PathStyle>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let text = service.format_path(&source_id, std::path::Path::new(&path), style.unwrap_or_default())
+        .await
+        .vfs_context("Failed to format path")?;
+    
+    get_clipboard_readonly().write_text_clipboard(&text)
+        .await
+        .vfs_context("Failed to write clipboard")?;
+    
+    Ok(text)
+}
+
+/// Copy a text file's contents (not the file itself) to the OS clipboard.
+/// Files that aren't UTF-8, or are larger than 4 MB, are rejected.
+#[tauri::command]
+pub async fn vfs_clipboard_copy_content(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    
+    clipboard.copy_file_contents(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to copy file contents")?;
+    Ok(())
+}
+
+/// Copy an image file to the OS clipboard as a picture, for pasting into
+/// image editors
+#[tauri::command]
+pub async fn vfs_clipboard_copy_image(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let clipboard = get_clipboard_with_vfs(&state)?;
+    
+    clipboard.copy_image(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to copy image")?;
+    Ok(())
+}
+
+// ============================================================================
+// Tags & Favorites Commands
+// ============================================================================
+
+use crate::vfs::adapters::{JsonMetadataStore, XattrMetadataStore};
+use crate::vfs::ports::{FolderView, IMetadataStore, MetadataQuery, SmartFolder, TagStorage, TierTransition, ViewMode};
+use crate::vfs::domain::{FileTag, ColorLabel};
+
+/// Global metadata store
+static METADATA_STORE: OnceLock<tokio::sync::RwLock<Option<Arc<dyn IMetadataStore>>>> = OnceLock::new();
+
+async fn get_metadata_store() -> Result<&'static tokio::sync::RwLock<Option<Arc<dyn IMetadataStore>>>, VfsError> {
+    let store = METADATA_STORE.get_or_init(|| tokio::sync::RwLock::new(None));
+    
+    // Initialize if needed
+    {
+        let guard = store.read().await;
+        if guard.is_none() {
+            drop(guard);
+            let mut write_guard = store.write().await;
+            if write_guard.is_none() {
+                let new_store = JsonMetadataStore::default_store()
+                    .await
+                    .vfs_context("Failed to initialize metadata store")?;
+                *write_guard = Some(Arc::new(new_store));
+            }
+        }
+    }
+    
+    Ok(store)
+}
+
+/// Choose where tags on local files are stored.
+///
+/// With `xattr`, tags on local sources live in extended attributes (Finder tags
+/// on macOS) so they show up in other tools; remote sources and all other
+/// metadata keep using the app's metadata file.
+#[tauri::command]
+pub async fn vfs_set_tag_storage(
+    storage: TagStorage,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let json = JsonMetadataStore::default_store()
+        .await
+        .vfs_context("Failed to initialize metadata store")?;
+    
+    let new_store: Arc<dyn IMetadataStore> = match storage {
+        TagStorage::Json => Arc::new(json),
+        TagStorage::Xattr => {
+            // Resolve through whichever service is current when a tag is touched
+            let services = state.0.clone();
+            Arc::new(XattrMetadataStore::new(json, move |source_id, path| {
+                services.read().as_ref()?.local_path(source_id, path)
+            }))
+        }
+    };
+    
+    if let Some(service) = state.get_service() {
+        service.set_metadata_store(new_store.clone());
+    }
+    let store = METADATA_STORE.get_or_init(|| tokio::sync::RwLock::new(None));
+    *store.write().await = Some(new_store);
+    
+    info!("Tag storage set to {:?}", storage);
+    Ok(())
+}
+
+/// Response for file metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileMetadataResponse {
+    pub tags: Vec<TagResponse>,
+    pub is_favorite: bool,
+    pub color_label: Option<String>,
+    pub rating: Option<u8>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagResponse {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+/// The tier a file is effectively in (`hot`, `cold`, ...): cached files are
+/// hot, and a tier change under way shows its destination
+#[tauri::command]
+pub async fn vfs_effective_tier(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<String, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.effective_tier(&source_id, std::path::Path::new(&path))
+        .await
+        .map(|tier| tier.as_str().to_string())
+        .vfs_context("Failed to get effective tier")
+}
+
+/// Tiers a file has moved through (hydrations and tier changes), oldest first
+#[tauri::command]
+pub async fn vfs_tier_history(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<TierTransition>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.tier_history(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to read tier history")
+}
+
+/// Forget tags, ratings and other metadata of files that were deleted from
+/// the source outside the app. Returns how many entries were pruned.
+#[tauri::command]
+pub async fn vfs_vacuum_metadata(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<usize, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    service.vacuum_metadata(&source_id, store.as_ref())
+        .await
+        .vfs_context("Failed to vacuum metadata")
+}
+
+/// How the folder at `path` should be shown: its saved view, or the OS
+/// default view and sort order when it has none
+#[tauri::command]
+pub async fn vfs_get_folder_view(
+    source_id: String,
+    path: String,
+) -> Result<FolderView, VfsError> {
+    let defaults = vfs_get_os_preferences().await?;
+    let default = FolderView {
+        view_mode: ViewMode::parse(&defaults.default_view).unwrap_or_default(),
+        sort_by: serde_json::from_value(serde_json::Value::String(defaults.sort_by)).unwrap_or_default(),
+        sort_ascending: defaults.sort_ascending,
+    };
+    
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.folder_view(&source_id, std::path::Path::new(&path), default)
+        .await
+        .vfs_context("Failed to get folder view")
+}
+
+/// Remember how the folder at `path` is shown; `None` reverts to the OS default
+#[tauri::command]
+pub async fn vfs_set_folder_view(
+    source_id: String,
+    path: String,
+    view: Option<FolderView>,
+) -> Result<(), VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.set_folder_view(&source_id, std::path::Path::new(&path), view)
+        .await
+        .vfs_context("Failed to set folder view")
+}
+
+/// Get metadata for a file
+#[tauri::command]
+pub async fn vfs_get_metadata(
+    source_id: String,
+    path: String,
+) -> Result<Option<FileMetadataResponse>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let meta = store.get(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to get metadata")?;
+    
+    Ok(meta.map(|m| FileMetadataResponse {
+        tags: m.tags.iter().map(|t| TagResponse {
+            name: t.name.clone(),
+            color: t.color.clone(),
+        }).collect(),
+        is_favorite: m.is_favorite,
+        color_label: m.color_label.map(|c| c.as_str().to_string()),
+        rating: m.rating,
+        comment: m.comment,
+    }))
+}
+
+/// Add a tag to a file
+#[tauri::command]
+pub async fn vfs_add_tag(
+    source_id: String,
+    path: String,
+    tag_name: String,
+    tag_color: Option<String>,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let tag = match tag_color {
+        Some(color) => FileTag::with_color(&tag_name, color),
+        None => FileTag::new(&tag_name),
+    };
+    
+    store.add_tag(&source_id, std::path::Path::new(&path), tag)
+        .await
+        .vfs_context("Failed to add tag")?;
+    
+    info!("Added tag '{}' to {}", tag_name, path);
+    Ok(format!("Added tag '{}'", tag_name))
+}
+
+/// Remove a tag from a file
+#[tauri::command]
+pub async fn vfs_remove_tag(
+    source_id: String,
+    path: String,
+    tag_name: String,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.remove_tag(&source_id, std::path::Path::new(&path), &tag_name)
+        .await
+        .vfs_context("Failed to remove tag")?;
+    
+    info!("Removed tag '{}' from {}", tag_name, path);
+    Ok(format!("Removed tag '{}'", tag_name))
+}
+
+/// Toggle favorite status
+#[tauri::command]
+pub async fn vfs_toggle_favorite(
+    source_id: String,
+    path: String,
+) -> Result<bool, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let new_state = store.toggle_favorite(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to toggle favorite")?;
+    
+    info!("Toggled favorite for {}: {}", path, new_state);
+    Ok(new_state)
+}
+
+/// Set favorite status explicitly
+#[tauri::command]
+pub async fn vfs_set_favorite(
+    source_id: String,
+    path: String,
+    is_favorite: bool,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.set_favorite(&source_id, std::path::Path::new(&path), is_favorite)
+        .await
+        .vfs_context("Failed to set favorite")?;
+    
+    Ok(if is_favorite { "Added to favorites" } else { "Removed from favorites" }.to_string())
+}
+
+/// Set color label
+#[tauri::command]
+pub async fn vfs_set_color_label(
+    source_id: String,
+    path: String,
+    color: Option<String>,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let color_label = color.as_ref().and_then(|c| ColorLabel::from_str(c));
+    
+    store.set_color_label(&source_id, std::path::Path::new(&path), color_label)
+        .await
+        .vfs_context("Failed to set color label")?;
+    
+    Ok(format!("Set color label to {:?}", color))
+}
+
+/// Set rating (0-5)
+#[tauri::command]
+pub async fn vfs_set_rating(
+    source_id: String,
+    path: String,
+    rating: Option<u8>,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.set_rating(&source_id, std::path::Path::new(&path), rating)
+        .await
+        .vfs_context("Failed to set rating")?;
+    
+    Ok(format!("Set rating to {:?}", rating))
+}
+
+/// Set comment
+#[tauri::command]
+pub async fn vfs_set_comment(
+    source_id: String,
+    path: String,
+    comment: Option<String>,
+) -> Result<String, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.set_comment(&source_id, std::path::Path::new(&path), comment.clone())
+        .await
+        .vfs_context("Failed to set comment")?;
+    
+    Ok("Comment saved".to_string())
+}
+
+/// List all favorites for a source
+#[tauri::command]
+pub async fn vfs_list_favorites(
+    source_id: String,
+) -> Result<Vec<String>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.list_favorites(&source_id)
+        .await
+        .vfs_context("Failed to list favorites")
+}
+
+/// List files with a specific tag
+#[tauri::command]
+pub async fn vfs_list_by_tag(
+    source_id: String,
+    tag_name: String,
+) -> Result<Vec<String>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.list_by_tag(&source_id, &tag_name)
+        .await
+        .vfs_context("Failed to list by tag")
+}
+
+/// List files with a specific color label
+#[tauri::command]
+pub async fn vfs_list_by_color(
+    source_id: String,
+    color: String,
+) -> Result<Vec<String>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let color_label = ColorLabel::from_str(&color)
+        .ok_or_else(|| VfsError::InvalidInput(format!("Invalid color: {}", color)))?;
+    
+    store.list_by_color(&source_id, color_label)
+        .await
+        .vfs_context("Failed to list by color")
+}
+
+/// List all unique tags
+#[tauri::command]
+pub async fn vfs_list_all_tags(
+    source_id: String,
+) -> Result<Vec<TagResponse>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    let tags = store.list_all_tags(&source_id)
+        .await
+        .vfs_context("Failed to list tags")?;
+    
+    Ok(tags.into_iter().map(|t| TagResponse {
+        name: t.name,
+        color: t.color,
+    }).collect())
+}
+
+/// Save a smart folder (a metadata query shown like a virtual source)
+#[tauri::command]
+pub async fn vfs_create_smart_folder(
+    name: String,
+    source_id: String,
+    query: MetadataQuery,
+) -> Result<SmartFolder, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.create_smart_folder(name, source_id, query)
+        .await
+        .vfs_context("Failed to create smart folder")
+}
+
+/// List all smart folders
+#[tauri::command]
+pub async fn vfs_list_smart_folders() -> Result<Vec<SmartFolder>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.list_smart_folders()
+        .await
+        .vfs_context("Failed to list smart folders")
+}
+
+/// Run a smart folder's query and return matching paths
+#[tauri::command]
+pub async fn vfs_eval_smart_folder(
+    id: String,
+) -> Result<Vec<String>, VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.eval_smart_folder(&id)
+        .await
+        .vfs_context("Failed to evaluate smart folder")
+}
+
+/// Delete a smart folder
+#[tauri::command]
+pub async fn vfs_delete_smart_folder(
+    id: String,
+) -> Result<(), VfsError> {
+    let store_lock = get_metadata_store().await?;
+    let guard = store_lock.read().await;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    
+    store.delete_smart_folder(&id)
+        .await
+        .vfs_context("Failed to delete smart folder")
+}
+
+// ============================================================================
+// Cross-Storage Commands - Move/Copy between storage sources
+// ============================================================================
+
+/// Response for cross-storage transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossStorageTransferResponse {
+    pub bytes_transferred: u64,
+    pub source_deleted: bool,
+    pub destination_path: String,
+}
+
+/// Response for available transfer targets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferTargetResponse {
+    pub id: String,
+    pub name: String,
+    pub source_type: String,
+}
+
+/// Copy file or folder to another storage source
+#[tauri::command]
+pub async fn vfs_copy_to_source(
+    from_source_id: String,
+    from_path: String,
+    to_source_id: String,
+    to_path: String,
+    rate_limit_bytes_per_sec: Option<u64>,
+    verify: Option<bool>,
+    fail_on_case_collision: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<CrossStorageTransferResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let options = CrossStorageOptions {
+        rate_limit_bytes_per_sec,
+        verify: verify.unwrap_or(false),
+        fail_on_case_collision: fail_on_case_collision.unwrap_or(false),
+        ..CrossStorageOptions::copy()
+    };
+    
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Copy,
+        from_source_id.clone(),
+        from_path.clone(),
+        Some(format!("{}:{}", to_source_id, to_path)),
+        None,
+    );
+    let progress_id = operation_id.clone();
+    let progress: Arc<CopyProgressFn> = Arc::new(move |progress: CopyProgress| {
+        let _ = get_operation_tracker().update_copy_progress(&progress_id, &progress);
+    });
     
-    let files_pasted = pasted_paths.len();
-    let files_failed = errors.len();
+    let result = service.copy_to_source_with_progress(
+        &from_source_id,
+        std::path::Path::new(&from_path),
+        &to_source_id,
+        std::path::Path::new(&to_path),
+        &options,
+        Some(progress),
+    ).await;
+    let bytes = match result {
+        Ok(bytes) => {
+            let _ = tracker.complete_operation(&operation_id);
+            bytes
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to copy");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            return Err(error);
+        }
+    };
     
-    info!("Pasted {} files to native {} (failed: {})", files_pasted, dest_path, files_failed);
+    info!(
+        "Copied {} from {} to {}:{} ({} bytes)",
+        from_path, from_source_id, to_source_id, to_path, bytes
+    );
     
-    Ok(PasteResponse {
-        files_pasted,
-        files_failed,
-        pasted_paths: pasted_paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
-        errors,
+    Ok(CrossStorageTransferResponse {
+        bytes_transferred: bytes,
+        source_deleted: false,
+        destination_path: to_path,
     })
 }
 
-/// Helper to copy native file/directory to native
-async fn copy_native_to_native(
-    source_path: &std::path::Path,
-    dest_path: &std::path::Path,
-) -> anyhow::Result<std::path::PathBuf> {
-    let file_name = source_path.file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unnamed".to_string());
-    let dest_file_path = dest_path.join(&file_name);
+/// Copy a file to several `(source_id, folder)` destinations at once, reading
+/// it only once. Each destination reports its own success or failure.
+#[tauri::command]
+pub async fn vfs_copy_to_multiple(
+    from_source_id: String,
+    from_path: String,
+    destinations: Vec<(String, String)>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<FanOutResult>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let metadata = tokio::fs::metadata(source_path).await?;
+    let destinations: Vec<(String, std::path::PathBuf)> = destinations.into_iter()
+        .map(|(source_id, path)| (source_id, std::path::PathBuf::from(path)))
+        .collect();
+    service.copy_to_multiple(&from_source_id, std::path::Path::new(&from_path), &destinations)
+        .await
+        .vfs_context("Failed to copy")
+}
+
+/// Move file or folder to another storage source (copy + delete source)
+#[tauri::command]
+pub async fn vfs_move_to_source(
+    from_source_id: String,
+    from_path: String,
+    to_source_id: String,
+    to_path: String,
+    rate_limit_bytes_per_sec: Option<u64>,
+    verify: Option<bool>,
+    fail_on_case_collision: Option<bool>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<CrossStorageTransferResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    if metadata.is_dir() {
-        // Create directory
-        tokio::fs::create_dir_all(&dest_file_path).await?;
-        
-        // Copy contents recursively
-        let mut entries = tokio::fs::read_dir(source_path).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
-            Box::pin(copy_native_to_native(&entry_path, &dest_file_path)).await?;
-        }
-    } else {
-        // Copy file
-        if let Some(parent) = dest_file_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        tokio::fs::copy(source_path, &dest_file_path).await?;
-    }
+    let options = CrossStorageOptions {
+        rate_limit_bytes_per_sec,
+        verify: verify.unwrap_or(false),
+        fail_on_case_collision: fail_on_case_collision.unwrap_or(false),
+        ..CrossStorageOptions::r#move()
+    };
+    let bytes = service.move_to_source_with(
+        &from_source_id,
+        std::path::Path::new(&from_path),
+        &to_source_id,
+        std::path::Path::new(&to_path),
+        &options,
+    )
+        .await
+        .vfs_context("Failed to move")?;
     
-    Ok(dest_file_path)
+    info!(
+        "Moved {} from {} to {}:{} ({} bytes)",
+        from_path, from_source_id, to_source_id, to_path, bytes
+    );
+    
+    Ok(CrossStorageTransferResponse {
+        bytes_transferred: bytes,
+        source_deleted: true,
+        destination_path: to_path,
+    })
 }
 
-/// Helper to copy VFS file/directory to native
-async fn copy_vfs_to_native(
-    vfs: &std::sync::Arc<crate::vfs::application::VfsService>,
-    source_id: &str,
-    source_path: &std::path::Path,
-    dest_path: &std::path::Path,
-) -> anyhow::Result<std::path::PathBuf> {
-    let file_name = source_path.file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unnamed".to_string());
-    let dest_file_path = dest_path.join(&file_name);
+/// Cap the combined bandwidth of all cross-storage transfers.
+/// `None` or `0` removes the limit.
+#[tauri::command]
+pub async fn vfs_set_global_transfer_limit(
+    bytes_per_sec: Option<u64>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Check if it's a directory by listing files
-    let is_dir = match vfs.list_files(source_id, source_path).await {
-        Ok(files) => !files.is_empty() || source_path.to_string_lossy().ends_with('/'),
-        Err(_) => false, // Assume file if listing fails
-    };
+    service.set_global_transfer_limit(bytes_per_sec);
+    Ok(())
+}
+
+/// Get available storage sources to transfer to
+#[tauri::command]
+pub async fn vfs_get_transfer_targets(
+    exclude_source_id: Option<String>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<TransferTargetResponse>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    if is_dir {
-        // Create directory
-        tokio::fs::create_dir_all(&dest_file_path).await?;
-        
-        // List and copy contents
-        let files = vfs.list_files(source_id, source_path).await?;
-        for file in files {
-            let file_path = std::path::Path::new(&file.path);
-            Box::pin(copy_vfs_to_native(vfs, source_id, file_path, &dest_file_path)).await?;
-        }
-    } else {
-        // Copy file
-        if let Some(parent) = dest_file_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        let data = vfs.read(source_id, source_path).await?;
-        tokio::fs::write(&dest_file_path, data).await?;
-    }
+    let targets = service.get_transfer_targets(exclude_source_id.as_deref());
     
-    Ok(dest_file_path)
+    Ok(targets.into_iter().map(|s| TransferTargetResponse {
+        id: s.id,
+        name: s.name,
+        source_type: format!("{:?}", s.source_type),
+    }).collect())
 }
 
-/// Read files from OS clipboard (Finder/Explorer copy)
+/// Batch copy multiple files to another storage source
 #[tauri::command]
-pub async fn vfs_clipboard_read_native() -> Result<Vec<String>, String> {
-    let clipboard = get_clipboard_readonly();
+pub async fn vfs_batch_copy_to_source(
+    from_source_id: String,
+    from_paths: Vec<String>,
+    to_source_id: String,
+    to_path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<CrossStorageTransferResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let paths = clipboard.read_native_clipboard()
-        .await
-        .map_err(|e| format!("Failed to read native clipboard: {}", e))?;
+    let mut total_bytes = 0u64;
     
-    Ok(paths.unwrap_or_default().iter().map(|p| p.to_string_lossy().to_string()).collect())
+    for path in &from_paths {
+        let bytes = service.copy_to_source(
+            &from_source_id,
+            std::path::Path::new(path),
+            &to_source_id,
+            std::path::Path::new(&to_path),
+        )
+            .await
+            .vfs_context(format!("Failed to copy {}", path))?;
+        
+        total_bytes += bytes;
+    }
+    
+    info!(
+        "Batch copied {} files from {} to {} ({} bytes)",
+        from_paths.len(), from_source_id, to_source_id, total_bytes
+    );
+    
+    Ok(CrossStorageTransferResponse {
+        bytes_transferred: total_bytes,
+        source_deleted: false,
+        destination_path: to_path,
+    })
 }
 
-/// Write files to OS clipboard (so Finder/Explorer can paste)
+/// Batch move multiple files to another storage source
 #[tauri::command]
-pub async fn vfs_clipboard_write_native(
-    paths: Vec<String>,
-) -> Result<String, String> {
-    let clipboard = get_clipboard_readonly();
+pub async fn vfs_batch_move_to_source(
+    from_source_id: String,
+    from_paths: Vec<String>,
+    to_source_id: String,
+    to_path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<CrossStorageTransferResponse, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let pathbufs: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let mut total_bytes = 0u64;
     
-    clipboard.write_native_clipboard(&pathbufs)
-        .await
-        .map_err(|e| format!("Failed to write native clipboard: {}", e))?;
+    for path in &from_paths {
+        let bytes = service.move_to_source(
+            &from_source_id,
+            std::path::Path::new(path),
+            &to_source_id,
+            std::path::Path::new(&to_path),
+        )
+            .await
+            .vfs_context(format!("Failed to move {}", path))?;
+        
+        total_bytes += bytes;
+    }
     
-    Ok(format!("Wrote {} files to native clipboard", paths.len()))
+    info!(
+        "Batch moved {} files from {} to {} ({} bytes)",
+        from_paths.len(), from_source_id, to_source_id, total_bytes
+    );
+    
+    Ok(CrossStorageTransferResponse {
+        bytes_transferred: total_bytes,
+        source_deleted: true,
+        destination_path: to_path,
+    })
 }
 
 // ============================================================================
-// Tags & Favorites Commands
+// Storage Sync Commands - Sync between storage backends (S3 ↔ FSx ONTAP)
 // ============================================================================
 
-use crate::vfs::adapters::JsonMetadataStore;
-use crate::vfs::ports::IMetadataStore;
-use crate::vfs::domain::{FileTag, ColorLabel};
-
-/// Global metadata store
-static METADATA_STORE: OnceLock<tokio::sync::RwLock<Option<JsonMetadataStore>>> = OnceLock::new();
-
-async fn get_metadata_store() -> Result<&'static tokio::sync::RwLock<Option<JsonMetadataStore>>, String> {
-    let store = METADATA_STORE.get_or_init(|| tokio::sync::RwLock::new(None));
-    
-    // Initialize if needed
-    {
-        let guard = store.read().await;
-        if guard.is_none() {
-            drop(guard);
-            let mut write_guard = store.write().await;
-            if write_guard.is_none() {
-                let new_store = JsonMetadataStore::default_store()
-                    .await
-                    .map_err(|e| format!("Failed to initialize metadata store: {}", e))?;
-                *write_guard = Some(new_store);
-            }
-        }
-    }
-    
-    Ok(store)
+/// Sync request from frontend
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncRequestDto {
+    pub from_source_id: String,
+    pub from_paths: Vec<String>,
+    pub to_source_id: String,
+    pub to_path: String,
+    pub direction: String,
+    pub mode: String,
+    pub use_nvme_cache: Option<bool>,
+    pub delete_orphans: Option<bool>,
+    pub priority: Option<String>,
 }
 
-/// Response for file metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FileMetadataResponse {
-    pub tags: Vec<TagResponse>,
-    pub is_favorite: bool,
-    pub color_label: Option<String>,
-    pub rating: Option<u8>,
-    pub comment: Option<String>,
+/// Sync result for frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncResultDto {
+    pub files_synced: usize,
+    pub files_skipped: usize,
+    pub files_failed: usize,
+    pub bytes_transferred: u64,
+    pub files_deleted: usize,
+    pub errors: Vec<String>,
+    pub duration_ms: u64,
+    pub used_nvme_cache: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TagResponse {
+/// Sync target for frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncTargetDto {
+    pub source_id: String,
     pub name: String,
-    pub color: Option<String>,
+    pub storage_type: String,
+    pub category: String,
+    pub has_nvme_cache: bool,
+    pub has_s3_integration: bool,
+    pub supported_directions: Vec<String>,
 }
 
-/// Get metadata for a file
+/// Sync files between storage sources
+/// 
+/// Supports:
+/// - S3 ↔ FSx ONTAP block storage (leveraging native FSx-S3 integration)
+/// - Multi-select file/folder operations
+/// - NVMe cache acceleration on Windows Server 2025
 #[tauri::command]
-pub async fn vfs_get_metadata(
-    source_id: String,
-    path: String,
-) -> Result<Option<FileMetadataResponse>, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
-    
-    let meta = store.get(&source_id, std::path::Path::new(&path))
-        .await
-        .map_err(|e| format!("Failed to get metadata: {}", e))?;
+pub async fn vfs_sync(
+    request: SyncRequestDto,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SyncResultDto, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    Ok(meta.map(|m| FileMetadataResponse {
-        tags: m.tags.iter().map(|t| TagResponse {
-            name: t.name.clone(),
-            color: t.color.clone(),
-        }).collect(),
-        is_favorite: m.is_favorite,
-        color_label: m.color_label.map(|c| c.as_str().to_string()),
-        rating: m.rating,
-        comment: m.comment,
-    }))
-}
-
-/// Add a tag to a file
-#[tauri::command]
-pub async fn vfs_add_tag(
-    source_id: String,
-    path: String,
-    tag_name: String,
-    tag_color: Option<String>,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    let start = std::time::Instant::now();
+    let use_cache = request.use_nvme_cache.unwrap_or(true);
     
-    let tag = match tag_color {
-        Some(color) => FileTag::with_color(&tag_name, color),
-        None => FileTag::new(&tag_name),
-    };
+    let mut files_synced = 0usize;
+    let mut files_failed = 0usize;
+    let mut bytes_transferred = 0u64;
+    let mut files_skipped = 0usize;
+    let mut errors = Vec::new();
     
-    store.add_tag(&source_id, std::path::Path::new(&path), tag)
+    // Items whose names would merge with another item, or with a file already
+    // at the destination, on a destination that ignores case are skipped; of
+    // several new items only the first is copied
+    let to_path = std::path::Path::new(&request.to_path);
+    let incoming: Vec<PathBuf> = request.from_paths.iter()
+        .map(|path| to_path.join(std::path::Path::new(path).file_name().unwrap_or_default()))
+        .collect();
+    let collisions = service.incoming_case_collisions(&request.to_source_id, to_path, &incoming)
         .await
-        .map_err(|e| format!("Failed to add tag: {}", e))?;
+        .vfs_context("Failed to check destination names")?;
+    let mut clashes: std::collections::HashMap<PathBuf, String> = std::collections::HashMap::new();
+    for group in &collisions {
+        let first_new = group.iter().position(|p| incoming.contains(p));
+        let lands_on_existing = group.iter().any(|p| !incoming.contains(p));
+        for (index, dest) in group.iter().enumerate() {
+            if incoming.contains(dest) && (lands_on_existing || Some(index) != first_new) {
+                let others: Vec<String> = group.iter()
+                    .filter(|other| *other != dest)
+                    .map(|other| other.display().to_string())
+                    .collect();
+                clashes.insert(dest.clone(), others.join(", "));
+            }
+        }
+    }
     
-    info!("Added tag '{}' to {}", tag_name, path);
-    Ok(format!("Added tag '{}'", tag_name))
-}
-
-/// Remove a tag from a file
-#[tauri::command]
-pub async fn vfs_remove_tag(
-    source_id: String,
-    path: String,
-    tag_name: String,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    for (path, dest) in request.from_paths.iter().zip(&incoming) {
+        if let Some(others) = clashes.get(dest) {
+            files_skipped += 1;
+            errors.push(format!("{}: differs only in case from {} at the destination", path, others));
+            continue;
+        }
+        match service.copy_to_source(
+            &request.from_source_id,
+            std::path::Path::new(path),
+            &request.to_source_id,
+            to_path,
+        ).await {
+            Ok(bytes) => {
+                files_synced += 1;
+                bytes_transferred += bytes;
+            }
+            Err(e) => {
+                files_failed += 1;
+                errors.push(format!("{}: {}", path, e));
+            }
+        }
+    }
     
-    store.remove_tag(&source_id, std::path::Path::new(&path), &tag_name)
-        .await
-        .map_err(|e| format!("Failed to remove tag: {}", e))?;
+    let duration_ms = start.elapsed().as_millis() as u64;
     
-    info!("Removed tag '{}' from {}", tag_name, path);
-    Ok(format!("Removed tag '{}'", tag_name))
+    info!(
+        "Sync {} -> {}: {} files synced, {} skipped, {} failed, {} bytes in {}ms",
+        request.from_source_id, request.to_source_id,
+        files_synced, files_skipped, files_failed, bytes_transferred, duration_ms
+    );
+    
+    Ok(SyncResultDto {
+        files_synced,
+        files_skipped,
+        files_failed,
+        bytes_transferred,
+        files_deleted: 0,
+        errors,
+        duration_ms,
+        used_nvme_cache: use_cache,
+    })
 }
 
-/// Toggle favorite status
+/// Get available sync targets for a source
 #[tauri::command]
-pub async fn vfs_toggle_favorite(
+pub async fn vfs_get_sync_targets(
     source_id: String,
-    path: String,
-) -> Result<bool, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<SyncTargetDto>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let new_state = store.toggle_favorite(&source_id, std::path::Path::new(&path))
-        .await
-        .map_err(|e| format!("Failed to toggle favorite: {}", e))?;
+    let targets = service.get_transfer_targets(Some(&source_id));
     
-    info!("Toggled favorite for {}: {}", path, new_state);
-    Ok(new_state)
+    Ok(targets.into_iter().map(|s| {
+        let is_fsx = matches!(s.source_type, crate::vfs::domain::StorageSourceType::FsxOntap);
+        let is_s3 = matches!(s.source_type, crate::vfs::domain::StorageSourceType::S3);
+        
+        SyncTargetDto {
+            source_id: s.id,
+            name: s.name,
+            storage_type: format!("{:?}", s.source_type),
+            category: match s.source_type {
+                crate::vfs::domain::StorageSourceType::Local => "local".to_string(),
+                crate::vfs::domain::StorageSourceType::S3 => "cloud".to_string(),
+                crate::vfs::domain::StorageSourceType::S3Compatible => "cloud".to_string(),
+                crate::vfs::domain::StorageSourceType::Gcs => "cloud".to_string(),
+                crate::vfs::domain::StorageSourceType::AzureBlob => "cloud".to_string(),
+                crate::vfs::domain::StorageSourceType::FsxOntap => "hybrid".to_string(),
+                crate::vfs::domain::StorageSourceType::FsxN => "hybrid".to_string(),
+                crate::vfs::domain::StorageSourceType::Block => "block".to_string(),
+                crate::vfs::domain::StorageSourceType::Nas => "network".to_string(),
+                crate::vfs::domain::StorageSourceType::Smb => "network".to_string(),
+                crate::vfs::domain::StorageSourceType::Nfs => "network".to_string(),
+                crate::vfs::domain::StorageSourceType::Sftp => "network".to_string(),
+                crate::vfs::domain::StorageSourceType::WebDav => "network".to_string(),
+                crate::vfs::domain::StorageSourceType::Custom(_) => "custom".to_string(),
+            },
+            has_nvme_cache: true, // Assume available on Windows Server 2025
+            has_s3_integration: is_fsx, // FSx ONTAP has native S3 integration
+            supported_directions: if is_fsx && is_s3 {
+                vec!["ObjectToBlock".to_string(), "BlockToObject".to_string()]
+            } else {
+                vec!["ToHot".to_string(), "FromHot".to_string()]
+            },
+        }
+    }).collect())
 }
 
-/// Set favorite status explicitly
+/// Change tier of files (for S3: change storage class)
 #[tauri::command]
-pub async fn vfs_set_favorite(
+pub async fn vfs_change_tier(
     source_id: String,
-    path: String,
-    is_favorite: bool,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    paths: Vec<String>,
+    target_tier: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SyncResultDto, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    store.set_favorite(&source_id, std::path::Path::new(&path), is_favorite)
-        .await
-        .map_err(|e| format!("Failed to set favorite: {}", e))?;
+    let start = std::time::Instant::now();
     
-    Ok(if is_favorite { "Added to favorites" } else { "Removed from favorites" }.to_string())
-}
-
-/// Set color label
-#[tauri::command]
-pub async fn vfs_set_color_label(
-    source_id: String,
-    path: String,
-    color: Option<String>,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    // Parse target tier
+    // Note: "cold" maps to Instant Retrieval tier (GLACIER_IR for S3, NEARLINE for GCS, Cool for Azure)
+    // This provides consistent customer-facing tier names across all object storage providers
+    let target_tier_enum = match target_tier.as_str() {
+        "hot" | "Hot" => crate::vfs::domain::StorageTier::Hot,
+        "warm" | "Warm" => crate::vfs::domain::StorageTier::Warm,
+        "nearline" | "Nearline" => crate::vfs::domain::StorageTier::Nearline,
+        "cold" | "Cold" => {
+            // Cold tier maps to Instant Retrieval equivalents across all providers
+            // S3: GLACIER_IR, GCS: NEARLINE, Azure: Cool
+            crate::vfs::domain::StorageTier::Cold
+        }
+        "archive" | "Archive" => crate::vfs::domain::StorageTier::Archive,
+        // Legacy support for instant-retrieval (maps to Cold)
+        "instant-retrieval" | "instantretrieval" | "InstantRetrieval" | "instant_retrieval" => {
+            crate::vfs::domain::StorageTier::Cold
+        }
+        _ => return Err(VfsError::InvalidInput(format!("Invalid target tier: {}", target_tier))),
+    };
     
-    let color_label = color.as_ref().and_then(|c| ColorLabel::from_str(c));
+    info!(
+        "Tier change requested: {} files to {:?} tier on {}",
+        paths.len(), target_tier_enum, source_id
+    );
     
-    store.set_color_label(&source_id, std::path::Path::new(&path), color_label)
-        .await
-        .map_err(|e| format!("Failed to set color label: {}", e))?;
+    // Get the source
+    let source = service.get_source(&source_id)
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
     
-    Ok(format!("Set color label to {:?}", color))
-}
-
-/// Set rating (0-5)
-#[tauri::command]
-pub async fn vfs_set_rating(
-    source_id: String,
-    path: String,
-    rating: Option<u8>,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    let mut files_synced = 0;
+    let mut files_failed = 0;
+    let mut errors = Vec::new();
     
-    store.set_rating(&source_id, std::path::Path::new(&path), rating)
-        .await
-        .map_err(|e| format!("Failed to set rating: {}", e))?;
+    // Handle object storage tier changes (S3, GCS, Azure Blob)
+    if matches!(
+        source.source_type,
+        crate::vfs::domain::StorageSourceType::S3
+            | crate::vfs::domain::StorageSourceType::Gcs
+            | crate::vfs::domain::StorageSourceType::AzureBlob
+            | crate::vfs::domain::StorageSourceType::S3Compatible
+    ) {
+        let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        let report = service.change_tier(&source_id, &paths, target_tier_enum).await
+            .vfs_context("Failed to change tier")?;
+        
+        files_synced = report.changed.len();
+        files_failed = report.failed.len();
+        errors = report.failed;
+    } else {
+        // For non-object-storage sources, tier changes are handled differently
+        // (e.g., FSx ONTAP fabric-pool, NVMe cache)
+        info!("Tier change for non-object-storage source - using default implementation");
+        files_synced = paths.len();
+    }
     
-    Ok(format!("Set rating to {:?}", rating))
+    let duration_ms = start.elapsed().as_millis() as u64;
+    
+    Ok(SyncResultDto {
+        files_synced,
+        files_skipped: 0,
+        files_failed,
+        bytes_transferred: 0,
+        files_deleted: 0,
+        errors,
+        duration_ms,
+        used_nvme_cache: target_tier == "hot",
+    })
 }
 
-/// Set comment
+/// Start restoring an archived file (e.g. from Glacier) so it can be opened.
+/// Reads of archived files fail with `ARCHIVE_NOT_RETRIEVED` until the
+/// restore finishes; `tier` defaults to standard speed.
 #[tauri::command]
-pub async fn vfs_set_comment(
+pub async fn vfs_initiate_retrieval(
     source_id: String,
     path: String,
-    comment: Option<String>,
-) -> Result<String, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    tier: Option<RetrievalTier>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    store.set_comment(&source_id, std::path::Path::new(&path), comment.clone())
+    service.initiate_retrieval(&source_id, std::path::Path::new(&path), tier.unwrap_or_default())
         .await
-        .map_err(|e| format!("Failed to set comment: {}", e))?;
-    
-    Ok("Comment saved".to_string())
+        .vfs_context("Failed to start retrieval")
 }
 
-/// List all favorites for a source
+/// Check if NVMe cache is available (Windows Server 2025 Native NVMe)
 #[tauri::command]
-pub async fn vfs_list_favorites(
-    source_id: String,
-) -> Result<Vec<String>, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+pub async fn vfs_check_nvme_cache() -> Result<NvmeCacheStatusDto, VfsError> {
+    // Check for Windows Server 2025 Native NVMe support
+    // Registry key: HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Policies\Microsoft\FeatureManagement\Overrides\1176759950
     
-    store.list_favorites(&source_id)
-        .await
-        .map_err(|e| format!("Failed to list favorites: {}", e))
+    #[cfg(target_os = "windows")]
+    {
+        use std::process::Command;
+        
+        // Check if Native NVMe is enabled via registry
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        let mut cmd = Command::new("reg");
+        cmd.args(["query", 
+                 r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Policies\Microsoft\FeatureManagement\Overrides",
+                 "/v", "1176759950"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output();
+        
+        let native_nvme_enabled = output
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("0x1"))
+            .unwrap_or(false);
+        
+        return Ok(NvmeCacheStatusDto {
+            available: true,
+            native_nvme_enabled,
+            iops_improvement: if native_nvme_enabled { Some(1.8) } else { None },
+            capacity_bytes: 0, // Would query actual NVMe capacity
+            used_bytes: 0,
+            hit_rate: 0,
+        });
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(NvmeCacheStatusDto {
+            available: false,
+            native_nvme_enabled: false,
+            iops_improvement: None,
+            capacity_bytes: 0,
+            used_bytes: 0,
+            hit_rate: 0,
+        })
+    }
 }
 
-/// List files with a specific tag
+/// NVMe cache status for frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct NvmeCacheStatusDto {
+    pub available: bool,
+    pub native_nvme_enabled: bool,
+    pub iops_improvement: Option<f32>,
+    pub capacity_bytes: u64,
+    pub used_bytes: u64,
+    pub hit_rate: u8,
+}
+
+/// Set tags for a file (replaces all existing tags).
+/// Colors of tags the file already has are kept.
 #[tauri::command]
-pub async fn vfs_list_by_tag(
+pub async fn vfs_set_tags(
     source_id: String,
-    tag_name: String,
-) -> Result<Vec<String>, String> {
+    path: String,
+    tags: Vec<String>,
+) -> Result<(), VfsError> {
     let store_lock = get_metadata_store().await?;
     let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    let store = guard.as_ref().ok_or_else(|| VfsError::NotInitialized("Metadata store not initialized".to_string()))?;
+    let path = std::path::Path::new(&path);
     
-    store.list_by_tag(&source_id, &tag_name)
+    let mut metadata = store.get(&source_id, path)
+        .await
+        .vfs_context("Failed to set tags")?
+        .unwrap_or_default();
+    
+    let existing = std::mem::take(&mut metadata.tags);
+    metadata.tags = tags.into_iter()
+        .map(|name| existing.iter()
+            .find(|t| t.name == name)
+            .cloned()
+            .unwrap_or_else(|| FileTag::new(name)))
+        .collect();
+    
+    info!("Setting tags for {:?}: {:?}", path, metadata.tags);
+    
+    store.set(&source_id, path, metadata)
         .await
-        .map_err(|e| format!("Failed to list by tag: {}", e))
+        .vfs_context("Failed to set tags")
 }
 
-/// List files with a specific color label
+/// Reveal file in system file manager (Finder on macOS, Explorer on Windows)
 #[tauri::command]
-pub async fn vfs_list_by_color(
-    source_id: String,
-    color: String,
-) -> Result<Vec<String>, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+pub async fn vfs_reveal_in_finder(
+    _source_id: String,
+    path: String,
+) -> Result<(), VfsError> {
+    // For local files, reveal directly using the path
+    // For remote files, we'd need to first cache them
     
-    let color_label = ColorLabel::from_str(&color)
-        .ok_or_else(|| format!("Invalid color: {}", color))?;
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .args(["-R", &path])
+            .spawn()
+            .vfs_context("Failed to open Finder")?;
+    }
     
-    store.list_by_color(&source_id, color_label)
-        .await
-        .map_err(|e| format!("Failed to list by color: {}", e))
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        std::process::Command::new("explorer")
+            .args(["/select,", &path.replace('/', "\\")])
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+            .vfs_context("Failed to open Explorer")?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        // Try various file managers
+        let managers = ["nautilus", "dolphin", "thunar", "nemo", "xdg-open"];
+        let mut opened = false;
+        for manager in managers {
+            if std::process::Command::new(manager)
+                .arg(&path)
+                .spawn()
+                .is_ok()
+            {
+                opened = true;
+                break;
+            }
+        }
+        if !opened {
+            return Err(VfsError::Unsupported("No file manager found".to_string()));
+        }
+    }
+    
+    Ok(())
 }
 
-/// List all unique tags
+/// Open the platform terminal in a folder (or the folder containing a file).
+/// Only sources on a local filesystem are supported.
 #[tauri::command]
-pub async fn vfs_list_all_tags(
+pub async fn vfs_open_terminal(
     source_id: String,
-) -> Result<Vec<TagResponse>, String> {
-    let store_lock = get_metadata_store().await?;
-    let guard = store_lock.read().await;
-    let store = guard.as_ref().ok_or("Metadata store not initialized")?;
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let tags = store.list_all_tags(&source_id)
+    let real_path = service.get_real_path(&source_id, std::path::Path::new(&path))
         .await
-        .map_err(|e| format!("Failed to list tags: {}", e))?;
+        .vfs_context("Failed to resolve path")?;
     
-    Ok(tags.into_iter().map(|t| TagResponse {
-        name: t.name,
-        color: t.color,
-    }).collect())
+    let dir = if real_path.is_dir() {
+        real_path
+    } else {
+        real_path.parent().map(PathBuf::from).unwrap_or(real_path)
+    };
+    
+    crate::vfs::platform::open_terminal(&dir)
+        .vfs_context("Failed to open terminal")
 }
 
 // ============================================================================
-// Cross-Storage Commands - Move/Copy between storage sources
+// File Open Commands - Open files with default or specific applications
 // ============================================================================
 
-/// Response for cross-storage transfer
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CrossStorageTransferResponse {
-    pub bytes_transferred: u64,
-    pub source_deleted: bool,
-    pub destination_path: String,
-}
-
-/// Response for available transfer targets
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TransferTargetResponse {
-    pub id: String,
+pub struct AppInfo {
     pub name: String,
-    pub source_type: String,
+    pub path: String,
+    pub bundle_id: Option<String>,
+    pub icon: Option<String>,
 }
 
-/// Copy file or folder to another storage source
+/// Where a file can be opened natively: `local` with its real path, `cached`
+/// with the hydrated copy of a remote file, or `remote` when it only exists in
+/// the cloud and an app that needs a real path can't open it yet
 #[tauri::command]
-pub async fn vfs_copy_to_source(
-    from_source_id: String,
-    from_path: String,
-    to_source_id: String,
-    to_path: String,
+pub async fn vfs_resolve_path(
+    source_id: String,
+    path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<CrossStorageTransferResponse, String> {
+) -> Result<ResolvedPath, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let bytes = service.copy_to_source(
-        &from_source_id,
-        std::path::Path::new(&from_path),
-        &to_source_id,
-        std::path::Path::new(&to_path),
-    )
+    service.resolve_path(&source_id, std::path::Path::new(&path))
         .await
-        .map_err(|e| format!("Failed to copy: {}", e))?;
-    
-    info!(
-        "Copied {} from {} to {}:{} ({} bytes)",
-        from_path, from_source_id, to_source_id, to_path, bytes
-    );
-    
-    Ok(CrossStorageTransferResponse {
-        bytes_transferred: bytes,
-        source_deleted: false,
-        destination_path: to_path,
-    })
+        .vfs_context("Failed to resolve path")
 }
 
-/// Move file or folder to another storage source (copy + delete source)
+/// Open a file with the default application
 #[tauri::command]
-pub async fn vfs_move_to_source(
-    from_source_id: String,
-    from_path: String,
-    to_source_id: String,
-    to_path: String,
+pub async fn vfs_open_file(
+    source_id: String,
+    file_path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<CrossStorageTransferResponse, String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let bytes = service.move_to_source(
-        &from_source_id,
-        std::path::Path::new(&from_path),
-        &to_source_id,
-        std::path::Path::new(&to_path),
-    )
+    // Get the real path for the file
+    let real_path = service.get_real_path(&source_id, std::path::Path::new(&file_path))
         .await
-        .map_err(|e| format!("Failed to move: {}", e))?;
-    
-    info!(
-        "Moved {} from {} to {}:{} ({} bytes)",
-        from_path, from_source_id, to_source_id, to_path, bytes
-    );
-    
-    Ok(CrossStorageTransferResponse {
-        bytes_transferred: bytes,
-        source_deleted: true,
-        destination_path: to_path,
-    })
-}
-
-/// Get available storage sources to transfer to
-#[tauri::command]
-pub async fn vfs_get_transfer_targets(
-    exclude_source_id: Option<String>,
-    state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<TransferTargetResponse>, String> {
-    let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .vfs_context("Failed to resolve path")?;
     
-    let targets = service.get_transfer_targets(exclude_source_id.as_deref());
+    info!("Opening file with default app: {:?}", real_path);
     
-    Ok(targets.into_iter().map(|s| TransferTargetResponse {
-        id: s.id,
-        name: s.name,
-        source_type: format!("{:?}", s.source_type),
-    }).collect())
-}
-
-/// Batch copy multiple files to another storage source
-#[tauri::command]
-pub async fn vfs_batch_copy_to_source(
-    from_source_id: String,
-    from_paths: Vec<String>,
-    to_source_id: String,
-    to_path: String,
-    state: State<'_, VfsStateWrapper>,
-) -> Result<CrossStorageTransferResponse, String> {
-    let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&real_path)
+            .spawn()
+            .vfs_context("Failed to open file")?;
+    }
     
-    let mut total_bytes = 0u64;
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", real_path.to_str().unwrap_or("")])
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+            .vfs_context("Failed to open file")?;
+    }
     
-    for path in &from_paths {
-        let bytes = service.copy_to_source(
-            &from_source_id,
-            std::path::Path::new(path),
-            &to_source_id,
-            std::path::Path::new(&to_path),
-        )
-            .await
-            .map_err(|e| format!("Failed to copy {}: {}", path, e))?;
-        
-        total_bytes += bytes;
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&real_path)
+            .spawn()
+            .vfs_context("Failed to open file")?;
     }
     
-    info!(
-        "Batch copied {} files from {} to {} ({} bytes)",
-        from_paths.len(), from_source_id, to_source_id, total_bytes
-    );
+    service.record_recent(&source_id, std::path::Path::new(&file_path)).await;
     
-    Ok(CrossStorageTransferResponse {
-        bytes_transferred: total_bytes,
-        source_deleted: false,
-        destination_path: to_path,
-    })
+    Ok(())
 }
 
-/// Batch move multiple files to another storage source
+/// Open a file with a specific application
 #[tauri::command]
-pub async fn vfs_batch_move_to_source(
-    from_source_id: String,
-    from_paths: Vec<String>,
-    to_source_id: String,
-    to_path: String,
+pub async fn vfs_open_file_with(
+    source_id: String,
+    file_path: String,
+    app_path: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<CrossStorageTransferResponse, String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let mut total_bytes = 0u64;
+    // Get the real path for the file
+    let real_path = service.get_real_path(&source_id, std::path::Path::new(&file_path))
+        .await
+        .vfs_context("Failed to resolve path")?;
     
-    for path in &from_paths {
-        let bytes = service.move_to_source(
-            &from_source_id,
-            std::path::Path::new(path),
-            &to_source_id,
-            std::path::Path::new(&to_path),
-        )
-            .await
-            .map_err(|e| format!("Failed to move {}: {}", path, e))?;
-        
-        total_bytes += bytes;
+    info!("Opening file with app {}: {:?}", app_path, real_path);
+    
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .args(["-a", &app_path])
+            .arg(&real_path)
+            .spawn()
+            .vfs_context("Failed to open file")?;
     }
     
-    info!(
-        "Batch moved {} files from {} to {} ({} bytes)",
-        from_paths.len(), from_source_id, to_source_id, total_bytes
-    );
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new(&app_path)
+            .arg(&real_path)
+            .spawn()
+            .vfs_context("Failed to open file")?;
+    }
     
-    Ok(CrossStorageTransferResponse {
-        bytes_transferred: total_bytes,
-        source_deleted: true,
-        destination_path: to_path,
-    })
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new(&app_path)
+            .arg(&real_path)
+            .spawn()
+            .vfs_context("Failed to open file")?;
+    }
+    
+    service.record_recent(&source_id, std::path::Path::new(&file_path)).await;
+    
+    Ok(())
 }
 
 // ============================================================================
-// Storage Sync Commands - Sync between storage backends (S3 ↔ FSx ONTAP)
+// Text Diff
 // ============================================================================
 
-/// Sync request from frontend
-#[derive(Debug, Clone, Deserialize)]
-pub struct SyncRequestDto {
-    pub from_source_id: String,
-    pub from_paths: Vec<String>,
-    pub to_source_id: String,
-    pub to_path: String,
-    pub direction: String,
-    pub mode: String,
-    pub use_nvme_cache: Option<bool>,
-    pub delete_orphans: Option<bool>,
-    pub priority: Option<String>,
-}
-
-/// Sync result for frontend
-#[derive(Debug, Clone, Serialize)]
-pub struct SyncResultDto {
-    pub files_synced: usize,
-    pub files_skipped: usize,
-    pub files_failed: usize,
-    pub bytes_transferred: u64,
-    pub files_deleted: usize,
-    pub errors: Vec<String>,
-    pub duration_ms: u64,
-    pub used_nvme_cache: bool,
+/// Compare two text files (optionally on different sources) line by line
+#[tauri::command]
+pub async fn vfs_diff_text(
+    source_id_a: String,
+    path_a: String,
+    source_id_b: String,
+    path_b: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::application::text_diff::DiffHunk>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.diff_text(
+        &source_id_a,
+        std::path::Path::new(&path_a),
+        &source_id_b,
+        std::path::Path::new(&path_b),
+    )
+    .await
+    .vfs_context("Failed to diff files")
 }
 
-/// Sync target for frontend
-#[derive(Debug, Clone, Serialize)]
-pub struct SyncTargetDto {
-    pub source_id: String,
-    pub name: String,
-    pub storage_type: String,
-    pub category: String,
-    pub has_nvme_cache: bool,
-    pub has_s3_integration: bool,
-    pub supported_directions: Vec<String>,
-}
+// ============================================================================
+// Archives
+// ============================================================================
 
-/// Sync files between storage sources
-/// 
-/// Supports:
-/// - S3 ↔ FSx ONTAP block storage (leveraging native FSx-S3 integration)
-/// - Multi-select file/folder operations
-/// - NVMe cache acceleration on Windows Server 2025
+/// Compress a selection into a zip or tar.gz archive
 #[tauri::command]
-pub async fn vfs_sync(
-    request: SyncRequestDto,
+pub async fn vfs_create_archive(
+    source_id: String,
+    paths: Vec<String>,
+    dest_path: String,
+    format: crate::vfs::application::archive::ArchiveFormat,
     state: State<'_, VfsStateWrapper>,
-) -> Result<SyncResultDto, String> {
+) -> Result<u64, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let start = std::time::Instant::now();
-    let use_cache = request.use_nvme_cache.unwrap_or(true);
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Archive,
+        source_id.clone(),
+        paths.join(", "),
+        Some(dest_path.clone()),
+        None,
+    );
     
-    let mut files_synced = 0usize;
-    let mut files_failed = 0usize;
-    let mut bytes_transferred = 0u64;
-    let mut errors = Vec::new();
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+    });
     
-    for path in &request.from_paths {
-        match service.copy_to_source(
-            &request.from_source_id,
-            std::path::Path::new(path),
-            &request.to_source_id,
-            std::path::Path::new(&request.to_path),
-        ).await {
-            Ok(bytes) => {
-                files_synced += 1;
-                bytes_transferred += bytes;
-            }
-            Err(e) => {
-                files_failed += 1;
-                errors.push(format!("{}: {}", path, e));
-            }
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    match service.create_archive(&source_id, &paths, std::path::Path::new(&dest_path), format, progress).await {
+        Ok(bytes) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Created archive {} ({} bytes)", dest_path, bytes);
+            Ok(bytes)
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to create archive");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
     }
+}
+
+/// Extract a zip or tar.gz archive into a directory
+#[tauri::command]
+pub async fn vfs_extract_archive(
+    source_id: String,
+    archive_path: String,
+    dest_dir: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<usize, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let duration_ms = start.elapsed().as_millis() as u64;
-    
-    info!(
-        "Sync {} -> {}: {} files synced, {} failed, {} bytes in {}ms",
-        request.from_source_id, request.to_source_id,
-        files_synced, files_failed, bytes_transferred, duration_ms
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Extract,
+        source_id.clone(),
+        archive_path.clone(),
+        Some(dest_dir.clone()),
+        None,
     );
     
-    Ok(SyncResultDto {
-        files_synced,
-        files_skipped: 0,
-        files_failed,
-        bytes_transferred,
-        files_deleted: 0,
-        errors,
-        duration_ms,
-        used_nvme_cache: use_cache,
-    })
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+    });
+    
+    match service.extract_archive(&source_id, std::path::Path::new(&archive_path), std::path::Path::new(&dest_dir), progress).await {
+        Ok(count) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Extracted {} files from {}", count, archive_path);
+            Ok(count)
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to extract archive");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
+        }
+    }
 }
 
-/// Get available sync targets for a source
+/// One line of a checksum manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntryResponse {
+    /// Path relative to the manifest root, `/`-separated
+    pub path: String,
+    /// Hex digest
+    pub hash: String,
+}
+
+/// Hash every file under a folder (md5deep-style delivery manifest)
 #[tauri::command]
-pub async fn vfs_get_sync_targets(
+pub async fn vfs_generate_manifest(
     source_id: String,
+    root: String,
+    algo: Option<HashAlgorithm>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<SyncTargetDto>, String> {
+) -> Result<Vec<ManifestEntryResponse>, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let targets = service.get_transfer_targets(Some(&source_id));
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Checksum,
+        source_id.clone(),
+        root.clone(),
+        None,
+        None,
+    );
     
-    Ok(targets.into_iter().map(|s| {
-        let is_fsx = matches!(s.source_type, crate::vfs::domain::StorageSourceType::FsxOntap);
-        let is_s3 = matches!(s.source_type, crate::vfs::domain::StorageSourceType::S3);
-        
-        SyncTargetDto {
-            source_id: s.id,
-            name: s.name,
-            storage_type: format!("{:?}", s.source_type),
-            category: match s.source_type {
-                crate::vfs::domain::StorageSourceType::Local => "local".to_string(),
-                crate::vfs::domain::StorageSourceType::S3 => "cloud".to_string(),
-                crate::vfs::domain::StorageSourceType::S3Compatible => "cloud".to_string(),
-                crate::vfs::domain::StorageSourceType::Gcs => "cloud".to_string(),
-                crate::vfs::domain::StorageSourceType::AzureBlob => "cloud".to_string(),
-                crate::vfs::domain::StorageSourceType::FsxOntap => "hybrid".to_string(),
-                crate::vfs::domain::StorageSourceType::FsxN => "hybrid".to_string(),
-                crate::vfs::domain::StorageSourceType::Block => "block".to_string(),
-                crate::vfs::domain::StorageSourceType::Nas => "network".to_string(),
-                crate::vfs::domain::StorageSourceType::Smb => "network".to_string(),
-                crate::vfs::domain::StorageSourceType::Nfs => "network".to_string(),
-                crate::vfs::domain::StorageSourceType::Sftp => "network".to_string(),
-                crate::vfs::domain::StorageSourceType::WebDav => "network".to_string(),
-                crate::vfs::domain::StorageSourceType::Custom(_) => "custom".to_string(),
-            },
-            has_nvme_cache: true, // Assume available on Windows Server 2025
-            has_s3_integration: is_fsx, // FSx ONTAP has native S3 integration
-            supported_directions: if is_fsx && is_s3 {
-                vec!["ObjectToBlock".to_string(), "BlockToObject".to_string()]
-            } else {
-                vec!["ToHot".to_string(), "FromHot".to_string()]
-            },
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+    });
+    
+    match service.generate_manifest(&source_id, std::path::Path::new(&root), algo.unwrap_or_default(), progress).await {
+        Ok(manifest) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Generated manifest for {} ({} files)", root, manifest.len());
+            Ok(manifest.into_iter().map(|(path, hash)| ManifestEntryResponse {
+                path: path.to_string_lossy().to_string(),
+                hash,
+            }).collect())
         }
-    }).collect())
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to generate manifest");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
+        }
+    }
 }
 
-/// Change tier of files (for S3: change storage class)
+/// Verify a folder against a checksum manifest
 #[tauri::command]
-pub async fn vfs_change_tier(
+pub async fn vfs_verify_manifest(
     source_id: String,
-    paths: Vec<String>,
-    target_tier: String,
+    root: String,
+    manifest: Vec<ManifestEntryResponse>,
+    algo: Option<HashAlgorithm>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<SyncResultDto, String> {
+) -> Result<ManifestVerification, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let start = std::time::Instant::now();
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Checksum,
+        source_id.clone(),
+        root.clone(),
+        None,
+        None,
+    );
     
-    // Parse target tier
-    // Note: "cold" maps to Instant Retrieval tier (GLACIER_IR for S3, NEARLINE for GCS, Cool for Azure)
-    // This provides consistent customer-facing tier names across all object storage providers
-    let target_tier_enum = match target_tier.as_str() {
-        "hot" | "Hot" => crate::vfs::domain::StorageTier::Hot,
-        "warm" | "Warm" => crate::vfs::domain::StorageTier::Warm,
-        "nearline" | "Nearline" => crate::vfs::domain::StorageTier::Nearline,
-        "cold" | "Cold" => {
-            // Cold tier maps to Instant Retrieval equivalents across all providers
-            // S3: GLACIER_IR, GCS: NEARLINE, Azure: Cool
-            crate::vfs::domain::StorageTier::Cold
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+    });
+    
+    let manifest: Vec<(PathBuf, String)> = manifest.into_iter()
+        .map(|entry| (PathBuf::from(entry.path), entry.hash))
+        .collect();
+    
+    match service.verify_manifest(&source_id, std::path::Path::new(&root), &manifest, algo.unwrap_or_default(), progress).await {
+        Ok(result) => {
+            let _ = tracker.complete_operation(&operation_id);
+            Ok(result)
         }
-        "archive" | "Archive" => crate::vfs::domain::StorageTier::Archive,
-        // Legacy support for instant-retrieval (maps to Cold)
-        "instant-retrieval" | "instantretrieval" | "InstantRetrieval" | "instant_retrieval" => {
-            crate::vfs::domain::StorageTier::Cold
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to verify manifest");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
-        _ => return Err(format!("Invalid target tier: {}", target_tier)),
-    };
+    }
+}
+
+/// Merkle hash of a file or folder. Hashes are cached until something under
+/// them changes, so re-checking an unchanged tree reads no files; where two
+/// roots differ, hash their subfolders to find the changed branch.
+#[tauri::command]
+pub async fn vfs_tree_hash(
+    source_id: String,
+    path: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<TreeHash, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!(
-        "Tier change requested: {} files to {:?} tier on {}",
-        paths.len(), target_tier_enum, source_id
+    service.tree_hash(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to hash tree")
+}
+
+/// Find groups of identical files under a folder.
+/// Progress is reported as `duplicates:progress` events (bytes read so far).
+#[tauri::command]
+pub async fn vfs_find_duplicates(
+    source_id: String,
+    root: String,
+    app: tauri::AppHandle,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<DuplicateGroup>, VfsError> {
+    use tauri::Emitter;
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Checksum,
+        source_id.clone(),
+        root.clone(),
+        None,
+        None,
     );
     
-    // Get the source
-    let source = service.get_source(&source_id)
-        .ok_or_else(|| format!("Storage source not found: {}", source_id))?;
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+        let _ = app.emit("duplicates:progress", serde_json::json!({
+            "operation_id": progress_id,
+            "bytes_read": bytes,
+        }));
+    });
     
-    let mut files_synced = 0;
-    let mut files_failed = 0;
-    let mut errors = Vec::new();
+    match service.find_duplicates(&source_id, std::path::Path::new(&root), progress).await {
+        Ok(groups) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Found {} duplicate groups under {}", groups.len(), root);
+            Ok(groups)
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to find duplicates");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
+        }
+    }
+}
+
+/// Find groups of near-duplicate images (re-exports, rescales, small edits)
+/// under a folder. `threshold` is the number of differing hash bits still
+/// treated as the same picture (default 10). Progress is reported as
+/// `similar_images:progress` events (images looked at so far, and the total).
+#[tauri::command]
+pub async fn vfs_find_similar_images(
+    source_id: String,
+    root: String,
+    threshold: Option<u32>,
+    app: tauri::AppHandle,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<SimilarImageGroup>, VfsError> {
+    use tauri::Emitter;
     
-    // Handle object storage tier changes (S3, GCS, Azure Blob)
-    if matches!(
-        source.source_type,
-        crate::vfs::domain::StorageSourceType::S3
-            | crate::vfs::domain::StorageSourceType::Gcs
-            | crate::vfs::domain::StorageSourceType::AzureBlob
-            | crate::vfs::domain::StorageSourceType::S3Compatible
-    ) {
-        use crate::vfs::adapters::object_storage_tiering::change_object_storage_tier;
-        
-        // Create operator for tier changes using the helper function
-        let operator = create_object_storage_operator(&source)
-            .map_err(|e| format!("Failed to create object storage operator: {}", e))?;
-        
-        // Change tier for each file
-        for path in &paths {
-            let key = path.trim_start_matches('/');
-            match change_object_storage_tier(&operator, &source.source_type, key, target_tier_enum).await {
-                Ok(_) => {
-                    files_synced += 1;
-                    info!("Changed tier for: {}", path);
-                }
-                Err(e) => {
-                    files_failed += 1;
-                    let error_msg = format!("Failed to change tier for '{}': {}", path, e);
-                    errors.push(error_msg.clone());
-                    warn!("{}", error_msg);
-                }
-            }
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Checksum,
+        source_id.clone(),
+        root.clone(),
+        None,
+        None,
+    );
+    
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |done: usize, total: usize| {
+        let _ = get_operation_tracker().update_progress(&progress_id, done as u64);
+        let _ = app.emit("similar_images:progress", serde_json::json!({
+            "operation_id": progress_id,
+            "done": done,
+            "total": total,
+        }));
+    });
+    
+    let threshold = threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    match service.find_similar_images(&source_id, std::path::Path::new(&root), threshold, progress).await {
+        Ok(groups) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Found {} similar image groups under {}", groups.len(), root);
+            Ok(groups)
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to find similar images");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
-    } else {
-        // For non-object-storage sources, tier changes are handled differently
-        // (e.g., FSx ONTAP fabric-pool, NVMe cache)
-        info!("Tier change for non-object-storage source - using default implementation");
-        files_synced = paths.len();
     }
+}
+
+/// Find files and folders under `root` whose name contains `query`.
+/// Indexed sources answer from the catalog; others are crawled (and an index
+/// is started for next time).
+#[tauri::command]
+pub async fn vfs_search(
+    source_id: String,
+    query: String,
+    root: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<CatalogEntry>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    let duration_ms = start.elapsed().as_millis() as u64;
+    let root = root.unwrap_or_else(|| "/".to_string());
+    service.search(&source_id, std::path::Path::new(&root), &query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)).await
+        .vfs_context("Search failed")
+}
+
+/// Searches recently run (with their source, folder and limit), newest first
+#[tauri::command]
+pub async fn vfs_recent_searches(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::adapters::RecentSearch>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    Ok(SyncResultDto {
-        files_synced,
-        files_skipped: 0,
-        files_failed,
-        bytes_transferred: 0,
-        files_deleted: 0,
-        errors,
-        duration_ms,
-        used_nvme_cache: target_tier == "hot",
-    })
+    Ok(service.recent_searches())
 }
 
-/// Check if NVMe cache is available (Windows Server 2025 Native NVMe)
+/// Forget every recorded search
 #[tauri::command]
-pub async fn vfs_check_nvme_cache() -> Result<NvmeCacheStatusDto, String> {
-    // Check for Windows Server 2025 Native NVMe support
-    // Registry key: HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Policies\Microsoft\FeatureManagement\Overrides\1176759950
+pub async fn vfs_clear_search_history(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        
-        // Check if Native NVMe is enabled via registry
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        let mut cmd = Command::new("reg");
-        cmd.args(["query", 
-                 r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Policies\Microsoft\FeatureManagement\Overrides",
-                 "/v", "1176759950"]);
-        cmd.creation_flags(CREATE_NO_WINDOW);
-        let output = cmd.output();
-        
-        let native_nvme_enabled = output
-            .map(|o| String::from_utf8_lossy(&o.stdout).contains("0x1"))
-            .unwrap_or(false);
-        
-        return Ok(NvmeCacheStatusDto {
-            available: true,
-            native_nvme_enabled,
-            iops_improvement: if native_nvme_enabled { Some(1.8) } else { None },
-            capacity_bytes: 0, // Would query actual NVMe capacity
-            used_bytes: 0,
-            hit_rate: 0,
-        });
-    }
+    service.clear_search_history()
+        .await
+        .vfs_context("Failed to clear search history")
+}
+
+/// Rebuild a source's search index in the background
+#[tauri::command]
+pub async fn vfs_reindex_source(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(not(target_os = "windows"))]
-    {
-        Ok(NvmeCacheStatusDto {
-            available: false,
-            native_nvme_enabled: false,
-            iops_improvement: None,
-            capacity_bytes: 0,
-            used_bytes: 0,
-            hit_rate: 0,
-        })
-    }
+    service.reindex_source(&source_id)
+        .vfs_context("Failed to start indexing")
 }
 
-/// NVMe cache status for frontend
-#[derive(Debug, Clone, Serialize)]
-pub struct NvmeCacheStatusDto {
-    pub available: bool,
-    pub native_nvme_enabled: bool,
-    pub iops_improvement: Option<f32>,
-    pub capacity_bytes: u64,
-    pub used_bytes: u64,
-    pub hit_rate: u8,
+/// Whether a source is indexed, and how many entries its index holds
+#[tauri::command]
+pub async fn vfs_index_status(
+    source_id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<IndexStatus, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.index_status(&source_id)
+        .vfs_context("Failed to get index status")
 }
 
-/// Set tags for a file (replaces all existing tags)
-/// Uses simple file for now, can be extended to use metadata service
+/// Make low-res editing proxies (H.264 MP4, 1080p by default) of the selected
+/// files in a `Proxies/` folder beside each one. Jobs go through the transcode
+/// queue; progress is reported as `proxies:progress` events (files finished).
 #[tauri::command]
-pub async fn vfs_set_tags(
-    _source_id: String,
-    path: String,
-    tags: Vec<String>,
-) -> Result<(), String> {
-    // Store tags in a sidecar file or extended attributes
-    // For now, just log and return success
-    info!("Setting tags for {}: {:?}", path, tags);
+pub async fn vfs_create_proxies(
+    source_id: String,
+    paths: Vec<String>,
+    quality: Option<TranscodeQuality>,
+    app: tauri::AppHandle,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<ProxyReport, VfsError> {
+    use tauri::Emitter;
     
-    // In a full implementation, this would:
-    // 1. Store tags in extended file attributes (macOS/Linux)
-    // 2. Store in alternate data streams (Windows)
-    // 3. Or use a local database/sidecar file
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    let queue = get_transcode_queue().await?;
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
     
-    Ok(())
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Transcode,
+        source_id.clone(),
+        format!("{} files", paths.len()),
+        None,
+        Some(paths.len() as u64),
+    );
+    
+    let progress_id = operation_id.clone();
+    let progress = move |done: usize, total: usize| {
+        let _ = get_operation_tracker().update_progress(&progress_id, done as u64);
+        let _ = app.emit("proxies:progress", serde_json::json!({
+            "operation_id": progress_id,
+            "done": done,
+            "total": total,
+        }));
+    };
+    
+    let quality = quality.unwrap_or(TranscodeQuality::High);
+    match service.create_proxies(&source_id, &paths, quality, &queue, &progress).await {
+        Ok(report) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!("Created {} proxies ({} failed)", report.created.len(), report.failed.len());
+            Ok(report)
+        }
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to create proxies");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
+        }
+    }
 }
 
-/// Reveal file in system file manager (Finder on macOS, Explorer on Windows)
+/// Path of an existing editing proxy for a file, if there is one
 #[tauri::command]
-pub async fn vfs_reveal_in_finder(
-    _source_id: String,
+pub async fn vfs_get_proxy(
+    source_id: String,
     path: String,
-) -> Result<(), String> {
-    // For local files, reveal directly using the path
-    // For remote files, we'd need to first cache them
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Option<String>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .args(["-R", &path])
-            .spawn()
-            .map_err(|e| format!("Failed to open Finder: {}", e))?;
-    }
+    let proxy = service.get_proxy(&source_id, std::path::Path::new(&path))
+        .await
+        .vfs_context("Failed to look up proxy")?;
+    Ok(proxy.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Compare two folders (on the same or different sources).
+/// `deep` hashes files of equal size instead of trusting sizes alone;
+/// progress is reported as `compare:progress` events (bytes hashed so far).
+#[tauri::command]
+pub async fn vfs_compare_dirs(
+    source_a: String,
+    path_a: String,
+    source_b: String,
+    path_b: String,
+    deep: Option<bool>,
+    app: tauri::AppHandle,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<DirDiff, VfsError> {
+    use tauri::Emitter;
     
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        std::process::Command::new("explorer")
-            .args(["/select,", &path.replace('/', "\\")])
-            .creation_flags(CREATE_NO_WINDOW)
-            .spawn()
-            .map_err(|e| format!("Failed to open Explorer: {}", e))?;
-    }
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    let tracker = get_operation_tracker();
+    let operation_id = tracker.create_operation(
+        OperationType::Checksum,
+        source_a.clone(),
+        path_a.clone(),
+        Some(format!("{}:{}", source_b, path_b)),
+        None,
+    );
+    
+    let progress_id = operation_id.clone();
+    let progress = Arc::new(move |bytes: u64| {
+        let _ = get_operation_tracker().update_progress(&progress_id, bytes);
+        let _ = app.emit("compare:progress", serde_json::json!({
+            "operation_id": progress_id,
+            "bytes_hashed": bytes,
+        }));
+    });
     
-    #[cfg(target_os = "linux")]
-    {
-        // Try various file managers
-        let managers = ["nautilus", "dolphin", "thunar", "nemo", "xdg-open"];
-        let mut opened = false;
-        for manager in managers {
-            if std::process::Command::new(manager)
-                .arg(&path)
-                .spawn()
-                .is_ok()
-            {
-                opened = true;
-                break;
-            }
+    let result = service.compare_dirs(
+        &source_a,
+        std::path::Path::new(&path_a),
+        &source_b,
+        std::path::Path::new(&path_b),
+        deep.unwrap_or(false),
+        progress,
+    ).await;
+    
+    match result {
+        Ok(diff) => {
+            let _ = tracker.complete_operation(&operation_id);
+            info!(
+                "Compared {} with {}: {} only in A, {} only in B, {} differing",
+                path_a, path_b, diff.only_in_a.len(), diff.only_in_b.len(), diff.differing.len()
+            );
+            Ok(diff)
         }
-        if !opened {
-            return Err("No file manager found".to_string());
+        Err(e) => {
+            let error = VfsError::from(e).context("Failed to compare folders");
+            let _ = tracker.fail_operation(&operation_id, error.to_string());
+            Err(error)
         }
     }
-    
-    Ok(())
 }
 
 // ============================================================================
-// File Open Commands - Open files with default or specific applications
+// Recents
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppInfo {
-    pub name: String,
-    pub path: String,
-    pub bundle_id: Option<String>,
-    pub icon: Option<String>,
+/// List recently opened/accessed files, newest first
+#[tauri::command]
+pub async fn vfs_list_recents(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::adapters::RecentEntry>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.list_recents()
+        .await
+        .vfs_context("Failed to list recents")
 }
 
-/// Open a file with the default application
+/// Clear the Recents list
 #[tauri::command]
-pub async fn vfs_open_file(
-    source_id: String,
-    file_path: String,
+pub async fn vfs_clear_recents(
     state: State<'_, VfsStateWrapper>,
-) -> Result<(), String> {
+) -> Result<(), VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Get the real path for the file
-    let real_path = service.get_real_path(&source_id, std::path::Path::new(&file_path))
+    service.clear_recents()
         .await
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    
-    info!("Opening file with default app: {:?}", real_path);
-    
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .arg(&real_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
-    
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        std::process::Command::new("cmd")
-            .args(["/C", "start", "", real_path.to_str().unwrap_or("")])
-            .creation_flags(CREATE_NO_WINDOW)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
-    
-    #[cfg(target_os = "linux")]
-    {
-        std::process::Command::new("xdg-open")
-            .arg(&real_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
+        .vfs_context("Failed to clear recents")
+}
+
+/// List folders recently copied or moved into, newest first
+#[tauri::command]
+pub async fn vfs_recent_destinations(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::adapters::RecentEntry>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    Ok(())
+    service.recent_destinations()
+        .await
+        .vfs_context("Failed to list recent destinations")
 }
 
-/// Open a file with a specific application
+// ============================================================================
+// Bookmarks
+// ============================================================================
+
+/// Pin a folder to the sidebar
 #[tauri::command]
-pub async fn vfs_open_file_with(
+pub async fn vfs_add_bookmark(
     source_id: String,
-    file_path: String,
-    app_path: String,
+    path: String,
+    name: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<(), String> {
+) -> Result<crate::vfs::adapters::Bookmark, VfsError> {
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
-    // Get the real path for the file
-    let real_path = service.get_real_path(&source_id, std::path::Path::new(&file_path))
+    service.add_bookmark(&source_id, &PathBuf::from(&path), &name)
         .await
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+        .vfs_context("Failed to add bookmark")
+}
+
+/// Unpin a bookmark
+#[tauri::command]
+pub async fn vfs_remove_bookmark(
+    id: String,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    info!("Opening file with app {}: {:?}", app_path, real_path);
+    service.remove_bookmark(&id)
+        .await
+        .vfs_context("Failed to remove bookmark")
+}
+
+/// List bookmarks in sidebar order; those whose target is gone are marked `stale`
+#[tauri::command]
+pub async fn vfs_list_bookmarks(
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::adapters::Bookmark>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .args(["-a", &app_path])
-            .arg(&real_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
+    service.list_bookmarks()
+        .await
+        .vfs_context("Failed to list bookmarks")
+}
+
+/// Reorder bookmarks by id, returning the new list
+#[tauri::command]
+pub async fn vfs_reorder_bookmarks(
+    order: Vec<String>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<crate::vfs::adapters::Bookmark>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new(&app_path)
-            .arg(&real_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
+    service.reorder_bookmarks(&order)
+        .await
+        .vfs_context("Failed to reorder bookmarks")
+}
+
+// ============================================================================
+// Audit Log
+// ============================================================================
+
+/// Dump audit entries (deletes, moves, renames) in a time range, oldest first.
+/// Either bound may be omitted; timestamps are RFC 3339.
+#[tauri::command]
+pub async fn vfs_export_audit_log(
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<AuditEntry>, VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
     
-    #[cfg(target_os = "linux")]
-    {
-        std::process::Command::new(&app_path)
-            .arg(&real_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
+    service.export_audit_log(from, to)
+        .await
+        .vfs_context("Failed to export audit log")
+}
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+/// Change the app's log level at runtime: `off`, `error`, `warn`, `info`,
+/// `debug` or `trace`
+#[tauri::command]
+pub async fn vfs_set_log_level(level: String) -> Result<(), VfsError> {
+    let level: tracing_subscriber::filter::LevelFilter = level.parse().map_err(|_| VfsError::InvalidInput(format!(
+        "Unknown log level '{}', expected one of: off, error, warn, info, debug, trace",
+        level
+    )))?;
+    
+    crate::logging::set_level(level)
+        .vfs_context("Failed to set log level")
+}
+
+/// Zip the last week of log files into the Downloads folder for a bug
+/// report. Returns the path of the zip.
+#[tauri::command]
+pub async fn vfs_export_logs() -> Result<String, VfsError> {
+    let dest_dir = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+    let dest = dest_dir.join(format!("ursly-logs-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S")));
     
-    Ok(())
+    let zip_path = dest.clone();
+    let count = tokio::task::spawn_blocking(move || crate::logging::export_logs(&crate::logging::log_dir(), &zip_path))
+        .await
+        .vfs_context("Failed to spawn blocking task")?
+        .vfs_context("Failed to export logs")?;
+    
+    info!("Exported {} log files to {:?}", count, dest);
+    Ok(dest.to_string_lossy().to_string())
 }
 
 /// Get list of applications that can open a file type
 #[tauri::command]
 pub async fn vfs_get_apps_for_file(
     file_path: String,
-) -> Result<Vec<AppInfo>, String> {
+) -> Result<Vec<AppInfo>, VfsError> {
     let extension = std::path::Path::new(&file_path)
         .extension()
         .and_then(|e| e.to_str())
@@ -3421,9 +5924,17 @@ pub struct OsPreferences {
     pub platform: String,
 }
 
+/// The OS "show hidden files" setting, read once per run so listings don't
+/// shell out to `defaults` each time
+static OS_SHOW_HIDDEN_FILES: Lazy<bool> = Lazy::new(|| os_preferences().map(|p| p.show_hidden_files).unwrap_or(false));
+
 /// Get OS file system preferences
 #[tauri::command]
-pub async fn vfs_get_os_preferences() -> Result<OsPreferences, String> {
+pub async fn vfs_get_os_preferences() -> Result<OsPreferences, VfsError> {
+    os_preferences()
+}
+
+fn os_preferences() -> Result<OsPreferences, VfsError> {
     #[cfg(target_os = "macos")]
     {
         get_macos_preferences()
@@ -3450,7 +5961,7 @@ pub async fn vfs_get_os_preferences() -> Result<OsPreferences, String> {
 }
 
 #[cfg(target_os = "macos")]
-fn get_macos_preferences() -> Result<OsPreferences, String> {
+fn get_macos_preferences() -> Result<OsPreferences, VfsError> {
     use std::process::Command;
     
     // Read Finder preferences from defaults
@@ -3509,7 +6020,7 @@ fn get_macos_preferences() -> Result<OsPreferences, String> {
 }
 
 #[cfg(target_os = "windows")]
-fn get_windows_preferences() -> Result<OsPreferences, String> {
+fn get_windows_preferences() -> Result<OsPreferences, VfsError> {
     // Windows Explorer preferences from registry
     // HKCU\Software\Microsoft\Windows\CurrentVersion\Explorer\Advanced
     
@@ -3565,12 +6076,12 @@ pub async fn vfs_get_thumbnail(
     file_path: String,
     size: Option<u32>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, VfsError> {
     use crate::vfs::adapters::native_thumbnail::{NativeThumbnailAdapter, ThumbnailType};
     use data_encoding::BASE64;
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     let thumb_size = size.unwrap_or(128);
     let path = std::path::Path::new(&file_path);
@@ -3587,7 +6098,7 @@ pub async fn vfs_get_thumbnail(
     
     // Get the source to determine how to get the thumbnail
     let source = service.get_source(&source_id)
-        .ok_or_else(|| "Source not found".to_string())?;
+        .ok_or_else(|| VfsError::NotFound("Source not found".to_string()))?;
     
     // For local/mounted sources, use native OS thumbnail generation
     if let Some(ref mount_point) = source.mount_point {
@@ -3595,9 +6106,7 @@ pub async fn vfs_get_thumbnail(
         
         if full_path.exists() {
             // Create thumbnail adapter
-            let cache_dir = dirs::cache_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("/tmp"))
-                .join("ursly-thumbnails");
+            let cache_dir = scratch_path("thumbnails");
             
             match NativeThumbnailAdapter::new(cache_dir).await {
                 Ok(adapter) => {
@@ -3620,13 +6129,197 @@ pub async fn vfs_get_thumbnail(
         }
     }
     
-    // For network/object storage without local mount, could call API
-    // For now, return None and let frontend handle with a placeholder
-    // TODO: Add API-based thumbnail fetching for cloud storage
+    // Object storage: download small images and thumbnail them here. Anything
+    // else gets None and the frontend shows the type icon.
+    if source.mount_point.is_none() && thumb_type == ThumbnailType::Image {
+        let thumb = service.remote_thumbnail(&source_id, path, thumb_size, &scratch_path("thumbnails").join("remote")).await
+            .vfs_context("Failed to generate remote thumbnail")?;
+        return Ok(thumb.map(|png| format!("data:image/png;base64,{}", BASE64.encode(&png))));
+    }
     
     Ok(None)
 }
 
+/// What the installed FFmpeg supports, probed on first use
+static MEDIA_CAPABILITIES: tokio::sync::OnceCell<MediaCapabilities> = tokio::sync::OnceCell::const_new();
+
+async fn media_capabilities() -> &'static MediaCapabilities {
+    MEDIA_CAPABILITIES.get_or_init(MediaCapabilities::probe).await
+}
+
+/// Probe FFmpeg in the background so the first media command doesn't wait for it
+pub fn start_media_probe() {
+    tauri::async_runtime::spawn(media_capabilities());
+}
+
+/// Whether FFmpeg is installed and which encoders it has, so the frontend
+/// can hide media features that can't work
+#[tauri::command]
+pub async fn vfs_media_capabilities() -> Result<MediaCapabilities, VfsError> {
+    Ok(media_capabilities().await.clone())
+}
+
+/// Global FFmpeg adapter for preview sprite sheets
+static MEDIA_ADAPTER: Lazy<SyncRwLock<Option<Arc<FfmpegMediaAdapter>>>> = Lazy::new(|| SyncRwLock::new(None));
+
+/// Get or initialize the global FFmpeg adapter
+async fn get_media_adapter() -> Result<Arc<FfmpegMediaAdapter>, VfsError> {
+    if let Some(adapter) = MEDIA_ADAPTER.read().as_ref() {
+        return Ok(adapter.clone());
+    }
+    
+    let output_dir = scratch_path("media");
+    let adapter = Arc::new(FfmpegMediaAdapter::new(output_dir).await
+        .vfs_context("Failed to initialize FFmpeg")?);
+    *MEDIA_ADAPTER.write() = Some(adapter.clone());
+    
+    Ok(adapter)
+}
+
+static TRANSCODE_QUEUE: Lazy<SyncRwLock<Option<Arc<TranscodeQueue>>>> = Lazy::new(|| SyncRwLock::new(None));
+
+/// Get or initialize the global transcode queue, run by the FFmpeg adapter
+async fn get_transcode_queue() -> Result<Arc<TranscodeQueue>, VfsError> {
+    if let Some(queue) = TRANSCODE_QUEUE.read().as_ref() {
+        return Ok(queue.clone());
+    }
+    
+    let adapter = get_media_adapter().await?;
+    let output_dir = scratch_path("transcodes");
+    
+    let mut slot = TRANSCODE_QUEUE.write();
+    let queue = slot.get_or_insert_with(|| {
+        Arc::new(TranscodeQueue::new(adapter, output_dir, DEFAULT_TRANSCODE_CONCURRENCY))
+    });
+    Ok(queue.clone())
+}
+
+/// Write clipboard exports, transcodes and other scratch files under `path`
+/// from now on. Fails unless `path` is writable and has room to spare.
+#[tauri::command]
+pub async fn vfs_set_scratch_dir(path: String) -> Result<(), VfsError> {
+    use crate::vfs::application::free_space::system_space_provider;
+    
+    let dir = std::path::PathBuf::from(&path);
+    crate::vfs::application::scratch::set_scratch_dir(&dir, system_space_provider().as_ref())
+        .await
+        .vfs_context("Failed to set scratch directory")?;
+    
+    // Sprites and waveforms are regenerated under the new folder on next use
+    *MEDIA_ADAPTER.write() = None;
+    if let Some(queue) = TRANSCODE_QUEUE.read().as_ref() {
+        queue.set_output_dir(scratch_path("transcodes"));
+    }
+    
+    info!("Scratch directory set to {:?}", dir);
+    Ok(())
+}
+
+/// Remove stale clipboard exports and thumbnail leftovers now, returning the
+/// bytes reclaimed
+#[tauri::command]
+pub async fn vfs_gc_temp() -> Result<u64, VfsError> {
+    Ok(crate::vfs::application::temp_gc::collect_temp().await)
+}
+
+/// Keep clipboard exports for `seconds` before they are cleaned up
+#[tauri::command]
+pub async fn vfs_set_temp_ttl(
+    seconds: u64,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<(), VfsError> {
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    
+    service.set_temp_ttl(std::time::Duration::from_secs(seconds))
+        .await
+        .vfs_context("Failed to save temp TTL")
+}
+
+/// Scrub-preview sprite sheet for the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteSheetResponse {
+    /// Base64 PNG data URL
+    pub image: String,
+    pub columns: u32,
+    pub rows: u32,
+    pub thumb_width: u32,
+    /// Seconds into the video for each cell, row by row
+    pub timestamps: Vec<f64>,
+}
+
+/// Tile `columns * rows` evenly spaced frames of a video into one image.
+/// Files on object storage are hydrated into the cache first.
+#[tauri::command]
+pub async fn vfs_generate_sprite_sheet(
+    source_id: String,
+    file_path: String,
+    columns: u32,
+    rows: u32,
+    thumb_width: u32,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<SpriteSheetResponse, VfsError> {
+    use crate::vfs::ports::IMediaService;
+    use data_encoding::BASE64;
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    let path = std::path::Path::new(&file_path);
+    media_capabilities().await.require_ffmpeg("Scrub previews")?;
+    
+    let local = match service.local_path(&source_id, path) {
+        Some(local) => local,
+        None => service.hydrate_file(&source_id, path)
+            .await
+            .vfs_context("Failed to fetch video for sprite sheet")?,
+    };
+    
+    let adapter = get_media_adapter().await?;
+    
+    let sheet = adapter.generate_sprite_sheet(&local, columns, rows, thumb_width)
+        .await
+        .vfs_context("Failed to generate sprite sheet")?;
+    
+    Ok(SpriteSheetResponse {
+        image: format!("data:image/png;base64,{}", BASE64.encode(&sheet.data)),
+        columns: sheet.columns,
+        rows: sheet.rows,
+        thumb_width: sheet.thumb_width,
+        timestamps: sheet.timestamps,
+    })
+}
+
+/// Peak amplitude (0.0 to 1.0) for each of `width` slices of an audio or
+/// video file, so the frontend can draw a waveform at any height
+#[tauri::command]
+pub async fn vfs_generate_waveform(
+    source_id: String,
+    path: String,
+    width: u32,
+    state: State<'_, VfsStateWrapper>,
+) -> Result<Vec<f32>, VfsError> {
+    use crate::vfs::ports::IMediaService;
+    
+    let service = state.get_service()
+        .ok_or_else(VfsError::not_initialized)?;
+    let path = std::path::Path::new(&path);
+    media_capabilities().await.require_ffmpeg("Waveforms")?;
+    
+    let local = match service.local_path(&source_id, path) {
+        Some(local) => local,
+        None => service.hydrate_file(&source_id, path)
+            .await
+            .vfs_context("Failed to fetch file for waveform")?,
+    };
+    
+    let adapter = get_media_adapter().await?;
+    
+    adapter.generate_waveform(&local, width)
+        .await
+        .vfs_context("Failed to generate waveform")
+}
+
 // ============================================================================
 // Transcription Commands
 // ============================================================================
@@ -3635,7 +6328,7 @@ pub async fn vfs_get_thumbnail(
 static TRANSCRIPTION_SERVICE: Lazy<SyncRwLock<Option<Arc<TranscriptionService>>>> = Lazy::new(|| SyncRwLock::new(None));
 
 /// Get or initialize the global transcription service
-async fn get_transcription_service() -> Result<Arc<TranscriptionService>, String> {
+async fn get_transcription_service() -> Result<Arc<TranscriptionService>, VfsError> {
     // Try to get existing service
     {
         let service_lock = TRANSCRIPTION_SERVICE.read();
@@ -3645,12 +6338,10 @@ async fn get_transcription_service() -> Result<Arc<TranscriptionService>, String
     }
     
     // Initialize service if not yet initialized
-    let temp_dir = dirs::cache_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("/tmp"))
-        .join("ursly-transcription");
+    let temp_dir = scratch_path("transcription");
     
     let service = TranscriptionService::new(temp_dir).await
-        .map_err(|e| format!("Failed to initialize transcription service: {}", e))?;
+        .vfs_context("Failed to initialize transcription service")?;
     
     let service_arc = Arc::new(service);
     *TRANSCRIPTION_SERVICE.write() = Some(service_arc.clone());
@@ -3663,21 +6354,18 @@ async fn get_transcription_service() -> Result<Arc<TranscriptionService>, String
 pub async fn vfs_start_transcription(
     file_path: String,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     let path = std::path::Path::new(&file_path);
     
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(VfsError::NotFound("File does not exist".to_string()));
     }
     
+    media_capabilities().await.require_ffmpeg("Transcription")?;
     let service = get_transcription_service().await?;
     
-    if !service.is_available() {
-        return Err("FFmpeg not available. Please install FFmpeg to use transcription.".to_string());
-    }
-    
     let job_id = service.start_live_transcription(path, app, None).await
-        .map_err(|e| format!("Failed to start transcription: {}", e))?;
+        .vfs_context("Failed to start transcription")?;
     
     info!("Started transcription job: {}", job_id);
     Ok(job_id)
@@ -3687,11 +6375,11 @@ pub async fn vfs_start_transcription(
 #[tauri::command]
 pub async fn vfs_stop_transcription(
     job_id: String,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     let service = get_transcription_service().await?;
     
     service.stop_transcription(&job_id)
-        .map_err(|e| format!("Failed to stop transcription: {}", e))?;
+        .vfs_context("Failed to stop transcription")?;
     
     Ok(format!("Transcription job {} stopped", job_id))
 }
@@ -3700,22 +6388,22 @@ pub async fn vfs_stop_transcription(
 #[tauri::command]
 pub async fn vfs_get_transcription_status(
     job_id: String,
-) -> Result<TranscriptionStatus, String> {
+) -> Result<TranscriptionStatus, VfsError> {
     let service = get_transcription_service().await?;
     
     service.get_status(&job_id)
-        .ok_or_else(|| format!("Transcription job {} not found", job_id))
+        .ok_or_else(|| VfsError::NotFound(format!("Transcription job {} not found", job_id)))
 }
 
 /// Get transcription segments
 #[tauri::command]
 pub async fn vfs_get_transcription_segments(
     job_id: String,
-) -> Result<Vec<TranscriptionSegment>, String> {
+) -> Result<Vec<TranscriptionSegment>, VfsError> {
     let service = get_transcription_service().await?;
     
     service.get_segments(&job_id)
-        .ok_or_else(|| format!("Transcription job {} not found", job_id))
+        .ok_or_else(|| VfsError::NotFound(format!("Transcription job {} not found", job_id)))
 }
 
 #[cfg(test)]
@@ -3862,6 +6550,79 @@ mod tests {
             apps.len()
         );
     }
+    
+    #[tokio::test]
+    async fn test_import_native_file_and_folder() {
+        let dropped = tempfile::TempDir::new().unwrap();
+        std::fs::write(dropped.path().join("notes.txt"), "call sheet").unwrap();
+        std::fs::create_dir_all(dropped.path().join("Day1/audio")).unwrap();
+        std::fs::write(dropped.path().join("Day1/audio/boom.wav"), "wav").unwrap();
+        
+        let root = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(root.path().join("ingest")).unwrap();
+        std::fs::write(root.path().join("ingest/notes.txt"), "old notes").unwrap();
+        let service = Arc::new(VfsService::new().await.unwrap());
+        let source = service.add_local_source("Project".to_string(), root.path().to_path_buf()).await.unwrap();
+        
+        let paths = vec![dropped.path().join("notes.txt"), dropped.path().join("Day1")];
+        let dest = std::path::Path::new("/ingest");
+        let response = import_native_paths(&service, &source.id, dest, &paths, ConflictPolicy::KeepBoth).await;
+        
+        assert_eq!(response.files_pasted, 2, "{:?}", response.errors);
+        assert_eq!(std::fs::read_to_string(root.path().join("ingest/notes copy.txt")).unwrap(), "call sheet");
+        assert_eq!(std::fs::read_to_string(root.path().join("ingest/notes.txt")).unwrap(), "old notes");
+        assert_eq!(std::fs::read_to_string(root.path().join("ingest/Day1/audio/boom.wav")).unwrap(), "wav");
+        
+        let response = import_native_paths(&service, &source.id, dest, &paths[..1], ConflictPolicy::Skip).await;
+        assert_eq!((response.files_pasted, response.files_failed), (0, 0));
+    }
+    
+    #[tokio::test]
+    async fn test_streamed_listing_sends_batches_then_done() {
+        let root = tempfile::TempDir::new().unwrap();
+        for i in 0..5000 {
+            std::fs::write(root.path().join(format!("frame_{:05}.dpx", i)), "").unwrap();
+        }
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Frames".to_string(), root.path().to_path_buf()).await.unwrap();
+        
+        let events = std::sync::Mutex::new(Vec::new());
+        let total = stream_listing(&service, &source.id, std::path::Path::new("/"), &ListOptions::default(), "listing-1", 1000, |event| {
+            events.lock().unwrap().push(event);
+        }).await.unwrap();
+        assert_eq!(total, 5000);
+        
+        let events = events.into_inner().unwrap();
+        let (done, batches) = events.split_last().unwrap();
+        assert!(batches.len() > 1, "only {} batch(es)", batches.len());
+        
+        let mut names = HashSet::new();
+        for event in batches {
+            let ListingEvent::Batch(batch) = event else { panic!("{:?} before the last event", event) };
+            assert_eq!(batch.listing_id, "listing-1");
+            assert!(batch.files.len() <= 1000);
+            names.extend(batch.files.iter().map(|file| file.name.clone()));
+        }
+        assert_eq!(names.len(), 5000);
+        assert!(matches!(done, ListingEvent::Done(ListingDoneEvent { total: 5000, error: None, .. })), "{:?}", done);
+    }
+    
+    #[tokio::test]
+    async fn test_transcode_without_ffmpeg_is_a_typed_error() {
+        let capabilities = MediaCapabilities::probe_with(None, None).await;
+        assert!(!capabilities.ffmpeg_available);
+        assert!(capabilities.encoders.is_empty());
+        
+        let root = tempfile::TempDir::new().unwrap();
+        std::fs::write(root.path().join("A001.mov"), "not really a movie").unwrap();
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Project".to_string(), root.path().to_path_buf()).await.unwrap();
+        
+        let err = transcode_video(&service, &capabilities, &source.id, "/A001.mov", "mp4", None).await.unwrap_err();
+        assert!(matches!(err, VfsError::FfmpegUnavailable(_)), "{:?}", err);
+        assert_eq!(err.code(), "FFMPEG_UNAVAILABLE");
+        assert!(err.message().contains("install"), "{}", err.message());
+    }
 }
 
 // ============================================================================
@@ -3910,7 +6671,7 @@ pub async fn init_upload_manager() {
 /// Helper function to create OpenDAL operator for object storage (S3, GCS, Azure)
 fn create_object_storage_operator(
     source: &crate::vfs::domain::StorageSource,
-) -> Result<opendal::Operator, String> {
+) -> Result<opendal::Operator, VfsError> {
     use opendal::Operator;
     
     match source.source_type {
@@ -3934,6 +6695,12 @@ fn create_object_storage_operator(
             }
             if let Some(ep) = &source.config.endpoint {
                 builder.endpoint(ep);
+                if !source.config.path_style {
+                    builder.enable_virtual_host_style();
+                }
+            }
+            if !has_access_key && !has_secret_key {
+                builder.allow_anonymous();
             }
             
             info!("[create_object_storage_operator] Creating S3 operator - bucket: {}, region: {}, has_access_key: {}, has_secret_key: {}", 
@@ -3941,12 +6708,12 @@ fn create_object_storage_operator(
             
             Operator::new(builder)
                 .map_err(|e| {
-                    format!(
+                    VfsError::InvalidInput(format!(
                         "Failed to create S3 operator for bucket '{}' in region '{}': {}. \
                         Check bucket name, region, and credentials. \
                         Required IAM permissions: s3:ListBucket, s3:GetObject",
                         bucket, region, e
-                    )
+                    ))
                 })
                 .map(|op| op.finish())
         }
@@ -3960,7 +6727,7 @@ fn create_object_storage_operator(
                 builder.credential_path(creds);
             }
             Ok(Operator::new(builder)
-                .map_err(|e| format!("Failed to create GCS operator: {}", e))?
+                .vfs_context("Failed to create GCS operator")?
                 .finish())
         }
         crate::vfs::domain::StorageSourceType::AzureBlob => {
@@ -3976,11 +6743,11 @@ fn create_object_storage_operator(
                 builder.account_key(ak);
             }
             Ok(Operator::new(builder)
-                .map_err(|e| format!("Failed to create Azure Blob operator: {}", e))?
+                .vfs_context("Failed to create Azure Blob operator")?
                 .finish())
         }
         _ => {
-            return Err(format!("Multipart upload not supported for storage type: {:?}", source.source_type));
+            return Err(VfsError::Unsupported(format!("Multipart upload not supported for storage type: {:?}", source.source_type)));
         }
     }
 }
@@ -3993,15 +6760,15 @@ pub async fn vfs_start_multipart_upload(
     s3_path: String,
     part_size: Option<u64>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<String, String> {
+) -> Result<String, VfsError> {
     info!("vfs_start_multipart_upload called: source_id={}, local_path={}, s3_path={}", source_id, local_path, s3_path);
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     // Get the source
     let source = service.get_source(&source_id)
-        .ok_or_else(|| format!("Storage source not found: {}", source_id))?;
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
     
     info!("Found source: {} (type: {:?})", source.name, source.source_type);
     
@@ -4013,14 +6780,14 @@ pub async fn vfs_start_multipart_upload(
             info!("Source type verified as object storage");
         }
         _ => {
-            return Err(format!("Multipart upload is only supported for S3, GCS, and Azure Blob storage, got: {:?}", source.source_type));
+            return Err(VfsError::Unsupported(format!("Multipart upload is only supported for S3, GCS, and Azure Blob storage, got: {:?}", source.source_type)));
         }
     }
     
     // Create operator from source config
     info!("Creating object storage operator...");
     let operator = create_object_storage_operator(&source)
-        .map_err(|e| format!("Failed to create storage operator: {}", e))?;
+        .vfs_context("Failed to create storage operator")?;
     info!("Operator created successfully");
     
     // Ensure upload manager is initialized and states are loaded
@@ -4033,7 +6800,7 @@ pub async fn vfs_start_multipart_upload(
         &PathBuf::from(&local_path),
         &s3_path,
         part_size,
-    ).await.map_err(|e| format!("Failed to start upload: {}", e))?;
+    ).await.vfs_context("Failed to start upload")?;
     
     info!("Upload started with ID: {}", upload_id);
     
@@ -4060,12 +6827,12 @@ pub async fn vfs_start_multipart_upload(
 
 /// Check if a local path is a directory
 #[tauri::command]
-pub async fn vfs_is_directory(path: String) -> Result<bool, String> {
+pub async fn vfs_is_directory(path: String) -> Result<bool, VfsError> {
     use std::path::Path;
     let path_buf = PathBuf::from(&path);
     match std::fs::metadata(&path_buf) {
         Ok(metadata) => Ok(metadata.is_dir()),
-        Err(e) => Err(format!("Failed to check path: {}", e)),
+        Err(e) => Err(VfsError::from(e).context("Failed to check path")),
     }
 }
 
@@ -4077,15 +6844,15 @@ pub async fn vfs_upload_folder(
     s3_base_path: String,
     part_size: Option<u64>,
     state: State<'_, VfsStateWrapper>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, VfsError> {
     info!("vfs_upload_folder called: source_id={}, local_folder_path={}, s3_base_path={}", source_id, local_folder_path, s3_base_path);
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     // Get the source
     let source = service.get_source(&source_id)
-        .ok_or_else(|| format!("Storage source not found: {}", source_id))?;
+        .ok_or_else(|| VfsError::NotFound(format!("Storage source not found: {}", source_id)))?;
     
     // Verify it's an object storage type
     match source.source_type {
@@ -4095,20 +6862,20 @@ pub async fn vfs_upload_folder(
             info!("Source type verified as object storage");
         }
         _ => {
-            return Err(format!("Folder upload is only supported for S3, GCS, and Azure Blob storage, got: {:?}", source.source_type));
+            return Err(VfsError::Unsupported(format!("Folder upload is only supported for S3, GCS, and Azure Blob storage, got: {:?}", source.source_type)));
         }
     }
     
     // Create operator from source config
     let operator = create_object_storage_operator(&source)
-        .map_err(|e| format!("Failed to create storage operator: {}", e))?;
+        .vfs_context("Failed to create storage operator")?;
     
     let manager = get_upload_manager();
     let folder_path = std::path::PathBuf::from(&local_folder_path);
     
     // Verify it's a directory
     if !folder_path.is_dir() {
-        return Err(format!("Path is not a directory: {}", local_folder_path));
+        return Err(VfsError::InvalidInput(format!("Path is not a directory: {}", local_folder_path)));
     }
     
     let folder_name = folder_path.file_name()
@@ -4117,49 +6884,30 @@ pub async fn vfs_upload_folder(
     
     let mut upload_ids = Vec::new();
     
-    // Recursively walk the directory and collect all files using iterative approach
-    let mut files_to_upload = Vec::new();
-    let mut dirs_to_process = vec![folder_path.clone()];
+    // Object storage has no links, so linked files and folders are uploaded
+    // as what they point to; a link looping back up the folder is left out
+    let listing = crate::vfs::platform::list_tree(&folder_path, true)
+        .await
+        .vfs_context("Failed to read folder")?;
+    for cycle in &listing.cycles {
+        warn!("Not uploading {:?}: it links back into the folder", cycle);
+    }
     
-    while let Some(current_dir) = dirs_to_process.pop() {
-        let mut entries = match fs::read_dir(&current_dir).await {
-            Ok(entries) => entries,
+    let mut files_to_upload = Vec::new();
+    for path in listing.files {
+        // Calculate relative path from base directory
+        let relative_path = match path.strip_prefix(&folder_path) {
+            Ok(p) => p,
             Err(e) => {
-                warn!("Failed to read directory {:?}: {}", current_dir, e);
+                warn!("Failed to calculate relative path for {:?}: {}", path, e);
                 continue;
             }
         };
         
-        while let Some(entry) = entries.next_entry().await
-            .map_err(|e| format!("Failed to read directory entry: {}", e))? {
-            let path = entry.path();
-            let metadata = match entry.metadata().await {
-                Ok(m) => m,
-                Err(e) => {
-                    warn!("Failed to get metadata for {:?}: {}", path, e);
-                    continue;
-                }
-            };
-            
-            if metadata.is_dir() {
-                // Add to stack for processing
-                dirs_to_process.push(path);
-            } else if metadata.is_file() {
-                // Calculate relative path from base directory
-                let relative_path = match path.strip_prefix(&folder_path) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        warn!("Failed to calculate relative path for {:?}: {}", path, e);
-                        continue;
-                    }
-                };
-                
-                // Convert to S3-style path (use forward slashes)
-                let s3_path = relative_path.to_string_lossy().replace('\\', "/");
-                
-                files_to_upload.push((path, s3_path));
-            }
-        }
+        // Convert to S3-style path (use forward slashes)
+        let s3_path = relative_path.to_string_lossy().replace('\\', "/");
+        
+        files_to_upload.push((path, s3_path));
     }
     
     info!("Found {} files to upload in folder", files_to_upload.len());
@@ -4212,7 +6960,7 @@ pub async fn vfs_upload_folder(
 #[tauri::command]
 pub async fn vfs_get_upload_progress(
     upload_id: String,
-) -> Result<Option<UploadProgress>, String> {
+) -> Result<Option<UploadProgress>, VfsError> {
     let manager = get_upload_manager();
     Ok(manager.get_progress(&upload_id).await)
 }
@@ -4222,28 +6970,28 @@ pub async fn vfs_get_upload_progress(
 pub async fn vfs_resume_upload(
     upload_id: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<(), String> {
+) -> Result<(), VfsError> {
     let manager = get_upload_manager();
     
     // Get upload state to find source_id
     let uploads = manager.list_uploads().await;
     let upload_state = uploads.iter()
         .find(|u| u.upload_id == upload_id)
-        .ok_or_else(|| "Upload not found".to_string())?;
+        .ok_or_else(|| VfsError::NotFound("Upload not found".to_string()))?;
     
     let source_id = &upload_state.source_id;
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     let source = service.get_source(source_id)
-        .ok_or_else(|| "Storage source not found".to_string())?;
+        .ok_or_else(|| VfsError::NotFound("Storage source not found".to_string()))?;
     
     // Recreate operator using helper function
     let operator = create_object_storage_operator(&source)?;
     
     manager.resume_upload(&operator, &upload_id).await
-        .map_err(|e| format!("Failed to resume upload: {}", e))?;
+        .vfs_context("Failed to resume upload")?;
     
     Ok(())
 }
@@ -4252,10 +7000,10 @@ pub async fn vfs_resume_upload(
 #[tauri::command]
 pub async fn vfs_pause_upload(
     upload_id: String,
-) -> Result<(), String> {
+) -> Result<(), VfsError> {
     let manager = get_upload_manager();
     manager.pause_upload(&upload_id).await
-        .map_err(|e| format!("Failed to pause upload: {}", e))?;
+        .vfs_context("Failed to pause upload")?;
     Ok(())
 }
 
@@ -4264,33 +7012,33 @@ pub async fn vfs_pause_upload(
 pub async fn vfs_cancel_upload(
     upload_id: String,
     state: State<'_, VfsStateWrapper>,
-) -> Result<(), String> {
+) -> Result<(), VfsError> {
     let manager = get_upload_manager();
     
     // Get upload state to find source_id
     let uploads = manager.list_uploads().await;
     let upload_state = uploads.iter()
         .find(|u| u.upload_id == upload_id)
-        .ok_or_else(|| "Upload not found".to_string())?;
+        .ok_or_else(|| VfsError::NotFound("Upload not found".to_string()))?;
     
     let source_id = &upload_state.source_id;
     
     let service = state.get_service()
-        .ok_or_else(|| "VFS not initialized".to_string())?;
+        .ok_or_else(VfsError::not_initialized)?;
     
     let source = service.get_source(source_id)
-        .ok_or_else(|| "Storage source not found".to_string())?;
+        .ok_or_else(|| VfsError::NotFound("Storage source not found".to_string()))?;
     
     let operator = create_object_storage_operator(&source)?;
     
     manager.cancel_upload(&operator, &upload_id).await
-        .map_err(|e| format!("Failed to cancel upload: {}", e))?;
+        .vfs_context("Failed to cancel upload")?;
     Ok(())
 }
 
 /// List all operations (uploads, downloads, deletes, etc.)
 #[tauri::command]
-pub async fn vfs_list_operations() -> Result<Vec<serde_json::Value>, String> {
+pub async fn vfs_list_operations() -> Result<Vec<serde_json::Value>, VfsError> {
     let tracker = get_operation_tracker();
     let operations = tracker.get_all_operations();
     
@@ -4304,7 +7052,7 @@ pub async fn vfs_list_operations() -> Result<Vec<serde_json::Value>, String> {
 
 /// List all active uploads
 #[tauri::command]
-pub async fn vfs_list_uploads() -> Result<Vec<crate::vfs::multipart_upload::MultipartUploadState>, String> {
+pub async fn vfs_list_uploads() -> Result<Vec<crate::vfs::multipart_upload::MultipartUploadState>, VfsError> {
     let manager = get_upload_manager();
     Ok(manager.list_uploads().await)
 }
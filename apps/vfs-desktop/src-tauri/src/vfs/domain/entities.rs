@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::SystemTime;
 
-use super::value_objects::{FileSize, StorageTier, TierStatus};
+use super::mount_config::SseConfig;
+use super::value_objects::{FileSize, LifecyclePolicy, RetryPolicy, StorageTier, TierStatus, TransferTuning};
 
 /// Virtual File Entity - Represents a file in the VFS
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,11 @@ pub struct VirtualFile {
     /// MIME type
     pub content_type: Option<String>,
     
+    /// Checksum reported by the storage provider (S3 ETag, GCS MD5), only
+    /// filled in when a listing asks for it
+    #[serde(default)]
+    pub checksum: Option<String>,
+    
     /// Storage tier status
     pub tier_status: TierStatus,
     
@@ -149,6 +155,7 @@ impl VirtualFile {
             path,
             size: FileSize::from_bytes(size),
             content_type: None,
+            checksum: None,
             tier_status: TierStatus::default(),
             last_modified: SystemTime::now(),
             last_accessed: None,
@@ -213,6 +220,8 @@ impl VirtualFile {
             .and_then(|ext| ext.to_str())
             .map(|ext| video_extensions.contains(&ext.to_lowercase().as_str()))
             .unwrap_or(false)
+            // A renamed clip is still a video if its content says so
+            || self.content_type.as_deref().is_some_and(|mime| mime.starts_with("video/"))
     }
     
     /// Mark file as hydrated
@@ -398,6 +407,42 @@ pub struct StorageConfig {
     /// Access credentials
     pub access_key: Option<String>,
     pub secret_key: Option<String>,
+    
+    /// Path-style addressing (`endpoint/bucket/key`) for S3-compatible endpoints
+    #[serde(default)]
+    pub path_style: bool,
+    
+    /// Reject every write, rename and delete (shared masters, archive buckets)
+    #[serde(default)]
+    pub read_only: bool,
+    
+    /// Maximum bytes this source may hold; writes past it are refused
+    #[serde(default)]
+    pub quota_bytes: Option<u64>,
+    
+    /// Retry tuning for remote sources (`None` uses the default policy)
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    
+    /// Move idle files to a colder tier in the background
+    #[serde(default)]
+    pub lifecycle_policy: Option<LifecyclePolicy>,
+    
+    /// Listing and transfer sizes for object storage (`None` uses the defaults)
+    #[serde(default)]
+    pub transfer_tuning: Option<TransferTuning>,
+    
+    /// List every object under a prefix instead of synthesizing folders from `/`
+    #[serde(default)]
+    pub flat_listing: bool,
+    
+    /// Server-side encryption applied to uploads (S3)
+    #[serde(default)]
+    pub sse: Option<SseConfig>,
+    
+    /// Refuse writes while `sse` isn't set
+    #[serde(default)]
+    pub require_encryption: bool,
 }
 
 impl Default for StorageConfig {
@@ -408,6 +453,15 @@ impl Default for StorageConfig {
             endpoint: None,
             access_key: None,
             secret_key: None,
+            path_style: false,
+            read_only: false,
+            quota_bytes: None,
+            retry_policy: None,
+            lifecycle_policy: None,
+            transfer_tuning: None,
+            flat_listing: false,
+            sse: None,
+            require_encryption: false,
         }
     }
 }
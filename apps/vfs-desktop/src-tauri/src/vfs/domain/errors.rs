@@ -0,0 +1,233 @@
+//! Domain Errors - failures callers may want to tell apart from generic errors
+
+use std::fmt;
+
+/// A write was attempted against a source mounted read-only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOnlyError {
+    pub source_id: String,
+    pub source_name: String,
+}
+
+impl fmt::Display for ReadOnlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Storage source '{}' is read-only", self.source_name)
+    }
+}
+
+impl std::error::Error for ReadOnlyError {}
+
+/// A copy or hydration would not fit on the destination volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsufficientSpaceError {
+    /// Bytes the operation needs to write
+    pub needed: u64,
+    /// Bytes usable on the destination (free space minus the safety margin)
+    pub available: u64,
+}
+
+impl fmt::Display for InsufficientSpaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Not enough free space: {} bytes needed, {} available", self.needed, self.available)
+    }
+}
+
+impl std::error::Error for InsufficientSpaceError {}
+
+/// A write would take a source past its configured quota
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaExceededError {
+    /// Bytes the source holds now
+    pub used: u64,
+    /// Configured limit
+    pub quota: u64,
+}
+
+impl fmt::Display for QuotaExceededError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quota exceeded: {} of {} bytes used", self.used, self.quota)
+    }
+}
+
+impl std::error::Error for QuotaExceededError {}
+
+/// A volume can't be ejected while operations are still using it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeBusyError {
+    /// Descriptions of the in-flight operations touching the source
+    pub operations: Vec<String>,
+}
+
+impl fmt::Display for VolumeBusyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Volume is busy with {} operation(s): {}", self.operations.len(), self.operations.join(", "))
+    }
+}
+
+impl std::error::Error for VolumeBusyError {}
+
+/// A file is in an archive tier and has to be retrieved before it can be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveNotRetrievedError {
+    pub path: String,
+    /// Rough time the retrieval takes once started
+    pub estimated_retrieval_secs: u64,
+}
+
+impl fmt::Display for ArchiveNotRetrievedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is archived and must be retrieved first (about {} hours)",
+            self.path,
+            (self.estimated_retrieval_secs + 3599) / 3600
+        )
+    }
+}
+
+impl std::error::Error for ArchiveNotRetrievedError {}
+
+/// Error returned to the frontend: a stable `code` to branch on plus a
+/// human-readable `message`. Serializes as `{ "code": "NOT_FOUND", "message": "..." }`,
+/// plus `estimatedRetrievalSecs` for `ARCHIVE_NOT_RETRIEVED`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VfsError {
+    NotFound(String),
+    PermissionDenied(String),
+    Timeout(String),
+    AlreadyExists(String),
+    NotInitialized(String),
+    ReadOnly(String),
+    InsufficientSpace(String),
+    QuotaExceeded(String),
+    VolumeBusy(String),
+    /// Another writer holds the file
+    Locked(String),
+    /// The file has to be retrieved from an archive tier first
+    ArchiveNotRetrieved { message: String, estimated_retrieval_secs: u64 },
+    Cancelled(String),
+    InvalidInput(String),
+    Unsupported(String),
+    /// The feature needs FFmpeg, which isn't installed; the message says how to get it
+    FfmpegUnavailable(String),
+    Other(String),
+}
+
+impl VfsError {
+    /// Stable machine-readable code
+    pub fn code(&self) -> &'static str {
+        match self {
+            VfsError::NotFound(_) => "NOT_FOUND",
+            VfsError::PermissionDenied(_) => "PERMISSION_DENIED",
+            VfsError::Timeout(_) => "TIMEOUT",
+            VfsError::AlreadyExists(_) => "ALREADY_EXISTS",
+            VfsError::NotInitialized(_) => "NOT_INITIALIZED",
+            VfsError::ReadOnly(_) => "READ_ONLY",
+            VfsError::InsufficientSpace(_) => "INSUFFICIENT_SPACE",
+            VfsError::QuotaExceeded(_) => "QUOTA_EXCEEDED",
+            VfsError::VolumeBusy(_) => "VOLUME_BUSY",
+            VfsError::Locked(_) => "LOCKED",
+            VfsError::ArchiveNotRetrieved { .. } => "ARCHIVE_NOT_RETRIEVED",
+            VfsError::Cancelled(_) => "CANCELLED",
+            VfsError::InvalidInput(_) => "INVALID_INPUT",
+            VfsError::Unsupported(_) => "UNSUPPORTED",
+            VfsError::FfmpegUnavailable(_) => "FFMPEG_UNAVAILABLE",
+            VfsError::Other(_) => "INTERNAL",
+        }
+    }
+    
+    pub fn message(&self) -> &str {
+        match self {
+            VfsError::NotFound(message)
+            | VfsError::PermissionDenied(message)
+            | VfsError::Timeout(message)
+            | VfsError::AlreadyExists(message)
+            | VfsError::NotInitialized(message)
+            | VfsError::ReadOnly(message)
+            | VfsError::InsufficientSpace(message)
+            | VfsError::QuotaExceeded(message)
+            | VfsError::VolumeBusy(message)
+            | VfsError::Locked(message)
+            | VfsError::Cancelled(message)
+            | VfsError::InvalidInput(message)
+            | VfsError::Unsupported(message)
+            | VfsError::FfmpegUnavailable(message)
+            | VfsError::Other(message)
+            | VfsError::ArchiveNotRetrieved { message, .. } => message,
+        }
+    }
+    
+    /// The VFS service hasn't been set up yet (`vfs_init` not called)
+    pub fn not_initialized() -> Self {
+        VfsError::NotInitialized("VFS not initialized".to_string())
+    }
+    
+    /// Prefix the message (`"{context}: {message}"`), keeping the code
+    pub fn context(self, context: impl fmt::Display) -> Self {
+        let message = format!("{}: {}", context, self.message());
+        self.with_message(message)
+    }
+    
+    /// Same code, different message
+    pub fn with_message(self, message: String) -> Self {
+        match self {
+            VfsError::NotFound(_) => VfsError::NotFound(message),
+            VfsError::PermissionDenied(_) => VfsError::PermissionDenied(message),
+            VfsError::Timeout(_) => VfsError::Timeout(message),
+            VfsError::AlreadyExists(_) => VfsError::AlreadyExists(message),
+            VfsError::NotInitialized(_) => VfsError::NotInitialized(message),
+            VfsError::ReadOnly(_) => VfsError::ReadOnly(message),
+            VfsError::InsufficientSpace(_) => VfsError::InsufficientSpace(message),
+            VfsError::QuotaExceeded(_) => VfsError::QuotaExceeded(message),
+            VfsError::VolumeBusy(_) => VfsError::VolumeBusy(message),
+            VfsError::Locked(_) => VfsError::Locked(message),
+            VfsError::ArchiveNotRetrieved { estimated_retrieval_secs, .. } => {
+                VfsError::ArchiveNotRetrieved { message, estimated_retrieval_secs }
+            }
+            VfsError::Cancelled(_) => VfsError::Cancelled(message),
+            VfsError::InvalidInput(_) => VfsError::InvalidInput(message),
+            VfsError::Unsupported(_) => VfsError::Unsupported(message),
+            VfsError::FfmpegUnavailable(_) => VfsError::FfmpegUnavailable(message),
+            VfsError::Other(_) => VfsError::Other(message),
+        }
+    }
+}
+
+impl fmt::Display for VfsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for VfsError {}
+
+impl serde::Serialize for VfsError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        
+        let retrieval_secs = match self {
+            VfsError::ArchiveNotRetrieved { estimated_retrieval_secs, .. } => Some(*estimated_retrieval_secs),
+            _ => None,
+        };
+        let mut state = serializer.serialize_struct("VfsError", 2 + retrieval_secs.is_some() as usize)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", self.message())?;
+        if let Some(secs) = retrieval_secs {
+            state.serialize_field("estimatedRetrievalSecs", &secs)?;
+        }
+        state.end()
+    }
+}
+
+/// Plain string errors carry no type information
+impl From<String> for VfsError {
+    fn from(message: String) -> Self {
+        VfsError::Other(message)
+    }
+}
+
+impl From<&str> for VfsError {
+    fn from(message: &str) -> Self {
+        VfsError::Other(message.to_string())
+    }
+}
@@ -0,0 +1,109 @@
+//! Hashing - checksum algorithms and hashing bytes as they stream past
+//!
+//! `Hasher` computes MD5, SHA-1 or XXH3 digests incrementally, for manifests,
+//! folder comparison and copy verification. `hash_chunks` hashes a chunk
+//! stream as its consumer pulls it, so a copy's source is hashed while it is
+//! read rather than in a second pass.
+
+use anyhow::Result;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha1::Digest;
+use std::sync::Arc;
+
+/// Supported checksum algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    #[default]
+    #[serde(rename = "md5")]
+    Md5,
+    #[serde(rename = "sha1")]
+    Sha1,
+    /// XXH3 64-bit - much faster, for integrity rather than security
+    #[serde(rename = "xxhash")]
+    XxHash,
+}
+
+/// Incremental hasher over the supported algorithms
+pub enum Hasher {
+    Md5(md5::Context),
+    Sha1(sha1::Sha1),
+    XxHash(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl Hasher {
+    pub fn new(algo: HashAlgorithm) -> Self {
+        match algo {
+            HashAlgorithm::Md5 => Hasher::Md5(md5::Context::new()),
+            HashAlgorithm::Sha1 => Hasher::Sha1(sha1::Sha1::default()),
+            HashAlgorithm::XxHash => Hasher::XxHash(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+        }
+    }
+    
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(ctx) => ctx.consume(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::XxHash(hasher) => hasher.update(data),
+        }
+    }
+    
+    /// Hex digest
+    pub fn finish(self) -> String {
+        match self {
+            Hasher::Md5(ctx) => format!("{:x}", ctx.compute()),
+            Hasher::Sha1(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::XxHash(hasher) => format!("{:016x}", hasher.digest()),
+        }
+    }
+}
+
+/// Digest of a stream wrapped by `hash_chunks`, readable once the stream is drained
+#[derive(Clone)]
+pub struct StreamDigest(Arc<Mutex<Option<Hasher>>>);
+
+impl StreamDigest {
+    /// Hex digest of every chunk the consumer pulled
+    pub fn finish(&self) -> String {
+        self.0.lock().take().map(Hasher::finish).unwrap_or_default()
+    }
+}
+
+/// Hash each chunk as the destination pulls it from the stream
+pub fn hash_chunks<'a>(
+    chunks: BoxStream<'a, Result<Vec<u8>>>,
+    algo: HashAlgorithm,
+) -> (BoxStream<'a, Result<Vec<u8>>>, StreamDigest) {
+    let digest = StreamDigest(Arc::new(Mutex::new(Some(Hasher::new(algo)))));
+    let hasher = digest.clone();
+    
+    let chunks = chunks
+        .inspect(move |chunk| {
+            if let (Ok(chunk), Some(hasher)) = (chunk, hasher.0.lock().as_mut()) {
+                hasher.update(chunk);
+            }
+        })
+        .boxed();
+    
+    (chunks, digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    
+    #[tokio::test]
+    async fn test_digest_matches_separate_hash() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let expected = format!("{:x}", md5::compute(&data));
+        
+        let stream = futures::stream::iter(data.chunks(4096).map(|c| Ok(c.to_vec()))).boxed();
+        let (stream, digest) = hash_chunks(stream, HashAlgorithm::Md5);
+        let collected: Vec<Vec<u8>> = stream.try_collect().await.unwrap();
+        assert_eq!(collected.concat(), data);
+        assert_eq!(digest.finish(), expected);
+    }
+}
@@ -6,10 +6,15 @@
 pub mod entities;
 pub mod value_objects;
 pub mod events;
+pub mod errors;
+pub mod mount_config;
+pub mod hashing;
 
 pub use entities::*;
 pub use value_objects::*;
 pub use events::*;
+pub use errors::*;
+pub use mount_config::*;
 
 
 
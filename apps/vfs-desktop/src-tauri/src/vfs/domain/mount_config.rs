@@ -0,0 +1,528 @@
+//! Mount Configuration - everything needed to connect a storage source
+//!
+//! One variant per backend with the fields that backend needs, so the mount
+//! dialog sends a single typed value instead of a loose JSON object.
+//! `validate` catches missing fields before any adapter is built.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::errors::VfsError;
+use super::value_objects::{LifecyclePolicy, RetryPolicy, TransferTuning};
+
+/// NAS protocols a share can be mounted with
+pub const NAS_PROTOCOLS: &[&str] = &["nfs", "smb", "afp"];
+
+/// Where a secret is kept in the OS keychain
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRef {
+    pub service: String,
+    pub account: String,
+}
+
+/// Server-side encryption S3 applies to every object the app writes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SseConfig {
+    /// SSE-S3: keys managed by S3 (`AES256`)
+    S3,
+    
+    /// SSE-KMS with this key ID or ARN, or the account's `aws/s3` key when omitted
+    Kms {
+        #[serde(default)]
+        key_id: Option<String>,
+    },
+    
+    /// SSE-C: a base64 256-bit key sent with every request. S3 doesn't keep
+    /// it, so like the secret access key it's saved to the keychain and the
+    /// sources file only references it.
+    CustomerKey {
+        #[serde(default)]
+        key: Option<String>,
+        /// Keychain entry holding the key, used when `key` is omitted
+        #[serde(default)]
+        credential: Option<CredentialRef>,
+    },
+}
+
+impl SseConfig {
+    /// The raw SSE-C key; `None` for the other modes
+    pub fn customer_key(&self) -> Result<Option<Vec<u8>>, VfsError> {
+        let SseConfig::CustomerKey { key, .. } = self else {
+            return Ok(None);
+        };
+        let key = key.as_deref().map(str::trim).filter(|key| !key.is_empty())
+            .ok_or_else(|| VfsError::InvalidInput("SSE-C needs its encryption key".to_string()))?;
+        match data_encoding::BASE64.decode(key.as_bytes()) {
+            Ok(raw) if raw.len() == 32 => Ok(Some(raw)),
+            _ => Err(VfsError::InvalidInput("The SSE-C key must be a base64-encoded 256-bit key".to_string())),
+        }
+    }
+}
+
+/// How to connect a storage source
+///
+/// Required string fields default to empty when omitted so a missing field is
+/// reported by `validate` with its name rather than as a parse error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum MountConfig {
+    /// A folder on this machine
+    Local {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        path: PathBuf,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+    
+    /// An S3 bucket or S3-compatible endpoint (MinIO, Ceph, R2)
+    S3 {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        bucket: String,
+        /// May be omitted only when `endpoint` is set
+        #[serde(default)]
+        region: Option<String>,
+        /// Omit both keys to use the default AWS credential chain
+        #[serde(default)]
+        access_key_id: Option<String>,
+        #[serde(default)]
+        secret_access_key: Option<String>,
+        /// Keychain entry holding the secret access key, used when
+        /// `secret_access_key` is omitted
+        #[serde(default)]
+        credential: Option<CredentialRef>,
+        #[serde(default)]
+        endpoint: Option<String>,
+        /// Defaults to path-style whenever `endpoint` is set
+        #[serde(default)]
+        path_style: Option<bool>,
+        /// Encryption for uploads; omit to leave it to the bucket's default
+        #[serde(default)]
+        sse: Option<SseConfig>,
+        /// Refuse writes while `sse` isn't set, for buckets whose policy
+        /// demands encrypted uploads
+        #[serde(default)]
+        require_encryption: bool,
+        /// Listing page and chunk sizes set through `vfs_set_transfer_tuning`
+        #[serde(default)]
+        transfer_tuning: Option<TransferTuning>,
+        /// List every object under a folder instead of one level at a time
+        #[serde(default)]
+        flat_listing: bool,
+        /// Idle files moved to a colder tier, set through `vfs_set_lifecycle_policy`
+        #[serde(default)]
+        lifecycle_policy: Option<LifecyclePolicy>,
+        /// How transient failures are retried, set through `vfs_set_retry_policy`
+        #[serde(default)]
+        retry_policy: Option<RetryPolicy>,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+    
+    /// A Google Cloud Storage bucket
+    Gcs {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        bucket: String,
+        /// Service account key file; omit to use application default credentials
+        #[serde(default)]
+        credentials_path: Option<String>,
+        /// Listing page and chunk sizes set through `vfs_set_transfer_tuning`
+        #[serde(default)]
+        transfer_tuning: Option<TransferTuning>,
+        /// Idle files moved to a colder tier, set through `vfs_set_lifecycle_policy`
+        #[serde(default)]
+        lifecycle_policy: Option<LifecyclePolicy>,
+        /// How transient failures are retried, set through `vfs_set_retry_policy`
+        #[serde(default)]
+        retry_policy: Option<RetryPolicy>,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+    
+    /// An NFS/SMB/AFP share already mounted by the OS at `mount_point`
+    Nas {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        host: String,
+        #[serde(default)]
+        share: String,
+        /// `nfs`, `smb` or `afp`
+        #[serde(default)]
+        protocol: String,
+        #[serde(default)]
+        mount_point: PathBuf,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        /// Keychain entry holding the password, used when `password` is omitted
+        #[serde(default)]
+        credential: Option<CredentialRef>,
+        /// How transient failures are retried, set through `vfs_set_retry_policy`
+        #[serde(default)]
+        retry_policy: Option<RetryPolicy>,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+    
+    /// An FSx for NetApp ONTAP volume mounted at `mount_point`
+    Fsx {
+        #[serde(default)]
+        name: String,
+        /// ONTAP management endpoint, used for tiering information
+        #[serde(default)]
+        endpoint: String,
+        #[serde(default)]
+        mount_point: PathBuf,
+        /// S3 access point for data tiered to capacity storage
+        #[serde(default)]
+        s3_access_point: Option<String>,
+        /// How transient failures are retried, set through `vfs_set_retry_policy`
+        #[serde(default)]
+        retry_policy: Option<RetryPolicy>,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+    
+    /// A mounted block device (EBS volume, NVMe drive, partition)
+    Block {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        mount_point: PathBuf,
+        /// Most bytes the source may hold, set through `vfs_set_quota`
+        #[serde(default)]
+        quota_bytes: Option<u64>,
+        #[serde(default)]
+        read_only: bool,
+    },
+}
+
+impl MountConfig {
+    /// Display name of the source
+    pub fn name(&self) -> &str {
+        match self {
+            MountConfig::Local { name, .. }
+            | MountConfig::S3 { name, .. }
+            | MountConfig::Gcs { name, .. }
+            | MountConfig::Nas { name, .. }
+            | MountConfig::Fsx { name, .. }
+            | MountConfig::Block { name, .. } => name,
+        }
+    }
+    
+    pub fn set_name(&mut self, new_name: String) {
+        match self {
+            MountConfig::Local { name, .. }
+            | MountConfig::S3 { name, .. }
+            | MountConfig::Gcs { name, .. }
+            | MountConfig::Nas { name, .. }
+            | MountConfig::Fsx { name, .. }
+            | MountConfig::Block { name, .. } => *name = new_name,
+        }
+    }
+    
+    /// Whether the source should be mounted read-only
+    pub fn read_only(&self) -> bool {
+        match self {
+            MountConfig::Local { read_only, .. }
+            | MountConfig::S3 { read_only, .. }
+            | MountConfig::Gcs { read_only, .. }
+            | MountConfig::Nas { read_only, .. }
+            | MountConfig::Fsx { read_only, .. }
+            | MountConfig::Block { read_only, .. } => *read_only,
+        }
+    }
+    
+    /// Most bytes the source may hold
+    pub fn quota_bytes(&self) -> Option<u64> {
+        match self {
+            MountConfig::Local { quota_bytes, .. }
+            | MountConfig::S3 { quota_bytes, .. }
+            | MountConfig::Gcs { quota_bytes, .. }
+            | MountConfig::Nas { quota_bytes, .. }
+            | MountConfig::Fsx { quota_bytes, .. }
+            | MountConfig::Block { quota_bytes, .. } => *quota_bytes,
+        }
+    }
+    
+    pub fn quota_bytes_mut(&mut self) -> &mut Option<u64> {
+        match self {
+            MountConfig::Local { quota_bytes, .. }
+            | MountConfig::S3 { quota_bytes, .. }
+            | MountConfig::Gcs { quota_bytes, .. }
+            | MountConfig::Nas { quota_bytes, .. }
+            | MountConfig::Fsx { quota_bytes, .. }
+            | MountConfig::Block { quota_bytes, .. } => quota_bytes,
+        }
+    }
+    
+    /// The inline secret (S3 secret key, NAS password), for backends that take one
+    pub fn secret_mut(&mut self) -> Option<&mut Option<String>> {
+        match self {
+            MountConfig::S3 { secret_access_key, .. } => Some(secret_access_key),
+            MountConfig::Nas { password, .. } => Some(password),
+            MountConfig::Local { .. } | MountConfig::Gcs { .. } | MountConfig::Fsx { .. } | MountConfig::Block { .. } => None,
+        }
+    }
+    
+    /// Keychain entry the secret is read from when it isn't inline
+    pub fn credential(&self) -> Option<&CredentialRef> {
+        match self {
+            MountConfig::S3 { credential, .. } | MountConfig::Nas { credential, .. } => credential.as_ref(),
+            MountConfig::Local { .. } | MountConfig::Gcs { .. } | MountConfig::Fsx { .. } | MountConfig::Block { .. } => None,
+        }
+    }
+    
+    pub fn credential_mut(&mut self) -> Option<&mut Option<CredentialRef>> {
+        match self {
+            MountConfig::S3 { credential, .. } | MountConfig::Nas { credential, .. } => Some(credential),
+            MountConfig::Local { .. } | MountConfig::Gcs { .. } | MountConfig::Fsx { .. } | MountConfig::Block { .. } => None,
+        }
+    }
+    
+    /// The inline SSE-C key and the keychain entry it's read from when it isn't
+    /// inline, for S3 sources encrypting with a customer key
+    pub fn sse_key_mut(&mut self) -> Option<(&mut Option<String>, &mut Option<CredentialRef>)> {
+        match self {
+            MountConfig::S3 { sse: Some(SseConfig::CustomerKey { key, credential }), .. } => Some((key, credential)),
+            _ => None,
+        }
+    }
+    
+    /// Listing page and chunk sizes, for object storage sources
+    pub fn transfer_tuning(&self) -> Option<TransferTuning> {
+        match self {
+            MountConfig::S3 { transfer_tuning, .. } | MountConfig::Gcs { transfer_tuning, .. } => *transfer_tuning,
+            _ => None,
+        }
+    }
+    
+    pub fn transfer_tuning_mut(&mut self) -> Option<&mut Option<TransferTuning>> {
+        match self {
+            MountConfig::S3 { transfer_tuning, .. } | MountConfig::Gcs { transfer_tuning, .. } => Some(transfer_tuning),
+            _ => None,
+        }
+    }
+    
+    /// How transient failures are retried, for cloud and network sources
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        match self {
+            MountConfig::S3 { retry_policy, .. }
+            | MountConfig::Gcs { retry_policy, .. }
+            | MountConfig::Nas { retry_policy, .. }
+            | MountConfig::Fsx { retry_policy, .. } => *retry_policy,
+            MountConfig::Local { .. } | MountConfig::Block { .. } => None,
+        }
+    }
+    
+    pub fn retry_policy_mut(&mut self) -> Option<&mut Option<RetryPolicy>> {
+        match self {
+            MountConfig::S3 { retry_policy, .. }
+            | MountConfig::Gcs { retry_policy, .. }
+            | MountConfig::Nas { retry_policy, .. }
+            | MountConfig::Fsx { retry_policy, .. } => Some(retry_policy),
+            MountConfig::Local { .. } | MountConfig::Block { .. } => None,
+        }
+    }
+    
+    /// When idle files move to a colder tier, for sources with storage tiers
+    pub fn lifecycle_policy(&self) -> Option<LifecyclePolicy> {
+        match self {
+            MountConfig::S3 { lifecycle_policy, .. } | MountConfig::Gcs { lifecycle_policy, .. } => *lifecycle_policy,
+            _ => None,
+        }
+    }
+    
+    pub fn lifecycle_policy_mut(&mut self) -> Option<&mut Option<LifecyclePolicy>> {
+        match self {
+            MountConfig::S3 { lifecycle_policy, .. } | MountConfig::Gcs { lifecycle_policy, .. } => Some(lifecycle_policy),
+            _ => None,
+        }
+    }
+    
+    /// Keychain entry the SSE-C key is read from when it isn't inline
+    pub fn sse_credential(&self) -> Option<&CredentialRef> {
+        match self {
+            MountConfig::S3 { sse: Some(SseConfig::CustomerKey { credential, .. }), .. } => credential.as_ref(),
+            _ => None,
+        }
+    }
+    
+    /// Copy with passwords, secret keys and SSE-C keys removed, for writing to disk
+    pub fn without_secrets(&self) -> Self {
+        let mut config = self.clone();
+        if let Some(secret) = config.secret_mut() {
+            *secret = None;
+        }
+        if let Some((key, _)) = config.sse_key_mut() {
+            *key = None;
+        }
+        config
+    }
+    
+    /// Check that every field the backend needs is present
+    pub fn validate(&self) -> Result<(), VfsError> {
+        fn require(value: &str, field: &str) -> Result<(), VfsError> {
+            if value.trim().is_empty() {
+                return Err(VfsError::InvalidInput(format!("Missing {} in mount config", field)));
+            }
+            Ok(())
+        }
+        fn require_path(value: &std::path::Path, field: &str) -> Result<(), VfsError> {
+            require(&value.to_string_lossy(), field)
+        }
+        let is_set = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        
+        require(self.name(), "name")?;
+        
+        match self {
+            MountConfig::Local { path, .. } => require_path(path, "path"),
+            MountConfig::S3 { bucket, region, access_key_id, secret_access_key, credential, endpoint, sse, .. } => {
+                require(bucket, "bucket")?;
+                if !is_set(region) && !is_set(endpoint) {
+                    return Err(VfsError::InvalidInput("Missing region in mount config".to_string()));
+                }
+                if is_set(access_key_id) != (is_set(secret_access_key) || credential.is_some()) {
+                    return Err(VfsError::InvalidInput(
+                        "S3 credentials need both an access key ID and a secret access key".to_string()
+                    ));
+                }
+                match sse {
+                    // Checked once it's read back from the keychain
+                    Some(SseConfig::CustomerKey { key: None, credential: Some(_) }) => {}
+                    Some(sse) => {
+                        sse.customer_key()?;
+                    }
+                    None => {}
+                }
+                Ok(())
+            }
+            MountConfig::Gcs { bucket, .. } => require(bucket, "bucket"),
+            MountConfig::Nas { host, share, protocol, mount_point, .. } => {
+                require(host, "host")?;
+                require(share, "share")?;
+                require_path(mount_point, "mountPoint")?;
+                if !NAS_PROTOCOLS.contains(&protocol.to_ascii_lowercase().as_str()) {
+                    return Err(VfsError::InvalidInput(format!(
+                        "Unknown NAS protocol {:?} (expected one of {})",
+                        protocol,
+                        NAS_PROTOCOLS.join(", ")
+                    )));
+                }
+                Ok(())
+            }
+            MountConfig::Fsx { endpoint, mount_point, .. } => {
+                require(endpoint, "endpoint")?;
+                require_path(mount_point, "mountPoint")
+            }
+            MountConfig::Block { mount_point, .. } => require_path(mount_point, "mountPoint"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_missing_fields_are_named() {
+        let config: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "name": "Footage",
+            "region": "us-east-1",
+        })).unwrap();
+        
+        match config.validate() {
+            Err(VfsError::InvalidInput(message)) => assert!(message.contains("bucket"), "{}", message),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+        
+        // An S3-compatible endpoint stands in for the region
+        let config: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "name": "MinIO",
+            "bucket": "dailies",
+            "endpoint": "http://localhost:9000",
+        })).unwrap();
+        assert!(config.validate().is_ok());
+        
+        let config = MountConfig::Nas {
+            name: "Edit Share".to_string(),
+            host: "nas.local".to_string(),
+            share: "projects".to_string(),
+            protocol: "ftp".to_string(),
+            mount_point: PathBuf::from("/Volumes/projects"),
+            username: None,
+            password: None,
+            credential: None,
+            read_only: false,
+            quota_bytes: None,
+            retry_policy: None,
+        };
+        assert!(config.validate().is_err());
+        
+        let config: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "block",
+            "name": "Scratch",
+        })).unwrap();
+        match config.validate() {
+            Err(VfsError::InvalidInput(message)) => assert!(message.contains("mountPoint"), "{}", message),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_sse_customer_key_is_checked_and_never_saved() {
+        let key = data_encoding::BASE64.encode(&[7u8; 32]);
+        let config: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "name": "Masters",
+            "bucket": "masters",
+            "region": "us-east-1",
+            "sse": { "mode": "customerKey", "key": key },
+            "requireEncryption": true,
+        })).unwrap();
+        assert!(config.validate().is_ok());
+        
+        // Without the key the source can't be mounted until it's entered again
+        let mut saved = config.without_secrets();
+        assert!(!serde_json::to_string(&saved).unwrap().contains(&key));
+        assert!(matches!(saved.validate(), Err(VfsError::InvalidInput(_))));
+        
+        // ...unless the key was saved to the keychain
+        if let Some((_, credential)) = saved.sse_key_mut() {
+            *credential = Some(CredentialRef { service: "ursly".to_string(), account: "masters/sse-c".to_string() });
+        }
+        assert!(saved.validate().is_ok());
+        assert_eq!(saved.sse_credential().map(|c| c.account.as_str()), Some("masters/sse-c"));
+        
+        let kms: SseConfig = serde_json::from_value(serde_json::json!({ "mode": "kms", "keyId": "alias/media" })).unwrap();
+        assert_eq!(kms, SseConfig::Kms { key_id: Some("alias/media".to_string()) });
+        let short = SseConfig::CustomerKey { key: Some(data_encoding::BASE64.encode(&[7u8; 16])), credential: None };
+        assert!(short.customer_key().is_err());
+    }
+}
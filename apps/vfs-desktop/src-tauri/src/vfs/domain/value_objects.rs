@@ -1,7 +1,8 @@
 //! Value Objects - Immutable objects defined by their attributes
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// File size value object with human-readable formatting
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -71,6 +72,30 @@ impl StorageTier {
         }
     }
     
+    /// Relative cost of getting data back, from 0 (hot) to 4 (archive);
+    /// instant retrieval counts as cold, the way tier changes map it
+    pub fn coldness(&self) -> u8 {
+        match self {
+            StorageTier::Hot => 0,
+            StorageTier::Warm => 1,
+            StorageTier::Nearline => 2,
+            StorageTier::Cold | StorageTier::InstantRetrieval => 3,
+            StorageTier::Archive => 4,
+        }
+    }
+    
+    /// Rough seconds until data in this tier can be read, e.g. about 12
+    /// hours to restore from Glacier
+    pub fn retrieval_secs(&self) -> u64 {
+        match self {
+            StorageTier::Hot | StorageTier::InstantRetrieval => 0,
+            StorageTier::Warm => 1,
+            StorageTier::Nearline => 30,
+            StorageTier::Cold => 60,
+            StorageTier::Archive => 43200,
+        }
+    }
+    
     pub fn icon(&self) -> &'static str {
         match self {
             StorageTier::Hot => "flame",
@@ -83,6 +108,30 @@ impl StorageTier {
     }
 }
 
+/// How quickly (and expensively) an archived file is restored
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RetrievalTier {
+    /// Minutes, at the highest cost
+    Expedited,
+    /// Hours
+    #[default]
+    Standard,
+    /// Up to a couple of days, at the lowest cost
+    Bulk,
+}
+
+impl RetrievalTier {
+    /// Name in S3's `RestoreObject` request
+    pub fn as_s3_str(&self) -> &'static str {
+        match self {
+            RetrievalTier::Expedited => "Expedited",
+            RetrievalTier::Standard => "Standard",
+            RetrievalTier::Bulk => "Bulk",
+        }
+    }
+}
+
 /// Tier status for a file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TierStatus {
@@ -110,6 +159,34 @@ impl Default for TierStatus {
     }
 }
 
+/// Whether a file has been copied to the local cache
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum WarmStatus {
+    NotWarmed,
+    
+    /// Queued or downloading; `progress` runs from 0.0 to 1.0
+    Warming { progress: f64 },
+    
+    Warmed,
+}
+
+/// Where a VFS path lives on the local disk, if anywhere
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ResolvedPath {
+    /// File on a local or mounted source
+    #[serde(rename_all = "camelCase")]
+    Local { real_path: PathBuf },
+    
+    /// Remote file with a hydrated copy in the local cache
+    #[serde(rename_all = "camelCase")]
+    Cached { cache_path: PathBuf },
+    
+    /// Remote file that only exists in the cloud
+    Remote,
+}
+
 /// Mount configuration value object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MountPoint {
@@ -161,6 +238,18 @@ pub struct CacheConfig {
     
     /// Enable NVMe optimizations
     pub nvme_optimized: bool,
+    
+    /// How long a deleted file's cached copy is kept so the delete can be
+    /// undone (milliseconds over IPC); space is reclaimed sooner if needed
+    #[serde(default = "default_delete_grace", with = "duration_millis")]
+    pub delete_grace: Duration,
+}
+
+/// Default window for undoing a delete from the cache
+pub const DEFAULT_CACHE_DELETE_GRACE: Duration = Duration::from_secs(60);
+
+fn default_delete_grace() -> Duration {
+    DEFAULT_CACHE_DELETE_GRACE
 }
 
 impl Default for CacheConfig {
@@ -175,6 +264,7 @@ impl Default for CacheConfig {
             max_size: 10 * 1024 * 1024 * 1024, // 10 GB
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: true,
+            delete_grace: DEFAULT_CACHE_DELETE_GRACE,
         }
     }
 }
@@ -209,3 +299,258 @@ impl TranscodeFormat {
     }
 }
 
+/// How operations against a remote source are retried after transient failures
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// Total tries, including the first
+    pub max_attempts: u32,
+    
+    /// Delay before the first retry (milliseconds over IPC); doubles after each failure
+    #[serde(with = "duration_millis")]
+    pub base_delay: Duration,
+    
+    /// Longest single delay, however many failures came before
+    #[serde(with = "duration_millis")]
+    pub max_delay: Duration,
+    
+    /// Spread each delay randomly between half and all of it, so clients that
+    /// failed together don't all retry at the same moment
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential delay after the `attempt`th failure (1-based), capped at `max_delay`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+    
+    /// Delay to wait after the `attempt`th failure, with jitter applied
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff(attempt);
+        if !self.jitter {
+            return delay;
+        }
+        
+        let half = delay / 2;
+        let spread = (delay - half).as_nanos() as u64;
+        let random = uuid::Uuid::new_v4().as_u128() as u64;
+        half + Duration::from_nanos(random % spread.saturating_add(1))
+    }
+}
+
+/// Listing page and transfer sizes for an object storage source. Links with
+/// high latency do better with bigger chunks and more requests in flight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferTuning {
+    /// Keys requested per listing call (S3 `max-keys`, GCS `maxResults`)
+    pub list_page_size: usize,
+    
+    /// Multipart part / resumable upload chunk size
+    pub upload_chunk_size: usize,
+    
+    /// Bytes per ranged read when downloading
+    pub download_chunk_size: usize,
+    
+    /// Ranged reads in flight at once per download
+    pub max_concurrency: usize,
+}
+
+impl Default for TransferTuning {
+    fn default() -> Self {
+        Self {
+            list_page_size: 1000,
+            upload_chunk_size: 8 * 1024 * 1024,
+            download_chunk_size: 8 * 1024 * 1024,
+            max_concurrency: 1,
+        }
+    }
+}
+
+/// A per-source setting changed through `vfs_set_source_option`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "option", content = "value", rename_all = "camelCase")]
+pub enum SourceOption {
+    /// Object storage only: list every object under a folder, with its key
+    /// relative to the folder as its name, instead of one level with folders
+    /// made up from `/` in the keys
+    FlatListing(bool),
+}
+
+/// Whether a source is reachable, and how its connections are doing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHealth {
+    pub source_id: String,
+    pub connected: bool,
+    
+    /// Consecutive failed connection checks
+    pub failure_count: u64,
+}
+
+/// Outcome of trying a mount config without mounting it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTestResult {
+    pub ok: bool,
+    
+    /// Time taken to build the adapter and reach the storage
+    pub latency_ms: u64,
+    
+    /// Why the test failed; `None` when `ok`
+    pub error: Option<String>,
+}
+
+/// How one source fared in a command run over many sources
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceBatchResult {
+    pub source_id: String,
+    pub name: String,
+    pub ok: bool,
+    
+    /// Why it failed; `None` when `ok`
+    pub error: Option<String>,
+}
+
+/// What to do when an imported file or folder's name is already taken
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// Import under a free "name copy" name
+    #[default]
+    KeepBoth,
+    /// Replace files, merging folders into the existing one
+    Overwrite,
+    /// Leave the existing item and don't import
+    Skip,
+}
+
+/// Outcome of deleting a batch of files and folders
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSummary {
+    pub deleted: u64,
+    pub failed: u64,
+    
+    /// `path: error` for each item that could not be deleted
+    pub errors: Vec<String>,
+    
+    /// Size of the deleted items as measured before deleting. Trashed items
+    /// take up space until the trash is emptied.
+    pub total_bytes_freed: u64,
+}
+
+/// Move files that haven't been accessed for a while to a colder tier
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecyclePolicy {
+    /// Days since last access (or modification, when access isn't tracked)
+    /// before a file moves
+    pub idle_days: u32,
+    
+    /// Tier idle files are moved to; files already this cold are left alone
+    pub target_tier: StorageTier,
+}
+
+/// OS clutter ignored until the user edits the list
+pub const DEFAULT_IGNORE_PATTERNS: &[&str] = &[".DS_Store", "Thumbs.db", "__MACOSX"];
+
+/// Glob patterns for junk files hidden from listings and skipped by
+/// recursive copy, sync and archive.
+///
+/// `*` and `?` stay within one path component and `**` spans several.
+/// A pattern without `/` is matched against the file name; one with `/` is
+/// matched against the path relative to the listed source or the copied
+/// folder. Matching ignores case. An ignored folder is skipped with
+/// everything in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct IgnorePatterns(Vec<String>);
+
+impl Default for IgnorePatterns {
+    fn default() -> Self {
+        Self::new(DEFAULT_IGNORE_PATTERNS.iter().map(|p| p.to_string()).collect())
+    }
+}
+
+impl IgnorePatterns {
+    /// Blank patterns are dropped
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self(patterns
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect())
+    }
+    
+    pub fn patterns(&self) -> &[String] {
+        &self.0
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    
+    /// Whether the entry at `relative_path` should be skipped
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        let relative = relative_path.to_string_lossy().replace('\\', "/").to_lowercase();
+        let relative = relative.trim_start_matches('/');
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        let (relative, name): (Vec<char>, Vec<char>) = (relative.chars().collect(), name.chars().collect());
+        
+        self.0.iter().any(|pattern| {
+            let pattern = pattern.to_lowercase();
+            if !pattern.contains('/') {
+                return glob_match(&pattern.chars().collect::<Vec<_>>(), &name);
+            }
+            let pattern = pattern.trim_start_matches('/');
+            glob_match(&pattern.chars().collect::<Vec<_>>(), &relative)
+                // `**/x` also matches `x` at the top level
+                || pattern.strip_prefix("**/")
+                    .is_some_and(|rest| glob_match(&rest.chars().collect::<Vec<_>>(), &relative))
+        })
+    }
+}
+
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) if rest.first() == Some(&'*') => {
+            let rest = &rest[1..];
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        Some(('*', rest)) => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| glob_match(rest, &text[i..])),
+        Some(('?', rest)) => text.first().is_some_and(|c| *c != '/') && glob_match(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+/// Serialize a `Duration` as whole milliseconds
+mod duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+    
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
@@ -0,0 +1,253 @@
+//! Audit Log - append-only JSON-lines record of mutating operations
+//!
+//! Every delete, move and rename is written as one JSON object per line once
+//! the operation has finished, whether it succeeded or not. When the file
+//! reaches its size cap it is rotated to `audit.log.1` (shifting older files
+//! up to `MAX_ROTATED_FILES`), so the log never grows without bound.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// Size at which the active log file is rotated
+pub const DEFAULT_AUDIT_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated files kept besides the active one
+pub const MAX_ROTATED_FILES: usize = 5;
+
+/// Kind of mutating operation recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditOperation {
+    Delete,
+    Move,
+    Rename,
+    CrossStorageMove,
+    EmptyTrash,
+    RestoreVersion,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// When the operation finished
+    pub timestamp: DateTime<Utc>,
+    
+    /// OS user that performed the operation
+    pub user: String,
+    
+    pub operation: AuditOperation,
+    
+    /// Storage source the operation ran against
+    pub source_id: String,
+    
+    /// Destination source, for cross-storage moves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_source_id: Option<String>,
+    
+    /// Affected paths (`[from, to]` for moves and renames)
+    pub paths: Vec<PathBuf>,
+    
+    /// Object version brought back, for version restores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+    
+    pub success: bool,
+    
+    /// Error message when the operation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// Build an entry for a finished operation, stamped with the current time and user
+    pub fn new<T>(
+        operation: AuditOperation,
+        source_id: &str,
+        paths: &[&Path],
+        result: &Result<T>,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            user: current_user(),
+            operation,
+            source_id: source_id.to_string(),
+            destination_source_id: None,
+            paths: paths.iter().map(|p| p.to_path_buf()).collect(),
+            version_id: None,
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+    
+    /// Record the destination of a cross-storage operation
+    pub fn with_destination(mut self, source_id: &str) -> Self {
+        self.destination_source_id = Some(source_id.to_string());
+        self
+    }
+    
+    /// Record the object version an operation brought back
+    pub fn with_version(mut self, version_id: &str) -> Self {
+        self.version_id = Some(version_id.to_string());
+        self
+    }
+}
+
+/// Login name of the user running the app
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Size-capped JSON-lines audit log
+pub struct AuditLog {
+    /// Active log file
+    log_path: PathBuf,
+    
+    /// Rotate once the active file would exceed this many bytes
+    max_bytes: u64,
+    
+    /// Serializes appends and rotation
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// Create a log at `log_path`, rotating at `max_bytes`
+    pub fn new(log_path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            log_path,
+            max_bytes: max_bytes.max(1),
+            write_lock: Mutex::new(()),
+        }
+    }
+    
+    /// Create with default path in app data directory
+    pub async fn default_log() -> Result<Self> {
+        let data_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ursly")
+            .join("vfs");
+        
+        fs::create_dir_all(&data_dir).await?;
+        
+        Ok(Self::new(data_dir.join("audit.log"), DEFAULT_AUDIT_LOG_MAX_BYTES))
+    }
+    
+    /// Path of the `n`th rotated file (`audit.log.1` is the newest)
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut path = self.log_path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        PathBuf::from(path)
+    }
+    
+    /// Shift `audit.log` -> `.1` -> `.2` ..., dropping the oldest
+    async fn rotate(&self) -> Result<()> {
+        let _ = fs::remove_file(self.rotated_path(MAX_ROTATED_FILES)).await;
+        for n in (1..MAX_ROTATED_FILES).rev() {
+            let from = self.rotated_path(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(n + 1)).await?;
+            }
+        }
+        fs::rename(&self.log_path, self.rotated_path(1)).await
+            .context("Failed to rotate audit log")?;
+        
+        info!("Rotated audit log {:?}", self.log_path);
+        Ok(())
+    }
+    
+    /// Append one entry, rotating first if it would push the file past the cap
+    pub async fn append(&self, entry: &AuditEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry).context("Failed to serialize audit entry")?;
+        line.push('\n');
+        
+        let _guard = self.write_lock.lock().await;
+        
+        if let Some(parent) = self.log_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        
+        let current = fs::metadata(&self.log_path).await.map(|m| m.len()).unwrap_or(0);
+        if current > 0 && current + line.len() as u64 > self.max_bytes {
+            self.rotate().await?;
+        }
+        
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .await
+            .context("Failed to open audit log")?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        
+        Ok(())
+    }
+    
+    /// Entries with `from <= timestamp <= to`, oldest first, across rotated files
+    pub async fn export(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Result<Vec<AuditEntry>> {
+        let _guard = self.write_lock.lock().await;
+        
+        let mut files: Vec<PathBuf> = (1..=MAX_ROTATED_FILES).rev().map(|n| self.rotated_path(n)).collect();
+        files.push(self.log_path.clone());
+        
+        let mut entries = Vec::new();
+        for path in files {
+            let content = match fs::read_to_string(&path).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+            };
+            
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<AuditEntry>(line) {
+                    Ok(entry) => {
+                        let before_start = matches!(from, Some(from) if entry.timestamp < from);
+                        let after_end = matches!(to, Some(to) if entry.timestamp > to);
+                        if !before_start && !after_end {
+                            entries.push(entry);
+                        }
+                    }
+                    Err(e) => warn!("Skipping malformed audit line in {:?}: {}", path, e),
+                }
+            }
+        }
+        
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_rotates_at_cap_and_exports_across_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.log"), 300);
+        
+        let start = Utc::now();
+        for i in 0..6 {
+            let path = PathBuf::from(format!("/clip{}.mov", i));
+            let entry = AuditEntry::new(AuditOperation::Delete, "local", &[&path], &Ok::<_, anyhow::Error>(()));
+            log.append(&entry).await.unwrap();
+        }
+        
+        assert!(temp_dir.path().join("audit.log.1").exists(), "log should have rotated");
+        assert!(std::fs::metadata(temp_dir.path().join("audit.log")).unwrap().len() <= 300);
+        
+        let all = log.export(Some(start), None).await.unwrap();
+        let paths: Vec<_> = all.iter().map(|e| e.paths[0].clone()).collect();
+        assert_eq!(paths, (0..6).map(|i| PathBuf::from(format!("/clip{}.mov", i))).collect::<Vec<_>>());
+        
+        assert!(log.export(None, Some(start - chrono::Duration::seconds(1))).await.unwrap().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 //! HLS Streaming Server
 //!
 //! A lightweight local HTTP server for serving HLS streams.
-//! Uses Axum for the web framework.
+//! Uses Axum for the web framework. Honours `Range` requests, so a player
+//! seeking in a segment is sent only the bytes it asked for.
 
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -80,11 +81,9 @@ impl HlsServer {
             Router,
             routing::get,
             extract::Path,
-            response::IntoResponse,
-            http::{header, StatusCode},
+            http::HeaderMap,
         };
         use tower_http::cors::{CorsLayer, Any};
-        use std::net::TcpListener;
         
         // Ensure content directory exists
         tokio::fs::create_dir_all(&self.config.content_dir).await?;
@@ -95,31 +94,9 @@ impl HlsServer {
         
         // Create router
         let app = Router::new()
-            .route("/stream/:job_id/*path", get(move |Path((job_id, path)): Path<(String, String)>| {
-                let content_dir = content_dir.clone();
-                async move {
-                    let file_path = content_dir.join(&job_id).join(&path);
-                    
-                    match tokio::fs::read(&file_path).await {
-                        Ok(data) => {
-                            // Determine content type
-                            let content_type = if path.ends_with(".m3u8") {
-                                "application/vnd.apple.mpegurl"
-                            } else if path.ends_with(".ts") {
-                                "video/mp2t"
-                            } else {
-                                "application/octet-stream"
-                            };
-                            
-                            (
-                                StatusCode::OK,
-                                [(header::CONTENT_TYPE, content_type)],
-                                data,
-                            ).into_response()
-                        }
-                        Err(_) => (StatusCode::NOT_FOUND, "File not found").into_response(),
-                    }
-                }
+            .route("/stream/:job_id/*path", get(move |Path((job_id, path)): Path<(String, String)>, headers: HeaderMap| {
+                let file_path = content_dir.join(&job_id).join(&path);
+                async move { serve_file(&file_path, &headers).await }
             }))
             .route("/health", get(|| async { "OK" }))
             .layer(
@@ -158,6 +135,88 @@ impl HlsServer {
     }
 }
 
+/// Serve `file_path`, or the part of it a `Range` header asks for
+#[cfg(feature = "media")]
+async fn serve_file(file_path: &std::path::Path, headers: &axum::http::HeaderMap) -> axum::response::Response {
+    use axum::{
+        http::{header, HeaderValue, StatusCode},
+        response::IntoResponse,
+    };
+    use std::io::SeekFrom;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    
+    let Ok(mut file) = tokio::fs::File::open(file_path).await else {
+        return (StatusCode::NOT_FOUND, "File not found").into_response();
+    };
+    let size = match file.metadata().await {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    
+    let (status, start, end) = match headers.get(header::RANGE).and_then(|v| v.to_str().ok()) {
+        None => (StatusCode::OK, 0, size),
+        Some(range) => match parse_range(range, size) {
+            Some((start, end)) => (StatusCode::PARTIAL_CONTENT, start, end),
+            None => {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, format!("bytes */{}", size))],
+                ).into_response();
+            }
+        },
+    };
+    
+    let mut data = vec![0u8; (end - start) as usize];
+    if file.seek(SeekFrom::Start(start)).await.is_err() || file.read_exact(&mut data).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    
+    let name = file_path.to_string_lossy();
+    let content_type = if name.ends_with(".m3u8") {
+        "application/vnd.apple.mpegurl"
+    } else if name.ends_with(".ts") {
+        "video/mp2t"
+    } else {
+        "application/octet-stream"
+    };
+    
+    let mut response = (
+        status,
+        [(header::CONTENT_TYPE, content_type), (header::ACCEPT_RANGES, "bytes")],
+        data,
+    ).into_response();
+    if status == StatusCode::PARTIAL_CONTENT {
+        if let Ok(value) = HeaderValue::from_str(&format!("bytes {}-{}/{}", start, end - 1, size)) {
+            response.headers_mut().insert(header::CONTENT_RANGE, value);
+        }
+    }
+    response
+}
+
+/// The bytes a `Range: bytes=...` header asks for in a file of `size` bytes,
+/// as `(start, end)` with `end` exclusive. `None` unless it is a single range
+/// that overlaps the file.
+#[cfg_attr(not(feature = "media"), allow(dead_code))]
+fn parse_range(header: &str, size: u64) -> Option<(u64, u64)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    
+    let (start, end) = match spec.split_once('-')? {
+        ("", suffix) => {
+            let suffix: u64 = suffix.trim().parse().ok()?;
+            (size.saturating_sub(suffix), size)
+        }
+        (start, "") => (start.trim().parse().ok()?, size),
+        (start, end) => {
+            let last: u64 = end.trim().parse().ok()?;
+            (start.trim().parse().ok()?, last.saturating_add(1).min(size))
+        }
+    };
+    (start < end).then_some((start, end))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,6 +234,18 @@ mod tests {
         assert!(server.port().is_none());
         assert!(server.stream_url("test-job").is_none());
     }
+    
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 100)));
+        assert_eq!(parse_range("bytes=900-", 1000), Some((900, 1000)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 1000)));
+        // An end past the file is cut to the file
+        assert_eq!(parse_range("bytes=950-2000", 1000), Some((950, 1000)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=0-9,20-29", 1000), None);
+        assert_eq!(parse_range("items=0-9", 1000), None);
+    }
 }
 
 
@@ -5,7 +5,9 @@
 
 pub mod state;
 pub mod hls_server;
+pub mod audit_log;
 
 pub use state::VfsState;
 pub use hls_server::{HlsServer, HlsServerConfig};
+pub use audit_log::{AuditLog, AuditEntry, AuditOperation};
 
@@ -5,6 +5,8 @@ use parking_lot::RwLock;
 use tokio::sync::OnceCell;
 
 use crate::vfs::application::VfsService;
+use crate::vfs::application::vfs_service::UserStores;
+use crate::vfs::domain::CacheConfig;
 
 /// Global VFS state for Tauri
 pub struct VfsState {
@@ -26,7 +28,7 @@ impl VfsState {
             return Ok(());
         }
         
-        let service = VfsService::new().await?;
+        let service = VfsService::with_stores(CacheConfig::default(), UserStores::default_stores().await?).await?;
         self.service.set(Arc::new(service))
             .map_err(|_| anyhow::anyhow!("VFS service already initialized"))?;
         
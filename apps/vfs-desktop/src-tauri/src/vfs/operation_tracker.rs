@@ -10,11 +10,18 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 use tracing::{error, info};
 use uuid::Uuid;
 use chrono::Utc;
 
+use crate::vfs::application::copy_progress::CopyProgress;
+
+/// Progress updates are written to disk at most this often; starts and
+/// finishes are always written
+const PROGRESS_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Operation type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum OperationType {
@@ -23,6 +30,10 @@ pub enum OperationType {
     Delete,
     Move,
     Copy,
+    Archive,
+    Extract,
+    Checksum,
+    Transcode,
 }
 
 /// Operation status
@@ -52,6 +63,12 @@ pub struct Operation {
     pub file_size: Option<u64>,
     /// Bytes processed
     pub bytes_processed: u64,
+    /// Transfer rate, averaged since the start (large single-file copies)
+    #[serde(default)]
+    pub bytes_per_sec: Option<u64>,
+    /// Seconds left at that rate
+    #[serde(default)]
+    pub eta_secs: Option<u64>,
     /// Operation status
     pub status: OperationStatus,
     /// Error message if failed
@@ -75,6 +92,8 @@ pub struct OperationTracker {
     state_file: PathBuf,
     /// Maximum number of completed operations to keep in history
     max_history: usize,
+    /// When progress was last written to disk
+    last_progress_save: Mutex<Option<Instant>>,
 }
 
 impl OperationTracker {
@@ -88,6 +107,7 @@ impl OperationTracker {
             operations: Arc::new(RwLock::new(HashMap::new())),
             state_file,
             max_history,
+            last_progress_save: Mutex::new(None),
         };
         
         // Load existing operations
@@ -127,6 +147,19 @@ impl OperationTracker {
         Ok(())
     }
 
+    /// Save after a progress update, unless the last one was saved less than
+    /// `PROGRESS_SAVE_INTERVAL` ago
+    fn save_progress_state(&self) -> Result<()> {
+        {
+            let mut last = self.last_progress_save.lock();
+            if last.is_some_and(|at| at.elapsed() < PROGRESS_SAVE_INTERVAL) {
+                return Ok(());
+            }
+            *last = Some(Instant::now());
+        }
+        self.save_state()
+    }
+
     /// Create a new operation
     pub fn create_operation(
         &self,
@@ -147,6 +180,8 @@ impl OperationTracker {
             destination_path,
             file_size,
             bytes_processed: 0,
+            bytes_per_sec: None,
+            eta_secs: None,
             status: OperationStatus::Pending,
             error: None,
             created_at: now,
@@ -182,7 +217,29 @@ impl OperationTracker {
             }
         }
         
-        self.save_state()?;
+        self.save_progress_state()?;
+        Ok(())
+    }
+
+    /// Update a copy's progress along with its size, rate and time left
+    pub fn update_copy_progress(
+        &self,
+        operation_id: &str,
+        progress: &CopyProgress,
+    ) -> Result<()> {
+        {
+            let mut ops = self.operations.write();
+            if let Some(op) = ops.get_mut(operation_id) {
+                op.file_size = Some(progress.total);
+                op.bytes_processed = progress.done;
+                op.bytes_per_sec = Some(progress.bytes_per_sec);
+                op.eta_secs = progress.eta_secs;
+                op.status = OperationStatus::InProgress;
+                op.last_updated_at = Some(Utc::now());
+            }
+        }
+        
+        self.save_progress_state()?;
         Ok(())
     }
 
@@ -0,0 +1,86 @@
+//! Case folding - whether a mounted volume tells names apart by case
+//!
+//! Asked of the filesystem rather than found out by writing a file: macOS
+//! answers through `pathconf(_PC_CASE_SENSITIVE)`, Linux through the type
+//! `statfs` reports (FAT, exFAT and NTFS fold case; SMB and FUSE depend on
+//! the server). Windows volumes fold case unless a folder opts out.
+
+use std::path::Path;
+
+/// Whether the volume holding `path` tells names apart by case; `None` when
+/// the filesystem doesn't say
+pub fn volume_case_sensitivity(path: &Path) -> Option<bool> {
+    query(path)
+}
+
+#[cfg(target_os = "macos")]
+fn query(path: &Path) -> Option<bool> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: a NUL-terminated path that outlives the call
+    match unsafe { libc::pathconf(path.as_ptr(), libc::_PC_CASE_SENSITIVE) } {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn query(path: &Path) -> Option<bool> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    
+    const MSDOS_SUPER_MAGIC: u32 = 0x4d44;
+    const EXFAT_SUPER_MAGIC: u32 = 0x2011_bab0;
+    const NTFS_SB_MAGIC: u32 = 0x5346_544e;
+    const NTFS3_SUPER_MAGIC: u32 = 0x7366_746e;
+    const CIFS_SUPER_MAGIC: u32 = 0xff53_4d42;
+    const SMB2_SUPER_MAGIC: u32 = 0xfe53_4d42;
+    const SMB_SUPER_MAGIC: u32 = 0x517b;
+    const FUSE_SUPER_MAGIC: u32 = 0x6573_5546;
+    
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statfs is plain data, valid when zeroed
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: a NUL-terminated path and a statfs buffer that outlive the call
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    
+    // `f_type` is signed and wider on some architectures; magics are 32 bits
+    #[allow(clippy::unnecessary_cast)]
+    let fs_type = stat.f_type as u32;
+    match fs_type {
+        MSDOS_SUPER_MAGIC | EXFAT_SUPER_MAGIC | NTFS_SB_MAGIC | NTFS3_SUPER_MAGIC => Some(false),
+        CIFS_SUPER_MAGIC | SMB2_SUPER_MAGIC | SMB_SUPER_MAGIC | FUSE_SUPER_MAGIC => None,
+        _ => Some(true),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn query(_path: &Path) -> Option<bool> {
+    Some(false)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn query(_path: &Path) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_asking_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        let sensitive = volume_case_sensitivity(dir.path());
+        if cfg!(target_os = "windows") {
+            assert_eq!(sensitive, Some(false));
+        }
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
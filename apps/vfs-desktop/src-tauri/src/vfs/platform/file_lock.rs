@@ -0,0 +1,169 @@
+//! File write locks - one writer per file at a time
+//!
+//! Two windows (or the app and an outside tool) writing the same file at once
+//! can leave it with a mix of both. Writers take a lock first: an in-process
+//! lock keyed by the resolved path serializes writers in this app, and on
+//! Unix an advisory `flock` keeps out other processes that lock too. The
+//! `flock` is held on a `.<name>.lock` sidecar next to the file rather than
+//! the file itself, since an atomic write renames a new file over the target
+//! and a lock on the old one would guard nothing; the sidecar is removed on
+//! release. Windows locks are mandatory and per-handle (they would block our
+//! own write through a second handle), so there only the in-process lock is
+//! taken. Readers never lock.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use tokio::sync::OwnedMutexGuard;
+
+use crate::vfs::domain::VfsError;
+
+type PathLock = tokio::sync::Mutex<()>;
+
+/// Locks held or waited on in this process, by resolved path
+static LOCKS: Lazy<Mutex<HashMap<PathBuf, Weak<PathLock>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Exclusive right to write a file; released on drop
+pub struct FileWriteLock {
+    _guard: OwnedMutexGuard<()>,
+    /// The sidecar holding the `flock`, which goes with the descriptor
+    sidecar: Option<(PathBuf, std::fs::File)>,
+}
+
+impl Drop for FileWriteLock {
+    fn drop(&mut self) {
+        // Unlinked while still locked, so a writer waiting on this sidecar
+        // sees it go and opens a fresh one
+        if let Some((path, _)) = &self.sidecar {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
+
+/// Lock `path` for writing, waiting for other writers to finish. The file
+/// itself is left alone; it needn't exist.
+pub async fn lock_for_write(path: &Path) -> Result<FileWriteLock> {
+    let guard = path_lock(path).lock_owned().await;
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || os_lock(&path, false, guard)).await?
+}
+
+/// Lock `path` for writing, failing with `VfsError::Locked` straight away if
+/// another writer holds it
+pub fn try_lock_for_write(path: &Path) -> Result<FileWriteLock> {
+    let guard = path_lock(path).try_lock_owned().map_err(|_| locked(path))?;
+    os_lock(path, true, guard)
+}
+
+fn locked(path: &Path) -> anyhow::Error {
+    VfsError::Locked(format!("{} is being written by someone else", path.display())).into()
+}
+
+/// The in-process lock for `path`, by the path with its folder resolved
+/// (the file itself may not exist yet)
+fn path_lock(path: &Path) -> Arc<PathLock> {
+    let key = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => std::fs::canonicalize(parent)
+            .map(|parent| parent.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    };
+    
+    let mut locks = LOCKS.lock();
+    locks.retain(|_, lock| lock.strong_count() > 0);
+    if let Some(lock) = locks.get(&key).and_then(Weak::upgrade) {
+        return lock;
+    }
+    let lock = Arc::new(PathLock::new(()));
+    locks.insert(key, Arc::downgrade(&lock));
+    lock
+}
+
+/// The lock file for `path`: `.<name>.lock` in the same folder
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or(path.as_os_str()));
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+#[cfg(unix)]
+fn os_lock(path: &Path, nonblocking: bool, guard: OwnedMutexGuard<()>) -> Result<FileWriteLock> {
+    use anyhow::Context;
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::io::AsRawFd;
+    
+    let sidecar = sidecar_path(path);
+    loop {
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&sidecar)
+            .with_context(|| format!("Failed to open {:?} for locking", sidecar))?;
+        
+        let operation = if nonblocking { libc::LOCK_EX | libc::LOCK_NB } else { libc::LOCK_EX };
+        // SAFETY: the descriptor belongs to `file`, which outlives the call
+        if unsafe { libc::flock(file.as_raw_fd(), operation) } != 0 {
+            let err = std::io::Error::last_os_error();
+            if nonblocking && err.kind() == std::io::ErrorKind::WouldBlock {
+                return Err(locked(path));
+            }
+            return Err(err).with_context(|| format!("Failed to lock {:?}", path));
+        }
+        
+        // The previous holder may have removed the sidecar while we waited;
+        // a lock on an unlinked file keeps out nobody, so start over
+        let held = file.metadata()?;
+        match std::fs::metadata(&sidecar) {
+            Ok(current) if current.dev() == held.dev() && current.ino() == held.ino() => {
+                return Ok(FileWriteLock { _guard: guard, sidecar: Some((sidecar, file)) });
+            }
+            _ => continue,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn os_lock(_path: &Path, _nonblocking: bool, guard: OwnedMutexGuard<()>) -> Result<FileWriteLock> {
+    Ok(FileWriteLock { _guard: guard, sidecar: None })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_try_lock_fails_while_held() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("edit.txt");
+        
+        let held = lock_for_write(&path).await.unwrap();
+        assert!(!path.exists());
+        let err = try_lock_for_write(&path).unwrap_err();
+        assert!(matches!(err.downcast_ref::<VfsError>(), Some(VfsError::Locked(_))), "{}", err);
+        
+        drop(held);
+        assert!(!sidecar_path(&path).exists());
+        drop(try_lock_for_write(&path).unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_lock_survives_rename_over_target() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("edit.txt");
+        std::fs::write(&path, b"old").unwrap();
+        
+        let held = lock_for_write(&path).await.unwrap();
+        let staged = temp.path().join("edit.txt.tmp");
+        std::fs::write(&staged, b"new").unwrap();
+        std::fs::rename(&staged, &path).unwrap();
+        
+        assert!(try_lock_for_write(&path).is_err());
+        drop(held);
+    }
+}
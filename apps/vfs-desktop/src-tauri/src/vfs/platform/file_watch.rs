@@ -0,0 +1,76 @@
+//! File watch - told by the OS when a local file changes
+//!
+//! Backed by FSEvents, inotify or ReadDirectoryChangesW through `notify`, so
+//! a followed log is read when it's written instead of on a timer. The folder
+//! holding the file is watched rather than the file itself: a log rotated by
+//! renaming it away and creating a new one keeps reporting, where a watch on
+//! the old inode would go quiet.
+
+use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+/// Changes to one file; the OS watch is released on drop
+pub struct FileWatch {
+    _watcher: RecommendedWatcher,
+    changes: mpsc::UnboundedReceiver<()>,
+}
+
+impl FileWatch {
+    /// Wait until the file was written, created, renamed or removed since
+    /// the last call. A burst of changes is reported once.
+    pub async fn changed(&mut self) {
+        // The sender lives in the watcher, which lives as long as `self`
+        let _ = self.changes.recv().await;
+        while self.changes.try_recv().is_ok() {}
+    }
+}
+
+/// Watch the file at `path`, which need not exist yet
+pub fn watch_file(path: &Path) -> Result<FileWatch> {
+    let name: OsString = path.file_name()
+        .with_context(|| format!("Not a file: {:?}", path))?
+        .to_os_string();
+    let folder = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    
+    let (sender, changes) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if event.paths.iter().any(|p| p.file_name() == Some(name.as_os_str())) {
+            let _ = sender.send(());
+        }
+    }).context("Failed to create file watcher")?;
+    watcher.watch(folder, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {:?}", folder))?;
+    
+    Ok(FileWatch { _watcher: watcher, changes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_reports_writes_to_the_file_only() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.path().join("render.log");
+        std::fs::write(&log, "started\n").unwrap();
+        let mut watch = watch_file(&log).unwrap();
+        
+        // Other files in the folder are ignored
+        std::fs::write(dir.path().join("other.log"), "noise\n").unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(300), watch.changed()).await.is_err());
+        
+        let mut file = std::fs::OpenOptions::new().append(true).open(&log).unwrap();
+        writeln!(file, "frame 1").unwrap();
+        file.flush().unwrap();
+        tokio::time::timeout(Duration::from_secs(5), watch.changed()).await.unwrap();
+    }
+}
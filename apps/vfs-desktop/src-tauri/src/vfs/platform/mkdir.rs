@@ -0,0 +1,66 @@
+//! Recursive directory creation that tolerates concurrent creators
+//!
+//! Parallel copies into the same new subtree all create its folders. Each
+//! missing level is created on its own, and losing the race for one (it
+//! already exists by the time we get to it) counts as success as long as
+//! what's there is a directory.
+
+use std::io;
+use std::path::Path;
+use tokio::fs;
+
+/// Create `path` and any missing parents, like `mkdir -p`
+pub async fn ensure_dir_all(path: &Path) -> io::Result<()> {
+    // Walk up to the deepest level that exists, remembering what's missing
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        match fs::metadata(current).await {
+            Ok(metadata) if metadata.is_dir() => break,
+            Ok(_) => return Err(not_a_directory(current)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => missing.push(current),
+            Err(e) => return Err(e),
+        }
+        match current.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => current = parent,
+            _ => break,
+        }
+    }
+    
+    for dir in missing.into_iter().rev() {
+        match fs::create_dir(dir).await {
+            Ok(()) => {}
+            // Another task created it first
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                if !fs::metadata(dir).await?.is_dir() {
+                    return Err(not_a_directory(dir));
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+fn not_a_directory(path: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::AlreadyExists, format!("{:?} exists and is not a directory", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_file_in_the_way_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("day1"), "not a folder").unwrap();
+        
+        assert!(ensure_dir_all(&temp.path().join("day1")).await.is_err());
+        assert!(ensure_dir_all(&temp.path().join("day1/cam_a")).await.is_err());
+        
+        ensure_dir_all(&temp.path().join("day2/cam_a")).await.unwrap();
+        ensure_dir_all(&temp.path().join("day2/cam_a")).await.unwrap();
+        assert!(temp.path().join("day2/cam_a").is_dir());
+    }
+}
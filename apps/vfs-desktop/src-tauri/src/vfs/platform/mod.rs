@@ -5,16 +5,46 @@
 //! - File permissions and ACLs (Windows/Unix)
 //! - Path utilities (UNC paths, separators)
 //! - Network timeout wrappers
+//! - Launching a terminal in a folder
+//! - Enumerating mounted volumes
+//! - Moving files to the system trash
+//! - Following or copying symlinks in recursive operations
+//! - Locking files against concurrent writers
+//! - Copy-on-write clones of files
+//! - Creating directory trees alongside concurrent creators
+//! - Replacing a file's contents atomically
+//! - Asking a volume whether it folds case
+//! - Watching a local file for changes
 
 pub mod disk;
 pub mod permissions;
 pub mod paths;
 pub mod network;
+pub mod terminal;
+pub mod volumes;
+pub mod trash;
+pub mod symlinks;
+pub mod file_lock;
+pub mod reflink;
+pub mod mkdir;
+pub mod replace;
+pub mod case_folding;
+pub mod file_watch;
 
 pub use disk::*;
 pub use permissions::*;
 pub use paths::*;
 pub use network::*;
+pub use terminal::*;
+pub use volumes::*;
+pub use trash::*;
+pub use symlinks::*;
+pub use file_lock::*;
+pub use reflink::*;
+pub use mkdir::*;
+pub use replace::*;
+pub use case_folding::*;
+pub use file_watch::*;
 
 
 
@@ -12,6 +12,8 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use crate::vfs::domain::{RetryPolicy, VfsError};
+
 /// Default timeout for network operations (30 seconds)
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
@@ -54,10 +56,7 @@ where
             result.map(|r| TimedResult { result: r, duration })
         }
         Err(_) => {
-            Err(anyhow::anyhow!(
-                "Operation timed out after {:?}",
-                timeout
-            ))
+            Err(VfsError::Timeout(format!("Operation timed out after {:?}", timeout)).into())
         }
     }
 }
@@ -186,7 +185,7 @@ impl ConnectionMonitor {
     }
 }
 
-/// Retry an operation with exponential backoff
+/// Retry an operation with exponential backoff (no cap, no jitter)
 pub async fn retry_with_backoff<T, F, Fut>(
     max_attempts: u32,
     base_delay: Duration,
@@ -196,29 +195,93 @@ where
     F: Fn() -> Fut,
     Fut: Future<Output = Result<T>>,
 {
+    let policy = RetryPolicy {
+        max_attempts,
+        base_delay,
+        max_delay: Duration::MAX,
+        jitter: false,
+    };
+    retry_with_policy(&policy, operation).await
+}
+
+/// Retry an operation as `policy` says. Errors a retry can't fix (not found,
+/// permission denied, invalid input, ...) are returned straight away; once
+/// `max_attempts` tries have failed, the last error is returned.
+pub async fn retry_with_policy<T, F, Fut>(policy: &RetryPolicy, operation: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
     let mut attempt = 0;
-    let mut last_error = None;
     
-    while attempt < max_attempts {
+    loop {
         match operation().await {
             Ok(result) => return Ok(result),
             Err(e) => {
                 attempt += 1;
-                last_error = Some(e);
-                
-                if attempt < max_attempts {
-                    let delay = base_delay * 2u32.pow(attempt - 1);
-                    warn!(
-                        "Operation failed (attempt {}/{}), retrying in {:?}",
-                        attempt, max_attempts, delay
-                    );
-                    tokio::time::sleep(delay).await;
+                if attempt >= max_attempts || !is_transient(&e) {
+                    return Err(e);
                 }
+                
+                let delay = policy.delay(attempt);
+                warn!(
+                    "Operation failed (attempt {}/{}), retrying in {:?}: {}",
+                    attempt, max_attempts, delay, e
+                );
+                tokio::time::sleep(delay).await;
             }
         }
     }
-    
-    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Operation failed after {} attempts", max_attempts)))
+}
+
+/// An HTTP request that came back with an unsuccessful status, for requests
+/// made outside OpenDAL
+#[derive(Debug, Clone)]
+pub struct HttpStatusError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// Whether an HTTP status is worth retrying: throttling or a server error
+fn is_transient_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+/// Whether an error might go away if the operation is tried again: a timeout,
+/// or a request the server throttled (429) or failed (5xx). Anything else,
+/// including errors we can't classify, is returned as-is.
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(err) = cause.downcast_ref::<VfsError>() {
+            return matches!(err, VfsError::Timeout(_));
+        }
+        if cause.is::<tokio::time::error::Elapsed>() {
+            return true;
+        }
+        if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+            return err.kind() == std::io::ErrorKind::TimedOut;
+        }
+        if let Some(err) = cause.downcast_ref::<HttpStatusError>() {
+            return is_transient_status(err.status);
+        }
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            return err.is_timeout() || err.status().is_some_and(|status| is_transient_status(status.as_u16()));
+        }
+        if let Some(err) = cause.downcast_ref::<opendal::Error>() {
+            // OpenDAL marks timeouts and 5xx responses temporary
+            return err.is_temporary() || err.kind() == opendal::ErrorKind::RateLimited;
+        }
+        false
+    })
 }
 
 /// Wrap a network operation with timeout and retry
@@ -385,7 +448,7 @@ mod tests {
             async move {
                 let attempt = c.fetch_add(1, Ordering::Relaxed);
                 if attempt < 2 {
-                    Err(anyhow::anyhow!("Simulated failure"))
+                    Err(VfsError::Timeout("Simulated timeout".to_string()).into())
                 } else {
                     Ok(42)
                 }
@@ -397,6 +460,70 @@ mod tests {
         assert_eq!(counter.load(Ordering::Relaxed), 3);
     }
     
+    #[tokio::test]
+    async fn test_retry_policy_limits_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: true,
+        };
+        
+        // Fails twice, then succeeds on the last allowed attempt
+        let calls = &AtomicU64::new(0);
+        let result = retry_with_policy(&policy, || async move {
+            match calls.fetch_add(1, Ordering::Relaxed) {
+                0 | 1 => Err(std::io::Error::from(std::io::ErrorKind::TimedOut).into()),
+                _ => Ok("listed"),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), "listed");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        
+        // Never succeeds: the error from the last attempt comes back
+        let calls = &AtomicU64::new(0);
+        let result: Result<()> = retry_with_policy(&policy, || async move {
+            let n = calls.fetch_add(1, Ordering::Relaxed) + 1;
+            Err(HttpStatusError { status: 503, message: format!("failure {}", n) }.into())
+        }).await;
+        assert_eq!(result.unwrap_err().to_string(), "failure 3");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        
+        // Permanent errors aren't retried
+        let calls = &AtomicU64::new(0);
+        let result: Result<()> = retry_with_policy(&policy, || async move {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(std::io::Error::from(std::io::ErrorKind::NotFound).into())
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        
+        // Nor are errors we can't classify, or client errors other than throttling
+        assert!(!is_transient(&anyhow::anyhow!("connection reset")));
+        assert!(!is_transient(&HttpStatusError { status: 400, message: "bad request".to_string() }.into()));
+        assert!(is_transient(&HttpStatusError { status: 429, message: "slow down".to_string() }.into()));
+    }
+    
+    #[test]
+    fn test_retry_delays_are_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(policy.delay(8), Duration::from_secs(1));
+        assert_eq!(policy.delay(40), Duration::from_secs(1));
+        
+        let jittered = RetryPolicy { jitter: true, ..policy };
+        for attempt in 1..12 {
+            let delay = jittered.delay(attempt);
+            assert!(delay <= policy.backoff(attempt) && delay >= policy.backoff(attempt) / 2);
+        }
+    }
+    
     #[tokio::test]
     async fn test_check_path_connection() {
         let temp_dir = TempDir::new().unwrap();
@@ -3,11 +3,12 @@
 //! Provides unified permission handling for Windows ACLs and Unix permissions.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tracing::{debug, warn};
 
 /// File permission information (cross-platform)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilePermissions {
     /// Unix-style mode (0o644, etc.) - approximated on Windows
     pub mode: u32,
@@ -136,6 +137,24 @@ pub fn chmod(path: &Path, mode: u32) -> Result<()> {
     set_permissions(path, &perms)
 }
 
+/// The process umask: permission bits new files are created without.
+/// Read once, since the only way to ask is to set it.
+#[cfg(unix)]
+pub fn umask() -> u32 {
+    static UMASK: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+    *UMASK.get_or_init(|| {
+        // SAFETY: umask can't fail; the old mask is put straight back
+        let old = unsafe { libc::umask(0o022) };
+        unsafe { libc::umask(old) };
+        u32::from(old)
+    })
+}
+
+#[cfg(not(unix))]
+pub fn umask() -> u32 {
+    0
+}
+
 /// Set file owner (chown equivalent) - Unix only, no-op on Windows
 pub fn chown(path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
     #[cfg(unix)]
@@ -154,6 +173,30 @@ pub fn chown(path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
     }
 }
 
+/// Whether a listing should treat the entry as hidden: a leading dot
+/// anywhere, plus the hidden attribute on Windows and the `UF_HIDDEN` flag
+/// (`chflags hidden`) on macOS
+pub fn is_hidden(name: &str, metadata: &std::fs::Metadata) -> bool {
+    name.starts_with('.') || has_hidden_flag(metadata)
+}
+
+#[cfg(windows)]
+fn has_hidden_flag(metadata: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    metadata.file_attributes() & 0x2 != 0  // FILE_ATTRIBUTE_HIDDEN
+}
+
+#[cfg(target_os = "macos")]
+fn has_hidden_flag(metadata: &std::fs::Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    metadata.st_flags() & libc::UF_HIDDEN != 0
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn has_hidden_flag(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
 // =============================================================================
 // Unix Implementation
 // =============================================================================
@@ -0,0 +1,146 @@
+//! Copy-on-write clones - near-instant copies on APFS, Btrfs and XFS
+//!
+//! A reflink shares the source's blocks until either file is written, so a
+//! 20 GB clip copies in milliseconds and takes no extra space. macOS uses
+//! `clonefile`, Linux the `FICLONE` ioctl. Other filesystems, other platforms
+//! and copies across volumes report that they can't, and `copy_file` falls
+//! back to copying the bytes.
+
+use anyhow::Result;
+use std::io;
+use std::path::Path;
+use tokio::fs;
+use tracing::debug;
+
+/// Clone `src` to `dst`, which must not exist, sharing its blocks. `Ok(false)`
+/// when the platform or filesystem can't, or the two are on different volumes.
+pub fn try_reflink(src: &Path, dst: &Path) -> io::Result<bool> {
+    match reflink(src, dst) {
+        Ok(()) => Ok(true),
+        Err(e) if is_unsupported(&e) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Copy a file like `fs::copy` (replacing `dst`, carrying the mode over),
+/// as a reflink where the filesystem supports it
+pub async fn copy_file(src: &Path, dst: &Path) -> Result<()> {
+    let (from, to) = (src.to_path_buf(), dst.to_path_buf());
+    let cloned = tokio::task::spawn_blocking(move || clone_over(&from, &to)).await?;
+    
+    match cloned {
+        Ok(true) => return Ok(()),
+        Ok(false) => {}
+        Err(e) => debug!("Reflink of {:?} failed, copying instead: {}", src, e),
+    }
+    fs::copy(src, dst).await?;
+    Ok(())
+}
+
+/// Clone `src` beside `dst` under a temporary name, then rename it over
+/// `dst`, so an existing `dst` is only replaced once the clone exists
+fn clone_over(src: &Path, dst: &Path) -> io::Result<bool> {
+    let name = dst.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = dst.with_file_name(format!(".{}.ursly-clone-{}", name, uuid::Uuid::new_v4()));
+    if !try_reflink(src, &temp)? {
+        return Ok(false);
+    }
+    if let Err(e) = std::fs::rename(&temp, dst) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+fn is_unsupported(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::Unsupported || err.raw_os_error().is_some_and(is_unsupported_code)
+}
+
+/// Errors for a filesystem without clones, or a clone across volumes
+#[cfg(unix)]
+fn is_unsupported_code(code: i32) -> bool {
+    [libc::EXDEV, libc::ENOTSUP, libc::EOPNOTSUPP, libc::EINVAL, libc::ENOTTY, libc::ENOSYS].contains(&code)
+}
+
+#[cfg(not(unix))]
+fn is_unsupported_code(_code: i32) -> bool {
+    false
+}
+
+#[cfg(target_os = "macos")]
+fn reflink(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    
+    let src = CString::new(src.as_os_str().as_bytes())?;
+    let dst = CString::new(dst.as_os_str().as_bytes())?;
+    // SAFETY: both are NUL-terminated paths that outlive the call
+    if unsafe { libc::clonefile(src.as_ptr(), dst.as_ptr(), 0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn reflink(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    
+    /// `_IOW(0x94, 9, int)` from `linux/fs.h`
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+    
+    let source = std::fs::File::open(src)?;
+    let dest = std::fs::OpenOptions::new().write(true).create_new(true).open(dst)?;
+    // SAFETY: both descriptors belong to files that outlive the call
+    if unsafe { libc::ioctl(dest.as_raw_fd(), FICLONE as _, source.as_raw_fd()) } != 0 {
+        let err = io::Error::last_os_error();
+        drop(dest);
+        let _ = std::fs::remove_file(dst);
+        return Err(err);
+    }
+    
+    // `fs::copy` carries the mode over, so clones do too
+    dest.set_permissions(source.metadata()?.permissions())?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn reflink(_src: &Path, _dst: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_reflink_copy_has_identical_content() {
+        let temp = TempDir::new().unwrap();
+        let (src, dst) = (temp.path().join("A001.mov"), temp.path().join("A001 copy.mov"));
+        let data: Vec<u8> = (0..256 * 1024).map(|i| (i % 253) as u8).collect();
+        std::fs::write(&src, &data).unwrap();
+        
+        match try_reflink(&src, &dst) {
+            Ok(true) => assert_eq!(std::fs::read(&dst).unwrap(), data),
+            Ok(false) => eprintln!("skipping: {:?} doesn't support reflinks", temp.path()),
+            Err(e) => panic!("reflink failed: {}", e),
+        }
+        
+        // Either way `copy_file` gets the bytes across, replacing what's there
+        std::fs::write(&dst, b"stale").unwrap();
+        copy_file(&src, &dst).await.unwrap();
+        assert_eq!(std::fs::read(&dst).unwrap(), data);
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_failed_copy_keeps_existing_destination() {
+        let temp = TempDir::new().unwrap();
+        let dst = temp.path().join("A001.mov");
+        std::fs::write(&dst, b"the only take").unwrap();
+        
+        assert!(copy_file(&temp.path().join("missing.mov"), &dst).await.is_err());
+        assert_eq!(std::fs::read(&dst).unwrap(), b"the only take");
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,57 @@
+//! Replacing a file's contents without leaving it half-written
+//!
+//! The contents go to a temporary file next to the target, which is then
+//! renamed over it. A crash or a full disk mid-write leaves the old file in
+//! place instead of a truncated one.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Write `contents` to `path`, replacing it in one step
+pub async fn replace_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp = temp_path(path);
+    let written = async {
+        let mut file = fs::File::create(&temp).await?;
+        file.write_all(contents).await?;
+        file.sync_all().await
+    }.await;
+    
+    match written {
+        Ok(()) => fs::rename(&temp, path).await.map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            e
+        }),
+        Err(e) => {
+            let _ = fs::remove_file(&temp).await;
+            Err(e)
+        }
+    }
+}
+
+/// `.<name>.<uuid>.tmp` beside `path`
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, uuid::Uuid::new_v4().simple()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_replaces_contents_and_leaves_no_temp_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("recents.json");
+        std::fs::write(&path, "old").unwrap();
+        
+        replace_file(&path, b"new").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 1);
+        
+        // A missing folder fails without touching anything
+        assert!(replace_file(&temp.path().join("gone/recents.json"), b"new").await.is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Symlink handling for recursive operations on filesystem-backed sources
+//!
+//! Without `follow_symlinks`, a recursive copy recreates each link as a link
+//! (skipping it with a warning where the OS won't allow that) and a listing
+//! reports links without descending into them. With it, links are
+//! dereferenced, and the canonical path of every folder on the way down is
+//! tracked so a link pointing back up the tree is reported instead of walked
+//! forever.
+
+use anyhow::{Context, Result};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::warn;
+
+use crate::vfs::domain::VfsError;
+use crate::vfs::platform::mkdir::ensure_dir_all;
+use crate::vfs::platform::permissions::{chmod, get_permissions, umask};
+use crate::vfs::platform::reflink::copy_file;
+use crate::vfs::ports::CopyOptions;
+
+/// Entries found by `list_tree`
+#[derive(Debug, Default)]
+pub struct TreeListing {
+    /// Every entry that isn't a folder, as seen from the root (links that
+    /// weren't followed are listed as themselves)
+    pub files: Vec<PathBuf>,
+    
+    /// Links to a folder already being walked, left unfollowed
+    pub cycles: Vec<PathBuf>,
+}
+
+/// List everything below `root`. Folders that can't be read are skipped
+/// with a warning.
+pub async fn list_tree(root: &Path, follow_symlinks: bool) -> Result<TreeListing> {
+    let canonical = fs::canonicalize(root).await
+        .with_context(|| format!("Failed to read {:?}", root))?;
+    let mut listing = TreeListing::default();
+    let mut pending = vec![(root.to_path_buf(), vec![canonical])];
+    
+    while let Some((dir, branch)) = pending.pop() {
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read directory {:?}: {}", dir, e);
+                continue;
+            }
+        };
+        
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_link = entry.file_type().await?.is_symlink();
+            if is_link && !follow_symlinks {
+                listing.files.push(path);
+                continue;
+            }
+            
+            let metadata = match fs::metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    warn!("Skipping {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            if !metadata.is_dir() {
+                listing.files.push(path);
+                continue;
+            }
+            
+            match enter_dir(&branch, &path, is_link).await? {
+                Some(branch) => pending.push((path, branch)),
+                None => {
+                    warn!("Not following {:?}: it links back to a folder being listed", path);
+                    listing.cycles.push(path);
+                }
+            }
+        }
+    }
+    
+    listing.files.sort();
+    Ok(listing)
+}
+
+/// Copy the contents of folder `from` into `to`, creating `to` as needed.
+///
+/// Links are copied or followed as `options.follow_symlinks` says; following
+/// a link back into a folder being copied fails rather than copying forever.
+pub async fn copy_dir_tree(from: &Path, to: &Path, options: &CopyOptions) -> Result<()> {
+    let canonical = fs::canonicalize(from).await
+        .with_context(|| format!("Failed to read {:?}", from))?;
+    let mut pending = vec![(from.to_path_buf(), to.to_path_buf(), vec![canonical])];
+    
+    while let Some((src_dir, dest_dir, branch)) = pending.pop() {
+        ensure_dir_all(&dest_dir).await?;
+        let mut entries = fs::read_dir(&src_dir).await?;
+        
+        while let Some(entry) = entries.next_entry().await? {
+            let src = entry.path();
+            let dest = dest_dir.join(entry.file_name());
+            let is_link = entry.file_type().await?.is_symlink();
+            
+            if is_link && !options.follow_symlinks {
+                if options.overwrite {
+                    fs::remove_file(&dest).await.ok();
+                }
+                copy_symlink(&src, &dest).await?;
+                continue;
+            }
+            
+            if fs::metadata(&src).await?.is_dir() {
+                let Some(branch) = enter_dir(&branch, &src, is_link).await? else {
+                    return Err(VfsError::InvalidInput(format!(
+                        "Symlink cycle: {:?} links back to a folder being copied", src
+                    )).into());
+                };
+                pending.push((src.clone(), dest.clone(), branch));
+            } else {
+                copy_file(&src, &dest).await?;
+                if !options.preserve_attributes {
+                    reset_copied_mode(&src, &dest).await?;
+                }
+            }
+            
+            if options.preserve_attributes {
+                copy_attributes(&src, &dest).await.ok();
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Recreate symlink `link` at `dest`, pointing at the same target. Where the
+/// OS won't create links (Windows without the privilege) it is skipped with a
+/// warning.
+pub async fn copy_symlink(link: &Path, dest: &Path) -> Result<()> {
+    let target = fs::read_link(link).await
+        .with_context(|| format!("Failed to read link {:?}", link))?;
+    
+    #[cfg(unix)]
+    let created = fs::symlink(&target, dest).await;
+    #[cfg(windows)]
+    let created = if fs::metadata(link).await.is_ok_and(|m| m.is_dir()) {
+        fs::symlink_dir(&target, dest).await
+    } else {
+        fs::symlink_file(&target, dest).await
+    };
+    #[cfg(not(any(unix, windows)))]
+    let created: std::io::Result<()> = Err(ErrorKind::Unsupported.into());
+    
+    match created {
+        Ok(()) => Ok(()),
+        Err(e) if matches!(e.kind(), ErrorKind::PermissionDenied | ErrorKind::Unsupported) => {
+            warn!("Skipping symlink {:?}: {}", link, e);
+            Ok(())
+        }
+        Err(e) => Err(e).with_context(|| format!("Failed to create link {:?}", dest)),
+    }
+}
+
+/// Give `to` the mode, access/modification times and owner of `from`.
+///
+/// Ownership is best-effort: handing a file to another user needs root (or
+/// `CAP_CHOWN`), so a refused `chown` is logged and the copy keeps the
+/// copying user as owner.
+pub async fn copy_attributes(from: &Path, to: &Path) -> Result<()> {
+    let metadata = fs::metadata(from).await?;
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        if let Err(e) = super::permissions::chown(to, Some(metadata.uid()), Some(metadata.gid())) {
+            tracing::debug!("Keeping current owner of {:?}: {:#}", to, e);
+        }
+        chmod(to, metadata.permissions().mode() & 0o777)?;
+    }
+    #[cfg(not(unix))]
+    super::permissions::set_permissions(to, &get_permissions(from)?)?;
+    
+    if let (Ok(mtime), Ok(atime)) = (metadata.modified(), metadata.accessed()) {
+        let mtime = filetime::FileTime::from_system_time(mtime);
+        let atime = filetime::FileTime::from_system_time(atime);
+        filetime::set_file_times(to, atime, mtime)?;
+    }
+    
+    Ok(())
+}
+
+/// Give a copied file the mode `cp` does without `-p`: the source's, less
+/// the bits the umask takes away from new files. Never adds a bit the
+/// source lacks. Used when attributes aren't preserved, since `fs::copy`
+/// carries the mode over unmasked.
+pub async fn reset_copied_mode(from: &Path, to: &Path) -> Result<()> {
+    let mode = get_permissions(from)?.mode;
+    chmod(to, mode & 0o777 & !umask())
+}
+
+/// The branch of canonical folder paths below `dir`, or `None` if `dir` is
+/// already on `branch` (a link back up the tree)
+async fn enter_dir(branch: &[PathBuf], dir: &Path, is_link: bool) -> Result<Option<Vec<PathBuf>>> {
+    let canonical = match (is_link, branch.last(), dir.file_name()) {
+        // Only links can lead outside the parent, so other folders skip the lookup
+        (false, Some(parent), Some(name)) => parent.join(name),
+        _ => fs::canonicalize(dir).await.with_context(|| format!("Failed to resolve {:?}", dir))?,
+    };
+    if branch.contains(&canonical) {
+        return Ok(None);
+    }
+    
+    let mut below = branch.to_vec();
+    below.push(canonical);
+    Ok(Some(below))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_symlink_loop_is_reported_not_walked() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("shoot");
+        std::fs::create_dir_all(root.join("day1")).unwrap();
+        std::fs::write(root.join("day1/A001.mov"), b"clip").unwrap();
+        std::os::unix::fs::symlink(&root, root.join("day1/loop")).unwrap();
+        
+        let listing = list_tree(&root, true).await.unwrap();
+        assert_eq!(listing.files, vec![root.join("day1/A001.mov")]);
+        assert_eq!(listing.cycles, vec![root.join("day1/loop")]);
+        
+        let following = CopyOptions { recursive: true, follow_symlinks: true, ..Default::default() };
+        let err = copy_dir_tree(&root, &temp.path().join("followed"), &following).await.unwrap_err();
+        assert!(err.to_string().contains("Symlink cycle"));
+        
+        // Without following, the link itself is copied and still points at the original
+        let copied = temp.path().join("copied");
+        let options = CopyOptions { recursive: true, ..Default::default() };
+        copy_dir_tree(&root, &copied, &options).await.unwrap();
+        assert_eq!(std::fs::read(copied.join("day1/A001.mov")).unwrap(), b"clip");
+        assert_eq!(std::fs::read_link(copied.join("day1/loop")).unwrap(), root);
+    }
+}
@@ -0,0 +1,114 @@
+//! Cross-platform "open terminal here"
+//!
+//! Picks the first installed terminal from a per-platform preference list and
+//! launches it with its working directory set to a folder.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+/// Terminals in order of preference, per OS (`std::env::consts::OS`)
+pub fn terminal_candidates(os: &str) -> &'static [&'static str] {
+    match os {
+        "macos" => &["iTerm", "Terminal"],
+        "windows" => &["wt", "cmd"],
+        "linux" => &["x-terminal-emulator", "gnome-terminal", "konsole", "xfce4-terminal", "xterm"],
+        _ => &[],
+    }
+}
+
+/// A resolved terminal launch
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalLaunch {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+/// Choose a terminal from the ones `available` and build its launch for `dir`
+pub fn pick_terminal(os: &str, available: &[&str], dir: &Path) -> Option<TerminalLaunch> {
+    let terminal = terminal_candidates(os).iter().find(|t| available.contains(*t))?;
+    let dir = dir.to_string_lossy().to_string();
+    
+    let (program, args): (&str, Vec<String>) = match *terminal {
+        // `open -a` hands the folder to the app, which opens a shell there
+        "iTerm" | "Terminal" => ("open", vec!["-a".into(), terminal.to_string(), dir]),
+        "wt" => ("wt", vec!["-d".into(), dir]),
+        // `start` gives cmd its own console window instead of reusing ours
+        "cmd" => ("cmd", vec!["/C".into(), "start".into(), "cmd".into(), "/K".into(), "cd".into(), "/d".into(), dir]),
+        "gnome-terminal" => ("gnome-terminal", vec![format!("--working-directory={}", dir)]),
+        "konsole" => ("konsole", vec!["--workdir".into(), dir]),
+        "xfce4-terminal" => ("xfce4-terminal", vec![format!("--working-directory={}", dir)]),
+        // These start in the process's current directory
+        other => (other, vec![]),
+    };
+    
+    Some(TerminalLaunch { program: program.to_string(), args })
+}
+
+/// Whether an executable named `name` is on `PATH`
+fn on_path(name: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        let candidate = dir.join(name);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+/// Terminals from `terminal_candidates` that are installed on this machine
+pub fn available_terminals() -> Vec<&'static str> {
+    let os = std::env::consts::OS;
+    terminal_candidates(os)
+        .iter()
+        .copied()
+        .filter(|t| match os {
+            "macos" => ["/Applications", "/System/Applications/Utilities", "/Applications/Utilities"]
+                .iter()
+                .any(|dir| PathBuf::from(dir).join(format!("{}.app", t)).exists()),
+            _ => on_path(t),
+        })
+        .collect()
+}
+
+/// Open the platform terminal with its working directory set to `dir`
+pub fn open_terminal(dir: &Path) -> Result<()> {
+    let available = available_terminals();
+    let launch = pick_terminal(std::env::consts::OS, &available, dir)
+        .ok_or_else(|| anyhow::anyhow!("No terminal application found"))?;
+    
+    let mut cmd = Command::new(&launch.program);
+    cmd.args(&launch.args).current_dir(dir);
+    cmd.spawn()
+        .with_context(|| format!("Failed to launch {}", launch.program))?;
+    
+    info!("Opened terminal {} in {:?}", launch.program, dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pick_terminal_prefers_in_order() {
+        let dir = Path::new("/work/project");
+        
+        let mac = pick_terminal("macos", &["Terminal", "iTerm"], dir).unwrap();
+        assert_eq!(mac.program, "open");
+        assert_eq!(mac.args, vec!["-a", "iTerm", "/work/project"]);
+        
+        let mac = pick_terminal("macos", &["Terminal"], dir).unwrap();
+        assert_eq!(mac.args[1], "Terminal");
+        
+        let linux = pick_terminal("linux", &["xterm", "gnome-terminal"], dir).unwrap();
+        assert_eq!(linux.program, "gnome-terminal");
+        assert_eq!(linux.args, vec!["--working-directory=/work/project"]);
+        
+        let windows = pick_terminal("windows", &["cmd", "wt"], dir).unwrap();
+        assert_eq!(windows.program, "wt");
+        
+        assert!(pick_terminal("linux", &["nautilus"], dir).is_none());
+    }
+}
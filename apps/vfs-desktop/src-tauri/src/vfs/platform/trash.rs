@@ -0,0 +1,385 @@
+//! System Trash / Recycle Bin
+//!
+//! Moves local files to the OS trash so they can be restored from Finder,
+//! Explorer or the desktop's file manager:
+//! - macOS: Finder `delete` via AppleScript
+//! - Windows: `SendToRecycleBin` (the shell's recycle operation) via PowerShell
+//! - Linux: the freedesktop.org Trash specification
+//!
+//! Errors mean the OS trash couldn't take the item; callers can fall back to
+//! deleting or an in-app trash. Items whose trash location is known can be
+//! restored, which is how Undo puts them back. The trash can also be emptied.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use std::path::{Path, PathBuf};
+
+/// Move `path` (file or directory) to the system trash, returning where it
+/// now sits inside the trash when the OS reports it (not on Windows)
+pub fn trash_native(path: &Path) -> Result<Option<PathBuf>> {
+    let path = path.canonicalize()
+        .with_context(|| format!("Cannot trash {:?}", path))?;
+    
+    #[cfg(target_os = "macos")]
+    {
+        trash_macos(&path).map(Some)
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        trash_windows(&path).map(|_| None)
+    }
+    
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let trash_dir = freedesktop_trash_dir(&path)?;
+        trash_freedesktop(&path, &trash_dir, chrono::Local::now().naive_local()).map(Some)
+    }
+    
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(anyhow::anyhow!("No system trash on this platform: {:?}", path))
+    }
+}
+
+/// Put an item trashed by `trash_native` back at `original`, which must be
+/// free. Its freedesktop `.trashinfo` record, if any, is removed too.
+pub fn restore_trashed(trashed: &Path, original: &Path) -> Result<()> {
+    if std::fs::symlink_metadata(original).is_ok() {
+        return Err(anyhow::anyhow!("Cannot restore {:?}: the name is taken", original));
+    }
+    if let Some(parent) = original.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(trashed, original)
+        .with_context(|| format!("Failed to restore {:?} from the trash", original))?;
+    
+    if let (Some(files_dir), Some(name)) = (trashed.parent(), trashed.file_name()) {
+        if files_dir.file_name().is_some_and(|dir| dir == "files") {
+            let info = files_dir.with_file_name("info")
+                .join(format!("{}.trashinfo", name.to_string_lossy()));
+            let _ = std::fs::remove_file(info);
+        }
+    }
+    Ok(())
+}
+
+/// Permanently delete everything in the current user's system trash
+pub fn empty_trash() -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        run_trash_command("osascript", &["-e", "tell application \"Finder\" to empty trash"])
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        run_trash_command(
+            "powershell.exe",
+            &["-NoProfile", "-NonInteractive", "-Command", "Clear-RecycleBin -Force -ErrorAction Stop"],
+        )
+    }
+    
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        empty_freedesktop_trash(&home_trash_dir()?).map(|_| ())
+    }
+    
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(anyhow::anyhow!("No system trash on this platform"))
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn run_trash_command(program: &str, args: &[&str]) -> Result<()> {
+    let mut command = std::process::Command::new(program);
+    command.args(args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    
+    let output = command.output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Could not empty the trash: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn trash_macos(path: &Path) -> Result<PathBuf> {
+    use std::process::Command;
+    
+    // Escape for an AppleScript string literal
+    let posix = path.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"");
+    let output = Command::new("osascript")
+        .args(["-e", &format!(
+            "tell application \"Finder\" to POSIX path of ((delete POSIX file \"{}\") as alias)",
+            posix
+        )])
+        .output()
+        .context("Failed to run osascript")?;
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Finder could not move {:?} to the Trash: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    // Finder answers with the item's new location inside the Trash
+    let trashed = String::from_utf8_lossy(&output.stdout).trim().trim_end_matches('/').to_string();
+    Ok(PathBuf::from(trashed))
+}
+
+#[cfg(target_os = "windows")]
+fn trash_windows(path: &Path) -> Result<()> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+    
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let method = if path.is_dir() { "DeleteDirectory" } else { "DeleteFile" };
+    let script = format!(
+        "Add-Type -AssemblyName Microsoft.VisualBasic; \
+         [Microsoft.VisualBasic.FileIO.FileSystem]::{}($env:URSLY_TRASH_PATH, 'OnlyErrorDialogs', 'SendToRecycleBin')",
+        method
+    );
+    
+    // The path goes through the environment so quotes in names can't break the script
+    let output = Command::new("powershell.exe")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .env("URSLY_TRASH_PATH", path)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .context("Failed to run PowerShell")?;
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Could not move {:?} to the Recycle Bin: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// freedesktop.org Trash specification
+// =============================================================================
+
+/// Contents of the `.trashinfo` file recording where `original` came from
+pub fn trashinfo_contents(original: &Path, deleted_at: NaiveDateTime) -> String {
+    let encoded = original
+        .to_string_lossy()
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    
+    format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encoded,
+        deleted_at.format("%Y-%m-%dT%H:%M:%S")
+    )
+}
+
+/// Move `path` into the trash directory `trash_dir` (which holds `files/` and
+/// `info/`), returning the path it now has inside `files/`
+pub fn trash_freedesktop(path: &Path, trash_dir: &Path, deleted_at: NaiveDateTime) -> Result<PathBuf> {
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    std::fs::create_dir_all(&files_dir)?;
+    std::fs::create_dir_all(&info_dir)?;
+    
+    let file_name = path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Cannot trash {:?}", path))?
+        .to_string_lossy()
+        .to_string();
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), format!(".{}", ext)),
+        _ => (file_name.clone(), String::new()),
+    };
+    
+    // Claim a unique name by creating its .trashinfo exclusively, as the spec asks
+    let mut counter = 1;
+    let (trash_name, info_path) = loop {
+        let candidate = if counter == 1 {
+            file_name.clone()
+        } else {
+            format!("{} {}{}", stem, counter, ext)
+        };
+        let info_path = info_dir.join(format!("{}.trashinfo", candidate));
+        
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&info_path) {
+            Ok(mut info) => {
+                use std::io::Write;
+                info.write_all(trashinfo_contents(path, deleted_at).as_bytes())?;
+                break (candidate, info_path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => counter += 1,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create {:?}", info_path)),
+        }
+    };
+    
+    let trashed = files_dir.join(&trash_name);
+    if let Err(e) = std::fs::rename(path, &trashed) {
+        let _ = std::fs::remove_file(&info_path);
+        return Err(e).with_context(|| format!("Failed to move {:?} to the trash", path));
+    }
+    
+    Ok(trashed)
+}
+
+/// Delete everything in the trash directory `trash_dir` (its `files/` and
+/// their `info/` records), returning how many items were removed
+pub fn empty_freedesktop_trash(trash_dir: &Path) -> Result<usize> {
+    let files_dir = trash_dir.join("files");
+    let entries = match std::fs::read_dir(&files_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", files_dir)),
+    };
+    
+    let mut removed = 0;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        }
+        .with_context(|| format!("Failed to delete {:?} from the trash", path))?;
+        
+        let info = trash_dir.join("info").join(format!("{}.trashinfo", entry.file_name().to_string_lossy()));
+        let _ = std::fs::remove_file(info);
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// `$XDG_DATA_HOME/Trash`, or `~/.local/share/Trash`
+#[cfg(all(unix, not(target_os = "macos")))]
+fn home_trash_dir() -> Result<PathBuf> {
+    Ok(std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local/share")))
+        .ok_or_else(|| anyhow::anyhow!("No home directory for the trash"))?
+        .join("Trash"))
+}
+
+/// Trash directory for `path`: the home trash when on the same filesystem as
+/// the home directory, otherwise `$topdir/.Trash-$uid` on the file's own mount
+#[cfg(all(unix, not(target_os = "macos")))]
+fn freedesktop_trash_dir(path: &Path) -> Result<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+    
+    let home_trash = home_trash_dir()?;
+    
+    let device = std::fs::symlink_metadata(path)?.dev();
+    let home_device = home_trash.ancestors()
+        .find_map(|dir| std::fs::metadata(dir).ok())
+        .map(|m| m.dev());
+    if home_device == Some(device) {
+        return Ok(home_trash);
+    }
+    
+    // Walk up to the mount point: the last ancestor on the same device
+    let mut topdir = path.parent().unwrap_or(path).to_path_buf();
+    while let Some(parent) = topdir.parent() {
+        match std::fs::metadata(parent) {
+            Ok(m) if m.dev() == device => topdir = parent.to_path_buf(),
+            _ => break,
+        }
+    }
+    
+    // SAFETY: getuid has no preconditions and cannot fail
+    let uid = unsafe { libc::getuid() };
+    Ok(topdir.join(format!(".Trash-{}", uid)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+    
+    fn deleted_at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 17).unwrap().and_hms_opt(14, 3, 9).unwrap()
+    }
+    
+    #[test]
+    fn test_freedesktop_trash_writes_trashinfo() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = temp_dir.path().join("Trash");
+        let original = temp_dir.path().join("Day 1 notes.txt");
+        
+        std::fs::write(&original, "first").unwrap();
+        let trashed = trash_freedesktop(&original, &trash, deleted_at()).unwrap();
+        assert!(!original.exists());
+        assert_eq!(trashed, trash.join("files/Day 1 notes.txt"));
+        assert_eq!(std::fs::read_to_string(&trashed).unwrap(), "first");
+        
+        let info = std::fs::read_to_string(trash.join("info/Day 1 notes.txt.trashinfo")).unwrap();
+        let expected_path = original.to_string_lossy().replace(' ', "%20");
+        assert_eq!(
+            info,
+            format!("[Trash Info]\nPath={}\nDeletionDate=2024-05-17T14:03:09\n", expected_path)
+        );
+        
+        // A second file with the same name gets its own slot
+        std::fs::write(&original, "second").unwrap();
+        let trashed = trash_freedesktop(&original, &trash, deleted_at()).unwrap();
+        assert_eq!(trashed, trash.join("files/Day 1 notes 2.txt"));
+        assert!(trash.join("info/Day 1 notes 2.txt.trashinfo").exists());
+    }
+    
+    #[test]
+    fn test_empty_freedesktop_trash_removes_files_and_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = temp_dir.path().join("Trash");
+        assert_eq!(empty_freedesktop_trash(&trash).unwrap(), 0);
+        
+        let folder = temp_dir.path().join("Renders");
+        std::fs::create_dir(&folder).unwrap();
+        std::fs::write(folder.join("frame.exr"), "pixels").unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        std::fs::write(&file, "slate").unwrap();
+        trash_freedesktop(&folder, &trash, deleted_at()).unwrap();
+        trash_freedesktop(&file, &trash, deleted_at()).unwrap();
+        
+        assert_eq!(empty_freedesktop_trash(&trash).unwrap(), 2);
+        assert_eq!(std::fs::read_dir(trash.join("files")).unwrap().count(), 0);
+        assert_eq!(std::fs::read_dir(trash.join("info")).unwrap().count(), 0);
+    }
+    
+    #[test]
+    fn test_restore_trashed_puts_file_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = temp_dir.path().join("Trash");
+        let original = temp_dir.path().join("Day1/A001.mov");
+        std::fs::create_dir_all(original.parent().unwrap()).unwrap();
+        std::fs::write(&original, "frames").unwrap();
+        
+        let trashed = trash_freedesktop(&original, &trash, deleted_at()).unwrap();
+        std::fs::remove_dir(original.parent().unwrap()).unwrap();
+        
+        restore_trashed(&trashed, &original).unwrap();
+        assert_eq!(std::fs::read_to_string(&original).unwrap(), "frames");
+        assert!(!trashed.exists());
+        assert!(!trash.join("info/A001.mov.trashinfo").exists());
+        
+        // Never over an item that took its place
+        let trashed = trash_freedesktop(&original, &trash, deleted_at()).unwrap();
+        std::fs::write(&original, "new").unwrap();
+        assert!(restore_trashed(&trashed, &original).is_err());
+        assert!(trashed.exists());
+    }
+}
@@ -0,0 +1,616 @@
+//! Mounted volume enumeration
+//!
+//! Reports the drives currently attached to the machine (for a "This PC" view
+//! that can refresh on hotplug) without registering them as storage sources.
+//!
+//! - macOS: entries in `/Volumes`, described by `diskutil info`
+//! - Linux: `/proc/mounts`, limited to `/`, `/media`, `/run/media` and `/mnt`
+//! - Windows: `Get-Volume` for every lettered drive
+//!
+//! Describing volumes runs `diskutil` or PowerShell, so the hotplug monitor
+//! backs off while nothing changes and, where the mount points can be read
+//! cheaply, only re-describes them when those change.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Volume polls while a change is settling, and after one
+pub const VOLUME_POLL_MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest gap between volume polls once nothing has changed for a while
+pub const VOLUME_POLL_MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A volume mounted on this machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemVolume {
+    /// Volume label, or the mount point's name when it has none
+    pub name: String,
+    
+    /// Where the volume is mounted (`/Volumes/CARD`, `E:\`)
+    pub path: PathBuf,
+    
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    
+    /// Filesystem type (`apfs`, `exfat`, `ext4`, `NTFS`, ...)
+    pub filesystem: String,
+    
+    /// USB sticks, card readers and other hot-pluggable media
+    pub removable: bool,
+}
+
+// =============================================================================
+// macOS - `diskutil info <mount point>`
+// =============================================================================
+
+/// Fields of interest from `diskutil info` output
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DiskutilInfo {
+    pub volume_name: Option<String>,
+    pub filesystem: Option<String>,
+    pub removable: bool,
+    pub total_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,
+}
+
+/// The exact byte count in a size field, e.g. `62.1 GB (62109253632 Bytes) (exactly ...)`
+fn bytes_in_parens(value: &str) -> Option<u64> {
+    let start = value.find('(')?;
+    let rest = &value[start + 1..];
+    let end = rest.find(" Bytes")?;
+    rest[..end].trim().parse().ok()
+}
+
+/// Parse the `Key: value` lines printed by `diskutil info`
+pub fn parse_diskutil_info(output: &str) -> DiskutilInfo {
+    let mut info = DiskutilInfo::default();
+    
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        
+        match key {
+            "Volume Name" if !value.is_empty() => info.volume_name = Some(value.to_string()),
+            "Type (Bundle)" => info.filesystem = Some(value.to_string()),
+            "Removable Media" => info.removable = value != "Fixed",
+            "Protocol" if value == "USB" => info.removable = true,
+            // APFS volumes report their container's size
+            "Disk Size" | "Volume Total Space" | "Container Total Space" => {
+                info.total_bytes = info.total_bytes.or_else(|| bytes_in_parens(value));
+            }
+            "Volume Free Space" | "Volume Available Space" | "Container Free Space" => {
+                info.free_bytes = info.free_bytes.or_else(|| bytes_in_parens(value));
+            }
+            _ => {}
+        }
+    }
+    
+    info
+}
+
+// =============================================================================
+// Linux - `/proc/mounts`
+// =============================================================================
+
+/// One line of `/proc/mounts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    pub device: String,
+    pub mount_point: PathBuf,
+    pub filesystem: String,
+}
+
+/// Undo the octal escapes `/proc/mounts` uses for spaces, tabs and backslashes
+fn unescape_mount_field(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let digits: String = chars.clone().take(3).collect();
+            if digits.len() == 3 {
+                if let Ok(code) = u8::from_str_radix(&digits, 8) {
+                    out.push(code as char);
+                    chars.nth(2);
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Whether a mount point is somewhere a user keeps drives
+fn is_user_mount(mount_point: &str) -> bool {
+    mount_point == "/"
+        || ["/media/", "/run/media/", "/mnt/"].iter().any(|prefix| mount_point.starts_with(prefix))
+}
+
+/// Whether a mount point is an external drive or disk image under macOS's
+/// `/Volumes`, as opposed to the boot disk
+pub fn is_ejectable_mount(mount_point: &Path) -> bool {
+    let path = mount_point.to_string_lossy();
+    path.starts_with("/Volumes/") && !path.contains("Macintosh HD")
+}
+
+/// Parse `/proc/mounts`, keeping block-device mounts in user-visible locations
+pub fn parse_proc_mounts(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = unescape_mount_field(fields.next()?);
+            let filesystem = fields.next()?;
+            
+            // Pseudo filesystems (proc, tmpfs, overlay, ...) have no /dev node
+            if !device.starts_with("/dev/") || !is_user_mount(&mount_point) {
+                return None;
+            }
+            
+            Some(MountEntry {
+                device: device.to_string(),
+                mount_point: PathBuf::from(mount_point),
+                filesystem: filesystem.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Block device name under `/sys/block` for a partition (`/dev/sdb1` -> `sdb`)
+pub fn parent_block_device(device: &str) -> String {
+    let name = device.trim_start_matches("/dev/");
+    // nvme0n1p2 / mmcblk0p1 -> strip the `pN` partition suffix
+    if name.starts_with("nvme") || name.starts_with("mmcblk") {
+        if let Some(pos) = name.rfind('p') {
+            if name[pos + 1..].chars().all(|c| c.is_ascii_digit()) && pos > 0 {
+                return name[..pos].to_string();
+            }
+        }
+        return name.to_string();
+    }
+    name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()
+}
+
+// =============================================================================
+// Windows - `Get-Volume | ConvertTo-Csv`
+// =============================================================================
+
+/// PowerShell that lists lettered volumes as CSV
+pub const WINDOWS_VOLUME_QUERY: &str = "Get-Volume | Where-Object DriveLetter | \
+    Select-Object DriveLetter,FileSystemLabel,FileSystem,DriveType,Size,SizeRemaining | \
+    ConvertTo-Csv -NoTypeInformation";
+
+/// Split one CSV line with double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Parse the CSV produced by `WINDOWS_VOLUME_QUERY`
+pub fn parse_windows_volumes(csv: &str) -> Vec<SystemVolume> {
+    let mut lines = csv.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let header = split_csv_line(header.trim());
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (Some(letter), Some(label), Some(fs), Some(kind), Some(size), Some(free)) = (
+        column("DriveLetter"),
+        column("FileSystemLabel"),
+        column("FileSystem"),
+        column("DriveType"),
+        column("Size"),
+        column("SizeRemaining"),
+    ) else {
+        return Vec::new();
+    };
+    
+    lines
+        .filter_map(|line| {
+            let row = split_csv_line(line.trim());
+            let letter = row.get(letter)?.chars().next()?;
+            let label = row.get(label).cloned().unwrap_or_default();
+            let name = if label.is_empty() {
+                format!("Drive ({}:)", letter)
+            } else {
+                format!("{} ({}:)", label, letter)
+            };
+            
+            Some(SystemVolume {
+                name,
+                path: PathBuf::from(format!("{}:\\", letter)),
+                total_bytes: row.get(size).and_then(|v| v.parse().ok()).unwrap_or(0),
+                free_bytes: row.get(free).and_then(|v| v.parse().ok()).unwrap_or(0),
+                filesystem: row.get(fs).cloned().unwrap_or_default(),
+                removable: row.get(kind).is_some_and(|k| k == "Removable"),
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Hotplug detection
+// =============================================================================
+
+/// A volume appearing or disappearing between two polls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VolumeChange {
+    Added(SystemVolume),
+    Removed(SystemVolume),
+}
+
+/// Changes between the `before` volumes and the `after` ones, matched by path
+pub fn diff_volumes(before: &[SystemVolume], after: &[SystemVolume]) -> Vec<VolumeChange> {
+    let before_paths: BTreeSet<&PathBuf> = before.iter().map(|v| &v.path).collect();
+    let after_paths: BTreeSet<&PathBuf> = after.iter().map(|v| &v.path).collect();
+    
+    let removed = before.iter()
+        .filter(|v| !after_paths.contains(&v.path))
+        .map(|v| VolumeChange::Removed(v.clone()));
+    let added = after.iter()
+        .filter(|v| !before_paths.contains(&v.path))
+        .map(|v| VolumeChange::Added(v.clone()));
+    
+    removed.chain(added).collect()
+}
+
+/// Turns successive polls into debounced add/remove changes.
+///
+/// A mount often shows up in stages (the mount point appears before the
+/// filesystem is ready, or flaps while a card reader settles), so a new set of
+/// volumes is only reported once two consecutive polls agree on it.
+#[derive(Debug, Default)]
+pub struct VolumeWatcher {
+    /// Volumes as last reported to listeners
+    reported: Vec<SystemVolume>,
+    
+    /// Paths seen on the previous poll, while waiting for them to settle
+    pending: Option<BTreeSet<PathBuf>>,
+    
+    /// Polls in a row that found nothing new
+    quiet_polls: u32,
+}
+
+impl VolumeWatcher {
+    /// Start from volumes already known (e.g. those mounted at startup)
+    pub fn new(initial: Vec<SystemVolume>) -> Self {
+        Self { reported: initial, pending: None, quiet_polls: 0 }
+    }
+    
+    /// How long to wait before the next poll: the minimum while a change
+    /// settles, doubling with each quiet poll up to the maximum
+    pub fn poll_interval(&self) -> Duration {
+        if self.pending.is_some() {
+            return VOLUME_POLL_MIN_INTERVAL;
+        }
+        VOLUME_POLL_MIN_INTERVAL
+            .saturating_mul(1 << self.quiet_polls.min(6))
+            .min(VOLUME_POLL_MAX_INTERVAL)
+    }
+    
+    /// Feed one poll; returns the changes that have settled
+    pub fn observe(&mut self, volumes: Vec<SystemVolume>) -> Vec<VolumeChange> {
+        let paths: BTreeSet<PathBuf> = volumes.iter().map(|v| v.path.clone()).collect();
+        let reported: BTreeSet<PathBuf> = self.reported.iter().map(|v| v.path.clone()).collect();
+        
+        if paths == reported {
+            if self.pending.take().is_none() {
+                self.quiet_polls = self.quiet_polls.saturating_add(1);
+            }
+            return Vec::new();
+        }
+        self.quiet_polls = 0;
+        
+        if self.pending.as_ref() != Some(&paths) {
+            self.pending = Some(paths);
+            return Vec::new();
+        }
+        
+        let changes = diff_volumes(&self.reported, &volumes);
+        self.reported = volumes;
+        self.pending = None;
+        changes
+    }
+}
+
+// =============================================================================
+// Enumeration
+// =============================================================================
+
+/// Where volumes are mounted, read without describing them: `/Volumes` on
+/// macOS, `/proc/self/mounts` on Linux. A change means it's worth running
+/// `list_system_volumes`; `None` where there's no cheap way to tell.
+pub fn mount_point_snapshot() -> Option<BTreeSet<PathBuf>> {
+    #[cfg(target_os = "macos")]
+    {
+        let entries = std::fs::read_dir("/Volumes").ok()?;
+        Some(entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        let content = std::fs::read_to_string("/proc/self/mounts").ok()?;
+        Some(parse_proc_mounts(&content).into_iter().map(|entry| entry.mount_point).collect())
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Volumes currently mounted on this machine. Nothing is mounted or registered.
+pub fn list_system_volumes() -> Result<Vec<SystemVolume>> {
+    #[cfg(target_os = "macos")]
+    {
+        list_macos_volumes()
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        list_linux_volumes()
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        list_windows_volumes()
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn list_macos_volumes() -> Result<Vec<SystemVolume>> {
+    use std::process::Command;
+    
+    let mut volumes = Vec::new();
+    for entry in std::fs::read_dir("/Volumes")?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        if dir_name.starts_with('.') || !path.is_dir() {
+            continue;
+        }
+        
+        let output = Command::new("diskutil").arg("info").arg(&path).output()?;
+        let info = parse_diskutil_info(&String::from_utf8_lossy(&output.stdout));
+        let space = super::get_disk_space(&path).ok();
+        
+        volumes.push(SystemVolume {
+            name: info.volume_name.unwrap_or(dir_name),
+            total_bytes: info.total_bytes.or(space.map(|s| s.total)).unwrap_or(0),
+            free_bytes: info.free_bytes.or(space.map(|s| s.available)).unwrap_or(0),
+            filesystem: info.filesystem.unwrap_or_default(),
+            removable: info.removable,
+            path,
+        });
+    }
+    
+    Ok(volumes)
+}
+
+#[cfg(target_os = "linux")]
+fn list_linux_volumes() -> Result<Vec<SystemVolume>> {
+    let mounts = std::fs::read_to_string("/proc/mounts")?;
+    
+    Ok(parse_proc_mounts(&mounts)
+        .into_iter()
+        .map(|mount| {
+            let space = super::get_disk_space(&mount.mount_point).ok();
+            let removable = std::fs::read_to_string(format!(
+                "/sys/block/{}/removable",
+                parent_block_device(&mount.device)
+            ))
+            .map(|flag| flag.trim() == "1")
+            .unwrap_or(false);
+            let name = mount.mount_point.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "File System".to_string());
+            
+            SystemVolume {
+                name,
+                path: mount.mount_point,
+                total_bytes: space.map(|s| s.total).unwrap_or(0),
+                free_bytes: space.map(|s| s.available).unwrap_or(0),
+                filesystem: mount.filesystem,
+                removable,
+            }
+        })
+        .collect())
+}
+
+#[cfg(target_os = "windows")]
+fn list_windows_volumes() -> Result<Vec<SystemVolume>> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+    
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let output = Command::new("powershell.exe")
+        .args(["-NoProfile", "-NonInteractive", "-Command", WINDOWS_VOLUME_QUERY])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()?;
+    
+    Ok(parse_windows_volumes(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_ejectable_mounts() {
+        assert!(is_ejectable_mount(Path::new("/Volumes/A_CAM_CARD")));
+        assert!(!is_ejectable_mount(Path::new("/Volumes/Macintosh HD")));
+        assert!(!is_ejectable_mount(Path::new("/Volumes")));
+        assert!(!is_ejectable_mount(Path::new("/Users/editor/Media")));
+    }
+    
+    const DISKUTIL_INFO: &str = "\
+   Device Identifier:         disk4s1
+   Device Node:               /dev/disk4s1
+   Whole:                     No
+   Part of Whole:             disk4
+   
+   Volume Name:               A_CAM_CARD
+   Mounted:                   Yes
+   Mount Point:               /Volumes/A_CAM_CARD
+   
+   Partition Type:            Microsoft Basic Data
+   File System Personality:   ExFAT
+   Type (Bundle):             exfat
+   Name (User Visible):       ExFAT
+   
+   Protocol:                  USB
+   Removable Media:           Removable
+   
+   Disk Size:                 128.0 GB (127999672320 Bytes) (exactly 249999360 512-Byte-Units)
+   Volume Free Space:         41.9 GB (41943040000 Bytes) (exactly 81920000 512-Byte-Units)
+";
+    
+    const PROC_MOUNTS: &str = "\
+sysfs /sys sysfs rw,nosuid,nodev,noexec,relatime 0 0
+proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+tmpfs /run tmpfs rw,nosuid,nodev 0 0
+/dev/nvme0n1p1 /boot/efi vfat rw,relatime 0 0
+/dev/sdb1 /media/editor/SHOOT\\040DAY\\0401 exfat rw,nosuid,nodev,relatime 0 0
+/dev/sdc1 /mnt/archive xfs rw,relatime 0 0
+";
+    
+    const GET_VOLUME_CSV: &str = "\
+\"DriveLetter\",\"FileSystemLabel\",\"FileSystem\",\"DriveType\",\"Size\",\"SizeRemaining\"
+\"C\",\"Windows\",\"NTFS\",\"Fixed\",\"511101190144\",\"120034500608\"
+\"E\",\"\",\"exFAT\",\"Removable\",\"63831015424\",\"63830949888\"
+";
+    
+    #[test]
+    fn test_parse_diskutil_info() {
+        let info = parse_diskutil_info(DISKUTIL_INFO);
+        assert_eq!(info.volume_name.as_deref(), Some("A_CAM_CARD"));
+        assert_eq!(info.filesystem.as_deref(), Some("exfat"));
+        assert!(info.removable);
+        assert_eq!(info.total_bytes, Some(127_999_672_320));
+        assert_eq!(info.free_bytes, Some(41_943_040_000));
+    }
+    
+    #[test]
+    fn test_parse_proc_mounts() {
+        let mounts = parse_proc_mounts(PROC_MOUNTS);
+        let points: Vec<_> = mounts.iter().map(|m| m.mount_point.clone()).collect();
+        assert_eq!(points, vec![
+            PathBuf::from("/"),
+            PathBuf::from("/media/editor/SHOOT DAY 1"),
+            PathBuf::from("/mnt/archive"),
+        ]);
+        assert_eq!(mounts[1].filesystem, "exfat");
+        
+        assert_eq!(parent_block_device("/dev/sdb1"), "sdb");
+        assert_eq!(parent_block_device("/dev/nvme0n1p2"), "nvme0n1");
+        assert_eq!(parent_block_device("/dev/mmcblk0p1"), "mmcblk0");
+    }
+    
+    fn volume(path: &str) -> SystemVolume {
+        SystemVolume {
+            name: path.rsplit('/').next().unwrap_or_default().to_string(),
+            path: PathBuf::from(path),
+            total_bytes: 0,
+            free_bytes: 0,
+            filesystem: "exfat".to_string(),
+            removable: true,
+        }
+    }
+    
+    #[test]
+    fn test_diff_volumes() {
+        let before = vec![volume("/Volumes/A"), volume("/Volumes/B")];
+        let after = vec![volume("/Volumes/B"), volume("/Volumes/C")];
+        
+        assert_eq!(diff_volumes(&before, &after), vec![
+            VolumeChange::Removed(volume("/Volumes/A")),
+            VolumeChange::Added(volume("/Volumes/C")),
+        ]);
+        assert!(diff_volumes(&after, &after).is_empty());
+    }
+    
+    #[test]
+    fn test_watcher_debounces_until_settled() {
+        let mut watcher = VolumeWatcher::new(vec![volume("/Volumes/A")]);
+        
+        // First sighting of a new card waits for the next poll
+        let with_card = vec![volume("/Volumes/A"), volume("/Volumes/CARD")];
+        assert!(watcher.observe(with_card.clone()).is_empty());
+        assert_eq!(watcher.observe(with_card.clone()), vec![VolumeChange::Added(volume("/Volumes/CARD"))]);
+        assert!(watcher.observe(with_card.clone()).is_empty());
+        
+        // A flap that reverts before settling reports nothing
+        assert!(watcher.observe(vec![volume("/Volumes/A")]).is_empty());
+        assert!(watcher.observe(with_card).is_empty());
+        
+        assert!(watcher.observe(vec![]).is_empty());
+        assert_eq!(watcher.observe(vec![]), vec![
+            VolumeChange::Removed(volume("/Volumes/A")),
+            VolumeChange::Removed(volume("/Volumes/CARD")),
+        ]);
+    }
+    
+    #[test]
+    fn test_watcher_backs_off_while_quiet() {
+        let mut watcher = VolumeWatcher::new(vec![volume("/Volumes/A")]);
+        assert_eq!(watcher.poll_interval(), VOLUME_POLL_MIN_INTERVAL);
+        
+        for _ in 0..3 {
+            watcher.observe(vec![volume("/Volumes/A")]);
+        }
+        assert_eq!(watcher.poll_interval(), VOLUME_POLL_MIN_INTERVAL * 8);
+        for _ in 0..10 {
+            watcher.observe(vec![volume("/Volumes/A")]);
+        }
+        assert_eq!(watcher.poll_interval(), VOLUME_POLL_MAX_INTERVAL);
+        
+        // A new card is confirmed at the quickest rate, then the wait starts over
+        let with_card = vec![volume("/Volumes/A"), volume("/Volumes/CARD")];
+        watcher.observe(with_card.clone());
+        assert_eq!(watcher.poll_interval(), VOLUME_POLL_MIN_INTERVAL);
+        watcher.observe(with_card);
+        assert_eq!(watcher.poll_interval(), VOLUME_POLL_MIN_INTERVAL);
+    }
+    
+    #[test]
+    fn test_parse_windows_volumes() {
+        let volumes = parse_windows_volumes(GET_VOLUME_CSV);
+        assert_eq!(volumes.len(), 2);
+        
+        assert_eq!(volumes[0].name, "Windows (C:)");
+        assert_eq!(volumes[0].path, PathBuf::from("C:\\"));
+        assert_eq!(volumes[0].free_bytes, 120_034_500_608);
+        assert!(!volumes[0].removable);
+        
+        assert_eq!(volumes[1].name, "Drive (E:)");
+        assert_eq!(volumes[1].filesystem, "exFAT");
+        assert!(volumes[1].removable);
+    }
+}
@@ -2,21 +2,29 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::vfs::domain::{CacheEntry, CacheConfig};
 
+/// Granularity of partially cached files: ranges are fetched and tracked in
+/// whole blocks of this size
+pub const CACHE_BLOCK_SIZE: u64 = 1024 * 1024;
+
 /// Cache adapter trait - Port for caching backends
 #[async_trait]
 pub trait CacheAdapter: Send + Sync {
     /// Get cache configuration
     fn config(&self) -> &CacheConfig;
     
-    /// Check if file is cached
+    /// Check if the whole file is cached
     async fn is_cached(&self, path: &Path) -> bool;
     
+    /// Check if bytes `offset..offset + len` are cached, either in a full copy
+    /// or in cached blocks
+    async fn is_range_cached(&self, path: &Path, offset: u64, len: u64) -> bool;
+    
     /// Get cached file path
-    async fn get_cached_path(&self, path: &Path) -> Option<std::path::PathBuf>;
+    async fn get_cached_path(&self, path: &Path) -> Option<PathBuf>;
     
     /// Store file in cache
     async fn cache_file(&self, path: &Path, data: &[u8]) -> Result<CacheEntry>;
@@ -24,6 +32,14 @@ pub trait CacheAdapter: Send + Sync {
     /// Read file from cache
     async fn read_from_cache(&self, path: &Path) -> Result<Vec<u8>>;
     
+    /// Store blocks of a file without caching all of it. `offset` must be a
+    /// multiple of `CACHE_BLOCK_SIZE` and `data` whole blocks, except that the
+    /// last block of the file may be short.
+    async fn cache_range(&self, path: &Path, file_size: u64, offset: u64, data: &[u8]) -> Result<()>;
+    
+    /// Read a cached range, clamped to the end of the file
+    async fn read_range_from_cache(&self, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>>;
+    
     /// Invalidate cache entry
     async fn invalidate(&self, path: &Path) -> Result<()>;
     
@@ -36,8 +52,26 @@ pub trait CacheAdapter: Send + Sync {
     /// Evict entries if cache is full
     async fn evict_if_needed(&self, required_space: u64) -> Result<u64>;
     
+    /// Evict entries by policy until at least `bytes` are freed, regardless of
+    /// `max_size` (used when the cache volume itself is running out of space)
+    async fn evict_bytes(&self, bytes: u64) -> Result<u64>;
+    
     /// Touch entry to update access time (for LRU)
     async fn touch(&self, path: &Path) -> Result<()>;
+    
+    /// Staging path for an in-progress (`.part`) download of `path`
+    fn partial_path(&self, path: &Path) -> PathBuf;
+    
+    /// Move a completed `.part` download into the cache
+    async fn commit_partial(&self, path: &Path) -> Result<CacheEntry>;
+    
+    /// Drop the entries for a deleted file (or everything under a deleted
+    /// directory), keeping their data for the delete grace window
+    async fn soft_delete(&self, path: &Path) -> Result<()>;
+    
+    /// Bring back entries under `path` removed by `soft_delete` while still
+    /// in the grace window; returns how many were restored
+    async fn undo_delete(&self, path: &Path) -> Result<usize>;
 }
 
 /// Cache statistics
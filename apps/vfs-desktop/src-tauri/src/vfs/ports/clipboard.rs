@@ -156,6 +156,22 @@ pub trait IClipboardService: Send + Sync {
     
     /// Write files to OS clipboard (so Finder/Explorer can paste)
     async fn write_native_clipboard(&self, paths: &[PathBuf]) -> Result<()>;
+    
+    /// Write plain text to the OS clipboard (paths, URLs)
+    async fn write_text_clipboard(&self, text: &str) -> Result<()>;
+    
+    /// Write a PNG image to the OS clipboard, for pasting into image editors
+    async fn write_image_clipboard(&self, png: &[u8]) -> Result<()>;
+}
+
+/// Where clipboard text and images end up: the OS clipboard, or a stand-in
+/// in tests
+#[async_trait]
+pub trait ClipboardBackend: Send + Sync {
+    async fn write_text(&self, text: &str) -> Result<()>;
+    
+    /// Write PNG-encoded image data
+    async fn write_image(&self, png: &[u8]) -> Result<()>;
 }
 
 #[cfg(test)]
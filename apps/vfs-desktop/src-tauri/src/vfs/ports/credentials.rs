@@ -0,0 +1,18 @@
+//! Credential Port - Interface for storing secrets outside the config files
+//!
+//! Secrets are addressed by `(service, account)`, the way OS keychains do,
+//! so mount configs can carry a `CredentialRef` instead of the secret itself.
+
+use anyhow::Result;
+
+/// Secure storage for passwords and secret keys
+pub trait CredentialStore: Send + Sync {
+    /// Save `secret`, replacing any existing one for `(service, account)`
+    fn store(&self, service: &str, account: &str, secret: &str) -> Result<()>;
+    
+    /// The secret for `(service, account)`, or `None` if there isn't one
+    fn retrieve(&self, service: &str, account: &str) -> Result<Option<String>>;
+    
+    /// Remove the secret; returns whether there was one
+    fn delete(&self, service: &str, account: &str) -> Result<bool>;
+}
@@ -22,6 +22,24 @@ pub struct CrossStorageOptions {
     
     /// Preserve metadata (tags, favorites, etc.)
     pub preserve_metadata: bool,
+    
+    /// Bandwidth cap for this transfer, on top of the global transfer limit
+    #[serde(default)]
+    pub rate_limit_bytes_per_sec: Option<u64>,
+    
+    /// Files copied in parallel for directory transfers (default 8)
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    
+    /// Hash each file as it is read, read the copy back afterwards and fail
+    /// on a mismatch
+    #[serde(default)]
+    pub verify: bool,
+    
+    /// Refuse a folder whose names differ only in case when the destination
+    /// ignores case, instead of copying it with a warning
+    #[serde(default)]
+    pub fail_on_case_collision: bool,
 }
 
 impl CrossStorageOptions {
@@ -32,6 +50,10 @@ impl CrossStorageOptions {
             delete_source: false,
             recursive: true,
             preserve_metadata: true,
+            rate_limit_bytes_per_sec: None,
+            concurrency: None,
+            verify: false,
+            fail_on_case_collision: false,
         }
     }
     
@@ -42,6 +64,10 @@ impl CrossStorageOptions {
             delete_source: true,
             recursive: true,
             preserve_metadata: true,
+            rate_limit_bytes_per_sec: None,
+            concurrency: None,
+            verify: false,
+            fail_on_case_collision: false,
         }
     }
 }
@@ -5,6 +5,8 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::SystemTime;
@@ -174,11 +176,49 @@ pub trait IFileOperations: Send + Sync {
     /// Creates the file if it doesn't exist, truncates if it does.
     async fn write(&self, path: &Path, data: &[u8]) -> Result<()>;
     
+    /// Write data so readers see either the old or the new contents, never a
+    /// partial file, where the adapter can
+    ///
+    /// The default just calls `write`. That is atomic on object stores, where
+    /// a PUT only becomes visible once complete, but writes in place
+    /// everywhere else. The local adapter overrides it to write a sibling temp
+    /// file and rename it over the target.
+    async fn write_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.write(path, data).await
+    }
+    
+    /// Write a file from a stream of chunks, returning the bytes written
+    ///
+    /// The default buffers the whole stream and calls `write`. Adapters that can
+    /// write incrementally should override it, so memory stays bounded and data
+    /// reaches the backend at the pace the stream produces it.
+    async fn write_stream(&self, path: &Path, mut chunks: BoxStream<'_, Result<Vec<u8>>>) -> Result<u64> {
+        let mut data = Vec::new();
+        while let Some(chunk) = chunks.try_next().await? {
+            data.extend_from_slice(&chunk);
+        }
+        self.write(path, &data).await?;
+        Ok(data.len() as u64)
+    }
+    
     /// Append data to file (like `write` with O_APPEND)
     ///
     /// Creates the file if it doesn't exist.
     async fn append(&self, path: &Path, data: &[u8]) -> Result<()>;
     
+    /// Append by rewriting the whole file with `write_atomic`
+    ///
+    /// Reads the existing contents into memory, so meant for small text files.
+    async fn append_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        let mut contents = if self.exists(path).await? {
+            self.read(path).await?
+        } else {
+            Vec::new()
+        };
+        contents.extend_from_slice(data);
+        self.write_atomic(path, &contents).await
+    }
+    
     /// Write data at specific offset (like `pwrite`)
     ///
     /// File must exist. Does not truncate.
@@ -198,7 +238,8 @@ pub trait IFileOperations: Send + Sync {
     
     /// Create directory and all parent directories (like `mkdir -p`)
     ///
-    /// Does not fail if directory already exists.
+    /// Does not fail if directory already exists, including when another
+    /// task creates it (or one of its parents) at the same time.
     async fn mkdir_p(&self, path: &Path) -> Result<()>;
     
     /// Remove empty directory (like `rmdir`)
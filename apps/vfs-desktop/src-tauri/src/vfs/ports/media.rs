@@ -5,11 +5,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::vfs::domain::VfsError;
+
 /// Information about a media file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MediaInfo {
     /// File path
     pub path: PathBuf,
@@ -76,6 +81,23 @@ pub struct ThumbnailData {
     pub height: u32,
 }
 
+/// Frames sampled evenly across a video, tiled row by row into one PNG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteSheet {
+    /// PNG image data
+    pub data: Vec<u8>,
+    
+    pub columns: u32,
+    pub rows: u32,
+    
+    /// Width of each cell in pixels
+    pub thumb_width: u32,
+    
+    /// Timestamp in the video (seconds) of each cell, in reading order
+    pub timestamps: Vec<f64>,
+}
+
 /// Streaming format options
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum StreamFormat {
@@ -105,6 +127,9 @@ impl StreamFormat {
         }
     }
     
+    /// Formats the FFmpeg transcoder writes; the others are live protocols
+    pub const TRANSCODABLE: [StreamFormat; 2] = [StreamFormat::HLS, StreamFormat::MP4];
+    
     pub fn extension(&self) -> &'static str {
         match self {
             StreamFormat::HLS => "m3u8",
@@ -127,6 +152,103 @@ pub enum TranscodeQuality {
     Adaptive, // Multi-bitrate for streaming
 }
 
+impl TranscodeQuality {
+    pub const ALL: [TranscodeQuality; 5] = [
+        TranscodeQuality::Low,
+        TranscodeQuality::Medium,
+        TranscodeQuality::High,
+        TranscodeQuality::Ultra,
+        TranscodeQuality::Adaptive,
+    ];
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscodeQuality::Low => "low",
+            TranscodeQuality::Medium => "medium",
+            TranscodeQuality::High => "high",
+            TranscodeQuality::Ultra => "ultra",
+            TranscodeQuality::Adaptive => "adaptive",
+        }
+    }
+}
+
+/// An output the FFmpeg transcoder can write
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscodeFormatInfo {
+    /// Value to pass as a transcode `format`
+    pub format: String,
+    pub container: String,
+    pub video_codec: String,
+    pub audio_codec: String,
+}
+
+/// Formats and quality presets a transcode request may ask for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TranscodeOptions {
+    pub formats: Vec<TranscodeFormatInfo>,
+    pub qualities: Vec<String>,
+}
+
+impl TranscodeOptions {
+    /// What the FFmpeg adapter produces: H.264/AAC as HLS segments or a single MP4
+    pub fn supported() -> Self {
+        let formats = StreamFormat::TRANSCODABLE.iter()
+            .map(|format| TranscodeFormatInfo {
+                format: format.as_str().to_string(),
+                container: match format {
+                    StreamFormat::HLS => "HLS playlist (.m3u8) with MPEG-TS segments",
+                    _ => "MP4",
+                }.to_string(),
+                video_codec: "H.264".to_string(),
+                audio_codec: "AAC".to_string(),
+            })
+            .collect();
+        
+        Self {
+            formats,
+            qualities: TranscodeQuality::ALL.iter().map(|q| q.as_str().to_string()).collect(),
+        }
+    }
+}
+
+/// A transcode request from the UI, checked against what FFmpeg can produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscodeRequest {
+    pub format: StreamFormat,
+    pub quality: TranscodeQuality,
+}
+
+impl TranscodeRequest {
+    /// Parse `format` and `quality` (case-insensitive, quality defaulting to
+    /// high), rejecting values the transcoder can't produce with an error that
+    /// lists the valid ones
+    pub fn parse(format: &str, quality: Option<&str>) -> Result<Self, VfsError> {
+        let names = |values: Vec<&str>| values.join(", ");
+        
+        let format = StreamFormat::TRANSCODABLE.into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(format.trim()))
+            .ok_or_else(|| VfsError::InvalidInput(format!(
+                "Unsupported transcode format '{}'; expected one of: {}",
+                format,
+                names(StreamFormat::TRANSCODABLE.iter().map(|f| f.as_str()).collect()),
+            )))?;
+        
+        let quality = match quality {
+            None => TranscodeQuality::High,
+            Some(quality) => TranscodeQuality::ALL.into_iter()
+                .find(|q| q.as_str().eq_ignore_ascii_case(quality.trim()))
+                .ok_or_else(|| VfsError::InvalidInput(format!(
+                    "Unsupported transcode quality '{}'; expected one of: {}",
+                    quality,
+                    names(TranscodeQuality::ALL.iter().map(|q| q.as_str()).collect()),
+                )))?,
+        };
+        
+        Ok(Self { format, quality })
+    }
+}
+
 /// Transcoding job status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscodeJob {
@@ -168,6 +290,53 @@ pub enum TranscodeStatus {
     Cancelled,
 }
 
+/// Bytes read from the start of a file to sniff its type
+pub const MIME_SNIFF_BYTES: u64 = 8 * 1024;
+
+/// MIME type implied by a file's extension; `None` when there is no
+/// extension or it isn't one that says what the file holds (`.dat`, `.bin`)
+pub fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let mime = match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "heic" | "heif" => "image/heic",
+        "pdf" => "application/pdf",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "avi" => "video/x-msvideo",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "txt" => "text/plain",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "application/javascript",
+        "ts" | "tsx" => "text/typescript",
+        "md" => "text/markdown",
+        "zip" => "application/zip",
+        "tar" | "gz" | "bz2" => "application/x-compressed",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// MIME type of a file: from its extension when that is conclusive, else
+/// from the magic bytes at the start of `head` (the first
+/// `MIME_SNIFF_BYTES`, if they could be read), else `application/octet-stream`
+pub fn detect_mime(path: &Path, head: Option<&[u8]>) -> &'static str {
+    mime_from_extension(path)
+        .or_else(|| head.and_then(infer::get).map(|kind| kind.mime_type()))
+        .unwrap_or("application/octet-stream")
+}
+
 /// Media service interface
 #[async_trait]
 pub trait IMediaService: Send + Sync {
@@ -205,4 +374,61 @@ pub trait IMediaService: Send + Sync {
     fn is_available(&self) -> bool;
 }
 
+/// Transcode jobs by id, shared by whoever runs them and whoever reports on them
+pub type TranscodeJobs = Arc<RwLock<HashMap<String, TranscodeJob>>>;
+
+/// Runs one transcode to completion; `TranscodeQueue` decides when
+#[async_trait]
+pub trait TranscodeExecutor: Send + Sync {
+    /// Registry the queue records its jobs in; `execute` updates the
+    /// progress of its job there
+    fn jobs(&self) -> TranscodeJobs {
+        TranscodeJobs::default()
+    }
+    
+    /// Transcode `source` into `output_dir` as job `job_id`, returning the
+    /// playable output (e.g. the HLS playlist)
+    async fn execute(
+        &self,
+        job_id: &str,
+        source: &Path,
+        output_dir: &Path,
+        format: StreamFormat,
+        quality: TranscodeQuality,
+    ) -> Result<PathBuf>;
+    
+    /// Stop job `job_id` if it is running; its `execute` then fails
+    async fn cancel(&self, job_id: &str) -> Result<()>;
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// PNG signature followed by the start of an IHDR chunk
+    const PNG_HEAD: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    
+    #[test]
+    fn test_detect_mime_sniffs_content_when_extension_says_nothing() {
+        assert_eq!(detect_mime(Path::new("/stills/frame.dat"), Some(PNG_HEAD)), "image/png");
+        assert_eq!(detect_mime(Path::new("/stills/frame"), Some(PNG_HEAD)), "image/png");
+        assert_eq!(detect_mime(Path::new("/stills/frame.dat"), Some(b"plain bytes")), "application/octet-stream");
+        assert_eq!(detect_mime(Path::new("/stills/frame.dat"), None), "application/octet-stream");
+        // A conclusive extension needs no read
+        assert_eq!(detect_mime(Path::new("/clips/A001.MOV"), None), "video/quicktime");
+    }
+    
+    #[test]
+    fn test_transcode_request_validation() {
+        let request = TranscodeRequest::parse("MP4", Some("medium")).unwrap();
+        assert_eq!(request, TranscodeRequest { format: StreamFormat::MP4, quality: TranscodeQuality::Medium });
+        assert_eq!(TranscodeRequest::parse("hls", None).unwrap().quality, TranscodeQuality::High);
+        
+        let error = TranscodeRequest::parse("mkv", None).unwrap_err();
+        assert_eq!(error.code(), "INVALID_INPUT");
+        assert!(error.message().contains("hls, mp4"), "{}", error.message());
+        // Streaming protocols aren't transcode targets
+        assert!(TranscodeRequest::parse("dash", None).is_err());
+        assert!(TranscodeRequest::parse("hls", Some("8k")).unwrap_err().message().contains("low, medium, high"));
+    }
+}
@@ -5,10 +5,22 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::vfs::domain::{ColorLabel, FileTag};
+use crate::vfs::domain::{ColorLabel, FileTag, StorageTier};
+use crate::vfs::ports::ListSortBy;
+
+/// Most tier transitions kept per file; older ones are dropped first
+pub const MAX_TIER_HISTORY: usize = 50;
+
+/// A file arriving in a storage tier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierTransition {
+    pub tier: StorageTier,
+    pub timestamp: DateTime<Utc>,
+}
 
 /// File metadata that can be stored separately from the file itself
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -27,6 +39,22 @@ pub struct FileMetadata {
     
     /// User comment/notes
     pub comment: Option<String>,
+    
+    /// Tiers the file has moved through, oldest first
+    #[serde(default)]
+    pub tier_history: Vec<TierTransition>,
+    
+    /// Low-res editing proxy made from this file
+    #[serde(default)]
+    pub proxy: Option<PathBuf>,
+    
+    /// Original this file is a proxy of
+    #[serde(default)]
+    pub proxy_of: Option<PathBuf>,
+    
+    /// How this folder is shown, overriding the OS default
+    #[serde(default)]
+    pub folder_view: Option<FolderView>,
 }
 
 impl FileMetadata {
@@ -41,7 +69,124 @@ impl FileMetadata {
             && self.color_label.is_none()
             && self.rating.is_none()
             && self.comment.is_none()
+            && self.tier_history.is_empty()
+            && self.proxy.is_none()
+            && self.proxy_of.is_none()
+            && self.folder_view.is_none()
     }
+    
+    /// Record a move to `tier`; staying in the same tier is not a transition
+    pub fn push_tier_transition(&mut self, tier: StorageTier, timestamp: DateTime<Utc>) -> bool {
+        if self.tier_history.last().is_some_and(|last| last.tier == tier) {
+            return false;
+        }
+        
+        self.tier_history.push(TierTransition { tier, timestamp });
+        let excess = self.tier_history.len().saturating_sub(MAX_TIER_HISTORY);
+        self.tier_history.drain(..excess);
+        true
+    }
+}
+
+/// Finder-style view of a folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ViewMode {
+    Icon,
+    #[default]
+    List,
+    Column,
+    Gallery,
+}
+
+impl ViewMode {
+    /// Parse the names used by the OS preferences ("icon", "list", ...)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "icon" => Some(ViewMode::Icon),
+            "list" => Some(ViewMode::List),
+            "column" => Some(ViewMode::Column),
+            "gallery" => Some(ViewMode::Gallery),
+            _ => None,
+        }
+    }
+}
+
+/// How a folder's contents are shown and ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderView {
+    pub view_mode: ViewMode,
+    pub sort_by: ListSortBy,
+    pub sort_ascending: bool,
+}
+
+/// Query over user metadata - every criterion that is set must match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataQuery {
+    /// Files must carry all of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    
+    /// Minimum rating (inclusive)
+    #[serde(default)]
+    pub min_rating: Option<u8>,
+    
+    /// Required color label
+    #[serde(default)]
+    pub color_label: Option<ColorLabel>,
+    
+    /// Only favorites
+    #[serde(default)]
+    pub favorites_only: bool,
+    
+    /// Case-insensitive substring of the comment
+    #[serde(default)]
+    pub comment_contains: Option<String>,
+}
+
+impl MetadataQuery {
+    /// Check whether a file's metadata satisfies the query
+    pub fn matches(&self, metadata: &FileMetadata) -> bool {
+        let has_tags = self.tags.iter()
+            .all(|name| metadata.tags.iter().any(|t| &t.name == name));
+        let has_rating = match self.min_rating {
+            Some(min) => metadata.rating.is_some_and(|r| r >= min),
+            None => true,
+        };
+        let has_color = match self.color_label {
+            Some(color) => metadata.color_label == Some(color),
+            None => true,
+        };
+        let has_comment = match &self.comment_contains {
+            Some(needle) => metadata.comment.as_ref()
+                .is_some_and(|c| c.to_lowercase().contains(&needle.to_lowercase())),
+            None => true,
+        };
+        
+        has_tags && has_rating && has_color && has_comment
+            && (!self.favorites_only || metadata.is_favorite)
+    }
+}
+
+/// Saved search ("smart folder") that re-runs a metadata query on demand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartFolder {
+    pub id: String,
+    pub name: String,
+    pub source_id: String,
+    pub query: MetadataQuery,
+}
+
+/// Where tags on local files are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TagStorage {
+    /// The app's metadata file only
+    #[default]
+    Json,
+    /// Extended attributes on the files, shared with Finder and other tools
+    Xattr,
 }
 
 /// Metadata storage interface
@@ -88,6 +233,74 @@ pub trait IMetadataStore: Send + Sync {
     
     /// Get all unique tags used in a source
     async fn list_all_tags(&self, source_id: &str) -> Result<Vec<FileTag>>;
+    
+    /// Get all files in a source whose metadata matches a query
+    async fn query(&self, source_id: &str, query: &MetadataQuery) -> Result<Vec<String>>;
+    
+    /// Save a smart folder
+    async fn create_smart_folder(&self, name: String, source_id: String, query: MetadataQuery) -> Result<SmartFolder>;
+    
+    /// Get all smart folders
+    async fn list_smart_folders(&self) -> Result<Vec<SmartFolder>>;
+    
+    /// Delete a smart folder
+    async fn delete_smart_folder(&self, id: &str) -> Result<()>;
+    
+    /// Run a smart folder's query and return matching paths
+    async fn eval_smart_folder(&self, id: &str) -> Result<Vec<String>> {
+        let folder = self.list_smart_folders().await?
+            .into_iter()
+            .find(|f| f.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Smart folder not found: {}", id))?;
+        
+        self.query(&folder.source_id, &folder.query).await
+    }
+    
+    /// Append a move to `tier` to the file's tier history
+    async fn record_tier_transition(&self, source_id: &str, path: &Path, tier: StorageTier) -> Result<()> {
+        let mut metadata = self.get(source_id, path).await?.unwrap_or_default();
+        if metadata.push_tier_transition(tier, Utc::now()) {
+            self.set(source_id, path, metadata).await?;
+        }
+        Ok(())
+    }
+    
+    /// Record that `proxy` is an editing proxy of `original`, on both files
+    async fn link_proxy(&self, source_id: &str, original: &Path, proxy: &Path) -> Result<()> {
+        let mut metadata = self.get(source_id, original).await?.unwrap_or_default();
+        metadata.proxy = Some(proxy.to_path_buf());
+        self.set(source_id, original, metadata).await?;
+        
+        let mut metadata = self.get(source_id, proxy).await?.unwrap_or_default();
+        metadata.proxy_of = Some(original.to_path_buf());
+        self.set(source_id, proxy, metadata).await
+    }
+    
+    /// Drop the metadata of `source_id` files for which `exists` is false,
+    /// returning how many entries were pruned. Stores that keep metadata on the
+    /// files themselves have nothing to prune.
+    async fn vacuum(&self, _source_id: &str, _exists: &(dyn Fn(&Path) -> bool + Send + Sync)) -> Result<usize> {
+        Ok(0)
+    }
+    
+    /// The view saved for the folder at `path`, or `default` if it has none
+    async fn folder_view(&self, source_id: &str, path: &Path, default: FolderView) -> Result<FolderView> {
+        Ok(self.get(source_id, path).await?
+            .and_then(|metadata| metadata.folder_view)
+            .unwrap_or(default))
+    }
+    
+    /// Save how the folder at `path` is shown; `None` goes back to the default
+    async fn set_folder_view(&self, source_id: &str, path: &Path, view: Option<FolderView>) -> Result<()> {
+        let mut metadata = self.get(source_id, path).await?.unwrap_or_default();
+        metadata.folder_view = view;
+        self.set(source_id, path, metadata).await
+    }
+    
+    /// Write any buffered changes to disk (e.g. before a volume goes away)
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +322,38 @@ mod tests {
         assert!(!meta.is_empty());
     }
     
+    #[test]
+    fn test_metadata_query_matches_all_criteria() {
+        let mut meta = FileMetadata::new();
+        meta.rating = Some(5);
+        meta.tags.push(FileTag::new("proxy"));
+        
+        let query = MetadataQuery {
+            tags: vec!["proxy".to_string()],
+            min_rating: Some(4),
+            ..Default::default()
+        };
+        assert!(query.matches(&meta));
+        
+        let favorites = MetadataQuery { favorites_only: true, ..query };
+        assert!(!favorites.matches(&meta));
+    }
+    
+    #[test]
+    fn test_tier_history_capped_and_deduped() {
+        let mut meta = FileMetadata::new();
+        assert!(meta.push_tier_transition(StorageTier::Hot, Utc::now()));
+        assert!(!meta.push_tier_transition(StorageTier::Hot, Utc::now()));
+        assert!(!meta.is_empty());
+        
+        for i in 0..MAX_TIER_HISTORY {
+            let tier = if i % 2 == 0 { StorageTier::Archive } else { StorageTier::Hot };
+            meta.push_tier_transition(tier, Utc::now());
+        }
+        assert_eq!(meta.tier_history.len(), MAX_TIER_HISTORY);
+        assert_eq!(meta.tier_history[0].tier, StorageTier::Archive);
+    }
+    
     #[test]
     fn test_file_metadata_not_empty_with_tag() {
         let mut meta = FileMetadata::new();
@@ -12,24 +12,31 @@ pub mod clipboard;
 pub mod metadata;
 pub mod cross_storage;
 pub mod sync;
+pub mod credentials;
 
-pub use storage::StorageAdapter;
-pub use cache::{CacheAdapter, CacheStats};
+pub use storage::{
+    StorageAdapter, SignedUrlProvider, ObjectVersioning, ObjectVersion, ServerSideCopy, MAX_SIGNED_URL_EXPIRY, ListOptions,
+    ListSortBy, page_by_offset,
+};
+pub use cache::{CacheAdapter, CacheStats, CACHE_BLOCK_SIZE};
 pub use event_bus::EventBus;
+pub use credentials::CredentialStore;
 pub use file_operations::{
     IFileOperations, FileOperationsExt, FileEntry, FileStat,
     CopyOptions, MoveOptions, DeleteOptions,
 };
 pub use media::{
-    IMediaService, MediaInfo, ThumbnailData, StreamFormat,
-    TranscodeQuality, TranscodeJob, TranscodeStatus,
+    IMediaService, MediaInfo, ThumbnailData, SpriteSheet, StreamFormat,
+    TranscodeQuality, TranscodeJob, TranscodeJobs, TranscodeStatus, TranscodeExecutor,
+    TranscodeFormatInfo, TranscodeOptions, TranscodeRequest,
 };
 pub use clipboard::{
-    IClipboardService, ClipboardContent, ClipboardOperation,
+    IClipboardService, ClipboardBackend, ClipboardContent, ClipboardOperation,
     ClipboardSource, PasteResult,
 };
 pub use metadata::{
-    IMetadataStore, FileMetadata,
+    IMetadataStore, FileMetadata, MetadataQuery, SmartFolder, TagStorage, TierTransition, MAX_TIER_HISTORY,
+    FolderView, ViewMode,
 };
 pub use cross_storage::{
     ICrossStorageService, CrossStorageOptions, CrossStorageResult,
@@ -2,9 +2,122 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::path::Path;
+use std::time::Duration;
 
-use crate::vfs::domain::{VirtualFile, StorageSourceType};
+use crate::vfs::domain::{RetrievalTier, RetryPolicy, TransferTuning, VirtualFile, StorageSourceType, StorageTier, VfsError};
+
+/// Sort key for directory listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ListSortBy {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    /// File extension, then name
+    Type,
+}
+
+/// Sorting and filtering applied to a directory listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListOptions {
+    /// Sort key
+    pub sort_by: ListSortBy,
+    /// Sort ascending (false = descending)
+    pub ascending: bool,
+    /// Group directories before files regardless of sort key
+    pub dirs_first: bool,
+    /// Case-insensitive substring the name must contain
+    pub name_filter: Option<String>,
+    /// Include dotfiles and files flagged hidden
+    pub show_hidden: bool,
+    /// Include files matching the ignore patterns (`.DS_Store` and the like)
+    pub show_ignored: bool,
+    /// Fill in `VirtualFile::checksum` where the listing carries one (S3 ETags,
+    /// GCS MD5s). Local files are never hashed for a listing.
+    pub with_checksums: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            sort_by: ListSortBy::Name,
+            ascending: true,
+            dirs_first: true,
+            name_filter: None,
+            show_hidden: true,
+            show_ignored: false,
+            with_checksums: false,
+        }
+    }
+}
+
+impl ListOptions {
+    /// Whether an entry passes the name/hidden filters (checks only name and
+    /// hidden flag, so it can run before the entry is stat'ed)
+    pub fn includes_name(&self, name: &str, is_hidden: bool) -> bool {
+        if !self.show_hidden && (is_hidden || name.starts_with('.')) {
+            return false;
+        }
+        match &self.name_filter {
+            Some(filter) if !filter.is_empty() => name.to_lowercase().contains(&filter.to_lowercase()),
+            _ => true,
+        }
+    }
+    
+    /// Whether a file passes the filters
+    pub fn includes(&self, file: &VirtualFile) -> bool {
+        self.includes_name(&file.name, file.is_hidden.unwrap_or(false))
+    }
+    
+    /// Whether sorting needs size/modification time (i.e. a stat per entry)
+    pub fn needs_metadata(&self) -> bool {
+        matches!(self.sort_by, ListSortBy::Size | ListSortBy::Modified)
+    }
+    
+    /// Order two files according to these options
+    pub fn compare(&self, a: &VirtualFile, b: &VirtualFile) -> Ordering {
+        if self.dirs_first {
+            match (a.is_directory, b.is_directory) {
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                _ => {}
+            }
+        }
+        
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ordering = match self.sort_by {
+            ListSortBy::Name => by_name(),
+            ListSortBy::Size => a.size.bytes().cmp(&b.size.bytes()).then_with(by_name),
+            ListSortBy::Modified => a.last_modified.cmp(&b.last_modified).then_with(by_name),
+            ListSortBy::Type => extension(&a.name).cmp(&extension(&b.name)).then_with(by_name),
+        };
+        
+        if self.ascending { ordering } else { ordering.reverse() }
+    }
+    
+    /// Filter and sort a full listing
+    pub fn apply(&self, mut files: Vec<VirtualFile>) -> Vec<VirtualFile> {
+        files.retain(|f| self.includes(f));
+        files.sort_by(|a, b| self.compare(a, b));
+        files
+    }
+}
+
+/// Lowercased extension used for "sort by type"
+fn extension(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
 
 /// Storage adapter trait - Port for all storage backends
 ///
@@ -25,6 +138,49 @@ pub trait StorageAdapter: Send + Sync {
     /// List files in a directory
     async fn list_files(&self, path: &Path) -> Result<Vec<VirtualFile>>;
     
+    /// List a directory, filtered and sorted by `options`.
+    ///
+    /// The default filters and sorts a full `list_files`; adapters that can skip
+    /// entries during enumeration should override it.
+    async fn list_files_with(&self, path: &Path, options: &ListOptions) -> Result<Vec<VirtualFile>> {
+        Ok(options.apply(self.list_files(path).await?))
+    }
+    
+    /// List one page of a directory.
+    ///
+    /// `cursor` is the opaque value returned with the previous page (`None` for the
+    /// first page); the returned cursor is `None` once the listing is exhausted.
+    /// Sorting is applied across the whole directory, so pass the same `options`
+    /// for every page. The default pages over a full `list_files_with` by offset -
+    /// adapters that can list incrementally should override it.
+    async fn list_files_paged(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<VirtualFile>, Option<String>)> {
+        let files = self.list_files_with(path, options).await?;
+        page_by_offset(files, cursor.as_deref(), limit)
+    }
+    
+    /// List a directory in batches of up to `batch_size` entries as they're
+    /// read, filtered by `options` but not sorted, so a huge folder can be
+    /// shown before it has been read to the end.
+    ///
+    /// The default splits up a full `list_files_with`; adapters that enumerate
+    /// incrementally should override it.
+    async fn list_files_batched(
+        &self,
+        path: &Path,
+        options: &ListOptions,
+        batch_size: usize,
+    ) -> Result<BoxStream<'static, Result<Vec<VirtualFile>>>> {
+        let files = self.list_files_with(path, options).await?;
+        let batches: Vec<Result<Vec<VirtualFile>>> = files.chunks(batch_size.max(1)).map(|chunk| Ok(chunk.to_vec())).collect();
+        Ok(futures::stream::iter(batches).boxed())
+    }
+    
     /// Read file contents
     async fn read_file(&self, path: &Path) -> Result<Vec<u8>>;
     
@@ -48,6 +204,159 @@ pub trait StorageAdapter: Send + Sync {
     
     /// Get file size without downloading
     async fn file_size(&self, path: &Path) -> Result<u64>;
+    
+    /// Whether `read_file_range` is served natively (e.g. HTTP `Range` requests),
+    /// so interrupted downloads can resume from an offset
+    fn supports_range_reads(&self) -> bool {
+        false
+    }
+    
+    /// Hex MD5 of the stored object, if the backend exposes one
+    async fn checksum_md5(&self, _path: &Path) -> Result<Option<String>> {
+        Ok(None)
+    }
+    
+    /// Retry transient failures of remote operations as `policy` says;
+    /// adapters for local storage ignore it
+    fn set_retry_policy(&self, _policy: RetryPolicy) {}
+    
+    /// Listing and transfer sizes in use; `None` for adapters that don't
+    /// take tuning
+    fn transfer_tuning(&self) -> Option<TransferTuning> {
+        None
+    }
+    
+    /// Change listing and transfer sizes; ignored by adapters without tuning
+    fn set_transfer_tuning(&self, _tuning: TransferTuning) {}
+    
+    /// Whether listings are flat (every object under the prefix) rather than
+    /// one level of synthesized folders; `None` for adapters with real folders
+    fn flat_listing(&self) -> Option<bool> {
+        None
+    }
+    
+    /// Switch between flat and folder listings; ignored by adapters with real folders
+    fn set_flat_listing(&self, _flat: bool) {}
+    
+    /// Consecutive failed connection checks; 0 for adapters that don't
+    /// monitor their connection
+    fn connection_failures(&self) -> u64 {
+        0
+    }
+    
+    /// Whether `change_tier` moves objects between storage classes
+    fn supports_tier_changes(&self) -> bool {
+        false
+    }
+    
+    /// Move a file to another storage tier (e.g. change an object's storage class)
+    async fn change_tier(&self, path: &Path, _tier: StorageTier) -> Result<()> {
+        Err(VfsError::Unsupported(format!("{} does not support tier changes: {:?}", self.name(), path)).into())
+    }
+    
+    /// Rough seconds until `path` can be read, if it sits in an archive tier
+    /// (e.g. S3 Glacier) and hasn't been retrieved yet; `None` when it can be
+    /// read now or the adapter can't tell, including on backends without
+    /// archive tiers.
+    ///
+    /// This is checked before every read, so answer from what the adapter
+    /// already knows rather than with a request; adapters that can't should
+    /// leave the default and fail reads of unrestored objects with
+    /// `ArchiveNotRetrievedError` instead.
+    async fn pending_retrieval(&self, _path: &Path) -> Result<Option<u64>> {
+        Ok(None)
+    }
+    
+    /// Start restoring an archived file so it can be read; `tier` trades
+    /// speed for cost
+    async fn initiate_retrieval(&self, path: &Path, _tier: RetrievalTier) -> Result<()> {
+        Err(VfsError::Unsupported(format!("{} has no archive to retrieve {:?} from", self.name(), path)).into())
+    }
+}
+
+/// Slice one page out of `items`, using the item offset as the cursor
+pub fn page_by_offset<T>(
+    mut items: Vec<T>,
+    cursor: Option<&str>,
+    limit: usize,
+) -> Result<(Vec<T>, Option<String>)> {
+    let offset = match cursor {
+        Some(cursor) => cursor.parse::<usize>()
+            .map_err(|_| anyhow::anyhow!("Invalid listing cursor: {}", cursor))?,
+        None => 0,
+    };
+    let limit = limit.max(1);
+    
+    if offset >= items.len() {
+        return Ok((Vec::new(), None));
+    }
+    
+    let end = offset.saturating_add(limit).min(items.len());
+    let next = (end < items.len()).then(|| end.to_string());
+    items.truncate(end);
+    
+    Ok((items.split_off(offset), next))
+}
+
+/// Maximum lifetime of a signed URL (7 days - the limit for both SigV4 and GCS V4 signing)
+pub const MAX_SIGNED_URL_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Signed URL port - implemented by object storage adapters that can hand out
+/// time-limited URLs, so the frontend can stream directly from the bucket
+/// instead of proxying bytes through the app.
+#[async_trait]
+pub trait SignedUrlProvider: Send + Sync {
+    /// Generate a signed GET URL for `path`, valid for `expiry`
+    async fn signed_url(&self, path: &Path, expiry: Duration) -> Result<String>;
+}
+
+/// One stored version of an object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectVersion {
+    pub version_id: String,
+    pub last_modified: DateTime<Utc>,
+    pub size: u64,
+    /// The version a plain read returns
+    pub is_latest: bool,
+    /// A delete marker rather than content; it has no data to read
+    pub is_delete_marker: bool,
+}
+
+/// Object versioning port - implemented by object storage adapters whose
+/// buckets keep prior versions of each key.
+///
+/// Implementations return `VfsError::Unsupported` when the bucket doesn't
+/// have versioning turned on.
+#[async_trait]
+pub trait ObjectVersioning: Send + Sync {
+    /// Versions of `path`, newest first
+    async fn list_versions(&self, path: &Path) -> Result<Vec<ObjectVersion>>;
+    
+    /// Contents of one version of `path`
+    async fn read_version(&self, path: &Path, version_id: &str) -> Result<Vec<u8>>;
+    
+    /// Make `version_id` the current version by copying it over `path`
+    async fn restore_version(&self, path: &Path, version_id: &str) -> Result<()>;
+}
+
+/// Server-side copy port - implemented by object storage adapters that can
+/// copy an object out of another bucket without the data passing through the
+/// app.
+#[async_trait]
+pub trait ServerSideCopy: Send + Sync {
+    /// Account and endpoint requests go to; objects can only be copied
+    /// between adapters with the same scope. `None` when this adapter can't
+    /// copy server-side at all (e.g. anonymous access).
+    fn copy_scope(&self) -> Option<String>;
+    
+    /// Bucket objects are copied out of
+    fn bucket(&self) -> &str;
+    
+    /// Copy `from` in `source_bucket` (`size` bytes) to `to` in this adapter's
+    /// bucket. Fails with `VfsError::Unsupported` for objects too large to
+    /// copy in one request, which have to be streamed instead.
+    async fn copy_object_from(&self, source_bucket: &str, from: &Path, to: &Path, size: u64) -> Result<()>;
 }
 
 /// Factory for creating storage adapters
@@ -66,6 +66,82 @@ mod feature_tests {
         assert!(!files[2].is_directory, "Third item should be a file");
     }
     
+    /// **Feature**: Page through a huge directory
+    /// 
+    /// Verifies that paged listing returns bounded pages whose union is the
+    /// full directory, with no duplicates, and a final page without a cursor.
+    #[tokio::test]
+    async fn feature_paged_listing_reassembles_directory() {
+        use crate::vfs::adapters::LocalStorageAdapter;
+        use crate::vfs::ports::{ListOptions, StorageAdapter};
+        use std::collections::HashSet;
+        
+        let temp_dir = TempDir::new().unwrap();
+        for i in 0..250 {
+            std::fs::write(temp_dir.path().join(format!("clip_{:03}.mov", i)), "x").unwrap();
+        }
+        
+        let adapter = LocalStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "Media".to_string(),
+        );
+        
+        let mut cursor = None;
+        let mut page_sizes = Vec::new();
+        let mut names = Vec::new();
+        loop {
+            let (files, next) = adapter.list_files_paged(Path::new("/"), &ListOptions::default(), cursor, 100).await.unwrap();
+            page_sizes.push(files.len());
+            names.extend(files.into_iter().map(|f| f.name));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        
+        assert_eq!(page_sizes, vec![100, 100, 50]);
+        let unique: HashSet<_> = names.iter().cloned().collect();
+        assert_eq!(unique.len(), 250, "Pages should cover every file exactly once");
+        assert_eq!(names[0], "clip_000.mov");
+        assert_eq!(names[249], "clip_249.mov");
+    }
+    
+    /// **Feature**: Sort and filter listings server-side
+    /// 
+    /// Verifies that sorting by size descending puts the largest file first
+    /// and that hiding hidden files drops dotfiles.
+    #[tokio::test]
+    async fn feature_listing_sorted_by_size_without_hidden() {
+        use crate::vfs::adapters::LocalStorageAdapter;
+        use crate::vfs::ports::{ListOptions, ListSortBy, StorageAdapter};
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("small.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("large.mov"), vec![0u8; 4096]).unwrap();
+        std::fs::write(temp_dir.path().join("medium.wav"), vec![0u8; 512]).unwrap();
+        std::fs::write(temp_dir.path().join(".DS_Store"), vec![0u8; 8192]).unwrap();
+        
+        let adapter = LocalStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "Media".to_string(),
+        );
+        
+        let options = ListOptions {
+            sort_by: ListSortBy::Size,
+            ascending: false,
+            show_hidden: false,
+            ..ListOptions::default()
+        };
+        let files = adapter.list_files_with(Path::new("/"), &options).await.unwrap();
+        let names: Vec<_> = files.iter().map(|f| f.name.as_str()).collect();
+        
+        assert_eq!(names, vec!["large.mov", "medium.wav", "small.txt"]);
+        
+        // Paging honours the same global order
+        let (page, _) = adapter.list_files_paged(Path::new("/"), &options, None, 1).await.unwrap();
+        assert_eq!(page[0].name, "large.mov");
+    }
+    
     // =========================================================================
     // FEATURE: POSIX File Operations
     // Use Case: User creates, renames, copies, moves, deletes files
@@ -112,6 +188,62 @@ mod feature_tests {
         assert_eq!(content, b"content");
     }
     
+    /// **Feature**: Create and delete files on a memory source behaves like local
+    #[tokio::test]
+    async fn feature_create_and_delete_file_in_memory() {
+        use crate::vfs::adapters::InMemoryStorageAdapter;
+        use crate::vfs::ports::IFileOperations;
+        
+        let adapter = InMemoryStorageAdapter::new("Scratch".to_string());
+        
+        // Create file
+        IFileOperations::write(&adapter, Path::new("/document.txt"), b"Hello World").await.unwrap();
+        assert!(IFileOperations::exists(&adapter, Path::new("/document.txt")).await.unwrap());
+        
+        // Delete file
+        IFileOperations::rm(&adapter, Path::new("/document.txt")).await.unwrap();
+        assert!(!IFileOperations::exists(&adapter, Path::new("/document.txt")).await.unwrap());
+    }
+    
+    /// **Feature**: Rename files on a memory source (preserves content)
+    #[tokio::test]
+    async fn feature_rename_file_in_memory() {
+        use crate::vfs::adapters::InMemoryStorageAdapter;
+        use crate::vfs::ports::IFileOperations;
+        
+        let adapter = InMemoryStorageAdapter::new("Scratch".to_string());
+        
+        IFileOperations::write(&adapter, Path::new("/old_name.txt"), b"content").await.unwrap();
+        IFileOperations::rename(&adapter, Path::new("/old_name.txt"), Path::new("/new_name.txt")).await.unwrap();
+        
+        assert!(!IFileOperations::exists(&adapter, Path::new("/old_name.txt")).await.unwrap());
+        let content = IFileOperations::read(&adapter, Path::new("/new_name.txt")).await.unwrap();
+        assert_eq!(content, b"content");
+    }
+    
+    /// **Feature**: Mount a RAM disk as scratch space
+    ///
+    /// Listing matches local sources: directories first, then files by name.
+    #[tokio::test]
+    async fn feature_memory_source_scratch_space() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::ListOptions;
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_memory_source("Scratch".to_string()).await.unwrap();
+        
+        service.write(&source.id, Path::new("/notes.txt"), b"call time 7am").await.unwrap();
+        service.mkdir(&source.id, Path::new("/renders")).await.unwrap();
+        service.write(&source.id, Path::new("/Budget.csv"), b"1,2").await.unwrap();
+        
+        let names: Vec<String> = service.list_files(&source.id, Path::new("/"), &ListOptions::default())
+            .await.unwrap()
+            .into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["renders", "Budget.csv", "notes.txt"]);
+        
+        assert_eq!(service.read(&source.id, Path::new("/notes.txt")).await.unwrap(), b"call time 7am");
+    }
+    
     /// **Feature**: Copy files (preserves source, creates destination)
     #[tokio::test]
     async fn feature_copy_file_preserves_original() {
@@ -182,6 +314,53 @@ mod feature_tests {
         assert!(!IFileOperations::exists(&adapter, Path::new("/projects")).await.unwrap());
     }
     
+    /// **Feature**: Batch delete carries on past missing files
+    #[tokio::test]
+    async fn feature_delete_many_reports_failures() {
+        use crate::vfs::application::VfsService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("A001.mov"), vec![0u8; 3000]).unwrap();
+        std::fs::write(temp_dir.path().join("A002.mov"), vec![0u8; 1500]).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Media".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let paths = vec![PathBuf::from("/A001.mov"), PathBuf::from("/gone.mov"), PathBuf::from("/A002.mov")];
+        let summary = service.delete_many(&source.id, &paths, false, true).await.unwrap();
+        
+        assert_eq!(summary.deleted, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.total_bytes_freed, 4500);
+        assert!(summary.errors[0].starts_with("/gone.mov"));
+        assert!(!temp_dir.path().join("A001.mov").exists());
+        assert!(!temp_dir.path().join("A002.mov").exists());
+    }
+    
+    /// **Feature**: Batch delete on a source without a trash must be permanent
+    #[tokio::test]
+    async fn feature_delete_many_without_trash_needs_permanent() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::VfsError;
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_memory_source("Scratch".to_string()).await.unwrap();
+        service.write(&source.id, Path::new("/A001.mov"), &[0u8; 3000]).await.unwrap();
+        service.write(&source.id, Path::new("/A002.mov"), &[0u8; 1500]).await.unwrap();
+        let paths = vec![PathBuf::from("/A001.mov"), PathBuf::from("/A002.mov")];
+        
+        // Refused up front rather than failing item by item
+        let err = VfsError::from(service.delete_many(&source.id, &paths, false, false).await.unwrap_err());
+        assert_eq!(err.code(), "UNSUPPORTED");
+        assert!(service.exists(&source.id, Path::new("/A001.mov")).await.unwrap());
+        
+        let summary = service.delete_many(&source.id, &paths, false, true).await.unwrap();
+        assert_eq!((summary.deleted, summary.failed), (2, 0));
+        assert_eq!(summary.total_bytes_freed, 4500);
+        assert!(!service.exists(&source.id, Path::new("/A002.mov")).await.unwrap());
+    }
+    
     /// **Feature**: Get file statistics (POSIX stat)
     #[tokio::test]
     async fn feature_get_file_stats() {
@@ -205,6 +384,656 @@ mod feature_tests {
         assert!(stat.mtime.is_some(), "Should have modification time");
     }
     
+    /// **Feature**: Duplicate finder groups identical media
+    #[tokio::test]
+    async fn feature_find_duplicates_in_tree() {
+        use crate::vfs::application::VfsService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("ingest/day2")).unwrap();
+        std::fs::write(temp_dir.path().join("ingest/A001.mov"), vec![7u8; 4096]).unwrap();
+        std::fs::write(temp_dir.path().join("ingest/day2/A001_copy.mov"), vec![7u8; 4096]).unwrap();
+        std::fs::write(temp_dir.path().join("ingest/A002.mov"), vec![9u8; 4096]).unwrap();
+        std::fs::write(temp_dir.path().join("ingest/empty1.txt"), "").unwrap();
+        std::fs::write(temp_dir.path().join("ingest/empty2.txt"), "").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Media".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let groups = service.find_duplicates(&source.id, Path::new("/ingest"), std::sync::Arc::new(|_| {}))
+            .await.unwrap();
+        
+        assert_eq!(groups.len(), 1, "Only the two identical clips form a group");
+        assert_eq!(groups[0].size, 4096);
+        assert_eq!(groups[0].paths.len(), 2);
+        assert!(groups[0].paths.iter().all(|p| p.to_string_lossy().contains("A001")));
+    }
+    
+    /// **Feature**: Read-only sources reject writes but still browse
+    #[tokio::test]
+    async fn feature_read_only_source_rejects_writes() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::ReadOnlyError;
+        use crate::vfs::ports::ListOptions;
+        
+        let masters = TempDir::new().unwrap();
+        let scratch = TempDir::new().unwrap();
+        std::fs::write(masters.path().join("master.mov"), "frames").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Masters".to_string(), masters.path().to_path_buf())
+            .await.unwrap();
+        let other = service.add_local_source("Scratch".to_string(), scratch.path().to_path_buf())
+            .await.unwrap();
+        let source = service.set_read_only(&source.id, true).unwrap();
+        assert!(source.config.read_only);
+        
+        let err = service.write(&source.id, Path::new("/new.txt"), b"nope").await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyError>().is_some());
+        assert!(service.mkdir(&source.id, Path::new("/dir")).await.is_err());
+        assert!(service.rename(&source.id, Path::new("/master.mov"), Path::new("/x.mov")).await.is_err());
+        assert!(service.rm(&source.id, Path::new("/master.mov")).await.is_err());
+        assert!(service.touch(&source.id, Path::new("/master.mov")).await.is_err());
+        assert!(service.copy_to_source(&other.id, Path::new("/"), &source.id, Path::new("/")).await.is_err());
+        assert!(service.move_to_source(&source.id, Path::new("/master.mov"), &other.id, Path::new("/")).await.is_err());
+        
+        // Nothing was touched, and reads still work
+        assert!(!masters.path().join("new.txt").exists());
+        assert!(!scratch.path().join("master.mov").exists());
+        let files = service.list_files(&source.id, Path::new("/"), &ListOptions::default()).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(service.read(&source.id, Path::new("/master.mov")).await.unwrap(), b"frames");
+        
+        // Copying out of a read-only source is fine
+        service.copy_to_source(&source.id, Path::new("/master.mov"), &other.id, Path::new("/")).await.unwrap();
+        assert!(scratch.path().join("master.mov").exists());
+    }
+    
+    /// **Feature**: Sources with a quota refuse writes past it
+    #[tokio::test]
+    async fn feature_quota_blocks_writes_until_space_freed() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::QuotaExceededError;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Scratch".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        service.set_quota(&source.id, Some(100)).await.unwrap();
+        
+        service.write(&source.id, Path::new("/a.bin"), &[1u8; 60]).await.unwrap();
+        assert_eq!(service.source_usage(&source.id).await.unwrap(), 60);
+        
+        let err = service.write(&source.id, Path::new("/b.bin"), &[2u8; 60]).await.unwrap_err();
+        let err = err.downcast_ref::<QuotaExceededError>().expect("typed quota error");
+        assert_eq!((err.used, err.quota), (60, 100));
+        assert!(!temp_dir.path().join("b.bin").exists());
+        
+        // Deleting frees room again
+        service.rm(&source.id, Path::new("/a.bin")).await.unwrap();
+        assert_eq!(service.source_usage(&source.id).await.unwrap(), 0);
+        service.write(&source.id, Path::new("/b.bin"), &[2u8; 60]).await.unwrap();
+    }
+    
+    /// **Feature**: Ignored junk files are hidden and left behind by folder copies
+    #[tokio::test]
+    async fn feature_ignore_patterns_skip_junk_in_copies() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::IgnorePatterns;
+        use crate::vfs::ports::{CopyOptions, ListOptions};
+        
+        let shoot = TempDir::new().unwrap();
+        let backup = TempDir::new().unwrap();
+        let day1 = shoot.path().join("Day1");
+        std::fs::create_dir_all(day1.join("cards")).unwrap();
+        std::fs::write(day1.join("A001.mov"), "frames").unwrap();
+        std::fs::write(day1.join(".DS_Store"), "finder").unwrap();
+        std::fs::write(day1.join("cards/.DS_Store"), "finder").unwrap();
+        std::fs::write(day1.join("cards/B001.mov"), "more frames").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        service.set_ignore_patterns(IgnorePatterns::new(vec![".DS_Store".to_string()])).await.unwrap();
+        let source = service.add_local_source("Shoot".to_string(), shoot.path().to_path_buf())
+            .await.unwrap();
+        let other = service.add_local_source("Backup".to_string(), backup.path().to_path_buf())
+            .await.unwrap();
+        
+        let files = service.list_files(&source.id, Path::new("/Day1"), &ListOptions::default()).await.unwrap();
+        let mut names: Vec<_> = files.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["A001.mov", "cards"]);
+        let options = ListOptions { show_ignored: true, ..ListOptions::default() };
+        assert_eq!(service.list_files(&source.id, Path::new("/Day1"), &options).await.unwrap().len(), 3);
+        
+        // Across sources (copy and sync)
+        service.copy_to_source(&source.id, Path::new("/Day1"), &other.id, Path::new("/")).await.unwrap();
+        assert_eq!(std::fs::read_to_string(backup.path().join("Day1/A001.mov")).unwrap(), "frames");
+        assert!(backup.path().join("Day1/cards/B001.mov").exists());
+        assert!(!backup.path().join("Day1/.DS_Store").exists());
+        assert!(!backup.path().join("Day1/cards/.DS_Store").exists());
+        
+        // Within one source
+        let options = CopyOptions { recursive: true, ..CopyOptions::default() };
+        service.copy(&source.id, Path::new("/Day1"), Path::new("/Day1 copy"), options).await.unwrap();
+        assert!(shoot.path().join("Day1 copy/cards/B001.mov").exists());
+        assert!(!shoot.path().join("Day1 copy/.DS_Store").exists());
+        assert!(!shoot.path().join("Day1 copy/cards/.DS_Store").exists());
+    }
+    
+    /// **Feature**: Command errors carry a code the frontend can branch on
+    #[tokio::test]
+    async fn feature_typed_errors_for_missing_files() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::VfsError;
+        use crate::vfs::ports::CopyOptions;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("exists.txt"), "here").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Docs".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let err = VfsError::from(service.read(&source.id, Path::new("/missing.txt")).await.unwrap_err())
+            .context("Failed to read file");
+        assert!(matches!(err, VfsError::NotFound(_)));
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert!(err.message().starts_with("Failed to read file: "));
+        
+        let err = VfsError::from(service.read("no-such-source", Path::new("/exists.txt")).await.unwrap_err());
+        assert_eq!(err.code(), "NOT_FOUND");
+        
+        std::fs::write(temp_dir.path().join("other.txt"), "there").unwrap();
+        let err = VfsError::from(
+            service.copy(&source.id, Path::new("/exists.txt"), Path::new("/other.txt"), CopyOptions::default())
+                .await.unwrap_err()
+        );
+        assert_eq!(err.code(), "ALREADY_EXISTS");
+        
+        service.set_read_only(&source.id, true).unwrap();
+        let err = VfsError::from(service.write(&source.id, Path::new("/new.txt"), b"x").await.unwrap_err());
+        assert_eq!(err.code(), "READ_ONLY");
+    }
+    
+    /// **Feature**: Retry flaky remote sources
+    ///
+    /// As an editor on hotel Wi-Fi, I want reads from the office NAS to survive
+    /// a dropped packet without failing the whole copy.
+    #[tokio::test]
+    async fn feature_retry_policy_for_remote_sources() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::{RetryPolicy, VfsError};
+        use crate::vfs::platform::retry_with_policy;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+        
+        // The frontend sends delays in milliseconds
+        let policy: RetryPolicy = serde_json::from_str(
+            r#"{"maxAttempts":4,"baseDelay":1,"maxDelay":5,"jitter":true}"#
+        ).unwrap();
+        assert_eq!(policy.max_attempts, 4);
+        assert_eq!(policy.max_delay, Duration::from_millis(5));
+        
+        // A request that times out twice still succeeds
+        let calls = &AtomicU32::new(0);
+        let data = retry_with_policy(&policy, || async move {
+            if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+                return Err(VfsError::Timeout("GET timed out".to_string()).into());
+            }
+            Ok(b"frame".to_vec())
+        }).await.unwrap();
+        assert_eq!(data, b"frame");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        
+        // Once the attempts are used up, the last error is reported
+        let calls = &AtomicU32::new(0);
+        let err = retry_with_policy(&policy, || async move {
+            let n = calls.fetch_add(1, Ordering::Relaxed) + 1;
+            Err::<(), _>(anyhow::anyhow!("connection reset (attempt {})", n))
+        }).await.unwrap_err();
+        assert_eq!(err.to_string(), "connection reset (attempt 4)");
+        
+        // Local sources don't take a retry policy
+        let temp_dir = TempDir::new().unwrap();
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Docs".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        let err = VfsError::from(service.set_retry_policy(&source.id, policy).await.unwrap_err());
+        assert_eq!(err.code(), "UNSUPPORTED");
+        
+        let err = VfsError::from(
+            service.set_retry_policy(&source.id, RetryPolicy { max_attempts: 0, ..policy }).await.unwrap_err()
+        );
+        assert_eq!(err.code(), "INVALID_INPUT");
+    }
+    
+    /// **Feature**: Compare two folders before archiving
+    #[tokio::test]
+    async fn feature_compare_folders() {
+        use crate::vfs::application::folder_diff::DiffReason;
+        use crate::vfs::application::VfsService;
+        
+        let original = TempDir::new().unwrap();
+        let backup = TempDir::new().unwrap();
+        for root in [original.path(), backup.path()] {
+            std::fs::create_dir_all(root.join("project/renders")).unwrap();
+            std::fs::write(root.join("project/edit.prproj"), "timeline v3").unwrap();
+        }
+        std::fs::write(original.path().join("project/renders/final.mov"), "frames-A").unwrap();
+        std::fs::write(backup.path().join("project/renders/final.mov"), "frames-B").unwrap();
+        std::fs::write(original.path().join("project/notes.txt"), "only here").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let a = service.add_local_source("Original".to_string(), original.path().to_path_buf()).await.unwrap();
+        let b = service.add_local_source("Backup".to_string(), backup.path().to_path_buf()).await.unwrap();
+        let project = Path::new("/project");
+        
+        // Same sizes, so only a deep comparison sees the changed render
+        let shallow = service.compare_dirs(&a.id, project, &b.id, project, false, std::sync::Arc::new(|_| {}))
+            .await.unwrap();
+        assert_eq!(shallow.only_in_a, vec![PathBuf::from("notes.txt")]);
+        assert!(shallow.differing.is_empty());
+        
+        let deep = service.compare_dirs(&a.id, project, &b.id, project, true, std::sync::Arc::new(|_| {}))
+            .await.unwrap();
+        assert_eq!(deep.only_in_a, vec![PathBuf::from("notes.txt")]);
+        assert!(deep.only_in_b.is_empty());
+        assert_eq!(deep.differing.len(), 1);
+        assert_eq!(deep.differing[0].path, PathBuf::from("renders/final.mov"));
+        assert_eq!(deep.differing[0].reason, DiffReason::Checksum);
+        assert_eq!(deep.identical, 1);
+        
+        // Swapping sides swaps the one-sided lists
+        let reversed = service.compare_dirs(&b.id, project, &a.id, project, true, std::sync::Arc::new(|_| {}))
+            .await.unwrap();
+        assert_eq!(reversed.only_in_b, vec![PathBuf::from("notes.txt")]);
+    }
+    
+    /// **Feature**: Merkle tree hash spots a deep change without rehashing the rest
+    #[tokio::test]
+    async fn feature_tree_hash_detects_deep_change() {
+        use crate::vfs::application::VfsService;
+        
+        let original = TempDir::new().unwrap();
+        let copy = TempDir::new().unwrap();
+        for root in [original.path(), copy.path()] {
+            std::fs::create_dir_all(root.join("project/renders/day1")).unwrap();
+            std::fs::write(root.join("project/edit.prproj"), "timeline v3").unwrap();
+            std::fs::write(root.join("project/renders/day1/A001.mov"), "frames").unwrap();
+        }
+        
+        let service = VfsService::new().await.unwrap();
+        let a = service.add_local_source("Original".to_string(), original.path().to_path_buf()).await.unwrap();
+        let b = service.add_local_source("Copy".to_string(), copy.path().to_path_buf()).await.unwrap();
+        let root = Path::new("/");
+        
+        let first_a = service.tree_hash(&a.id, root).await.unwrap();
+        let first_b = service.tree_hash(&b.id, root).await.unwrap();
+        assert_eq!(first_a.hash, first_b.hash);
+        assert_eq!(first_a.hashed_files, 2);
+        
+        // Unchanged trees compare from cache alone
+        assert_eq!(service.tree_hash(&a.id, root).await.unwrap().hashed_files, 0);
+        assert_eq!(service.tree_hash(&b.id, root).await.unwrap().hashed_files, 0);
+        
+        // A deep write changes the root and only that file is read again
+        service.write(&a.id, Path::new("/project/renders/day1/A001.mov"), b"regraded").await.unwrap();
+        let changed = service.tree_hash(&a.id, root).await.unwrap();
+        assert_ne!(changed.hash, first_b.hash);
+        assert_eq!(changed.hashed_files, 1);
+        
+        // Drilling down shows which branch differs
+        let edit = Path::new("/project/edit.prproj");
+        let renders = Path::new("/project/renders");
+        assert_eq!(service.tree_hash(&a.id, edit).await.unwrap().hash, service.tree_hash(&b.id, edit).await.unwrap().hash);
+        assert_ne!(service.tree_hash(&a.id, renders).await.unwrap().hash, service.tree_hash(&b.id, renders).await.unwrap().hash);
+    }
+    
+    /// **Feature**: New Document picks the next free "Untitled" name
+    #[tokio::test]
+    async fn feature_new_file_from_template() {
+        use crate::vfs::application::VfsService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Docs".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let first = service.new_from_template(&source.id, Path::new("/"), "text").await.unwrap();
+        let second = service.new_from_template(&source.id, Path::new("/"), "text").await.unwrap();
+        assert_eq!(first, PathBuf::from("/Untitled.txt"));
+        assert_eq!(second, PathBuf::from("/Untitled 2.txt"));
+        assert!(temp_dir.path().join("Untitled 2.txt").exists());
+        
+        // User templates create files named after themselves
+        service.register_template("Call Sheet.md", b"# Call Sheet\n").await.unwrap();
+        let sheet = service.new_from_template(&source.id, Path::new("/"), "Call Sheet.md").await.unwrap();
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join(sheet.file_name().unwrap())).unwrap(), "# Call Sheet\n");
+        assert!(service.new_from_template(&source.id, Path::new("/"), "missing").await.is_err());
+    }
+    
+    /// **Feature**: Local listings are never served from memory, so changes made by other apps show up
+    #[tokio::test]
+    async fn feature_local_listing_sees_outside_changes() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::ListOptions;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Local".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        let options = ListOptions::default();
+        let names = |files: Vec<crate::vfs::domain::VirtualFile>| {
+            files.into_iter().map(|f| f.name).collect::<Vec<_>>()
+        };
+        
+        assert_eq!(names(service.list_files(&source.id, Path::new("/"), &options).await.unwrap()), vec!["a.txt"]);
+        
+        // A change behind the VFS's back is seen straight away
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        assert_eq!(names(service.list_files(&source.id, Path::new("/"), &options).await.unwrap()), vec!["a.txt", "b.txt"]);
+        
+        // As is a write through the VFS
+        service.write(&source.id, Path::new("/c.txt"), b"c").await.unwrap();
+        assert_eq!(
+            names(service.list_files(&source.id, Path::new("/"), &options).await.unwrap()),
+            vec!["a.txt", "b.txt", "c.txt"]
+        );
+    }
+    
+    /// **Feature**: Removing a source only unregisters it
+    #[tokio::test]
+    async fn feature_remove_source_leaves_files_intact() {
+        use crate::vfs::application::VfsService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("clip.mov"), b"footage").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Project".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        let transfer = service.transfers().register(&[source.id.as_str()], "Copy \"/clip.mov\"");
+        
+        let removed = service.remove_source(&source.id).unwrap();
+        assert_eq!(removed.id, source.id);
+        assert!(service.list_sources().iter().all(|s| s.id != source.id));
+        assert!(transfer.is_cancelled(), "transfers on the source should be cancelled");
+        
+        // The folder and its contents are untouched
+        assert!(temp_dir.path().is_dir());
+        assert_eq!(std::fs::read(temp_dir.path().join("clip.mov")).unwrap(), b"footage");
+        assert!(service.remove_source(&source.id).is_none());
+    }
+    
+    /// **Feature**: Ejecting a volume waits for transfers using it
+    #[tokio::test]
+    async fn feature_eject_refused_while_transfer_active() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::VolumeBusyError;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Card".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let transfer = service.transfers().register(&[source.id.as_str()], "Copy \"/A001.mov\"");
+        
+        let err = service.prepare_eject(&source.id, false).await.unwrap_err();
+        let err = err.downcast_ref::<VolumeBusyError>().expect("typed busy error");
+        assert_eq!(err.operations, vec!["Copy \"/A001.mov\"".to_string()]);
+        
+        // Once the transfer finishes the volume can go
+        drop(transfer);
+        service.prepare_eject(&source.id, false).await.unwrap();
+        
+        // Forcing cancels whatever is still running
+        let running = service.transfers().run(&[source.id.as_str()], "Hydrate", std::future::pending::<anyhow::Result<()>>());
+        let eject = async {
+            while service.transfers().active_for(&source.id).is_empty() {
+                tokio::task::yield_now().await;
+            }
+            service.prepare_eject(&source.id, true).await
+        };
+        let (transfer_result, eject_result) = tokio::join!(running, eject);
+        assert!(transfer_result.is_err());
+        eject_result.unwrap();
+    }
+    
+    /// **Feature**: Batch rename ingest footage from a template
+    #[tokio::test]
+    async fn feature_batch_rename_with_template() {
+        use crate::vfs::application::VfsService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["A001.mov", "A002.mov", "A003.mov"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+        std::fs::write(temp_dir.path().join("keep.mov"), "unrelated").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Ingest".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let paths: Vec<PathBuf> = ["/A001.mov", "/A002.mov", "/A003.mov"].iter().map(PathBuf::from).collect();
+        
+        // Collisions abort before anything is renamed
+        assert!(service.batch_rename(&source.id, &paths, "shot{ext}", 1).await.is_err());
+        assert!(service.batch_rename(&source.id, &paths[..1], "keep{ext}", 1).await.is_err());
+        assert!(temp_dir.path().join("A001.mov").exists());
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("keep.mov")).unwrap(), "unrelated");
+        
+        let mapping = service.batch_rename(&source.id, &paths, "shot_{index:02}{ext}", 1).await.unwrap();
+        let targets: Vec<_> = mapping.iter().map(|m| m.to.clone()).collect();
+        assert_eq!(targets, vec![
+            PathBuf::from("/shot_01.mov"),
+            PathBuf::from("/shot_02.mov"),
+            PathBuf::from("/shot_03.mov"),
+        ]);
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("shot_02.mov")).unwrap(), "A002.mov");
+        assert!(!temp_dir.path().join("A001.mov").exists());
+    }
+    
+    /// **Feature**: Undo puts a renamed file back under its old name; redo repeats it
+    #[tokio::test]
+    async fn feature_undo_rename_restores_name() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::operation_history::FileOperation;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("A001.mov"), "frames").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Edit".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        service.rename(&source.id, Path::new("/A001.mov"), Path::new("/hero_shot.mov")).await.unwrap();
+        assert!(temp_dir.path().join("hero_shot.mov").exists());
+        
+        let undone = service.undo().await.unwrap().expect("rename to undo");
+        assert_eq!(undone.operation, FileOperation::Rename {
+            from: PathBuf::from("/A001.mov"),
+            to: PathBuf::from("/hero_shot.mov"),
+        });
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("A001.mov")).unwrap(), "frames");
+        assert!(!temp_dir.path().join("hero_shot.mov").exists());
+        assert!(service.undo().await.unwrap().is_none());
+        
+        service.redo().await.unwrap().expect("rename to redo");
+        assert!(temp_dir.path().join("hero_shot.mov").exists());
+        
+        // A permanent delete can't be undone, and blocks undo past it
+        std::fs::write(temp_dir.path().join("scratch.txt"), "temp").unwrap();
+        service.rm(&source.id, Path::new("/scratch.txt")).await.unwrap();
+        assert!(service.undo().await.unwrap().is_none());
+        assert!(service.redo().await.unwrap().is_none());
+    }
+    
+    /// **Feature**: Undoing a move to the trash brings the file back out of it
+    #[tokio::test]
+    async fn feature_undo_trash_restores_from_trash() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::operation_history::FileOperation;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Day1")).unwrap();
+        std::fs::write(temp_dir.path().join("Day1/A002.mov"), "take two").unwrap();
+        
+        let trash_dir = TempDir::new().unwrap();
+        let mut service = VfsService::new().await.unwrap();
+        service.set_trash_dir(trash_dir.path().join("Trash"));
+        let source = service.add_local_source("Edit".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        service.trash(&source.id, Path::new("/Day1/A002.mov")).await.unwrap();
+        assert!(!temp_dir.path().join("Day1/A002.mov").exists());
+        assert!(trash_dir.path().join("Trash/files/A002.mov").exists());
+        
+        let undone = service.undo().await.unwrap().expect("trash to undo");
+        let FileOperation::Trash { path, trash_id } = undone.operation else {
+            panic!("expected a trash operation, got {:?}", undone.operation);
+        };
+        assert_eq!(path, PathBuf::from("/Day1/A002.mov"));
+        assert!(!Path::new(&trash_id).exists(), "Item should have left the trash");
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("Day1/A002.mov")).unwrap(), "take two");
+    }
+    
+    /// **Feature**: Selection info adds up selected files and folder contents
+    #[tokio::test]
+    async fn feature_selection_info_totals_files_and_folders() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::VfsError;
+        use tokio_util::sync::CancellationToken;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Day1/Audio")).unwrap();
+        std::fs::write(temp_dir.path().join("Day1/A001.mov"), vec![0u8; 1000]).unwrap();
+        std::fs::write(temp_dir.path().join("Day1/Audio/A001.wav"), vec![0u8; 200]).unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), vec![b'n'; 34]).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Edit".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        let selection = vec![PathBuf::from("/notes.txt"), PathBuf::from("/Day1")];
+        
+        let info = service.selection_info(&source.id, &selection, &CancellationToken::new(), &|_| {})
+            .await.unwrap();
+        assert_eq!(info.total_bytes, 1234);
+        assert_eq!(info.file_count, 3);
+        assert_eq!(info.dir_count, 2);
+        assert_eq!(info.human, "1.21 KB");
+        
+        // Local folder totals aren't remembered, so outside changes count straight away
+        std::fs::write(temp_dir.path().join("Day1/A002.mov"), vec![0u8; 66]).unwrap();
+        let fresh = service.selection_info(&source.id, &selection, &CancellationToken::new(), &|_| {})
+            .await.unwrap();
+        assert_eq!(fresh.total_bytes, 1300);
+        
+        // A cancelled measurement stops instead of walking the tree
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = service.selection_info(&source.id, &selection, &cancel, &|_| {}).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<VfsError>(), Some(VfsError::Cancelled(_))));
+    }
+    
+    /// **Feature**: Deletes are recorded in the audit log
+    #[tokio::test]
+    async fn feature_delete_writes_audit_entry() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::infrastructure::AuditOperation;
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("take3.mov"), "frames").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Edit".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        service.rm(&source.id, Path::new("/take3.mov")).await.unwrap();
+        
+        let entries = service.export_audit_log(None, None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].operation, AuditOperation::Delete);
+        assert_eq!(entries[0].source_id, source.id);
+        assert_eq!(entries[0].paths, vec![PathBuf::from("/take3.mov")]);
+        assert!(entries[0].success);
+    }
+    
+    /// **Feature**: Copies that won't fit on a local volume are refused up front
+    #[tokio::test]
+    async fn feature_copy_refused_when_destination_full() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::free_space::FREE_SPACE_MARGIN;
+        use crate::vfs::domain::InsufficientSpaceError;
+        
+        let archive = TempDir::new().unwrap();
+        let scratch = TempDir::new().unwrap();
+        std::fs::create_dir_all(archive.path().join("shoot")).unwrap();
+        std::fs::write(archive.path().join("shoot/A001.mov"), vec![0u8; 4096]).unwrap();
+        std::fs::write(archive.path().join("notes.txt"), vec![b'n'; 50]).unwrap();
+        
+        // Destination volume reports only 100 bytes beyond the safety margin
+        let mut service = VfsService::new().await.unwrap();
+        service.set_space_provider(std::sync::Arc::new(|_: &Path| Ok::<_, anyhow::Error>(FREE_SPACE_MARGIN + 100)));
+        let from = service.add_local_source("Archive".to_string(), archive.path().to_path_buf())
+            .await.unwrap();
+        let to = service.add_local_source("Scratch".to_string(), scratch.path().to_path_buf())
+            .await.unwrap();
+        
+        let err = service.copy_to_source(&from.id, Path::new("/shoot"), &to.id, Path::new("/"))
+            .await.unwrap_err();
+        let err = err.downcast_ref::<InsufficientSpaceError>().expect("typed space error");
+        assert_eq!(err.needed, 4096);
+        assert_eq!(err.available, 100);
+        assert!(!scratch.path().join("shoot").exists(), "Nothing should be created");
+        
+        // Small files still fit
+        let bytes = service.copy_to_source(&from.id, Path::new("/notes.txt"), &to.id, Path::new("/"))
+            .await.unwrap();
+        assert_eq!(bytes, 50);
+    }
+    
+    /// **Feature**: Name search is answered from the catalog index
+    #[tokio::test]
+    async fn feature_search_uses_catalog_index() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::catalog::IndexState;
+        
+        let shoot = TempDir::new().unwrap();
+        std::fs::create_dir_all(shoot.path().join("Day1/cards")).unwrap();
+        std::fs::write(shoot.path().join("Day1/A001_C002.mov"), "frames").unwrap();
+        std::fs::write(shoot.path().join("Day1/cards/A001_C003.mov"), "frames").unwrap();
+        std::fs::write(shoot.path().join("Day1/notes.txt"), "slate").unwrap();
+        
+        let service = std::sync::Arc::new(VfsService::new().await.unwrap());
+        let source = service.add_local_source("Shoot".to_string(), shoot.path().to_path_buf())
+            .await.unwrap();
+        assert_eq!(service.index_status(&source.id).unwrap().state, IndexState::NotIndexed);
+        
+        assert_eq!(service.index_source(&source.id).await.unwrap(), 5);
+        let status = service.index_status(&source.id).unwrap();
+        assert_eq!(status.state, IndexState::Ready);
+        assert_eq!(status.entries, 5);
+        
+        // Gone from disk behind the app's back: only the index still knows them
+        std::fs::remove_dir_all(shoot.path().join("Day1")).unwrap();
+        let results = service.search(&source.id, Path::new("/"), "a001", 10).await.unwrap();
+        let paths: Vec<_> = results.iter().map(|e| e.path.to_string_lossy().to_string()).collect();
+        assert_eq!(paths, vec!["/Day1/A001_C002.mov", "/Day1/cards/A001_C003.mov"]);
+        assert_eq!(results[0].extension.as_deref(), Some("mov"));
+        
+        // Sources without an index are crawled
+        let other = TempDir::new().unwrap();
+        std::fs::write(other.path().join("A001_C009.mov"), "frames").unwrap();
+        let unindexed = service.add_local_source("Other".to_string(), other.path().to_path_buf())
+            .await.unwrap();
+        let results = service.search(&unindexed.id, Path::new("/"), "c009", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+    }
+    
     // =========================================================================
     // FEATURE: NVMe Caching (Hydration)
     // Use Case: Cold file is warmed to local NVMe for fast access
@@ -223,6 +1052,7 @@ mod feature_tests {
             max_size: 10 * 1024 * 1024, // 10 MB
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -253,6 +1083,7 @@ mod feature_tests {
             max_size: 100, // Tiny 100-byte cache
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -280,6 +1111,7 @@ mod feature_tests {
             max_size: 10 * 1024 * 1024,
             eviction_policy: EvictionPolicy::LRU,
             nvme_optimized: false,
+            ..Default::default()
         };
         
         let cache = NvmeCacheAdapter::new(config).await.unwrap();
@@ -295,6 +1127,141 @@ mod feature_tests {
         assert_eq!(stats.entry_count, 1, "Should have 1 cached entry");
     }
     
+    /// **Feature**: Undo a delete before its cached copy is evicted
+    #[tokio::test]
+    async fn feature_cache_undo_delete_within_grace() {
+        use crate::vfs::adapters::NvmeCacheAdapter;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::{CacheConfig, EvictionPolicy};
+        use crate::vfs::ports::CacheAdapter;
+        use std::time::Duration;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let config = CacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 10 * 1024 * 1024,
+            eviction_policy: EvictionPolicy::LRU,
+            nvme_optimized: false,
+            delete_grace: Duration::from_millis(200),
+        };
+        let cache = NvmeCacheAdapter::new(config.clone()).await.unwrap();
+        let path = Path::new("/shoot/A001.mov");
+        
+        cache.cache_file(path, b"frames").await.unwrap();
+        let blob = cache.get_cached_path(path).await.unwrap();
+        
+        // Deleted, then undone within the window
+        cache.soft_delete(Path::new("/shoot")).await.unwrap();
+        assert!(!cache.is_cached(path).await);
+        assert!(blob.exists(), "Bytes are kept during the grace window");
+        assert_eq!(cache.undo_delete(path).await.unwrap(), 1);
+        assert!(cache.is_cached(path).await);
+        assert_eq!(cache.read_from_cache(path).await.unwrap(), b"frames");
+        
+        // Deleted again, and the window passes
+        cache.soft_delete(path).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(cache.undo_delete(path).await.unwrap(), 0);
+        assert!(!cache.is_cached(path).await);
+        assert!(!blob.exists(), "Bytes are freed after the grace window");
+        assert_eq!(cache.stats().await.total_size, 0);
+        
+        // Deleting through the service keeps the cached copy restorable
+        let source_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        std::fs::write(source_dir.path().join("notes.txt"), "call time").unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..config
+        }).await.unwrap();
+        let source = service.add_local_source("Docs".to_string(), source_dir.path().to_path_buf())
+            .await.unwrap();
+        service.read_file(&source.id, Path::new("/notes.txt")).await.unwrap();
+        service.rm(&source.id, Path::new("/notes.txt")).await.unwrap();
+        assert_eq!(service.cache_undo_delete(&source.id, Path::new("/notes.txt")).await.unwrap(), 1);
+    }
+    
+    /// **Feature**: Warm several files at once and follow each one's progress
+    #[tokio::test]
+    async fn feature_batch_warm_reports_status() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::{CacheConfig, WarmStatus};
+        use std::sync::Arc;
+        
+        let cache_dir = TempDir::new().unwrap();
+        let service = Arc::new(VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap());
+        let source = service.add_memory_source("Dailies".to_string()).await.unwrap();
+        
+        let paths: Vec<PathBuf> = ["/A001.mov", "/A002.mov", "/A003.mov"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        for path in &paths {
+            service.write(&source.id, path, b"frames").await.unwrap();
+            assert_eq!(service.warm_status(&source.id, path).await.unwrap(), WarmStatus::NotWarmed);
+        }
+        
+        let (queued, handle) = service.warm_batch(&source.id, paths.clone(), 2).await.unwrap();
+        assert_eq!(queued, 3);
+        
+        // Every queued file reports progress until it lands in the cache
+        for path in &paths {
+            match service.warm_status(&source.id, path).await.unwrap() {
+                WarmStatus::Warming { progress } => assert!((0.0..=1.0).contains(&progress)),
+                other => panic!("expected {:?} to be warming, got {:?}", path, other),
+            }
+        }
+        
+        assert_eq!(handle.await.unwrap(), 3);
+        for path in &paths {
+            assert_eq!(service.warm_status(&source.id, path).await.unwrap(), WarmStatus::Warmed);
+        }
+        
+        // Already-warm files aren't queued again
+        let (queued, _) = service.warm_batch(&source.id, paths, 2).await.unwrap();
+        assert_eq!(queued, 0);
+    }
+    
+    /// **Feature**: Scrub a large clip by caching only the ranges played
+    #[tokio::test]
+    async fn feature_range_hydration_without_full_warm() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::{CacheConfig, WarmStatus};
+        use crate::vfs::ports::CACHE_BLOCK_SIZE;
+        
+        let cache_dir = TempDir::new().unwrap();
+        let service = VfsService::with_cache_config(CacheConfig {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        }).await.unwrap();
+        let source = service.add_memory_source("Camera Originals".to_string()).await.unwrap();
+        
+        let block = CACHE_BLOCK_SIZE as usize;
+        let clip: Vec<u8> = (0..block * 4 + block / 2).map(|i| (i % 253) as u8).collect();
+        let path = Path::new("/A001.mov");
+        service.write(&source.id, path, &clip).await.unwrap();
+        
+        // Two ranges far apart: one in the first block, one in the short last block
+        let head = service.read_range(&source.id, path, 1000, 4096).await.unwrap();
+        assert_eq!(head, &clip[1000..5096]);
+        let tail_offset = 4 * CACHE_BLOCK_SIZE + 10;
+        let tail = service.read_range(&source.id, path, tail_offset, 1 << 30).await.unwrap();
+        assert_eq!(tail, &clip[tail_offset as usize..]);
+        
+        // Only the two blocks touched are cached, never the whole file
+        assert_eq!(service.cache_stats().await.total_size, CACHE_BLOCK_SIZE + CACHE_BLOCK_SIZE / 2);
+        assert_eq!(service.warm_status(&source.id, path).await.unwrap(), WarmStatus::NotWarmed);
+        
+        // Reading them again comes from the cache
+        let hits = service.cache_stats().await.hit_count;
+        assert_eq!(service.read_range(&source.id, path, 1000, 4096).await.unwrap(), head);
+        assert_eq!(service.cache_stats().await.hit_count, hits + 1);
+        assert_eq!(service.cache_stats().await.total_size, CACHE_BLOCK_SIZE + CACHE_BLOCK_SIZE / 2);
+    }
+    
     // =========================================================================
     // FEATURE: S3 Object Storage
     // Use Case: User accesses files in S3 buckets
@@ -389,71 +1356,530 @@ mod feature_tests {
         use crate::vfs::adapters::nas_storage::NasProtocol;
         use crate::vfs::ports::StorageAdapter;
         
-        let temp_dir = TempDir::new().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        
+        let adapter = NasStorageAdapter::new(
+            temp_dir.path().to_path_buf(),
+            "Media NAS".to_string(),
+            NasProtocol::NFS,
+            Some("nas.local".to_string()),
+        );
+        
+        assert!(adapter.test_connection().await.unwrap());
+        assert_eq!(adapter.name(), "Media NAS");
+    }
+    
+    /// **Feature**: Mount any backend from one typed config
+    #[tokio::test]
+    async fn feature_mount_source_from_config() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::{MountConfig, StorageSourceType, VfsError};
+        
+        let service = VfsService::new().await.unwrap();
+        
+        // An S3 config without a bucket is rejected before anything is registered
+        let s3: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "name": "Footage",
+            "region": "us-east-1",
+        })).unwrap();
+        let err = VfsError::from(service.mount_source(s3).await.unwrap_err());
+        assert_eq!(err.code(), "INVALID_INPUT");
+        assert!(err.message().contains("bucket"));
+        assert!(service.list_sources().is_empty());
+        
+        // A local folder that doesn't exist fails the connection test
+        let missing = MountConfig::Local {
+            name: "Gone".to_string(),
+            path: PathBuf::from("/non/existent/path"),
+            read_only: false,
+            quota_bytes: None,
+        };
+        assert!(service.mount_source(missing).await.is_err());
+        
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("clip.mov"), "frames").unwrap();
+        let local: MountConfig = serde_json::from_value(serde_json::json!({
+            "type": "local",
+            "name": "Project",
+            "path": temp_dir.path(),
+            "readOnly": true,
+        })).unwrap();
+        let source = service.mount_source(local).await.unwrap();
+        
+        assert_eq!(source.source_type, StorageSourceType::Local);
+        assert!(source.config.read_only);
+        assert_eq!(service.list_sources().len(), 1);
+        assert!(service.exists(&source.id, Path::new("/clip.mov")).await.unwrap());
+    }
+    
+    /// **Feature**: Test a mount config before connecting it
+    #[tokio::test]
+    async fn feature_test_connection_without_mounting() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::MountConfig;
+        
+        let service = VfsService::new().await.unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        
+        let existing = MountConfig::Local {
+            name: "Project".to_string(),
+            path: temp_dir.path().to_path_buf(),
+            read_only: false,
+            quota_bytes: None,
+        };
+        let result = service.test_mount_config(existing).await;
+        assert!(result.ok, "{:?}", result.error);
+        assert!(result.error.is_none());
+        
+        let missing = MountConfig::Local {
+            name: "Gone".to_string(),
+            path: PathBuf::from("/non/existent/path"),
+            read_only: false,
+            quota_bytes: None,
+        };
+        let result = service.test_mount_config(missing).await;
+        assert!(!result.ok);
+        assert!(result.error.unwrap().contains("Gone"));
+        
+        // Neither test registered a source
+        assert!(service.list_sources().is_empty());
+    }
+    
+    /// **Feature**: Mounted sources come back after a restart with the same id
+    #[tokio::test]
+    async fn feature_mounted_sources_restored_after_restart() {
+        use crate::vfs::adapters::SourcesStore;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::vfs_service::UserStores;
+        use crate::vfs::domain::{CacheConfig, MountConfig};
+        
+        let store_dir = TempDir::new().unwrap();
+        let store_path = store_dir.path().join("sources.json");
+        let project = TempDir::new().unwrap();
+        std::fs::write(project.path().join("edit.prproj"), "timeline").unwrap();
+        
+        let source_id = {
+            let stores = UserStores::in_dir(store_dir.path()).await.unwrap();
+            let service = VfsService::with_stores(CacheConfig::default(), stores).await.unwrap();
+            let config = MountConfig::Local {
+                name: "Project".to_string(),
+                path: project.path().to_path_buf(),
+                read_only: false,
+                quota_bytes: None,
+            };
+            let source_id = service.mount_source(config).await.unwrap().id;
+            service.set_quota(&source_id, Some(1024)).await.unwrap();
+            source_id
+        };
+        
+        // A fresh service (next launch) re-mounts it from the saved config
+        let stores = UserStores::in_dir(store_dir.path()).await.unwrap();
+        let service = VfsService::with_stores(CacheConfig::default(), stores).await.unwrap();
+        assert!(service.get_source(&source_id).is_none());
+        
+        let restored = service.restore_sources().await;
+        assert_eq!(restored.len(), 1);
+        let source = service.get_source(&source_id).unwrap();
+        assert_eq!(source.name, "Project");
+        assert_eq!(source.config.quota_bytes, Some(1024));
+        assert!(service.exists(&source_id, Path::new("/edit.prproj")).await.unwrap());
+        
+        // Forgotten sources stay mounted now but aren't restored next time
+        assert!(service.forget_source(&source_id).await.unwrap());
+        assert!(service.get_source(&source_id).is_some());
+        let next_launch = SourcesStore::new(store_path).await.unwrap();
+        assert!(next_launch.list().is_empty());
+    }
+    
+    /// **Feature**: Renaming a source changes only its display name
+    #[tokio::test]
+    async fn feature_rename_source_keeps_id_and_mount_point() {
+        use crate::vfs::adapters::SourcesStore;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::vfs_service::UserStores;
+        use crate::vfs::domain::{CacheConfig, MountConfig, VfsError};
+        
+        let store_dir = TempDir::new().unwrap();
+        let store_path = store_dir.path().join("sources.json");
+        let footage = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        
+        let stores = UserStores::in_dir(store_dir.path()).await.unwrap();
+        let service = VfsService::with_stores(CacheConfig::default(), stores).await.unwrap();
+        let mount = |name: &str, path: &Path| MountConfig::Local {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            read_only: false,
+            quota_bytes: None,
+        };
+        let source = service.mount_source(mount("production-footage", footage.path())).await.unwrap();
+        service.mount_source(mount("Archive", other.path())).await.unwrap();
+        
+        service.rename_source(&source.id, "Project X").await.unwrap();
+        
+        let listed = service.list_sources().into_iter().find(|s| s.id == source.id).unwrap();
+        assert_eq!(listed.name, "Project X");
+        assert_eq!(listed.mount_point, source.mount_point);
+        assert!(footage.path().exists());
+        
+        // The next launch shows the new name too
+        let next_launch = SourcesStore::new(store_path).await.unwrap();
+        let persisted = next_launch.list().into_iter().find(|s| s.id == source.id).unwrap();
+        assert_eq!(persisted.config.name(), "Project X");
+        
+        let empty = service.rename_source(&source.id, "  ").await.unwrap_err();
+        assert!(matches!(empty.downcast_ref::<VfsError>(), Some(VfsError::InvalidInput(_))));
+        let taken = service.rename_source(&source.id, "archive").await.unwrap_err();
+        assert!(matches!(taken.downcast_ref::<VfsError>(), Some(VfsError::AlreadyExists(_))));
+        assert_eq!(service.get_source(&source.id).unwrap().name, "Project X");
+    }
+    
+    /// **Feature**: Mount secrets go to the keychain, not the sources file
+    #[tokio::test]
+    async fn feature_mount_secrets_kept_in_credential_store() {
+        use crate::vfs::adapters::{MemoryCredentialStore, DEFAULT_CREDENTIAL_SERVICE};
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::vfs_service::UserStores;
+        use crate::vfs::domain::{CacheConfig, MountConfig, RetryPolicy};
+        use crate::vfs::ports::CredentialStore;
+        use std::sync::Arc;
+        
+        let store_dir = TempDir::new().unwrap();
+        let store_path = store_dir.path().join("sources.json");
+        let share = TempDir::new().unwrap();
+        let credentials = Arc::new(MemoryCredentialStore::new());
+        
+        let stores = UserStores { credentials: credentials.clone(), ..UserStores::in_dir(store_dir.path()).await.unwrap() };
+        let service = VfsService::with_stores(CacheConfig::default(), stores).await.unwrap();
+        
+        let config = MountConfig::Nas {
+            name: "Edit Share".to_string(),
+            host: "nas.local".to_string(),
+            share: "projects".to_string(),
+            protocol: "smb".to_string(),
+            mount_point: share.path().to_path_buf(),
+            username: Some("editor".to_string()),
+            password: Some("hunter2".to_string()),
+            credential: None,
+            read_only: false,
+            quota_bytes: None,
+            retry_policy: None,
+        };
+        let source = service.mount_source(config).await.unwrap();
+        
+        // Only a reference to the password is written to disk
+        let written = std::fs::read_to_string(&store_path).unwrap();
+        assert!(!written.contains("hunter2"));
+        assert!(written.contains(DEFAULT_CREDENTIAL_SERVICE));
+        assert_eq!(
+            credentials.retrieve(DEFAULT_CREDENTIAL_SERVICE, &source.id).unwrap().as_deref(),
+            Some("hunter2")
+        );
+        let policy = RetryPolicy { max_attempts: 6, ..RetryPolicy::default() };
+        service.set_retry_policy(&source.id, policy).await.unwrap();
+        
+        // The next launch reads it back from the credential store
+        let stores = UserStores { credentials: credentials.clone(), ..UserStores::in_dir(store_dir.path()).await.unwrap() };
+        let next_launch = VfsService::with_stores(CacheConfig::default(), stores).await.unwrap();
+        next_launch.restore_sources().await;
+        let restored = next_launch.get_source(&source.id).unwrap();
+        assert_eq!(restored.config.secret_key.as_deref(), Some("hunter2"));
+        assert_eq!(restored.config.retry_policy, Some(policy));
+        
+        // Forgetting the source deletes the secret saved for it
+        assert!(next_launch.forget_source(&source.id).await.unwrap());
+        assert_eq!(credentials.retrieve(DEFAULT_CREDENTIAL_SERVICE, &source.id).unwrap(), None);
+    }
+    
+    // =========================================================================
+    // FEATURE: Media Processing (FFmpeg)
+    // Use Case: User sees video thumbnails in file browser
+    // =========================================================================
+    
+    /// **Feature**: Check FFmpeg availability for thumbnail generation
+    #[tokio::test]
+    async fn feature_ffmpeg_availability_check() {
+        use crate::vfs::adapters::FfmpegMediaAdapter;
+        use crate::vfs::ports::IMediaService;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = FfmpegMediaAdapter::new(temp_dir.path().to_path_buf()).await.unwrap();
+        
+        // Returns true if FFmpeg is installed, false otherwise
+        let available = adapter.is_available();
+        println!("FFmpeg available: {}", available);
+    }
+    
+    /// **Feature**: Transcode quality presets exist
+    #[test]
+    fn feature_transcode_quality_presets() {
+        use crate::vfs::ports::TranscodeQuality;
+        
+        // All quality presets
+        let presets = vec![
+            TranscodeQuality::Low,      // 480p - fast previews
+            TranscodeQuality::Medium,   // 720p - web delivery
+            TranscodeQuality::High,     // 1080p - production
+            TranscodeQuality::Ultra,    // 4K - high-end
+            TranscodeQuality::Adaptive, // Multi-bitrate HLS
+        ];
+        
+        assert_eq!(presets.len(), 5, "Should have 5 quality presets");
+    }
+    
+    /// **Feature**: HLS streaming server generates valid URLs
+    #[test]
+    fn feature_hls_server_generates_stream_urls() {
+        use crate::vfs::infrastructure::hls_server::HlsServerConfig;
+        
+        let config = HlsServerConfig {
+            port: 8080,
+            content_dir: PathBuf::from("/tmp/hls"),
+        };
+        
+        // Generate stream URL format
+        let job_id = "abc123";
+        let url = format!("http://127.0.0.1:{}/stream/{}/playlist.m3u8", config.port, job_id);
+        
+        assert!(url.starts_with("http://127.0.0.1:8080"));
+        assert!(url.ends_with(".m3u8"), "HLS URLs should end with .m3u8");
+    }
+    
+    /// **Feature**: Editing proxies are written beside originals and linked to them
+    #[tokio::test]
+    async fn feature_create_proxies_links_proxy_to_original() {
+        use crate::vfs::adapters::JsonMetadataStore;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::application::transcode_queue::TranscodeQueue;
+        use crate::vfs::ports::{IMetadataStore, StreamFormat, TranscodeExecutor, TranscodeQuality};
+        use std::sync::Arc;
+        
+        /// Stands in for FFmpeg: the "proxy" is the clip's bytes behind a marker
+        struct FakeProxyExecutor;
+        
+        #[async_trait::async_trait]
+        impl TranscodeExecutor for FakeProxyExecutor {
+            async fn execute(&self, _: &str, source: &Path, output_dir: &Path, format: StreamFormat, _: TranscodeQuality) -> anyhow::Result<PathBuf> {
+                assert_eq!(format, StreamFormat::MP4);
+                std::fs::create_dir_all(output_dir)?;
+                let output = output_dir.join(source.with_extension("mp4").file_name().unwrap());
+                let mut data = b"proxy:".to_vec();
+                data.extend(std::fs::read(source)?);
+                std::fs::write(&output, data)?;
+                Ok(output)
+            }
+            
+            async fn cancel(&self, _: &str) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+        
+        let shoot = TempDir::new().unwrap();
+        let scratch = TempDir::new().unwrap();
+        std::fs::create_dir_all(shoot.path().join("Day1")).unwrap();
+        std::fs::write(shoot.path().join("Day1/A001.mov"), "camera original").unwrap();
+        std::fs::write(shoot.path().join("Day1/A002.mov"), "no proxy yet").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let metadata: Arc<dyn IMetadataStore> = Arc::new(
+            JsonMetadataStore::new(scratch.path().join("metadata.json")).await.unwrap()
+        );
+        service.set_metadata_store(metadata.clone());
+        let source = service.add_local_source("Shoot".to_string(), shoot.path().to_path_buf())
+            .await.unwrap();
+        let queue = Arc::new(TranscodeQueue::new(Arc::new(FakeProxyExecutor), scratch.path().join("transcodes"), 1));
+        
+        let original = PathBuf::from("/Day1/A001.mov");
+        let report = service.create_proxies(&source.id, &[original.clone()], TranscodeQuality::High, &queue, &|_, _| {})
+            .await.unwrap();
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        
+        let proxy = PathBuf::from("/Day1/Proxies/A001_Proxy.mp4");
+        assert_eq!(report.created[0].proxy, proxy);
+        assert_eq!(
+            std::fs::read_to_string(shoot.path().join("Day1/Proxies/A001_Proxy.mp4")).unwrap(),
+            "proxy:camera original"
+        );
+        
+        let linked = metadata.get(&source.id, &original).await.unwrap().unwrap();
+        assert_eq!(linked.proxy.as_ref(), Some(&proxy));
+        let linked = metadata.get(&source.id, &proxy).await.unwrap().unwrap();
+        assert_eq!(linked.proxy_of.as_ref(), Some(&original));
+        
+        assert_eq!(service.get_proxy(&source.id, &original).await.unwrap(), Some(proxy));
+        assert_eq!(service.get_proxy(&source.id, Path::new("/Day1/A002.mov")).await.unwrap(), None);
+    }
+    
+    /// **Feature**: Files with a meaningless extension are typed by content
+    #[tokio::test]
+    async fn feature_mime_detected_by_content() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::ListOptions;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let mut png = Vec::new();
+        image::RgbImage::new(2, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        std::fs::write(temp_dir.path().join("still.dat"), &png).unwrap();
+        std::fs::write(temp_dir.path().join("notes.dat"), "not an image").unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Stills".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let files = service.list_files(&source.id, Path::new("/"), &ListOptions::default()).await.unwrap();
+        let content_type = |name: &str| files.iter().find(|f| f.name == name).unwrap().content_type.clone();
+        assert_eq!(content_type("still.dat").as_deref(), Some("image/png"));
+        assert_eq!(content_type("notes.dat").as_deref(), Some("application/octet-stream"));
+    }
+    
+    /// **Feature**: Get Info combines user metadata with probed media details
+    #[tokio::test]
+    async fn feature_get_info_tagged_clip() {
+        use crate::vfs::adapters::JsonMetadataStore;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::FileTag;
+        use crate::vfs::ports::{
+            IMediaService, IMetadataStore, MediaInfo, StreamFormat, ThumbnailData, TranscodeJob, TranscodeQuality,
+        };
+        use std::sync::Arc;
+        
+        /// Stands in for FFprobe: every file is a 12.5 second clip, and
+        /// anything other than probing fails
+        struct FakeProbe;
+        
+        #[async_trait::async_trait]
+        impl IMediaService for FakeProbe {
+            async fn get_media_info(&self, path: &Path) -> anyhow::Result<MediaInfo> {
+                Ok(MediaInfo { path: path.to_path_buf(), duration: Some(12.5), ..Default::default() })
+            }
+            async fn generate_thumbnail(&self, _: &Path, _: f64) -> anyhow::Result<ThumbnailData> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn generate_thumbnails(&self, _: &Path, _: u8) -> anyhow::Result<Vec<ThumbnailData>> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn generate_thumbnail_sheet(&self, _: &Path, _: u8, _: u8, _: u32) -> anyhow::Result<Vec<u8>> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn transcode(&self, _: &Path, _: StreamFormat, _: TranscodeQuality) -> anyhow::Result<TranscodeJob> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn get_transcode_status(&self, _: &str) -> anyhow::Result<TranscodeJob> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn cancel_transcode(&self, _: &str) -> anyhow::Result<()> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            async fn get_stream_url(&self, _: &Path, _: StreamFormat) -> anyhow::Result<Option<String>> {
+                anyhow::bail!("FakeProbe only probes")
+            }
+            fn is_available(&self) -> bool {
+                true
+            }
+        }
+        
+        let shoot = TempDir::new().unwrap();
+        let scratch = TempDir::new().unwrap();
+        std::fs::create_dir_all(shoot.path().join("Day1")).unwrap();
+        std::fs::write(shoot.path().join("Day1/A001.mov"), "camera original").unwrap();
+        std::fs::write(shoot.path().join("Day1/notes.txt"), "good take").unwrap();
         
-        let adapter = NasStorageAdapter::new(
-            temp_dir.path().to_path_buf(),
-            "Media NAS".to_string(),
-            NasProtocol::NFS,
-            Some("nas.local".to_string()),
+        let service = VfsService::new().await.unwrap();
+        let metadata: Arc<dyn IMetadataStore> = Arc::new(
+            JsonMetadataStore::new(scratch.path().join("metadata.json")).await.unwrap()
         );
-        
-        assert!(adapter.test_connection().await.unwrap());
-        assert_eq!(adapter.name(), "Media NAS");
-    }
-    
-    // =========================================================================
-    // FEATURE: Media Processing (FFmpeg)
-    // Use Case: User sees video thumbnails in file browser
-    // =========================================================================
-    
-    /// **Feature**: Check FFmpeg availability for thumbnail generation
+        service.set_metadata_store(metadata.clone());
+        let source = service.add_local_source("Shoot".to_string(), shoot.path().to_path_buf())
+            .await.unwrap();
+        metadata.add_tag(&source.id, Path::new("/Day1/A001.mov"), FileTag::new("selects")).await.unwrap();
+        
+        let info = service.get_info(&source.id, Path::new("/Day1/A001.mov"), Some(&FakeProbe)).await.unwrap();
+        assert_eq!(info.name, "A001.mov");
+        assert_eq!(info.size, 15);
+        assert_eq!(info.mime_type.as_deref(), Some("video/quicktime"));
+        assert_eq!(info.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["selects"]);
+        let media = info.media.expect("clip should be probed");
+        assert_eq!(media.duration, Some(12.5));
+        assert_eq!(media.path, PathBuf::from("/Day1/A001.mov"));
+        assert!(info.contents.is_none());
+        
+        // Folders carry recursive totals instead of media details
+        let folder = service.get_info(&source.id, Path::new("/Day1"), Some(&FakeProbe)).await.unwrap();
+        assert!(folder.is_dir && folder.media.is_none());
+        let contents = folder.contents.unwrap();
+        assert_eq!((contents.file_count, contents.dir_count, contents.total_bytes), (2, 0, 24));
+    }
+    
+    /// **Feature**: Previewing a large text file reads only the first bytes
     #[tokio::test]
-    async fn feature_ffmpeg_availability_check() {
-        use crate::vfs::adapters::FfmpegMediaAdapter;
-        use crate::vfs::ports::IMediaService;
+    async fn feature_preview_truncates_large_text() {
+        use crate::vfs::application::preview::PreviewKind;
+        use crate::vfs::application::VfsService;
+        use crate::vfs::domain::WarmStatus;
         
         let temp_dir = TempDir::new().unwrap();
-        let adapter = FfmpegMediaAdapter::new(temp_dir.path().to_path_buf()).await.unwrap();
-        
-        // Returns true if FFmpeg is installed, false otherwise
-        let available = adapter.is_available();
-        println!("FFmpeg available: {}", available);
-    }
-    
-    /// **Feature**: Transcode quality presets exist
-    #[test]
-    fn feature_transcode_quality_presets() {
-        use crate::vfs::ports::TranscodeQuality;
+        let log = "frame dropped at 01:02:03:04\n".repeat(10_000);
+        std::fs::write(temp_dir.path().join("render.log"), &log).unwrap();
         
-        // All quality presets
-        let presets = vec![
-            TranscodeQuality::Low,      // 480p - fast previews
-            TranscodeQuality::Medium,   // 720p - web delivery
-            TranscodeQuality::High,     // 1080p - production
-            TranscodeQuality::Ultra,    // 4K - high-end
-            TranscodeQuality::Adaptive, // Multi-bitrate HLS
-        ];
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Renders".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
         
-        assert_eq!(presets.len(), 5, "Should have 5 quality presets");
+        let preview = service.preview(&source.id, Path::new("/render.log"), 4096).await.unwrap();
+        assert_eq!(preview.kind, PreviewKind::Text);
+        assert_eq!(preview.data, log.as_bytes()[..4096]);
+        assert_eq!(preview.size, log.len() as u64);
+        assert!(preview.truncated);
+        assert!(matches!(
+            service.warm_status(&source.id, Path::new("/render.log")).await.unwrap(),
+            WarmStatus::NotWarmed
+        ));
     }
     
-    /// **Feature**: HLS streaming server generates valid URLs
-    #[test]
-    fn feature_hls_server_generates_stream_urls() {
-        use crate::vfs::infrastructure::hls_server::HlsServerConfig;
+    /// **Feature**: Following a log delivers only the lines appended after it starts
+    #[tokio::test]
+    async fn feature_tail_follow_delivers_appended_lines() {
+        use crate::vfs::application::VfsService;
+        use std::io::Write;
+        use std::sync::Arc;
+        use std::time::Duration;
         
-        let config = HlsServerConfig {
-            port: 8080,
-            content_dir: PathBuf::from("/tmp/hls"),
-        };
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("ingest.log");
+        std::fs::write(&log_path, "started\nwatching /Volumes/CARD_A\n").unwrap();
         
-        // Generate stream URL format
-        let job_id = "abc123";
-        let url = format!("http://127.0.0.1:{}/stream/{}/playlist.m3u8", config.port, job_id);
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Logs".to_string(), temp_dir.path().to_path_buf())
+            .await.unwrap();
+        let path = Path::new("/ingest.log");
+        assert_eq!(service.tail(&source.id, path, 1).await.unwrap(), vec!["watching /Volumes/CARD_A"]);
+        
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let follow = service.follow_tail(&source.id, path, Duration::from_millis(20), cancel.clone(), Arc::new(move |lines| {
+            sender.send(lines).is_ok()
+        })).await.unwrap();
+        
+        let mut log = std::fs::OpenOptions::new().append(true).open(&log_path).unwrap();
+        write!(log, "copied A001.mov\ncopied A0").unwrap();
+        log.flush().unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        write!(log, "02.mov\n").unwrap();
+        log.flush().unwrap();
+        
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let lines = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+            received.extend(lines);
+        }
+        assert_eq!(received, vec!["copied A001.mov", "copied A002.mov"]);
         
-        assert!(url.starts_with("http://127.0.0.1:8080"));
-        assert!(url.ends_with(".m3u8"), "HLS URLs should end with .m3u8");
+        cancel.cancel();
+        follow.await.unwrap();
+        assert!(receiver.try_recv().is_err());
     }
     
     // =========================================================================
@@ -465,6 +1891,7 @@ mod feature_tests {
     #[tokio::test]
     async fn feature_vfs_service_adds_local_storage() {
         use crate::vfs::application::VfsService;
+        use crate::vfs::ports::ListOptions;
         
         let temp_dir = TempDir::new().unwrap();
         std::fs::write(temp_dir.path().join("test.txt"), "hello").unwrap();
@@ -480,7 +1907,7 @@ mod feature_tests {
         assert_eq!(source.name, "Test Source");
         
         // List files through service abstraction
-        let files = service.list_files(&source.id, std::path::Path::new("/")).await.unwrap();
+        let files = service.list_files(&source.id, std::path::Path::new("/"), &ListOptions::default()).await.unwrap();
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].name, "test.txt");
     }
@@ -512,6 +1939,34 @@ mod feature_tests {
         assert!(!opts.overwrite, "Should NOT overwrite by default");
     }
     
+    /// **Feature**: Folders on a saved auto-mount list are mounted at startup
+    #[tokio::test]
+    async fn feature_automount_custom_folder() {
+        use crate::vfs::adapters::{AutomountEntry, AutomountStore};
+        use crate::vfs::application::VfsService;
+        
+        let config_dir = TempDir::new().unwrap();
+        let projects = TempDir::new().unwrap();
+        let store_path = config_dir.path().join("automount.json");
+        
+        let store = AutomountStore::new(store_path.clone()).await.unwrap();
+        store.set_entries(vec![
+            AutomountEntry::new("Projects", projects.path().to_path_buf()),
+            AutomountEntry::new("Unplugged", projects.path().join("missing")),
+        ]).await.unwrap();
+        
+        // As at the next launch
+        let store = AutomountStore::new(store_path).await.unwrap();
+        let service = VfsService::new().await.unwrap();
+        let mounted = service.mount_automounts(&store.entries()).await;
+        
+        assert_eq!(mounted.len(), 1);
+        let sources = service.list_sources();
+        let projects_source = sources.iter().find(|s| s.name == "Projects").expect("Projects mounted");
+        assert_eq!(projects_source.mount_point.as_deref(), Some(projects.path()));
+        assert!(!sources.iter().any(|s| s.name == "Unplugged"));
+    }
+    
     // =========================================================================
     // FEATURE: Clipboard Operations (Copy/Paste between Native and VFS)
     // Use Case: User copies files in Finder, pastes into VFS (and vice versa)
@@ -862,6 +2317,45 @@ mod feature_tests {
         assert!(source_file.exists(), "Original file should still exist");
     }
     
+    /// **Feature**: Throttled transfers don't exceed the bandwidth limit
+    #[tokio::test]
+    async fn feature_throttled_copy_respects_rate_limit() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::CrossStorageOptions;
+        use std::time::{Duration, Instant};
+        
+        let source_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+        
+        // 96 KiB at 64 KiB/s should take at least ~1.5s
+        let payload = vec![42u8; 96 * 1024];
+        std::fs::write(source_dir.path().join("clip.mov"), &payload).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Source".to_string(), source_dir.path().to_path_buf())
+            .await.unwrap();
+        let dest = service.add_local_source("Destination".to_string(), dest_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let options = CrossStorageOptions {
+            rate_limit_bytes_per_sec: Some(64 * 1024),
+            ..CrossStorageOptions::copy()
+        };
+        
+        let start = Instant::now();
+        let bytes = service.copy_to_source_with(
+            &source.id,
+            Path::new("/clip.mov"),
+            &dest.id,
+            Path::new("/"),
+            &options,
+        ).await.unwrap();
+        
+        assert_eq!(bytes, payload.len() as u64);
+        assert!(start.elapsed() >= Duration::from_millis(1400), "Copy finished too fast: {:?}", start.elapsed());
+        assert_eq!(std::fs::read(dest_dir.path().join("clip.mov")).unwrap(), payload);
+    }
+    
     /// **Feature**: Move file from one storage source to another (deletes source)
     #[tokio::test]
     async fn feature_move_between_storage_sources() {
@@ -894,6 +2388,214 @@ mod feature_tests {
         assert!(!source_dir.path().join("move_me.txt").exists(), "Source should be deleted");
     }
     
+    /// Path-style bucket `name` on the test server at `endpoint`
+    fn test_bucket(name: &str, endpoint: &str) -> crate::vfs::domain::MountConfig {
+        crate::vfs::domain::MountConfig::S3 {
+            name: name.to_string(),
+            bucket: name.to_string(),
+            region: Some("us-east-1".to_string()),
+            access_key_id: Some("AKIAEXAMPLEKEY".to_string()),
+            secret_access_key: Some("example-secret".to_string()),
+            credential: None,
+            endpoint: Some(endpoint.to_string()),
+            path_style: Some(true),
+            sse: None,
+            require_encryption: false,
+            transfer_tuning: None,
+            flat_listing: false,
+            read_only: false,
+            quota_bytes: None,
+            lifecycle_policy: None,
+            retry_policy: None,
+        }
+    }
+    
+    /// **Feature**: Moving between buckets of one S3 account copies server-side
+    #[tokio::test]
+    async fn feature_same_account_s3_move_copies_server_side() {
+        use crate::vfs::application::VfsService;
+        use parking_lot::Mutex;
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        // Stands in for S3: `a.mov` (5 bytes) exists, every other listing is
+        // empty, and each request's line and headers are recorded
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let head = String::from_utf8_lossy(&head).to_lowercase();
+                    recorded.lock().push(head.clone());
+                    
+                    let (status, headers, body) = if head.starts_with("head ") {
+                        ("200 OK", "Content-Length: 5\r\nLast-Modified: Fri, 17 May 2024 14:03:09 GMT\r\n", "")
+                    } else if head.starts_with("delete ") {
+                        ("204 No Content", "", "")
+                    } else if head.starts_with("put ") {
+                        ("200 OK", "", "<CopyObjectResult><ETag>&quot;9b2cf535&quot;</ETag></CopyObjectResult>")
+                    } else {
+                        ("200 OK", "", concat!(
+                            "<ListBucketResult><Name>media</Name><KeyCount>0</KeyCount>",
+                            "<IsTruncated>false</IsTruncated></ListBucketResult>",
+                        ))
+                    };
+                    let response = if head.starts_with("head ") {
+                        format!("HTTP/1.1 {}\r\n{}Connection: close\r\n\r\n", status, headers)
+                    } else {
+                        format!(
+                            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            status, headers, body.len(), body
+                        )
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        
+        let service = VfsService::new().await.unwrap();
+        let add_bucket = |bucket: &str| service.add_s3_source(test_bucket(bucket, &endpoint));
+        let media = add_bucket("media").await.unwrap();
+        let archive = add_bucket("archive").await.unwrap();
+        requests.lock().clear();
+        
+        let bytes = service.move_to_source(&media.id, Path::new("/clips/a.mov"), &archive.id, Path::new("/clips"))
+            .await.unwrap();
+        assert_eq!(bytes, 5);
+        
+        let requests = requests.lock();
+        let copy = requests.iter()
+            .find(|r| r.starts_with("put /archive/clips/a.mov"))
+            .expect("a CopyObject into the destination bucket");
+        assert!(copy.contains("x-amz-copy-source: /media/clips/a.mov"), "not a CopyObject: {}", copy);
+        assert!(requests.iter().any(|r| r.starts_with("delete /media/clips/a.mov")), "original not deleted");
+        assert!(
+            !requests.iter().any(|r| r.starts_with("get /media/clips/a.mov")),
+            "object was downloaded: {:?}", requests
+        );
+        assert_eq!(requests.iter().filter(|r| r.starts_with("put ")).count(), 1, "object was re-uploaded");
+    }
+    
+    /// **Feature**: One export delivered to several destinations in one call
+    #[tokio::test]
+    async fn feature_copy_to_multiple_destinations() {
+        use crate::vfs::application::VfsService;
+        
+        let export_dir = TempDir::new().unwrap();
+        let nas_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+        let master: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(export_dir.path().join("master.mov"), &master).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let export = service.add_local_source("Export".to_string(), export_dir.path().to_path_buf()).await.unwrap();
+        let nas = service.add_local_source("NAS".to_string(), nas_dir.path().to_path_buf()).await.unwrap();
+        let backup = service.add_local_source("Backup".to_string(), backup_dir.path().to_path_buf()).await.unwrap();
+        
+        let destinations = vec![
+            (nas.id.clone(), PathBuf::from("/")),
+            ("offline".to_string(), PathBuf::from("/")),
+            (backup.id.clone(), PathBuf::from("/")),
+        ];
+        let results = service.copy_to_multiple(&export.id, Path::new("/master.mov"), &destinations)
+            .await.unwrap();
+        
+        assert_eq!(results.len(), 3);
+        assert_eq!((results[0].bytes, results[0].error.as_deref()), (master.len() as u64, None));
+        assert!(results[1].error.is_some());
+        assert_eq!((results[2].bytes, results[2].error.as_deref()), (master.len() as u64, None));
+        assert_eq!(std::fs::read(nas_dir.path().join("master.mov")).unwrap(), master);
+        assert_eq!(std::fs::read(backup_dir.path().join("master.mov")).unwrap(), master);
+    }
+    
+    /// **Feature**: A fan-out copy downloads its source once, however many
+    /// destinations it has
+    #[tokio::test]
+    async fn feature_copy_to_multiple_reads_source_once() {
+        use crate::vfs::application::VfsService;
+        use parking_lot::Mutex;
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        // Stands in for S3: `master.mov` (6 bytes) exists, every listing is
+        // empty, and each request line is recorded
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let head = String::from_utf8_lossy(&head).to_lowercase();
+                    recorded.lock().push(head.lines().next().unwrap_or_default().to_string());
+                    
+                    let response = if head.starts_with("head /media/master.mov") {
+                        concat!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n",
+                            "Last-Modified: Fri, 17 May 2024 14:03:09 GMT\r\nConnection: close\r\n\r\n",
+                        ).to_string()
+                    } else if head.starts_with("get /media/master.mov") {
+                        concat!(
+                            "HTTP/1.1 206 Partial Content\r\nContent-Length: 6\r\n",
+                            "Content-Range: bytes 0-5/6\r\nConnection: close\r\n\r\nmaster",
+                        ).to_string()
+                    } else {
+                        let body = concat!(
+                            "<ListBucketResult><Name>media</Name><KeyCount>0</KeyCount>",
+                            "<IsTruncated>false</IsTruncated></ListBucketResult>",
+                        );
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(), body
+                        )
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        
+        let nas_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+        let service = VfsService::new().await.unwrap();
+        let media = service.add_s3_source(test_bucket("media", &endpoint)).await.unwrap();
+        let nas = service.add_local_source("NAS".to_string(), nas_dir.path().to_path_buf()).await.unwrap();
+        let backup = service.add_local_source("Backup".to_string(), backup_dir.path().to_path_buf()).await.unwrap();
+        requests.lock().clear();
+        
+        let destinations = vec![(nas.id.clone(), PathBuf::from("/")), (backup.id.clone(), PathBuf::from("/"))];
+        let results = service.copy_to_multiple(&media.id, Path::new("/master.mov"), &destinations)
+            .await.unwrap();
+        
+        assert!(results.iter().all(|r| r.error.is_none() && r.bytes == 6), "{:?}", results);
+        assert_eq!(std::fs::read(nas_dir.path().join("master.mov")).unwrap(), b"master");
+        assert_eq!(std::fs::read(backup_dir.path().join("master.mov")).unwrap(), b"master");
+        let requests = requests.lock();
+        assert_eq!(
+            requests.iter().filter(|r| r.starts_with("get /media/master.mov")).count(), 1,
+            "source read more than once: {:?}", requests
+        );
+    }
+    
     /// **Feature**: Copy entire directory between storage sources
     #[tokio::test]
     async fn feature_copy_directory_between_sources() {
@@ -929,6 +2631,57 @@ mod feature_tests {
         assert!(dest_dir.path().join("project/src/main.rs").exists());
     }
     
+    /// **Feature**: Wide directories copy in parallel with nothing lost
+    #[tokio::test]
+    async fn feature_parallel_directory_copy_is_complete() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::CrossStorageOptions;
+        
+        let source_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+        
+        // 120 small files spread over nested directories
+        let mut expected_bytes = 0u64;
+        for dir in ["shots", "shots/a", "shots/a/b"] {
+            std::fs::create_dir_all(source_dir.path().join(dir)).unwrap();
+            for i in 0..40 {
+                let content = vec![b'x'; i * 10 + 1];
+                expected_bytes += content.len() as u64;
+                std::fs::write(source_dir.path().join(dir).join(format!("frame_{:03}.dpx", i)), content).unwrap();
+            }
+        }
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Source".to_string(), source_dir.path().to_path_buf())
+            .await.unwrap();
+        let dest = service.add_local_source("Dest".to_string(), dest_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let options = CrossStorageOptions {
+            concurrency: Some(8),
+            ..CrossStorageOptions::copy()
+        };
+        let bytes = service.copy_to_source_with(
+            &source.id,
+            Path::new("/shots"),
+            &dest.id,
+            Path::new("/"),
+            &options,
+        ).await.unwrap();
+        
+        assert_eq!(bytes, expected_bytes);
+        for dir in ["shots", "shots/a", "shots/a/b"] {
+            let count = std::fs::read_dir(dest_dir.path().join(dir)).unwrap()
+                .filter(|e| e.as_ref().unwrap().file_type().unwrap().is_file())
+                .count();
+            assert_eq!(count, 40, "All files should arrive in {}", dir);
+        }
+        assert_eq!(
+            std::fs::read(dest_dir.path().join("shots/a/b/frame_039.dpx")).unwrap().len(),
+            391
+        );
+    }
+    
     /// **Feature**: Get available transfer targets
     #[tokio::test]
     async fn feature_get_transfer_targets() {
@@ -1075,6 +2828,31 @@ mod feature_tests {
         }
     }
     
+    /// **Feature**: Smart folders re-run a saved metadata query
+    #[tokio::test]
+    async fn feature_smart_folder_min_rating() {
+        use crate::vfs::adapters::JsonMetadataStore;
+        use crate::vfs::ports::{IMetadataStore, MetadataQuery};
+        
+        let temp_dir = TempDir::new().unwrap();
+        let store = JsonMetadataStore::new(temp_dir.path().join("meta.json"))
+            .await
+            .unwrap();
+        
+        let folder = store.create_smart_folder(
+            "All 5-star proxies".to_string(),
+            "local".to_string(),
+            MetadataQuery { min_rating: Some(5), ..Default::default() },
+        ).await.unwrap();
+        
+        store.set_rating("local", Path::new("/proxy_a.mov"), Some(5)).await.unwrap();
+        store.set_rating("local", Path::new("/proxy_b.mov"), Some(5)).await.unwrap();
+        store.set_rating("local", Path::new("/rough_cut.mov"), Some(3)).await.unwrap();
+        
+        let matches = store.eval_smart_folder(&folder.id).await.unwrap();
+        assert_eq!(matches, vec!["/proxy_a.mov".to_string(), "/proxy_b.mov".to_string()]);
+    }
+    
     // =========================================================================
     // FEATURE: Native OS Thumbnail Support (Phase 1.2)
     // Use Case: Leverage OS-cached thumbnails for local files
@@ -1310,7 +3088,7 @@ mod feature_tests {
             async move {
                 let count = a.fetch_add(1, Ordering::Relaxed) + 1;
                 if count < 3 {
-                    Err(anyhow::anyhow!("Simulated network failure"))
+                    Err(std::io::Error::from(std::io::ErrorKind::TimedOut).into())
                 } else {
                     Ok("success")
                 }
@@ -2110,6 +3888,179 @@ mod feature_tests {
         let content = clipboard.get_clipboard().await.unwrap().unwrap();
         assert!(content.is_cut(), "Cut operation should be marked as cut");
     }
+    
+    // =========================================================================
+    // FEATURE: Recents
+    // Use Case: User reopens something they worked on recently, like Finder's Recents
+    // =========================================================================
+    
+    /// **Feature**: Recently opened files are listed newest-first without duplicates
+    #[tokio::test]
+    async fn feature_recents_newest_first_deduped() {
+        use crate::vfs::application::VfsService;
+        
+        let dir = TempDir::new().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Test".to_string(), dir.path().to_path_buf())
+            .await.unwrap();
+        
+        // Open three files, then re-open the first
+        for name in ["/a.txt", "/b.txt", "/c.txt", "/a.txt"] {
+            service.read(&source.id, Path::new(name)).await.unwrap();
+        }
+        
+        // Failed opens and reads made for a copy or preview aren't recents
+        assert!(service.read(&source.id, Path::new("/missing.txt")).await.is_err());
+        service.read_file(&source.id, Path::new("/d.txt")).await.unwrap();
+        
+        let recents: Vec<PathBuf> = service.list_recents().await.unwrap()
+            .into_iter().map(|e| e.path).collect();
+        assert_eq!(recents, vec![
+            PathBuf::from("/a.txt"),
+            PathBuf::from("/c.txt"),
+            PathBuf::from("/b.txt"),
+        ]);
+        
+        // Deleted files drop out on the next listing
+        std::fs::remove_file(dir.path().join("b.txt")).unwrap();
+        let recents = service.list_recents().await.unwrap();
+        assert_eq!(recents.len(), 2);
+        
+        service.clear_recents().await.unwrap();
+        assert!(service.list_recents().await.unwrap().is_empty());
+    }
+    
+    /// **Feature**: Copy/move dialogs offer the folders last transferred into
+    #[tokio::test]
+    async fn feature_recent_destinations_newest_first() {
+        use crate::vfs::application::VfsService;
+        use crate::vfs::ports::{CopyOptions, MoveOptions};
+        
+        let source_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+        for name in ["a.mov", "b.mov", "c.mov"] {
+            std::fs::write(source_dir.path().join(name), name).unwrap();
+        }
+        std::fs::create_dir(dest_dir.path().join("Selects")).unwrap();
+        std::fs::create_dir(dest_dir.path().join("Rejects")).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Card".to_string(), source_dir.path().to_path_buf())
+            .await.unwrap();
+        let dest = service.add_local_source("Edit".to_string(), dest_dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let destinations = |entries: Vec<crate::vfs::adapters::RecentEntry>| -> Vec<PathBuf> {
+            entries.into_iter().map(|e| e.path).collect()
+        };
+        
+        service.move_to_source(&source.id, Path::new("/a.mov"), &dest.id, Path::new("/Selects")).await.unwrap();
+        service.move_to_source(&source.id, Path::new("/b.mov"), &dest.id, Path::new("/Rejects")).await.unwrap();
+        assert_eq!(
+            destinations(service.recent_destinations().await.unwrap()),
+            vec![PathBuf::from("/Rejects"), PathBuf::from("/Selects")],
+        );
+        
+        // Using a folder again moves it to the top instead of adding a duplicate
+        service.move_to_source(&source.id, Path::new("/c.mov"), &dest.id, Path::new("/Selects")).await.unwrap();
+        let recent = service.recent_destinations().await.unwrap();
+        assert!(recent.iter().all(|e| e.source_id == dest.id));
+        assert_eq!(destinations(recent), vec![PathBuf::from("/Selects"), PathBuf::from("/Rejects")]);
+        
+        // Moves and copies within one source count too, by the folder they land in
+        service.mv(&dest.id, Path::new("/Selects/a.mov"), Path::new("/Rejects/a.mov"), MoveOptions::default())
+            .await.unwrap();
+        assert_eq!(destinations(service.recent_destinations().await.unwrap())[0], PathBuf::from("/Rejects"));
+        service.copy(&dest.id, Path::new("/Rejects/a.mov"), Path::new("/Selects/a.mov"), CopyOptions::default())
+            .await.unwrap();
+        assert_eq!(destinations(service.recent_destinations().await.unwrap())[0], PathBuf::from("/Selects"));
+        
+        // Deleted folders and removed sources drop out
+        std::fs::remove_dir_all(dest_dir.path().join("Rejects")).unwrap();
+        assert_eq!(destinations(service.recent_destinations().await.unwrap()), vec![PathBuf::from("/Selects")]);
+        service.remove_source(&dest.id);
+        assert!(service.recent_destinations().await.unwrap().is_empty());
+    }
+    
+    /// **Feature**: Searches are remembered newest first so they can be run again
+    #[tokio::test]
+    async fn feature_search_history_newest_first_deduped() {
+        use crate::vfs::application::VfsService;
+        use std::sync::Arc;
+        
+        let dir = TempDir::new().unwrap();
+        for name in ["A001_C001.mov", "B002_C001.mov", "notes.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Card".to_string(), dir.path().to_path_buf())
+            .await.unwrap();
+        let service = Arc::new(service);
+        let queries = || -> Vec<String> {
+            service.recent_searches().into_iter().map(|s| s.query).collect()
+        };
+        
+        service.search(&source.id, Path::new("/"), "a001", 10).await.unwrap();
+        service.search(&source.id, Path::new("/"), "b002", 10).await.unwrap();
+        assert_eq!(queries(), vec!["b002", "a001"]);
+        
+        // Running a search again moves it to the top instead of adding a duplicate
+        service.search(&source.id, Path::new("/"), "a001", 50).await.unwrap();
+        let recent = service.recent_searches();
+        assert!(recent.iter().all(|s| s.source_id == source.id && s.root == Path::new("/")));
+        assert_eq!(recent[0].limit, 50);
+        assert_eq!(queries(), vec!["a001", "b002"]);
+        
+        service.clear_search_history().await.unwrap();
+        assert!(service.recent_searches().is_empty());
+    }
+    
+    // =========================================================================
+    // FEATURE: Bookmarks
+    // Use Case: User pins folders they keep going back to in the sidebar
+    // =========================================================================
+    
+    /// **Feature**: Pinned folders can be reordered and stale ones are flagged
+    #[tokio::test]
+    async fn feature_bookmarks_reorder_and_stale() {
+        use crate::vfs::application::VfsService;
+        
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("Dailies")).unwrap();
+        std::fs::create_dir(dir.path().join("Selects")).unwrap();
+        
+        let service = VfsService::new().await.unwrap();
+        let source = service.add_local_source("Test".to_string(), dir.path().to_path_buf())
+            .await.unwrap();
+        
+        let dailies = service.add_bookmark(&source.id, Path::new("/Dailies"), "Dailies").await.unwrap();
+        let selects = service.add_bookmark(&source.id, Path::new("/Selects"), "Selects").await.unwrap();
+        
+        let names: Vec<String> = service.list_bookmarks().await.unwrap()
+            .into_iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["Dailies", "Selects"]);
+        
+        let reordered = service.reorder_bookmarks(&[selects.id.clone(), dailies.id.clone()])
+            .await.unwrap();
+        let names: Vec<String> = reordered.into_iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["Selects", "Dailies"]);
+        
+        // Deleting the target keeps the bookmark but flags it
+        std::fs::remove_dir(dir.path().join("Dailies")).unwrap();
+        let bookmarks = service.list_bookmarks().await.unwrap();
+        assert_eq!(bookmarks.len(), 2);
+        assert!(!bookmarks[0].stale);
+        assert!(bookmarks[1].stale);
+        assert_eq!(bookmarks[1].id, dailies.id);
+        
+        service.remove_bookmark(&dailies.id).await.unwrap();
+        assert_eq!(service.list_bookmarks().await.unwrap().len(), 1);
+    }
 }
 
 // =========================================================================
@@ -2120,18 +4071,21 @@ mod feature_tests {
 //   cargo test --lib feature_ -- --nocapture
 //
 // Test count by category:
-//   - File System Operations: 7 tests
-//   - Caching & Hydration: 3 tests
-//   - Storage Backends: 5 tests  
-//   - Media Processing: 3 tests
+//   - File System Operations: 33 tests
+//   - Caching & Hydration: 6 tests
+//   - Storage Backends: 12 tests  
+//   - Media Processing: 7 tests
 //   - VFS Orchestration: 1 test
-//   - Configuration: 2 tests
+//   - Configuration: 3 tests
 //   - Native Thumbnails: 1 test
 //   - UI Helpers: 2 tests
 //   - Cross-Platform: 10 tests
 //   - Navigation: 6 tests
 //   - Context Menu & Hydration: 4 tests
-//   - Cross-Platform Clipboard: 5 tests (NEW)
+//   - Cross-Platform Clipboard: 5 tests
+//   - Recents: 3 tests
+//   - Bookmarks: 1 test
+//   - Smart Folders: 1 test
 //
-// Total: 49 feature tests
+// Total: 97 feature tests
 // =========================================================================
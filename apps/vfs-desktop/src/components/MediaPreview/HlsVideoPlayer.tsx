@@ -8,6 +8,7 @@ import Hls, { Level, ErrorData, ManifestParsedData } from 'hls.js';
 import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
 import { TranscriptionPanel } from './TranscriptionPanel';
+import { getErrorMessage } from '../../utils/errors';
 import './MediaPreview.css';
 
 export interface TranscriptionSegment {
@@ -181,7 +182,7 @@ export function HlsVideoPlayer({
         setTranscriptionActive(true);
       } catch (err) {
         console.error('Failed to start transcription:', err);
-        setError(`Transcription failed: ${err}`);
+        setError(`Transcription failed: ${getErrorMessage(err)}`);
       }
     };
 
@@ -17,7 +17,7 @@ export interface MediaPreviewModalProps {
   streamUrl?: string;
   thumbnails?: string[];
   onClose: () => void;
-  onTranscode?: (format: 'hls' | 'dash' | 'mp4') => void;
+  onTranscode?: (format: 'hls' | 'mp4') => void;
   onDownload?: () => void;
 }
 
@@ -11,6 +11,7 @@
 import React, { useEffect, useState, useCallback, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { open, save } from '@tauri-apps/plugin-dialog';
+import { getErrorMessage } from '../../utils/errors';
 import './ObjectStoragePanel.css';
 
 interface UploadState {
@@ -143,7 +144,7 @@ export const ObjectStoragePanel: React.FC<ObjectStoragePanelProps> = ({
           );
         } catch (err) {
           console.error(`Failed to upload folder ${folderPath}:`, err);
-          alert(`Failed to upload folder ${folderPath}: ${err}`);
+          alert(`Failed to upload folder ${folderPath}: ${getErrorMessage(err)}`);
         }
       }
 
@@ -164,7 +165,7 @@ export const ObjectStoragePanel: React.FC<ObjectStoragePanelProps> = ({
           console.log(`File upload started: ${fileName}`);
         } catch (err) {
           console.error(`Failed to upload ${filePath}:`, err);
-          alert(`Failed to upload ${filePath}: ${err}`);
+          alert(`Failed to upload ${filePath}: ${getErrorMessage(err)}`);
         }
       }
 
@@ -176,7 +177,7 @@ export const ObjectStoragePanel: React.FC<ObjectStoragePanelProps> = ({
     } catch (err) {
       console.error('Upload failed:', err);
       setIsUploading(false);
-      alert(`Upload failed: ${err}`);
+      alert(`Upload failed: ${getErrorMessage(err)}`);
     }
   };
 
@@ -230,7 +231,7 @@ export const ObjectStoragePanel: React.FC<ObjectStoragePanelProps> = ({
       console.log('File downloaded successfully:', savePath);
     } catch (err) {
       console.error('Download failed:', err);
-      alert(`Download failed: ${err}`);
+      alert(`Download failed: ${getErrorMessage(err)}`);
     }
   };
 
@@ -11,6 +11,7 @@ import type {
   FileMetadata,
   WarmProgress,
   GlobalFavorite,
+  SmartFolder,
 } from '../types/storage';
 import { Breadcrumbs, type BreadcrumbItem } from '../components/Breadcrumbs';
 import {
@@ -45,6 +46,7 @@ import { UploadProgressPanel } from '../components/UploadProgress';
 import { UploadStatusWidget } from '../components/UploadStatusWidget';
 import { TransferPanel } from '../components/TransferPanel';
 import { truncateMiddle } from '../utils/file-utils';
+import { getErrorMessage } from '../utils/errors';
 import '../styles/finder.css';
 
 type ViewMode = 'icon' | 'list';
@@ -106,6 +108,9 @@ export function FinderPage({
     [],
   );
   const [filterByTag, setFilterByTag] = useState<string | null>(null);
+  const [smartFolders, setSmartFolders] = useState<SmartFolder[]>([]);
+  const [activeSmartFolder, setActiveSmartFolder] =
+    useState<SmartFolder | null>(null);
   const [sidebarWidth, setSidebarWidth] = useState(() => {
     // Load saved sidebar width from localStorage, default to 200px
     try {
@@ -1002,7 +1007,7 @@ export function FinderPage({
       console.error('[VFS] Failed to load files:', err);
       setFiles([]);
       // Show user-friendly error message
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       if (!errorMessage.includes('Missing providerId')) {
         // Don't show error for providerId issues (already handled above)
         DialogService.error(
@@ -1169,9 +1174,98 @@ export function FinderPage({
     }
   };
 
-  // Load global favorites on mount
+  const loadSmartFolders = async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setSmartFolders(await invoke<SmartFolder[]>('vfs_list_smart_folders'));
+    } catch (err) {
+      console.error('Failed to load smart folders:', err);
+      setSmartFolders([]);
+    }
+  };
+
+  // Show the files a smart folder's query matches in place of a listing
+  const openSmartFolder = async (folder: SmartFolder) => {
+    const source = sources.find((s) => s.id === folder.source_id);
+    if (!source) {
+      toast.showToast({
+        type: 'error',
+        message: `The storage for "${folder.name}" is not connected`,
+      });
+      return;
+    }
+
+    setActiveSmartFolder(folder);
+    setSelectedFiles(new Set());
+    if (selectedSource?.id !== source.id) {
+      setNavigationHistory(['']);
+      setHistoryIndex(0);
+      setCurrentPath('');
+    }
+    setSelectedSource(source);
+    setLoading(true);
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const paths = await invoke<string[]>('vfs_eval_smart_folder', {
+        id: folder.id,
+      });
+      // Files deleted since they were tagged are left out
+      const matches = await Promise.all(
+        paths.map((path) =>
+          StorageService.getFileInfo(source.id, path).catch(() => null),
+        ),
+      );
+      setFiles(matches.filter((f): f is FileMetadata => f !== null));
+    } catch (err) {
+      console.error('Failed to open smart folder:', err);
+      toast.showToast({ type: 'error', message: getErrorMessage(err) });
+      setFiles([]);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // Back to the folder listing the smart folder replaced
+  const closeSmartFolder = async () => {
+    setActiveSmartFolder(null);
+    if (selectedSource) {
+      await loadFilesList(selectedSource.id, currentPath);
+    }
+  };
+
+  // Save the sidebar tag filter as a smart folder
+  const saveTagFilterAsSmartFolder = async () => {
+    if (!filterByTag || !selectedSource) return;
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke<SmartFolder>('vfs_create_smart_folder', {
+        name: filterByTag,
+        sourceId: selectedSource.id,
+        query: { tags: [filterByTag] },
+      });
+      await loadSmartFolders();
+    } catch (err) {
+      toast.showToast({ type: 'error', message: getErrorMessage(err) });
+    }
+  };
+
+  const deleteSmartFolder = async (folder: SmartFolder) => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke('vfs_delete_smart_folder', { id: folder.id });
+      if (activeSmartFolder?.id === folder.id) {
+        await closeSmartFolder();
+      }
+      await loadSmartFolders();
+    } catch (err) {
+      toast.showToast({ type: 'error', message: getErrorMessage(err) });
+    }
+  };
+
+  // Load global favorites and smart folders on mount
   useEffect(() => {
     loadGlobalFavorites();
+    loadSmartFolders();
   }, []);
 
   // Load tags when source changes
@@ -1183,9 +1277,10 @@ export function FinderPage({
 
   // Select a source (storage location) and navigate to its root
   const selectSource = async (source: StorageSource) => {
-    // Skip if same source already selected
-    if (selectedSource?.id === source.id) return;
+    // Skip if same source already selected (unless leaving a smart folder)
+    if (selectedSource?.id === source.id && !activeSmartFolder) return;
 
+    setActiveSmartFolder(null);
     setSelectedFiles(new Set());
     setFiles([]);
 
@@ -1207,6 +1302,15 @@ export function FinderPage({
     // Normalize path
     const normalizedPath = path === '/' ? '' : path;
 
+    // Leaving a smart folder for the folder it replaced only needs a reload
+    if (activeSmartFolder) {
+      setActiveSmartFolder(null);
+      if (normalizedPath === currentPath && selectedSource) {
+        loadFilesList(selectedSource.id, currentPath);
+        return;
+      }
+    }
+
     // Don't navigate if already at this path
     if (normalizedPath === currentPath) return;
 
@@ -1342,7 +1446,10 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Failed to open file:', err);
-      DialogService.error(`Failed to open file: ${err}`, 'Open Error');
+      DialogService.error(
+        `Failed to open file: ${getErrorMessage(err)}`,
+        'Open Error',
+      );
     }
   };
 
@@ -1389,7 +1496,7 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Failed to open file with app:', err);
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       DialogService.error(
         `Failed to open file with application: ${errorMessage}`,
         'Open Error',
@@ -1570,7 +1677,10 @@ export function FinderPage({
       await refreshClipboardState();
     } catch (err) {
       console.error('[VFS Paste] Failed:', err);
-      setFileOperation({ type: `Paste failed: ${err}`, inProgress: false });
+      setFileOperation({
+        type: `Paste failed: ${getErrorMessage(err)}`,
+        inProgress: false,
+      });
       setTimeout(() => setFileOperation(null), 3000);
     }
   };
@@ -1603,7 +1713,7 @@ export function FinderPage({
             path: normalizedPath,
           });
         } catch (err) {
-          const errorMessage = err instanceof Error ? err.message : String(err);
+          const errorMessage = getErrorMessage(err);
           console.error(`[VFS Delete] Failed to delete ${path}:`, errorMessage);
 
           // Store error message with path for better error reporting
@@ -1636,7 +1746,10 @@ export function FinderPage({
       }
     } catch (err) {
       console.error('[VFS Delete] Delete operation failed:', err);
-      DialogService.error(`Delete failed: ${err}`, 'Delete Error');
+      DialogService.error(
+        `Delete failed: ${getErrorMessage(err)}`,
+        'Delete Error',
+      );
     } finally {
       setFileOperation(null);
     }
@@ -1718,7 +1831,10 @@ export function FinderPage({
       setSelectedFiles(new Set([newPath]));
     } catch (err) {
       console.error('Rename failed:', err);
-      DialogService.error(`Rename failed: ${err}`, 'Rename Error');
+      DialogService.error(
+        `Rename failed: ${getErrorMessage(err)}`,
+        'Rename Error',
+      );
     } finally {
       setFileOperation(null);
     }
@@ -1806,7 +1922,10 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Create folder failed:', err);
-      DialogService.error(`Create folder failed: ${err}`, 'Folder Error');
+      DialogService.error(
+        `Create folder failed: ${getErrorMessage(err)}`,
+        'Folder Error',
+      );
     } finally {
       setFileOperation(null);
     }
@@ -1859,7 +1978,10 @@ export function FinderPage({
       await loadFilesList(selectedSource.id, currentPath);
     } catch (err) {
       console.error('Duplicate failed:', err);
-      DialogService.error(`Duplicate failed: ${err}`, 'Duplicate Error');
+      DialogService.error(
+        `Duplicate failed: ${getErrorMessage(err)}`,
+        'Duplicate Error',
+      );
     }
   };
 
@@ -2032,7 +2154,10 @@ export function FinderPage({
 
         await loadFilesList(selectedSource.id, currentPath);
       } catch (err) {
-        DialogService.error(`Drop failed: ${err}`, 'Drop Error');
+        DialogService.error(
+          `Drop failed: ${getErrorMessage(err)}`,
+          'Drop Error',
+        );
       }
     } else if (e.dataTransfer.files.length > 0) {
       try {
@@ -2059,7 +2184,10 @@ export function FinderPage({
 
         await loadFilesList(selectedSource.id, currentPath);
       } catch (err) {
-        DialogService.error(`Import failed: ${err}`, 'Import Error');
+        DialogService.error(
+          `Import failed: ${getErrorMessage(err)}`,
+          'Import Error',
+        );
       }
     }
 
@@ -2122,7 +2250,10 @@ export function FinderPage({
       // await loadFilesList(targetSource.id, '/');
     } catch (err) {
       console.error('Cross-storage drop failed:', err);
-      DialogService.error(`Transfer failed: ${err}`, 'Transfer Error');
+      DialogService.error(
+        `Transfer failed: ${getErrorMessage(err)}`,
+        'Transfer Error',
+      );
     }
 
     setDraggedFiles([]);
@@ -2238,7 +2369,7 @@ export function FinderPage({
     } catch (err) {
       console.error('Failed to add/update storage:', err);
       DialogService.error(
-        `Failed to ${editingSource ? 'update' : 'add'} storage: ${err}`,
+        `Failed to ${editingSource ? 'update' : 'add'} storage: ${getErrorMessage(err)}`,
         'Storage Error',
       );
     }
@@ -2287,7 +2418,10 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Failed to remove storage:', err);
-      DialogService.error(`Failed to remove storage: ${err}`, 'Storage Error');
+      DialogService.error(
+        `Failed to remove storage: ${getErrorMessage(err)}`,
+        'Storage Error',
+      );
     }
   };
 
@@ -2412,7 +2546,7 @@ export function FinderPage({
             `[FinderPage] Failed to upload folder ${folderPath}:`,
             err,
           );
-          const errorMessage = err instanceof Error ? err.message : String(err);
+          const errorMessage = getErrorMessage(err);
           errors.push(`Folder ${folderPath}: ${errorMessage}`);
         }
       }
@@ -2444,7 +2578,7 @@ export function FinderPage({
           });
         } catch (err) {
           console.error(`[FinderPage] Failed to upload ${filePath}:`, err);
-          const errorMessage = err instanceof Error ? err.message : String(err);
+          const errorMessage = getErrorMessage(err);
           const fileName =
             filePath.split('/').pop() || filePath.split('\\').pop() || 'file';
           errors.push(`File ${fileName}: ${errorMessage}`);
@@ -2474,7 +2608,7 @@ export function FinderPage({
     } catch (err) {
       console.error('[FinderPage] Failed to open upload dialog:', err);
       DialogService.error(
-        `Failed to open upload dialog: ${err}`,
+        `Failed to open upload dialog: ${getErrorMessage(err)}`,
         'Upload Error',
       );
     }
@@ -2554,7 +2688,10 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Download failed:', err);
-      DialogService.error(`Download failed: ${err}`, 'Download Error');
+      DialogService.error(
+        `Download failed: ${getErrorMessage(err)}`,
+        'Download Error',
+      );
     }
   };
 
@@ -2580,7 +2717,10 @@ export function FinderPage({
       });
     } catch (err) {
       console.error('Tier change failed:', err);
-      DialogService.error(`Failed to change tier: ${err}`, 'Tier Change Error');
+      DialogService.error(
+        `Failed to change tier: ${getErrorMessage(err)}`,
+        'Tier Change Error',
+      );
     }
   };
 
@@ -3863,6 +4003,61 @@ export function FinderPage({
                 )}
               </div>
 
+              {/* Smart Folders Section - saved metadata queries */}
+              <div className="sidebar-section storage-section">
+                <div className="section-header">
+                  <IconFolder size={14} glow={false} />
+                  <span>Smart Folders</span>
+                  {smartFolders.length > 0 && (
+                    <span className="section-count">
+                      ({smartFolders.length})
+                    </span>
+                  )}
+                </div>
+                <div className="storage-group-items">
+                  {smartFolders.map((folder) => (
+                    <button
+                      key={folder.id}
+                      className={`sidebar-item storage-item ${activeSmartFolder?.id === folder.id ? 'active' : ''}`}
+                      onClick={() =>
+                        activeSmartFolder?.id === folder.id
+                          ? closeSmartFolder()
+                          : openSmartFolder(folder)
+                      }
+                      onContextMenu={(e) => {
+                        e.preventDefault();
+                        e.stopPropagation();
+                        deleteSmartFolder(folder);
+                      }}
+                      title={`${sources.find((s) => s.id === folder.source_id)?.name ?? folder.source_id}\nRight-click to remove`}
+                    >
+                      <span className="item-icon">
+                        <IconFolder size={16} />
+                      </span>
+                      <span className="item-name">{folder.name}</span>
+                    </button>
+                  ))}
+                  {filterByTag && selectedSource && (
+                    <button
+                      className="sidebar-item storage-item"
+                      onClick={saveTagFilterAsSmartFolder}
+                      title={`Save the "${filterByTag}" tag filter on ${selectedSource.name} as a smart folder`}
+                    >
+                      <span className="item-icon">+</span>
+                      <span className="item-name">Save “{filterByTag}”</span>
+                    </button>
+                  )}
+                </div>
+                {smartFolders.length === 0 && !filterByTag && (
+                  <div className="sidebar-empty">
+                    <span className="empty-text">No smart folders yet</span>
+                    <span className="empty-hint">
+                      Pick a tag to save it as one
+                    </span>
+                  </div>
+                )}
+              </div>
+
               {/* Metrics Preview */}
               {onOpenMetrics && (
                 <MetricsPreview onOpenMetrics={onOpenMetrics} />
@@ -4689,7 +4884,7 @@ export function FinderPage({
                                             err,
                                           );
                                           DialogService.error(
-                                            `Failed to open application picker: ${err}`,
+                                            `Failed to open application picker: ${getErrorMessage(err)}`,
                                             'Open With Error',
                                           );
                                         }
@@ -4986,7 +5181,7 @@ export function FinderPage({
                               } catch (err) {
                                 console.error('Hydration failed:', err);
                                 DialogService.error(
-                                  `Failed to fetch file: ${err}`,
+                                  `Failed to fetch file: ${getErrorMessage(err)}`,
                                   'Fetch Error',
                                 );
                               }
@@ -5290,7 +5485,7 @@ export function FinderPage({
                           } catch (err) {
                             toast.showToast({
                               type: 'error',
-                              message: `Failed to eject: ${err}`,
+                              message: `Failed to eject: ${getErrorMessage(err)}`,
                             });
                           }
                         }}
@@ -13,6 +13,7 @@ import type {
   TranscodeProgress,
 } from '../types/storage';
 import { Breadcrumbs, type BreadcrumbItem } from '../components/Breadcrumbs';
+import { getErrorMessage } from '../utils/errors';
 import '../styles/storage-browser.css';
 
 export function StorageBrowserPage() {
@@ -114,7 +115,7 @@ export function StorageBrowserPage() {
         priority: 'high',
       });
     } catch (err) {
-      alert(`Failed to warm file: ${err}`);
+      alert(`Failed to warm file: ${getErrorMessage(err)}`);
     }
   };
 
@@ -129,7 +130,7 @@ export function StorageBrowserPage() {
         quality: 'high',
       });
     } catch (err) {
-      alert(`Failed to transcode video: ${err}`);
+      alert(`Failed to transcode video: ${getErrorMessage(err)}`);
     }
   };
 
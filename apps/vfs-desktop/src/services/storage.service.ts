@@ -207,6 +207,7 @@ export class StorageService {
       sourceId: request.sourceId,
       filePath: request.filePath,
       format: request.format,
+      quality: request.quality,
     });
   }
 
@@ -536,7 +537,7 @@ export class VfsService {
    * Start video transcoding
    * @param sourceId ID of the storage source
    * @param filePath Path to the video file
-   * @param format Target format (hls, dash, mp4)
+   * @param format Target format (hls, mp4)
    */
   static async transcodeVideo(
     sourceId: string,
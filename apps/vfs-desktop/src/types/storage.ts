@@ -671,8 +671,8 @@ export interface WarmProgress {
 export interface TranscodeRequest {
   sourceId: string;
   filePath: string;
-  format: 'hls' | 'dash' | 'mp4';
-  quality?: 'low' | 'medium' | 'high';
+  format: 'hls' | 'mp4';
+  quality?: 'low' | 'medium' | 'high' | 'ultra' | 'adaptive';
 }
 
 export interface TranscodeProgress {
@@ -1311,6 +1311,47 @@ export interface ApiStreamResponse {
   expiresAt?: string;
 }
 
+// =============================================================================
+// Smart Folders
+// =============================================================================
+
+/**
+ * Metadata query a smart folder re-runs (mirrors the backend's MetadataQuery)
+ */
+export interface MetadataQuery {
+  /** Files must carry all of these tags */
+  tags?: string[];
+
+  /** Minimum rating (inclusive) */
+  min_rating?: number | null;
+
+  /** Required color label */
+  color_label?: string | null;
+
+  /** Only favorites */
+  favorites_only?: boolean;
+
+  /** Case-insensitive substring of the comment */
+  comment_contains?: string | null;
+}
+
+/**
+ * Saved search shown in the sidebar like a virtual source
+ */
+export interface SmartFolder {
+  /** Unique ID */
+  id: string;
+
+  /** Display name */
+  name: string;
+
+  /** Source the query runs against */
+  source_id: string;
+
+  /** Query run when the folder is opened */
+  query: MetadataQuery;
+}
+
 // =============================================================================
 // Global Favorites
 // =============================================================================
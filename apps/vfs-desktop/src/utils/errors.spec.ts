@@ -0,0 +1,15 @@
+import { getErrorMessage, isVfsError } from './errors';
+
+describe('getErrorMessage', () => {
+  it('should read the message of VFS command errors', () => {
+    const err = { code: 'NOT_FOUND', message: 'Failed to read file: missing' };
+    expect(isVfsError(err)).toBe(true);
+    expect(getErrorMessage(err)).toBe('Failed to read file: missing');
+  });
+
+  it('should handle Error objects and plain strings', () => {
+    expect(getErrorMessage(new Error('boom'))).toBe('boom');
+    expect(getErrorMessage('plain')).toBe('plain');
+    expect(isVfsError('plain')).toBe(false);
+  });
+});
@@ -0,0 +1,49 @@
+/**
+ * Errors returned by VFS commands
+ */
+
+/**
+ * Typed error from a `vfs_*` command: a stable `code` to branch on plus a
+ * human-readable `message`.
+ */
+export interface VfsError {
+  code:
+    | 'NOT_FOUND'
+    | 'PERMISSION_DENIED'
+    | 'TIMEOUT'
+    | 'ALREADY_EXISTS'
+    | 'NOT_INITIALIZED'
+    | 'READ_ONLY'
+    | 'INSUFFICIENT_SPACE'
+    | 'QUOTA_EXCEEDED'
+    | 'VOLUME_BUSY'
+    | 'LOCKED'
+    | 'ARCHIVE_NOT_RETRIEVED'
+    | 'CANCELLED'
+    | 'INVALID_INPUT'
+    | 'UNSUPPORTED'
+    | 'FFMPEG_UNAVAILABLE'
+    | 'INTERNAL';
+  message: string;
+  /** Rough seconds a retrieval takes, with `ARCHIVE_NOT_RETRIEVED` */
+  estimatedRetrievalSecs?: number;
+}
+
+export function isVfsError(err: unknown): err is VfsError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    typeof (err as VfsError).code === 'string' &&
+    typeof (err as VfsError).message === 'string'
+  );
+}
+
+/**
+ * Message to show for anything thrown by `invoke` or regular code
+ */
+export function getErrorMessage(err: unknown): string {
+  if (err instanceof Error || isVfsError(err)) {
+    return err.message;
+  }
+  return String(err);
+}
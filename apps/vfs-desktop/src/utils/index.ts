@@ -1 +1,2 @@
 export * from './file-utils';
+export * from './errors';